@@ -3,11 +3,90 @@
 use bytemuck::{Pod, Zeroable};
 use wgpu::{util::DeviceExt, Buffer, BufferUsages, Device, Queue};
 
+/// Frames the data must stay small before a [`DynamicBuffer`] shrinks by default.
+pub const DEFAULT_SHRINK_AFTER_FRAMES: u32 = 240;
+
+/// Capacity and allocation counts of a [`DynamicBuffer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BufferStats {
+    /// Elements written by the last [`DynamicBuffer::write`].
+    pub len: usize,
+    /// Elements the GPU buffer can hold.
+    pub capacity: usize,
+    /// GPU buffers allocated so far, including the first.
+    pub allocations: u64,
+}
+
+/// Decides when a [`DynamicBuffer`] reallocates, apart from the GPU calls.
+#[derive(Clone, Debug)]
+struct CapacityPlanner {
+    capacity: usize,
+    /// Never shrink below the capacity the buffer was created with.
+    min_capacity: usize,
+    shrink_after: Option<u32>,
+    /// Consecutive writes using at most a quarter of the capacity.
+    low_frames: u32,
+    /// Largest write during those frames.
+    low_peak: usize,
+}
+
+impl CapacityPlanner {
+    fn new(capacity: usize, shrink_after: Option<u32>) -> Self {
+        Self {
+            capacity,
+            min_capacity: capacity,
+            shrink_after,
+            low_frames: 0,
+            low_peak: 0,
+        }
+    }
+
+    /// The capacity to reallocate to before writing `len` elements, if any.
+    fn plan(&mut self, len: usize) -> Option<usize> {
+        if len > self.capacity {
+            // Half again as much headroom, and at least double, so a burst
+            // that keeps growing reallocates only a few times
+            self.capacity = (len + len / 2).max(self.capacity * 2);
+            self.low_frames = 0;
+            return Some(self.capacity);
+        }
+
+        let shrink_after = self.shrink_after?;
+        if len > self.capacity / 4 {
+            self.low_frames = 0;
+            self.low_peak = 0;
+            return None;
+        }
+        self.low_frames += 1;
+        self.low_peak = self.low_peak.max(len);
+        if self.low_frames < shrink_after {
+            return None;
+        }
+        self.low_frames = 0;
+        let target = (self.low_peak * 2).max(self.min_capacity);
+        self.low_peak = 0;
+        if target >= self.capacity {
+            return None;
+        }
+        self.capacity = target;
+        Some(target)
+    }
+}
+
 /// A dynamically growing GPU buffer for vertex/instance data.
+///
+/// Grows geometrically when a write doesn't fit, and by default shrinks
+/// back once writes have used at most a quarter of the capacity for
+/// [`DEFAULT_SHRINK_AFTER_FRAMES`] frames, so a burst (opening a long list)
+/// doesn't hold its memory forever. Data goes through `queue.write_buffer`,
+/// which already stages it in wgpu's own upload memory; a buffer kept
+/// mapped across frames isn't possible on WebGPU, where a mapped buffer
+/// can't be used by the GPU.
 pub struct DynamicBuffer<T: Pod + Zeroable> {
     buffer: Buffer,
-    capacity: usize,
+    planner: CapacityPlanner,
     len: usize,
+    allocations: u64,
     usage: BufferUsages,
     label: &'static str,
     _marker: std::marker::PhantomData<T>,
@@ -17,17 +96,11 @@ impl<T: Pod + Zeroable> DynamicBuffer<T> {
     /// Create a new dynamic buffer with the given initial capacity.
     pub fn new(device: &Device, label: &'static str, usage: BufferUsages, capacity: usize) -> Self {
         let capacity = capacity.max(1);
-        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some(label),
-            size: (capacity * std::mem::size_of::<T>()) as u64,
-            usage: usage | BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
         Self {
-            buffer,
-            capacity,
+            buffer: Self::allocate(device, label, usage, capacity),
+            planner: CapacityPlanner::new(capacity, Some(DEFAULT_SHRINK_AFTER_FRAMES)),
             len: 0,
+            allocations: 1,
             usage,
             label,
             _marker: std::marker::PhantomData,
@@ -44,26 +117,41 @@ impl<T: Pod + Zeroable> DynamicBuffer<T> {
         Self::new(device, label, BufferUsages::INDEX, capacity)
     }
 
-    /// Write data to the buffer, growing if necessary.
+    /// Shrink after `frames` small writes in a row, or never with `None`.
+    pub fn shrink_after(mut self, frames: Option<u32>) -> Self {
+        self.planner.shrink_after = frames.map(|frames| frames.max(1));
+        self
+    }
+
+    /// Write data to the buffer, growing or shrinking if necessary.
+    ///
+    /// Call once per frame, even with no data, so quiet frames count
+    /// toward shrinking.
     pub fn write(&mut self, device: &Device, queue: &Queue, data: &[T]) {
         self.len = data.len();
 
-        if data.is_empty() {
-            return;
+        if let Some(capacity) = self.planner.plan(data.len()) {
+            self.buffer = Self::allocate(device, self.label, self.usage, capacity);
+            self.allocations += 1;
         }
 
-        // Grow buffer if needed
-        if data.len() > self.capacity {
-            self.capacity = (data.len() * 2).next_power_of_two();
-            self.buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some(self.label),
-                size: (self.capacity * std::mem::size_of::<T>()) as u64,
-                usage: self.usage | BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            });
+        if !data.is_empty() {
+            queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(data));
         }
+    }
 
-        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(data));
+    fn allocate(
+        device: &Device,
+        label: &'static str,
+        usage: BufferUsages,
+        capacity: usize,
+    ) -> Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size: (capacity * std::mem::size_of::<T>()) as u64,
+            usage: usage | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
     }
 
     /// Get the underlying wgpu buffer.
@@ -80,6 +168,20 @@ impl<T: Pod + Zeroable> DynamicBuffer<T> {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// Number of elements the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.planner.capacity
+    }
+
+    /// Current usage, capacity and allocation count.
+    pub fn stats(&self) -> BufferStats {
+        BufferStats {
+            len: self.len,
+            capacity: self.planner.capacity,
+            allocations: self.allocations,
+        }
+    }
 }
 
 /// A static GPU buffer initialized once.
@@ -153,3 +255,52 @@ impl QuadBuffers {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Count reallocations over a sequence of per-frame lengths.
+    fn allocations(planner: &mut CapacityPlanner, lens: impl IntoIterator<Item = usize>) -> usize {
+        lens.into_iter()
+            .filter(|&len| planner.plan(len).is_some())
+            .count()
+    }
+
+    #[test]
+    fn test_alternating_bursts_allocate_boundedly() {
+        let mut planner = CapacityPlanner::new(1024, Some(DEFAULT_SHRINK_AFTER_FRAMES));
+        let frames = (0..10_000).map(|frame| if frame % 2 == 0 { 100 } else { 50_000 });
+        assert_eq!(allocations(&mut planner, frames), 1);
+        assert!(planner.capacity >= 50_000);
+    }
+
+    #[test]
+    fn test_growing_burst_reallocates_geometrically() {
+        let mut planner = CapacityPlanner::new(1024, None);
+        let count = allocations(&mut planner, (1..=100_000).step_by(100));
+        assert!(count <= 7, "{count} reallocations");
+    }
+
+    #[test]
+    fn test_shrinks_after_quiet_frames() {
+        let mut planner = CapacityPlanner::new(1024, Some(10));
+        assert!(planner.plan(50_000).is_some());
+        // A few quiet frames aren't enough
+        assert_eq!(allocations(&mut planner, [100; 9]), 0);
+        // A busy frame restarts the count
+        assert_eq!(planner.plan(40_000), None);
+        assert_eq!(allocations(&mut planner, [100; 9]), 0);
+        assert_eq!(planner.plan(100), Some(1024));
+        // Never below the initial capacity
+        assert_eq!(allocations(&mut planner, [0; 100]), 0);
+    }
+
+    #[test]
+    fn test_shrinking_can_be_disabled() {
+        let mut planner = CapacityPlanner::new(1024, None);
+        planner.plan(50_000);
+        let capacity = planner.capacity;
+        assert_eq!(allocations(&mut planner, [0; 1000]), 0);
+        assert_eq!(planner.capacity, capacity);
+    }
+}
@@ -0,0 +1,119 @@
+//! Error types for GPU initialization, pipelines, and surfaces.
+
+use std::fmt;
+
+/// Errors that can occur while initializing wgpu.
+#[derive(Debug)]
+pub enum WgpuInitError {
+    /// The window surface could not be created.
+    CreateSurface(wgpu::CreateSurfaceError),
+    /// No adapter satisfied the requested options.
+    NoAdapter(wgpu::RequestAdapterError),
+    /// The adapter refused to create a device.
+    RequestDevice(wgpu::RequestDeviceError),
+    /// The surface reports no supported texture formats for the adapter.
+    NoSurfaceFormat,
+    /// The adapter lacks features the options require.
+    MissingFeatures(wgpu::Features),
+}
+
+impl fmt::Display for WgpuInitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateSurface(e) => write!(f, "failed to create surface: {e}"),
+            Self::NoAdapter(e) => write!(f, "no compatible GPU adapter: {e}"),
+            Self::RequestDevice(e) => write!(f, "failed to create GPU device: {e}"),
+            Self::NoSurfaceFormat => write!(f, "surface has no supported texture formats"),
+            Self::MissingFeatures(features) => {
+                write!(f, "GPU adapter lacks required features: {features:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WgpuInitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CreateSurface(e) => Some(e),
+            Self::NoAdapter(e) => Some(e),
+            Self::RequestDevice(e) => Some(e),
+            Self::NoSurfaceFormat | Self::MissingFeatures(_) => None,
+        }
+    }
+}
+
+/// Errors that can occur while acquiring a frame from the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceError {
+    /// Acquiring the frame timed out; the frame should be skipped.
+    Timeout,
+    /// The surface is outdated and must be reconfigured.
+    Outdated,
+    /// The surface was lost and must be reconfigured or recreated.
+    Lost,
+    /// The GPU ran out of memory.
+    OutOfMemory,
+    /// Any other acquisition failure.
+    Other,
+}
+
+impl From<wgpu::SurfaceError> for SurfaceError {
+    fn from(err: wgpu::SurfaceError) -> Self {
+        match err {
+            wgpu::SurfaceError::Timeout => Self::Timeout,
+            wgpu::SurfaceError::Outdated => Self::Outdated,
+            wgpu::SurfaceError::Lost => Self::Lost,
+            wgpu::SurfaceError::OutOfMemory => Self::OutOfMemory,
+            wgpu::SurfaceError::Other => Self::Other,
+        }
+    }
+}
+
+impl fmt::Display for SurfaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Timeout => "timed out acquiring surface frame",
+            Self::Outdated => "surface is outdated",
+            Self::Lost => "surface was lost",
+            Self::OutOfMemory => "out of memory acquiring surface frame",
+            Self::Other => "failed to acquire surface frame",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for SurfaceError {}
+
+/// Errors that can occur while building a render pipeline.
+#[derive(Debug)]
+pub enum PipelineError {
+    /// The shader failed to compile.
+    Shader {
+        /// Shader label.
+        label: String,
+        /// Message reported by wgpu.
+        message: String,
+    },
+    /// Pipeline validation failed.
+    Validation {
+        /// Pipeline label.
+        label: String,
+        /// Message reported by wgpu.
+        message: String,
+    },
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shader { label, message } => {
+                write!(f, "shader '{label}' failed to compile: {message}")
+            }
+            Self::Validation { label, message } => {
+                write!(f, "pipeline '{label}' failed validation: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
@@ -1,17 +1,21 @@
 //! Spark Core - GPU primitives, pipelines, and low-level rendering.
 
 pub mod buffer;
+pub mod error;
 pub mod pipeline;
 pub mod types;
 pub mod vertex;
 pub mod wgpu_init;
 
 // Re-exports
-pub use buffer::{DynamicBuffer, QuadBuffers, StaticBuffer};
-pub use pipeline::{Pipeline, UniformBuffer};
-pub use types::{Color, GlobalUniforms, Point, Rect};
-pub use vertex::{GlyphInstance, ShapeInstance, Vertex2D};
-pub use wgpu_init::{init_wgpu, SurfaceState};
+pub use buffer::{
+    BufferStats, DynamicBuffer, QuadBuffers, StaticBuffer, DEFAULT_SHRINK_AFTER_FRAMES,
+};
+pub use error::{PipelineError, SurfaceError, WgpuInitError};
+pub use pipeline::{Pipeline, PipelineCache, PipelineConfig, UniformBuffer};
+pub use types::{Color, GlobalUniforms, Point, Rect, Vec2};
+pub use vertex::{GlyphInstance, ImageInstance, ShapeInstance, Vertex2D};
+pub use wgpu_init::{init_wgpu, request_adapter, SurfaceState, WgpuOptions};
 
 // Re-export wgpu and glam for convenience
 pub use glam;
@@ -1,5 +1,7 @@
 //! Render pipeline abstractions.
 
+use crate::error::PipelineError;
+use std::collections::HashMap;
 use wgpu::*;
 
 /// A GPU uniform buffer with typed data.
@@ -37,6 +39,8 @@ pub struct PipelineConfig<'a> {
     pub vertex_layouts: &'a [VertexBufferLayout<'a>],
     pub blend_state: Option<BlendState>,
     pub cull_mode: Option<Face>,
+    /// MSAA samples per pixel of the render target.
+    pub sample_count: u32,
     pub extra_bind_group_layouts: &'a [&'a BindGroupLayout],
 }
 
@@ -51,12 +55,242 @@ impl<'a> Default for PipelineConfig<'a> {
             vertex_layouts: &[],
             blend_state: Some(BlendState::ALPHA_BLENDING),
             cull_mode: None, // No culling for 2D UI
+            sample_count: 1,
             extra_bind_group_layouts: &[],
         }
     }
 }
 
+/// Everything that makes two render pipelines interchangeable.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct PipelineKey {
+    shader: u64,
+    vs_entry: String,
+    fs_entry: String,
+    target_format: TextureFormat,
+    blend_state: Option<BlendState>,
+    cull_mode: Option<Face>,
+    sample_count: u32,
+    vertex_layouts: Vec<(BufferAddress, VertexStepMode, Vec<VertexAttribute>)>,
+    extra_bind_group_layouts: Vec<BindGroupLayout>,
+}
+
+impl PipelineKey {
+    fn new(config: &PipelineConfig) -> Self {
+        Self {
+            shader: shader_hash(config.shader_source),
+            vs_entry: config.vs_entry.to_string(),
+            fs_entry: config.fs_entry.to_string(),
+            target_format: config.target_format,
+            blend_state: config.blend_state,
+            cull_mode: config.cull_mode,
+            sample_count: config.sample_count,
+            vertex_layouts: config
+                .vertex_layouts
+                .iter()
+                .map(|layout| {
+                    (
+                        layout.array_stride,
+                        layout.step_mode,
+                        layout.attributes.to_vec(),
+                    )
+                })
+                .collect(),
+            extra_bind_group_layouts: config
+                .extra_bind_group_layouts
+                .iter()
+                .map(|&layout| layout.clone())
+                .collect(),
+        }
+    }
+}
+
+fn shader_hash(source: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Run `build`, returning the validation error it raised, if any.
+fn capture<T>(device: &Device, build: impl FnOnce() -> T) -> (T, Option<String>) {
+    let scope = device.push_error_scope(ErrorFilter::Validation);
+    let value = build();
+
+    // Error scopes resolve asynchronously; on the web they can only be
+    // awaited from the browser event loop, so validation is left to the
+    // uncaptured error handler there.
+    #[cfg(not(target_arch = "wasm32"))]
+    let error = pollster::block_on(scope.pop()).map(|error| error.to_string());
+    #[cfg(target_arch = "wasm32")]
+    let error = {
+        drop(scope);
+        None
+    };
+    (value, error)
+}
+
+/// Shared shader modules, bind group layouts and render pipelines.
+///
+/// Building a pipeline compiles its shader, so passes that need the same
+/// one (for several windows, or render targets of the same format) should
+/// get it from a shared cache. `spark_render::Renderer` owns one and lends
+/// it to custom passes.
+#[derive(Default)]
+pub struct PipelineCache {
+    shaders: HashMap<u64, ShaderModule>,
+    bind_group_layouts: HashMap<Vec<BindGroupLayoutEntry>, BindGroupLayout>,
+    pipelines: HashMap<PipelineKey, RenderPipeline>,
+}
+
+impl PipelineCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bind group layout with these entries, created on first use.
+    pub fn bind_group_layout(
+        &mut self,
+        device: &Device,
+        label: &str,
+        entries: &[BindGroupLayoutEntry],
+    ) -> BindGroupLayout {
+        self.bind_group_layouts
+            .entry(entries.to_vec())
+            .or_insert_with(|| {
+                device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some(label),
+                    entries,
+                })
+            })
+            .clone()
+    }
+
+    /// The shader module for `source`, compiled on first use.
+    pub fn shader(
+        &mut self,
+        device: &Device,
+        label: &str,
+        source: &str,
+    ) -> Result<ShaderModule, PipelineError> {
+        let hash = shader_hash(source);
+        if let Some(shader) = self.shaders.get(&hash) {
+            return Ok(shader.clone());
+        }
+        let (shader, error) = capture(device, || {
+            device.create_shader_module(ShaderModuleDescriptor {
+                label: Some(label),
+                source: ShaderSource::Wgsl(source.into()),
+            })
+        });
+        if let Some(message) = error {
+            return Err(PipelineError::Shader {
+                label: label.to_string(),
+                message,
+            });
+        }
+        self.shaders.insert(hash, shader.clone());
+        Ok(shader)
+    }
+
+    /// The render pipeline for `config`, built on first use.
+    ///
+    /// `uniform_layout` is bind group 0, followed by the config's extra layouts.
+    pub fn render_pipeline(
+        &mut self,
+        device: &Device,
+        config: &PipelineConfig,
+        uniform_layout: &BindGroupLayout,
+    ) -> Result<RenderPipeline, PipelineError> {
+        let key = PipelineKey::new(config);
+        if let Some(pipeline) = self.pipelines.get(&key) {
+            return Ok(pipeline.clone());
+        }
+        let shader = self.shader(
+            device,
+            &format!("{}_shader", config.label),
+            config.shader_source,
+        )?;
+
+        let mut all_layouts: Vec<&BindGroupLayout> = vec![uniform_layout];
+        all_layouts.extend(config.extra_bind_group_layouts);
+
+        let (pipeline, error) = capture(device, || {
+            let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some(&format!("{}_layout", config.label)),
+                bind_group_layouts: &all_layouts,
+                immediate_size: 0,
+            });
+            device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: Some(config.label),
+                layout: Some(&pipeline_layout),
+                vertex: VertexState {
+                    module: &shader,
+                    entry_point: Some(config.vs_entry),
+                    buffers: config.vertex_layouts,
+                    compilation_options: PipelineCompilationOptions::default(),
+                },
+                primitive: PrimitiveState {
+                    topology: PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: FrontFace::Ccw,
+                    cull_mode: config.cull_mode,
+                    unclipped_depth: false,
+                    polygon_mode: PolygonMode::Fill,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: MultisampleState {
+                    count: config.sample_count,
+                    ..Default::default()
+                },
+                fragment: Some(FragmentState {
+                    module: &shader,
+                    entry_point: Some(config.fs_entry),
+                    targets: &[Some(ColorTargetState {
+                        format: config.target_format,
+                        blend: config.blend_state,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                    compilation_options: PipelineCompilationOptions::default(),
+                }),
+                multiview_mask: None,
+                cache: None,
+            })
+        });
+        if let Some(message) = error {
+            return Err(PipelineError::Validation {
+                label: config.label.to_string(),
+                message,
+            });
+        }
+        self.pipelines.insert(key, pipeline.clone());
+        Ok(pipeline)
+    }
+
+    /// Number of distinct render pipelines built so far.
+    pub fn pipeline_count(&self) -> usize {
+        self.pipelines.len()
+    }
+}
+
+/// Layout of the uniform bind group every [`Pipeline`] uses as group 0.
+const UNIFORM_LAYOUT_ENTRIES: &[BindGroupLayoutEntry] = &[BindGroupLayoutEntry {
+    binding: 0,
+    visibility: ShaderStages::VERTEX_FRAGMENT,
+    ty: BindingType::Buffer {
+        ty: BufferBindingType::Uniform,
+        has_dynamic_offset: false,
+        min_binding_size: None,
+    },
+    count: None,
+}];
+
 /// A typed render pipeline with uniforms.
+///
+/// The pipeline and layouts come from a [`PipelineCache`] and may be shared;
+/// the uniform buffer and its bind group belong to this value.
 pub struct Pipeline<U: bytemuck::Pod + bytemuck::Zeroable> {
     pub pipeline: RenderPipeline,
     pub bind_group_layout: BindGroupLayout,
@@ -65,39 +299,23 @@ pub struct Pipeline<U: bytemuck::Pod + bytemuck::Zeroable> {
 }
 
 impl<U: bytemuck::Pod + bytemuck::Zeroable> Pipeline<U> {
-    /// Create a new pipeline with the given configuration.
-    pub fn with_config(device: &Device, config: PipelineConfig) -> Self {
-        let shader = device.create_shader_module(ShaderModuleDescriptor {
-            label: Some(&format!("{}_shader", config.label)),
-            source: ShaderSource::Wgsl(config.shader_source.into()),
-        });
-
-        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-            label: Some(&format!("{}_uniform_bgl", config.label)),
-            entries: &[BindGroupLayoutEntry {
-                binding: 0,
-                visibility: ShaderStages::VERTEX_FRAGMENT,
-                ty: BindingType::Buffer {
-                    ty: BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-        });
-
-        // Combine uniform bind group layout with any extra layouts
-        let mut all_layouts: Vec<&BindGroupLayout> = vec![&bind_group_layout];
-        all_layouts.extend(config.extra_bind_group_layouts);
+    /// Create a new pipeline, reporting shader or validation errors instead of
+    /// deferring them to wgpu's uncaptured error handler.
+    pub fn try_with_config(device: &Device, config: PipelineConfig) -> Result<Self, PipelineError> {
+        Self::try_with_cache(device, &mut PipelineCache::new(), config)
+    }
 
-        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
-            label: Some(&format!("{}_layout", config.label)),
-            bind_group_layouts: &all_layouts,
-            immediate_size: 0,
-        });
+    /// Like [`Pipeline::try_with_config`], reusing what `cache` already built.
+    pub fn try_with_cache(
+        device: &Device,
+        cache: &mut PipelineCache,
+        config: PipelineConfig,
+    ) -> Result<Self, PipelineError> {
+        let bind_group_layout =
+            cache.bind_group_layout(device, "uniform_bgl", UNIFORM_LAYOUT_ENTRIES);
+        let pipeline = cache.render_pipeline(device, &config, &bind_group_layout)?;
 
         let uniform = UniformBuffer::<U>::new(device);
-
         let bind_group = device.create_bind_group(&BindGroupDescriptor {
             label: Some(&format!("{}_uniform_bg", config.label)),
             layout: &bind_group_layout,
@@ -107,57 +325,23 @@ impl<U: bytemuck::Pod + bytemuck::Zeroable> Pipeline<U> {
             }],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
-            label: Some(config.label),
-            layout: Some(&pipeline_layout),
-            vertex: VertexState {
-                module: &shader,
-                entry_point: Some(config.vs_entry),
-                buffers: config.vertex_layouts,
-                compilation_options: PipelineCompilationOptions::default(),
-            },
-            primitive: PrimitiveState {
-                topology: PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: FrontFace::Ccw,
-                cull_mode: config.cull_mode,
-                unclipped_depth: false,
-                polygon_mode: PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: MultisampleState::default(),
-            fragment: Some(FragmentState {
-                module: &shader,
-                entry_point: Some(config.fs_entry),
-                targets: &[Some(ColorTargetState {
-                    format: config.target_format,
-                    blend: config.blend_state,
-                    write_mask: ColorWrites::ALL,
-                })],
-                compilation_options: PipelineCompilationOptions::default(),
-            }),
-            multiview_mask: None,
-            cache: None,
-        });
-
-        Self {
+        Ok(Self {
             pipeline,
             bind_group_layout,
             bind_group,
             uniform,
-        }
+        })
     }
 
-    /// Legacy constructor for backwards compatibility.
+    /// Create a pipeline from a shader and its entry points.
     pub fn new(
         device: &Device,
         wgsl_src: &str,
         vs_entry: &str,
         fs_entry: &str,
         target_format: TextureFormat,
-    ) -> Self {
-        Self::with_config(
+    ) -> Result<Self, PipelineError> {
+        Self::try_with_config(
             device,
             PipelineConfig {
                 label: "basic_pipeline",
@@ -175,3 +359,53 @@ impl<U: bytemuck::Pod + bytemuck::Zeroable> Pipeline<U> {
         self.uniform.write(queue, value);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_key_matches_identical_configs() {
+        let config = || PipelineConfig {
+            shader_source: "@vertex fn vs_main() {}",
+            ..Default::default()
+        };
+        assert_eq!(PipelineKey::new(&config()), PipelineKey::new(&config()));
+        // Labels don't matter, only what the GPU sees
+        let relabeled = PipelineConfig {
+            label: "other",
+            ..config()
+        };
+        assert_eq!(PipelineKey::new(&relabeled), PipelineKey::new(&config()));
+    }
+
+    #[test]
+    fn test_pipeline_key_separates_formats_blends_and_shaders() {
+        let base = PipelineKey::new(&PipelineConfig::default());
+        let variants = [
+            PipelineConfig {
+                target_format: TextureFormat::Rgba8Unorm,
+                ..Default::default()
+            },
+            PipelineConfig {
+                blend_state: None,
+                ..Default::default()
+            },
+            PipelineConfig {
+                sample_count: 4,
+                ..Default::default()
+            },
+            PipelineConfig {
+                shader_source: "// changed",
+                ..Default::default()
+            },
+            PipelineConfig {
+                fs_entry: "fs_other",
+                ..Default::default()
+            },
+        ];
+        for config in variants {
+            assert_ne!(PipelineKey::new(&config), base);
+        }
+    }
+}
@@ -63,6 +63,207 @@ impl Color {
     pub fn with_alpha(self, a: f32) -> Self {
         Self { a, ..self }
     }
+
+    /// Create from hue in degrees, and saturation and lightness from 0.0 to 1.0.
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        from_hue_chroma(h, chroma, l - chroma / 2.0)
+    }
+
+    /// Hue in degrees, and saturation and lightness from 0.0 to 1.0, ignoring alpha.
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        let l = (max + min) / 2.0;
+        if delta == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        (self.hue(max, delta), s, l)
+    }
+
+    /// Create from hue in degrees, and saturation and value from 0.0 to 1.0.
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Self {
+        let chroma = v * s;
+        from_hue_chroma(h, chroma, v - chroma)
+    }
+
+    /// Hue in degrees, and saturation and value from 0.0 to 1.0, ignoring alpha.
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, max);
+        }
+        (self.hue(max, delta), delta / max, max)
+    }
+
+    /// Create from OKLCH lightness (0.0 to 1.0), chroma and hue in degrees.
+    ///
+    /// Equal steps in lightness look equally far apart, which makes OKLCH
+    /// a good fit for theme ramps. Colors outside sRGB lose chroma until
+    /// they fit.
+    pub fn from_oklch(l: f32, c: f32, h: f32) -> Self {
+        let (sin, cos) = h.to_radians().sin_cos();
+        from_oklab([l, c * cos, c * sin], 1.0)
+    }
+
+    /// OKLCH lightness, chroma and hue in degrees, ignoring alpha.
+    pub fn to_oklch(self) -> (f32, f32, f32) {
+        let [l, a, b] = self.to_oklab();
+        let h = b.atan2(a).to_degrees().rem_euclid(360.0);
+        (l, a.hypot(b), h)
+    }
+
+    /// Blend toward `other` by `t` (0.0 gives `self`, 1.0 gives `other`).
+    ///
+    /// Blends in OKLab so midpoints don't turn muddy or dark the way plain
+    /// sRGB blends do; alpha blends linearly.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let from = self.to_oklab();
+        let to = other.to_oklab();
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        from_oklab(
+            [mix(from[0], to[0]), mix(from[1], to[1]), mix(from[2], to[2])],
+            mix(self.a, other.a),
+        )
+    }
+
+    /// Raise OKLCH lightness by `amount` (0.0 to 1.0), keeping hue and alpha.
+    pub fn lighten(self, amount: f32) -> Self {
+        let [l, a, b] = self.to_oklab();
+        from_oklab([(l + amount).clamp(0.0, 1.0), a, b], self.a)
+    }
+
+    /// Lower OKLCH lightness by `amount` (0.0 to 1.0), keeping hue and alpha.
+    pub fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// CSS hex notation, e.g. `#FF5500`, with an alpha byte when not opaque.
+    pub fn to_hex_string(self) -> String {
+        let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let (r, g, b, a) = (byte(self.r), byte(self.g), byte(self.b), byte(self.a));
+        if a == 255 {
+            format!("#{r:02X}{g:02X}{b:02X}")
+        } else {
+            format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+        }
+    }
+
+    /// Relative luminance of the sRGB color (WCAG 2), ignoring alpha.
+    pub fn relative_luminance(self) -> f32 {
+        0.2126 * srgb_to_linear(self.r)
+            + 0.7152 * srgb_to_linear(self.g)
+            + 0.0722 * srgb_to_linear(self.b)
+    }
+
+    /// WCAG contrast ratio between two colors, from 1.0 to 21.0.
+    pub fn contrast_ratio(self, other: Self) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Hue in degrees of a color with the given largest component and chroma.
+    fn hue(self, max: f32, delta: f32) -> f32 {
+        let sector = if max == self.r {
+            (self.g - self.b) / delta
+        } else if max == self.g {
+            (self.b - self.r) / delta + 2.0
+        } else {
+            (self.r - self.g) / delta + 4.0
+        };
+        (sector * 60.0).rem_euclid(360.0)
+    }
+
+    fn to_oklab(self) -> [f32; 3] {
+        let (r, g, b) = (
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+        );
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        [
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        ]
+    }
+}
+
+/// Color from a hue in degrees, chroma, and the amount added to each component.
+fn from_hue_chroma(h: f32, chroma: f32, offset: f32) -> Color {
+    let sector = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    Color::rgb(r + offset, g + offset, b + offset)
+}
+
+/// sRGB color from OKLab.
+///
+/// Colors outside sRGB keep their lightness and hue and lose chroma until
+/// they fit, instead of having each channel clipped.
+fn from_oklab([l, a, b]: [f32; 3], alpha: f32) -> Color {
+    let l = l.clamp(0.0, 1.0);
+    let mut rgb = oklab_to_linear(l, a, b);
+    if !in_gamut(rgb) {
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..20 {
+            let mid = (low + high) / 2.0;
+            if in_gamut(oklab_to_linear(l, a * mid, b * mid)) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        rgb = oklab_to_linear(l, a * low, b * low);
+    }
+    let channel = |c: f32| linear_to_srgb(c).clamp(0.0, 1.0);
+    Color::rgba(channel(rgb[0]), channel(rgb[1]), channel(rgb[2]), alpha)
+}
+
+/// Linear sRGB components of an OKLab color.
+fn oklab_to_linear(l: f32, a: f32, b: f32) -> [f32; 3] {
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+        -0.004_196_086_4 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+}
+
+fn in_gamut(rgb: [f32; 3]) -> bool {
+    rgb.iter().all(|c| (-1e-4..=1.0 + 1e-4).contains(c))
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
 }
 
 impl From<[f32; 4]> for Color {
@@ -162,13 +363,69 @@ impl Rect {
         }
     }
 
-    pub fn inset(&self, amount: f32) -> Self {
+    /// Shrink by `dx` on the left and right and `dy` on the top and bottom.
+    pub fn inset(&self, dx: f32, dy: f32) -> Self {
         Self {
-            x: self.x + amount,
-            y: self.y + amount,
-            width: (self.width - amount * 2.0).max(0.0),
-            height: (self.height - amount * 2.0).max(0.0),
+            x: self.x + dx,
+            y: self.y + dy,
+            width: (self.width - dx * 2.0).max(0.0),
+            height: (self.height - dy * 2.0).max(0.0),
+        }
+    }
+
+    /// Grow by `amount` on every side, e.g. for a focus ring around a widget.
+    pub fn expand(&self, amount: f32) -> Self {
+        self.inset(-amount, -amount)
+    }
+
+    /// The smallest rect containing both; an empty rect adds nothing.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
         }
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let max_x = (self.x + self.width).max(other.x + other.width);
+        let max_y = (self.y + self.height).max(other.y + other.height);
+        Rect::new(x, y, max_x - x, max_y - y)
+    }
+
+    /// Whether `other` lies entirely inside this rect.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.x + other.width <= self.x + self.width
+            && other.y + other.height <= self.y + self.height
+    }
+
+    /// Scale position and size by `factor`, e.g. from logical to physical pixels.
+    pub fn scale(&self, factor: f32) -> Self {
+        Self::new(
+            self.x * factor,
+            self.y * factor,
+            self.width * factor,
+            self.height * factor,
+        )
+    }
+
+    /// Snap the edges of a logical rect to physical pixel boundaries.
+    ///
+    /// Each edge is rounded on its own, so rects that share an edge still
+    /// share it after rounding and nothing is left half a pixel wide.
+    pub fn round_to_pixels(&self, scale_factor: f32) -> Self {
+        let snap = |v: f32| (v * scale_factor).round() / scale_factor;
+        let (x, y) = (snap(self.x), snap(self.y));
+        let max_x = snap(self.x + self.width);
+        let max_y = snap(self.y + self.height);
+        Self::new(x, y, max_x - x, max_y - y)
+    }
+
+    /// Whether the rect has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
     }
 }
 
@@ -176,6 +433,10 @@ impl Rect {
 pub type Point = Vec2;
 
 /// Global uniforms passed to all shaders.
+///
+/// The built-in passes bind these at group 0, binding 0, and custom passes
+/// can do the same by declaring [`GlobalUniforms::WGSL`] in their shader.
+/// Pixel values are physical unless the name says logical.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct GlobalUniforms {
@@ -185,6 +446,30 @@ pub struct GlobalUniforms {
     pub scale_factor: f32,
     /// Time since app start in seconds.
     pub time: f32,
+    /// Pointer position in pixels.
+    pub cursor: [f32; 2],
+    /// Pointer position in logical pixels.
+    pub cursor_logical: [f32; 2],
+    /// Seconds since the previous frame.
+    pub delta_time: f32,
+    /// Pads the struct to the 16-byte multiple uniform buffers need.
+    pub _padding: [f32; 3],
+}
+
+impl GlobalUniforms {
+    /// WGSL declaration matching this struct, bound at group 0, binding 0.
+    pub const WGSL: &'static str = "struct Globals {
+    viewport_size: vec2<f32>,
+    scale_factor: f32,
+    time: f32,
+    cursor: vec2<f32>,
+    cursor_logical: vec2<f32>,
+    delta_time: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> globals: Globals;
+";
 }
 
 impl Default for GlobalUniforms {
@@ -193,7 +478,156 @@ impl Default for GlobalUniforms {
             viewport_size: [800.0, 600.0],
             scale_factor: 1.0,
             time: 0.0,
+            cursor: [-1.0, -1.0],
+            cursor_logical: [-1.0, -1.0],
+            delta_time: 0.0,
+            _padding: [0.0; 3],
         }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Color, b: Color) {
+        let close = a
+            .to_array()
+            .iter()
+            .zip(b.to_array())
+            .all(|(x, y)| (x - y).abs() < 1e-3);
+        assert!(close, "{a:?} != {b:?}");
+    }
+
+    const SAMPLES: [u32; 6] = [0xFF5500, 0x3B82F6, 0x10B981, 0x7C3AED, 0x808080, 0x000000];
+
+    #[test]
+    fn test_hsl_round_trip() {
+        for hex in SAMPLES {
+            let color = Color::from_hex(hex);
+            let (h, s, l) = color.to_hsl();
+            assert_close(Color::from_hsl(h, s, l), color);
+        }
+        assert_close(Color::from_hsl(120.0, 1.0, 0.5), Color::GREEN);
+    }
+
+    #[test]
+    fn test_hsv_round_trip() {
+        for hex in SAMPLES {
+            let color = Color::from_hex(hex);
+            let (h, s, v) = color.to_hsv();
+            assert_close(Color::from_hsv(h, s, v), color);
+        }
+        assert_close(Color::from_hsv(240.0, 1.0, 1.0), Color::BLUE);
+    }
+
+    #[test]
+    fn test_oklch_round_trip() {
+        for hex in SAMPLES {
+            let color = Color::from_hex(hex);
+            let (l, c, h) = color.to_oklch();
+            assert_close(Color::from_oklch(l, c, h), color);
+        }
+        let (l, c, _) = Color::WHITE.to_oklch();
+        assert!((l - 1.0).abs() < 1e-3 && c < 1e-3);
+    }
+
+    #[test]
+    fn test_lerp_endpoints_and_alpha() {
+        let from = Color::from_hex(0xFF5500);
+        let to = Color::from_hex(0x3B82F6).with_alpha(0.0);
+        assert_close(from.lerp(to, 0.0), from);
+        assert_close(from.lerp(to, 1.0), to);
+        assert!((from.lerp(to, 0.25).a - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_lighten_and_darken() {
+        let color = Color::from_hex(0x3B82F6);
+        let (l, _, h) = color.to_oklch();
+        let (lighter, _, lighter_h) = color.lighten(0.1).to_oklch();
+        assert!(lighter > l && (lighter_h - h).abs() < 1.0);
+        assert!(color.darken(0.1).to_oklch().0 < l);
+        assert_close(Color::WHITE.lighten(0.5), Color::WHITE);
+    }
+
+    #[test]
+    fn test_hex_string_round_trip() {
+        assert_eq!(Color::from_hex(0xFF5500).to_hex_string(), "#FF5500");
+        assert_eq!(Color::from_hex_alpha(0x3B82F680).to_hex_string(), "#3B82F680");
+        for hex in SAMPLES {
+            let text = Color::from_hex(hex).to_hex_string();
+            assert_eq!(u32::from_str_radix(&text[1..], 16).unwrap(), hex);
+        }
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 1e-3);
+        assert!((Color::RED.contrast_ratio(Color::RED) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rect_inset_and_expand() {
+        let rect = Rect::new(10.0, 10.0, 20.0, 10.0);
+        assert_eq!(rect.inset(2.0, 1.0), Rect::new(12.0, 11.0, 16.0, 8.0));
+        assert_eq!(rect.inset(15.0, 0.0).width, 0.0);
+        assert_eq!(rect.expand(2.0), Rect::new(8.0, 8.0, 24.0, 14.0));
+    }
+
+    #[test]
+    fn test_rect_union_and_intersection() {
+        let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = Rect::new(5.0, 5.0, 10.0, 10.0);
+        assert_eq!(a.union(&b), Rect::new(0.0, 0.0, 15.0, 15.0));
+        assert_eq!(a.union(&Rect::ZERO), a);
+        assert_eq!(Rect::ZERO.union(&b), b);
+        assert_eq!(a.intersection(&b), Some(Rect::new(5.0, 5.0, 5.0, 5.0)));
+        assert_eq!(a.intersection(&Rect::new(10.0, 0.0, 5.0, 5.0)), None);
+    }
+
+    #[test]
+    fn test_rect_contains_rect() {
+        let outer = Rect::new(0.0, 0.0, 10.0, 10.0);
+        assert!(outer.contains_rect(&Rect::new(2.0, 2.0, 8.0, 8.0)));
+        assert!(outer.contains_rect(&outer));
+        assert!(!outer.contains_rect(&Rect::new(2.0, 2.0, 9.0, 8.0)));
+    }
+
+    #[test]
+    fn test_rect_translate_scale_and_center() {
+        let rect = Rect::new(10.0, 20.0, 30.0, 40.0);
+        assert_eq!(rect.translate(Vec2::new(-10.0, 5.0)), Rect::new(0.0, 25.0, 30.0, 40.0));
+        assert_eq!(rect.scale(2.0), Rect::new(20.0, 40.0, 60.0, 80.0));
+        assert_eq!(rect.center(), Vec2::new(25.0, 40.0));
+    }
+
+    #[test]
+    fn test_rect_round_to_pixels_keeps_shared_edges() {
+        // At 1.5x, 0.5 logical pixels is 0.75 physical pixels
+        let left = Rect::new(0.5, 0.0, 10.0, 10.0).round_to_pixels(1.5);
+        let right = Rect::new(10.5, 0.0, 10.0, 10.0).round_to_pixels(1.5);
+        assert!((left.x + left.width - right.x).abs() < 1e-4);
+        for edge in [left.x, left.width, right.x, right.width] {
+            let physical = edge * 1.5;
+            assert!((physical - physical.round()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_global_uniforms_layout() {
+        // Offsets in GlobalUniforms::WGSL, padded to a multiple of 16
+        assert_eq!(std::mem::size_of::<GlobalUniforms>(), 48);
+        assert_eq!(std::mem::offset_of!(GlobalUniforms, cursor), 16);
+        assert_eq!(std::mem::offset_of!(GlobalUniforms, cursor_logical), 24);
+        assert_eq!(std::mem::offset_of!(GlobalUniforms, delta_time), 32);
+    }
+
+    #[test]
+    fn test_rect_is_empty() {
+        assert!(Rect::ZERO.is_empty());
+        assert!(Rect::new(0.0, 0.0, 10.0, -1.0).is_empty());
+        assert!(!Rect::new(0.0, 0.0, 1.0, 1.0).is_empty());
+    }
+}
@@ -53,8 +53,17 @@ pub struct ShapeInstance {
     pub border_width: f32,
     /// Border color RGBA.
     pub border_color: [f32; 4],
+    /// Clip rectangle in pixels (left, top, right, bottom), applied per pixel.
+    pub clip: [f32; 4],
+    /// Shadow blur radius in pixels; the quad grows by this much on each side
+    /// and alpha fades across the shape edge. Zero draws a solid shape.
+    pub blur: f32,
+    /// Start angle and sweep in radians, clockwise from 12 o'clock. A
+    /// positive sweep draws an arc of the circle inscribed in the shape,
+    /// `border_width` thick, instead of the shape itself.
+    pub arc: [f32; 2],
     /// Padding for alignment.
-    pub _padding: [f32; 2],
+    pub _padding: [f32; 3],
 }
 
 impl Default for ShapeInstance {
@@ -66,13 +75,19 @@ impl Default for ShapeInstance {
             corner_radius: 0.0,
             border_width: 0.0,
             border_color: [0.0, 0.0, 0.0, 0.0],
-            _padding: [0.0, 0.0],
+            clip: Self::NO_CLIP,
+            blur: 0.0,
+            arc: [0.0, 0.0],
+            _padding: [0.0; 3],
         }
     }
 }
 
 impl ShapeInstance {
-    pub const ATTRIBS: [VertexAttribute; 6] = wgpu::vertex_attr_array![
+    /// A clip rectangle that clips nothing.
+    pub const NO_CLIP: [f32; 4] = [f32::MIN, f32::MIN, f32::MAX, f32::MAX];
+
+    pub const ATTRIBS: [VertexAttribute; 9] = wgpu::vertex_attr_array![
         // Start at location 2 (after Vertex2D uses 0 and 1)
         2 => Float32x2,   // pos
         3 => Float32x2,   // size
@@ -80,6 +95,9 @@ impl ShapeInstance {
         5 => Float32,     // corner_radius
         6 => Float32,     // border_width
         7 => Float32x4,   // border_color
+        8 => Float32x4,   // clip
+        9 => Float32,     // blur
+        10 => Float32x2,  // arc
         // _padding not needed in shader
     ];
 
@@ -126,3 +144,42 @@ impl GlyphInstance {
     }
 }
 
+
+/// Instance data for rendering an image from a texture.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ImageInstance {
+    /// Position in pixels (top-left corner).
+    pub pos: [f32; 2],
+    /// Size in pixels (width, height).
+    pub size: [f32; 2],
+    /// UV coordinates in the texture (top-left).
+    pub uv_pos: [f32; 2],
+    /// UV size in the texture.
+    pub uv_size: [f32; 2],
+    /// Color multiplied with each texel.
+    pub tint: [f32; 4],
+    /// Corner radius in pixels.
+    pub corner_radius: f32,
+    /// Padding for alignment.
+    pub _padding: [f32; 3],
+}
+
+impl ImageInstance {
+    pub const ATTRIBS: [VertexAttribute; 6] = wgpu::vertex_attr_array![
+        2 => Float32x2,   // pos
+        3 => Float32x2,   // size
+        4 => Float32x2,   // uv_pos
+        5 => Float32x2,   // uv_size
+        6 => Float32x4,   // tint
+        7 => Float32,     // corner_radius
+    ];
+
+    pub fn layout() -> VertexBufferLayout<'static> {
+        VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as BufferAddress,
+            step_mode: VertexStepMode::Instance,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+}
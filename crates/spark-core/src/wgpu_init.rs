@@ -1,52 +1,181 @@
+use crate::error::{SurfaceError, WgpuInitError};
 use wgpu::*;
 use winit::{dpi::PhysicalSize, window::Window};
 
+/// How [`init_wgpu`] picks a GPU adapter and device.
+///
+/// For debugging, environment variables override these at startup:
+/// `SPARK_BACKEND` (a comma-separated list such as `gl` or `vulkan,metal`),
+/// `SPARK_POWER_PREFERENCE` (`low`, `high` or `none`) and
+/// `SPARK_FORCE_FALLBACK_ADAPTER=1`.
+#[derive(Clone, Debug)]
+pub struct WgpuOptions {
+    /// Backends to try.
+    pub backends: Backends,
+    /// Which GPU to prefer on machines with more than one.
+    pub power_preference: PowerPreference,
+    /// Use a software adapter.
+    pub force_fallback_adapter: bool,
+    /// Features the device must support.
+    pub required_features: Features,
+    /// Limits the device must support.
+    pub required_limits: Limits,
+}
+
+impl Default for WgpuOptions {
+    fn default() -> Self {
+        // On web, prefer WebGPU. On native, use primary backends.
+        #[cfg(target_arch = "wasm32")]
+        let backends = Backends::BROWSER_WEBGPU | Backends::GL;
+        #[cfg(not(target_arch = "wasm32"))]
+        let backends = Backends::PRIMARY;
+
+        Self {
+            backends,
+            // UI rarely needs the discrete GPU, and waking it costs battery
+            power_preference: PowerPreference::LowPower,
+            force_fallback_adapter: false,
+            required_features: Features::empty(),
+            required_limits: Limits::default(),
+        }
+    }
+}
+
+impl WgpuOptions {
+    /// Apply the `SPARK_*` environment variable overrides.
+    pub fn with_env_overrides(mut self) -> Self {
+        if let Ok(value) = std::env::var("SPARK_BACKEND") {
+            match parse_backends(&value) {
+                Some(backends) => self.backends = backends,
+                None => log::warn!("ignoring unknown SPARK_BACKEND {value:?}"),
+            }
+        }
+        if let Ok(value) = std::env::var("SPARK_POWER_PREFERENCE") {
+            match parse_power_preference(&value) {
+                Some(preference) => self.power_preference = preference,
+                None => log::warn!("ignoring unknown SPARK_POWER_PREFERENCE {value:?}"),
+            }
+        }
+        if let Ok(value) = std::env::var("SPARK_FORCE_FALLBACK_ADAPTER") {
+            self.force_fallback_adapter = matches!(value.as_str(), "1" | "true");
+        }
+        self
+    }
+}
+
+fn parse_backends(value: &str) -> Option<Backends> {
+    let backends = Backends::from_comma_list(value);
+    (!backends.is_empty()).then_some(backends)
+}
+
+fn parse_power_preference(value: &str) -> Option<PowerPreference> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "low" | "low-power" | "lowpower" => Some(PowerPreference::LowPower),
+        "high" | "high-performance" | "highperformance" => Some(PowerPreference::HighPerformance),
+        "none" => Some(PowerPreference::None),
+        _ => None,
+    }
+}
+
+/// Consecutive outdated or lost frames before the surface is recreated.
+const RECREATE_AFTER_FAILURES: u32 = 3;
+
+/// What to do about a failed frame acquisition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Recovery {
+    /// Skip the frame and try again on the next one.
+    Skip,
+    /// Reconfigure the surface and retry.
+    Reconfigure,
+    /// Replace the surface with a new one and retry.
+    Recreate,
+}
+
+/// Counts consecutive surface failures to escalate recovery.
+#[derive(Debug, Default)]
+struct FailureTracker {
+    consecutive: u32,
+}
+
+impl FailureTracker {
+    fn succeeded(&mut self) {
+        self.consecutive = 0;
+    }
+
+    fn failed(&mut self, error: SurfaceError) -> Recovery {
+        if !matches!(error, SurfaceError::Outdated | SurfaceError::Lost) {
+            return Recovery::Skip;
+        }
+        self.consecutive += 1;
+        if self.consecutive < RECREATE_AFTER_FAILURES {
+            return Recovery::Reconfigure;
+        }
+        self.consecutive = 0;
+        Recovery::Recreate
+    }
+}
+
 pub struct SurfaceState<'a> {
     pub surface: Surface<'a>,
     pub config: SurfaceConfiguration,
     pub size: PhysicalSize<u32>,
+    /// Kept to recreate the surface when reconfiguring doesn't help.
+    instance: Instance,
+    window: &'a dyn Window,
+    failures: FailureTracker,
+    /// Whether the last acquisition failed even after recreating the surface.
+    recreate_failed: bool,
 }
 
-pub async fn init_wgpu<'a>(window: &'a dyn Window) -> (Device, Queue, SurfaceState<'a>) {
+/// Create a device and a surface for `window`.
+///
+/// `options` pass through [`WgpuOptions::with_env_overrides`] first, and
+/// the chosen adapter is logged.
+pub async fn init_wgpu<'a>(
+    window: &'a dyn Window,
+    options: &WgpuOptions,
+) -> Result<(Device, Queue, SurfaceState<'a>), WgpuInitError> {
     let size = window.surface_size();
-
-    // On web, prefer WebGPU. On native, use primary backends.
-    #[cfg(target_arch = "wasm32")]
-    let backends = Backends::BROWSER_WEBGPU | Backends::GL;
-    #[cfg(not(target_arch = "wasm32"))]
-    let backends = Backends::PRIMARY;
+    let options = options.clone().with_env_overrides();
 
     let instance = Instance::new(&InstanceDescriptor {
-        backends,
+        backends: options.backends,
         ..Default::default()
     });
-    let surface = instance.create_surface(window).expect("create surface");
+    let surface = instance
+        .create_surface(window)
+        .map_err(WgpuInitError::CreateSurface)?;
 
-    let adapter = instance
-        .request_adapter(&RequestAdapterOptions {
-            power_preference: PowerPreference::HighPerformance,
-            force_fallback_adapter: false,
-            compatible_surface: Some(&surface),
-        })
-        .await
-        .expect("adapter");
+    let adapter = request_adapter(&instance, Some(&surface), &options).await?;
+    let info = adapter.get_info();
+    log::info!(
+        "using GPU adapter {} ({:?}, {:?})",
+        info.name,
+        info.backend,
+        info.device_type
+    );
+
+    let missing = options.required_features - adapter.features();
+    if !missing.is_empty() {
+        return Err(WgpuInitError::MissingFeatures(missing));
+    }
 
     let (device, queue) = adapter
         .request_device(
             &DeviceDescriptor {
                 label: Some("device"),
-                required_features: Features::empty(),
-                required_limits: Limits::default(),
+                required_features: options.required_features,
+                required_limits: options.required_limits.clone(),
                 memory_hints: Default::default(),
                 experimental_features: Default::default(),
                 trace: Default::default(),
             },
         )
         .await
-        .expect("device");
+        .map_err(WgpuInitError::RequestDevice)?;
 
     let caps = surface.get_capabilities(&adapter);
-    let format = caps.formats[0];
+    let format = *caps.formats.first().ok_or(WgpuInitError::NoSurfaceFormat)?;
 
     let config = SurfaceConfiguration {
         usage: TextureUsages::RENDER_ATTACHMENT,
@@ -60,10 +189,34 @@ pub async fn init_wgpu<'a>(window: &'a dyn Window) -> (Device, Queue, SurfaceSta
     };
     surface.configure(&device, &config);
 
-    let mut state = SurfaceState { surface, config, size };
+    let mut state = SurfaceState {
+        surface,
+        config,
+        size,
+        instance,
+        window,
+        failures: FailureTracker::default(),
+        recreate_failed: false,
+    };
     state.reconfigure(&device);
 
-    (device, queue, state)
+    Ok((device, queue, state))
+}
+
+/// Request an adapter from the instance, optionally compatible with a surface.
+pub async fn request_adapter(
+    instance: &Instance,
+    compatible_surface: Option<&Surface<'_>>,
+    options: &WgpuOptions,
+) -> Result<Adapter, WgpuInitError> {
+    instance
+        .request_adapter(&RequestAdapterOptions {
+            power_preference: options.power_preference,
+            force_fallback_adapter: options.force_fallback_adapter,
+            compatible_surface,
+        })
+        .await
+        .map_err(WgpuInitError::NoAdapter)
 }
 
 impl<'a> SurfaceState<'a> {
@@ -79,4 +232,112 @@ impl<'a> SurfaceState<'a> {
             self.surface.configure(device, &self.config);
         }
     }
+
+    /// Acquire the next frame.
+    ///
+    /// An outdated or lost surface is reconfigured and retried once; after
+    /// several such frames in a row it is recreated instead. Errors that
+    /// remain should be treated as a skipped frame; see
+    /// [`recreate_failed`](Self::recreate_failed) for when that stops helping.
+    pub fn acquire_frame(&mut self, device: &Device) -> Result<SurfaceTexture, SurfaceError> {
+        self.recreate_failed = false;
+        let error = match self.surface.get_current_texture() {
+            Ok(frame) => {
+                self.failures.succeeded();
+                return Ok(frame);
+            }
+            Err(e) => SurfaceError::from(e),
+        };
+        let recovery = self.failures.failed(error);
+        match recovery {
+            Recovery::Skip => return Err(error),
+            Recovery::Reconfigure => self.reconfigure(device),
+            Recovery::Recreate => self.recreate(device),
+        }
+        match self.surface.get_current_texture() {
+            Ok(frame) => {
+                self.failures.succeeded();
+                Ok(frame)
+            }
+            Err(e) => {
+                self.recreate_failed = recovery == Recovery::Recreate;
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Whether the last [`acquire_frame`](Self::acquire_frame) failed even
+    /// after recreating the surface, i.e. recovery is not working.
+    pub fn recreate_failed(&self) -> bool {
+        self.recreate_failed
+    }
+
+    /// Replace the surface, e.g. after the compositor restarted.
+    fn recreate(&mut self, device: &Device) {
+        log::warn!("surface keeps failing; recreating it");
+        match self.instance.create_surface(self.window) {
+            Ok(surface) => {
+                self.surface = surface;
+                self.surface.configure(device, &self.config);
+            }
+            Err(err) => log::error!("failed to recreate the surface: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adapter_selection_failure_is_an_error() {
+        // An instance with no backends can never produce an adapter.
+        let instance = Instance::new(&InstanceDescriptor {
+            backends: Backends::empty(),
+            ..Default::default()
+        });
+        let result = pollster::block_on(request_adapter(&instance, None, &WgpuOptions::default()));
+        assert!(matches!(result, Err(WgpuInitError::NoAdapter(_))));
+    }
+
+    #[test]
+    fn test_repeated_outdated_frames_escalate_boundedly() {
+        let mut tracker = FailureTracker::default();
+        let actions: Vec<_> = (0..9)
+            .map(|_| tracker.failed(SurfaceError::Outdated))
+            .collect();
+        // Reconfigure twice, then recreate, over and over; the count never
+        // grows past the threshold
+        let recreations = actions.iter().filter(|&&a| a == Recovery::Recreate).count();
+        assert_eq!(recreations, 3);
+        use Recovery::*;
+        assert_eq!(actions[..3], [Reconfigure, Reconfigure, Recreate]);
+        assert!(tracker.consecutive < RECREATE_AFTER_FAILURES);
+    }
+
+    #[test]
+    fn test_timeouts_skip_and_success_resets() {
+        let mut tracker = FailureTracker::default();
+        tracker.failed(SurfaceError::Lost);
+        tracker.failed(SurfaceError::Lost);
+        // Timeouts skip the frame without counting toward recreation
+        assert_eq!(tracker.failed(SurfaceError::Timeout), Recovery::Skip);
+        tracker.succeeded();
+        assert_eq!(tracker.failed(SurfaceError::Lost), Recovery::Reconfigure);
+    }
+
+    #[test]
+    fn test_backend_override_parsing() {
+        assert_eq!(parse_backends("gl"), Some(Backends::GL));
+        assert_eq!(parse_backends("Vulkan,metal"), Some(Backends::VULKAN | Backends::METAL));
+        assert_eq!(parse_backends("nonsense"), None);
+    }
+
+    #[test]
+    fn test_power_preference_override_parsing() {
+        assert_eq!(parse_power_preference("low"), Some(PowerPreference::LowPower));
+        assert_eq!(parse_power_preference(" High "), Some(PowerPreference::HighPerformance));
+        assert_eq!(parse_power_preference("none"), Some(PowerPreference::None));
+        assert_eq!(parse_power_preference("fast"), None);
+    }
 }
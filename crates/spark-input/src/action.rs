@@ -4,7 +4,7 @@
 //! This allows multiple input methods (keyboard, mouse, touch) to trigger
 //! the same logical action.
 
-use crate::{InputEvent, Key, KeyboardEvent, NamedKey, PointerButton, shortcuts};
+use crate::{InputEvent, Key, KeyboardEvent, NamedKey, Platform, PointerButton, Shortcut};
 
 /// Built-in UI actions that have standard semantics.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -110,7 +110,12 @@ impl From<&str> for Action {
 }
 
 /// Maps input events to actions using pattern matching.
-pub struct ActionMapper;
+pub struct ActionMapper {
+    /// Shortcut bindings, checked before the built-in named-key mapping.
+    bindings: Vec<(Shortcut, StandardAction)>,
+    /// Whose conventions the named keys follow, e.g. for word movement.
+    platform: Platform,
+}
 
 impl Default for ActionMapper {
     fn default() -> Self {
@@ -119,9 +124,42 @@ impl Default for ActionMapper {
 }
 
 impl ActionMapper {
-    /// Create a new action mapper.
+    /// Create a new action mapper with the current platform's default bindings.
     pub fn new() -> Self {
-        Self
+        Self::for_platform(Platform::current())
+    }
+
+    /// Create a new action mapper with the default bindings of a platform.
+    pub fn for_platform(platform: Platform) -> Self {
+        use StandardAction::*;
+
+        let shortcut = |spec: &str| Shortcut::parse_for(spec, platform).expect("valid default");
+        let mut bindings = vec![
+            (shortcut("Mod+C"), Copy),
+            (shortcut("Mod+X"), Cut),
+            (shortcut("Mod+V"), Paste),
+            (shortcut("Mod+A"), SelectAll),
+            (shortcut("Mod+Z"), Undo),
+            (shortcut("Mod+Shift+Z"), Redo),
+        ];
+        if platform == Platform::Other {
+            bindings.push((shortcut("Ctrl+Y"), Redo));
+        }
+
+        Self { bindings, platform }
+    }
+
+    /// Bind a shortcut to a standard action, taking precedence over earlier bindings.
+    pub fn bind(&mut self, shortcut: Shortcut, action: StandardAction) {
+        self.bindings.insert(0, (shortcut, action));
+    }
+
+    /// Get the first shortcut bound to an action (e.g. for menu accelerators).
+    pub fn shortcut_for(&self, action: StandardAction) -> Option<&Shortcut> {
+        self.bindings
+            .iter()
+            .find(|(_, a)| *a == action)
+            .map(|(shortcut, _)| shortcut)
     }
 
     /// Map a keyboard event to a standard action.
@@ -129,25 +167,16 @@ impl ActionMapper {
         use StandardAction::*;
 
         // Check shortcuts first (they use modifiers)
-        if shortcuts::is_copy(event) {
-            return Some(Copy);
-        }
-        if shortcuts::is_cut(event) {
-            return Some(Cut);
-        }
-        if shortcuts::is_paste(event) {
-            return Some(Paste);
-        }
-        if shortcuts::is_select_all(event) {
-            return Some(SelectAll);
-        }
-        if shortcuts::is_undo(event) {
-            return Some(Undo);
-        }
-        if shortcuts::is_redo(event) {
-            return Some(Redo);
+        if let Some((_, action)) = self.bindings.iter().find(|(s, _)| s.matches(event)) {
+            return Some(*action);
         }
 
+        // Option moves by word and Cmd by line on macOS; Ctrl moves by word elsewhere
+        let (by_word, by_line) = match self.platform {
+            Platform::Apple => (event.modifiers.alt(), event.modifiers.meta()),
+            Platform::Other => (event.modifiers.ctrl(), false),
+        };
+
         // Check named keys
         match &event.key {
             Key::Named(named) => match named {
@@ -163,22 +192,30 @@ impl ActionMapper {
                 NamedKey::Backspace => Some(Backspace),
                 NamedKey::Delete => Some(Delete),
                 NamedKey::ArrowLeft => {
-                    if event.modifiers.shift() && event.modifiers.ctrl() {
+                    if by_line && event.modifiers.shift() {
+                        Some(SelectToStart)
+                    } else if by_line {
+                        Some(MoveToStart)
+                    } else if event.modifiers.shift() && by_word {
                         Some(SelectWordLeft)
                     } else if event.modifiers.shift() {
                         Some(SelectLeft)
-                    } else if event.modifiers.ctrl() {
+                    } else if by_word {
                         Some(MoveWordLeft)
                     } else {
                         Some(MoveLeft)
                     }
                 }
                 NamedKey::ArrowRight => {
-                    if event.modifiers.shift() && event.modifiers.ctrl() {
+                    if by_line && event.modifiers.shift() {
+                        Some(SelectToEnd)
+                    } else if by_line {
+                        Some(MoveToEnd)
+                    } else if event.modifiers.shift() && by_word {
                         Some(SelectWordRight)
                     } else if event.modifiers.shift() {
                         Some(SelectRight)
-                    } else if event.modifiers.ctrl() {
+                    } else if by_word {
                         Some(MoveWordRight)
                     } else {
                         Some(MoveRight)
@@ -307,6 +344,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_default_copy_binding_is_platform_aware() {
+        let event = |modifiers| InputEvent::KeyDown {
+            event: KeyboardEvent {
+                key: Key::Character("c".into()),
+                modifiers,
+                ..Default::default()
+            },
+        };
+        let copy = Some(Action::Standard(StandardAction::Copy));
+
+        let mac = ActionMapper::for_platform(Platform::Apple);
+        assert_eq!(mac.map_event(&event(crate::Modifiers::META)), copy);
+        assert_eq!(mac.map_event(&event(crate::Modifiers::CONTROL)), None);
+
+        let win = ActionMapper::for_platform(Platform::Other);
+        assert_eq!(win.map_event(&event(crate::Modifiers::CONTROL)), copy);
+    }
+
+    #[test]
+    fn test_word_and_line_movement_per_platform() {
+        use crate::Modifiers;
+        use StandardAction::*;
+
+        let event = |modifiers| InputEvent::KeyDown {
+            event: KeyboardEvent {
+                key: Key::Named(NamedKey::ArrowLeft),
+                modifiers,
+                ..Default::default()
+            },
+        };
+        let action = |mapper: &ActionMapper, modifiers| match mapper.map_event(&event(modifiers)) {
+            Some(Action::Standard(action)) => action,
+            other => panic!("expected a standard action, got {other:?}"),
+        };
+        let shift = Modifiers::SHIFT;
+
+        let mac = ActionMapper::for_platform(Platform::Apple);
+        assert_eq!(action(&mac, Modifiers::ALT), MoveWordLeft);
+        assert_eq!(action(&mac, Modifiers::META), MoveToStart);
+        assert_eq!(action(&mac, Modifiers::META | shift), SelectToStart);
+        assert_eq!(action(&mac, Modifiers::CONTROL), MoveLeft);
+
+        let win = ActionMapper::for_platform(Platform::Other);
+        assert_eq!(action(&win, Modifiers::CONTROL), MoveWordLeft);
+        assert_eq!(action(&win, Modifiers::CONTROL | shift), SelectWordLeft);
+        assert_eq!(action(&win, Modifiers::ALT), MoveLeft);
+    }
+
     #[test]
     fn test_enter_maps_to_activate() {
         let mapper = ActionMapper::new();
@@ -0,0 +1,87 @@
+//! Multi-click detection for double and triple clicks.
+
+use crate::PointerButton;
+use glam::Vec2;
+use std::time::Duration;
+
+/// Longest gap between presses that still counts as the same click series.
+pub const MULTI_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// Furthest, in logical pixels, the pointer may move between presses of a
+/// click series.
+pub const MULTI_CLICK_DISTANCE: f32 = 4.0;
+
+/// Counts consecutive presses to tell single, double and triple clicks apart.
+///
+/// A press continues the series when it uses the same button, comes within
+/// [`MULTI_CLICK_TIME`] of the previous one and lands within
+/// [`MULTI_CLICK_DISTANCE`] of it.
+#[derive(Clone, Debug, Default)]
+pub struct ClickCounter {
+    last: Option<LastPress>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LastPress {
+    pos: Vec2,
+    button: PointerButton,
+    time: Duration,
+    count: u8,
+}
+
+impl ClickCounter {
+    /// Create a counter with no clicks recorded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a press and return its position in the series, starting at 1.
+    ///
+    /// `time` is any monotonic timestamp, such as time since app start.
+    pub fn press(&mut self, pos: Vec2, button: PointerButton, time: Duration) -> u8 {
+        let count = match self.last {
+            Some(last)
+                if last.button == button
+                    && time.saturating_sub(last.time) <= MULTI_CLICK_TIME
+                    && last.pos.distance(pos) <= MULTI_CLICK_DISTANCE =>
+            {
+                last.count.saturating_add(1)
+            }
+            _ => 1,
+        };
+        self.last = Some(LastPress {
+            pos,
+            button,
+            time,
+            count,
+        });
+        count
+    }
+
+    /// Forget the current series, so the next press is a single click.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click_series() {
+        let mut clicks = ClickCounter::new();
+        let ms = Duration::from_millis;
+        let primary = PointerButton::Primary;
+        assert_eq!(clicks.press(Vec2::ZERO, primary, ms(0)), 1);
+        assert_eq!(clicks.press(Vec2::new(2.0, 2.0), primary, ms(300)), 2);
+        assert_eq!(clicks.press(Vec2::new(2.0, 2.0), primary, ms(600)), 3);
+        // Too slow
+        assert_eq!(clicks.press(Vec2::new(2.0, 2.0), primary, ms(1100)), 1);
+        // Too far
+        assert_eq!(clicks.press(Vec2::new(10.0, 2.0), primary, ms(1200)), 1);
+        // Another button
+        let secondary = PointerButton::Secondary;
+        assert_eq!(clicks.press(Vec2::new(10.0, 2.0), secondary, ms(1300)), 1);
+    }
+}
@@ -4,37 +4,265 @@
 //! W3C-compliant UI event types with winit integration.
 
 pub use ui_events::{
-    keyboard::{CompositionEvent, Key, KeyState, KeyboardEvent, Modifiers, NamedKey},
+    keyboard::{
+        CompositionEvent, CompositionState, Key, KeyState, KeyboardEvent, Modifiers, NamedKey,
+    },
     pointer::{PointerButton, PointerId, PointerState, PointerType},
-    ScrollDelta,
 };
 
 use glam::Vec2;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Logical pixels per line of a wheel scroll, for widgets without a better
+/// measure such as a row height.
+pub const SCROLL_LINE_HEIGHT: f32 = 20.0;
 
 /// Wrapper for common input events used in the widget system.
+///
+/// Pointer and scroll events carry the modifier keys held when they happened,
+/// e.g. Ctrl or Shift for extending a selection.
 #[derive(Clone, Debug)]
 pub enum InputEvent {
     /// Pointer (mouse/touch/pen) moved.
-    PointerMove { pos: Vec2 },
+    PointerMove {
+        pos: Vec2,
+        pointer: Pointer,
+        modifiers: Modifiers,
+    },
     /// Pointer button pressed.
-    PointerDown { pos: Vec2, button: PointerButton },
-    /// Pointer button released.
-    PointerUp { pos: Vec2, button: PointerButton },
-    /// Scroll wheel event.
-    Scroll { pos: Vec2, delta: Vec2 },
+    ///
+    /// `click_count` is 2 for a double click, 3 for a triple click, and so on.
+    /// `time` is the time since the app started. The app fills in both when
+    /// dispatching.
+    ///
+    /// A finger touching down presses [`PointerButton::Primary`].
+    PointerDown {
+        pos: Vec2,
+        button: PointerButton,
+        pointer: Pointer,
+        modifiers: Modifiers,
+        click_count: u8,
+        time: Duration,
+    },
+    /// Pointer button released, `time` after the app started.
+    PointerUp {
+        pos: Vec2,
+        button: PointerButton,
+        pointer: Pointer,
+        modifiers: Modifiers,
+        time: Duration,
+    },
+    /// The pointer's press ended without a release, e.g. the window lost
+    /// focus mid-drag or the system took over a touch.
+    ///
+    /// Widgets drop their pressed or dragging state without acting on it.
+    PointerCancel { pointer: Pointer },
+    /// Scroll wheel, trackpad or touch pan. Positive `delta` moves the
+    /// content down and right, revealing what's above and to the left.
+    Scroll {
+        pos: Vec2,
+        delta: ScrollDelta,
+        modifiers: Modifiers,
+    },
+    /// The pointer moved onto the widget or one of its descendants.
+    ///
+    /// Sent only to the widgets whose hover state changed, outermost first.
+    PointerEnter { pos: Vec2 },
+    /// The pointer moved off the widget and its descendants, or out of the
+    /// window. Sent innermost first.
+    PointerLeave,
     /// Key pressed.
     KeyDown { event: KeyboardEvent },
     /// Key released.
     KeyUp { event: KeyboardEvent },
     /// Text input (after IME processing).
     TextInput { text: String },
+    /// Text pasted from the clipboard.
+    Paste { text: String },
+    /// Files dragged over the window moved; their contents arrive with
+    /// the drop.
+    FileHover { pos: Vec2 },
+    /// Files dropped on the window.
+    FileDrop { pos: Vec2, files: Vec<DroppedFile> },
+    /// Files dragged over the window left it without being dropped.
+    FileHoverCancelled,
+    /// IME composition started, changed or ended.
+    ///
+    /// Composed text is only inserted when the composition ends.
+    Composition { event: CompositionEvent },
     /// Focus gained.
     FocusGained,
     /// Focus lost.
     FocusLost,
 }
 
+/// The mouse, finger or pen a pointer event comes from.
+///
+/// Each finger on a touch screen is its own pointer, with an id that stays
+/// the same from touching down to lifting off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pointer {
+    pub id: PointerId,
+    pub kind: PointerType,
+    /// The mouse, or the first finger of a multi-touch gesture.
+    pub primary: bool,
+}
+
+impl Pointer {
+    /// The mouse.
+    pub const MOUSE: Self = Self {
+        id: PointerId::PRIMARY,
+        kind: PointerType::Mouse,
+        primary: true,
+    };
+
+    /// The finger the platform numbers `finger`.
+    pub fn touch(finger: u64, primary: bool) -> Self {
+        Self {
+            // The mouse keeps the primary id
+            id: PointerId::new(finger.saturating_add(2)).unwrap_or(PointerId::PRIMARY),
+            kind: PointerType::Touch,
+            primary,
+        }
+    }
+
+    /// A pen or other stylus.
+    pub fn pen() -> Self {
+        Self {
+            kind: PointerType::Pen,
+            ..Self::MOUSE
+        }
+    }
+
+    /// Check if this is a finger on a touch screen.
+    pub fn is_touch(&self) -> bool {
+        self.kind == PointerType::Touch
+    }
+}
+
+impl Default for Pointer {
+    fn default() -> Self {
+        Self::MOUSE
+    }
+}
+
+/// How far a scroll moves, in the units the device reports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollDelta {
+    /// Lines, from a notched mouse wheel. Widgets pick how tall a line is.
+    Lines(Vec2),
+    /// Logical pixels, from a trackpad or a panning finger.
+    Pixels(Vec2),
+}
+
+impl ScrollDelta {
+    /// The distance in logical pixels, with lines `line_height` tall.
+    pub fn to_pixels(self, line_height: f32) -> Vec2 {
+        match self {
+            ScrollDelta::Lines(lines) => lines * line_height,
+            ScrollDelta::Pixels(pixels) => pixels,
+        }
+    }
+
+    /// The same delta with both axes multiplied by `factor`.
+    pub fn scaled(self, factor: f32) -> Self {
+        match self {
+            ScrollDelta::Lines(lines) => ScrollDelta::Lines(lines * factor),
+            ScrollDelta::Pixels(pixels) => ScrollDelta::Pixels(pixels * factor),
+        }
+    }
+}
+
 impl InputEvent {
+    /// A move of the mouse, for platform code; see [`Self::with_pointer`]
+    /// for other pointers.
+    pub fn pointer_move(pos: Vec2) -> Self {
+        InputEvent::PointerMove {
+            pos,
+            pointer: Pointer::MOUSE,
+            modifiers: Modifiers::empty(),
+        }
+    }
+
+    /// A single-click press of the mouse, for platform code; the app stamps
+    /// the real click count and time.
+    pub fn pointer_down(pos: Vec2, button: PointerButton) -> Self {
+        InputEvent::PointerDown {
+            pos,
+            button,
+            pointer: Pointer::MOUSE,
+            modifiers: Modifiers::empty(),
+            click_count: 1,
+            time: Duration::ZERO,
+        }
+    }
+
+    /// A release of the mouse, for platform code; the app stamps the real
+    /// time.
+    pub fn pointer_up(pos: Vec2, button: PointerButton) -> Self {
+        InputEvent::PointerUp {
+            pos,
+            button,
+            pointer: Pointer::MOUSE,
+            modifiers: Modifiers::empty(),
+            time: Duration::ZERO,
+        }
+    }
+
+    /// A cancelled press of the mouse, for platform code.
+    pub fn pointer_cancel() -> Self {
+        InputEvent::PointerCancel {
+            pointer: Pointer::MOUSE,
+        }
+    }
+
+    /// A scroll by `delta`, for platform code.
+    pub fn scroll(pos: Vec2, delta: ScrollDelta) -> Self {
+        InputEvent::Scroll {
+            pos,
+            delta,
+            modifiers: Modifiers::empty(),
+        }
+    }
+
+    /// The same event from `pointer`, if it's a pointer move, press,
+    /// release or cancel.
+    pub fn with_pointer(mut self, pointer: Pointer) -> Self {
+        match &mut self {
+            InputEvent::PointerMove { pointer: from, .. }
+            | InputEvent::PointerDown { pointer: from, .. }
+            | InputEvent::PointerUp { pointer: from, .. }
+            | InputEvent::PointerCancel { pointer: from } => *from = pointer,
+            _ => {}
+        }
+        self
+    }
+
+    /// The same event with the `held` modifiers, if it's a pointer or scroll
+    /// event.
+    pub fn with_modifiers(mut self, held: Modifiers) -> Self {
+        match &mut self {
+            InputEvent::PointerMove { modifiers, .. }
+            | InputEvent::PointerDown { modifiers, .. }
+            | InputEvent::PointerUp { modifiers, .. }
+            | InputEvent::Scroll { modifiers, .. } => *modifiers = held,
+            _ => {}
+        }
+        self
+    }
+
+    /// The pointer a move, press, release or cancel comes from.
+    pub fn pointer(&self) -> Option<Pointer> {
+        match self {
+            InputEvent::PointerMove { pointer, .. }
+            | InputEvent::PointerDown { pointer, .. }
+            | InputEvent::PointerUp { pointer, .. }
+            | InputEvent::PointerCancel { pointer } => Some(*pointer),
+            _ => None,
+        }
+    }
+
     /// Get the position if this is a pointer event.
     pub fn pos(&self) -> Option<Vec2> {
         match self {
@@ -42,10 +270,30 @@ impl InputEvent {
             InputEvent::PointerDown { pos, .. } => Some(*pos),
             InputEvent::PointerUp { pos, .. } => Some(*pos),
             InputEvent::Scroll { pos, .. } => Some(*pos),
+            InputEvent::PointerEnter { pos } => Some(*pos),
+            InputEvent::FileHover { pos } => Some(*pos),
+            InputEvent::FileDrop { pos, .. } => Some(*pos),
             _ => None,
         }
     }
 
+    /// The same event with its position moved by `offset`, e.g. into the
+    /// content of a scrolled container.
+    pub fn translated(&self, offset: Vec2) -> Self {
+        let mut event = self.clone();
+        match &mut event {
+            InputEvent::PointerMove { pos, .. }
+            | InputEvent::PointerDown { pos, .. }
+            | InputEvent::PointerUp { pos, .. }
+            | InputEvent::Scroll { pos, .. }
+            | InputEvent::PointerEnter { pos }
+            | InputEvent::FileHover { pos }
+            | InputEvent::FileDrop { pos, .. } => *pos += offset,
+            _ => {}
+        }
+        event
+    }
+
     /// Check if this is a key event.
     pub fn is_key_event(&self) -> bool {
         matches!(self, InputEvent::KeyDown { .. } | InputEvent::KeyUp { .. })
@@ -72,54 +320,103 @@ impl InputEvent {
         matches!(self, InputEvent::PointerDown { button: PointerButton::Secondary, .. })
     }
 
-    /// Get modifiers from keyboard events.
+    /// Get modifiers from keyboard, pointer and scroll events.
     pub fn modifiers(&self) -> Option<Modifiers> {
         match self {
             InputEvent::KeyDown { event } | InputEvent::KeyUp { event } => {
                 Some(event.modifiers)
             }
+            InputEvent::PointerMove { modifiers, .. }
+            | InputEvent::PointerDown { modifiers, .. }
+            | InputEvent::PointerUp { modifiers, .. }
+            | InputEvent::Scroll { modifiers, .. } => Some(*modifiers),
             _ => None,
         }
     }
 }
 
+/// A file dropped on the window or picked in a file dialog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DroppedFile {
+    /// File name without its directory.
+    pub name: String,
+    /// MIME type, when the platform reports one.
+    pub mime: Option<String>,
+    pub contents: FileContents,
+}
+
+/// Where a [`DroppedFile`]'s data is.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileContents {
+    /// A file on disk (desktop platforms).
+    Path(PathBuf),
+    /// The data itself (the web, which doesn't expose paths).
+    Bytes(Vec<u8>),
+}
+
+impl DroppedFile {
+    /// Describe a file on disk.
+    pub fn from_path(path: PathBuf) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            name,
+            mime: None,
+            contents: FileContents::Path(path),
+        }
+    }
+
+    /// Read the file's data, wherever it is.
+    pub fn read(&self) -> std::io::Result<Vec<u8>> {
+        match &self.contents {
+            FileContents::Path(path) => std::fs::read(path),
+            FileContents::Bytes(bytes) => Ok(bytes.clone()),
+        }
+    }
+}
+
 /// Helper for checking common key combinations.
 pub mod shortcuts {
     use super::*;
+    use crate::{ModifiersExt, Platform};
 
     fn is_char(event: &KeyboardEvent, ch: char) -> bool {
         matches!(&event.key, Key::Character(s) if s.chars().next() == Some(ch.to_ascii_lowercase()) || s.chars().next() == Some(ch.to_ascii_uppercase()))
     }
 
-    /// Check if this is Ctrl+C (copy).
+    /// Check if this is Cmd+C on macOS or Ctrl+C elsewhere (copy).
     pub fn is_copy(event: &KeyboardEvent) -> bool {
-        event.modifiers.ctrl() && is_char(event, 'c')
+        event.modifiers.primary() && is_char(event, 'c')
     }
 
-    /// Check if this is Ctrl+V (paste).
+    /// Check if this is Cmd+V on macOS or Ctrl+V elsewhere (paste).
     pub fn is_paste(event: &KeyboardEvent) -> bool {
-        event.modifiers.ctrl() && is_char(event, 'v')
+        event.modifiers.primary() && is_char(event, 'v')
     }
 
-    /// Check if this is Ctrl+X (cut).
+    /// Check if this is Cmd+X on macOS or Ctrl+X elsewhere (cut).
     pub fn is_cut(event: &KeyboardEvent) -> bool {
-        event.modifiers.ctrl() && is_char(event, 'x')
+        event.modifiers.primary() && is_char(event, 'x')
     }
 
-    /// Check if this is Ctrl+A (select all).
+    /// Check if this is Cmd+A on macOS or Ctrl+A elsewhere (select all).
     pub fn is_select_all(event: &KeyboardEvent) -> bool {
-        event.modifiers.ctrl() && is_char(event, 'a')
+        event.modifiers.primary() && is_char(event, 'a')
     }
 
-    /// Check if this is Ctrl+Z (undo).
+    /// Check if this is Cmd+Z on macOS or Ctrl+Z elsewhere (undo).
     pub fn is_undo(event: &KeyboardEvent) -> bool {
-        event.modifiers.ctrl() && !event.modifiers.shift() && is_char(event, 'z')
+        event.modifiers.primary() && !event.modifiers.shift() && is_char(event, 'z')
     }
 
-    /// Check if this is Ctrl+Shift+Z or Ctrl+Y (redo).
+    /// Check if this is Cmd+Shift+Z on macOS, or Ctrl+Shift+Z or Ctrl+Y
+    /// elsewhere (redo).
     pub fn is_redo(event: &KeyboardEvent) -> bool {
-        (event.modifiers.ctrl() && event.modifiers.shift() && is_char(event, 'z'))
-            || (event.modifiers.ctrl() && is_char(event, 'y'))
+        let primary = event.modifiers.primary();
+        (primary && event.modifiers.shift() && is_char(event, 'z'))
+            || (primary && Platform::current() == Platform::Other && is_char(event, 'y'))
     }
     
     /// Check if this is the Escape key.
@@ -1,6 +1,8 @@
 //! Focus management for widgets.
 
+use spark_core::Rect;
 use spark_layout::WidgetId;
+use std::cell::Cell;
 
 /// Manages keyboard focus for widgets.
 #[derive(Debug, Default)]
@@ -9,6 +11,11 @@ pub struct FocusManager {
     focused: Option<WidgetId>,
     /// Stack of widgets that can receive focus (in tab order).
     focusable: Vec<WidgetId>,
+    /// Whether the focus was acquired via keyboard or assistive technology.
+    focus_visible: bool,
+    /// Caret rectangle of the focused text widget, in logical window coordinates.
+    /// Reported during paint so the platform IME can position its candidate window.
+    caret_rect: Cell<Option<Rect>>,
 }
 
 impl FocusManager {
@@ -35,6 +42,20 @@ impl FocusManager {
     /// Clear focus (no widget is focused).
     pub fn clear_focus(&mut self) {
         self.focused = None;
+        self.focus_visible = false;
+    }
+
+    /// Whether the focused widget should draw a focus ring.
+    ///
+    /// Set when focus moves via keyboard or assistive technology, cleared
+    /// when it moves via pointer.
+    pub fn focus_visible(&self) -> bool {
+        self.focused.is_some() && self.focus_visible
+    }
+
+    /// Record how the current focus was acquired.
+    pub fn set_focus_visible(&mut self, visible: bool) {
+        self.focus_visible = visible;
     }
 
     /// Register a widget as focusable (in tab order).
@@ -75,6 +96,7 @@ impl FocusManager {
         };
 
         self.focused = self.focusable.get(next_idx).copied();
+        self.focus_visible = true;
     }
 
     /// Move focus to the previous focusable widget (Shift+Tab).
@@ -101,6 +123,20 @@ impl FocusManager {
         };
 
         self.focused = self.focusable.get(prev_idx).copied();
+        self.focus_visible = true;
+    }
+
+    /// Report the caret rectangle of the focused text-editing widget.
+    ///
+    /// Called by text widgets while painting; the rect is in logical window
+    /// coordinates. A reported caret also tells the app that IME input is wanted.
+    pub fn report_caret_rect(&self, rect: Rect) {
+        self.caret_rect.set(Some(rect));
+    }
+
+    /// Take the caret rectangle reported since the last call.
+    pub fn take_caret_rect(&self) -> Option<Rect> {
+        self.caret_rect.take()
     }
 
     /// Get the number of focusable widgets.
@@ -4,9 +4,12 @@
 //! ecosystem for W3C-compliant UI event types.
 
 mod action;
+mod click;
 mod events;
 mod focus;
 mod hit_test;
+mod shortcut;
+mod touch;
 
 // Re-export ui-events types
 pub use ui_events;
@@ -17,11 +20,16 @@ pub use action::{
     Action, ActionContext, ActionHandler, ActionMapper, CustomAction, StandardAction,
 };
 
+pub use click::{ClickCounter, MULTI_CLICK_DISTANCE, MULTI_CLICK_TIME};
+
 // Our wrapper types
 pub use events::{
-    shortcuts, CompositionEvent, InputEvent, Key, KeyState, KeyboardEvent, Modifiers,
-    NamedKey, PointerButton, PointerId, PointerState, PointerType, ScrollDelta,
+    shortcuts, CompositionEvent, CompositionState, DroppedFile, FileContents, InputEvent, Key,
+    KeyState, KeyboardEvent, Modifiers, NamedKey, Pointer, PointerButton, PointerId, PointerState,
+    PointerType, ScrollDelta, SCROLL_LINE_HEIGHT,
 };
 pub use focus::FocusManager;
 pub use hit_test::{hit_test, hit_test_all, hit_test_filtered, HitTestResult};
+pub use shortcut::{ModifiersExt, Platform, Shortcut, ShortcutMap, ShortcutParseError};
+pub use touch::{TouchPan, TOUCH_SLOP};
 
@@ -0,0 +1,453 @@
+//! Keyboard shortcuts with platform-aware modifiers and display.
+//!
+//! Shortcuts are written as `"Mod+S"`, where `Mod` is Cmd on macOS and Ctrl
+//! everywhere else, so the same binding follows each platform's convention.
+//! A [`ShortcutMap`] binds shortcuts to actions for a whole app.
+
+use crate::{Action, Key, KeyboardEvent, Modifiers, NamedKey};
+use std::fmt;
+
+/// Platform whose shortcut conventions apply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Platform {
+    /// macOS and iOS: Cmd is the primary modifier, symbols for display.
+    Apple,
+    /// Windows, Linux, and the web: Ctrl is the primary modifier.
+    Other,
+}
+
+impl Platform {
+    /// The platform this binary was compiled for.
+    pub const fn current() -> Self {
+        if cfg!(any(target_os = "macos", target_os = "ios")) {
+            Platform::Apple
+        } else {
+            Platform::Other
+        }
+    }
+
+    /// The modifier that `Mod` resolves to on this platform.
+    pub fn primary_modifier(self) -> Modifiers {
+        match self {
+            Platform::Apple => Modifiers::META,
+            Platform::Other => Modifiers::CONTROL,
+        }
+    }
+}
+
+/// Platform-aware checks on held [`Modifiers`].
+pub trait ModifiersExt {
+    /// Check if the primary modifier is held: Cmd on macOS, Ctrl elsewhere.
+    fn primary(&self) -> bool {
+        self.primary_for(Platform::current())
+    }
+
+    /// Check if the primary modifier of `platform` is held.
+    fn primary_for(&self, platform: Platform) -> bool;
+}
+
+impl ModifiersExt for Modifiers {
+    fn primary_for(&self, platform: Platform) -> bool {
+        self.contains(platform.primary_modifier())
+    }
+}
+
+/// Error returned when a shortcut string cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortcutParseError(pub String);
+
+impl fmt::Display for ShortcutParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid shortcut: {}", self.0)
+    }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+/// A key combined with modifiers, e.g. Cmd+S.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// Required modifiers (only Ctrl, Alt, Shift and Meta are compared).
+    pub modifiers: Modifiers,
+    /// The key; characters are stored lowercase.
+    pub key: Key,
+    /// Platform used for display.
+    pub platform: Platform,
+}
+
+/// Modifiers that participate in shortcut matching.
+fn relevant(modifiers: Modifiers) -> Modifiers {
+    modifiers & (Modifiers::CONTROL | Modifiers::ALT | Modifiers::SHIFT | Modifiers::META)
+}
+
+impl Shortcut {
+    /// Parse a shortcut like `"Mod+Shift+Z"` for the current platform.
+    ///
+    /// Panics on invalid input; use [`Shortcut::parse`] for user-provided strings.
+    pub fn new(spec: &str) -> Self {
+        Self::parse(spec).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Parse a shortcut for the current platform.
+    pub fn parse(spec: &str) -> Result<Self, ShortcutParseError> {
+        Self::parse_for(spec, Platform::current())
+    }
+
+    /// Parse a shortcut, resolving `Mod` for the given platform.
+    pub fn parse_for(spec: &str, platform: Platform) -> Result<Self, ShortcutParseError> {
+        let err = || ShortcutParseError(spec.to_string());
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err(err());
+        }
+
+        // A trailing "++" means the key itself is '+'.
+        let (mods_part, key_part) = if let Some(rest) = spec.strip_suffix("++") {
+            (rest, "+")
+        } else if spec == "+" {
+            ("", "+")
+        } else {
+            match spec.rsplit_once('+') {
+                Some((mods, key)) => (mods, key),
+                None => ("", spec),
+            }
+        };
+
+        let mut modifiers = Modifiers::empty();
+        for part in mods_part.split('+').filter(|p| !p.is_empty()) {
+            let modifier = match part.trim().to_ascii_lowercase().as_str() {
+                "mod" | "primary" => platform.primary_modifier(),
+                "cmd" | "command" | "meta" | "super" | "win" => Modifiers::META,
+                "ctrl" | "control" => Modifiers::CONTROL,
+                "alt" | "option" | "opt" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                _ => return Err(err()),
+            };
+            modifiers |= modifier;
+        }
+
+        let key = parse_key(key_part.trim()).ok_or_else(err)?;
+        Ok(Self {
+            modifiers,
+            key,
+            platform,
+        })
+    }
+
+    /// Create a shortcut from explicit modifiers and key.
+    pub fn from_parts(modifiers: Modifiers, key: Key) -> Self {
+        let key = match key {
+            Key::Character(s) => Key::Character(s.to_lowercase()),
+            other => other,
+        };
+        Self {
+            modifiers: relevant(modifiers),
+            key,
+            platform: Platform::current(),
+        }
+    }
+
+    /// Check whether a keyboard event triggers this shortcut.
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        if relevant(event.modifiers) != relevant(self.modifiers) {
+            return false;
+        }
+        match (&self.key, &event.key) {
+            (Key::Character(a), Key::Character(b)) => a.eq_ignore_ascii_case(b),
+            (a, b) => a == b,
+        }
+    }
+
+    /// Format the shortcut for display on the given platform.
+    pub fn display_for(&self, platform: Platform) -> String {
+        let m = self.modifiers;
+        match platform {
+            Platform::Apple => {
+                let mut out = String::new();
+                if m.ctrl() {
+                    out.push('⌃');
+                }
+                if m.alt() {
+                    out.push('⌥');
+                }
+                if m.shift() {
+                    out.push('⇧');
+                }
+                if m.meta() {
+                    out.push('⌘');
+                }
+                out.push_str(&key_label(&self.key, platform));
+                out
+            }
+            Platform::Other => {
+                let mut parts: Vec<String> = Vec::new();
+                if m.ctrl() {
+                    parts.push("Ctrl".into());
+                }
+                if m.alt() {
+                    parts.push("Alt".into());
+                }
+                if m.shift() {
+                    parts.push("Shift".into());
+                }
+                if m.meta() {
+                    parts.push("Super".into());
+                }
+                parts.push(key_label(&self.key, platform));
+                parts.join("+")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display_for(self.platform))
+    }
+}
+
+/// App-wide shortcuts, checked before a key press reaches any widget.
+///
+/// Bindings take precedence over the defaults of [`ActionMapper`](crate::ActionMapper)
+/// and widgets' own keys; a disabled shortcut does nothing at all. Shortcuts
+/// usually run an [`Action`], but can be bound to anything else that should
+/// take a key press first, such as the widget that registered it.
+#[derive(Clone, Debug)]
+pub struct ShortcutMap<A = Action> {
+    bindings: Vec<(Shortcut, A)>,
+    disabled: Vec<Shortcut>,
+}
+
+impl<A> Default for ShortcutMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Vec::new(),
+            disabled: Vec::new(),
+        }
+    }
+}
+
+impl ShortcutMap {
+    /// Create a map of actions with no bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<A> ShortcutMap<A> {
+    /// Bind a shortcut to an action, replacing any earlier binding for it.
+    pub fn bind(&mut self, shortcut: Shortcut, action: impl Into<A>) {
+        self.unbind(&shortcut);
+        self.bindings.push((shortcut, action.into()));
+    }
+
+    /// Remove a binding or re-enable a disabled shortcut, restoring its default.
+    pub fn unbind(&mut self, shortcut: &Shortcut) {
+        self.bindings
+            .retain(|(bound, _)| !same_keys(bound, shortcut));
+        self.disabled
+            .retain(|disabled| !same_keys(disabled, shortcut));
+    }
+
+    /// Make a shortcut do nothing, even where widgets handle it by default.
+    pub fn disable(&mut self, shortcut: Shortcut) {
+        self.unbind(&shortcut);
+        self.disabled.push(shortcut);
+    }
+
+    /// Remove every binding and disabled shortcut.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.disabled.clear();
+    }
+
+    /// Get the action bound to a key press.
+    pub fn action_for(&self, event: &KeyboardEvent) -> Option<&A> {
+        self.bindings
+            .iter()
+            .find(|(shortcut, _)| shortcut.matches(event))
+            .map(|(_, action)| action)
+    }
+
+    /// Check if a key press is a disabled shortcut.
+    pub fn is_disabled(&self, event: &KeyboardEvent) -> bool {
+        self.disabled.iter().any(|shortcut| shortcut.matches(event))
+    }
+}
+
+/// Check if two shortcuts are pressed the same way, whatever their display platform.
+fn same_keys(a: &Shortcut, b: &Shortcut) -> bool {
+    relevant(a.modifiers) == relevant(b.modifiers) && a.key == b.key
+}
+
+/// Parse the key portion of a shortcut string.
+fn parse_key(s: &str) -> Option<Key> {
+    if s.chars().count() == 1 {
+        return Some(Key::Character(s.to_lowercase()));
+    }
+    let named = match s.to_ascii_lowercase().as_str() {
+        "enter" | "return" => NamedKey::Enter,
+        "tab" => NamedKey::Tab,
+        "esc" | "escape" => NamedKey::Escape,
+        "backspace" => NamedKey::Backspace,
+        "delete" | "del" => NamedKey::Delete,
+        "up" | "arrowup" => NamedKey::ArrowUp,
+        "down" | "arrowdown" => NamedKey::ArrowDown,
+        "left" | "arrowleft" => NamedKey::ArrowLeft,
+        "right" | "arrowright" => NamedKey::ArrowRight,
+        "home" => NamedKey::Home,
+        "end" => NamedKey::End,
+        "pageup" => NamedKey::PageUp,
+        "pagedown" => NamedKey::PageDown,
+        "space" => return Some(Key::Character(" ".into())),
+        "f1" => NamedKey::F1,
+        "f2" => NamedKey::F2,
+        "f3" => NamedKey::F3,
+        "f4" => NamedKey::F4,
+        "f5" => NamedKey::F5,
+        "f6" => NamedKey::F6,
+        "f7" => NamedKey::F7,
+        "f8" => NamedKey::F8,
+        "f9" => NamedKey::F9,
+        "f10" => NamedKey::F10,
+        "f11" => NamedKey::F11,
+        "f12" => NamedKey::F12,
+        _ => return None,
+    };
+    Some(Key::Named(named))
+}
+
+/// Human-readable label for a key.
+fn key_label(key: &Key, platform: Platform) -> String {
+    let apple = platform == Platform::Apple;
+    match key {
+        Key::Character(s) if s == " " => "Space".into(),
+        Key::Character(s) => s.to_uppercase(),
+        Key::Named(named) => {
+            let label = match (named, apple) {
+                (NamedKey::Enter, true) => "↩",
+                (NamedKey::Enter, false) => "Enter",
+                (NamedKey::Tab, true) => "⇥",
+                (NamedKey::Tab, false) => "Tab",
+                (NamedKey::Escape, true) => "⎋",
+                (NamedKey::Escape, false) => "Esc",
+                (NamedKey::Backspace, true) => "⌫",
+                (NamedKey::Backspace, false) => "Backspace",
+                (NamedKey::Delete, true) => "⌦",
+                (NamedKey::Delete, false) => "Del",
+                (NamedKey::ArrowUp, true) => "↑",
+                (NamedKey::ArrowUp, false) => "Up",
+                (NamedKey::ArrowDown, true) => "↓",
+                (NamedKey::ArrowDown, false) => "Down",
+                (NamedKey::ArrowLeft, true) => "←",
+                (NamedKey::ArrowLeft, false) => "Left",
+                (NamedKey::ArrowRight, true) => "→",
+                (NamedKey::ArrowRight, false) => "Right",
+                (NamedKey::Home, true) => "↖",
+                (NamedKey::Home, false) => "Home",
+                (NamedKey::End, true) => "↘",
+                (NamedKey::End, false) => "End",
+                (NamedKey::PageUp, true) => "⇞",
+                (NamedKey::PageUp, false) => "PgUp",
+                (NamedKey::PageDown, true) => "⇟",
+                (NamedKey::PageDown, false) => "PgDn",
+                (other, _) => return format!("{other:?}"),
+            };
+            label.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_event(key: Key, modifiers: Modifiers) -> KeyboardEvent {
+        KeyboardEvent {
+            key,
+            modifiers,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_mod_resolves_per_platform() {
+        let mac = Shortcut::parse_for("Mod+S", Platform::Apple).unwrap();
+        assert_eq!(mac.modifiers, Modifiers::META);
+        assert_eq!(mac.key, Key::Character("s".into()));
+
+        let win = Shortcut::parse_for("Mod+S", Platform::Other).unwrap();
+        assert_eq!(win.modifiers, Modifiers::CONTROL);
+    }
+
+    #[test]
+    fn test_primary_modifier_per_platform() {
+        assert!(Modifiers::META.primary_for(Platform::Apple));
+        assert!(!Modifiers::CONTROL.primary_for(Platform::Apple));
+        assert!(Modifiers::CONTROL.primary_for(Platform::Other));
+        assert!(!Modifiers::META.primary_for(Platform::Other));
+    }
+
+    #[test]
+    fn test_parse_named_and_plus_keys() {
+        let s = Shortcut::parse_for("Ctrl+Shift+Enter", Platform::Other).unwrap();
+        assert_eq!(s.modifiers, Modifiers::CONTROL | Modifiers::SHIFT);
+        assert_eq!(s.key, Key::Named(NamedKey::Enter));
+
+        let plus = Shortcut::parse_for("Mod++", Platform::Other).unwrap();
+        assert_eq!(plus.key, Key::Character("+".into()));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_tokens() {
+        assert!(Shortcut::parse_for("Hyper+S", Platform::Other).is_err());
+        assert!(Shortcut::parse_for("Ctrl+Banana", Platform::Other).is_err());
+        assert!(Shortcut::parse_for("", Platform::Other).is_err());
+    }
+
+    #[test]
+    fn test_matches_ignores_character_case() {
+        let s = Shortcut::parse_for("Mod+Shift+Z", Platform::Other).unwrap();
+        let event = key_event(
+            Key::Character("Z".into()),
+            Modifiers::CONTROL | Modifiers::SHIFT,
+        );
+        assert!(s.matches(&event));
+
+        let without_shift = key_event(Key::Character("z".into()), Modifiers::CONTROL);
+        assert!(!s.matches(&without_shift));
+    }
+
+    #[test]
+    fn test_map_bindings_replace_and_disable() {
+        let palette = Shortcut::parse_for("Mod+K", Platform::Other).unwrap();
+        let press = key_event(Key::Character("k".into()), Modifiers::CONTROL);
+        let mut map = ShortcutMap::new();
+        map.bind(palette.clone(), "palette");
+        map.bind(palette.clone(), "search");
+        assert_eq!(map.action_for(&press), Some(&Action::from("search")));
+
+        map.disable(palette.clone());
+        assert_eq!(map.action_for(&press), None);
+        assert!(map.is_disabled(&press));
+
+        map.unbind(&palette);
+        assert!(!map.is_disabled(&press));
+    }
+
+    #[test]
+    fn test_display_macos() {
+        let s = Shortcut::parse_for("Mod+Shift+S", Platform::Apple).unwrap();
+        assert_eq!(s.display_for(Platform::Apple), "⇧⌘S");
+        let esc = Shortcut::parse_for("Mod+Escape", Platform::Apple).unwrap();
+        assert_eq!(esc.to_string(), "⌘⎋");
+    }
+
+    #[test]
+    fn test_display_windows() {
+        let s = Shortcut::parse_for("Mod+S", Platform::Other).unwrap();
+        assert_eq!(s.to_string(), "Ctrl+S");
+        let s = Shortcut::parse_for("Ctrl+Alt+Delete", Platform::Other).unwrap();
+        assert_eq!(s.to_string(), "Ctrl+Alt+Del");
+    }
+}
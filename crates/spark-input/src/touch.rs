@@ -0,0 +1,66 @@
+//! Panning scrollable content with a finger.
+
+use crate::{InputEvent, ScrollDelta};
+use glam::Vec2;
+
+/// Logical distance a touch moves before it pans instead of pressing.
+pub const TOUCH_SLOP: f32 = 8.0;
+
+/// Turns a finger dragged over scrollable content into scroll events.
+///
+/// The finger presses as usual until it moves past [`TOUCH_SLOP`]; from then
+/// on it pans, and the content under where it started follows it.
+#[derive(Clone, Copy, Debug)]
+pub struct TouchPan {
+    start: Vec2,
+    last: Vec2,
+    panning: bool,
+}
+
+impl TouchPan {
+    /// Start following a finger that touched down at `pos`.
+    pub fn new(pos: Vec2) -> Self {
+        Self {
+            start: pos,
+            last: pos,
+            panning: false,
+        }
+    }
+
+    /// Follow the finger to `pos`, returning the scroll to deliver once it
+    /// pans.
+    pub fn move_to(&mut self, pos: Vec2) -> Option<InputEvent> {
+        if !self.panning && pos.distance(self.start) > TOUCH_SLOP {
+            self.panning = true;
+        }
+        let delta = pos - self.last;
+        self.last = pos;
+        self.panning
+            .then(|| InputEvent::scroll(self.start, ScrollDelta::Pixels(delta)))
+    }
+
+    /// Check if the finger moved far enough to pan, so lifting it isn't a tap.
+    pub fn is_panning(&self) -> bool {
+        self.panning
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pan_starts_past_the_slop() {
+        let mut pan = TouchPan::new(Vec2::new(50.0, 50.0));
+        assert!(pan.move_to(Vec2::new(50.0, 46.0)).is_none());
+        assert!(!pan.is_panning());
+
+        let Some(InputEvent::Scroll { pos, delta, .. }) = pan.move_to(Vec2::new(50.0, 30.0)) else {
+            panic!("expected a scroll");
+        };
+        assert!(pan.is_panning());
+        assert_eq!(pos, Vec2::new(50.0, 50.0));
+        // Dragging up scrolls down
+        assert_eq!(delta, ScrollDelta::Pixels(Vec2::new(0.0, -16.0)));
+    }
+}
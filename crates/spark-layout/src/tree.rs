@@ -50,9 +50,17 @@ impl NodeMapping {
     }
 }
 
+/// Intrinsic content size of a leaf, from the widget's `measure()`.
+#[derive(Clone, Copy, Debug)]
+struct MeasuredSize {
+    size: (f32, f32),
+    /// Whether narrower widths must be measured again, as for wrapping text.
+    wraps: bool,
+}
+
 /// The layout tree manages widget layout using taffy flexbox.
 pub struct LayoutTree {
-    taffy: TaffyTree<()>,
+    taffy: TaffyTree<MeasuredSize>,
     mapping: NodeMapping,
     root: Option<WidgetId>,
 }
@@ -79,6 +87,31 @@ impl LayoutTree {
         self.mapping.insert(node_id)
     }
 
+    /// Create a leaf whose content has an intrinsic size (e.g. measured text).
+    ///
+    /// The size is the content box; the style's padding and border are added
+    /// around it. Explicit sizes in the style take precedence.
+    pub fn new_measured_leaf(&mut self, style: Style, size: (f32, f32)) -> WidgetId {
+        self.new_leaf_with_size(style, MeasuredSize { size, wraps: false })
+    }
+
+    /// Create a leaf whose height depends on its width, like wrapping text.
+    ///
+    /// `size` is the content size at full width. When layout offers less,
+    /// the leaf is measured again by the callback passed to
+    /// [`compute_layout_with_wrapping`](Self::compute_layout_with_wrapping).
+    pub fn new_wrapping_leaf(&mut self, style: Style, size: (f32, f32)) -> WidgetId {
+        self.new_leaf_with_size(style, MeasuredSize { size, wraps: true })
+    }
+
+    fn new_leaf_with_size(&mut self, style: Style, size: MeasuredSize) -> WidgetId {
+        let node_id = self
+            .taffy
+            .new_leaf_with_context(style, size)
+            .expect("create measured leaf node");
+        self.mapping.insert(node_id)
+    }
+
     /// Create a new node with children.
     pub fn new_with_children(&mut self, style: Style, children: &[WidgetId]) -> WidgetId {
         let child_nodes: Vec<NodeId> = children
@@ -138,19 +171,54 @@ impl LayoutTree {
 
     /// Compute the layout for the given available space.
     pub fn compute_layout(&mut self, available_width: f32, available_height: f32) {
-        if let Some(root_id) = self.root {
-            if let Some(node_id) = self.mapping.get_node(root_id) {
-                self.taffy
-                    .compute_layout(
-                        node_id,
-                        Size {
-                            width: AvailableSpace::Definite(available_width),
-                            height: AvailableSpace::Definite(available_height),
-                        },
-                    )
-                    .ok();
-            }
-        }
+        self.compute_layout_with_wrapping(available_width, available_height, |_, _| None);
+    }
+
+    /// Compute the layout, measuring wrapping leaves again when they get
+    /// less width than their full size.
+    ///
+    /// `measure` is called with the leaf and the width on offer, and returns
+    /// the content size at that width (or `None` to keep the full size).
+    pub fn compute_layout_with_wrapping(
+        &mut self,
+        available_width: f32,
+        available_height: f32,
+        mut measure: impl FnMut(WidgetId, f32) -> Option<(f32, f32)>,
+    ) {
+        let Some(node_id) = self.root.and_then(|root_id| self.mapping.get_node(root_id)) else {
+            return;
+        };
+        let mapping = &self.mapping;
+        self.taffy
+            .compute_layout_with_measure(
+                node_id,
+                Size {
+                    width: AvailableSpace::Definite(available_width),
+                    height: AvailableSpace::Definite(available_height),
+                },
+                |known, available, node, measured, _style| {
+                    let Some(measured) = measured else {
+                        return Size::ZERO;
+                    };
+                    let offered = known.width.or(match available.width {
+                        AvailableSpace::Definite(width) => Some(width),
+                        AvailableSpace::MinContent => Some(0.0),
+                        AvailableSpace::MaxContent => None,
+                    });
+                    let (width, height) = match offered {
+                        Some(offered) if measured.wraps && offered < measured.size.0 => mapping
+                            .get_widget(node)
+                            .and_then(|id| measure(id, offered))
+                            .unwrap_or(measured.size),
+                        _ => measured.size,
+                    };
+                    Size {
+                        width: known.width.unwrap_or(width),
+                        height: known.height.unwrap_or(height),
+                    }
+                },
+            )
+            .ok();
     }
 
     /// Get the computed layout for a widget.
@@ -345,3 +413,63 @@ pub mod styles {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measured_leaf_sizes_its_parent_row() {
+        let mut tree = LayoutTree::new();
+        let padded = Style {
+            padding: taffy::Rect {
+                left: length(8.0),
+                right: length(8.0),
+                top: length(4.0),
+                bottom: length(4.0),
+            },
+            ..Default::default()
+        };
+        let leaf = tree.new_measured_leaf(padded, (60.0, 20.0));
+        let fixed = tree.new_measured_leaf(styles::fixed(10.0, 10.0), (60.0, 20.0));
+        let root = tree.new_with_children(
+            Style {
+                align_items: Some(AlignItems::FlexStart),
+                ..styles::flex_row()
+            },
+            &[leaf, fixed],
+        );
+        tree.set_root(root);
+        tree.compute_layout(400.0, 300.0);
+
+        let bounds = tree.get_layout(leaf).unwrap().bounds;
+        assert_eq!((bounds.width, bounds.height), (76.0, 28.0));
+        let bounds = tree.get_layout(fixed).unwrap().bounds;
+        assert_eq!((bounds.width, bounds.height), (10.0, 10.0));
+    }
+
+    #[test]
+    fn test_wrapping_leaf_is_measured_at_offered_width() {
+        let mut tree = LayoutTree::new();
+        let leaf = tree.new_wrapping_leaf(Style::default(), (300.0, 20.0));
+        let root = tree.new_with_children(
+            Style {
+                size: Size {
+                    width: length(100.0),
+                    height: auto(),
+                },
+                ..styles::flex_column()
+            },
+            &[leaf],
+        );
+        tree.set_root(root);
+        // Text 300 wide wraps to three lines at 100
+        tree.compute_layout_with_wrapping(400.0, 300.0, |id, width| {
+            assert_eq!(id, leaf);
+            Some((width, 20.0 * (300.0 / width.max(1.0)).ceil()))
+        });
+
+        let bounds = tree.get_layout(leaf).unwrap().bounds;
+        assert_eq!((bounds.width, bounds.height), (100.0, 60.0));
+    }
+}
@@ -1,6 +1,14 @@
 //! Event bridge - converts native events to Rust InputEvent types.
+//!
+//! Also hosts [`ActionTarget`], the Objective-C target that native controls
+//! send their target/action messages to, and [`run_on_main`] for updating
+//! controls from other threads.
 
+use objc2::runtime::{AnyClass, AnyObject, ClassBuilder, Sel};
+use objc2::{class, msg_send, sel};
 use spark_input::{InputEvent, PointerButton};
+use std::ffi::c_void;
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// Bridge for converting native events to Spark InputEvent types.
 pub struct EventBridge;
@@ -15,7 +23,7 @@ impl EventBridge {
     ) -> InputEvent {
         let pos =
             crate::layout::LayoutBridge::native_to_taffy_point(x, y, parent_height, scale_factor);
-        InputEvent::PointerMove { pos }
+        InputEvent::pointer_move(pos)
     }
 
     /// Convert a native mouse/touch down event to InputEvent.
@@ -33,7 +41,7 @@ impl EventBridge {
             NativeButton::Right => PointerButton::Secondary,
             NativeButton::Middle => PointerButton::Auxiliary,
         };
-        InputEvent::PointerDown { pos, button }
+        InputEvent::pointer_down(pos, button)
     }
 
     /// Convert a native mouse/touch up event to InputEvent.
@@ -51,7 +59,7 @@ impl EventBridge {
             NativeButton::Right => PointerButton::Secondary,
             NativeButton::Middle => PointerButton::Auxiliary,
         };
-        InputEvent::PointerUp { pos, button }
+        InputEvent::pointer_up(pos, button)
     }
 
     /// Convert native text input to InputEvent.
@@ -77,3 +85,211 @@ pub enum NativeButton {
     Right,
     Middle,
 }
+
+/// Callback run when a control sends its action; receives the sending control.
+type ActionCallback = Box<dyn Fn(*mut AnyObject) + Send + Sync>;
+
+/// Name of the instance variable holding the `*mut ActionCallback`.
+const CALLBACK_IVAR: &std::ffi::CStr = c"sparkCallback";
+
+/// Objective-C object that runs a Rust closure when a control sends its action.
+///
+/// It can also be set as an NSTextField's delegate, where
+/// `controlTextDidChange:` runs the closure with the field after every edit.
+///
+/// Controls do not retain their target or delegate, so the owner must keep this alive for
+/// as long as the control can fire. Dropping it detaches the closure and
+/// releases the object.
+pub struct ActionTarget {
+    obj: *mut AnyObject,
+    callback: *mut ActionCallback,
+}
+
+unsafe impl Send for ActionTarget {}
+unsafe impl Sync for ActionTarget {}
+
+impl ActionTarget {
+    /// Create a target that calls `callback` with the sending control.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(*mut AnyObject) + Send + Sync + 'static,
+    {
+        let callback: *mut ActionCallback = Box::into_raw(Box::new(Box::new(callback)));
+        unsafe {
+            let class = target_class();
+            let obj: *mut AnyObject = msg_send![class, new];
+            let ivar = class
+                .instance_variable(CALLBACK_IVAR)
+                .expect("SparkActionTarget callback ivar");
+            *ivar.load_ptr::<*mut c_void>(&*obj) = callback.cast();
+            Self { obj, callback }
+        }
+    }
+
+    /// The selector to pass to `setAction:` / `addTarget:action:forControlEvents:`.
+    pub fn selector() -> Sel {
+        sel!(sparkAction:)
+    }
+
+    /// The underlying Objective-C object.
+    pub fn as_ptr(&self) -> *mut AnyObject {
+        self.obj
+    }
+}
+
+impl Drop for ActionTarget {
+    fn drop(&mut self) {
+        unsafe {
+            // Detach first so a late action is a no-op rather than a use-after-free.
+            if let Some(ivar) = target_class().instance_variable(CALLBACK_IVAR) {
+                *ivar.load_ptr::<*mut c_void>(&*self.obj) = std::ptr::null_mut();
+            }
+            let _: () = msg_send![self.obj, release];
+            drop(Box::from_raw(self.callback));
+        }
+    }
+}
+
+/// The `SparkActionTarget` class, declared on first use.
+fn target_class() -> &'static AnyClass {
+    static CLASS: OnceLock<&'static AnyClass> = OnceLock::new();
+    CLASS.get_or_init(|| {
+        let superclass = AnyClass::get(c"NSObject").expect("NSObject class");
+        let mut builder = ClassBuilder::new(c"SparkActionTarget", superclass)
+            .expect("SparkActionTarget declared twice");
+        builder.add_ivar::<*mut c_void>(CALLBACK_IVAR);
+        unsafe {
+            builder.add_method(
+                sel!(sparkAction:),
+                spark_action as unsafe extern "C-unwind" fn(_, _, _),
+            );
+            builder.add_method(
+                sel!(controlTextDidChange:),
+                spark_text_did_change as unsafe extern "C-unwind" fn(_, _, _),
+            );
+        }
+        builder.register()
+    })
+}
+
+/// Implementation of `-[SparkActionTarget sparkAction:]`.
+unsafe extern "C-unwind" fn spark_action(this: &AnyObject, _cmd: Sel, sender: *mut AnyObject) {
+    let Some(ivar) = this.class().instance_variable(CALLBACK_IVAR) else {
+        return;
+    };
+    let callback = *ivar.load::<*mut c_void>(this) as *const ActionCallback;
+    if let Some(callback) = callback.as_ref() {
+        callback(sender);
+    }
+}
+
+/// Implementation of `-[SparkActionTarget controlTextDidChange:]`.
+///
+/// The notification's object is the edited control.
+unsafe extern "C-unwind" fn spark_text_did_change(
+    this: &AnyObject,
+    cmd: Sel,
+    notification: *mut AnyObject,
+) {
+    if notification.is_null() {
+        return;
+    }
+    let sender: *mut AnyObject = msg_send![notification, object];
+    spark_action(this, cmd, sender);
+}
+
+/// A widget callback shared with its action target, so it can be set after the target exists.
+pub(crate) type SharedCallback<T> = Arc<Mutex<Option<Box<dyn Fn(T) + Send + Sync>>>>;
+
+/// A retained reference to a native view that can be sent to other threads.
+///
+/// Holds one strong reference for its lifetime: cloning retains and dropping
+/// releases. Compares and prints as the view's address. Only message the
+/// view on the main thread (see [`run_on_main`]).
+#[derive(Debug, PartialEq, Eq)]
+pub struct RetainedView {
+    obj: *mut AnyObject,
+}
+
+unsafe impl Send for RetainedView {}
+unsafe impl Sync for RetainedView {}
+
+impl RetainedView {
+    /// Retain `obj` for the lifetime of the returned value.
+    pub(crate) fn new(obj: *mut AnyObject) -> Self {
+        unsafe {
+            let _: *mut AnyObject = msg_send![obj, retain];
+        }
+        Self { obj }
+    }
+
+    /// The retained object.
+    pub fn as_ptr(&self) -> *mut AnyObject {
+        self.obj
+    }
+
+    /// The object's retain count, for leak checks in tests.
+    #[cfg(test)]
+    pub(crate) fn retain_count(&self) -> usize {
+        unsafe { msg_send![self.obj, retainCount] }
+    }
+}
+
+impl Clone for RetainedView {
+    fn clone(&self) -> Self {
+        Self::new(self.obj)
+    }
+}
+
+impl Drop for RetainedView {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.obj, release];
+        }
+    }
+}
+
+/// Opaque libdispatch queue.
+#[repr(C)]
+struct DispatchQueue {
+    _private: [u8; 0],
+}
+
+extern "C" {
+    /// The main queue; `dispatch_get_main_queue()` is a macro for its address.
+    static _dispatch_main_q: DispatchQueue;
+    fn dispatch_async_f(
+        queue: *const DispatchQueue,
+        context: *mut c_void,
+        work: extern "C" fn(*mut c_void),
+    );
+}
+
+/// Whether the current thread is the main (UI) thread.
+pub fn is_main_thread() -> bool {
+    unsafe { msg_send![class!(NSThread), isMainThread] }
+}
+
+/// Run `work` on the main thread: immediately if already on it, otherwise
+/// queued on the main dispatch queue.
+///
+/// AppKit and UIKit views may only be touched from the main thread.
+pub fn run_on_main<F>(work: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    extern "C" fn trampoline<F: FnOnce()>(context: *mut c_void) {
+        // SAFETY: `context` is the box leaked below, run exactly once.
+        let work = unsafe { Box::from_raw(context.cast::<F>()) };
+        work();
+    }
+
+    if is_main_thread() {
+        work();
+        return;
+    }
+    let context = Box::into_raw(Box::new(work)).cast::<c_void>();
+    unsafe {
+        dispatch_async_f(&_dispatch_main_q, context, trampoline::<F>);
+    }
+}
@@ -2,21 +2,40 @@
 
 use objc2::runtime::AnyObject;
 use objc2::msg_send;
+use spark_core::Color;
 
 /// NSView wrapper for macOS.
+///
+/// Owns one strong reference to the view, released on drop. Views created by
+/// the wrappers here start with the reference from `alloc`/`init`; views
+/// wrapped with [`from_ptr`](Self::from_ptr) are retained, so borrowing a view
+/// owned elsewhere (such as a window's content view) never takes it over.
 pub struct NSView {
     pub(crate) obj: *mut AnyObject,
 }
 
 impl NSView {
-    /// Create an NSView from a raw pointer (unsafe).
-    /// This is used when we have a pointer from the window handle.
+    /// Wrap and retain an existing view, such as a window's content view.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that the pointer is valid and points to a valid NSView (or subclass).
     pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        let _: *mut AnyObject = msg_send![ptr, retain];
         Self { obj: ptr }
     }
+
+    /// Wrap the view a handle refers to.
+    pub(crate) fn from_handle(view: &crate::RetainedView) -> Self {
+        // SAFETY: handles are only made for live views of this platform.
+        unsafe { Self::from_ptr(view.as_ptr()) }
+    }
+
+    /// A handle that keeps the view alive independently of this wrapper.
+    pub fn handle(&self) -> crate::NativeViewHandle {
+        // SAFETY: the wrapper owns a reference, so the view is live.
+        unsafe { crate::NativeViewHandle::from_ptr(self.obj) }
+    }
 }
 
 unsafe impl Send for NSView {}
@@ -36,6 +55,15 @@ impl NSView {
         }
     }
 
+    /// Create an NSView whose origin is the top-left corner, matching layout coordinates.
+    pub fn new_flipped() -> Self {
+        unsafe {
+            let obj: *mut AnyObject = msg_send![flipped_view_class(), alloc];
+            let obj: *mut AnyObject = msg_send![obj, init];
+            Self { obj }
+        }
+    }
+
     /// Get the raw object pointer.
     pub fn as_ptr(&self) -> *mut AnyObject {
         self.obj
@@ -56,6 +84,15 @@ impl NSView {
         }
     }
     
+    /// Get the frame of the view as (x, y, width, height) in its superview.
+    pub fn frame(&self) -> (f64, f64, f64, f64) {
+        unsafe {
+            use objc2_foundation::NSRect;
+            let rect: NSRect = msg_send![self.obj, frame];
+            (rect.origin.x, rect.origin.y, rect.size.width, rect.size.height)
+        }
+    }
+
     /// Get the view's intrinsic content size (if available).
     /// Returns (width, height) or (-1.0, -1.0) if no intrinsic size.
     pub fn intrinsic_content_size(&self) -> (f64, f64) {
@@ -103,18 +140,63 @@ impl NSView {
         }
     }
     
-    /// Bring the view to the front.
-    pub fn bring_to_front(&self) {
+    /// Number of direct subviews.
+    pub fn subview_count(&self) -> usize {
+        unsafe {
+            let subviews: *mut AnyObject = msg_send![self.obj, subviews];
+            msg_send![subviews, count]
+        }
+    }
+
+    /// The view's superview, if it has one.
+    pub fn superview(&self) -> Option<NSView> {
         unsafe {
             let superview: *mut AnyObject = msg_send![self.obj, superview];
-            if !superview.is_null() {
-                // Remove and re-add to bring to front
-                let _: () = msg_send![self.obj, removeFromSuperview];
-                let _: () = msg_send![superview, addSubview: self.obj];
-            }
+            (!superview.is_null()).then(|| NSView::from_ptr(superview))
+        }
+    }
+
+    /// Reorder the subviews so those in `order` come last, in that order, drawing over the rest.
+    ///
+    /// Sorts in place, so views keep their first responder status, unlike
+    /// removing and re-adding them.
+    pub fn order_subviews(&self, order: &[*mut AnyObject]) {
+        type Ranks = std::collections::HashMap<*mut AnyObject, usize>;
+
+        extern "C" fn compare(a: *mut AnyObject, b: *mut AnyObject, context: *mut std::ffi::c_void) -> isize {
+            // SAFETY: `context` is the rank map below, which outlives the sort.
+            let ranks = unsafe { &*(context as *const Ranks) };
+            // Unlisted views rank below listed ones
+            let rank = |view| ranks.get(&view).map_or(0, |rank| rank + 1);
+            // Less, Equal, Greater match NSOrderedAscending, Same, Descending
+            rank(a).cmp(&rank(b)) as isize
+        }
+
+        let ranks: Ranks = order.iter().enumerate().map(|(rank, view)| (*view, rank)).collect();
+        let compare: extern "C" fn(*mut AnyObject, *mut AnyObject, *mut std::ffi::c_void) -> isize = compare;
+        unsafe {
+            let _: () = msg_send![
+                self.obj,
+                sortSubviewsUsingFunction: compare,
+                context: &ranks as *const Ranks as *mut std::ffi::c_void,
+            ];
+        }
+    }
+
+    /// Subview at `index`, back to front.
+    pub fn subview_at(&self, index: usize) -> Option<NSView> {
+        unsafe {
+            let subviews: *mut AnyObject = msg_send![self.obj, subviews];
+            let count: usize = msg_send![subviews, count];
+            (index < count).then(|| NSView::from_ptr(msg_send![subviews, objectAtIndex: index]))
         }
     }
 
+    /// Whether the view is hidden.
+    pub fn is_hidden(&self) -> bool {
+        unsafe { msg_send![self.obj, isHidden] }
+    }
+
     /// Remove from superview.
     pub fn remove_from_superview(&self) {
         unsafe {
@@ -136,22 +218,61 @@ impl NSView {
         }
     }
 
+    /// Set the menu AppKit shows when the view is right-clicked.
+    pub fn set_menu(&self, menu: &NSMenu) {
+        unsafe {
+            let _: () = msg_send![self.obj, setMenu: menu.as_ptr()];
+        }
+    }
+
     /// Set the appearance of the view (e.g. "NSAppearanceNameAqua").
     pub fn set_appearance(&self, name: &str) {
-        unsafe {
-            use objc2::runtime::AnyClass;
-            use objc2_foundation::NSString;
-            
-            let class_name = c"NSAppearance";
-            let class = AnyClass::get(class_name).expect("NSAppearance class");
-            let ns_name = NSString::from_str(name);
-            
-            let appearance: *mut AnyObject = msg_send![class, appearanceNamed: &*ns_name];
-            if !appearance.is_null() {
+        let appearance = appearance_named(name);
+        if !appearance.is_null() {
+            unsafe {
                 let _: () = msg_send![self.obj, setAppearance: appearance];
             }
         }
     }
+
+    /// Name of the appearance set on this view, or `None` if it inherits one.
+    pub fn appearance_name(&self) -> Option<String> {
+        unsafe {
+            let appearance: *mut AnyObject = msg_send![self.obj, appearance];
+            if appearance.is_null() {
+                return None;
+            }
+            let name: *mut AnyObject = msg_send![appearance, name];
+            Some(super::nsstring::to_string(name))
+        }
+    }
+
+    /// Set the accent color of controls that have one, or `None` for the system accent.
+    ///
+    /// Applies to slider fills and selected segments; other views are left
+    /// unchanged, since AppKit has no general tint for them.
+    pub fn set_tint_color(&self, color: Option<Color>) {
+        unsafe {
+            let color = color.map_or(std::ptr::null_mut(), ns_color);
+            let responds: bool = msg_send![self.obj, respondsToSelector: objc2::sel!(setTrackFillColor:)];
+            if responds {
+                let _: () = msg_send![self.obj, setTrackFillColor: color];
+            }
+            let responds: bool =
+                msg_send![self.obj, respondsToSelector: objc2::sel!(setSelectedSegmentBezelColor:)];
+            if responds {
+                let _: () = msg_send![self.obj, setSelectedSegmentBezelColor: color];
+            }
+        }
+    }
+
+    /// The window the view is in, if any.
+    pub fn window(&self) -> Option<NSWindow> {
+        unsafe {
+            let window: *mut AnyObject = msg_send![self.obj, window];
+            (!window.is_null()).then(|| NSWindow::from_ptr(window))
+        }
+    }
 }
 
 impl Default for NSView {
@@ -162,11 +283,103 @@ impl Default for NSView {
 
 impl Drop for NSView {
     fn drop(&mut self) {
-        // Don't release here - views are retained by their superviews
-        // If we manually release, we'll cause over-release crashes
-        // The Objective-C runtime will handle memory management
-        // We only need to release if we explicitly retained (which we don't)
+        // A superview or handle may still hold its own reference
+        unsafe {
+            let _: () = msg_send![self.obj, release];
+        }
+    }
+}
+
+/// NSWindow wrapper for macOS; owns one strong reference, like [`NSView`].
+pub struct NSWindow {
+    obj: *mut AnyObject,
+}
+
+impl NSWindow {
+    /// Wrap and retain an existing window.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSWindow.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        let _: *mut AnyObject = msg_send![ptr, retain];
+        Self { obj: ptr }
+    }
+
+    /// Get the raw object pointer.
+    pub fn as_ptr(&self) -> *mut AnyObject {
+        self.obj
+    }
+
+    /// Set the appearance of the window and the views in it (e.g. "NSAppearanceNameDarkAqua").
+    pub fn set_appearance(&self, name: &str) {
+        let appearance = appearance_named(name);
+        if !appearance.is_null() {
+            unsafe {
+                let _: () = msg_send![self.obj, setAppearance: appearance];
+            }
+        }
+    }
+}
+
+impl Drop for NSWindow {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.obj, release];
+        }
+    }
+}
+
+/// Look up a named NSAppearance; null if there is none with that name.
+fn appearance_named(name: &str) -> *mut AnyObject {
+    unsafe {
+        use objc2::runtime::AnyClass;
+        use objc2_foundation::NSString;
+
+        let class = AnyClass::get(c"NSAppearance").expect("NSAppearance class");
+        let ns_name = NSString::from_str(name);
+        msg_send![class, appearanceNamed: &*ns_name]
+    }
+}
+
+/// Create an autoreleased NSColor from an sRGB color.
+fn ns_color(color: Color) -> *mut AnyObject {
+    unsafe {
+        use objc2::runtime::AnyClass;
+
+        let class = AnyClass::get(c"NSColor").expect("NSColor class");
+        msg_send![
+            class,
+            colorWithSRGBRed: color.r as f64,
+            green: color.g as f64,
+            blue: color.b as f64,
+            alpha: color.a as f64,
+        ]
+    }
+}
+
+/// The `SparkFlippedView` class, an NSView subclass with a top-left origin.
+fn flipped_view_class() -> &'static objc2::runtime::AnyClass {
+    use objc2::runtime::{AnyClass, Bool, ClassBuilder, Sel};
+    use std::sync::OnceLock;
+
+    unsafe extern "C-unwind" fn is_flipped(_this: &AnyObject, _cmd: Sel) -> Bool {
+        Bool::YES
     }
+
+    static CLASS: OnceLock<&'static AnyClass> = OnceLock::new();
+    CLASS.get_or_init(|| {
+        let superclass = AnyClass::get(c"NSView").expect("NSView class");
+        let mut builder = ClassBuilder::new(c"SparkFlippedView", superclass)
+            .expect("SparkFlippedView declared twice");
+        unsafe {
+            builder.add_method(
+                objc2::sel!(isFlipped),
+                is_flipped as unsafe extern "C-unwind" fn(_, _) -> _,
+            );
+        }
+        builder.register()
+    })
 }
 
 /// NSButton wrapper for macOS.
@@ -175,6 +388,17 @@ pub struct NSButton {
 }
 
 impl NSButton {
+    /// Wrap and retain an existing NSButton, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSButton.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
+        }
+    }
+
     /// Create a new NSButton.
     pub fn new() -> Self {
         unsafe {
@@ -199,6 +423,14 @@ impl NSButton {
         }
     }
 
+    /// Get the button title.
+    pub fn title(&self) -> String {
+        unsafe {
+            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), title];
+            super::nsstring::to_string(ns_string)
+        }
+    }
+
     /// Set the button style.
     pub fn set_bezel_style(&self, style: NSBezelStyle) {
         unsafe {
@@ -236,6 +468,47 @@ impl NSButton {
             let _: () = msg_send![self.view.as_ptr(), sizeToFit];
         }
     }
+
+    /// Set the button type, e.g. [`NSButtonType::Switch`] for a check box.
+    pub fn set_button_type(&self, button_type: NSButtonType) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setButtonType: button_type as u64];
+        }
+    }
+
+    /// Set the state. Does not send the action.
+    pub fn set_state(&self, state: NSControlStateValue) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setState: state as i64];
+        }
+    }
+
+    /// Get the state.
+    pub fn state(&self) -> NSControlStateValue {
+        unsafe {
+            let state: i64 = msg_send![self.view.as_ptr(), state];
+            NSControlStateValue::from_raw(state)
+        }
+    }
+
+    /// Set whether the mixed state can be shown, and reached by clicking.
+    pub fn set_allows_mixed_state(&self, allows: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setAllowsMixedState: allows];
+        }
+    }
+
+    /// Set the tag, an integer that identifies the button to its action target.
+    pub fn set_tag(&self, tag: isize) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setTag: tag];
+        }
+    }
+
+    /// Get the tag.
+    pub fn tag(&self) -> isize {
+        unsafe { msg_send![self.view.as_ptr(), tag] }
+    }
 }
 
 impl Default for NSButton {
@@ -244,180 +517,231 @@ impl Default for NSButton {
     }
 }
 
-/// NSButton bezel styles.
-#[repr(i64)]
-pub enum NSBezelStyle {
-    Rounded = 1,
-    RegularSquare = 2,
-    TexturedSquare = 3,
-    Disclosure = 5,
-    ShadowlessSquare = 6,
-    Circular = 7,
-    TexturedRounded = 8,
-    HelpButton = 9,
-    SmallSquare = 10,
-    Toolbar = 11,
-    AccessoryBarAction = 12,
-    AccessoryBarAction2 = 13,
-    PushOnPushOff = 14,
-    MomentaryPushIn = 15,
-    Accordion = 16,
-    Inline = 17,
-    Recessed = 18,
-    RoundedDisclosure = 19,
-}
-
-/// NSTextField wrapper for macOS.
-pub struct NSTextField {
+/// NSPopUpButton wrapper for macOS.
+pub struct NSPopUpButton {
     view: NSView,
 }
 
-impl NSTextField {
-    /// Create a new NSTextField.
+impl NSPopUpButton {
+    /// Create a new pop-up (not pull-down) button.
     pub fn new() -> Self {
         unsafe {
             use objc2::runtime::AnyClass;
-            
-            let class_name = c"NSTextField";
-            let class = AnyClass::get(class_name).expect("NSTextField class");
+
+            let class_name = c"NSPopUpButton";
+            let class = AnyClass::get(class_name).expect("NSPopUpButton class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
+            let _: () = msg_send![obj, setPullsDown: false];
             Self {
                 view: NSView { obj },
             }
         }
     }
 
-    /// Set the text field's string value.
-    pub fn set_string_value(&self, value: &str) {
-        unsafe {
-            use objc2_foundation::NSString;
-            let ns_string = NSString::from_str(value);
-            let _: () = msg_send![self.view.as_ptr(), setStringValue: &*ns_string];
+    /// Wrap and retain an existing NSPopUpButton, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSPopUpButton.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
         }
     }
 
-    /// Get the text field's string value.
-    pub fn string_value(&self) -> String {
+    /// Append an item to the menu.
+    pub fn add_item(&self, title: &str) {
         unsafe {
-            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), stringValue];
-            if ns_string.is_null() {
-                return String::new();
-            }
-            // Convert NSString to Rust String (simplified)
-            // In practice, you'd use proper NSString methods
-            String::new()
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(title);
+            let _: () = msg_send![self.view.as_ptr(), addItemWithTitle: &*ns_string];
         }
     }
 
-    /// Set placeholder string.
-    pub fn set_placeholder_string(&self, value: &str) {
+    /// Remove every item.
+    pub fn remove_all_items(&self) {
         unsafe {
-            use objc2_foundation::NSString;
-            let ns_string = NSString::from_str(value);
-            let _: () = msg_send![self.view.as_ptr(), setPlaceholderString: &*ns_string];
+            let _: () = msg_send![self.view.as_ptr(), removeAllItems];
         }
     }
 
-    /// Get the underlying view.
-    pub fn view(&self) -> &NSView {
-        &self.view
-    }
-    
-    /// Get the text field's intrinsic content size.
-    pub fn intrinsic_content_size(&self) -> (f64, f64) {
-        self.view.intrinsic_content_size()
-    }
-    
-    /// Size the text field to fit its content.
-    pub fn size_to_fit(&self) {
+    /// Number of items in the menu.
+    pub fn number_of_items(&self) -> usize {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), sizeToFit];
+            let count: isize = msg_send![self.view.as_ptr(), numberOfItems];
+            count.max(0) as usize
         }
     }
 
-    /// Set whether the text field is editable.
-    pub fn set_editable(&self, editable: bool) {
+    /// Select the item at `index`. Does not send the action.
+    pub fn select_item(&self, index: usize) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setEditable: editable];
+            let _: () = msg_send![self.view.as_ptr(), selectItemAtIndex: index as isize];
         }
     }
 
-    /// Set whether the text field is selectable.
-    pub fn set_selectable(&self, selectable: bool) {
+    /// Index of the selected item, or `None` if nothing is selected.
+    pub fn index_of_selected_item(&self) -> Option<usize> {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setSelectable: selectable];
+            let index: isize = msg_send![self.view.as_ptr(), indexOfSelectedItem];
+            usize::try_from(index).ok()
         }
     }
 
-    /// Set whether the text field is bezeled.
-    pub fn set_bezeled(&self, bezeled: bool) {
+    /// Set the action sent when the selection changes.
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setBezeled: bezeled];
+            let _: () = msg_send![self.view.as_ptr(), setTarget: target];
+            let _: () = msg_send![self.view.as_ptr(), setAction: selector];
         }
     }
 
-    /// Set whether the text field draws its background.
-    pub fn set_draws_background(&self, draws: bool) {
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+
+    /// Get the pop-up button's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+
+    /// Size the pop-up button to fit its widest item.
+    pub fn size_to_fit(&self) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setDrawsBackground: draws];
+            let _: () = msg_send![self.view.as_ptr(), sizeToFit];
         }
     }
 }
 
-impl Default for NSTextField {
+impl Default for NSPopUpButton {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// NSSlider wrapper for macOS.
-pub struct NSSlider {
+/// NSComboBox wrapper for macOS: a text field with a list of suggestions.
+pub struct NSComboBox {
     view: NSView,
 }
 
-impl NSSlider {
-    /// Create a new NSSlider.
+impl NSComboBox {
+    /// Create an empty combo box that completes typed text from its items.
     pub fn new() -> Self {
         unsafe {
             use objc2::runtime::AnyClass;
-            
-            let class_name = c"NSSlider";
-            let class = AnyClass::get(class_name).expect("NSSlider class");
+
+            let class_name = c"NSComboBox";
+            let class = AnyClass::get(class_name).expect("NSComboBox class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
+            let _: () = msg_send![obj, setCompletes: true];
             Self {
                 view: NSView { obj },
             }
         }
     }
 
-    /// Set the slider's minimum value.
-    pub fn set_min_value(&self, value: f64) {
-        unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setMinValue: value];
+    /// Wrap and retain an existing NSComboBox, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSComboBox.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
         }
     }
 
-    /// Set the slider's maximum value.
-    pub fn set_max_value(&self, value: f64) {
+    /// Append an item to the list.
+    pub fn add_item(&self, title: &str) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setMaxValue: value];
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(title);
+            let _: () = msg_send![self.view.as_ptr(), addItemWithObjectValue: &*ns_string];
         }
     }
 
-    /// Set the slider's current value.
-    pub fn set_double_value(&self, value: f64) {
+    /// Remove every item. The text is kept.
+    pub fn remove_all_items(&self) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setDoubleValue: value];
+            let _: () = msg_send![self.view.as_ptr(), removeAllItems];
         }
     }
 
-    /// Get the slider's current value.
-    pub fn double_value(&self) -> f64 {
+    /// Number of items in the list.
+    pub fn number_of_items(&self) -> usize {
         unsafe {
-            let value: f64 = msg_send![self.view.as_ptr(), doubleValue];
-            value
+            let count: isize = msg_send![self.view.as_ptr(), numberOfItems];
+            count.max(0) as usize
+        }
+    }
+
+    /// Set how many items the list shows before it scrolls.
+    pub fn set_number_of_visible_items(&self, count: usize) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setNumberOfVisibleItems: count as isize];
+        }
+    }
+
+    /// How many items the list shows before it scrolls.
+    pub fn number_of_visible_items(&self) -> usize {
+        unsafe {
+            let count: isize = msg_send![self.view.as_ptr(), numberOfVisibleItems];
+            count.max(0) as usize
+        }
+    }
+
+    /// Select the item at `index`, putting its text in the field. Does not send the action.
+    pub fn select_item(&self, index: usize) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), selectItemAtIndex: index as isize];
+        }
+    }
+
+    /// Index of the selected item, or `None` if the text was typed.
+    pub fn index_of_selected_item(&self) -> Option<usize> {
+        unsafe {
+            let index: isize = msg_send![self.view.as_ptr(), indexOfSelectedItem];
+            usize::try_from(index).ok()
+        }
+    }
+
+    /// Set the text.
+    pub fn set_string_value(&self, value: &str) {
+        unsafe {
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(value);
+            let _: () = msg_send![self.view.as_ptr(), setStringValue: &*ns_string];
+        }
+    }
+
+    /// Get the text.
+    pub fn string_value(&self) -> String {
+        unsafe {
+            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), stringValue];
+            super::nsstring::to_string(ns_string)
+        }
+    }
+
+    /// Set the placeholder shown while the text is empty.
+    pub fn set_placeholder_string(&self, value: &str) {
+        unsafe {
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(value);
+            let _: () = msg_send![self.view.as_ptr(), setPlaceholderString: &*ns_string];
+        }
+    }
+
+    /// Set the action, sent when an item is picked or editing ends.
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setTarget: target];
+            let _: () = msg_send![self.view.as_ptr(), setAction: selector];
+            // Also send it when focus leaves, not only on Return
+            let cell: *mut AnyObject = msg_send![self.view.as_ptr(), cell];
+            let _: () = msg_send![cell, setSendsActionOnEndEditing: true];
         }
     }
 
@@ -425,157 +749,274 @@ impl NSSlider {
     pub fn view(&self) -> &NSView {
         &self.view
     }
-    
-    /// Get the slider's intrinsic content size.
+
+    /// Get the combo box's intrinsic content size.
     pub fn intrinsic_content_size(&self) -> (f64, f64) {
         self.view.intrinsic_content_size()
     }
 }
 
-impl Default for NSSlider {
+impl Default for NSComboBox {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// NSSwitch (NSButton with switch style) wrapper for macOS.
-pub struct NSSwitch {
+/// NSSegmentedControl wrapper for macOS.
+pub struct NSSegmentedControl {
     view: NSView,
 }
 
-impl NSSwitch {
-    /// Create a new NSSwitch.
+impl NSSegmentedControl {
+    /// Create a new segmented control with no segments.
     pub fn new() -> Self {
         unsafe {
             use objc2::runtime::AnyClass;
-            
-            let class_name = c"NSSwitch";
-            let class = AnyClass::get(class_name).expect("NSSwitch class (requires macOS 10.15+)");
+
+            let class_name = c"NSSegmentedControl";
+            let class = AnyClass::get(class_name).expect("NSSegmentedControl class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
-            
             Self {
                 view: NSView { obj },
             }
         }
     }
 
-    /// Set the switch state (on/off).
-    pub fn set_state(&self, state: i64) {
+    /// Wrap and retain an existing NSSegmentedControl, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSSegmentedControl.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
+        }
+    }
+
+    /// Set the number of segments.
+    pub fn set_segment_count(&self, count: usize) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setState: state];
+            let _: () = msg_send![self.view.as_ptr(), setSegmentCount: count as isize];
         }
     }
 
-    /// Get the switch state.
-    pub fn state(&self) -> i64 {
+    /// Number of segments.
+    pub fn segment_count(&self) -> usize {
         unsafe {
-            let state: i64 = msg_send![self.view.as_ptr(), state];
-            state
+            let count: isize = msg_send![self.view.as_ptr(), segmentCount];
+            count.max(0) as usize
         }
     }
 
-    /// Set the switch title.
-    pub fn set_title(&self, _title: &str) {
-        // NSSwitch does not support titles. The label should be managed externally.
+    /// Set the label of the segment at `segment`.
+    pub fn set_label(&self, label: &str, segment: usize) {
+        unsafe {
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(label);
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                setLabel: &*ns_string,
+                forSegment: segment as isize
+            ];
+        }
     }
 
-    /// Get the underlying view.
-    pub fn view(&self) -> &NSView {
-        &self.view
+    /// Select the segment at `segment`. Does not send the action.
+    pub fn set_selected_segment(&self, segment: usize) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setSelectedSegment: segment as isize];
+        }
     }
-    
-    /// Get the switch's intrinsic content size.
-    pub fn intrinsic_content_size(&self) -> (f64, f64) {
-        self.view.intrinsic_content_size()
+
+    /// Index of the selected segment, or `None` if nothing is selected.
+    pub fn selected_segment(&self) -> Option<usize> {
+        unsafe {
+            let index: isize = msg_send![self.view.as_ptr(), selectedSegment];
+            usize::try_from(index).ok()
+        }
     }
-    
-    /// Size the switch to fit its content.
-    pub fn size_to_fit(&self) {
+
+    /// Set how clicking segments changes the selection.
+    pub fn set_tracking_mode(&self, mode: NSSegmentSwitchTracking) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), sizeToFit];
+            let _: () = msg_send![self.view.as_ptr(), setTrackingMode: mode as u64];
+        }
+    }
+
+    /// Set the action sent when the selection changes.
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setTarget: target];
+            let _: () = msg_send![self.view.as_ptr(), setAction: selector];
         }
     }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+
+    /// Get the size that fits every segment's label.
+    pub fn fitting_size(&self) -> (f64, f64) {
+        self.view.fitting_size()
+    }
 }
 
-impl Default for NSSwitch {
+impl Default for NSSegmentedControl {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// NSProgressIndicator wrapper for macOS.
-pub struct NSProgressIndicator {
+/// NSSegmentedControl tracking mode.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NSSegmentSwitchTracking {
+    SelectOne = 0,
+    SelectAny = 1,
+    Momentary = 2,
+}
+
+/// NSButton types.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NSButtonType {
+    MomentaryLight = 0,
+    PushOnPushOff = 1,
+    Toggle = 2,
+    /// A check box.
+    Switch = 3,
+    Radio = 4,
+    MomentaryChange = 5,
+    OnOff = 6,
+    MomentaryPushIn = 7,
+}
+
+/// States of buttons and switches.
+#[repr(i64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NSControlStateValue {
+    Mixed = -1,
+    Off = 0,
+    On = 1,
+}
+
+impl NSControlStateValue {
+    /// Convert a raw state; unknown values count as on.
+    fn from_raw(state: i64) -> Self {
+        match state {
+            -1 => Self::Mixed,
+            0 => Self::Off,
+            _ => Self::On,
+        }
+    }
+}
+
+/// NSButton bezel styles.
+#[repr(i64)]
+pub enum NSBezelStyle {
+    Rounded = 1,
+    RegularSquare = 2,
+    TexturedSquare = 3,
+    Disclosure = 5,
+    ShadowlessSquare = 6,
+    Circular = 7,
+    TexturedRounded = 8,
+    HelpButton = 9,
+    SmallSquare = 10,
+    Toolbar = 11,
+    AccessoryBarAction = 12,
+    AccessoryBarAction2 = 13,
+    PushOnPushOff = 14,
+    MomentaryPushIn = 15,
+    Accordion = 16,
+    Inline = 17,
+    Recessed = 18,
+    RoundedDisclosure = 19,
+}
+
+/// NSTextField wrapper for macOS.
+pub struct NSTextField {
     view: NSView,
 }
 
-impl NSProgressIndicator {
-    /// Create a new NSProgressIndicator.
+impl NSTextField {
+    /// Create a new NSTextField.
     pub fn new() -> Self {
         unsafe {
             use objc2::runtime::AnyClass;
             
-            let class_name = c"NSProgressIndicator";
-            let class = AnyClass::get(class_name).expect("NSProgressIndicator class");
+            let class_name = c"NSTextField";
+            let class = AnyClass::get(class_name).expect("NSTextField class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
-
             Self {
                 view: NSView { obj },
             }
         }
     }
 
-    /// Set the progress indicator style.
-    pub fn set_style(&self, style: NSProgressIndicatorStyle) {
-        unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setStyle: style as i64];
+    /// Wrap and retain an existing NSTextField, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSTextField.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
         }
     }
 
-    /// Set the progress indicator's minimum value.
-    pub fn set_min_value(&self, value: f64) {
+    /// Set the text field's string value.
+    pub fn set_string_value(&self, value: &str) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setMinValue: value];
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(value);
+            let _: () = msg_send![self.view.as_ptr(), setStringValue: &*ns_string];
         }
     }
 
-    /// Set the progress indicator's maximum value.
-    pub fn set_max_value(&self, value: f64) {
+    /// Get the text field's string value.
+    pub fn string_value(&self) -> String {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setMaxValue: value];
+            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), stringValue];
+            super::nsstring::to_string(ns_string)
         }
     }
 
-    /// Set the progress indicator's current value.
-    pub fn set_double_value(&self, value: f64) {
+    /// Set placeholder string.
+    pub fn set_placeholder_string(&self, value: &str) {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setDoubleValue: value];
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(value);
+            let _: () = msg_send![self.view.as_ptr(), setPlaceholderString: &*ns_string];
         }
     }
 
-    /// Set whether the progress indicator is indeterminate.
-    pub fn set_indeterminate(&self, indeterminate: bool) {
+    /// Get the placeholder string.
+    pub fn placeholder_string(&self) -> String {
         unsafe {
-            let _: () = msg_send![self.view.as_ptr(), setIndeterminate: indeterminate];
+            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), placeholderString];
+            super::nsstring::to_string(ns_string)
         }
     }
 
-    /// Start animation (for indeterminate progress).
-    pub fn start_animation(&self) {
+    /// Set the action, sent when Return is pressed or editing ends.
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
         unsafe {
-            // Note: startAnimation: takes a sender parameter, we pass nil
-            let nil: *mut objc2::runtime::AnyObject = std::ptr::null_mut();
-            let _: () = msg_send![self.view.as_ptr(), startAnimation: nil];
+            let _: () = msg_send![self.view.as_ptr(), setTarget: target];
+            let _: () = msg_send![self.view.as_ptr(), setAction: selector];
+            // Also send it when focus leaves, not only on Return
+            let cell: *mut AnyObject = msg_send![self.view.as_ptr(), cell];
+            let _: () = msg_send![cell, setSendsActionOnEndEditing: true];
         }
     }
 
-    /// Stop animation.
-    pub fn stop_animation(&self) {
+    /// Set the delegate, which receives `controlTextDidChange:` after every edit.
+    pub fn set_delegate(&self, delegate: *mut AnyObject) {
         unsafe {
-            // Note: stopAnimation: takes a sender parameter, we pass nil
-            let nil: *mut objc2::runtime::AnyObject = std::ptr::null_mut();
-            let _: () = msg_send![self.view.as_ptr(), stopAnimation: nil];
+            let _: () = msg_send![self.view.as_ptr(), setDelegate: delegate];
         }
     }
 
@@ -584,30 +1025,1186 @@ impl NSProgressIndicator {
         &self.view
     }
     
-    /// Get the progress indicator's intrinsic content size.
+    /// Get the text field's intrinsic content size.
     pub fn intrinsic_content_size(&self) -> (f64, f64) {
         self.view.intrinsic_content_size()
     }
+    
+    /// Size the text field to fit its content.
+    pub fn size_to_fit(&self) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), sizeToFit];
+        }
+    }
+
+    /// Set whether the text field is editable.
+    pub fn set_editable(&self, editable: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setEditable: editable];
+        }
+    }
+
+    /// Set whether the text field is selectable.
+    pub fn set_selectable(&self, selectable: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setSelectable: selectable];
+        }
+    }
+
+    /// Set whether the text field is bezeled.
+    pub fn set_bezeled(&self, bezeled: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setBezeled: bezeled];
+        }
+    }
+
+    /// Set whether the text field draws its background.
+    pub fn set_draws_background(&self, draws: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setDrawsBackground: draws];
+        }
+    }
 }
 
-impl Default for NSProgressIndicator {
+impl Default for NSTextField {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// NSProgressIndicator style.
-#[repr(i64)]
-pub enum NSProgressIndicatorStyle {
-    Bar = 0,
-    Spinning = 1,
+/// NSSlider wrapper for macOS.
+pub struct NSSlider {
+    view: NSView,
 }
 
-/// Helper to get NSWindow from a winit window.
-/// Note: This requires winit as a dependency, which is not included here.
-/// The app runner should handle window embedding directly.
-pub fn get_nswindow_from_winit(_window: &dyn std::any::Any) -> *mut AnyObject {
-    // This is platform-specific - winit provides access to the native window
-    // For now, return a placeholder - in practice, you'd use winit's native window access
-    std::ptr::null_mut()
+impl NSSlider {
+    /// Create a new NSSlider.
+    pub fn new() -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+            
+            let class_name = c"NSSlider";
+            let class = AnyClass::get(class_name).expect("NSSlider class");
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![obj, init];
+            Self {
+                view: NSView { obj },
+            }
+        }
+    }
+
+    /// Wrap and retain an existing NSSlider, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSSlider.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
+        }
+    }
+
+    /// Set the slider's minimum value.
+    pub fn set_min_value(&self, value: f64) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setMinValue: value];
+        }
+    }
+
+    /// Set the slider's maximum value.
+    pub fn set_max_value(&self, value: f64) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setMaxValue: value];
+        }
+    }
+
+    /// Set the slider's current value.
+    pub fn set_double_value(&self, value: f64) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setDoubleValue: value];
+        }
+    }
+
+    /// Get the slider's current value.
+    pub fn double_value(&self) -> f64 {
+        unsafe {
+            let value: f64 = msg_send![self.view.as_ptr(), doubleValue];
+            value
+        }
+    }
+
+    /// Send the action while the knob is dragged, not just on release.
+    pub fn set_continuous(&self, continuous: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setContinuous: continuous];
+        }
+    }
+
+    /// Set the slider action (callback).
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setTarget: target];
+            let _: () = msg_send![self.view.as_ptr(), setAction: selector];
+        }
+    }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+    
+    /// Get the slider's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+}
+
+impl Default for NSSlider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// NSSwitch wrapper for macOS.
+///
+/// Before macOS 10.15, which introduced NSSwitch, this is a check box style
+/// NSButton instead.
+pub struct NSSwitch {
+    view: NSView,
+    /// Whether this is the NSButton fallback, which shows a title.
+    is_button: bool,
+}
+
+impl NSSwitch {
+    /// Create a new NSSwitch, or a check box where NSSwitch is unavailable.
+    pub fn new() -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+
+            let Some(class) = AnyClass::get(c"NSSwitch") else {
+                let button = NSButton::new();
+                button.set_button_type(NSButtonType::Switch);
+                return Self {
+                    view: button.view,
+                    is_button: true,
+                };
+            };
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![obj, init];
+            
+            Self {
+                view: NSView { obj },
+                is_button: false,
+            }
+        }
+    }
+
+    /// Whether this is a check box because NSSwitch is unavailable.
+    pub fn is_fallback(&self) -> bool {
+        self.is_button
+    }
+
+    /// Wrap and retain an existing NSSwitch, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSSwitch.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        let is_button = objc2::runtime::AnyClass::get(c"NSSwitch").is_none();
+        Self {
+            view: NSView::from_ptr(ptr),
+            is_button,
+        }
+    }
+
+    /// Set the switch state (on/off). Does not send the action.
+    pub fn set_state(&self, state: i64) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setState: state];
+        }
+    }
+
+    /// Get the switch state.
+    pub fn state(&self) -> i64 {
+        unsafe {
+            let state: i64 = msg_send![self.view.as_ptr(), state];
+            state
+        }
+    }
+
+    /// Set the switch action (callback).
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setTarget: target];
+            let _: () = msg_send![self.view.as_ptr(), setAction: selector];
+        }
+    }
+
+    /// Set the title shown by the check box fallback.
+    ///
+    /// NSSwitch has no title; label it with a separate view.
+    pub fn set_title(&self, title: &str) {
+        if !self.is_button {
+            return;
+        }
+        unsafe {
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(title);
+            let _: () = msg_send![self.view.as_ptr(), setTitle: &*ns_string];
+        }
+    }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+    
+    /// Get the switch's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+    
+    /// Size the switch to fit its content.
+    pub fn size_to_fit(&self) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), sizeToFit];
+        }
+    }
+}
+
+impl Default for NSSwitch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// NSProgressIndicator wrapper for macOS.
+pub struct NSProgressIndicator {
+    view: NSView,
+}
+
+impl NSProgressIndicator {
+    /// Create a new NSProgressIndicator.
+    pub fn new() -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+            
+            let class_name = c"NSProgressIndicator";
+            let class = AnyClass::get(class_name).expect("NSProgressIndicator class");
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![obj, init];
+
+            Self {
+                view: NSView { obj },
+            }
+        }
+    }
+
+    /// Wrap and retain an existing NSProgressIndicator.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSProgressIndicator.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
+        }
+    }
+
+    /// Set the progress indicator style.
+    pub fn set_style(&self, style: NSProgressIndicatorStyle) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setStyle: style as i64];
+        }
+    }
+
+    /// Set the progress indicator's minimum value.
+    pub fn set_min_value(&self, value: f64) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setMinValue: value];
+        }
+    }
+
+    /// Set the progress indicator's maximum value.
+    pub fn set_max_value(&self, value: f64) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setMaxValue: value];
+        }
+    }
+
+    /// Set the progress indicator's current value.
+    pub fn set_double_value(&self, value: f64) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setDoubleValue: value];
+        }
+    }
+
+    /// Set whether the progress indicator is indeterminate.
+    pub fn set_indeterminate(&self, indeterminate: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setIndeterminate: indeterminate];
+        }
+    }
+
+    /// Start animation (for indeterminate progress).
+    pub fn start_animation(&self) {
+        unsafe {
+            // Note: startAnimation: takes a sender parameter, we pass nil
+            let nil: *mut objc2::runtime::AnyObject = std::ptr::null_mut();
+            let _: () = msg_send![self.view.as_ptr(), startAnimation: nil];
+        }
+    }
+
+    /// Stop animation.
+    pub fn stop_animation(&self) {
+        unsafe {
+            // Note: stopAnimation: takes a sender parameter, we pass nil
+            let nil: *mut objc2::runtime::AnyObject = std::ptr::null_mut();
+            let _: () = msg_send![self.view.as_ptr(), stopAnimation: nil];
+        }
+    }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+    
+    /// Get the progress indicator's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+}
+
+impl Default for NSProgressIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// NSProgressIndicator style.
+#[repr(i64)]
+pub enum NSProgressIndicatorStyle {
+    Bar = 0,
+    Spinning = 1,
+}
+
+/// NSScrollView wrapper for macOS.
+pub struct NSScrollView {
+    view: NSView,
+}
+
+impl NSScrollView {
+    /// Create a new scroll view with no document view.
+    pub fn new() -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"NSScrollView";
+            let class = AnyClass::get(class_name).expect("NSScrollView class");
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![obj, init];
+            Self {
+                view: NSView { obj },
+            }
+        }
+    }
+
+    /// Set the view that is scrolled.
+    pub fn set_document_view(&self, document: &NSView) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setDocumentView: document.as_ptr()];
+        }
+    }
+
+    /// Show or hide the vertical scroller.
+    pub fn set_has_vertical_scroller(&self, has: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setHasVerticalScroller: has];
+        }
+    }
+
+    /// Show or hide the horizontal scroller.
+    pub fn set_has_horizontal_scroller(&self, has: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setHasHorizontalScroller: has];
+        }
+    }
+
+    /// Set whether scrollers overlay the content or take up space beside it.
+    pub fn set_scroller_style(&self, style: NSScrollerStyle) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setScrollerStyle: style as i64];
+        }
+    }
+
+    /// Set whether the scroll view fills its background.
+    pub fn set_draws_background(&self, draws: bool) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setDrawsBackground: draws];
+        }
+    }
+
+    /// Scroll so that document point (`x`, `y`) is at the top-left of the visible area.
+    pub fn scroll_to(&self, x: f64, y: f64) {
+        unsafe {
+            use objc2_foundation::NSPoint;
+            let clip: *mut AnyObject = msg_send![self.view.as_ptr(), contentView];
+            let _: () = msg_send![clip, scrollToPoint: NSPoint { x, y }];
+            let _: () = msg_send![self.view.as_ptr(), reflectScrolledClipView: clip];
+        }
+    }
+
+    /// Document point at the top-left of the visible area.
+    pub fn scroll_position(&self) -> (f64, f64) {
+        unsafe {
+            use objc2_foundation::NSRect;
+            let visible: NSRect = msg_send![self.view.as_ptr(), documentVisibleRect];
+            (visible.origin.x, visible.origin.y)
+        }
+    }
+
+    /// Size of the visible area, excluding legacy scrollers.
+    pub fn content_size(&self) -> (f64, f64) {
+        unsafe {
+            use objc2_foundation::NSSize;
+            let size: NSSize = msg_send![self.view.as_ptr(), contentSize];
+            (size.width, size.height)
+        }
+    }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+}
+
+impl Default for NSScrollView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// NSScrollView scroller style.
+#[repr(i64)]
+pub enum NSScrollerStyle {
+    Legacy = 0,
+    Overlay = 1,
+}
+
+/// NSDatePicker wrapper for macOS.
+///
+/// Dates cross the boundary as seconds since the Unix epoch.
+pub struct NSDatePicker {
+    view: NSView,
+}
+
+impl NSDatePicker {
+    /// Create a new NSDatePicker.
+    pub fn new() -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"NSDatePicker";
+            let class = AnyClass::get(class_name).expect("NSDatePicker class");
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![obj, init];
+            Self {
+                view: NSView { obj },
+            }
+        }
+    }
+
+    /// Wrap and retain an existing NSDatePicker, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSDatePicker.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: NSView::from_ptr(ptr),
+        }
+    }
+
+    /// Set the date picker style.
+    pub fn set_style(&self, style: NSDatePickerStyle) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setDatePickerStyle: style as u64];
+        }
+    }
+
+    /// Set which date and time fields are shown.
+    pub fn set_elements(&self, elements: NSDatePickerElements) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setDatePickerElements: elements.0];
+        }
+    }
+
+    /// Set the selected date, in seconds since the Unix epoch.
+    pub fn set_date_value(&self, seconds: f64) {
+        unsafe {
+            let date = objc2_foundation::NSDate::dateWithTimeIntervalSince1970(seconds);
+            let _: () = msg_send![self.view.as_ptr(), setDateValue: &*date];
+        }
+    }
+
+    /// Get the selected date, in seconds since the Unix epoch.
+    pub fn date_value(&self) -> f64 {
+        unsafe {
+            let date: *mut AnyObject = msg_send![self.view.as_ptr(), dateValue];
+            if date.is_null() {
+                return 0.0;
+            }
+            let seconds: f64 = msg_send![date, timeIntervalSince1970];
+            seconds
+        }
+    }
+
+    /// Set the earliest selectable date, or `None` for no limit.
+    pub fn set_min_date(&self, seconds: Option<f64>) {
+        unsafe {
+            let date = seconds.map(objc2_foundation::NSDate::dateWithTimeIntervalSince1970);
+            let _: () = msg_send![self.view.as_ptr(), setMinDate: date.as_deref()];
+        }
+    }
+
+    /// Set the latest selectable date, or `None` for no limit.
+    pub fn set_max_date(&self, seconds: Option<f64>) {
+        unsafe {
+            let date = seconds.map(objc2_foundation::NSDate::dateWithTimeIntervalSince1970);
+            let _: () = msg_send![self.view.as_ptr(), setMaxDate: date.as_deref()];
+        }
+    }
+
+    /// Set the date picker action (callback).
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setTarget: target];
+            let _: () = msg_send![self.view.as_ptr(), setAction: selector];
+        }
+    }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+
+    /// Get the date picker's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+}
+
+impl Default for NSDatePicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// NSDatePicker style.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NSDatePickerStyle {
+    TextFieldAndStepper = 0,
+    ClockAndCalendar = 1,
+    TextField = 2,
+}
+
+/// Fields shown by an NSDatePicker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NSDatePickerElements(pub u64);
+
+impl NSDatePickerElements {
+    pub const HOUR_MINUTE: Self = Self(0x000c);
+    pub const HOUR_MINUTE_SECOND: Self = Self(0x000e);
+    pub const YEAR_MONTH: Self = Self(0x00c0);
+    pub const YEAR_MONTH_DAY: Self = Self(0x00e0);
+}
+
+impl std::ops::BitOr for NSDatePickerElements {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// NSImageView wrapper for macOS.
+pub struct NSImageView {
+    view: NSView,
+}
+
+impl NSImageView {
+    /// Create a new image view with no image.
+    pub fn new() -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"NSImageView";
+            let class = AnyClass::get(class_name).expect("NSImageView class");
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![obj, init];
+            Self {
+                view: NSView { obj },
+            }
+        }
+    }
+
+    /// Show an encoded image (PNG, JPEG, HEIC, ...). Returns false if AppKit can't decode it.
+    pub fn set_image_data(&self, data: &[u8]) -> bool {
+        unsafe {
+            use objc2::runtime::AnyClass;
+            use objc2_foundation::NSData;
+
+            let class = AnyClass::get(c"NSImage").expect("NSImage class");
+            let ns_data = NSData::with_bytes(data);
+            let image: *mut AnyObject = msg_send![class, alloc];
+            let image: *mut AnyObject = msg_send![image, initWithData: &*ns_data];
+            if image.is_null() {
+                return false;
+            }
+            let _: () = msg_send![self.view.as_ptr(), setImage: image];
+            let _: () = msg_send![image, release];
+            true
+        }
+    }
+
+    /// Show `width` x `height` pixels of non-premultiplied 8-bit RGBA.
+    ///
+    /// Returns false if `rgba` is not exactly `width * height * 4` bytes.
+    pub fn set_image_rgba(&self, width: usize, height: usize, rgba: &[u8]) -> bool {
+        if width == 0 || height == 0 || rgba.len() != width * height * 4 {
+            return false;
+        }
+        unsafe {
+            use objc2::runtime::AnyClass;
+            use objc2_foundation::{NSSize, NSString};
+
+            // NSAlphaNonpremultipliedBitmapFormat
+            const NON_PREMULTIPLIED: usize = 1 << 1;
+
+            let rep_class = AnyClass::get(c"NSBitmapImageRep").expect("NSBitmapImageRep class");
+            let color_space = NSString::from_str("NSDeviceRGBColorSpace");
+            let rep: *mut AnyObject = msg_send![rep_class, alloc];
+            // Null planes: the rep allocates its own buffer, filled below.
+            let rep: *mut AnyObject = msg_send![
+                rep,
+                initWithBitmapDataPlanes: std::ptr::null_mut::<*mut u8>(),
+                pixelsWide: width as isize,
+                pixelsHigh: height as isize,
+                bitsPerSample: 8isize,
+                samplesPerPixel: 4isize,
+                hasAlpha: true,
+                isPlanar: false,
+                colorSpaceName: &*color_space,
+                bitmapFormat: NON_PREMULTIPLIED,
+                bytesPerRow: (width * 4) as isize,
+                bitsPerPixel: 32isize
+            ];
+            if rep.is_null() {
+                return false;
+            }
+            let pixels: *mut u8 = msg_send![rep, bitmapData];
+            std::ptr::copy_nonoverlapping(rgba.as_ptr(), pixels, rgba.len());
+
+            let image_class = AnyClass::get(c"NSImage").expect("NSImage class");
+            let size = NSSize {
+                width: width as f64,
+                height: height as f64,
+            };
+            let image: *mut AnyObject = msg_send![image_class, alloc];
+            let image: *mut AnyObject = msg_send![image, initWithSize: size];
+            let _: () = msg_send![image, addRepresentation: rep];
+            let _: () = msg_send![self.view.as_ptr(), setImage: image];
+            let _: () = msg_send![image, release];
+            let _: () = msg_send![rep, release];
+            true
+        }
+    }
+
+    /// Size of the current image in points, if there is one.
+    pub fn image_size(&self) -> Option<(f64, f64)> {
+        unsafe {
+            use objc2_foundation::NSSize;
+            let image: *mut AnyObject = msg_send![self.view.as_ptr(), image];
+            if image.is_null() {
+                return None;
+            }
+            let size: NSSize = msg_send![image, size];
+            Some((size.width, size.height))
+        }
+    }
+
+    /// Set how the image is scaled to the view's bounds.
+    pub fn set_image_scaling(&self, scaling: NSImageScaling) {
+        unsafe {
+            let _: () = msg_send![self.view.as_ptr(), setImageScaling: scaling as u64];
+        }
+    }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &NSView {
+        &self.view
+    }
+}
+
+impl Default for NSImageView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// NSImageView scaling mode.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NSImageScaling {
+    ProportionallyDown = 0,
+    AxesIndependently = 1,
+    None = 2,
+    ProportionallyUpOrDown = 3,
+}
+
+/// NSMenu wrapper for macOS.
+pub struct NSMenu {
+    obj: *mut AnyObject,
+}
+
+unsafe impl Send for NSMenu {}
+unsafe impl Sync for NSMenu {}
+
+impl NSMenu {
+    /// Create an empty menu.
+    pub fn new(title: &str) -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+            use objc2_foundation::NSString;
+
+            let class_name = c"NSMenu";
+            let class = AnyClass::get(class_name).expect("NSMenu class");
+            let ns_title = NSString::from_str(title);
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![obj, initWithTitle: &*ns_title];
+            Self { obj }
+        }
+    }
+
+    /// Get the raw object pointer.
+    pub fn as_ptr(&self) -> *mut AnyObject {
+        self.obj
+    }
+
+    /// Append an item.
+    pub fn add_item(&self, item: &NSMenuItem) {
+        unsafe {
+            let _: () = msg_send![self.obj, addItem: item.as_ptr()];
+        }
+    }
+
+    /// Number of items, including separators.
+    pub fn number_of_items(&self) -> usize {
+        unsafe {
+            let count: isize = msg_send![self.obj, numberOfItems];
+            count.max(0) as usize
+        }
+    }
+
+    /// Show the menu as a context menu at (`x`, `y`) in `view`'s coordinates.
+    ///
+    /// Blocks until the menu is dismissed; the chosen item's action is sent
+    /// before this returns. Returns whether an item was chosen.
+    pub fn popup_at(&self, view: &NSView, x: f64, y: f64) -> bool {
+        unsafe {
+            use objc2_foundation::NSPoint;
+            let nil: *mut AnyObject = std::ptr::null_mut();
+            msg_send![
+                self.obj,
+                popUpMenuPositioningItem: nil,
+                atLocation: NSPoint { x, y },
+                inView: view.as_ptr()
+            ]
+        }
+    }
+
+    /// Set whether items are enabled automatically from their targets.
+    ///
+    /// Turn this off to control items with [`NSMenuItem::set_enabled`].
+    pub fn set_autoenables_items(&self, autoenables: bool) {
+        unsafe {
+            let _: () = msg_send![self.obj, setAutoenablesItems: autoenables];
+        }
+    }
+}
+
+impl Drop for NSMenu {
+    fn drop(&mut self) {
+        // Menus it was added to keep their own reference
+        unsafe {
+            let _: () = msg_send![self.obj, release];
+        }
+    }
+}
+
+/// NSMenuItem wrapper for macOS.
+pub struct NSMenuItem {
+    obj: *mut AnyObject,
+}
+
+unsafe impl Send for NSMenuItem {}
+unsafe impl Sync for NSMenuItem {}
+
+impl NSMenuItem {
+    /// Create an item with a key equivalent ("" for none) and no action.
+    pub fn new(title: &str, key_equivalent: &str) -> Self {
+        unsafe {
+            use objc2::runtime::AnyClass;
+            use objc2_foundation::NSString;
+
+            let class_name = c"NSMenuItem";
+            let class = AnyClass::get(class_name).expect("NSMenuItem class");
+            let ns_title = NSString::from_str(title);
+            let ns_key = NSString::from_str(key_equivalent);
+            let obj: *mut AnyObject = msg_send![class, alloc];
+            let obj: *mut AnyObject = msg_send![
+                obj,
+                initWithTitle: &*ns_title,
+                action: None::<objc2::runtime::Sel>,
+                keyEquivalent: &*ns_key
+            ];
+            Self { obj }
+        }
+    }
+
+    /// Create a separator item.
+    pub fn separator() -> Self {
+        unsafe {
+            let obj: *mut AnyObject = msg_send![objc2::class!(NSMenuItem), separatorItem];
+            // Returned autoreleased; take the reference released on drop.
+            let obj: *mut AnyObject = msg_send![obj, retain];
+            Self { obj }
+        }
+    }
+
+    /// Wrap and retain an existing NSMenuItem, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to an NSMenuItem.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        let _: *mut AnyObject = msg_send![ptr, retain];
+        Self { obj: ptr }
+    }
+
+    /// Get the raw object pointer.
+    pub fn as_ptr(&self) -> *mut AnyObject {
+        self.obj
+    }
+
+    /// Get the title.
+    pub fn title(&self) -> String {
+        unsafe {
+            let ns_string: *mut AnyObject = msg_send![self.obj, title];
+            super::nsstring::to_string(ns_string)
+        }
+    }
+
+    /// Set the action sent when the item is chosen. A null target uses the responder chain.
+    pub fn set_action(&self, target: *mut AnyObject, selector: objc2::runtime::Sel) {
+        unsafe {
+            let _: () = msg_send![self.obj, setTarget: target];
+            let _: () = msg_send![self.obj, setAction: selector];
+        }
+    }
+
+    /// Set the modifiers that must be held with the key equivalent.
+    pub fn set_key_equivalent_modifier_mask(&self, mask: NSEventModifierFlags) {
+        unsafe {
+            let _: () = msg_send![self.obj, setKeyEquivalentModifierMask: mask.0];
+        }
+    }
+
+    /// Attach a submenu.
+    pub fn set_submenu(&self, submenu: &NSMenu) {
+        unsafe {
+            let _: () = msg_send![self.obj, setSubmenu: submenu.as_ptr()];
+        }
+    }
+
+    /// Enable or disable the item.
+    pub fn set_enabled(&self, enabled: bool) {
+        unsafe {
+            let _: () = msg_send![self.obj, setEnabled: enabled];
+        }
+    }
+
+    /// Check if the item is enabled.
+    pub fn is_enabled(&self) -> bool {
+        unsafe { msg_send![self.obj, isEnabled] }
+    }
+
+    /// Show or hide the check mark.
+    pub fn set_checked(&self, checked: bool) {
+        unsafe {
+            // NSControlStateValueOn / NSControlStateValueOff
+            let state: isize = if checked { 1 } else { 0 };
+            let _: () = msg_send![self.obj, setState: state];
+        }
+    }
+
+    /// Check if the check mark is shown.
+    pub fn is_checked(&self) -> bool {
+        unsafe {
+            let state: isize = msg_send![self.obj, state];
+            state == 1
+        }
+    }
+
+    /// Set the tag, an integer identifying the item to its target.
+    pub fn set_tag(&self, tag: isize) {
+        unsafe {
+            let _: () = msg_send![self.obj, setTag: tag];
+        }
+    }
+
+    /// Get the tag.
+    pub fn tag(&self) -> isize {
+        unsafe { msg_send![self.obj, tag] }
+    }
+}
+
+impl Drop for NSMenuItem {
+    fn drop(&mut self) {
+        // Menus it was added to keep their own reference
+        unsafe {
+            let _: () = msg_send![self.obj, release];
+        }
+    }
+}
+
+/// Modifier keys for menu item key equivalents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NSEventModifierFlags(pub u64);
+
+impl NSEventModifierFlags {
+    pub const SHIFT: Self = Self(1 << 17);
+    pub const CONTROL: Self = Self(1 << 18);
+    pub const OPTION: Self = Self(1 << 19);
+    pub const COMMAND: Self = Self(1 << 20);
+}
+
+impl std::ops::BitOr for NSEventModifierFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for NSEventModifierFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// The shared NSApplication instance.
+fn shared_application() -> *mut AnyObject {
+    unsafe { msg_send![objc2::class!(NSApplication), sharedApplication] }
+}
+
+/// Install `menu` as the application's menu bar.
+pub fn set_main_menu(menu: &NSMenu) {
+    unsafe {
+        let _: () = msg_send![shared_application(), setMainMenu: menu.as_ptr()];
+    }
+}
+
+/// Let AppKit fill `menu` with the system Services.
+pub fn set_services_menu(menu: &NSMenu) {
+    unsafe {
+        let _: () = msg_send![shared_application(), setServicesMenu: menu.as_ptr()];
+    }
+}
+
+/// Send `selector` to the first responder that implements it.
+///
+/// Returns false when nothing in the responder chain handled it.
+pub fn send_action_to_first_responder(selector: objc2::runtime::Sel) -> bool {
+    unsafe {
+        let nil: *mut AnyObject = std::ptr::null_mut();
+        msg_send![shared_application(), sendAction: selector, to: nil, from: nil]
+    }
+}
+
+/// Content view of the key window, if there is one.
+pub fn key_window_content_view() -> Option<NSView> {
+    unsafe {
+        let window: *mut AnyObject = msg_send![shared_application(), keyWindow];
+        if window.is_null() {
+            return None;
+        }
+        let view: *mut AnyObject = msg_send![window, contentView];
+        (!view.is_null()).then(|| NSView::from_ptr(view))
+    }
+}
+
+/// Check if the key window's first responder is a text view, such as a text field's field editor.
+pub fn first_responder_is_text_view() -> bool {
+    unsafe {
+        let window: *mut AnyObject = msg_send![shared_application(), keyWindow];
+        if window.is_null() {
+            return false;
+        }
+        let responder: *mut AnyObject = msg_send![window, firstResponder];
+        if responder.is_null() {
+            return false;
+        }
+        msg_send![responder, isKindOfClass: objc2::class!(NSText)]
+    }
+}
+
+/// Helper to get NSWindow from a winit window.
+/// Note: This requires winit as a dependency, which is not included here.
+/// The app runner should handle window embedding directly.
+pub fn get_nswindow_from_winit(_window: &dyn std::any::Any) -> *mut AnyObject {
+    // This is platform-specific - winit provides access to the native window
+    // For now, return a placeholder - in practice, you'd use winit's native window access
+    std::ptr::null_mut()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_subviews_moves_listed_views_to_the_front() {
+        let parent = NSView::new();
+        let views = [NSView::new(), NSView::new(), NSView::new()];
+        for view in &views {
+            parent.add_subview(view);
+        }
+        parent.order_subviews(&[views[2].as_ptr(), views[0].as_ptr()]);
+        let order: Vec<_> = (0..3).map(|i| parent.subview_at(i).unwrap().as_ptr()).collect();
+        assert_eq!(order, [views[1].as_ptr(), views[2].as_ptr(), views[0].as_ptr()]);
+    }
+
+    #[test]
+    fn test_text_field_string_value_round_trip() {
+        let field = NSTextField::new();
+        field.set_string_value("Grüße, 世界 ✓");
+        assert_eq!(field.string_value(), "Grüße, 世界 ✓");
+    }
+
+    #[test]
+    fn test_text_field_placeholder_round_trip() {
+        let field = NSTextField::new();
+        assert_eq!(field.placeholder_string(), "");
+        field.set_placeholder_string("Suchen… 検索");
+        assert_eq!(field.placeholder_string(), "Suchen… 検索");
+    }
+
+    #[test]
+    fn test_button_title_round_trip() {
+        let button = NSButton::new();
+        button.set_title("Save…");
+        assert_eq!(button.title(), "Save…");
+    }
+
+    #[test]
+    fn test_check_box_mixed_state() {
+        let button = NSButton::new();
+        button.set_button_type(NSButtonType::Switch);
+        button.set_allows_mixed_state(true);
+        button.set_state(NSControlStateValue::Mixed);
+        assert_eq!(button.state(), NSControlStateValue::Mixed);
+        button.set_state(NSControlStateValue::On);
+        assert_eq!(button.state(), NSControlStateValue::On);
+    }
+
+    #[test]
+    fn test_pop_up_button_selection() {
+        let popup = NSPopUpButton::new();
+        for title in ["Small", "Medium", "Large"] {
+            popup.add_item(title);
+        }
+        assert_eq!(popup.number_of_items(), 3);
+        popup.select_item(2);
+        assert_eq!(popup.index_of_selected_item(), Some(2));
+        popup.remove_all_items();
+        assert_eq!(popup.index_of_selected_item(), None);
+    }
+
+    #[test]
+    fn test_combo_box_items_and_text() {
+        let combo = NSComboBox::new();
+        for title in ["Austria", "Belgium", "Canada"] {
+            combo.add_item(title);
+        }
+        assert_eq!(combo.number_of_items(), 3);
+        combo.select_item(1);
+        assert_eq!(combo.index_of_selected_item(), Some(1));
+        assert_eq!(combo.string_value(), "Belgium");
+        combo.set_string_value("Côte d’Ivoire");
+        assert_eq!(combo.string_value(), "Côte d’Ivoire");
+        combo.set_number_of_visible_items(4);
+        assert_eq!(combo.number_of_visible_items(), 4);
+    }
+
+    #[test]
+    fn test_segmented_control_selection() {
+        let control = NSSegmentedControl::new();
+        control.set_segment_count(3);
+        for (segment, label) in ["Day", "Week", "Month"].into_iter().enumerate() {
+            control.set_label(label, segment);
+        }
+        assert_eq!(control.segment_count(), 3);
+        assert_eq!(control.selected_segment(), None);
+        control.set_selected_segment(1);
+        assert_eq!(control.selected_segment(), Some(1));
+    }
+
+    #[test]
+    fn test_scroll_view_scrolls_flipped_document() {
+        let scroll = NSScrollView::new();
+        scroll.view().set_frame(0.0, 0.0, 200.0, 100.0);
+        let document = NSView::new_flipped();
+        document.set_frame(0.0, 0.0, 200.0, 400.0);
+        scroll.set_document_view(&document);
+
+        assert_eq!(scroll.scroll_position(), (0.0, 0.0));
+        scroll.scroll_to(0.0, 150.0);
+        assert_eq!(scroll.scroll_position(), (0.0, 150.0));
+    }
+
+    #[test]
+    fn test_date_picker_date_round_trip() {
+        let picker = NSDatePicker::new();
+        picker.set_date_value(1_700_000_000.0);
+        assert_eq!(picker.date_value(), 1_700_000_000.0);
+    }
+
+    #[test]
+    fn test_image_view_reports_rgba_image_size() {
+        let image_view = NSImageView::new();
+        assert_eq!(image_view.image_size(), None);
+        assert!(!image_view.set_image_rgba(2, 2, &[0; 12]));
+        assert!(image_view.set_image_rgba(3, 2, &[255; 24]));
+        assert_eq!(image_view.image_size(), Some((3.0, 2.0)));
+        assert!(!image_view.set_image_data(b"not an image"));
+    }
+
+    #[test]
+    fn test_menu_item_state_round_trip() {
+        let menu = NSMenu::new("File");
+        menu.set_autoenables_items(false);
+        let item = NSMenuItem::new("Open…", "o");
+        item.set_tag(3);
+        item.set_enabled(false);
+        item.set_checked(true);
+        menu.add_item(&item);
+        menu.add_item(&NSMenuItem::separator());
+
+        assert_eq!(menu.number_of_items(), 2);
+        assert_eq!(item.title(), "Open…");
+        assert_eq!(item.tag(), 3);
+        assert!(!item.is_enabled());
+        assert!(item.is_checked());
+    }
 }
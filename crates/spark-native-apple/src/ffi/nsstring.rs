@@ -0,0 +1,51 @@
+//! NSString conversion shared by the AppKit and UIKit bindings.
+
+use objc2::msg_send;
+use objc2::runtime::AnyObject;
+use std::ffi::c_char;
+
+/// `NSUTF8StringEncoding`.
+const NS_UTF8_STRING_ENCODING: usize = 4;
+
+/// Copy an NSString into a Rust `String`.
+///
+/// Returns an empty string for nil. The UTF-8 buffer is owned by the
+/// NSString, so the bytes are copied before returning.
+///
+/// # Safety
+///
+/// `ns_string` must be nil or point to a live NSString.
+pub(crate) unsafe fn to_string(ns_string: *mut AnyObject) -> String {
+    if ns_string.is_null() {
+        return String::new();
+    }
+    unsafe {
+        let utf8: *const c_char = msg_send![ns_string, UTF8String];
+        if utf8.is_null() {
+            return String::new();
+        }
+        let len: usize = msg_send![ns_string, lengthOfBytesUsingEncoding: NS_UTF8_STRING_ENCODING];
+        let bytes = std::slice::from_raw_parts(utf8.cast::<u8>(), len);
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objc2_foundation::NSString;
+
+    #[test]
+    fn test_round_trip_preserves_unicode() {
+        for text in ["", "hello", "héllo wörld ✓ 日本語"] {
+            let ns_string = NSString::from_str(text);
+            let ptr = &*ns_string as *const NSString as *mut AnyObject;
+            assert_eq!(unsafe { to_string(ptr) }, text);
+        }
+    }
+
+    #[test]
+    fn test_nil_is_empty() {
+        assert_eq!(unsafe { to_string(std::ptr::null_mut()) }, "");
+    }
+}
@@ -1,9 +1,13 @@
 //! UIKit (iOS/iPadOS) FFI bindings.
 
 use objc2::msg_send;
-use objc2::runtime::{AnyObject, Class};
+use objc2::runtime::AnyObject;
+use spark_core::Color;
 
 /// UIView wrapper for iOS.
+///
+/// Owns one strong reference to the view, released on drop, with the same
+/// rules as the AppKit `NSView` wrapper.
 pub struct UIView {
     pub(crate) obj: *mut AnyObject,
 }
@@ -15,20 +19,43 @@ impl UIView {
     /// Create a new UIView.
     pub fn new() -> Self {
         unsafe {
-            use std::ffi::CStr;
-            let class_name = CStr::from_bytes_with_nul(b"UIView\0").unwrap();
-            let class = Class::get(class_name).expect("UIView class");
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"UIView";
+            let class = AnyClass::get(class_name).expect("UIView class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
             Self { obj }
         }
     }
 
+    /// Wrap and retain an existing UIView, such as a window's root view.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to a UIView (or subclass).
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        let _: *mut AnyObject = msg_send![ptr, retain];
+        Self { obj: ptr }
+    }
+
     /// Get the raw object pointer.
     pub fn as_ptr(&self) -> *mut AnyObject {
         self.obj
     }
 
+    /// Wrap the view a handle refers to.
+    pub(crate) fn from_handle(view: &crate::RetainedView) -> Self {
+        // SAFETY: handles are only made for live views of this platform.
+        unsafe { Self::from_ptr(view.as_ptr()) }
+    }
+
+    /// A handle that keeps the view alive independently of this wrapper.
+    pub fn handle(&self) -> crate::NativeViewHandle {
+        // SAFETY: the wrapper owns a reference, so the view is live.
+        unsafe { crate::NativeViewHandle::from_ptr(self.obj) }
+    }
+
     /// Set the frame of the view.
     pub fn set_frame(&self, x: f64, y: f64, width: f64, height: f64) {
         unsafe {
@@ -48,19 +75,109 @@ impl UIView {
         }
     }
 
+    /// The view's superview, if it has one.
+    pub fn superview(&self) -> Option<UIView> {
+        unsafe {
+            let superview: *mut AnyObject = msg_send![self.obj, superview];
+            (!superview.is_null()).then(|| UIView::from_ptr(superview))
+        }
+    }
+
     /// Remove from superview.
     pub fn remove_from_superview(&self) {
         unsafe {
             let _: () = msg_send![self.obj, removeFromSuperview];
         }
     }
+
+    /// Show or hide the view.
+    pub fn set_visible(&self, visible: bool) {
+        unsafe {
+            let _: () = msg_send![self.obj, setHidden: !visible];
+        }
+    }
+
+    /// Move the view above its siblings.
+    pub fn bring_to_front(&self) {
+        unsafe {
+            let superview: *mut AnyObject = msg_send![self.obj, superview];
+            if !superview.is_null() {
+                let _: () = msg_send![superview, bringSubviewToFront: self.obj];
+            }
+        }
+    }
+
+    /// Force a light or dark style from an AppKit appearance name.
+    ///
+    /// "NSAppearanceNameDarkAqua" selects dark and "NSAppearanceNameAqua"
+    /// light; any other name follows the system again.
+    pub fn set_appearance(&self, name: &str) {
+        // UIUserInterfaceStyle
+        let style: isize = match name {
+            "NSAppearanceNameDarkAqua" => 2,
+            "NSAppearanceNameAqua" => 1,
+            _ => 0,
+        };
+        unsafe {
+            let _: () = msg_send![self.obj, setOverrideUserInterfaceStyle: style];
+        }
+    }
+
+    /// Set the tint color inherited by the view's subviews, or `None` to inherit the superview's.
+    pub fn set_tint_color(&self, color: Option<Color>) {
+        unsafe {
+            let color: *mut AnyObject = match color {
+                Some(color) => {
+                    use objc2::runtime::AnyClass;
+                    let class = AnyClass::get(c"UIColor").expect("UIColor class");
+                    msg_send![
+                        class,
+                        colorWithRed: color.r as f64,
+                        green: color.g as f64,
+                        blue: color.b as f64,
+                        alpha: color.a as f64,
+                    ]
+                }
+                None => std::ptr::null_mut(),
+            };
+            let _: () = msg_send![self.obj, setTintColor: color];
+        }
+    }
+
+    /// Get the view's intrinsic content size.
+    /// Returns (width, height); a dimension is -1.0 (`UIViewNoIntrinsicMetric`) if it has none.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        unsafe {
+            use objc2_foundation::CGSize;
+            let size: CGSize = msg_send![self.obj, intrinsicContentSize];
+            (size.width, size.height)
+        }
+    }
+
+    /// Get the smallest size that satisfies the view's constraints.
+    pub fn fitting_size(&self) -> (f64, f64) {
+        unsafe {
+            use objc2_foundation::CGSize;
+            // UILayoutFittingCompressedSize
+            let target = CGSize { width: 0.0, height: 0.0 };
+            let size: CGSize = msg_send![self.obj, systemLayoutSizeFittingSize: target];
+            (size.width, size.height)
+        }
+    }
+}
+
+impl Default for UIView {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Drop for UIView {
     fn drop(&mut self) {
-        // Don't release here - views are retained by their superviews
-        // If we manually release, we'll cause over-release crashes
-        // The Objective-C runtime will handle memory management
+        // A superview or handle may still hold its own reference
+        unsafe {
+            let _: () = msg_send![self.obj, release];
+        }
     }
 }
 
@@ -70,14 +187,19 @@ pub struct UIButton {
 }
 
 impl UIButton {
-    /// Create a new UIButton.
+    /// Create a new system-style UIButton.
     pub fn new() -> Self {
         unsafe {
-            use std::ffi::CStr;
-            let class_name = CStr::from_bytes_with_nul(b"UIButton\0").unwrap();
-            let class = Class::get(class_name).expect("UIButton class");
-            let obj: *mut AnyObject = msg_send![class, alloc];
-            let obj: *mut AnyObject = msg_send![obj, init];
+            use objc2::runtime::AnyClass;
+
+            // UIButtonTypeSystem; plain `init` gives an invisible custom button.
+            const SYSTEM: isize = 1;
+
+            let class_name = c"UIButton";
+            let class = AnyClass::get(class_name).expect("UIButton class");
+            let obj: *mut AnyObject = msg_send![class, buttonWithType: SYSTEM];
+            // Returned autoreleased; keep it alive until a superview retains it.
+            let obj: *mut AnyObject = msg_send![obj, retain];
             Self {
                 view: UIView { obj },
             }
@@ -89,7 +211,46 @@ impl UIButton {
         unsafe {
             use objc2_foundation::NSString;
             let ns_string = NSString::from_str(title);
-            let _: () = msg_send![self.view.as_ptr(), setTitle: &*ns_string forState: state];
+            let _: () = msg_send![self.view.as_ptr(), setTitle: &*ns_string, forState: state as usize];
+        }
+    }
+
+    /// Send `selector` to `target` when any of `events` occur.
+    ///
+    /// The button does not retain `target`.
+    pub fn add_target(&self, target: *mut AnyObject, selector: objc2::runtime::Sel, events: UIControlEvents) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                addTarget: target,
+                action: selector,
+                forControlEvents: events as usize
+            ];
+        }
+    }
+
+    /// Stop sending actions to `target`.
+    pub fn remove_target(&self, target: *mut AnyObject) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                removeTarget: target,
+                action: std::ptr::null::<std::ffi::c_void>(),
+                forControlEvents: usize::MAX
+            ];
+        }
+    }
+
+    /// Get the button's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+
+    /// Get the title for a control state.
+    pub fn title(&self, state: UIControlState) -> String {
+        unsafe {
+            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), titleForState: state as usize];
+            super::nsstring::to_string(ns_string)
         }
     }
 
@@ -99,18 +260,36 @@ impl UIButton {
     }
 }
 
+impl Default for UIButton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// UITextField wrapper for iOS.
 pub struct UITextField {
     view: UIView,
 }
 
 impl UITextField {
+    /// Wrap and retain an existing UITextField, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to a UITextField.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: UIView::from_ptr(ptr),
+        }
+    }
+
     /// Create a new UITextField.
     pub fn new() -> Self {
         unsafe {
-            use std::ffi::CStr;
-            let class_name = CStr::from_bytes_with_nul(b"UITextField\0").unwrap();
-            let class = Class::get(class_name).expect("UITextField class");
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"UITextField";
+            let class = AnyClass::get(class_name).expect("UITextField class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
             Self {
@@ -132,11 +311,58 @@ impl UITextField {
     pub fn text(&self) -> String {
         unsafe {
             let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), text];
-            if ns_string.is_null() {
-                return String::new();
-            }
-            // Convert NSString to Rust String (simplified)
-            String::new()
+            super::nsstring::to_string(ns_string)
+        }
+    }
+
+    /// Set the text shown while the field is empty.
+    pub fn set_placeholder(&self, placeholder: &str) {
+        unsafe {
+            use objc2_foundation::NSString;
+            let ns_string = NSString::from_str(placeholder);
+            let _: () = msg_send![self.view.as_ptr(), setPlaceholder: &*ns_string];
+        }
+    }
+
+    /// Get the text shown while the field is empty.
+    pub fn placeholder(&self) -> String {
+        unsafe {
+            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), placeholder];
+            super::nsstring::to_string(ns_string)
+        }
+    }
+
+    /// Draw the standard rounded-rect border.
+    pub fn set_rounded_border(&self) {
+        unsafe {
+            // UITextBorderStyleRoundedRect
+            let _: () = msg_send![self.view.as_ptr(), setBorderStyle: 3isize];
+        }
+    }
+
+    /// Send `selector` to `target` when any of `events` occur.
+    ///
+    /// The text field does not retain `target`.
+    pub fn add_target(&self, target: *mut AnyObject, selector: objc2::runtime::Sel, events: UIControlEvents) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                addTarget: target,
+                action: selector,
+                forControlEvents: events as usize
+            ];
+        }
+    }
+
+    /// Stop sending actions to `target`.
+    pub fn remove_target(&self, target: *mut AnyObject) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                removeTarget: target,
+                action: std::ptr::null::<std::ffi::c_void>(),
+                forControlEvents: usize::MAX
+            ];
         }
     }
 
@@ -146,6 +372,12 @@ impl UITextField {
     }
 }
 
+impl Default for UITextField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// UILabel wrapper for iOS.
 pub struct UILabel {
     view: UIView,
@@ -155,9 +387,10 @@ impl UILabel {
     /// Create a new UILabel.
     pub fn new() -> Self {
         unsafe {
-            use std::ffi::CStr;
-            let class_name = CStr::from_bytes_with_nul(b"UILabel\0").unwrap();
-            let class = Class::get(class_name).expect("UILabel class");
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"UILabel";
+            let class = AnyClass::get(class_name).expect("UILabel class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
             Self {
@@ -175,12 +408,31 @@ impl UILabel {
         }
     }
 
+    /// Get the label's text.
+    pub fn text(&self) -> String {
+        unsafe {
+            let ns_string: *mut AnyObject = msg_send![self.view.as_ptr(), text];
+            super::nsstring::to_string(ns_string)
+        }
+    }
+
+    /// Get the label's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+
     /// Get the underlying view.
     pub fn view(&self) -> &UIView {
         &self.view
     }
 }
 
+impl Default for UILabel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// UIControlState for button states.
 #[repr(usize)]
 pub enum UIControlState {
@@ -190,6 +442,15 @@ pub enum UIControlState {
     Selected = 4,
 }
 
+/// UIControlEvents that can trigger a target/action message.
+#[derive(Clone, Copy, Debug)]
+#[repr(usize)]
+pub enum UIControlEvents {
+    TouchUpInside = 1 << 6,
+    ValueChanged = 1 << 12,
+    EditingChanged = 1 << 17,
+}
+
 /// UISlider wrapper for iOS.
 pub struct UISlider {
     view: UIView,
@@ -199,9 +460,10 @@ impl UISlider {
     /// Create a new UISlider.
     pub fn new() -> Self {
         unsafe {
-            use std::ffi::CStr;
-            let class_name = CStr::from_bytes_with_nul(b"UISlider\0").unwrap();
-            let class = Class::get(class_name).expect("UISlider class");
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"UISlider";
+            let class = AnyClass::get(class_name).expect("UISlider class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
             Self {
@@ -210,6 +472,17 @@ impl UISlider {
         }
     }
 
+    /// Wrap and retain an existing UISlider, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to a UISlider.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: UIView::from_ptr(ptr),
+        }
+    }
+
     /// Set the slider's minimum value.
     pub fn set_minimum_value(&self, value: f32) {
         unsafe {
@@ -239,12 +512,44 @@ impl UISlider {
         }
     }
 
+    /// Send `selector` to `target` when any of `events` occur.
+    ///
+    /// The slider does not retain `target`.
+    pub fn add_target(&self, target: *mut AnyObject, selector: objc2::runtime::Sel, events: UIControlEvents) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                addTarget: target,
+                action: selector,
+                forControlEvents: events as usize
+            ];
+        }
+    }
+
+    /// Stop sending actions to `target`.
+    pub fn remove_target(&self, target: *mut AnyObject) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                removeTarget: target,
+                action: std::ptr::null::<std::ffi::c_void>(),
+                forControlEvents: usize::MAX
+            ];
+        }
+    }
+
     /// Get the underlying view.
     pub fn view(&self) -> &UIView {
         &self.view
     }
 }
 
+impl Default for UISlider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// UISwitch wrapper for iOS.
 pub struct UISwitch {
     view: UIView,
@@ -254,9 +559,10 @@ impl UISwitch {
     /// Create a new UISwitch.
     pub fn new() -> Self {
         unsafe {
-            use std::ffi::CStr;
-            let class_name = CStr::from_bytes_with_nul(b"UISwitch\0").unwrap();
-            let class = Class::get(class_name).expect("UISwitch class");
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"UISwitch";
+            let class = AnyClass::get(class_name).expect("UISwitch class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
             Self {
@@ -265,7 +571,18 @@ impl UISwitch {
         }
     }
 
-    /// Set the switch state (on/off).
+    /// Wrap and retain an existing UISwitch, such as the sender of an action.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to a UISwitch.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: UIView::from_ptr(ptr),
+        }
+    }
+
+    /// Set the switch state (on/off). Does not send actions.
     pub fn set_on(&self, on: bool) {
         unsafe {
             let _: () = msg_send![self.view.as_ptr(), setOn: on];
@@ -280,12 +597,49 @@ impl UISwitch {
         }
     }
 
+    /// Get the switch's intrinsic content size.
+    pub fn intrinsic_content_size(&self) -> (f64, f64) {
+        self.view.intrinsic_content_size()
+    }
+
+    /// Send `selector` to `target` when any of `events` occur.
+    ///
+    /// The switch does not retain `target`.
+    pub fn add_target(&self, target: *mut AnyObject, selector: objc2::runtime::Sel, events: UIControlEvents) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                addTarget: target,
+                action: selector,
+                forControlEvents: events as usize
+            ];
+        }
+    }
+
+    /// Stop sending actions to `target`.
+    pub fn remove_target(&self, target: *mut AnyObject) {
+        unsafe {
+            let _: () = msg_send![
+                self.view.as_ptr(),
+                removeTarget: target,
+                action: std::ptr::null::<std::ffi::c_void>(),
+                forControlEvents: usize::MAX
+            ];
+        }
+    }
+
     /// Get the underlying view.
     pub fn view(&self) -> &UIView {
         &self.view
     }
 }
 
+impl Default for UISwitch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// UIProgressView wrapper for iOS.
 pub struct UIProgressView {
     view: UIView,
@@ -295,9 +649,10 @@ impl UIProgressView {
     /// Create a new UIProgressView.
     pub fn new() -> Self {
         unsafe {
-            use std::ffi::CStr;
-            let class_name = CStr::from_bytes_with_nul(b"UIProgressView\0").unwrap();
-            let class = Class::get(class_name).expect("UIProgressView class");
+            use objc2::runtime::AnyClass;
+
+            let class_name = c"UIProgressView";
+            let class = AnyClass::get(class_name).expect("UIProgressView class");
             let obj: *mut AnyObject = msg_send![class, alloc];
             let obj: *mut AnyObject = msg_send![obj, init];
             Self {
@@ -306,6 +661,17 @@ impl UIProgressView {
         }
     }
 
+    /// Wrap and retain an existing UIProgressView.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is valid and points to a UIProgressView.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        Self {
+            view: UIView::from_ptr(ptr),
+        }
+    }
+
     /// Set the progress (0.0 to 1.0).
     pub fn set_progress(&self, progress: f32) {
         unsafe {
@@ -318,3 +684,9 @@ impl UIProgressView {
         &self.view
     }
 }
+
+impl Default for UIProgressView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -98,6 +98,12 @@ impl LayoutBridge {
         }
     }
 
+    /// Convert a taffy point to a native point in the content view (e.g. to place a popup).
+    pub fn taffy_to_native_point(pos: Vec2) -> (f64, f64) {
+        // Both content views have a top-left origin, like taffy
+        (pos.x as f64, pos.y as f64)
+    }
+
     /// Update a native view's frame based on taffy layout.
     pub fn update_native_view_frame(
         view_handle: &crate::NativeViewHandle,
@@ -114,15 +120,15 @@ impl LayoutBridge {
                 use crate::ffi::appkit::NSView;
                 // Create a temporary view wrapper to call set_frame
                 // In practice, you'd store the view properly
-                let view = NSView { obj: *ptr };
+                let view = NSView::from_handle(ptr);
                 view.set_frame(x, y, width, height);
             }
             #[cfg(target_os = "ios")]
-            crate::NativeViewHandle::UIKit(ptr) => unsafe {
+            crate::NativeViewHandle::UIKit(ptr) => {
                 use crate::ffi::uikit::UIView;
-                let view = UIView { obj: *ptr };
+                let view = UIView::from_handle(ptr);
                 view.set_frame(x, y, width, height);
-            },
+            }
         }
     }
 }
@@ -13,19 +13,21 @@ mod view_manager;
 #[cfg(target_os = "macos")]
 pub mod ffi {
     pub mod appkit;
+    pub(crate) mod nsstring;
 }
 
 #[cfg(target_os = "ios")]
 pub mod ffi {
+    pub(crate) mod nsstring;
     pub mod uikit;
 }
 
 pub mod widgets;
 
-pub use events::EventBridge;
+pub use events::{is_main_thread, run_on_main, ActionTarget, EventBridge, RetainedView};
 pub use layout::LayoutBridge;
 pub use native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
-pub use view_manager::ViewManager;
+pub use view_manager::{NativeAppearance, ViewManager, ViewPlacement};
 
 // Re-export for convenience
 pub use spark_layout;
@@ -45,6 +47,45 @@ impl NativeWidgetRegistration for widgets::NativeButton {
     }
 }
 
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativeCheckbox {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativeComboBox {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativeDatePicker {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
+impl NativeWidgetRegistration for widgets::NativeHostView {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativeImageView {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
 impl NativeWidgetRegistration for widgets::NativeLabel {
     fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
         let view_handle = <Self as NativeWidget>::native_view(self);
@@ -52,6 +93,38 @@ impl NativeWidgetRegistration for widgets::NativeLabel {
     }
 }
 
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativePopUpButton {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativeRadioGroup {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativeScrollView {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl NativeWidgetRegistration for widgets::NativeSegmentedControl {
+    fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        manager.register_widget(widget_id, view_handle);
+    }
+}
+
 impl NativeWidgetRegistration for widgets::NativeTextField {
     fn register_with_manager(&self, widget_id: spark_layout::WidgetId, manager: &mut ViewManager) {
         let view_handle = <Self as NativeWidget>::native_view(self);
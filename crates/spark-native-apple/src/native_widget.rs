@@ -1,20 +1,48 @@
 //! Base trait and types for native widgets.
 
+use crate::events::RetainedView;
+use objc2::runtime::AnyObject;
 use spark_input::InputEvent;
 use spark_layout::taffy;
 use spark_widgets::{EventContext, EventResponse, Widget};
 
 /// Handle to a native view (platform-specific).
-#[derive(Clone, Debug)]
+///
+/// Every handle owns a strong reference, so a registered view stays alive
+/// until the view manager drops its handle, even if the widget that created
+/// it is gone. Handles compare equal when they refer to the same view.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum NativeViewHandle {
     #[cfg(target_os = "macos")]
-    AppKit(*mut objc2::runtime::AnyObject),
+    AppKit(RetainedView),
     #[cfg(target_os = "ios")]
-    UIKit(*mut objc2::runtime::AnyObject),
+    UIKit(RetainedView),
 }
 
-unsafe impl Send for NativeViewHandle {}
-unsafe impl Sync for NativeViewHandle {}
+impl NativeViewHandle {
+    /// Retain the view at `ptr` in a new handle.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a live NSView on macOS, or UIView on iOS.
+    pub unsafe fn from_ptr(ptr: *mut AnyObject) -> Self {
+        #[cfg(target_os = "macos")]
+        let handle = Self::AppKit(RetainedView::new(ptr));
+        #[cfg(target_os = "ios")]
+        let handle = Self::UIKit(RetainedView::new(ptr));
+        handle
+    }
+
+    /// The view, without transferring ownership.
+    pub fn as_ptr(&self) -> *mut AnyObject {
+        match self {
+            #[cfg(target_os = "macos")]
+            Self::AppKit(view) => view.as_ptr(),
+            #[cfg(target_os = "ios")]
+            Self::UIKit(view) => view.as_ptr(),
+        }
+    }
+}
 
 /// Trait for widgets that wrap native Apple views.
 pub trait NativeWidget: Widget {
@@ -1,17 +1,74 @@
 //! View manager - manages native view hierarchy and lifecycle.
 
+use spark_core::{Color, Vec2};
 use spark_layout::WidgetId;
-use std::collections::HashMap;
+use spark_widgets::{Theme, ThemeMode};
+use std::collections::{HashMap, HashSet};
 use crate::NativeViewHandle;
 
+/// Appearance applied to native views so they match the GPU-rendered theme.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NativeAppearance {
+    /// Light or dark controls.
+    pub mode: ThemeMode,
+    /// Accent color for controls that support one; `None` keeps the system accent.
+    pub tint: Option<Color>,
+}
+
+impl NativeAppearance {
+    /// The appearance matching `theme`, tinted with its primary color.
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            mode: theme.mode,
+            tint: Some(theme.colors.primary),
+        }
+    }
+
+    /// AppKit appearance name for the mode; UIKit maps it to a user interface style.
+    fn appearance_name(self) -> &'static str {
+        match self.mode {
+            ThemeMode::Light => "NSAppearanceNameAqua",
+            ThemeMode::Dark => "NSAppearanceNameDarkAqua",
+        }
+    }
+}
+
+/// Where a view goes relative to its layout, for views moved by GPU scrolling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewPlacement {
+    /// Offset added to the layout position, in the units of the layouts.
+    pub offset: (f32, f32),
+    /// Whether the view is shown; views scrolled out of their viewport are hidden.
+    pub visible: bool,
+}
+
+impl Default for ViewPlacement {
+    fn default() -> Self {
+        Self {
+            offset: (0.0, 0.0),
+            visible: true,
+        }
+    }
+}
+
 /// Manages the native view hierarchy and maps widget IDs to native views.
 pub struct ViewManager {
     /// Map from widget ID to native view handle.
     views: HashMap<WidgetId, NativeViewHandle>,
     /// Map from widget ID to parent widget ID.
     parent_map: HashMap<WidgetId, WidgetId>,
+    /// Map from container widget ID to its document view and content widget ID.
+    documents: HashMap<WidgetId, (NativeViewHandle, WidgetId)>,
     /// Root view handle (NSView/UIView that contains all native widgets).
     root_view: Option<NativeViewHandle>,
+    /// Widgets in paint order, back to front.
+    paint_order: Vec<WidgetId>,
+    /// Widgets whose views are hidden because GPU content covers them.
+    covered: HashSet<WidgetId>,
+    /// Widgets whose views are hidden because they are scrolled out of view.
+    scrolled_out: HashSet<WidgetId>,
+    /// Appearance applied to the root view and every registered view.
+    appearance: Option<NativeAppearance>,
 }
 
 impl ViewManager {
@@ -20,25 +77,44 @@ impl ViewManager {
         Self {
             views: HashMap::new(),
             parent_map: HashMap::new(),
+            documents: HashMap::new(),
             root_view: None,
+            paint_order: Vec::new(),
+            covered: HashSet::new(),
+            scrolled_out: HashSet::new(),
+            appearance: None,
         }
     }
 
     /// Register a native widget with its view handle.
+    ///
+    /// Registering the same view for the same widget again is a no-op.
     pub fn register_widget(&mut self, widget_id: WidgetId, view_handle: NativeViewHandle) {
+        if self.views.get(&widget_id) == Some(&view_handle) {
+            return;
+        }
+
         // Make the view visible and set up for rendering
         #[cfg(target_os = "macos")]
         {
             let crate::NativeViewHandle::AppKit(ptr) = &view_handle;
             use crate::ffi::appkit::NSView;
-            // NSView wrapper creation is safe as it just wraps the pointer
-            let view = NSView { obj: *ptr };
+            let view = NSView::from_handle(ptr);
             view.set_visible(true);
             view.set_wants_layer(true);
             // Set a background color temporarily for debugging
             // view.set_background_color(1.0, 0.0, 0.0, 0.5); // Red with transparency
         }
+        #[cfg(target_os = "ios")]
+        {
+            let crate::NativeViewHandle::UIKit(ptr) = &view_handle;
+            let view = crate::ffi::uikit::UIView::from_handle(ptr);
+            view.set_visible(true);
+        }
         
+        if let Some(appearance) = self.appearance {
+            Self::set_tint(&view_handle, appearance.tint);
+        }
         self.views.insert(widget_id, view_handle);
         
         // If we have a root view, add this view to it
@@ -54,18 +130,16 @@ impl ViewManager {
                 #[cfg(target_os = "macos")]
                 (NativeViewHandle::AppKit(child_ptr), NativeViewHandle::AppKit(parent_ptr)) => {
                     use crate::ffi::appkit::NSView;
-                    let child = NSView { obj: *child_ptr };
-                    let parent = NSView { obj: *parent_ptr };
+                    let child = NSView::from_handle(child_ptr);
+                    let parent = NSView::from_handle(parent_ptr);
                     parent.add_subview(&child);
                 }
                 #[cfg(target_os = "ios")]
                 (NativeViewHandle::UIKit(child_ptr), NativeViewHandle::UIKit(parent_ptr)) => {
-                    unsafe {
-                        use crate::ffi::uikit::UIView;
-                        let child = UIView { obj: *child_ptr };
-                        let parent = UIView { obj: *parent_ptr };
-                        parent.add_subview(&child);
-                    }
+                    use crate::ffi::uikit::UIView;
+                    let child = UIView::from_handle(child_ptr);
+                    let parent = UIView::from_handle(parent_ptr);
+                    parent.add_subview(&child);
                 }
             }
         }
@@ -84,27 +158,94 @@ impl ViewManager {
                 #[cfg(target_os = "macos")]
                 NativeViewHandle::AppKit(ptr) => {
                     use crate::ffi::appkit::NSView;
-                    let view = NSView { obj: ptr };
+                    let view = NSView::from_handle(&ptr);
                     view.remove_from_superview();
                 }
                 #[cfg(target_os = "ios")]
                 NativeViewHandle::UIKit(ptr) => {
-                    unsafe {
-                        use crate::ffi::uikit::UIView;
-                        let view = UIView { obj: ptr };
-                        view.remove_from_superview();
-                    }
+                    use crate::ffi::uikit::UIView;
+                    let view = UIView::from_handle(&ptr);
+                    view.remove_from_superview();
                 }
             }
         }
         self.parent_map.remove(&widget_id);
     }
 
+    /// Make the registered views match the native widgets currently in the tree.
+    ///
+    /// Views no longer present are removed from their superview and new ones
+    /// are registered. Widget ids are reassigned on every layout pass, so
+    /// views are matched by pointer and re-keyed under their new ids. Parents
+    /// and document views are keyed by id too and must be set again afterwards.
+    pub fn sync_widgets(&mut self, current: Vec<(WidgetId, NativeViewHandle)>) {
+        let vanished: Vec<WidgetId> = self
+            .views
+            .iter()
+            .filter(|(_, handle)| !current.iter().any(|(_, h)| h == *handle))
+            .map(|(id, _)| *id)
+            .collect();
+        for widget_id in vanished {
+            self.unregister_widget(widget_id);
+        }
+
+        let previous = std::mem::take(&mut self.views);
+        for (widget_id, view_handle) in current {
+            if previous.values().any(|h| *h == view_handle) {
+                self.views.insert(widget_id, view_handle);
+            } else {
+                self.register_widget(widget_id, view_handle);
+            }
+        }
+        self.parent_map.clear();
+        self.documents.clear();
+    }
+
+    /// Remove every registered view from the hierarchy and forget the root view.
+    pub fn clear(&mut self) {
+        let ids: Vec<WidgetId> = self.views.keys().copied().collect();
+        for widget_id in ids {
+            self.unregister_widget(widget_id);
+        }
+        self.parent_map.clear();
+        self.documents.clear();
+        self.paint_order.clear();
+        self.covered.clear();
+        self.scrolled_out.clear();
+        self.root_view = None;
+    }
+
     /// Set the parent of a widget.
     pub fn set_parent(&mut self, widget_id: WidgetId, parent_id: WidgetId) {
         self.parent_map.insert(widget_id, parent_id);
     }
 
+    /// Set the view that a container's native children are placed in.
+    ///
+    /// Children are positioned relative to the layout of `content_id`, and the
+    /// document view is sized to it.
+    pub fn set_document_view(
+        &mut self,
+        container_id: WidgetId,
+        document: NativeViewHandle,
+        content_id: WidgetId,
+    ) {
+        self.documents.insert(container_id, (document, content_id));
+    }
+
+    /// Add every widget with a parent to that parent's view.
+    pub fn attach_children(&self) {
+        for (widget_id, parent_id) in &self.parent_map {
+            self.add_to_parent(*widget_id, *parent_id);
+        }
+    }
+
+    /// Whether `update_layouts` needs the layout of this widget.
+    pub fn uses_layout(&self, widget_id: WidgetId) -> bool {
+        self.views.contains_key(&widget_id)
+            || self.documents.values().any(|(_, content)| *content == widget_id)
+    }
+
     /// Get the view handle for a widget.
     pub fn get_view(&self, widget_id: WidgetId) -> Option<&NativeViewHandle> {
         self.views.get(&widget_id)
@@ -118,6 +259,52 @@ impl ViewManager {
     /// Set the root view (container for all native widgets).
     pub fn set_root_view(&mut self, root_view: NativeViewHandle) {
         self.root_view = Some(root_view);
+        self.apply_root_appearance();
+    }
+
+    /// Match native views to `appearance`, now and for views registered later.
+    ///
+    /// Light or dark is set on the root view and its window, so views inherit
+    /// it unless their widget set its own; the tint is set on every view.
+    pub fn set_appearance(&mut self, appearance: NativeAppearance) {
+        if self.appearance == Some(appearance) {
+            return;
+        }
+        self.appearance = Some(appearance);
+        self.apply_root_appearance();
+        for view_handle in self.views.values() {
+            Self::set_tint(view_handle, appearance.tint);
+        }
+    }
+
+    /// The appearance last passed to [`set_appearance`](Self::set_appearance).
+    pub fn appearance(&self) -> Option<NativeAppearance> {
+        self.appearance
+    }
+
+    /// Apply the appearance to the root view, if both are set.
+    fn apply_root_appearance(&self) {
+        let (Some(appearance), Some(root)) = (self.appearance, &self.root_view) else {
+            return;
+        };
+        let name = appearance.appearance_name();
+        match root {
+            #[cfg(target_os = "macos")]
+            NativeViewHandle::AppKit(ptr) => {
+                let view = crate::ffi::appkit::NSView::from_handle(ptr);
+                view.set_appearance(name);
+                // The window's own chrome follows too
+                if let Some(window) = view.window() {
+                    window.set_appearance(name);
+                }
+            }
+            #[cfg(target_os = "ios")]
+            NativeViewHandle::UIKit(ptr) => {
+                let view = crate::ffi::uikit::UIView::from_handle(ptr);
+                view.set_appearance(name);
+                view.set_tint_color(appearance.tint);
+            }
+        }
     }
 
     /// Get the root view.
@@ -126,27 +313,29 @@ impl ViewManager {
     }
 
     /// Add a view to its parent in the native hierarchy.
+    ///
+    /// Containers with a document view receive the child in that view.
     pub fn add_to_parent(&self, widget_id: WidgetId, parent_id: WidgetId) {
-        if let (Some(child_handle), Some(parent_handle)) = (
-            self.views.get(&widget_id),
-            self.views.get(&parent_id),
-        ) {
+        let parent_view = self
+            .documents
+            .get(&parent_id)
+            .map(|(document, _)| document)
+            .or_else(|| self.views.get(&parent_id));
+        if let (Some(child_handle), Some(parent_handle)) = (self.views.get(&widget_id), parent_view) {
             match (child_handle, parent_handle) {
                 #[cfg(target_os = "macos")]
                 (NativeViewHandle::AppKit(child_ptr), NativeViewHandle::AppKit(parent_ptr)) => {
                     use crate::ffi::appkit::NSView;
-                    let child = NSView { obj: *child_ptr };
-                    let parent = NSView { obj: *parent_ptr };
+                    let child = NSView::from_handle(child_ptr);
+                    let parent = NSView::from_handle(parent_ptr);
                     parent.add_subview(&child);
                 }
                 #[cfg(target_os = "ios")]
                 (NativeViewHandle::UIKit(child_ptr), NativeViewHandle::UIKit(parent_ptr)) => {
-                    unsafe {
-                        use crate::ffi::uikit::UIView;
-                        let child = UIView { obj: *child_ptr };
-                        let parent = UIView { obj: *parent_ptr };
-                        parent.add_subview(&child);
-                    }
+                    use crate::ffi::uikit::UIView;
+                    let child = UIView::from_handle(child_ptr);
+                    let parent = UIView::from_handle(parent_ptr);
+                    parent.add_subview(&child);
                 }
                 #[allow(unreachable_patterns)]
                 _ => {
@@ -160,18 +349,16 @@ impl ViewManager {
                     #[cfg(target_os = "macos")]
                     (NativeViewHandle::AppKit(child_ptr), NativeViewHandle::AppKit(root_ptr)) => {
                         use crate::ffi::appkit::NSView;
-                        let child = NSView { obj: *child_ptr };
-                        let root = NSView { obj: *root_ptr };
+                        let child = NSView::from_handle(child_ptr);
+                        let root = NSView::from_handle(root_ptr);
                         root.add_subview(&child);
                     }
                     #[cfg(target_os = "ios")]
                     (NativeViewHandle::UIKit(child_ptr), NativeViewHandle::UIKit(root_ptr)) => {
-                        unsafe {
-                            use crate::ffi::uikit::UIView;
-                            let child = UIView { obj: *child_ptr };
-                            let root = UIView { obj: *root_ptr };
-                            root.add_subview(&child);
-                        }
+                        use crate::ffi::uikit::UIView;
+                        let child = UIView::from_handle(child_ptr);
+                        let root = UIView::from_handle(root_ptr);
+                        root.add_subview(&child);
                     }
                     #[allow(unreachable_patterns)]
                     _ => {}
@@ -181,46 +368,147 @@ impl ViewManager {
     }
 
     /// Update all native view layouts based on computed layout results.
+    ///
+    /// Each view is moved by its entry in `placements` and hidden if that
+    /// says so; views without one are placed at their layout. Children of a
+    /// container with a document view are placed in document coordinates,
+    /// relative to the container's content.
     pub fn update_layouts(
-        &self,
+        &mut self,
         layouts: &HashMap<WidgetId, spark_layout::ComputedLayout>,
+        placements: &HashMap<WidgetId, ViewPlacement>,
         parent_height: f32,
         scale_factor: f32,
     ) {
+        for (document, content_id) in self.documents.values() {
+            if let Some(content) = layouts.get(content_id) {
+                let bounds = &content.bounds;
+                Self::set_frame(document, 0.0, 0.0, bounds.width as f64, bounds.height as f64);
+            }
+        }
+
+        let mut scrolled_out = HashSet::new();
         for (widget_id, computed) in layouts {
             if let Some(view_handle) = self.views.get(widget_id) {
+                let placement = placements.get(widget_id).copied().unwrap_or_default();
+                if !placement.visible {
+                    scrolled_out.insert(*widget_id);
+                }
+                let mut bounds = computed
+                    .bounds
+                    .translate(Vec2::new(placement.offset.0, placement.offset.1));
+                if let Some(content) = self
+                    .parent_map
+                    .get(widget_id)
+                    .and_then(|parent| self.documents.get(parent))
+                    .and_then(|(_, content_id)| layouts.get(content_id))
+                {
+                    bounds = bounds.translate(-content.bounds.pos());
+                }
+
                 // Convert ComputedLayout to the format needed for native views
                 let (x, y, width, height) = crate::layout::LayoutBridge::taffy_to_native_frame_from_bounds(
-                    &computed.bounds,
+                    &bounds,
                     parent_height,
                     scale_factor,
                 );
-                
-                match view_handle {
-                    #[cfg(target_os = "macos")]
-                    crate::NativeViewHandle::AppKit(ptr) => {
-                        use crate::ffi::appkit::NSView;
-                        let view = NSView { obj: *ptr };
-                        // Debug: log the frame being set (only for first few to avoid spam)
-                        if layouts.len() <= 3 {
-                            eprintln!("Setting native view frame: x={:.1}, y={:.1}, w={:.1}, h={:.1}, parent_height={:.1}, scale={:.1}", 
-                                x, y, width, height, parent_height, scale_factor);
-                        }
-                        view.set_frame(x, y, width, height);
-                        // Ensure view is visible and bring to front
-                        view.set_visible(true);
-                        view.bring_to_front();
-                    }
-                    #[cfg(target_os = "ios")]
-                    crate::NativeViewHandle::UIKit(ptr) => {
-                        unsafe {
-                            use crate::ffi::uikit::UIView;
-                            let view = UIView { obj: *ptr };
-                            view.set_frame(x, y, width, height);
-                        }
-                    }
+                Self::set_frame(view_handle, x, y, width, height);
+            }
+        }
+
+        if scrolled_out != self.scrolled_out {
+            self.scrolled_out = scrolled_out;
+            self.apply_visibility();
+        }
+    }
+
+    /// Stack the views in paint order and hide those covered by GPU content.
+    ///
+    /// `order` lists widgets back to front. Views sharing a superview are
+    /// reordered among themselves; GPU content is always drawn below them,
+    /// which is why covered views are hidden rather than overlapped.
+    pub fn set_stacking(&mut self, order: Vec<WidgetId>, covered: HashSet<WidgetId>) {
+        self.paint_order = order;
+        self.covered = covered;
+        self.apply_stacking();
+    }
+
+    /// Apply the last stacking passed to [`set_stacking`](Self::set_stacking).
+    ///
+    /// Call this again after views are moved to a new superview.
+    pub fn apply_stacking(&self) {
+        self.apply_visibility();
+
+        #[cfg(target_os = "macos")]
+        {
+            use crate::ffi::appkit::NSView;
+            // Sort each superview's subviews once, by their paint order
+            let mut by_superview: Vec<(NSView, Vec<*mut objc2::runtime::AnyObject>)> = Vec::new();
+            for widget_id in &self.paint_order {
+                let Some(NativeViewHandle::AppKit(ptr)) = self.views.get(widget_id) else {
+                    continue;
+                };
+                let Some(superview) = NSView::from_handle(ptr).superview() else {
+                    continue;
+                };
+                match by_superview.iter_mut().find(|(view, _)| view.obj == superview.obj) {
+                    Some((_, order)) => order.push(ptr.as_ptr()),
+                    None => by_superview.push((superview, vec![ptr.as_ptr()])),
                 }
             }
+            for (superview, order) in by_superview {
+                superview.order_subviews(&order);
+            }
+        }
+        #[cfg(target_os = "ios")]
+        for widget_id in &self.paint_order {
+            if let Some(NativeViewHandle::UIKit(ptr)) = self.views.get(widget_id) {
+                // Bringing each view forward in turn leaves them in paint order
+                crate::ffi::uikit::UIView::from_handle(ptr).bring_to_front();
+            }
+        }
+    }
+}
+
+impl ViewManager {
+    /// Show the views that are neither covered by GPU content nor scrolled out of view.
+    fn apply_visibility(&self) {
+        for (widget_id, view_handle) in &self.views {
+            let visible = !self.covered.contains(widget_id) && !self.scrolled_out.contains(widget_id);
+            match view_handle {
+                #[cfg(target_os = "macos")]
+                NativeViewHandle::AppKit(ptr) => crate::ffi::appkit::NSView::from_handle(ptr).set_visible(visible),
+                #[cfg(target_os = "ios")]
+                NativeViewHandle::UIKit(ptr) => crate::ffi::uikit::UIView::from_handle(ptr).set_visible(visible),
+            }
+        }
+    }
+
+    /// Set a view's tint color.
+    fn set_tint(view_handle: &NativeViewHandle, tint: Option<Color>) {
+        match view_handle {
+            #[cfg(target_os = "macos")]
+            NativeViewHandle::AppKit(ptr) => crate::ffi::appkit::NSView::from_handle(ptr).set_tint_color(tint),
+            #[cfg(target_os = "ios")]
+            NativeViewHandle::UIKit(ptr) => crate::ffi::uikit::UIView::from_handle(ptr).set_tint_color(tint),
+        }
+    }
+
+    /// Set a view's frame without any coordinate conversion.
+    fn set_frame(view_handle: &NativeViewHandle, x: f64, y: f64, width: f64, height: f64) {
+        match view_handle {
+            #[cfg(target_os = "macos")]
+            NativeViewHandle::AppKit(ptr) => {
+                use crate::ffi::appkit::NSView;
+                let view = NSView::from_handle(ptr);
+                view.set_frame(x, y, width, height);
+            }
+            #[cfg(target_os = "ios")]
+            NativeViewHandle::UIKit(ptr) => {
+                use crate::ffi::uikit::UIView;
+                let view = UIView::from_handle(ptr);
+                view.set_frame(x, y, width, height);
+            }
         }
     }
 }
@@ -231,3 +519,176 @@ impl Default for ViewManager {
     }
 }
 
+
+#[cfg(all(test, target_os = "macos"))]
+mod tests {
+    use super::*;
+    use crate::ffi::appkit::NSView;
+    use spark_layout::LayoutTree;
+
+    #[test]
+    fn test_vanished_widgets_leave_the_content_view() {
+        let content = NSView::new();
+        let child = NSView::new();
+        let handle = child.handle();
+        let mut tree = LayoutTree::new();
+        let first_id = tree.new_leaf(Default::default());
+        let second_id = tree.new_leaf(Default::default());
+
+        let mut manager = ViewManager::new();
+        manager.set_root_view(content.handle());
+
+        manager.sync_widgets(vec![(first_id, handle.clone())]);
+        assert_eq!(content.subview_count(), 1);
+
+        // A relayout that reassigns the id keeps the same view.
+        manager.sync_widgets(vec![(second_id, handle.clone())]);
+        assert_eq!(content.subview_count(), 1);
+        assert_eq!(manager.get_view(second_id), Some(&handle));
+        assert!(manager.get_view(first_id).is_none());
+
+        manager.sync_widgets(Vec::new());
+        assert_eq!(content.subview_count(), 0);
+
+        manager.sync_widgets(vec![(first_id, handle)]);
+        assert_eq!(content.subview_count(), 1);
+        manager.clear();
+        assert_eq!(content.subview_count(), 0);
+    }
+
+    #[test]
+    fn test_children_are_placed_in_the_document_view() {
+        let content = NSView::new();
+        let scroll = NSView::new();
+        let document = NSView::new_flipped();
+        let child = NSView::new();
+        let mut tree = LayoutTree::new();
+        let child_id = tree.new_leaf(Default::default());
+        let content_id = tree.new_with_children(Default::default(), &[child_id]);
+        let scroll_id = tree.new_with_children(Default::default(), &[content_id]);
+
+        let mut manager = ViewManager::new();
+        manager.set_root_view(content.handle());
+        manager.sync_widgets(vec![
+            (scroll_id, scroll.handle()),
+            (child_id, child.handle()),
+        ]);
+        manager.set_document_view(scroll_id, document.handle(), content_id);
+        manager.set_parent(child_id, scroll_id);
+        manager.attach_children();
+
+        assert_eq!(content.subview_count(), 1);
+        assert_eq!(document.subview_count(), 1);
+        assert!(manager.uses_layout(content_id));
+    }
+
+    #[test]
+    fn test_stacking_follows_paint_order_and_hides_covered_views() {
+        let content = NSView::new();
+        let (back, front) = (NSView::new(), NSView::new());
+        let mut tree = LayoutTree::new();
+        let back_id = tree.new_leaf(Default::default());
+        let front_id = tree.new_leaf(Default::default());
+
+        let mut manager = ViewManager::new();
+        manager.set_root_view(content.handle());
+        // Registered front first, as a HashMap iteration might
+        manager.sync_widgets(vec![
+            (front_id, front.handle()),
+            (back_id, back.handle()),
+        ]);
+        manager.set_stacking(vec![back_id, front_id], HashSet::from([back_id]));
+
+        assert_eq!(content.subview_at(0).map(|view| view.as_ptr()), Some(back.as_ptr()));
+        assert_eq!(content.subview_at(1).map(|view| view.as_ptr()), Some(front.as_ptr()));
+        assert!(back.is_hidden());
+        assert!(!front.is_hidden());
+
+        manager.set_stacking(vec![back_id, front_id], HashSet::new());
+        assert!(!back.is_hidden());
+    }
+
+    #[test]
+    fn test_placements_move_and_hide_scrolled_views() {
+        let content = NSView::new();
+        let (above, inside) = (NSView::new(), NSView::new());
+        let mut tree = LayoutTree::new();
+        let above_id = tree.new_leaf(Default::default());
+        let inside_id = tree.new_leaf(Default::default());
+
+        let mut manager = ViewManager::new();
+        manager.set_root_view(content.handle());
+        manager.sync_widgets(vec![
+            (above_id, above.handle()),
+            (inside_id, inside.handle()),
+        ]);
+        let layouts = HashMap::from([
+            (above_id, spark_layout::ComputedLayout::new(spark_core::Rect::new(0.0, 0.0, 50.0, 20.0))),
+            (inside_id, spark_layout::ComputedLayout::new(spark_core::Rect::new(0.0, 200.0, 50.0, 20.0))),
+        ]);
+        let scrolled = |visible| ViewPlacement {
+            offset: (0.0, -150.0),
+            visible,
+        };
+        let placements = HashMap::from([(above_id, scrolled(false)), (inside_id, scrolled(true))]);
+        manager.update_layouts(&layouts, &placements, 400.0, 1.0);
+
+        assert_eq!(inside.frame(), (0.0, 50.0, 50.0, 20.0));
+        assert!(above.is_hidden());
+        assert!(!inside.is_hidden());
+
+        // Scrolled back to the top
+        manager.update_layouts(&layouts, &HashMap::new(), 400.0, 1.0);
+        assert_eq!(above.frame(), (0.0, 0.0, 50.0, 20.0));
+        assert!(!above.is_hidden());
+    }
+
+    #[test]
+    fn test_appearance_is_set_on_the_root_view() {
+        let content = NSView::new();
+        let child = NSView::new();
+        let mut tree = LayoutTree::new();
+        let id = tree.new_leaf(Default::default());
+
+        let mut manager = ViewManager::new();
+        manager.set_appearance(NativeAppearance {
+            mode: ThemeMode::Dark,
+            tint: None,
+        });
+        // Applied once the root view is known
+        manager.set_root_view(content.handle());
+        manager.sync_widgets(vec![(id, child.handle())]);
+        assert_eq!(content.appearance_name().as_deref(), Some("NSAppearanceNameDarkAqua"));
+        // Registered views inherit it
+        assert_eq!(child.appearance_name(), None);
+
+        manager.set_appearance(NativeAppearance::from_theme(&Theme::light()));
+        assert_eq!(content.appearance_name().as_deref(), Some("NSAppearanceNameAqua"));
+    }
+
+    #[test]
+    fn test_dropped_buttons_release_their_views() {
+        use crate::widgets::NativeButton;
+        use crate::NativeWidget;
+
+        let content = NSView::new();
+        let mut tree = LayoutTree::new();
+        let id = tree.new_leaf(Default::default());
+        let mut manager = ViewManager::new();
+        manager.set_root_view(content.handle());
+
+        for _ in 0..1000 {
+            // Drain anything AppKit autoreleased before counting
+            let view = objc2::rc::autoreleasepool(|_| {
+                let button = NativeButton::new("Leak check");
+                manager.sync_widgets(vec![(id, button.native_view())]);
+                manager.sync_widgets(Vec::new());
+                let NativeViewHandle::AppKit(view) = button.native_view();
+                view
+            });
+            // Only this handle is left holding the view
+            assert_eq!(view.retain_count(), 1);
+        }
+        assert_eq!(content.subview_count(), 0);
+    }
+}
@@ -2,7 +2,11 @@
 
 use spark_input::InputEvent;
 use spark_layout::{taffy, WidgetId};
-use spark_widgets::{EventContext, EventResponse, LayoutContext, Widget};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use crate::events::ActionTarget;
 use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -22,7 +26,9 @@ pub struct NativeButton {
     #[cfg(target_os = "ios")]
     button: crate::ffi::uikit::UIButton,
     title: String,
-    on_click: Option<Box<dyn Fn() + Send + Sync>>,
+    on_click: Option<Arc<dyn Fn() + Send + Sync>>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: Option<ActionTarget>,
     pending_events: Arc<Mutex<Vec<InputEvent>>>,
     /// Cached intrinsic size (width, height)
     cached_size: Option<(f32, f32)>,
@@ -40,6 +46,7 @@ impl NativeButton {
             button: crate::ffi::uikit::UIButton::new(),
             title: title.clone(),
             on_click: None,
+            action_target: None,
             pending_events: Arc::new(Mutex::new(Vec::new())),
             cached_size: None,
         };
@@ -68,14 +75,33 @@ impl NativeButton {
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.on_click = Some(Box::new(callback));
+        #[cfg(target_os = "ios")]
+        if let Some(old) = &self.action_target {
+            self.button.remove_target(old.as_ptr());
+        }
+        let callback: Arc<dyn Fn() + Send + Sync> = Arc::new(callback);
+        let target = {
+            let callback = callback.clone();
+            ActionTarget::new(move |_sender| callback())
+        };
+        #[cfg(target_os = "macos")]
+        self.button.set_action(target.as_ptr(), ActionTarget::selector());
+        #[cfg(target_os = "ios")]
+        self.button.add_target(
+            target.as_ptr(),
+            ActionTarget::selector(),
+            crate::ffi::uikit::UIControlEvents::TouchUpInside,
+        );
+        self.on_click = Some(callback);
+        self.action_target = Some(target);
         self
     }
 
     /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
-    pub fn appearance(self, name: &str) -> Self {
-        #[cfg(target_os = "macos")]
+    pub fn appearance(mut self, name: &str) -> Self {
         self.button.view().set_appearance(name);
+        // Metrics can differ between appearances
+        self.update_cached_size();
         self
     }
     
@@ -98,9 +124,13 @@ impl NativeButton {
         }
         #[cfg(target_os = "ios")]
         {
-            // On iOS, use a similar estimation approach
-            let estimated_width = (self.title.len() as f32 * 8.0 + BUTTON_PADDING * 2.0).max(DEFAULT_MIN_BUTTON_WIDTH);
-            self.cached_size = Some((estimated_width, 44.0)); // iOS standard button height
+            let (width, height) = self.button.intrinsic_content_size();
+            if width > 0.0 && height > 0.0 {
+                self.cached_size = Some((width as f32, height as f32));
+            } else {
+                let estimated_width = (self.title.len() as f32 * 8.0 + BUTTON_PADDING * 2.0).max(DEFAULT_MIN_BUTTON_WIDTH);
+                self.cached_size = Some((estimated_width, 44.0)); // iOS standard button height
+            }
         }
         #[cfg(not(any(target_os = "macos", target_os = "ios")))]
         {
@@ -124,16 +154,12 @@ impl Widget for NativeButton {
         self.id = id;
     }
 
-    fn style(&self) -> taffy::Style {
+    fn style(&self, _theme: &Theme) -> taffy::Style {
         use taffy::prelude::*;
-        let (pref_width, pref_height) = self.preferred_size();
+        let (_, pref_height) = self.preferred_size();
         taffy::Style {
-            // Use min_size to ensure the button has at least its intrinsic size
-            min_size: Size {
-                width: length(pref_width),
-                height: length(pref_height),
-            },
-            // Allow the button to grow but prefer its intrinsic size
+            // Width comes from measure(); the bezel has a fixed height, so
+            // don't let rows stretch it
             size: Size {
                 width: auto(),
                 height: length(pref_height),
@@ -159,9 +185,36 @@ impl Widget for NativeButton {
     fn is_native(&self) -> bool {
         true
     }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Button,
+            name: Some(self.title.clone()),
+            focusable: true,
+            actions: vec![AccessibleAction::Click],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        _value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        if action != AccessibleAction::Click {
+            return EventResponse::default();
+        }
+        if let Some(ref callback) = self.on_click {
+            callback();
+        }
+        EventResponse::handled()
+    }
     
-    fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
-        Some(self.preferred_size())
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((width / ctx.native_point_scale, height / ctx.native_point_scale))
     }
     
     fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
@@ -169,25 +222,38 @@ impl Widget for NativeButton {
         match view_handle {
             #[cfg(target_os = "macos")]
             crate::NativeViewHandle::AppKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
             #[cfg(target_os = "ios")]
             crate::NativeViewHandle::UIKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
         }
     }
 }
 
+impl Drop for NativeButton {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
+        if let Some(_target) = &self.action_target {
+            #[cfg(target_os = "macos")]
+            self.button.set_action(std::ptr::null_mut(), ActionTarget::selector());
+            #[cfg(target_os = "ios")]
+            self.button.remove_target(_target.as_ptr());
+        }
+    }
+}
+
 impl NativeWidget for NativeButton {
     fn native_view(&self) -> NativeViewHandle {
         #[cfg(target_os = "macos")]
         {
-            NativeViewHandle::AppKit(self.button.view().as_ptr())
+            self.button.view().handle()
         }
         #[cfg(target_os = "ios")]
         {
-            NativeViewHandle::UIKit(self.button.view().as_ptr())
+            self.button.view().handle()
         }
     }
 
@@ -203,15 +269,9 @@ impl NativeWidget for NativeButton {
 }
 
 impl NativeWidgetExt for NativeButton {
-    fn handle_event(&mut self, _ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
-        if let InputEvent::PointerDown { button: spark_input::PointerButton::Primary, .. } = event {
-            if let Some(ref callback) = self.on_click {
-                callback();
-            }
-            EventResponse::handled()
-        } else {
-            EventResponse::default()
-        }
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // Clicks arrive through the control's target/action, not Spark input.
+        EventResponse::default()
     }
 }
 
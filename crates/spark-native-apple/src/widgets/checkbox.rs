@@ -0,0 +1,303 @@
+//! Native check box widget (check box style NSButton on macOS).
+
+use crate::events::{ActionTarget, SharedCallback};
+use crate::ffi::appkit::{NSButton, NSButtonType, NSControlStateValue};
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Default width for check boxes when AppKit reports no size (in logical pixels)
+const DEFAULT_CHECKBOX_WIDTH: f32 = 18.0;
+/// Default height for check boxes (in logical pixels)
+const DEFAULT_CHECKBOX_HEIGHT: f32 = 18.0;
+
+/// State of a check box.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CheckboxState {
+    /// Not checked.
+    #[default]
+    Off,
+    /// Checked.
+    On,
+    /// Partly checked, e.g. for a group whose items are only some checked.
+    Mixed,
+}
+
+impl CheckboxState {
+    fn to_native(self) -> NSControlStateValue {
+        match self {
+            Self::Off => NSControlStateValue::Off,
+            Self::On => NSControlStateValue::On,
+            Self::Mixed => NSControlStateValue::Mixed,
+        }
+    }
+
+    fn from_native(state: NSControlStateValue) -> Self {
+        match state {
+            NSControlStateValue::Off => Self::Off,
+            NSControlStateValue::On => Self::On,
+            NSControlStateValue::Mixed => Self::Mixed,
+        }
+    }
+}
+
+/// Native check box widget with a title.
+///
+/// The mixed state can always be set in code. Clicking only cycles through
+/// it when [`allows_mixed_state`](Self::allows_mixed_state) is on; otherwise
+/// clicking a mixed check box checks it.
+pub struct NativeCheckbox {
+    id: WidgetId,
+    button: NSButton,
+    title: String,
+    /// Last known state, updated by the action target.
+    state: Arc<Mutex<CheckboxState>>,
+    /// Whether clicking may select the mixed state.
+    user_mixed: Arc<AtomicBool>,
+    on_change: SharedCallback<CheckboxState>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: ActionTarget,
+    /// Cached fitting size (width, height)
+    cached_size: Option<(f32, f32)>,
+}
+
+impl NativeCheckbox {
+    /// Create an unchecked check box.
+    pub fn new(title: impl Into<String>) -> Self {
+        let title = title.into();
+        let state = Arc::new(Mutex::new(CheckboxState::Off));
+        let user_mixed = Arc::new(AtomicBool::new(false));
+        let on_change = SharedCallback::<CheckboxState>::default();
+        let action_target = {
+            let state = state.clone();
+            let user_mixed = user_mixed.clone();
+            let on_change = on_change.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's button.
+                let button = unsafe { NSButton::from_ptr(sender) };
+                let mut new_state = CheckboxState::from_native(button.state());
+                if new_state == CheckboxState::Mixed && !user_mixed.load(Ordering::Relaxed) {
+                    // Mixed was only allowed so code could set it
+                    button.set_state(NSControlStateValue::On);
+                    new_state = CheckboxState::On;
+                }
+                *state.lock().unwrap() = new_state;
+                if let Some(callback) = on_change.lock().unwrap().as_ref() {
+                    callback(new_state);
+                }
+            })
+        };
+        let button = NSButton::new();
+        button.set_button_type(NSButtonType::Switch);
+        button.set_action(action_target.as_ptr(), ActionTarget::selector());
+        let mut checkbox = Self {
+            id: WidgetId::default(),
+            button,
+            title: String::new(),
+            state,
+            user_mixed,
+            on_change,
+            action_target,
+            cached_size: None,
+        };
+        checkbox.set_title(&title);
+        checkbox
+    }
+
+    /// Check or uncheck the check box.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.set_state(if checked { CheckboxState::On } else { CheckboxState::Off });
+        self
+    }
+
+    /// Set the state.
+    pub fn state(mut self, state: CheckboxState) -> Self {
+        self.set_state(state);
+        self
+    }
+
+    /// Let clicking cycle through the mixed state.
+    pub fn allows_mixed_state(self, allows: bool) -> Self {
+        self.user_mixed.store(allows, Ordering::Relaxed);
+        let mixed = self.current_state() == CheckboxState::Mixed;
+        self.button.set_allows_mixed_state(allows || mixed);
+        self
+    }
+
+    /// Set the change callback, called with the state the user picked.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: Fn(CheckboxState) + Send + Sync + 'static,
+    {
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
+    pub fn appearance(mut self, name: &str) -> Self {
+        self.button.view().set_appearance(name);
+        // Metrics can differ between appearances
+        self.update_cached_size();
+        self
+    }
+
+    /// Update the state without calling `on_change`.
+    pub fn set_state(&mut self, state: CheckboxState) {
+        if state == CheckboxState::Mixed {
+            // AppKit turns a mixed state into on unless it is allowed
+            self.button.set_allows_mixed_state(true);
+        }
+        self.button.set_state(state.to_native());
+        *self.state.lock().unwrap() = state;
+    }
+
+    /// The current state.
+    pub fn current_state(&self) -> CheckboxState {
+        *self.state.lock().unwrap()
+    }
+
+    /// Whether the check box is fully checked.
+    pub fn is_checked(&self) -> bool {
+        self.current_state() == CheckboxState::On
+    }
+
+    /// Set the title shown next to the box.
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+        self.button.set_title(title);
+        self.update_cached_size();
+    }
+
+    /// Update the cached size from the button's fitting size, which includes the title.
+    fn update_cached_size(&mut self) {
+        self.button.size_to_fit();
+        let (width, height) = self.button.fitting_size();
+        let width = if width > 0.0 { width as f32 } else { DEFAULT_CHECKBOX_WIDTH };
+        let height = if height > 0.0 { height as f32 } else { DEFAULT_CHECKBOX_HEIGHT };
+        self.cached_size = Some((width, height));
+    }
+
+    /// Get the preferred size for this check box.
+    pub fn preferred_size(&self) -> (f32, f32) {
+        self.cached_size
+            .unwrap_or((DEFAULT_CHECKBOX_WIDTH, DEFAULT_CHECKBOX_HEIGHT))
+    }
+}
+
+impl Widget for NativeCheckbox {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            // Sized by measure(); never narrower than the title
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let value = match self.current_state() {
+            CheckboxState::Off => "off",
+            CheckboxState::On => "on",
+            CheckboxState::Mixed => "mixed",
+        };
+        AccessibleInfo {
+            role: AccessibleRole::CheckBox,
+            name: Some(self.title.clone()),
+            value: Some(value.to_string()),
+            focusable: true,
+            actions: vec![AccessibleAction::Click],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        _value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        if action != AccessibleAction::Click {
+            return EventResponse::default();
+        }
+        let state = match self.current_state() {
+            CheckboxState::On => CheckboxState::Off,
+            CheckboxState::Off | CheckboxState::Mixed => CheckboxState::On,
+        };
+        self.set_state(state);
+        if let Some(callback) = self.on_change.lock().unwrap().as_ref() {
+            callback(state);
+        }
+        EventResponse::handled()
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((width / ctx.native_point_scale, height / ctx.native_point_scale))
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+}
+
+impl NativeWidget for NativeCheckbox {
+    fn native_view(&self) -> NativeViewHandle {
+        self.button.view().handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // State changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeCheckbox {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // Clicks are delivered through the action target
+        EventResponse::default()
+    }
+}
+
+impl Drop for NativeCheckbox {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
+        self.button.set_action(std::ptr::null_mut(), ActionTarget::selector());
+    }
+}
+
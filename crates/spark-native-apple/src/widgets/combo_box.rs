@@ -0,0 +1,290 @@
+//! Native combo box widget (NSComboBox on macOS).
+//!
+//! A text field with a list of suggestions: the user can pick an item or
+//! type any text, and typing completes from the items.
+
+use crate::events::{ActionTarget, SharedCallback};
+use crate::ffi::appkit::NSComboBox;
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use std::sync::{Arc, Mutex};
+
+/// Default width for combo boxes (in logical pixels)
+const DEFAULT_COMBO_BOX_WIDTH: f32 = 200.0;
+/// Minimum width for combo boxes (in logical pixels)
+const MIN_COMBO_BOX_WIDTH: f32 = 80.0;
+/// Default height for combo boxes (in logical pixels)
+const DEFAULT_COMBO_BOX_HEIGHT: f32 = 26.0;
+
+/// Text callbacks receive a `&str`, so they are stored separately from [`SharedCallback`].
+type TextCallback = Arc<Mutex<Option<Box<dyn Fn(&str) + Send + Sync>>>>;
+
+/// Native combo box widget.
+///
+/// `on_change` fires when the text is committed, by picking an item,
+/// pressing Return or leaving the field; `on_select` also fires when the
+/// text came from an item.
+pub struct NativeComboBox {
+    id: WidgetId,
+    combo: NSComboBox,
+    items: Vec<String>,
+    /// Last committed text, updated by the action target.
+    value: Arc<Mutex<String>>,
+    on_change: TextCallback,
+    on_select: SharedCallback<usize>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: ActionTarget,
+    width: f32,
+}
+
+impl NativeComboBox {
+    /// Create an empty combo box.
+    pub fn new() -> Self {
+        let value = Arc::new(Mutex::new(String::new()));
+        let on_change = TextCallback::default();
+        let on_select = SharedCallback::<usize>::default();
+        let action_target = {
+            let value = value.clone();
+            let on_change = on_change.clone();
+            let on_select = on_select.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's combo box.
+                let combo = unsafe { NSComboBox::from_ptr(sender) };
+                let text = combo.string_value();
+                let changed = {
+                    let mut value = value.lock().unwrap();
+                    let changed = *value != text;
+                    value.clone_from(&text);
+                    changed
+                };
+                if let Some(index) = combo.index_of_selected_item() {
+                    if let Some(callback) = on_select.lock().unwrap().as_ref() {
+                        callback(index);
+                    }
+                }
+                if changed {
+                    if let Some(callback) = on_change.lock().unwrap().as_ref() {
+                        callback(&text);
+                    }
+                }
+            })
+        };
+        let combo = NSComboBox::new();
+        combo.set_action(action_target.as_ptr(), ActionTarget::selector());
+        Self {
+            id: WidgetId::default(),
+            combo,
+            items: Vec::new(),
+            value,
+            on_change,
+            on_select,
+            action_target,
+            width: DEFAULT_COMBO_BOX_WIDTH,
+        }
+    }
+
+    /// Set the suggestions, replacing any existing ones. The text is kept.
+    pub fn items(mut self, items: Vec<String>) -> Self {
+        self.set_items(items);
+        self
+    }
+
+    /// Set the text.
+    pub fn value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Set the placeholder shown while the text is empty.
+    pub fn placeholder(self, placeholder: &str) -> Self {
+        self.combo.set_placeholder_string(placeholder);
+        self
+    }
+
+    /// Set how many items the list shows before it scrolls.
+    pub fn visible_items(self, count: usize) -> Self {
+        self.combo.set_number_of_visible_items(count);
+        self
+    }
+
+    /// Set the width.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width.max(MIN_COMBO_BOX_WIDTH);
+        self
+    }
+
+    /// Set the callback for committed text changes.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the callback for picking an item, called with its index.
+    pub fn on_select<F>(self, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        *self.on_select.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
+    pub fn appearance(self, name: &str) -> Self {
+        self.combo.view().set_appearance(name);
+        self
+    }
+
+    /// Replace the suggestions. The text is kept.
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.combo.remove_all_items();
+        for item in &items {
+            self.combo.add_item(item);
+        }
+        self.items = items;
+    }
+
+    /// Set the text without calling `on_change`.
+    pub fn set_value(&mut self, value: &str) {
+        self.combo.set_string_value(value);
+        *self.value.lock().unwrap() = value.to_string();
+    }
+
+    /// The last committed text.
+    pub fn current_value(&self) -> String {
+        self.value.lock().unwrap().clone()
+    }
+
+    /// The suggestions.
+    pub fn item_labels(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Get the preferred size for this combo box.
+    pub fn preferred_size(&self) -> (f32, f32) {
+        let (_, height) = self.combo.intrinsic_content_size();
+        let height = if height > 0.0 { height as f32 } else { DEFAULT_COMBO_BOX_HEIGHT };
+        (self.width, height)
+    }
+}
+
+impl Default for NativeComboBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for NativeComboBox {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            // Sized by measure()
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::ComboBox,
+            value: Some(self.current_value()),
+            focusable: true,
+            actions: vec![AccessibleAction::SetValue],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        let (AccessibleAction::SetValue, Some(value)) = (action, value) else {
+            return EventResponse::default();
+        };
+        let text = value.as_text();
+        self.set_value(&text);
+        if let Some(index) = self.items.iter().position(|item| *item == text) {
+            self.combo.select_item(index);
+            if let Some(callback) = self.on_select.lock().unwrap().as_ref() {
+                callback(index);
+            }
+        }
+        if let Some(callback) = self.on_change.lock().unwrap().as_ref() {
+            callback(&text);
+        }
+        EventResponse::handled()
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The width is logical; the height comes from AppKit in native points
+        let (width, height) = self.preferred_size();
+        Some((width, height / ctx.native_point_scale))
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+}
+
+impl NativeWidget for NativeComboBox {
+    fn native_view(&self) -> NativeViewHandle {
+        self.combo.view().handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // Text and selection changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeComboBox {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // Text and selection changes are delivered through the action target
+        EventResponse::default()
+    }
+}
+
+impl Drop for NativeComboBox {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
+        self.combo.set_action(std::ptr::null_mut(), ActionTarget::selector());
+    }
+}
@@ -0,0 +1,218 @@
+//! Native context menus (NSMenu on macOS).
+//!
+//! [`NativeContextMenuExt::native_context_menu`] wraps any widget so that a
+//! right-click over it pops up a real NSMenu at the pointer. Native widgets
+//! get the menu as their view's menu instead. The wrapper delegates
+//! everything else to the widget, so layout is unchanged.
+
+use crate::events::ActionTarget;
+use crate::ffi::appkit::{self, NSMenu, NSMenuItem, NSView};
+use crate::layout::LayoutBridge;
+use spark_core::Rect;
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleValue, EventContext, EventResponse, LayoutContext,
+    PaintContext, Theme, Widget,
+};
+use std::sync::Arc;
+
+type ItemCallback = Arc<dyn Fn() + Send + Sync>;
+
+/// An entry in a native context menu.
+pub struct ContextMenuItem {
+    title: String,
+    /// `None` for separators.
+    callback: Option<ItemCallback>,
+}
+
+impl ContextMenuItem {
+    /// Create an item that calls `callback` when chosen.
+    pub fn new<F>(title: impl Into<String>, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        Self {
+            title: title.into(),
+            callback: Some(Arc::new(callback)),
+        }
+    }
+
+    /// Create a separator line.
+    pub fn separator() -> Self {
+        Self {
+            title: String::new(),
+            callback: None,
+        }
+    }
+}
+
+/// A widget with a native context menu.
+pub struct NativeContextMenu {
+    inner: Box<dyn Widget>,
+    menu: NSMenu,
+    /// Receives the items' actions; the menu is only shown while this widget lives.
+    _action_target: ActionTarget,
+}
+
+impl NativeContextMenu {
+    /// Attach a context menu with `items` to `inner`.
+    pub fn new(inner: impl Widget + 'static, items: Vec<ContextMenuItem>) -> Self {
+        let menu = NSMenu::new("");
+        let mut callbacks = Vec::new();
+        let mut natives = Vec::new();
+        for item in items {
+            match item.callback {
+                Some(callback) => {
+                    let native = NSMenuItem::new(&item.title, "");
+                    native.set_tag(callbacks.len() as isize);
+                    menu.add_item(&native);
+                    callbacks.push(callback);
+                    natives.push(native);
+                }
+                None => menu.add_item(&NSMenuItem::separator()),
+            }
+        }
+
+        let action_target = ActionTarget::new(move |sender| {
+            // SAFETY: this target is only attached to this menu's items.
+            let tag = unsafe { NSMenuItem::from_ptr(sender) }.tag();
+            if let Some(callback) = usize::try_from(tag).ok().and_then(|i| callbacks.get(i)) {
+                callback();
+            }
+        });
+        for native in &natives {
+            native.set_action(action_target.as_ptr(), ActionTarget::selector());
+        }
+
+        // Native views get right-clicks from AppKit, never as Spark events.
+        if inner.is_native() {
+            inner.register_native(inner.id(), &mut |_, ptr| {
+                // SAFETY: native widgets register their own live NSView.
+                unsafe { NSView::from_ptr(ptr.cast()) }.set_menu(&menu);
+            });
+        }
+
+        Self {
+            inner: Box::new(inner),
+            menu,
+            _action_target: action_target,
+        }
+    }
+
+    /// Show the menu at `pos` in the key window.
+    fn popup(&self, pos: glam::Vec2) -> bool {
+        let Some(view) = appkit::key_window_content_view() else {
+            return false;
+        };
+        let (x, y) = LayoutBridge::taffy_to_native_point(pos);
+        self.menu.popup_at(&view, x, y);
+        true
+    }
+}
+
+/// Adds [`native_context_menu`](NativeContextMenuExt::native_context_menu) to every widget.
+pub trait NativeContextMenuExt: Widget + Sized + 'static {
+    /// Show a native context menu with `items` when the widget is right-clicked.
+    fn native_context_menu(self, items: Vec<ContextMenuItem>) -> NativeContextMenu {
+        NativeContextMenu::new(self, items)
+    }
+}
+
+impl<W: Widget + 'static> NativeContextMenuExt for W {}
+
+impl Widget for NativeContextMenu {
+    fn id(&self) -> WidgetId {
+        self.inner.id()
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.inner.set_id(id);
+    }
+
+    fn style(&self, theme: &Theme) -> taffy::Style {
+        self.inner.style(theme)
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        self.inner.paint(ctx);
+    }
+
+    fn paint_after_children(&self, ctx: &mut PaintContext) {
+        self.inner.paint_after_children(ctx);
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        if let InputEvent::PointerDown { pos, .. } = event {
+            if event.is_right_click() && ctx.contains(*pos) && self.popup(*pos) {
+                return EventResponse::handled();
+            }
+        }
+        self.inner.event(ctx, event)
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        self.inner.children()
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        self.inner.children_mut()
+    }
+
+    fn on_focus(&mut self) {
+        self.inner.on_focus();
+    }
+
+    fn on_blur(&mut self) {
+        self.inner.on_blur();
+    }
+
+    fn focusable(&self) -> bool {
+        self.inner.focusable()
+    }
+
+    fn is_scroll_container(&self) -> bool {
+        self.inner.is_scroll_container()
+    }
+
+    fn scroll_offset(&self) -> Option<(f32, f32)> {
+        self.inner.scroll_offset()
+    }
+
+    fn set_scroll_offset(&mut self, x: f32, y: f32) {
+        self.inner.set_scroll_offset(x, y);
+    }
+
+    fn scroll_to_visible(&mut self, rect: Rect, viewport: Rect) {
+        self.inner.scroll_to_visible(rect, viewport);
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        self.inner.accessibility_info()
+    }
+
+    fn accessibility_action(
+        &mut self,
+        ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        self.inner.accessibility_action(ctx, action, value)
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        self.inner.measure(ctx)
+    }
+
+    fn is_native(&self) -> bool {
+        self.inner.is_native()
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        self.inner.register_native(widget_id, register);
+    }
+
+    fn native_document_view(&self) -> Option<*mut std::ffi::c_void> {
+        self.inner.native_document_view()
+    }
+}
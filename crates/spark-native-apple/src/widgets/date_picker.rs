@@ -0,0 +1,239 @@
+//! Native date picker widget (NSDatePicker on macOS).
+//!
+//! Dates are seconds since the Unix epoch, so no date library is required.
+
+use crate::events::{ActionTarget, SharedCallback};
+use crate::ffi::appkit::{NSDatePicker, NSDatePickerElements, NSDatePickerStyle};
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use objc2::runtime::AnyObject;
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext, Theme, Widget,
+};
+use std::sync::{Arc, Mutex};
+
+/// Default width for date pickers (in logical pixels)
+const DEFAULT_DATE_PICKER_WIDTH: f32 = 120.0;
+/// Default height for date pickers (in logical pixels)
+const DEFAULT_DATE_PICKER_HEIGHT: f32 = 27.0;
+
+/// Native date picker widget.
+pub struct NativeDatePicker {
+    id: WidgetId,
+    picker: NSDatePicker,
+    /// Selected date in epoch seconds, updated by the action target.
+    value: Arc<Mutex<f64>>,
+    on_change: SharedCallback<f64>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: ActionTarget,
+    /// Cached intrinsic size (width, height)
+    cached_size: Option<(f32, f32)>,
+}
+
+impl NativeDatePicker {
+    /// Create a date picker showing the current date.
+    pub fn new() -> Self {
+        let picker = NSDatePicker::new();
+        let value = Arc::new(Mutex::new(picker.date_value()));
+        let on_change = SharedCallback::<f64>::default();
+        let action_target = {
+            let value = value.clone();
+            let on_change = on_change.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's date picker.
+                let seconds = unsafe { sender_date(sender) };
+                *value.lock().unwrap() = seconds;
+                if let Some(callback) = on_change.lock().unwrap().as_ref() {
+                    callback(seconds);
+                }
+            })
+        };
+        picker.set_action(action_target.as_ptr(), ActionTarget::selector());
+        let mut picker = Self {
+            id: WidgetId::default(),
+            picker,
+            value,
+            on_change,
+            action_target,
+            cached_size: None,
+        };
+        picker.update_cached_size();
+        picker
+    }
+
+    /// Set the selected date, in seconds since the Unix epoch.
+    pub fn value(self, seconds: f64) -> Self {
+        self.picker.set_date_value(seconds);
+        self.sync_value();
+        self
+    }
+
+    /// Set the picker style.
+    pub fn style(mut self, style: NSDatePickerStyle) -> Self {
+        self.picker.set_style(style);
+        self.update_cached_size();
+        self
+    }
+
+    /// Set which date and time fields are shown.
+    pub fn elements(mut self, elements: NSDatePickerElements) -> Self {
+        self.picker.set_elements(elements);
+        self.update_cached_size();
+        self
+    }
+
+    /// Set the earliest selectable date, in epoch seconds.
+    pub fn min_date(self, seconds: f64) -> Self {
+        self.picker.set_min_date(Some(seconds));
+        self.sync_value();
+        self
+    }
+
+    /// Set the latest selectable date, in epoch seconds.
+    pub fn max_date(self, seconds: f64) -> Self {
+        self.picker.set_max_date(Some(seconds));
+        self.sync_value();
+        self
+    }
+
+    /// Set the change callback, called with the new date in epoch seconds.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: Fn(f64) + Send + Sync + 'static,
+    {
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
+    pub fn appearance(mut self, name: &str) -> Self {
+        self.picker.view().set_appearance(name);
+        // Metrics can differ between appearances
+        self.update_cached_size();
+        self
+    }
+
+    /// The selected date, in seconds since the Unix epoch.
+    pub fn current_value(&self) -> f64 {
+        *self.value.lock().unwrap()
+    }
+
+    /// Read the date back from the control, which may have clamped it.
+    fn sync_value(&self) {
+        *self.value.lock().unwrap() = self.picker.date_value();
+    }
+
+    /// Update the cached intrinsic size from the native view.
+    fn update_cached_size(&mut self) {
+        let (width, height) = self.picker.intrinsic_content_size();
+        let width = if width > 0.0 { width as f32 } else { DEFAULT_DATE_PICKER_WIDTH };
+        let height = if height > 0.0 { height as f32 } else { DEFAULT_DATE_PICKER_HEIGHT };
+        self.cached_size = Some((width, height));
+    }
+
+    /// Get the preferred size for this date picker.
+    pub fn preferred_size(&self) -> (f32, f32) {
+        self.cached_size
+            .unwrap_or((DEFAULT_DATE_PICKER_WIDTH, DEFAULT_DATE_PICKER_HEIGHT))
+    }
+}
+
+impl Default for NativeDatePicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for NativeDatePicker {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            // Sized by measure()
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::DateTimeInput,
+            value: Some(self.current_value().to_string()),
+            focusable: true,
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((width / ctx.native_point_scale, height / ctx.native_point_scale))
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+}
+
+impl NativeWidget for NativeDatePicker {
+    fn native_view(&self) -> NativeViewHandle {
+        self.picker.view().handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // Date changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeDatePicker {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // Date changes are delivered through the action target
+        EventResponse::default()
+    }
+}
+
+impl Drop for NativeDatePicker {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
+        self.picker.set_action(std::ptr::null_mut(), ActionTarget::selector());
+    }
+}
+
+/// Read the date of the date picker that sent an action.
+///
+/// # Safety
+///
+/// `sender` must point to a live NSDatePicker.
+unsafe fn sender_date(sender: *mut AnyObject) -> f64 {
+    NSDatePicker::from_ptr(sender).date_value()
+}
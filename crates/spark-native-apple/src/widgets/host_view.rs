@@ -0,0 +1,154 @@
+//! Widget hosting a native view built by the app (NSView on macOS, UIView on iOS).
+//!
+//! This is the escape hatch for controls the crate has no wrapper for,
+//! including SwiftUI views wrapped in an `NSHostingView` or
+//! `UIHostingController`'s view.
+
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use objc2::runtime::AnyObject;
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{EventContext, EventResponse, LayoutContext, PaintContext, Theme, Widget};
+
+#[cfg(target_os = "macos")]
+use crate::ffi::appkit::NSView as PlatformView;
+#[cfg(target_os = "ios")]
+use crate::ffi::uikit::UIView as PlatformView;
+
+/// Widget that places an app-provided native view in the layout.
+///
+/// Like the built-in native widgets, the view is positioned by the layout,
+/// hidden while a GPU overlay covers it and removed from its superview when
+/// the widget leaves the tree.
+pub struct NativeHostView {
+    id: WidgetId,
+    view: PlatformView,
+    /// Explicit size in logical pixels; the view's fitting size otherwise.
+    size: Option<(f32, f32)>,
+}
+
+impl NativeHostView {
+    /// Host an existing view.
+    ///
+    /// The widget retains the view, so the caller may release its own
+    /// reference afterwards.
+    ///
+    /// # Safety
+    ///
+    /// - `view` must be a valid, non-null `NSView` (`UIView` on iOS) or subclass.
+    /// - This must be called on the main thread, and the widget must only be
+    ///   used there.
+    /// - The view must not already have a superview; the view manager adds
+    ///   it to the view hierarchy.
+    pub unsafe fn from_raw(view: *mut AnyObject) -> Self {
+        assert!(!view.is_null(), "NativeHostView::from_raw called with a null view");
+        let view = PlatformView::from_ptr(view);
+        debug_assert!(
+            view.superview().is_none(),
+            "views passed to NativeHostView::from_raw must not have a superview"
+        );
+        Self {
+            id: WidgetId::default(),
+            view,
+            size: None,
+        }
+    }
+
+    /// Use a fixed size instead of the view's fitting size.
+    ///
+    /// Needed for views without constraints, whose fitting size is zero.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Get the raw pointer of the hosted view.
+    pub fn as_ptr(&self) -> *mut AnyObject {
+        self.view.as_ptr()
+    }
+}
+
+impl Widget for NativeHostView {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        let mut style = taffy::Style {
+            flex_shrink: 0.0,
+            ..Default::default()
+        };
+        if let Some((width, height)) = self.size {
+            style.size = taffy::Size {
+                width: taffy::prelude::length(width),
+                height: taffy::prelude::length(height),
+            };
+        }
+        style
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        if self.size.is_some() {
+            // Sized by style()
+            return None;
+        }
+        // The fitting size is in native points
+        let (width, height) = self.view.fitting_size();
+        Some((
+            width.max(0.0) as f32 / ctx.native_point_scale,
+            height.max(0.0) as f32 / ctx.native_point_scale,
+        ))
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let view_handle = <Self as NativeWidget>::native_view(self);
+        match view_handle {
+            #[cfg(target_os = "macos")]
+            NativeViewHandle::AppKit(ptr) => {
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+            }
+            #[cfg(target_os = "ios")]
+            NativeViewHandle::UIKit(ptr) => {
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+            }
+        }
+    }
+}
+
+impl NativeWidget for NativeHostView {
+    fn native_view(&self) -> NativeViewHandle {
+        self.view.handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // The hosted view handles its own input
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeHostView {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // The hosted view handles its own input
+        EventResponse::default()
+    }
+}
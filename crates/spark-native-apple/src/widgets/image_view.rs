@@ -0,0 +1,190 @@
+//! Native image widget (NSImageView on macOS).
+//!
+//! AppKit decodes the image and handles HDR and color-managed display.
+
+use crate::ffi::appkit::{NSImageScaling, NSImageView};
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext, Theme, Widget,
+};
+
+/// How an image is scaled to the widget's bounds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContentMode {
+    /// Scale up or down to fit, keeping the aspect ratio.
+    #[default]
+    Fit,
+    /// Like `Fit`, but never scale up.
+    ScaleDown,
+    /// Stretch to fill, ignoring the aspect ratio.
+    Stretch,
+    /// Draw at natural size, centered.
+    Center,
+}
+
+impl ContentMode {
+    fn scaling(self) -> NSImageScaling {
+        match self {
+            Self::Fit => NSImageScaling::ProportionallyUpOrDown,
+            Self::ScaleDown => NSImageScaling::ProportionallyDown,
+            Self::Stretch => NSImageScaling::AxesIndependently,
+            Self::Center => NSImageScaling::None,
+        }
+    }
+}
+
+/// Native image widget.
+pub struct NativeImageView {
+    id: WidgetId,
+    image_view: NSImageView,
+    /// Fixed size in logical pixels; the image's own size otherwise.
+    size: Option<(f32, f32)>,
+    description: Option<String>,
+}
+
+impl NativeImageView {
+    /// Create an empty image view.
+    pub fn new() -> Self {
+        let image_view = NSImageView::new();
+        image_view.set_image_scaling(ContentMode::default().scaling());
+        Self {
+            id: WidgetId::default(),
+            image_view,
+            size: None,
+            description: None,
+        }
+    }
+
+    /// Create an image view showing encoded image data (PNG, JPEG, HEIC, ...).
+    ///
+    /// Data AppKit can't decode leaves the view empty.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let view = Self::new();
+        view.image_view.set_image_data(data);
+        view
+    }
+
+    /// Create an image view showing non-premultiplied 8-bit RGBA pixels.
+    ///
+    /// Leaves the view empty if `rgba` is not `width * height * 4` bytes.
+    pub fn from_rgba(width: usize, height: usize, rgba: &[u8]) -> Self {
+        let view = Self::new();
+        view.image_view.set_image_rgba(width, height, rgba);
+        view
+    }
+
+    /// Whether there is an image to show.
+    pub fn has_image(&self) -> bool {
+        self.image_view.image_size().is_some()
+    }
+
+    /// Set how the image is scaled to the widget's bounds.
+    pub fn fit(self, mode: ContentMode) -> Self {
+        self.image_view.set_image_scaling(mode.scaling());
+        self
+    }
+
+    /// Set a fixed size.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Describe the image for assistive technology.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+impl Default for NativeImageView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for NativeImageView {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        use taffy::prelude::*;
+        match self.size {
+            Some((width, height)) => taffy::Style {
+                size: Size {
+                    width: length(width),
+                    height: length(height),
+                },
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            // Sized by measure()
+            None => taffy::Style::default(),
+        }
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Image,
+            name: self.description.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        if self.size.is_some() {
+            return None;
+        }
+        // Image sizes are in native points
+        let (width, height) = self.image_view.image_size()?;
+        Some((
+            width as f32 / ctx.native_point_scale,
+            height as f32 / ctx.native_point_scale,
+        ))
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+}
+
+impl NativeWidget for NativeImageView {
+    fn native_view(&self) -> NativeViewHandle {
+        self.image_view.view().handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // Images are not interactive
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeImageView {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        EventResponse::default()
+    }
+}
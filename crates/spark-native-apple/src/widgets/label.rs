@@ -3,7 +3,10 @@
 use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
 use spark_input::InputEvent;
 use spark_layout::{taffy, WidgetId};
-use spark_widgets::{EventContext, EventResponse, LayoutContext, PaintContext, Widget};
+use spark_widgets::{
+    AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext, PaintContext,
+    Theme, Widget,
+};
 
 /// Default minimum height for labels (in logical pixels)
 const DEFAULT_MIN_LABEL_HEIGHT: f32 = 17.0;
@@ -58,9 +61,13 @@ impl NativeLabel {
     }
 
     /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
-    pub fn appearance(self, name: &str) -> Self {
+    pub fn appearance(mut self, name: &str) -> Self {
         #[cfg(target_os = "macos")]
         self.text_field.view().set_appearance(name);
+        #[cfg(target_os = "ios")]
+        self.label.view().set_appearance(name);
+        // Metrics can differ between appearances
+        self.update_cached_size();
         self
     }
 
@@ -88,9 +95,14 @@ impl NativeLabel {
         }
         #[cfg(target_os = "ios")]
         {
-            // On iOS, estimate based on text
-            let estimated_width = (self.text.len() as f32 * CHAR_WIDTH_ESTIMATE).max(10.0);
-            self.cached_size = Some((estimated_width, 21.0)); // iOS standard label height
+            let (width, height) = self.label.intrinsic_content_size();
+            if width > 0.0 && height > 0.0 {
+                self.cached_size = Some((width as f32, height as f32));
+            } else {
+                // Estimate: roughly 7 pixels per character
+                let estimated_width = (self.text.len() as f32 * CHAR_WIDTH_ESTIMATE).max(10.0);
+                self.cached_size = Some((estimated_width, 21.0)); // iOS standard label height
+            }
         }
         #[cfg(not(any(target_os = "macos", target_os = "ios")))]
         {
@@ -114,16 +126,10 @@ impl Widget for NativeLabel {
         self.id = id;
     }
 
-    fn style(&self) -> taffy::Style {
+    fn style(&self, _theme: &Theme) -> taffy::Style {
         use taffy::prelude::*;
-        let (pref_width, pref_height) = self.preferred_size();
         taffy::Style {
-            // Use min_size to ensure the label has at least its intrinsic size
-            min_size: Size {
-                width: length(pref_width),
-                height: length(pref_height),
-            },
-            // Allow the label to shrink and grow
+            // Sized from measure()
             size: Size {
                 width: auto(),
                 height: auto(),
@@ -145,9 +151,19 @@ impl Widget for NativeLabel {
     fn is_native(&self) -> bool {
         true
     }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Label,
+            name: Some(self.text.clone()),
+            ..Default::default()
+        }
+    }
     
-    fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
-        Some(self.preferred_size())
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((width / ctx.native_point_scale, height / ctx.native_point_scale))
     }
 
     fn register_native(
@@ -159,11 +175,11 @@ impl Widget for NativeLabel {
         match view_handle {
             #[cfg(target_os = "macos")]
             crate::NativeViewHandle::AppKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
             #[cfg(target_os = "ios")]
             crate::NativeViewHandle::UIKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
         }
     }
@@ -173,11 +189,11 @@ impl NativeWidget for NativeLabel {
     fn native_view(&self) -> NativeViewHandle {
         #[cfg(target_os = "macos")]
         {
-            NativeViewHandle::AppKit(self.text_field.view().as_ptr())
+            self.text_field.view().handle()
         }
         #[cfg(target_os = "ios")]
         {
-            NativeViewHandle::UIKit(self.label.view().as_ptr())
+            self.label.view().handle()
         }
     }
 
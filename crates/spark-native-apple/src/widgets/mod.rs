@@ -1,17 +1,55 @@
 //! Native widget implementations.
 
 mod button;
+#[cfg(target_os = "macos")]
+mod checkbox;
+#[cfg(target_os = "macos")]
+mod combo_box;
+#[cfg(target_os = "macos")]
+mod context_menu;
+#[cfg(target_os = "macos")]
+mod date_picker;
+mod host_view;
+#[cfg(target_os = "macos")]
+mod image_view;
 mod label;
+#[cfg(target_os = "macos")]
+mod popup_button;
 mod progress_indicator;
+#[cfg(target_os = "macos")]
+mod radio_group;
+#[cfg(target_os = "macos")]
+mod scroll_view;
+#[cfg(target_os = "macos")]
+mod segmented_control;
 mod slider;
 mod switch;
 mod text_field;
 mod view;
 
 pub use button::NativeButton;
+#[cfg(target_os = "macos")]
+pub use checkbox::{CheckboxState, NativeCheckbox};
+#[cfg(target_os = "macos")]
+pub use combo_box::NativeComboBox;
+#[cfg(target_os = "macos")]
+pub use context_menu::{ContextMenuItem, NativeContextMenu, NativeContextMenuExt};
+#[cfg(target_os = "macos")]
+pub use date_picker::NativeDatePicker;
+pub use host_view::NativeHostView;
+#[cfg(target_os = "macos")]
+pub use image_view::{ContentMode, NativeImageView};
 pub use label::NativeLabel;
-pub use progress_indicator::NativeProgressIndicator;
-pub use slider::NativeSlider;
+#[cfg(target_os = "macos")]
+pub use popup_button::NativePopUpButton;
+pub use progress_indicator::{NativeProgressIndicator, ProgressHandle};
+#[cfg(target_os = "macos")]
+pub use radio_group::NativeRadioGroup;
+#[cfg(target_os = "macos")]
+pub use scroll_view::NativeScrollView;
+#[cfg(target_os = "macos")]
+pub use segmented_control::NativeSegmentedControl;
+pub use slider::{NativeSlider, SliderHandle};
 pub use switch::NativeSwitch;
 pub use text_field::NativeTextField;
 pub use view::NativeView;
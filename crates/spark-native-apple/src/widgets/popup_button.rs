@@ -0,0 +1,285 @@
+//! Native pop-up button widget (NSPopUpButton on macOS).
+
+use crate::events::{ActionTarget, SharedCallback};
+use crate::ffi::appkit::NSPopUpButton;
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use objc2::runtime::AnyObject;
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Default minimum width for pop-up buttons (in logical pixels)
+const DEFAULT_MIN_POPUP_WIDTH: f32 = 80.0;
+/// Default height for pop-up buttons (in logical pixels)
+const DEFAULT_POPUP_HEIGHT: f32 = 25.0;
+/// Stored in `selected` when no item is selected.
+const NO_SELECTION: usize = usize::MAX;
+
+/// Native pop-up button (dropdown) widget.
+pub struct NativePopUpButton {
+    id: WidgetId,
+    popup: NSPopUpButton,
+    items: Vec<String>,
+    /// Selected index, updated by the action target.
+    selected: Arc<AtomicUsize>,
+    on_select: SharedCallback<usize>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: ActionTarget,
+    /// Cached intrinsic size (width, height)
+    cached_size: Option<(f32, f32)>,
+}
+
+impl NativePopUpButton {
+    /// Create an empty pop-up button.
+    pub fn new() -> Self {
+        let selected = Arc::new(AtomicUsize::new(NO_SELECTION));
+        let on_select = SharedCallback::<usize>::default();
+        let action_target = {
+            let selected = selected.clone();
+            let on_select = on_select.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's pop-up button.
+                let Some(index) = (unsafe { sender_selection(sender) }) else {
+                    return;
+                };
+                selected.store(index, Ordering::Relaxed);
+                if let Some(callback) = on_select.lock().unwrap().as_ref() {
+                    callback(index);
+                }
+            })
+        };
+        let mut popup = Self {
+            id: WidgetId::default(),
+            popup: NSPopUpButton::new(),
+            items: Vec::new(),
+            selected,
+            on_select,
+            action_target,
+            cached_size: None,
+        };
+        popup
+            .popup
+            .set_action(popup.action_target.as_ptr(), ActionTarget::selector());
+        popup.update_cached_size();
+        popup
+    }
+
+    /// Set the items, replacing any existing ones. The first item is selected.
+    pub fn items(mut self, items: Vec<String>) -> Self {
+        self.set_items(items);
+        self
+    }
+
+    /// Select the item at `index`.
+    pub fn selected(mut self, index: usize) -> Self {
+        self.set_selected(index);
+        self
+    }
+
+    /// Set the selection callback, called with the index the user picked.
+    pub fn on_select<F>(self, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        *self.on_select.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
+    pub fn appearance(mut self, name: &str) -> Self {
+        self.popup.view().set_appearance(name);
+        // Metrics can differ between appearances
+        self.update_cached_size();
+        self
+    }
+
+    /// Replace the items. The first item is selected.
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.popup.remove_all_items();
+        for item in &items {
+            self.popup.add_item(item);
+        }
+        self.items = items;
+        self.sync_selection();
+        self.update_cached_size();
+    }
+
+    /// Select the item at `index` without calling `on_select`. Out-of-range indices are ignored.
+    pub fn set_selected(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.popup.select_item(index);
+            self.sync_selection();
+        }
+    }
+
+    /// Index of the selected item.
+    pub fn selected_index(&self) -> Option<usize> {
+        match self.selected.load(Ordering::Relaxed) {
+            NO_SELECTION => None,
+            index => Some(index),
+        }
+    }
+
+    /// The items.
+    pub fn item_titles(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Read the selection back from the control.
+    fn sync_selection(&self) {
+        let index = self.popup.index_of_selected_item().unwrap_or(NO_SELECTION);
+        self.selected.store(index, Ordering::Relaxed);
+    }
+
+    /// Update the cached intrinsic size from the native view.
+    fn update_cached_size(&mut self) {
+        // Fits the widest item
+        self.popup.size_to_fit();
+        let (width, height) = self.popup.intrinsic_content_size();
+        let width = if width > 0.0 { width as f32 } else { DEFAULT_MIN_POPUP_WIDTH };
+        let height = if height > 0.0 { height as f32 } else { DEFAULT_POPUP_HEIGHT };
+        self.cached_size = Some((width.max(DEFAULT_MIN_POPUP_WIDTH), height));
+    }
+
+    /// Get the preferred size for this pop-up button.
+    pub fn preferred_size(&self) -> (f32, f32) {
+        self.cached_size
+            .unwrap_or((DEFAULT_MIN_POPUP_WIDTH, DEFAULT_POPUP_HEIGHT))
+    }
+}
+
+impl Default for NativePopUpButton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for NativePopUpButton {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        use taffy::prelude::*;
+        let (_, pref_height) = self.preferred_size();
+        taffy::Style {
+            // Width comes from measure(); the bezel has a fixed height
+            size: Size {
+                width: auto(),
+                height: length(pref_height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::ComboBox,
+            value: self
+                .selected_index()
+                .and_then(|index| self.items.get(index).cloned()),
+            focusable: true,
+            actions: vec![AccessibleAction::SetValue],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        if action != AccessibleAction::SetValue {
+            return EventResponse::default();
+        }
+        let Some(value) = value else {
+            return EventResponse::default();
+        };
+        let title = value.as_text();
+        let Some(index) = self.items.iter().position(|item| *item == title) else {
+            return EventResponse::default();
+        };
+        self.set_selected(index);
+        if let Some(callback) = self.on_select.lock().unwrap().as_ref() {
+            callback(index);
+        }
+        EventResponse::handled()
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((width / ctx.native_point_scale, height / ctx.native_point_scale))
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+}
+
+impl NativeWidget for NativePopUpButton {
+    fn native_view(&self) -> NativeViewHandle {
+        self.popup.view().handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // Selection changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativePopUpButton {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // Selection changes are delivered through the action target
+        EventResponse::default()
+    }
+}
+
+impl Drop for NativePopUpButton {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
+        self.popup.set_action(std::ptr::null_mut(), ActionTarget::selector());
+    }
+}
+
+/// Read the selection of the pop-up button that sent an action.
+///
+/// # Safety
+///
+/// `sender` must point to a live NSPopUpButton.
+unsafe fn sender_selection(sender: *mut AnyObject) -> Option<usize> {
+    NSPopUpButton::from_ptr(sender).index_of_selected_item()
+}
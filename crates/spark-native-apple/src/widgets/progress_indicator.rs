@@ -2,8 +2,10 @@
 
 use spark_input::InputEvent;
 use spark_layout::{taffy, WidgetId};
-use spark_widgets::{EventContext, EventResponse, LayoutContext, Widget};
+use spark_widgets::{AccessibleInfo, EventContext, EventResponse, LayoutContext, Theme, Widget};
+use crate::events::{run_on_main, RetainedView};
 use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use std::sync::{Arc, Mutex};
 
 /// Default minimum width for progress indicators (in logical pixels)
 const DEFAULT_MIN_PROGRESS_WIDTH: f32 = 100.0;
@@ -21,7 +23,8 @@ pub struct NativeProgressIndicator {
     indicator: crate::ffi::appkit::NSProgressIndicator,
     #[cfg(target_os = "ios")]
     indicator: crate::ffi::uikit::UIProgressView,
-    value: f64,
+    /// Current value, shared with handles.
+    value: Arc<Mutex<f64>>,
     min_value: f64,
     max_value: f64,
     indeterminate: bool,
@@ -38,7 +41,7 @@ impl NativeProgressIndicator {
             indicator: crate::ffi::appkit::NSProgressIndicator::new(),
             #[cfg(target_os = "ios")]
             indicator: crate::ffi::uikit::UIProgressView::new(),
-            value: 0.0,
+            value: Arc::new(Mutex::new(0.0)),
             min_value: 0.0,
             max_value: 100.0,
             indeterminate: false,
@@ -50,11 +53,28 @@ impl NativeProgressIndicator {
 
     /// Set the progress value (0.0 to 1.0 for determinate, or use min/max).
     pub fn value(mut self, value: f64) -> Self {
-        self.value = value.clamp(self.min_value, self.max_value);
+        *self.value.lock().unwrap() = value.clamp(self.min_value, self.max_value);
         self.update_native_values();
         self
     }
 
+    /// A handle for updating the value after the indicator is in the tree.
+    ///
+    /// The handle uses the range configured at the time it is created.
+    pub fn handle(&self) -> ProgressHandle {
+        let view = match <Self as NativeWidget>::native_view(self) {
+            #[cfg(target_os = "macos")]
+            NativeViewHandle::AppKit(ptr) => ptr,
+            #[cfg(target_os = "ios")]
+            NativeViewHandle::UIKit(ptr) => ptr,
+        };
+        ProgressHandle {
+            view,
+            value: self.value.clone(),
+            range: (self.min_value, self.max_value),
+        }
+    }
+
     /// Set the minimum value.
     pub fn min_value(mut self, value: f64) -> Self {
         self.min_value = value;
@@ -89,7 +109,6 @@ impl NativeProgressIndicator {
 
     /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
     pub fn appearance(self, name: &str) -> Self {
-        #[cfg(target_os = "macos")]
         self.indicator.view().set_appearance(name);
         self
     }
@@ -101,18 +120,18 @@ impl NativeProgressIndicator {
     }
 
     fn update_native_values(&mut self) {
+        let value = *self.value.lock().unwrap();
         #[cfg(target_os = "macos")]
         {
             if !self.indeterminate {
                 self.indicator.set_min_value(self.min_value);
                 self.indicator.set_max_value(self.max_value);
-                self.indicator.set_double_value(self.value);
+                self.indicator.set_double_value(value);
             }
         }
         #[cfg(target_os = "ios")]
         {
-            let progress = ((self.value - self.min_value) / (self.max_value - self.min_value)) as f32;
-            self.indicator.set_progress(progress);
+            self.indicator.set_progress(fraction(value, (self.min_value, self.max_value)));
         }
     }
     
@@ -171,7 +190,7 @@ impl Widget for NativeProgressIndicator {
         self.id = id;
     }
 
-    fn style(&self) -> taffy::Style {
+    fn style(&self, _theme: &Theme) -> taffy::Style {
         use taffy::prelude::*;
         let (pref_width, pref_height) = self.preferred_size();
         
@@ -219,6 +238,13 @@ impl Widget for NativeProgressIndicator {
     fn is_native(&self) -> bool {
         true
     }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            value: (!self.indeterminate).then(|| self.value.lock().unwrap().to_string()),
+            ..Default::default()
+        }
+    }
     
     fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
         Some(self.preferred_size())
@@ -229,11 +255,11 @@ impl Widget for NativeProgressIndicator {
         match view_handle {
             #[cfg(target_os = "macos")]
             crate::NativeViewHandle::AppKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
             #[cfg(target_os = "ios")]
             crate::NativeViewHandle::UIKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
         }
     }
@@ -243,11 +269,11 @@ impl NativeWidget for NativeProgressIndicator {
     fn native_view(&self) -> NativeViewHandle {
         #[cfg(target_os = "macos")]
         {
-            NativeViewHandle::AppKit(self.indicator.view().as_ptr())
+            self.indicator.view().handle()
         }
         #[cfg(target_os = "ios")]
         {
-            NativeViewHandle::UIKit(self.indicator.view().as_ptr())
+            self.indicator.view().handle()
         }
     }
 
@@ -266,3 +292,45 @@ impl NativeWidgetExt for NativeProgressIndicator {
     }
 }
 
+
+/// Position of `value` within `range` as 0.0..=1.0 (UIProgressView's scale).
+#[cfg(target_os = "ios")]
+fn fraction(value: f64, (min, max): (f64, f64)) -> f32 {
+    if max > min {
+        ((value - min) / (max - min)) as f32
+    } else {
+        0.0
+    }
+}
+
+/// Updates a [`NativeProgressIndicator`]'s value after it has been added to the tree.
+///
+/// Usable from any thread; the native control is updated on the main thread.
+#[derive(Clone)]
+pub struct ProgressHandle {
+    view: RetainedView,
+    value: Arc<Mutex<f64>>,
+    range: (f64, f64),
+}
+
+impl ProgressHandle {
+    /// Set the progress, clamped to the indicator's range.
+    pub fn set_value(&self, value: f64) {
+        let value = value.clamp(self.range.0, self.range.1);
+        *self.value.lock().unwrap() = value;
+        let view = self.view.clone();
+        #[cfg(target_os = "ios")]
+        let range = self.range;
+        run_on_main(move || unsafe {
+            #[cfg(target_os = "macos")]
+            crate::ffi::appkit::NSProgressIndicator::from_ptr(view.as_ptr()).set_double_value(value);
+            #[cfg(target_os = "ios")]
+            crate::ffi::uikit::UIProgressView::from_ptr(view.as_ptr()).set_progress(fraction(value, range));
+        });
+    }
+
+    /// The indicator's current value.
+    pub fn value(&self) -> f64 {
+        *self.value.lock().unwrap()
+    }
+}
@@ -0,0 +1,364 @@
+//! Native radio group widget (radio style NSButtons on macOS).
+//!
+//! The buttons are stacked in one container view. AppKit keeps radio
+//! buttons that share a superview and action mutually exclusive.
+
+use crate::events::{ActionTarget, SharedCallback};
+use crate::ffi::appkit::{NSButton, NSButtonType, NSControlStateValue, NSView};
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Default width for a radio button when AppKit reports no size (in native points)
+const DEFAULT_RADIO_WIDTH: f32 = 18.0;
+/// Default height for a radio button (in native points)
+const DEFAULT_RADIO_HEIGHT: f32 = 18.0;
+/// Vertical space between radio buttons (in native points)
+const RADIO_SPACING: f32 = 6.0;
+/// Stored in `selected` when no option is selected.
+const NO_SELECTION: usize = usize::MAX;
+
+/// Native radio group widget; at most one option is selected at a time.
+pub struct NativeRadioGroup {
+    id: WidgetId,
+    /// Flipped container so the buttons stack from the top.
+    container: NSView,
+    buttons: Vec<NSButton>,
+    options: Vec<String>,
+    /// Selected index, updated by the action target.
+    selected: Arc<AtomicUsize>,
+    on_change: SharedCallback<usize>,
+    /// Receives every button's action; detached in `Drop`.
+    action_target: ActionTarget,
+    /// Cached size of the stacked buttons (width, height)
+    cached_size: Option<(f32, f32)>,
+}
+
+impl NativeRadioGroup {
+    /// Create a radio group with no options.
+    pub fn new() -> Self {
+        let selected = Arc::new(AtomicUsize::new(NO_SELECTION));
+        let on_change = SharedCallback::<usize>::default();
+        let action_target = {
+            let selected = selected.clone();
+            let on_change = on_change.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's buttons.
+                let button = unsafe { NSButton::from_ptr(sender) };
+                let Ok(index) = usize::try_from(button.tag()) else {
+                    return;
+                };
+                if selected.swap(index, Ordering::Relaxed) == index {
+                    return;
+                }
+                if let Some(callback) = on_change.lock().unwrap().as_ref() {
+                    callback(index);
+                }
+            })
+        };
+        let mut group = Self {
+            id: WidgetId::default(),
+            container: NSView::new_flipped(),
+            buttons: Vec::new(),
+            options: Vec::new(),
+            selected,
+            on_change,
+            action_target,
+            cached_size: None,
+        };
+        group.layout_buttons();
+        group
+    }
+
+    /// Set the options, replacing any existing ones. The first option is selected.
+    pub fn options(mut self, options: Vec<String>) -> Self {
+        self.set_options(options);
+        self
+    }
+
+    /// Select the option at `index`.
+    pub fn selected(mut self, index: usize) -> Self {
+        self.set_selected(index);
+        self
+    }
+
+    /// Set the change callback, called with the index the user picked.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
+    pub fn appearance(mut self, name: &str) -> Self {
+        self.container.set_appearance(name);
+        // Metrics can differ between appearances
+        self.layout_buttons();
+        self
+    }
+
+    /// Replace the options. The first option is selected.
+    pub fn set_options(&mut self, options: Vec<String>) {
+        for button in self.buttons.drain(..) {
+            button.set_action(std::ptr::null_mut(), ActionTarget::selector());
+            button.view().remove_from_superview();
+        }
+        for (index, title) in options.iter().enumerate() {
+            let button = NSButton::new();
+            button.set_button_type(NSButtonType::Radio);
+            button.set_title(title);
+            button.set_tag(index as isize);
+            button.set_action(self.action_target.as_ptr(), ActionTarget::selector());
+            self.container.add_subview(button.view());
+            self.buttons.push(button);
+        }
+        self.options = options;
+        self.selected.store(NO_SELECTION, Ordering::Relaxed);
+        self.set_selected(0);
+        self.layout_buttons();
+    }
+
+    /// Select the option at `index` without calling `on_change`. Out-of-range indices are ignored.
+    pub fn set_selected(&mut self, index: usize) {
+        if index >= self.buttons.len() {
+            return;
+        }
+        for (i, button) in self.buttons.iter().enumerate() {
+            let state = if i == index {
+                NSControlStateValue::On
+            } else {
+                NSControlStateValue::Off
+            };
+            button.set_state(state);
+        }
+        self.selected.store(index, Ordering::Relaxed);
+    }
+
+    /// Index of the selected option.
+    pub fn selected_index(&self) -> Option<usize> {
+        match self.selected.load(Ordering::Relaxed) {
+            NO_SELECTION => None,
+            index => Some(index),
+        }
+    }
+
+    /// The option labels.
+    pub fn option_labels(&self) -> &[String] {
+        &self.options
+    }
+
+    /// Stack the buttons at their fitting sizes and cache the total size.
+    fn layout_buttons(&mut self) {
+        let mut width = 0.0_f32;
+        let mut y = 0.0_f32;
+        for (index, button) in self.buttons.iter().enumerate() {
+            if index > 0 {
+                y += RADIO_SPACING;
+            }
+            button.size_to_fit();
+            let (w, h) = button.fitting_size();
+            let w = size_or(w, DEFAULT_RADIO_WIDTH);
+            let h = size_or(h, DEFAULT_RADIO_HEIGHT);
+            button.view().set_frame(0.0, y as f64, w as f64, h as f64);
+            width = width.max(w);
+            y += h;
+        }
+        let width = size_or(width as f64, DEFAULT_RADIO_WIDTH);
+        let height = size_or(y as f64, DEFAULT_RADIO_HEIGHT);
+        self.cached_size = Some((width, height));
+    }
+
+    /// Get the preferred size for this radio group.
+    pub fn preferred_size(&self) -> (f32, f32) {
+        self.cached_size
+            .unwrap_or((DEFAULT_RADIO_WIDTH, DEFAULT_RADIO_HEIGHT))
+    }
+}
+
+impl Default for NativeRadioGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for NativeRadioGroup {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            // Sized by measure(); never narrower than the titles
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::RadioGroup,
+            value: self
+                .selected_index()
+                .and_then(|index| self.options.get(index).cloned()),
+            focusable: true,
+            actions: vec![AccessibleAction::SetValue],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        if action != AccessibleAction::SetValue {
+            return EventResponse::default();
+        }
+        let Some(value) = value else {
+            return EventResponse::default();
+        };
+        let label = value.as_text();
+        let Some(index) = self.options.iter().position(|option| *option == label) else {
+            return EventResponse::default();
+        };
+        self.set_selected(index);
+        if let Some(callback) = self.on_change.lock().unwrap().as_ref() {
+            callback(index);
+        }
+        EventResponse::handled()
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((
+            width / ctx.native_point_scale,
+            height / ctx.native_point_scale,
+        ))
+    }
+
+    fn register_native(
+        &self,
+        widget_id: WidgetId,
+        register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void),
+    ) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+}
+
+impl NativeWidget for NativeRadioGroup {
+    fn native_view(&self) -> NativeViewHandle {
+        self.container.handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // Selection changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeRadioGroup {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // Selection changes are delivered through the action target
+        EventResponse::default()
+    }
+}
+
+impl Drop for NativeRadioGroup {
+    fn drop(&mut self) {
+        // The views may outlive this widget in their superview; don't leave
+        // them pointing at a released target.
+        for button in &self.buttons {
+            button.set_action(std::ptr::null_mut(), ActionTarget::selector());
+        }
+    }
+}
+
+/// `size`, or `default` when AppKit reported no size.
+fn size_or(size: f64, default: f32) -> f32 {
+    if size > 0.0 {
+        size as f32
+    } else {
+        default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objc2::msg_send;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_selection_follows_code_and_clicks() {
+        let picked = Arc::new(Mutex::new(Vec::new()));
+        let mut group = {
+            let picked = picked.clone();
+            NativeRadioGroup::new()
+                .options(vec!["Small".into(), "Medium".into(), "Large".into()])
+                .on_change(move |index| picked.lock().unwrap().push(index))
+        };
+        assert_eq!(group.selected_index(), Some(0));
+        group.set_selected(1);
+        let on: Vec<_> = group
+            .buttons
+            .iter()
+            .map(|button| button.state() == NSControlStateValue::On)
+            .collect();
+        assert_eq!(on, [false, true, false]);
+        let sent: bool = unsafe {
+            msg_send![
+                group.buttons[2].view().as_ptr(),
+                sendAction: ActionTarget::selector(),
+                to: group.action_target.as_ptr()
+            ]
+        };
+        assert!(sent);
+        assert_eq!(group.selected_index(), Some(2));
+        assert_eq!(*picked.lock().unwrap(), [2]);
+    }
+
+    #[test]
+    fn test_buttons_stack_without_overlapping() {
+        let group = NativeRadioGroup::new().options(vec!["One".into(), "Two".into()]);
+        let (_, first_y, _, first_height) = group.buttons[0].view().frame();
+        let (_, second_y, _, _) = group.buttons[1].view().frame();
+        assert!(second_y >= first_y + first_height);
+        let (_, height) = group.preferred_size();
+        assert!(height as f64 >= second_y);
+    }
+}
@@ -0,0 +1,203 @@
+//! Native scroll container widget (NSScrollView on macOS).
+//!
+//! Native widgets inside the content are placed in the scroll view's document
+//! view and scrolled by AppKit. GPU-painted widgets inside it are not scrolled,
+//! so the content should be made of native widgets.
+
+use crate::ffi::appkit::{NSScrollView, NSScrollerStyle, NSView};
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use spark_core::Rect;
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{AccessibleInfo, AccessibleRole, EventContext, EventResponse, Theme, Widget};
+
+/// Native scroll container widget.
+pub struct NativeScrollView {
+    id: WidgetId,
+    scroll: NSScrollView,
+    /// Flipped, so document coordinates match layout coordinates.
+    document: NSView,
+    /// The content widget, at most one.
+    children: Vec<Box<dyn Widget>>,
+    layout_style: taffy::Style,
+}
+
+impl NativeScrollView {
+    /// Create a vertically scrolling container with overlay scrollers.
+    pub fn new() -> Self {
+        let scroll = NSScrollView::new();
+        let document = NSView::new_flipped();
+        scroll.set_document_view(&document);
+        scroll.set_has_vertical_scroller(true);
+        scroll.set_scroller_style(NSScrollerStyle::Overlay);
+        scroll.set_draws_background(false);
+        Self {
+            id: WidgetId::default(),
+            scroll,
+            document,
+            children: Vec::new(),
+            layout_style: taffy::Style {
+                display: taffy::Display::Flex,
+                flex_direction: taffy::FlexDirection::Column,
+                overflow: taffy::Point {
+                    x: taffy::Overflow::Hidden,
+                    y: taffy::Overflow::Hidden,
+                },
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Set the content widget.
+    pub fn content(mut self, widget: impl Widget + 'static) -> Self {
+        self.children = vec![Box::new(widget)];
+        self
+    }
+
+    /// Also scroll horizontally.
+    pub fn horizontal(self, enabled: bool) -> Self {
+        self.scroll.set_has_horizontal_scroller(enabled);
+        self
+    }
+
+    /// Set the scroller style.
+    pub fn scroller_style(self, style: NSScrollerStyle) -> Self {
+        self.scroll.set_scroller_style(style);
+        self
+    }
+
+    /// Fill available space.
+    pub fn fill(mut self) -> Self {
+        self.layout_style.size = taffy::Size {
+            width: taffy::prelude::percent(1.0),
+            height: taffy::prelude::percent(1.0),
+        };
+        self.layout_style.flex_grow = 1.0;
+        self
+    }
+
+    /// Set fixed size.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.layout_style.size = taffy::Size {
+            width: taffy::prelude::length(width),
+            height: taffy::prelude::length(height),
+        };
+        self
+    }
+
+    /// Scroll so that document point (`x`, `y`) is at the top-left, in native points.
+    pub fn scroll_to(&self, x: f64, y: f64) {
+        self.scroll.scroll_to(x, y);
+    }
+
+    /// Document point at the top-left of the visible area, in native points.
+    pub fn scroll_position(&self) -> (f64, f64) {
+        self.scroll.scroll_position()
+    }
+}
+
+impl Default for NativeScrollView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for NativeScrollView {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        self.layout_style.clone()
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        &mut self.children
+    }
+
+    fn is_scroll_container(&self) -> bool {
+        true
+    }
+
+    fn scroll_to_visible(&mut self, rect: Rect, viewport: Rect) {
+        // The scroll view works in points; derive the scale from its visible size.
+        let (visible_width, visible_height) = self.scroll.content_size();
+        if viewport.width <= 0.0 || viewport.height <= 0.0 {
+            return;
+        }
+        let scale_x = visible_width / viewport.width as f64;
+        let scale_y = visible_height / viewport.height as f64;
+        let (mut x, mut y) = self.scroll.scroll_position();
+
+        let (top, bottom) = (rect.y as f64 * scale_y, (rect.y + rect.height) as f64 * scale_y);
+        if top < y {
+            y = top;
+        } else if bottom > y + visible_height {
+            y = bottom - visible_height;
+        }
+        let (left, right) = (rect.x as f64 * scale_x, (rect.x + rect.width) as f64 * scale_x);
+        if left < x {
+            x = left;
+        } else if right > x + visible_width {
+            x = right - visible_width;
+        }
+        self.scroll.scroll_to(x, y);
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::ScrollView,
+            ..Default::default()
+        }
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+
+    fn native_document_view(&self) -> Option<*mut std::ffi::c_void> {
+        Some(self.document.as_ptr() as *mut std::ffi::c_void)
+    }
+}
+
+impl NativeWidget for NativeScrollView {
+    fn native_view(&self) -> NativeViewHandle {
+        self.scroll.view().handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // Scrolling is handled entirely by AppKit
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeScrollView {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        EventResponse::default()
+    }
+}
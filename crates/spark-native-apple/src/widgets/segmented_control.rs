@@ -0,0 +1,282 @@
+//! Native segmented control widget (NSSegmentedControl on macOS).
+
+use crate::events::{ActionTarget, SharedCallback};
+use crate::ffi::appkit::{NSSegmentSwitchTracking, NSSegmentedControl};
+use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use objc2::runtime::AnyObject;
+use spark_input::InputEvent;
+use spark_layout::{taffy, WidgetId};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Default width per segment when the control reports no fitting size (in logical pixels)
+const DEFAULT_SEGMENT_WIDTH: f32 = 60.0;
+/// Default height for segmented controls (in logical pixels)
+const DEFAULT_SEGMENTED_HEIGHT: f32 = 24.0;
+/// Stored in `selected` when no segment is selected.
+const NO_SELECTION: usize = usize::MAX;
+
+/// Native segmented control widget; exactly one segment is selected at a time.
+pub struct NativeSegmentedControl {
+    id: WidgetId,
+    control: NSSegmentedControl,
+    segments: Vec<String>,
+    /// Selected index, updated by the action target.
+    selected: Arc<AtomicUsize>,
+    on_change: SharedCallback<usize>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: ActionTarget,
+    /// Cached fitting size (width, height)
+    cached_size: Option<(f32, f32)>,
+}
+
+impl NativeSegmentedControl {
+    /// Create a segmented control with no segments.
+    pub fn new() -> Self {
+        let selected = Arc::new(AtomicUsize::new(NO_SELECTION));
+        let on_change = SharedCallback::<usize>::default();
+        let action_target = {
+            let selected = selected.clone();
+            let on_change = on_change.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's control.
+                let Some(index) = (unsafe { sender_selection(sender) }) else {
+                    return;
+                };
+                selected.store(index, Ordering::Relaxed);
+                if let Some(callback) = on_change.lock().unwrap().as_ref() {
+                    callback(index);
+                }
+            })
+        };
+        let mut control = Self {
+            id: WidgetId::default(),
+            control: NSSegmentedControl::new(),
+            segments: Vec::new(),
+            selected,
+            on_change,
+            action_target,
+            cached_size: None,
+        };
+        control.control.set_tracking_mode(NSSegmentSwitchTracking::SelectOne);
+        control
+            .control
+            .set_action(control.action_target.as_ptr(), ActionTarget::selector());
+        control.update_cached_size();
+        control
+    }
+
+    /// Set the segments, replacing any existing ones. The first segment is selected.
+    pub fn segments(mut self, segments: Vec<String>) -> Self {
+        self.set_segments(segments);
+        self
+    }
+
+    /// Select the segment at `index`.
+    pub fn selected(mut self, index: usize) -> Self {
+        self.set_selected(index);
+        self
+    }
+
+    /// Set the change callback, called with the index the user picked.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
+    pub fn appearance(mut self, name: &str) -> Self {
+        self.control.view().set_appearance(name);
+        // Metrics can differ between appearances
+        self.update_cached_size();
+        self
+    }
+
+    /// Replace the segments. The first segment is selected.
+    pub fn set_segments(&mut self, segments: Vec<String>) {
+        self.control.set_segment_count(segments.len());
+        for (index, label) in segments.iter().enumerate() {
+            self.control.set_label(label, index);
+        }
+        self.segments = segments;
+        if !self.segments.is_empty() {
+            self.control.set_selected_segment(0);
+        }
+        self.sync_selection();
+        self.update_cached_size();
+    }
+
+    /// Select the segment at `index` without calling `on_change`. Out-of-range indices are ignored.
+    pub fn set_selected(&mut self, index: usize) {
+        if index < self.segments.len() {
+            self.control.set_selected_segment(index);
+            self.sync_selection();
+        }
+    }
+
+    /// Index of the selected segment.
+    pub fn selected_index(&self) -> Option<usize> {
+        match self.selected.load(Ordering::Relaxed) {
+            NO_SELECTION => None,
+            index => Some(index),
+        }
+    }
+
+    /// The segment labels.
+    pub fn segment_labels(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Read the selection back from the control.
+    fn sync_selection(&self) {
+        let index = self.control.selected_segment().unwrap_or(NO_SELECTION);
+        self.selected.store(index, Ordering::Relaxed);
+    }
+
+    /// Update the cached size from the control's fitting size, so labels never truncate.
+    fn update_cached_size(&mut self) {
+        let (width, height) = self.control.fitting_size();
+        let fallback_width = DEFAULT_SEGMENT_WIDTH * self.segments.len().max(1) as f32;
+        let width = if width > 0.0 { width as f32 } else { fallback_width };
+        let height = if height > 0.0 { height as f32 } else { DEFAULT_SEGMENTED_HEIGHT };
+        self.cached_size = Some((width, height));
+    }
+
+    /// Get the preferred size for this segmented control.
+    pub fn preferred_size(&self) -> (f32, f32) {
+        self.cached_size
+            .unwrap_or((DEFAULT_SEGMENT_WIDTH, DEFAULT_SEGMENTED_HEIGHT))
+    }
+}
+
+impl Default for NativeSegmentedControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for NativeSegmentedControl {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            // Sized by measure(); never narrower than the labels
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut spark_widgets::PaintContext) {
+        // Native widgets render themselves
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        <Self as NativeWidgetExt>::handle_event(self, ctx, event)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::RadioGroup,
+            value: self
+                .selected_index()
+                .and_then(|index| self.segments.get(index).cloned()),
+            focusable: true,
+            actions: vec![AccessibleAction::SetValue],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        if action != AccessibleAction::SetValue {
+            return EventResponse::default();
+        }
+        let Some(value) = value else {
+            return EventResponse::default();
+        };
+        let label = value.as_text();
+        let Some(index) = self.segments.iter().position(|segment| *segment == label) else {
+            return EventResponse::default();
+        };
+        self.set_selected(index);
+        if let Some(callback) = self.on_change.lock().unwrap().as_ref() {
+            callback(index);
+        }
+        EventResponse::handled()
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((width / ctx.native_point_scale, height / ctx.native_point_scale))
+    }
+
+    fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
+        let NativeViewHandle::AppKit(ptr) = <Self as NativeWidget>::native_view(self);
+        register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
+    }
+}
+
+impl NativeWidget for NativeSegmentedControl {
+    fn native_view(&self) -> NativeViewHandle {
+        self.control.view().handle()
+    }
+
+    fn update_layout(&mut self, layout: &taffy::Layout, scale_factor: f32) {
+        let _ = (layout, scale_factor);
+    }
+
+    fn bridge_events(&mut self) -> Vec<InputEvent> {
+        // Selection changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl NativeWidgetExt for NativeSegmentedControl {
+    fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
+        // Selection changes are delivered through the action target
+        EventResponse::default()
+    }
+}
+
+impl Drop for NativeSegmentedControl {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
+        self.control.set_action(std::ptr::null_mut(), ActionTarget::selector());
+    }
+}
+
+/// Read the selection of the segmented control that sent an action.
+///
+/// # Safety
+///
+/// `sender` must point to a live NSSegmentedControl.
+unsafe fn sender_selection(sender: *mut AnyObject) -> Option<usize> {
+    NSSegmentedControl::from_ptr(sender).selected_segment()
+}
@@ -2,8 +2,14 @@
 
 use spark_input::InputEvent;
 use spark_layout::{taffy, WidgetId};
-use spark_widgets::{EventContext, EventResponse, LayoutContext, Widget};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use crate::events::{run_on_main, ActionTarget, RetainedView, SharedCallback};
 use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use objc2::runtime::AnyObject;
+use std::sync::{Arc, Mutex};
 
 /// Default minimum width for sliders (in logical pixels)
 const DEFAULT_MIN_SLIDER_WIDTH: f32 = 100.0;
@@ -21,8 +27,11 @@ pub struct NativeSlider {
     slider: crate::ffi::uikit::UISlider,
     min_value: f64,
     max_value: f64,
-    value: f64,
-    on_change: Option<Box<dyn Fn(f64) + Send + Sync>>,
+    /// Current value, shared with the action target and handles.
+    value: Arc<Mutex<f64>>,
+    on_change: SharedCallback<f64>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: ActionTarget,
     /// Preferred width (can be customized)
     preferred_width: f32,
 }
@@ -30,6 +39,20 @@ pub struct NativeSlider {
 impl NativeSlider {
     /// Create a new native slider.
     pub fn new() -> Self {
+        let value = Arc::new(Mutex::new(50.0));
+        let on_change = SharedCallback::<f64>::default();
+        let action_target = {
+            let value = value.clone();
+            let on_change = on_change.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's slider.
+                let new_value = unsafe { sender_value(sender) };
+                *value.lock().unwrap() = new_value;
+                if let Some(callback) = on_change.lock().unwrap().as_ref() {
+                    callback(new_value);
+                }
+            })
+        };
         let mut slider = Self {
             id: WidgetId::default(),
             #[cfg(target_os = "macos")]
@@ -38,10 +61,24 @@ impl NativeSlider {
             slider: crate::ffi::uikit::UISlider::new(),
             min_value: 0.0,
             max_value: 100.0,
-            value: 50.0,
-            on_change: None,
+            value,
+            on_change,
+            action_target,
             preferred_width: DEFAULT_PREFERRED_SLIDER_WIDTH,
         };
+        #[cfg(target_os = "macos")]
+        {
+            slider.slider.set_continuous(true);
+            slider
+                .slider
+                .set_action(slider.action_target.as_ptr(), ActionTarget::selector());
+        }
+        #[cfg(target_os = "ios")]
+        slider.slider.add_target(
+            slider.action_target.as_ptr(),
+            ActionTarget::selector(),
+            crate::ffi::uikit::UIControlEvents::ValueChanged,
+        );
         slider.update_native_values();
         slider
     }
@@ -63,19 +100,41 @@ impl NativeSlider {
 
     /// Set the current value.
     pub fn value(mut self, value: f64) -> Self {
-        self.value = value.clamp(self.min_value, self.max_value);
+        *self.value.lock().unwrap() = value.clamp(self.min_value, self.max_value);
         self.update_native_values();
         self
     }
 
-    /// Set the change callback.
-    pub fn on_change<F>(mut self, callback: F) -> Self
+    /// Set the change callback, called continuously while the knob is dragged.
+    pub fn on_change<F>(self, callback: F) -> Self
     where
         F: Fn(f64) + Send + Sync + 'static,
     {
-        self.on_change = Some(Box::new(callback));
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
         self
     }
+
+    /// A handle for setting the value after the slider is in the tree.
+    ///
+    /// The handle clamps to the range configured at the time it is created.
+    pub fn value_handle(&self) -> SliderHandle {
+        let view = match <Self as NativeWidget>::native_view(self) {
+            #[cfg(target_os = "macos")]
+            NativeViewHandle::AppKit(ptr) => ptr,
+            #[cfg(target_os = "ios")]
+            NativeViewHandle::UIKit(ptr) => ptr,
+        };
+        SliderHandle {
+            view,
+            value: self.value.clone(),
+            range: (self.min_value, self.max_value),
+        }
+    }
+
+    /// The current value.
+    pub fn current_value(&self) -> f64 {
+        *self.value.lock().unwrap()
+    }
     
     /// Set the preferred width for the slider.
     pub fn width(mut self, width: f32) -> Self {
@@ -84,17 +143,18 @@ impl NativeSlider {
     }
 
     fn update_native_values(&mut self) {
+        let value = self.current_value();
         #[cfg(target_os = "macos")]
         {
             self.slider.set_min_value(self.min_value);
             self.slider.set_max_value(self.max_value);
-            self.slider.set_double_value(self.value);
+            self.slider.set_double_value(value);
         }
         #[cfg(target_os = "ios")]
         {
             self.slider.set_minimum_value(self.min_value as f32);
             self.slider.set_maximum_value(self.max_value as f32);
-            self.slider.set_value(self.value as f32);
+            self.slider.set_value(value as f32);
         }
     }
     
@@ -136,7 +196,7 @@ impl Widget for NativeSlider {
         self.id = id;
     }
 
-    fn style(&self) -> taffy::Style {
+    fn style(&self, _theme: &Theme) -> taffy::Style {
         use taffy::prelude::*;
         let (pref_width, pref_height) = self.preferred_size();
         taffy::Style {
@@ -172,6 +232,50 @@ impl Widget for NativeSlider {
     fn is_native(&self) -> bool {
         true
     }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Slider,
+            value: Some(self.current_value().to_string()),
+            focusable: true,
+            actions: vec![
+                AccessibleAction::Increment,
+                AccessibleAction::Decrement,
+                AccessibleAction::SetValue,
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        // Step by a tenth of the range, like the native slider's arrow keys.
+        let step = (self.max_value - self.min_value) / 10.0;
+        let current = self.current_value();
+        let new_value = match (action, value) {
+            (AccessibleAction::Increment, _) => current + step,
+            (AccessibleAction::Decrement, _) => current - step,
+            (AccessibleAction::SetValue, Some(AccessibleValue::Number(value))) => *value,
+            (AccessibleAction::SetValue, Some(AccessibleValue::Text(text))) => {
+                match text.trim().parse() {
+                    Ok(value) => value,
+                    Err(_) => return EventResponse::default(),
+                }
+            }
+            _ => return EventResponse::default(),
+        };
+        let new_value = new_value.clamp(self.min_value, self.max_value);
+        *self.value.lock().unwrap() = new_value;
+        self.update_native_values();
+        if let Some(callback) = self.on_change.lock().unwrap().as_ref() {
+            callback(new_value);
+        }
+        EventResponse::handled()
+    }
     
     fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
         Some(self.preferred_size())
@@ -182,11 +286,11 @@ impl Widget for NativeSlider {
         match view_handle {
             #[cfg(target_os = "macos")]
             crate::NativeViewHandle::AppKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
             #[cfg(target_os = "ios")]
             crate::NativeViewHandle::UIKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
         }
     }
@@ -196,11 +300,11 @@ impl NativeWidget for NativeSlider {
     fn native_view(&self) -> NativeViewHandle {
         #[cfg(target_os = "macos")]
         {
-            NativeViewHandle::AppKit(self.slider.view().as_ptr())
+            self.slider.view().handle()
         }
         #[cfg(target_os = "ios")]
         {
-            NativeViewHandle::UIKit(self.slider.view().as_ptr())
+            self.slider.view().handle()
         }
     }
 
@@ -209,34 +313,69 @@ impl NativeWidget for NativeSlider {
     }
 
     fn bridge_events(&mut self) -> Vec<InputEvent> {
-        // Check if value changed
+        // Value changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl Drop for NativeSlider {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
         #[cfg(target_os = "macos")]
-        {
-            let new_value = self.slider.double_value();
-            if (new_value - self.value).abs() > 0.01 {
-                self.value = new_value;
-                if let Some(ref callback) = self.on_change {
-                    callback(self.value);
-                }
-            }
-        }
+        self.slider.set_action(std::ptr::null_mut(), ActionTarget::selector());
         #[cfg(target_os = "ios")]
-        {
-            let new_value = self.slider.value() as f64;
-            if (new_value - self.value).abs() > 0.01 {
-                self.value = new_value;
-                if let Some(ref callback) = self.on_change {
-                    callback(self.value);
-                }
-            }
-        }
-        Vec::new()
+        self.slider.remove_target(self.action_target.as_ptr());
+    }
+}
+
+/// Read the value of the slider that sent an action.
+///
+/// # Safety
+///
+/// `sender` must point to a live NSSlider (macOS) or UISlider (iOS).
+unsafe fn sender_value(sender: *mut AnyObject) -> f64 {
+    #[cfg(target_os = "macos")]
+    let value = crate::ffi::appkit::NSSlider::from_ptr(sender).double_value();
+    #[cfg(target_os = "ios")]
+    let value = crate::ffi::uikit::UISlider::from_ptr(sender).value() as f64;
+    value
+}
+
+/// Sets a [`NativeSlider`]'s value after it has been added to the tree.
+///
+/// Usable from any thread; the native control is updated on the main thread.
+/// Setting the value does not call the slider's `on_change`.
+#[derive(Clone)]
+pub struct SliderHandle {
+    view: RetainedView,
+    value: Arc<Mutex<f64>>,
+    range: (f64, f64),
+}
+
+impl SliderHandle {
+    /// Move the slider to `value`, clamped to its range.
+    pub fn set_value(&self, value: f64) {
+        let value = value.clamp(self.range.0, self.range.1);
+        *self.value.lock().unwrap() = value;
+        let view = self.view.clone();
+        run_on_main(move || unsafe {
+            #[cfg(target_os = "macos")]
+            crate::ffi::appkit::NSSlider::from_ptr(view.as_ptr()).set_double_value(value);
+            #[cfg(target_os = "ios")]
+            crate::ffi::uikit::UISlider::from_ptr(view.as_ptr()).set_value(value as f32);
+        });
+    }
+
+    /// The slider's current value.
+    pub fn value(&self) -> f64 {
+        *self.value.lock().unwrap()
     }
 }
 
 impl NativeWidgetExt for NativeSlider {
     fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
-        // Events are handled through bridge_events
+        // Value changes are delivered through the action target
         EventResponse::default()
     }
 }
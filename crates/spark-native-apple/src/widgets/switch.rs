@@ -1,9 +1,19 @@
 //! Native switch widget (NSSwitch on macOS, UISwitch on iOS).
+//!
+//! Before macOS 10.15 the switch falls back to a titled check box; use
+//! [`NativeCheckbox`](super::NativeCheckbox) when a check box is wanted.
 
 use spark_input::InputEvent;
 use spark_layout::{taffy, WidgetId};
-use spark_widgets::{EventContext, EventResponse, LayoutContext, Widget};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
+use crate::events::{ActionTarget, SharedCallback};
 use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
+use objc2::runtime::AnyObject;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// Default minimum width for switches (in logical pixels)
 const DEFAULT_MIN_SWITCH_WIDTH: f32 = 40.0;
@@ -17,9 +27,12 @@ pub struct NativeSwitch {
     switch: crate::ffi::appkit::NSSwitch,
     #[cfg(target_os = "ios")]
     switch: crate::ffi::uikit::UISwitch,
-    checked: bool,
+    /// Last known state, updated by the action target.
+    checked: Arc<AtomicBool>,
     title: String,
-    on_change: Option<Box<dyn Fn(bool) + Send + Sync>>,
+    on_change: SharedCallback<bool>,
+    /// Receives the control's action; detached in `Drop`.
+    action_target: ActionTarget,
     /// Cached intrinsic size
     cached_size: Option<(f32, f32)>,
 }
@@ -28,17 +41,42 @@ impl NativeSwitch {
     /// Create a new native switch.
     pub fn new(title: impl Into<String>) -> Self {
         let title = title.into();
+        let checked = Arc::new(AtomicBool::new(false));
+        let on_change = SharedCallback::<bool>::default();
+        let action_target = {
+            let checked = checked.clone();
+            let on_change = on_change.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's switch.
+                let on = unsafe { sender_is_on(sender) };
+                checked.store(on, Ordering::Relaxed);
+                if let Some(callback) = on_change.lock().unwrap().as_ref() {
+                    callback(on);
+                }
+            })
+        };
         let mut switch = Self {
             id: WidgetId::default(),
             #[cfg(target_os = "macos")]
             switch: crate::ffi::appkit::NSSwitch::new(),
             #[cfg(target_os = "ios")]
             switch: crate::ffi::uikit::UISwitch::new(),
-            checked: false,
+            checked,
             title: title.clone(),
-            on_change: None,
+            on_change,
+            action_target,
             cached_size: None,
         };
+        #[cfg(target_os = "macos")]
+        switch
+            .switch
+            .set_action(switch.action_target.as_ptr(), ActionTarget::selector());
+        #[cfg(target_os = "ios")]
+        switch.switch.add_target(
+            switch.action_target.as_ptr(),
+            ActionTarget::selector(),
+            crate::ffi::uikit::UIControlEvents::ValueChanged,
+        );
         switch.set_title(&title);
         switch.update_cached_size();
         switch
@@ -46,16 +84,22 @@ impl NativeSwitch {
 
     /// Set the switch state.
     pub fn checked(mut self, checked: bool) -> Self {
-        self.checked = checked;
+        self.set_checked(checked);
+        self
+    }
+
+    /// Update the switch state without calling `on_change`.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked.store(checked, Ordering::Relaxed);
         #[cfg(target_os = "macos")]
-        {
-            self.switch.set_state(if checked { 1 } else { 0 });
-        }
+        self.switch.set_state(if checked { 1 } else { 0 });
         #[cfg(target_os = "ios")]
-        {
-            self.switch.set_on(checked);
-        }
-        self
+        self.switch.set_on(checked);
+    }
+
+    /// Whether the switch is on.
+    pub fn is_checked(&self) -> bool {
+        self.checked.load(Ordering::Relaxed)
     }
 
     /// Set the switch title.
@@ -70,17 +114,16 @@ impl NativeSwitch {
 
     /// Set the appearance (e.g. "NSAppearanceNameAqua" for light mode).
     pub fn appearance(self, name: &str) -> Self {
-        #[cfg(target_os = "macos")]
         self.switch.view().set_appearance(name);
         self
     }
 
-    /// Set the change callback.
-    pub fn on_change<F>(mut self, callback: F) -> Self
+    /// Set the change callback, called when the user toggles the switch.
+    pub fn on_change<F>(self, callback: F) -> Self
     where
         F: Fn(bool) + Send + Sync + 'static,
     {
-        self.on_change = Some(Box::new(callback));
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
         self
     }
     
@@ -88,7 +131,7 @@ impl NativeSwitch {
     fn update_cached_size(&mut self) {
         #[cfg(target_os = "macos")]
         {
-            // NSSwitch has a fixed size and no label
+            // NSSwitch has a fixed size and no label; the fallback check box has one
             self.switch.size_to_fit();
             let (width, height) = self.switch.intrinsic_content_size();
             // Standard NSSwitch size is approx 38x21 or similar depending on OS version
@@ -98,9 +141,11 @@ impl NativeSwitch {
         }
         #[cfg(target_os = "ios")]
         {
-            // iOS switches don't have labels by default, use standard size
-            // UISwitch has a fixed size of approximately 51x31
-            self.cached_size = Some((51.0, 31.0));
+            // UISwitch has no label and a fixed size of approximately 51x31
+            let (width, height) = self.switch.intrinsic_content_size();
+            let w = if width > 0.0 { width as f32 } else { 51.0 };
+            let h = if height > 0.0 { height as f32 } else { 31.0 };
+            self.cached_size = Some((w, h));
         }
         #[cfg(not(any(target_os = "macos", target_os = "ios")))]
         {
@@ -125,7 +170,7 @@ impl Widget for NativeSwitch {
         self.id = id;
     }
 
-    fn style(&self) -> taffy::Style {
+    fn style(&self, _theme: &Theme) -> taffy::Style {
         use taffy::prelude::*;
         let (pref_width, pref_height) = self.preferred_size();
         taffy::Style {
@@ -160,9 +205,39 @@ impl Widget for NativeSwitch {
     fn is_native(&self) -> bool {
         true
     }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::CheckBox,
+            name: Some(self.title.clone()),
+            value: Some(if self.is_checked() { "on" } else { "off" }.to_string()),
+            focusable: true,
+            actions: vec![AccessibleAction::Click],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        _value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        if action != AccessibleAction::Click {
+            return EventResponse::default();
+        }
+        let checked = !self.is_checked();
+        self.set_checked(checked);
+        if let Some(callback) = self.on_change.lock().unwrap().as_ref() {
+            callback(checked);
+        }
+        EventResponse::handled()
+    }
     
-    fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
-        Some(self.preferred_size())
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        // The cached size is in native points
+        let (width, height) = self.preferred_size();
+        Some((width / ctx.native_point_scale, height / ctx.native_point_scale))
     }
     
     fn register_native(&self, widget_id: WidgetId, register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
@@ -170,11 +245,11 @@ impl Widget for NativeSwitch {
         match view_handle {
             #[cfg(target_os = "macos")]
             crate::NativeViewHandle::AppKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
             #[cfg(target_os = "ios")]
             crate::NativeViewHandle::UIKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
         }
     }
@@ -184,11 +259,11 @@ impl NativeWidget for NativeSwitch {
     fn native_view(&self) -> NativeViewHandle {
         #[cfg(target_os = "macos")]
         {
-            NativeViewHandle::AppKit(self.switch.view().as_ptr())
+            self.switch.view().handle()
         }
         #[cfg(target_os = "ios")]
         {
-            NativeViewHandle::UIKit(self.switch.view().as_ptr())
+            self.switch.view().handle()
         }
     }
 
@@ -197,35 +272,38 @@ impl NativeWidget for NativeSwitch {
     }
 
     fn bridge_events(&mut self) -> Vec<InputEvent> {
-        // Check if state changed
+        // State changes arrive through the action target
+        Vec::new()
+    }
+}
+
+impl Drop for NativeSwitch {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
         #[cfg(target_os = "macos")]
-        {
-            let new_state = self.switch.state();
-            let new_checked = new_state == 1;
-            if new_checked != self.checked {
-                self.checked = new_checked;
-                if let Some(ref callback) = self.on_change {
-                    callback(self.checked);
-                }
-            }
-        }
+        self.switch.set_action(std::ptr::null_mut(), ActionTarget::selector());
         #[cfg(target_os = "ios")]
-        {
-            let new_checked = self.switch.is_on();
-            if new_checked != self.checked {
-                self.checked = new_checked;
-                if let Some(ref callback) = self.on_change {
-                    callback(self.checked);
-                }
-            }
-        }
-        Vec::new()
+        self.switch.remove_target(self.action_target.as_ptr());
     }
 }
 
+/// Read the state of the switch that sent an action.
+///
+/// # Safety
+///
+/// `sender` must point to a live NSSwitch (macOS) or UISwitch (iOS).
+unsafe fn sender_is_on(sender: *mut AnyObject) -> bool {
+    #[cfg(target_os = "macos")]
+    let on = crate::ffi::appkit::NSSwitch::from_ptr(sender).state() == 1;
+    #[cfg(target_os = "ios")]
+    let on = crate::ffi::uikit::UISwitch::from_ptr(sender).is_on();
+    on
+}
+
 impl NativeWidgetExt for NativeSwitch {
     fn handle_event(&mut self, _ctx: &mut EventContext, _event: &InputEvent) -> EventResponse {
-        // Events are handled through bridge_events
+        // Toggles are delivered through the action target
         EventResponse::default()
     }
 }
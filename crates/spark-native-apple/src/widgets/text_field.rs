@@ -1,14 +1,22 @@
 //! Native text field widget (NSTextField on macOS, UITextField on iOS).
 
+use crate::events::ActionTarget;
+#[cfg(target_os = "macos")]
+use crate::ffi::appkit::NSTextField;
+#[cfg(target_os = "ios")]
+use crate::ffi::uikit::UITextField;
 use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
 use spark_input::InputEvent;
 use spark_layout::{taffy, WidgetId};
-use spark_widgets::{EventContext, EventResponse, LayoutContext, Widget};
+use spark_widgets::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, Theme, Widget,
+};
 use std::sync::Arc;
 use std::sync::Mutex;
 
-type OnChangeCallback = Box<dyn Fn(&str) + Send + Sync>;
-
+/// Text callbacks receive a `&str`, so they are stored separately from `SharedCallback`.
+type TextCallback = Arc<Mutex<Option<Box<dyn Fn(&str) + Send + Sync>>>>;
 
 /// Default minimum width for text fields (in logical pixels)
 const DEFAULT_MIN_TEXT_FIELD_WIDTH: f32 = 100.0;
@@ -18,15 +26,19 @@ const DEFAULT_MIN_TEXT_FIELD_HEIGHT: f32 = 22.0;
 const DEFAULT_PREFERRED_TEXT_FIELD_WIDTH: f32 = 200.0;
 
 /// Native text field widget.
+///
+/// `on_change` fires after every edit with the new text.
 pub struct NativeTextField {
     id: WidgetId,
     #[cfg(target_os = "macos")]
     view: crate::ffi::appkit::NSTextField,
     #[cfg(target_os = "ios")]
     view: crate::ffi::uikit::UITextField,
-    text: String,
-    placeholder: String,
-    on_change: Option<OnChangeCallback>,
+    /// Last text reported to `on_change`, updated by the action target.
+    text: Arc<Mutex<String>>,
+    on_change: TextCallback,
+    /// Receives edits (as the delegate on macOS, the action on iOS); detached in `Drop`.
+    action_target: ActionTarget,
     pending_events: Arc<Mutex<Vec<InputEvent>>>,
     /// Preferred width (can be customized)
     preferred_width: f32,
@@ -35,50 +47,101 @@ pub struct NativeTextField {
 impl NativeTextField {
     /// Create a new native text field.
     pub fn new() -> Self {
-        Self {
+        let text = Arc::new(Mutex::new(String::new()));
+        let on_change = TextCallback::default();
+        let action_target = {
+            let text = text.clone();
+            let on_change = on_change.clone();
+            ActionTarget::new(move |sender| {
+                // SAFETY: this target is only attached to this widget's text field.
+                #[cfg(target_os = "macos")]
+                let value = unsafe { NSTextField::from_ptr(sender) }.string_value();
+                #[cfg(target_os = "ios")]
+                let value = unsafe { UITextField::from_ptr(sender) }.text();
+                report_change(&text, &on_change, value);
+            })
+        };
+        let field = Self {
             id: WidgetId::default(),
             #[cfg(target_os = "macos")]
-            view: crate::ffi::appkit::NSTextField::new(),
+            view: NSTextField::new(),
             #[cfg(target_os = "ios")]
-            view: crate::ffi::uikit::UITextField::new(),
-            text: String::new(),
-            placeholder: String::new(),
-            on_change: None,
+            view: UITextField::new(),
+            text,
+            on_change,
+            action_target,
             pending_events: Arc::new(Mutex::new(Vec::new())),
             preferred_width: DEFAULT_PREFERRED_TEXT_FIELD_WIDTH,
+        };
+        #[cfg(target_os = "macos")]
+        {
+            let target = field.action_target.as_ptr();
+            field.view.set_delegate(target);
+            field.view.set_action(target, ActionTarget::selector());
+        }
+        // UITextField is a UIControl and reports every edit
+        #[cfg(target_os = "ios")]
+        {
+            field.view.set_rounded_border();
+            field.view.add_target(
+                field.action_target.as_ptr(),
+                ActionTarget::selector(),
+                crate::ffi::uikit::UIControlEvents::EditingChanged,
+            );
         }
+        field
     }
 
     /// Set the placeholder text.
-    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
-        self.placeholder = placeholder.into();
+    pub fn placeholder(self, placeholder: impl Into<String>) -> Self {
+        let placeholder = placeholder.into();
         #[cfg(target_os = "macos")]
-        {
-            self.view.set_placeholder_string(&self.placeholder);
-        }
+        self.view.set_placeholder_string(&placeholder);
+        #[cfg(target_os = "ios")]
+        self.view.set_placeholder(&placeholder);
         self
     }
 
-    /// Set the text value.
+    /// Set the text value without calling `on_change`.
     pub fn set_text(&mut self, text: impl Into<String>) {
-        self.text = text.into();
+        let text = text.into();
         #[cfg(target_os = "macos")]
-        self.view.set_string_value(&self.text);
+        self.view.set_string_value(&text);
         #[cfg(target_os = "ios")]
-        self.view.set_text(&self.text);
+        self.view.set_text(&text);
+        *self.text.lock().unwrap() = text;
     }
 
-    /// Get the text value.
-    pub fn text(&self) -> &str {
-        &self.text
+    /// The text currently in the field, including uncommitted edits.
+    pub fn value(&self) -> String {
+        #[cfg(target_os = "macos")]
+        {
+            self.view.string_value()
+        }
+        #[cfg(target_os = "ios")]
+        {
+            self.view.text()
+        }
     }
 
-    /// Set the change callback.
-    pub fn on_change<F>(mut self, callback: F) -> Self
+    /// The placeholder text.
+    pub fn placeholder_text(&self) -> String {
+        #[cfg(target_os = "macos")]
+        {
+            self.view.placeholder_string()
+        }
+        #[cfg(target_os = "ios")]
+        {
+            self.view.placeholder()
+        }
+    }
+
+    /// Set the change callback, called with the new text.
+    pub fn on_change<F>(self, callback: F) -> Self
     where
         F: Fn(&str) + Send + Sync + 'static,
     {
-        self.on_change = Some(Box::new(callback));
+        *self.on_change.lock().unwrap() = Some(Box::new(callback));
         self
     }
     
@@ -120,7 +183,7 @@ impl Widget for NativeTextField {
         self.id = id;
     }
 
-    fn style(&self) -> taffy::Style {
+    fn style(&self, _theme: &Theme) -> taffy::Style {
         use taffy::prelude::*;
         let (pref_width, pref_height) = self.preferred_size();
         taffy::Style {
@@ -156,6 +219,37 @@ impl Widget for NativeTextField {
     fn is_native(&self) -> bool {
         true
     }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let placeholder = self.placeholder_text();
+        AccessibleInfo {
+            role: AccessibleRole::TextField,
+            value: Some(self.value()),
+            description: (!placeholder.is_empty()).then_some(placeholder),
+            focusable: true,
+            actions: vec![AccessibleAction::SetValue],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        match (action, value) {
+            (AccessibleAction::SetValue, Some(value)) => {
+                let text = value.as_text();
+                self.set_text(text.as_str());
+                if let Some(callback) = self.on_change.lock().unwrap().as_ref() {
+                    callback(&text);
+                }
+                EventResponse::handled()
+            }
+            _ => EventResponse::default(),
+        }
+    }
     
     fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
         Some(self.preferred_size())
@@ -170,11 +264,11 @@ impl Widget for NativeTextField {
         match view_handle {
             #[cfg(target_os = "macos")]
             crate::NativeViewHandle::AppKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
             #[cfg(target_os = "ios")]
             crate::NativeViewHandle::UIKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
         }
     }
@@ -184,11 +278,11 @@ impl NativeWidget for NativeTextField {
     fn native_view(&self) -> NativeViewHandle {
         #[cfg(target_os = "macos")]
         {
-            NativeViewHandle::AppKit(self.view.view().as_ptr())
+            self.view.view().handle()
         }
         #[cfg(target_os = "ios")]
         {
-            NativeViewHandle::UIKit(self.view.view().as_ptr())
+            self.view.view().handle()
         }
     }
 
@@ -200,17 +294,9 @@ impl NativeWidget for NativeTextField {
         let mut events = self.pending_events.lock().unwrap();
         let mut bridged = events.drain(..).collect::<Vec<_>>();
 
-        // Check for text changes
-        #[cfg(target_os = "macos")]
-        let new_text = self.view.string_value();
-        #[cfg(target_os = "ios")]
-        let new_text = self.view.text();
-
-        if new_text != self.text {
-            self.text = new_text.clone();
-            if let Some(ref callback) = self.on_change {
-                callback(&new_text);
-            }
+        // Pick up edits the action target has not reported yet
+        let new_text = self.value();
+        if report_change(&self.text, &self.on_change, new_text.clone()) {
             bridged.push(InputEvent::TextInput { text: new_text });
         }
 
@@ -233,8 +319,94 @@ impl NativeWidgetExt for NativeTextField {
         }
     }
 }
+/// Store `value` as the last reported text and call `on_change` if it differs.
+/// Returns whether it changed.
+fn report_change(text: &Mutex<String>, on_change: &TextCallback, value: String) -> bool {
+    {
+        let mut text = text.lock().unwrap();
+        if *text == value {
+            return false;
+        }
+        text.clone_from(&value);
+    }
+    if let Some(callback) = on_change.lock().unwrap().as_ref() {
+        callback(&value);
+    }
+    true
+}
+
+impl Drop for NativeTextField {
+    fn drop(&mut self) {
+        // The view may outlive this widget in its superview; don't leave it
+        // pointing at a released target.
+        #[cfg(target_os = "macos")]
+        {
+            self.view.set_delegate(std::ptr::null_mut());
+            self.view.set_action(std::ptr::null_mut(), ActionTarget::selector());
+        }
+        #[cfg(target_os = "ios")]
+        self.view.remove_target(self.action_target.as_ptr());
+    }
+}
+
 impl Default for NativeTextField {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(all(test, target_os = "macos"))]
+mod tests {
+    use super::*;
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+    use objc2_foundation::NSString;
+
+    #[test]
+    fn test_on_change_reports_the_field_text() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let field = {
+            let seen = seen.clone();
+            NativeTextField::new()
+                .placeholder("Name…")
+                .on_change(move |text| seen.lock().unwrap().push(text.to_string()))
+        };
+        assert_eq!(field.placeholder_text(), "Name…");
+        field.view.set_string_value("Grüße, 世界 ✓");
+        assert_eq!(field.value(), "Grüße, 世界 ✓");
+        let sent: bool = unsafe {
+            msg_send![
+                field.view.view().as_ptr(),
+                sendAction: ActionTarget::selector(),
+                to: field.action_target.as_ptr()
+            ]
+        };
+        assert!(sent);
+        assert_eq!(*seen.lock().unwrap(), ["Grüße, 世界 ✓"]);
+    }
+
+    #[test]
+    fn test_on_change_fires_for_each_edit() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let field = {
+            let seen = seen.clone();
+            NativeTextField::new()
+                .on_change(move |text| seen.lock().unwrap().push(text.to_string()))
+        };
+        for text in ["S", "Sp", "Spä"] {
+            field.view.set_string_value(text);
+            unsafe {
+                let notification: *mut AnyObject = msg_send![
+                    objc2::class!(NSNotification),
+                    notificationWithName: &*NSString::from_str("NSControlTextDidChangeNotification"),
+                    object: field.view.view().as_ptr()
+                ];
+                let _: () = msg_send![
+                    field.action_target.as_ptr(),
+                    controlTextDidChange: notification
+                ];
+            }
+        }
+        assert_eq!(*seen.lock().unwrap(), ["S", "Sp", "Spä"]);
+    }
+}
@@ -3,7 +3,7 @@
 use crate::native_widget::{NativeViewHandle, NativeWidget, NativeWidgetExt};
 use spark_input::InputEvent;
 use spark_layout::{taffy, WidgetId};
-use spark_widgets::{EventContext, EventResponse, PaintContext, Widget};
+use spark_widgets::{EventContext, EventResponse, PaintContext, Theme, Widget};
 
 /// Native view container widget.
 pub struct NativeView {
@@ -44,7 +44,7 @@ impl Widget for NativeView {
         self.id = id;
     }
 
-    fn style(&self) -> taffy::Style {
+    fn style(&self, _theme: &Theme) -> taffy::Style {
         taffy::Style::default()
     }
 
@@ -77,11 +77,11 @@ impl Widget for NativeView {
         match view_handle {
             #[cfg(target_os = "macos")]
             crate::NativeViewHandle::AppKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
             #[cfg(target_os = "ios")]
             crate::NativeViewHandle::UIKit(ptr) => {
-                register(widget_id, ptr as *mut std::ffi::c_void);
+                register(widget_id, ptr.as_ptr() as *mut std::ffi::c_void);
             }
         }
     }
@@ -91,11 +91,11 @@ impl NativeWidget for NativeView {
     fn native_view(&self) -> NativeViewHandle {
         #[cfg(target_os = "macos")]
         {
-            NativeViewHandle::AppKit(self.view.as_ptr())
+            self.view.handle()
         }
         #[cfg(target_os = "ios")]
         {
-            NativeViewHandle::UIKit(self.view.as_ptr())
+            self.view.handle()
         }
     }
 
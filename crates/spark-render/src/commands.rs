@@ -1,5 +1,6 @@
 //! Draw commands that represent what to render.
 
+use crate::{ImageHandle, TextureId};
 use spark_core::{Color, GlyphInstance, Rect};
 
 /// A single draw command representing a primitive to render.
@@ -13,10 +14,36 @@ pub enum DrawCommand {
         border_width: f32,
         border_color: Color,
     },
+    /// Draw a soft shadow of a rounded rectangle, spreading `blur` pixels
+    /// past `bounds`.
+    Shadow {
+        bounds: Rect,
+        color: Color,
+        corner_radius: f32,
+        blur: f32,
+    },
+    /// Draw an arc of the circle inscribed in `bounds`, `thickness` wide
+    /// with round ends. Angles are in radians, clockwise from 12 o'clock.
+    Arc {
+        bounds: Rect,
+        color: Color,
+        thickness: f32,
+        start: f32,
+        sweep: f32,
+    },
     /// Draw text glyphs.
     Text {
         glyphs: Vec<GlyphInstance>,
     },
+    /// Draw part of an image, multiplied by `tint`.
+    Image {
+        rect: Rect,
+        texture_id: TextureId,
+        /// Region of the image to draw, in 0-1 texture coordinates.
+        uv: Rect,
+        tint: Color,
+        corner_radius: f32,
+    },
     /// Push a clip rectangle (future draw commands will be clipped).
     PushClip {
         bounds: Rect,
@@ -29,6 +56,8 @@ pub enum DrawCommand {
     },
     /// Pop the current translation offset.
     PopTranslation,
+    /// Start a layer drawn over everything before it, text included.
+    BeginLayer,
 }
 
 impl DrawCommand {
@@ -76,6 +105,10 @@ impl DrawCommand {
 #[derive(Clone, Debug, Default)]
 pub struct DrawList {
     commands: Vec<DrawCommand>,
+    /// Images drawn by `commands`, for the renderer to upload.
+    images: Vec<ImageHandle>,
+    /// Accumulated offsets of the pushed translations, as the renderer applies them.
+    translations: Vec<(f32, f32)>,
 }
 
 impl DrawList {
@@ -117,6 +150,28 @@ impl DrawList {
         ));
     }
 
+    /// Draw a soft shadow of a rounded rectangle.
+    pub fn shadow(&mut self, bounds: Rect, color: Color, corner_radius: f32, blur: f32) {
+        self.push(DrawCommand::Shadow {
+            bounds,
+            color,
+            corner_radius,
+            blur,
+        });
+    }
+
+    /// Draw an arc of the circle inscribed in `bounds`, sweeping clockwise
+    /// from `start` radians past 12 o'clock.
+    pub fn arc(&mut self, bounds: Rect, color: Color, thickness: f32, start: f32, sweep: f32) {
+        self.push(DrawCommand::Arc {
+            bounds,
+            color,
+            thickness,
+            start,
+            sweep,
+        });
+    }
+
     /// Draw text glyphs.
     pub fn text(&mut self, glyphs: Vec<GlyphInstance>) {
         if !glyphs.is_empty() {
@@ -124,6 +179,28 @@ impl DrawList {
         }
     }
 
+    /// Draw the `uv` region of `image` into `rect`, with rounded corners.
+    pub fn image(
+        &mut self,
+        rect: Rect,
+        image: &ImageHandle,
+        uv: Rect,
+        tint: Color,
+        corner_radius: f32,
+    ) {
+        let texture_id = image.id();
+        if !self.images.iter().any(|known| known.id() == texture_id) {
+            self.images.push(image.clone());
+        }
+        self.push(DrawCommand::Image {
+            rect,
+            texture_id,
+            uv,
+            tint,
+            corner_radius,
+        });
+    }
+
     /// Push a clip rectangle.
     pub fn push_clip(&mut self, bounds: Rect) {
         self.push(DrawCommand::PushClip { bounds });
@@ -136,22 +213,44 @@ impl DrawList {
 
     /// Push a translation offset for subsequent draw commands.
     pub fn push_translation(&mut self, offset: (f32, f32)) {
+        let (x, y) = self.translation();
+        self.translations.push((x + offset.0, y + offset.1));
         self.push(DrawCommand::PushTranslation { offset });
     }
 
     /// Pop the current translation offset.
     pub fn pop_translation(&mut self) {
+        self.translations.pop();
         self.push(DrawCommand::PopTranslation);
     }
 
+    /// The total offset applied to commands pushed now (sum of all pushed translations).
+    pub fn translation(&self) -> (f32, f32) {
+        self.translations.last().copied().unwrap_or((0.0, 0.0))
+    }
+
+    /// Draw what follows over everything drawn so far (e.g. popups).
+    ///
+    /// Within a layer, text is always drawn over shapes.
+    pub fn begin_layer(&mut self) {
+        self.push(DrawCommand::BeginLayer);
+    }
+
     /// Get all commands.
     pub fn commands(&self) -> &[DrawCommand] {
         &self.commands
     }
 
+    /// Images drawn by the commands.
+    pub fn images(&self) -> &[ImageHandle] {
+        &self.images
+    }
+
     /// Clear all commands.
     pub fn clear(&mut self) {
         self.commands.clear();
+        self.images.clear();
+        self.translations.clear();
     }
 
     /// Check if the list is empty.
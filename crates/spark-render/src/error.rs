@@ -0,0 +1,82 @@
+//! Error types for the renderer.
+
+use spark_core::{PipelineError, SurfaceError};
+use std::fmt;
+
+/// Errors that can occur while creating or running the renderer.
+#[derive(Debug)]
+pub enum RenderError {
+    /// A render pipeline failed to build.
+    Pipeline(PipelineError),
+    /// A frame could not be acquired from the surface.
+    Surface(SurfaceError),
+}
+
+impl From<PipelineError> for RenderError {
+    fn from(err: PipelineError) -> Self {
+        Self::Pipeline(err)
+    }
+}
+
+impl From<SurfaceError> for RenderError {
+    fn from(err: SurfaceError) -> Self {
+        Self::Surface(err)
+    }
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pipeline(e) => write!(f, "render pipeline error: {e}"),
+            Self::Surface(e) => write!(f, "surface error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Pipeline(e) => Some(e),
+            Self::Surface(e) => Some(e),
+        }
+    }
+}
+
+/// Errors that can occur while loading an image.
+#[derive(Debug)]
+pub enum ImageError {
+    /// The pixel buffer does not hold `width * height` RGBA8 pixels.
+    SizeMismatch { expected: usize, actual: usize },
+    /// The file could not be decoded.
+    #[cfg(feature = "image")]
+    Decode(image::ImageError),
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for ImageError {
+    fn from(err: image::ImageError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SizeMismatch { expected, actual } => {
+                write!(f, "expected {expected} bytes of RGBA pixels, got {actual}")
+            }
+            #[cfg(feature = "image")]
+            Self::Decode(e) => write!(f, "image decode error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::SizeMismatch { .. } => None,
+            #[cfg(feature = "image")]
+            Self::Decode(e) => Some(e),
+        }
+    }
+}
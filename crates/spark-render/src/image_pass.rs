@@ -0,0 +1,333 @@
+//! Image rendering pass, sampling one texture per image.
+
+use crate::texture::ImageData;
+use crate::{ImageHandle, TextureId};
+use spark_core::{
+    buffer::QuadBuffers,
+    pipeline::{Pipeline, PipelineCache, PipelineConfig},
+    vertex::{ImageInstance, Vertex2D},
+    BufferStats, DynamicBuffer, GlobalUniforms, PipelineError,
+};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Weak;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+    BindingResource, BindingType, Device, Extent3d, FilterMode, Queue, RenderPass, Sampler,
+    SamplerBindingType, SamplerDescriptor, ShaderStages, Texture, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureViewDescriptor,
+    TextureViewDimension,
+};
+
+/// WGSL shader for rendering textured quads with rounded corners.
+const IMAGE_SHADER: &str = r#"
+// Keep in sync with spark_core::GlobalUniforms::WGSL
+struct Globals {
+    viewport_size: vec2<f32>,
+    scale_factor: f32,
+    time: f32,
+    cursor: vec2<f32>,
+    cursor_logical: vec2<f32>,
+    delta_time: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> globals: Globals;
+
+@group(1) @binding(0)
+var image_texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var image_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+};
+
+struct InstanceInput {
+    @location(2) pos: vec2<f32>,
+    @location(3) size: vec2<f32>,
+    @location(4) uv_pos: vec2<f32>,
+    @location(5) uv_size: vec2<f32>,
+    @location(6) tint: vec4<f32>,
+    @location(7) corner_radius: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) tint: vec4<f32>,
+    @location(2) local_pos: vec2<f32>,
+    @location(3) size: vec2<f32>,
+    @location(4) corner_radius: f32,
+};
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Transform vertex position to pixel coordinates
+    let pixel_pos = instance.pos + vertex.position * instance.size;
+
+    // Convert to clip space (-1 to 1)
+    let clip_pos = (pixel_pos / globals.viewport_size) * 2.0 - 1.0;
+    out.clip_position = vec4<f32>(clip_pos.x, -clip_pos.y, 0.0, 1.0);
+
+    out.uv = instance.uv_pos + vertex.uv * instance.uv_size;
+    out.tint = instance.tint;
+    out.local_pos = vertex.position * instance.size;
+    out.size = instance.size;
+    out.corner_radius = instance.corner_radius;
+
+    return out;
+}
+
+// Signed distance function for a rounded rectangle
+fn sd_rounded_rect(pos: vec2<f32>, size: vec2<f32>, radius: f32) -> f32 {
+    let half_size = size * 0.5;
+    let center_pos = pos - half_size;
+    let q = abs(center_pos) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - radius;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(image_texture, image_sampler, in.uv) * in.tint;
+
+    // Anti-aliased rounded corners
+    let radius = min(in.corner_radius, min(in.size.x, in.size.y) * 0.5);
+    let dist = sd_rounded_rect(in.local_pos, in.size, radius);
+    let alpha = 1.0 - smoothstep(-1.0, 1.0, dist);
+
+    return vec4<f32>(color.rgb, color.a * alpha);
+}
+"#;
+
+/// An uploaded image, kept while any handle to it is alive.
+struct CachedTexture {
+    image: Weak<ImageData>,
+    _texture: Texture,
+    bind_group: BindGroup,
+}
+
+/// Rendering pass for images, with a texture cache keyed by image.
+pub struct ImagePass {
+    pipeline: Pipeline<GlobalUniforms>,
+    texture_bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+    textures: HashMap<TextureId, CachedTexture>,
+    quad_buffers: QuadBuffers,
+    instance_buffer: DynamicBuffer<ImageInstance>,
+    instances: Vec<ImageInstance>,
+    /// Consecutive instances drawn from the same texture.
+    batches: Vec<(TextureId, Range<u32>)>,
+}
+
+impl ImagePass {
+    /// Create a new image pass.
+    pub fn new(
+        device: &Device,
+        format: TextureFormat,
+        cache: &mut PipelineCache,
+    ) -> Result<Self, PipelineError> {
+        let texture_bind_group_layout = cache.bind_group_layout(
+            device,
+            "image_texture_bgl",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        );
+
+        let pipeline = Pipeline::try_with_cache(
+            device,
+            cache,
+            PipelineConfig {
+                label: "image_pipeline",
+                shader_source: IMAGE_SHADER,
+                vs_entry: "vs_main",
+                fs_entry: "fs_main",
+                target_format: format,
+                vertex_layouts: &[Vertex2D::layout(), ImageInstance::layout()],
+                extra_bind_group_layouts: &[&texture_bind_group_layout],
+                ..Default::default()
+            },
+        )?;
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("image_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Ok(Self {
+            pipeline,
+            texture_bind_group_layout,
+            sampler,
+            textures: HashMap::new(),
+            quad_buffers: QuadBuffers::new(device),
+            instance_buffer: DynamicBuffer::vertex(device, "image_instances", 64),
+            instances: Vec::new(),
+            batches: Vec::new(),
+        })
+    }
+
+    /// Add an image instance drawn from `texture`.
+    pub fn add_image(&mut self, texture: TextureId, instance: ImageInstance) {
+        let index = self.instances.len() as u32;
+        self.instances.push(instance);
+        match self.batches.last_mut() {
+            Some((id, range)) if *id == texture => range.end = index + 1,
+            _ => self.batches.push((texture, index..index + 1)),
+        }
+    }
+
+    /// Clear all pending instances.
+    pub fn clear(&mut self) {
+        self.instances.clear();
+        self.batches.clear();
+    }
+
+    /// Upload new images, drop textures of images no longer alive, and
+    /// update GPU buffers with pending instances.
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        globals: &GlobalUniforms,
+        images: &[ImageHandle],
+    ) {
+        self.textures
+            .retain(|_, cached| cached.image.strong_count() > 0);
+        for image in images {
+            if !self.textures.contains_key(&image.id()) {
+                let cached = self.upload(device, queue, image);
+                self.textures.insert(image.id(), cached);
+            }
+        }
+
+        self.pipeline.update_uniforms(queue, globals);
+        self.instance_buffer.write(device, queue, &self.instances);
+    }
+
+    fn upload(&self, device: &Device, queue: &Queue, image: &ImageHandle) -> CachedTexture {
+        let (width, height) = image.size();
+        let size = Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("image_texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            // Sampled as stored, like draw colors
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        if width > 0 && height > 0 {
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                image.pixels(),
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(width * 4),
+                    rows_per_image: Some(height),
+                },
+                size,
+            );
+        }
+
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("image_texture_bg"),
+            layout: &self.texture_bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+        CachedTexture {
+            image: image.downgrade(),
+            _texture: texture,
+            bind_group,
+        }
+    }
+
+    /// Render all images to the given render pass.
+    pub fn render<'a>(&'a self, render_pass: &mut RenderPass<'a>) {
+        self.render_range(render_pass, 0..self.instances.len() as u32);
+    }
+
+    /// Render the images at `instances` (indices in the order they were added).
+    pub fn render_range<'a>(&'a self, render_pass: &mut RenderPass<'a>, instances: Range<u32>) {
+        if instances.is_empty() {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline.pipeline);
+        render_pass.set_bind_group(0, &self.pipeline.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.quad_buffers.vertices.buffer().slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.buffer().slice(..));
+        render_pass.set_index_buffer(
+            self.quad_buffers.indices.buffer().slice(..),
+            wgpu::IndexFormat::Uint16,
+        );
+        for (texture, batch) in &self.batches {
+            let start = batch.start.max(instances.start);
+            let end = batch.end.min(instances.end);
+            let Some(cached) = self.textures.get(texture) else {
+                continue;
+            };
+            if start < end {
+                render_pass.set_bind_group(1, &cached.bind_group, &[]);
+                render_pass.draw_indexed(0..6, 0, start..end);
+            }
+        }
+    }
+
+    /// Get the number of pending image instances.
+    pub fn instance_count(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Number of images with an uploaded texture.
+    pub fn texture_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Usage of the GPU instance buffer.
+    pub fn buffer_stats(&self) -> BufferStats {
+        self.instance_buffer.stats()
+    }
+}
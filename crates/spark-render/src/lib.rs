@@ -1,12 +1,18 @@
 //! Spark Render - Draw list, batching, and GPU rendering passes.
 
 mod commands;
+mod error;
+mod image_pass;
 mod renderer;
 mod shape_pass;
 mod text_pass;
+mod texture;
 
 pub use commands::{DrawCommand, DrawList};
-pub use renderer::Renderer;
+pub use error::{ImageError, RenderError};
+pub use image_pass::ImagePass;
+pub use renderer::{FrameStats, Renderer};
 pub use shape_pass::ShapePass;
 pub use text_pass::TextPass;
+pub use texture::{ImageHandle, TextureId};
 
@@ -1,29 +1,55 @@
 //! Main renderer that processes draw lists and issues GPU commands.
 
-use crate::{DrawCommand, DrawList, ShapePass, TextPass};
-use spark_core::{GlobalUniforms, Rect};
+use crate::{DrawCommand, DrawList, ImagePass, RenderError, ShapePass, TextPass};
+use spark_core::{
+    BufferStats, GlobalUniforms, ImageInstance, PipelineCache, Rect, SurfaceState, Vec2,
+};
 use spark_text::GlyphAtlas;
 use wgpu::{CommandEncoder, Device, Queue, TextureFormat, TextureView};
 
+/// GPU instance buffer usage after the last [`Renderer::prepare`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Shape instances.
+    pub shapes: BufferStats,
+    /// Glyph instances.
+    pub glyphs: BufferStats,
+    /// Image instances.
+    pub images: BufferStats,
+}
+
 /// The main renderer that processes draw lists and renders to the screen.
 pub struct Renderer {
+    pipeline_cache: PipelineCache,
     shape_pass: ShapePass,
+    image_pass: ImagePass,
     text_pass: TextPass,
     globals: GlobalUniforms,
     clip_stack: Vec<Rect>,
     translation_stack: Vec<(f32, f32)>,
+    /// First shape, image and glyph instance of each layer after the first.
+    layer_starts: Vec<(u32, u32, u32)>,
 }
 
 impl Renderer {
     /// Create a new renderer.
-    pub fn new(device: &Device, format: TextureFormat) -> Self {
-        Self {
-            shape_pass: ShapePass::new(device, format),
-            text_pass: TextPass::new(device, format),
+    pub fn new(device: &Device, format: TextureFormat) -> Result<Self, RenderError> {
+        let mut pipeline_cache = PipelineCache::new();
+        Ok(Self {
+            shape_pass: ShapePass::new(device, format, &mut pipeline_cache)?,
+            image_pass: ImagePass::new(device, format, &mut pipeline_cache)?,
+            text_pass: TextPass::new(device, format, &mut pipeline_cache)?,
+            pipeline_cache,
             globals: GlobalUniforms::default(),
             clip_stack: Vec::new(),
             translation_stack: vec![(0.0, 0.0)],
-        }
+            layer_starts: Vec::new(),
+        })
+    }
+
+    /// Pipelines and layouts built so far, for custom passes to share.
+    pub fn pipeline_cache_mut(&mut self) -> &mut PipelineCache {
+        &mut self.pipeline_cache
     }
 
     /// Update global uniforms (call once per frame before rendering).
@@ -32,11 +58,27 @@ impl Renderer {
         self.globals.scale_factor = scale_factor;
     }
 
-    /// Update time uniform.
+    /// Update time uniform, and the time since the last call.
     pub fn set_time(&mut self, time: f32) {
+        self.globals.delta_time = (time - self.globals.time).max(0.0);
         self.globals.time = time;
     }
 
+    /// Update the pointer position uniforms from a position in logical pixels.
+    ///
+    /// Call after [`Renderer::set_viewport`] so the physical position uses
+    /// the current scale factor.
+    pub fn set_cursor(&mut self, logical: Vec2) {
+        self.globals.cursor_logical = logical.to_array();
+        self.globals.cursor = (logical * self.globals.scale_factor).to_array();
+    }
+
+    /// The uniforms uploaded by the next [`Renderer::prepare`], for custom
+    /// passes that bind the same layout.
+    pub fn globals(&self) -> &GlobalUniforms {
+        &self.globals
+    }
+
     /// Process a draw list and prepare GPU resources.
     pub fn prepare(
         &mut self,
@@ -46,10 +88,12 @@ impl Renderer {
         atlas: &GlyphAtlas,
     ) {
         self.shape_pass.clear();
+        self.image_pass.clear();
         self.text_pass.clear();
         self.clip_stack.clear();
         self.translation_stack.clear();
         self.translation_stack.push((0.0, 0.0));
+        self.layer_starts.clear();
 
         for command in draw_list.commands() {
             match command {
@@ -61,12 +105,7 @@ impl Renderer {
                     border_color,
                 } => {
                     let translation = self.translation_stack.last().copied().unwrap_or((0.0, 0.0));
-                    let translated_bounds = Rect::new(
-                        bounds.x + translation.0,
-                        bounds.y + translation.1,
-                        bounds.width,
-                        bounds.height,
-                    );
+                    let translated_bounds = bounds.translate(Vec2::new(translation.0, translation.1));
                     // Apply clipping if needed
                     let clipped_bounds = if let Some(clip) = self.clip_stack.last() {
                         match translated_bounds.intersection(clip) {
@@ -85,6 +124,54 @@ impl Renderer {
                         border_color.to_array(),
                     );
                 }
+                DrawCommand::Shadow {
+                    bounds,
+                    color,
+                    corner_radius,
+                    blur,
+                } => {
+                    let translation = self.translation_stack.last().copied().unwrap_or((0.0, 0.0));
+                    let translated_bounds =
+                        bounds.translate(Vec2::new(translation.0, translation.1));
+                    // The blurred edge reaches past the bounds, so clip per pixel
+                    // instead of shrinking the shape
+                    let clip = self.clip_stack.last().copied();
+                    let spread = translated_bounds.expand(*blur);
+                    if clip.is_some_and(|clip| spread.intersection(&clip).is_none()) {
+                        continue;
+                    }
+                    self.shape_pass.add_shadow(
+                        translated_bounds,
+                        color.to_array(),
+                        *corner_radius,
+                        *blur,
+                        clip,
+                    );
+                }
+                DrawCommand::Arc {
+                    bounds,
+                    color,
+                    thickness,
+                    start,
+                    sweep,
+                } => {
+                    let translation = self.translation_stack.last().copied().unwrap_or((0.0, 0.0));
+                    let translated_bounds =
+                        bounds.translate(Vec2::new(translation.0, translation.1));
+                    // Shrinking the bounds would distort the circle, so clip per pixel
+                    let clip = self.clip_stack.last().copied();
+                    if clip.is_some_and(|clip| translated_bounds.intersection(&clip).is_none()) {
+                        continue;
+                    }
+                    self.shape_pass.add_arc(
+                        translated_bounds,
+                        color.to_array(),
+                        *thickness,
+                        *start,
+                        *sweep,
+                        clip,
+                    );
+                }
                 DrawCommand::Text { glyphs } => {
                     // Apply clipping to glyphs
                     let translation = self.translation_stack.last().copied().unwrap_or((0.0, 0.0));
@@ -124,14 +211,39 @@ impl Renderer {
                         }
                     }
                 }
-                DrawCommand::PushClip { bounds } => {
+                DrawCommand::Image {
+                    rect,
+                    texture_id,
+                    uv,
+                    tint,
+                    corner_radius,
+                } => {
                     let translation = self.translation_stack.last().copied().unwrap_or((0.0, 0.0));
-                    let translated_bounds = Rect::new(
-                        bounds.x + translation.0,
-                        bounds.y + translation.1,
-                        bounds.width,
-                        bounds.height,
+                    let rect = rect.translate(Vec2::new(translation.0, translation.1));
+                    // Clipping crops the texture region along with the rect
+                    let (visible, uv) = match self.clip_stack.last() {
+                        Some(clip) => match rect.intersection(clip) {
+                            Some(visible) => (visible, clip_uv(rect, visible, *uv)),
+                            None => continue,
+                        },
+                        None => (rect, *uv),
+                    };
+                    self.image_pass.add_image(
+                        *texture_id,
+                        ImageInstance {
+                            pos: [visible.x, visible.y],
+                            size: [visible.width, visible.height],
+                            uv_pos: [uv.x, uv.y],
+                            uv_size: [uv.width, uv.height],
+                            tint: tint.to_array(),
+                            corner_radius: *corner_radius,
+                            _padding: [0.0; 3],
+                        },
                     );
+                }
+                DrawCommand::PushClip { bounds } => {
+                    let translation = self.translation_stack.last().copied().unwrap_or((0.0, 0.0));
+                    let translated_bounds = bounds.translate(Vec2::new(translation.0, translation.1));
                     // Intersect with current clip if any
                     let new_clip = if let Some(current) = self.clip_stack.last() {
                         translated_bounds
@@ -155,11 +267,20 @@ impl Renderer {
                         self.translation_stack.pop();
                     }
                 }
+                DrawCommand::BeginLayer => {
+                    self.layer_starts.push((
+                        self.shape_pass.instance_count() as u32,
+                        self.image_pass.instance_count() as u32,
+                        self.text_pass.instance_count() as u32,
+                    ));
+                }
             }
         }
 
         // Update GPU buffers
         self.shape_pass.prepare(device, queue, &self.globals);
+        self.image_pass
+            .prepare(device, queue, &self.globals, draw_list.images());
         self.text_pass.prepare(device, queue, &self.globals, atlas);
     }
 
@@ -187,11 +308,46 @@ impl Renderer {
             multiview_mask: None,
         });
 
-        // Render shapes first (background)
-        self.shape_pass.render(&mut render_pass);
+        // Each layer draws its shapes, then its images, then its text on top
+        let end = (
+            self.shape_pass.instance_count() as u32,
+            self.image_pass.instance_count() as u32,
+            self.text_pass.instance_count() as u32,
+        );
+        let layers = &self.layer_starts;
+        let starts = std::iter::once((0, 0, 0)).chain(layers.iter().copied());
+        let ends = layers.iter().copied().chain(std::iter::once(end));
+        for (start, end) in starts.zip(ends) {
+            self.shape_pass
+                .render_range(&mut render_pass, start.0..end.0);
+            self.image_pass
+                .render_range(&mut render_pass, start.1..end.1);
+            self.text_pass
+                .render_range(&mut render_pass, start.2..end.2);
+        }
+    }
+
+    /// Acquire the next surface frame, render into it, and present it.
+    pub fn render_to_surface(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        surface_state: &mut SurfaceState<'_>,
+        clear_color: wgpu::Color,
+    ) -> Result<(), RenderError> {
+        let frame = surface_state.acquire_frame(device)?;
+        let view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("spark_encoder"),
+        });
+        self.render(&mut encoder, &view, clear_color);
 
-        // Render text on top
-        self.text_pass.render(&mut render_pass);
+        queue.submit(Some(encoder.finish()));
+        frame.present();
+        Ok(())
     }
 
     /// Get the number of shape instances being rendered.
@@ -199,8 +355,50 @@ impl Renderer {
         self.shape_pass.instance_count()
     }
 
+    /// Get the number of image instances being rendered.
+    pub fn image_count(&self) -> usize {
+        self.image_pass.instance_count()
+    }
+
     /// Get the number of glyph instances being rendered.
     pub fn glyph_count(&self) -> usize {
         self.text_pass.instance_count()
     }
+
+    /// Instance counts, buffer capacities and reallocations so far.
+    pub fn frame_stats(&self) -> FrameStats {
+        FrameStats {
+            shapes: self.shape_pass.buffer_stats(),
+            glyphs: self.text_pass.buffer_stats(),
+            images: self.image_pass.buffer_stats(),
+        }
+    }
+}
+
+/// The part of `uv` that lands on `visible`, a region of `rect`.
+fn clip_uv(rect: Rect, visible: Rect, uv: Rect) -> Rect {
+    if rect.width <= 0.0 || rect.height <= 0.0 {
+        return uv;
+    }
+    let scale_x = uv.width / rect.width;
+    let scale_y = uv.height / rect.height;
+    Rect::new(
+        uv.x + (visible.x - rect.x) * scale_x,
+        uv.y + (visible.y - rect.y) * scale_y,
+        visible.width * scale_x,
+        visible.height * scale_y,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_uv_crops_the_texture_region() {
+        let rect = Rect::new(0.0, 0.0, 200.0, 100.0);
+        let visible = Rect::new(100.0, 0.0, 100.0, 50.0);
+        let uv = clip_uv(rect, visible, Rect::new(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(uv, Rect::new(0.5, 0.0, 0.5, 0.5));
+    }
 }
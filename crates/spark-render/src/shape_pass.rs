@@ -1,19 +1,24 @@
-//! Shape rendering pass for rectangles with rounded corners.
+//! Shape rendering pass for rectangles with rounded corners and arcs.
 
 use spark_core::{
-    pipeline::{Pipeline, PipelineConfig},
+    pipeline::{Pipeline, PipelineCache, PipelineConfig},
     buffer::QuadBuffers,
     vertex::{ShapeInstance, Vertex2D},
-    DynamicBuffer, GlobalUniforms, Rect,
+    BufferStats, DynamicBuffer, GlobalUniforms, PipelineError, Rect,
 };
+use std::ops::Range;
 use wgpu::{Device, Queue, RenderPass, TextureFormat};
 
 /// WGSL shader for rendering shapes (rectangles with rounded corners and borders).
 const SHAPE_SHADER: &str = r#"
+// Keep in sync with spark_core::GlobalUniforms::WGSL
 struct Globals {
     viewport_size: vec2<f32>,
     scale_factor: f32,
     time: f32,
+    cursor: vec2<f32>,
+    cursor_logical: vec2<f32>,
+    delta_time: f32,
 };
 
 @group(0) @binding(0)
@@ -31,6 +36,9 @@ struct InstanceInput {
     @location(5) corner_radius: f32,
     @location(6) border_width: f32,
     @location(7) border_color: vec4<f32>,
+    @location(8) clip: vec4<f32>,
+    @location(9) blur: f32,
+    @location(10) arc: vec2<f32>,
 };
 
 struct VertexOutput {
@@ -41,25 +49,36 @@ struct VertexOutput {
     @location(3) corner_radius: f32,
     @location(4) border_width: f32,
     @location(5) border_color: vec4<f32>,
+    @location(6) pixel_pos: vec2<f32>,
+    @location(7) clip: vec4<f32>,
+    @location(8) blur: f32,
+    @location(9) arc: vec2<f32>,
 };
 
 @vertex
 fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
     var out: VertexOutput;
     
+    // Shadows spread past the shape by the blur radius on every side
+    let quad_size = instance.size + 2.0 * instance.blur;
+
     // Transform vertex position to pixel coordinates
-    let pixel_pos = instance.pos + vertex.position * instance.size;
+    let pixel_pos = instance.pos - instance.blur + vertex.position * quad_size;
     
     // Convert to clip space (-1 to 1)
     let clip_pos = (pixel_pos / globals.viewport_size) * 2.0 - 1.0;
     out.clip_position = vec4<f32>(clip_pos.x, -clip_pos.y, 0.0, 1.0);
     
     out.color = instance.color;
-    out.local_pos = vertex.position * instance.size;
+    out.local_pos = vertex.position * quad_size - instance.blur;
     out.size = instance.size;
     out.corner_radius = instance.corner_radius;
     out.border_width = instance.border_width;
     out.border_color = instance.border_color;
+    out.pixel_pos = pixel_pos;
+    out.clip = instance.clip;
+    out.blur = instance.blur;
+    out.arc = instance.arc;
     
     return out;
 }
@@ -72,10 +91,50 @@ fn sd_rounded_rect(pos: vec2<f32>, size: vec2<f32>, radius: f32) -> f32 {
     return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - radius;
 }
 
+// Signed distance function for a ring segment with round caps, centered in
+// the box. `arc` is the start angle and sweep, clockwise from 12 o'clock.
+fn sd_arc(pos: vec2<f32>, size: vec2<f32>, arc: vec2<f32>, thickness: f32) -> f32 {
+    let p = pos - size * 0.5;
+    let ring_radius = min(size.x, size.y) * 0.5 - thickness * 0.5;
+    // Rotate so the middle of the arc points along +y
+    let middle = arc.x + arc.y * 0.5;
+    let dir = vec2<f32>(sin(middle), -cos(middle));
+    let q = vec2<f32>(abs(dir.y * p.x - dir.x * p.y), dot(p, dir));
+    let half_sweep = min(arc.y * 0.5, 3.14159265);
+    let sc = vec2<f32>(sin(half_sweep), cos(half_sweep));
+    var dist: f32;
+    if sc.y * q.x > sc.x * q.y {
+        // Past the ends: distance to the nearest cap center
+        dist = length(q - sc * ring_radius);
+    } else {
+        dist = abs(length(q) - ring_radius);
+    }
+    return dist - thickness * 0.5;
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    if any(in.pixel_pos < in.clip.xy) || any(in.pixel_pos >= in.clip.zw) {
+        discard;
+    }
+
+    if in.arc.y > 0.0 {
+        let arc_dist = sd_arc(in.local_pos, in.size, in.arc, in.border_width);
+        let arc_alpha = 1.0 - smoothstep(-0.75, 0.75, arc_dist);
+        if arc_alpha < 0.001 {
+            discard;
+        }
+        return vec4<f32>(in.color.rgb, in.color.a * arc_alpha);
+    }
+
     let radius = min(in.corner_radius, min(in.size.x, in.size.y) * 0.5);
     let dist = sd_rounded_rect(in.local_pos, in.size, radius);
+
+    // Soft shadow: alpha falls off smoothly across the blur band around the edge
+    if in.blur > 0.0 {
+        let shadow_alpha = 1.0 - smoothstep(-in.blur, in.blur, dist);
+        return vec4<f32>(in.color.rgb, in.color.a * shadow_alpha);
+    }
     
     // Anti-aliasing
     let aa = 1.0;
@@ -98,7 +157,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 }
 "#;
 
-/// Rendering pass for shapes (rectangles with rounded corners).
+/// Rendering pass for shapes (rectangles with rounded corners and arcs).
 pub struct ShapePass {
     pipeline: Pipeline<GlobalUniforms>,
     quad_buffers: QuadBuffers,
@@ -108,9 +167,14 @@ pub struct ShapePass {
 
 impl ShapePass {
     /// Create a new shape pass.
-    pub fn new(device: &Device, format: TextureFormat) -> Self {
-        let pipeline = Pipeline::with_config(
+    pub fn new(
+        device: &Device,
+        format: TextureFormat,
+        cache: &mut PipelineCache,
+    ) -> Result<Self, PipelineError> {
+        let pipeline = Pipeline::try_with_cache(
             device,
+            cache,
             PipelineConfig {
                 label: "shape_pipeline",
                 shader_source: SHAPE_SHADER,
@@ -120,17 +184,17 @@ impl ShapePass {
                 vertex_layouts: &[Vertex2D::layout(), ShapeInstance::layout()],
                 ..Default::default()
             },
-        );
+        )?;
 
         let quad_buffers = QuadBuffers::new(device);
         let instance_buffer = DynamicBuffer::vertex(device, "shape_instances", 1024);
 
-        Self {
+        Ok(Self {
             pipeline,
             quad_buffers,
             instance_buffer,
             instances: Vec::with_capacity(1024),
-        }
+        })
     }
 
     /// Add a shape instance to be rendered.
@@ -149,7 +213,71 @@ impl ShapePass {
             corner_radius,
             border_width,
             border_color,
-            _padding: [0.0, 0.0],
+            clip: ShapeInstance::NO_CLIP,
+            blur: 0.0,
+            arc: [0.0, 0.0],
+            _padding: [0.0; 3],
+        });
+    }
+
+    /// Add a soft shadow of a rounded `bounds`, spreading `blur` pixels past
+    /// it and clipped per pixel to `clip`.
+    pub fn add_shadow(
+        &mut self,
+        bounds: Rect,
+        color: [f32; 4],
+        corner_radius: f32,
+        blur: f32,
+        clip: Option<Rect>,
+    ) {
+        let clip = clip.map_or(ShapeInstance::NO_CLIP, |clip| {
+            [clip.x, clip.y, clip.x + clip.width, clip.y + clip.height]
+        });
+        self.instances.push(ShapeInstance {
+            pos: [bounds.x, bounds.y],
+            size: [bounds.width, bounds.height],
+            color,
+            corner_radius,
+            border_width: 0.0,
+            border_color: [0.0; 4],
+            clip,
+            blur: blur.max(0.0),
+            arc: [0.0, 0.0],
+            _padding: [0.0; 3],
+        });
+    }
+
+    /// Add an arc of the circle inscribed in `bounds`, `thickness` pixels
+    /// wide with round ends, clipped per pixel to `clip`.
+    ///
+    /// Angles are in radians, clockwise from 12 o'clock.
+    pub fn add_arc(
+        &mut self,
+        bounds: Rect,
+        color: [f32; 4],
+        thickness: f32,
+        start: f32,
+        sweep: f32,
+        clip: Option<Rect>,
+    ) {
+        // A zero sweep would draw the whole rectangle
+        if sweep <= 0.0 {
+            return;
+        }
+        let clip = clip.map_or(ShapeInstance::NO_CLIP, |clip| {
+            [clip.x, clip.y, clip.x + clip.width, clip.y + clip.height]
+        });
+        self.instances.push(ShapeInstance {
+            pos: [bounds.x, bounds.y],
+            size: [bounds.width, bounds.height],
+            color,
+            corner_radius: 0.0,
+            border_width: thickness,
+            border_color: [0.0; 4],
+            clip,
+            blur: 0.0,
+            arc: [start, sweep],
+            _padding: [0.0; 3],
         });
     }
 
@@ -166,7 +294,12 @@ impl ShapePass {
 
     /// Render all shapes to the given render pass.
     pub fn render<'a>(&'a self, render_pass: &mut RenderPass<'a>) {
-        if self.instances.is_empty() {
+        self.render_range(render_pass, 0..self.instances.len() as u32);
+    }
+
+    /// Render the shapes at `instances` (indices in the order they were added).
+    pub fn render_range<'a>(&'a self, render_pass: &mut RenderPass<'a>, instances: Range<u32>) {
+        if instances.is_empty() {
             return;
         }
 
@@ -178,12 +311,17 @@ impl ShapePass {
             self.quad_buffers.indices.buffer().slice(..),
             wgpu::IndexFormat::Uint16,
         );
-        render_pass.draw_indexed(0..6, 0, 0..self.instances.len() as u32);
+        render_pass.draw_indexed(0..6, 0, instances);
     }
 
     /// Get the number of pending instances.
     pub fn instance_count(&self) -> usize {
         self.instances.len()
     }
+
+    /// Usage of the GPU instance buffer.
+    pub fn buffer_stats(&self) -> BufferStats {
+        self.instance_buffer.stats()
+    }
 }
 
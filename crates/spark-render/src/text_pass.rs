@@ -1,25 +1,30 @@
 //! Text rendering pass using a glyph atlas.
 
 use spark_core::{
-    pipeline::{Pipeline, PipelineConfig},
+    pipeline::{Pipeline, PipelineCache, PipelineConfig},
     buffer::QuadBuffers,
     vertex::{GlyphInstance, Vertex2D},
-    DynamicBuffer, GlobalUniforms,
+    BufferStats, DynamicBuffer, GlobalUniforms, PipelineError,
 };
 use spark_text::GlyphAtlas;
+use std::ops::Range;
 use wgpu::{
-    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
-    BindGroupLayoutEntry, BindingResource, BindingType, Device, FilterMode, Queue, RenderPass,
-    Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages, TextureFormat,
-    TextureSampleType, TextureViewDimension,
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+    BindingResource, BindingType, Device, FilterMode, Queue, RenderPass, Sampler,
+    SamplerBindingType, SamplerDescriptor, ShaderStages, TextureFormat, TextureSampleType,
+    TextureViewDimension,
 };
 
 /// WGSL shader for rendering text glyphs from an atlas.
 const TEXT_SHADER: &str = r#"
+// Keep in sync with spark_core::GlobalUniforms::WGSL
 struct Globals {
     viewport_size: vec2<f32>,
     scale_factor: f32,
     time: f32,
+    cursor: vec2<f32>,
+    cursor_logical: vec2<f32>,
+    delta_time: f32,
 };
 
 @group(0) @binding(0)
@@ -93,33 +98,37 @@ pub struct TextPass {
 
 impl TextPass {
     /// Create a new text pass.
-    pub fn new(device: &Device, format: TextureFormat) -> Self {
-        // Create atlas bind group layout
-        let atlas_bind_group_layout =
-            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-                label: Some("text_atlas_bgl"),
-                entries: &[
-                    BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: ShaderStages::FRAGMENT,
-                        ty: BindingType::Texture {
-                            sample_type: TextureSampleType::Float { filterable: true },
-                            view_dimension: TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    BindGroupLayoutEntry {
-                        binding: 1,
-                        visibility: ShaderStages::FRAGMENT,
-                        ty: BindingType::Sampler(SamplerBindingType::Filtering),
-                        count: None,
+    pub fn new(
+        device: &Device,
+        format: TextureFormat,
+        cache: &mut PipelineCache,
+    ) -> Result<Self, PipelineError> {
+        let atlas_bind_group_layout = cache.bind_group_layout(
+            device,
+            "text_atlas_bgl",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
                     },
-                ],
-            });
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        );
 
-        let pipeline = Pipeline::with_config(
+        let pipeline = Pipeline::try_with_cache(
             device,
+            cache,
             PipelineConfig {
                 label: "text_pipeline",
                 shader_source: TEXT_SHADER,
@@ -130,7 +139,7 @@ impl TextPass {
                 extra_bind_group_layouts: &[&atlas_bind_group_layout],
                 ..Default::default()
             },
-        );
+        )?;
 
         let sampler = device.create_sampler(&SamplerDescriptor {
             label: Some("text_atlas_sampler"),
@@ -142,7 +151,7 @@ impl TextPass {
         let quad_buffers = QuadBuffers::new(device);
         let instance_buffer = DynamicBuffer::vertex(device, "text_instances", 4096);
 
-        Self {
+        Ok(Self {
             pipeline,
             atlas_bind_group_layout,
             atlas_bind_group: None,
@@ -150,7 +159,7 @@ impl TextPass {
             quad_buffers,
             instance_buffer,
             instances: Vec::with_capacity(4096),
-        }
+        })
     }
 
     /// Add glyph instances to be rendered.
@@ -193,7 +202,12 @@ impl TextPass {
 
     /// Render all text to the given render pass.
     pub fn render<'a>(&'a self, render_pass: &mut RenderPass<'a>) {
-        if self.instances.is_empty() {
+        self.render_range(render_pass, 0..self.instances.len() as u32);
+    }
+
+    /// Render the glyphs at `instances` (indices in the order they were added).
+    pub fn render_range<'a>(&'a self, render_pass: &mut RenderPass<'a>, instances: Range<u32>) {
+        if instances.is_empty() {
             return;
         }
 
@@ -210,12 +224,17 @@ impl TextPass {
             self.quad_buffers.indices.buffer().slice(..),
             wgpu::IndexFormat::Uint16,
         );
-        render_pass.draw_indexed(0..6, 0, 0..self.instances.len() as u32);
+        render_pass.draw_indexed(0..6, 0, instances);
     }
 
     /// Get the number of pending glyph instances.
     pub fn instance_count(&self) -> usize {
         self.instances.len()
     }
+
+    /// Usage of the GPU instance buffer.
+    pub fn buffer_stats(&self) -> BufferStats {
+        self.instance_buffer.stats()
+    }
 }
 
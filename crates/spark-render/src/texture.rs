@@ -0,0 +1,109 @@
+//! Images shared between widgets and the renderer's texture cache.
+
+use crate::ImageError;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+
+/// Identifies an image's texture in the renderer's cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextureId(u64);
+
+impl TextureId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+pub(crate) struct ImageData {
+    id: TextureId,
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+/// Decoded RGBA8 pixels, cheap to clone.
+///
+/// The renderer uploads each image once and keeps its texture until every
+/// handle to the image is dropped. Handles keep their pixels, so a renderer
+/// rebuilt after a lost GPU device uploads them again.
+#[derive(Clone)]
+pub struct ImageHandle(Arc<ImageData>);
+
+impl ImageHandle {
+    /// Wrap `width * height` RGBA8 pixels, row by row from the top.
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, ImageError> {
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected {
+            return Err(ImageError::SizeMismatch {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+        Ok(Self(Arc::new(ImageData {
+            id: TextureId::next(),
+            width,
+            height,
+            pixels,
+        })))
+    }
+
+    /// Decode a PNG or JPEG file.
+    #[cfg(feature = "image")]
+    pub fn decode(bytes: &[u8]) -> Result<Self, ImageError> {
+        let image = image::load_from_memory(bytes)?.to_rgba8();
+        let (width, height) = image.dimensions();
+        Self::from_rgba(width, height, image.into_raw())
+    }
+
+    /// The texture this image is drawn from.
+    pub fn id(&self) -> TextureId {
+        self.0.id
+    }
+
+    /// Width and height in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        (self.0.width, self.0.height)
+    }
+
+    /// RGBA8 pixels, row by row from the top.
+    pub fn pixels(&self) -> &[u8] {
+        &self.0.pixels
+    }
+
+    /// A reference that does not keep the pixels alive.
+    pub(crate) fn downgrade(&self) -> Weak<ImageData> {
+        Arc::downgrade(&self.0)
+    }
+}
+
+impl fmt::Debug for ImageHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageHandle")
+            .field("id", &self.0.id)
+            .field("width", &self.0.width)
+            .field("height", &self.0.height)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_rgba_checks_length() {
+        let image = ImageHandle::from_rgba(2, 1, vec![0; 8]).unwrap();
+        assert_eq!(image.size(), (2, 1));
+        let err = ImageHandle::from_rgba(2, 2, vec![0; 8]).unwrap_err();
+        assert_eq!(err.to_string(), "expected 16 bytes of RGBA pixels, got 8");
+    }
+
+    #[test]
+    fn test_images_get_distinct_ids() {
+        let a = ImageHandle::from_rgba(1, 1, vec![0; 4]).unwrap();
+        let b = ImageHandle::from_rgba(1, 1, vec![0; 4]).unwrap();
+        assert_ne!(a.id(), b.id());
+    }
+}
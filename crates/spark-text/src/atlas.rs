@@ -24,25 +24,34 @@ pub struct CachedGlyph {
 }
 
 /// Key for looking up cached glyphs.
-/// Uses a hash of the font data + glyph ID + font size for unique identification.
+/// Uses a hash of the font data + glyph ID + rasterized pixel size for unique identification.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct GlyphKey {
     /// Hash of the font source for identification.
     pub font_hash: u64,
     /// Glyph ID.
     pub glyph_id: u32,
-    /// Font size in 1/16th pixels (for sub-pixel precision).
-    pub font_size_16: u32,
+    /// Effective pixel size (font size × scale factor) in 1/16th pixels.
+    ///
+    /// Keying on the physical size means a scale factor change re-rasterizes
+    /// glyphs instead of stretching ones cached at the old size.
+    pub pixel_size_16: u32,
 }
 
 impl GlyphKey {
-    pub fn new(font_hash: u64, glyph_id: u32, font_size: f32) -> Self {
+    /// Create a key for a glyph rasterized at `pixel_size` physical pixels.
+    pub fn new(font_hash: u64, glyph_id: u32, pixel_size: f32) -> Self {
         Self {
             font_hash,
             glyph_id,
-            font_size_16: (font_size * 16.0) as u32,
+            pixel_size_16: (pixel_size * 16.0).round() as u32,
         }
     }
+
+    /// Create a key for a logical font size drawn at a scale factor.
+    pub fn scaled(font_hash: u64, glyph_id: u32, font_size: f32, scale_factor: f32) -> Self {
+        Self::new(font_hash, glyph_id, font_size * scale_factor)
+    }
 }
 
 /// A simple shelf-based atlas packer.
@@ -244,3 +253,16 @@ impl GlyphAtlas {
         self.dirty = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_key_changes_with_scale_factor() {
+        let at_1x = GlyphKey::scaled(1, 42, 14.0, 1.0);
+        let at_2x = GlyphKey::scaled(1, 42, 14.0, 2.0);
+        assert_ne!(at_1x, at_2x);
+        assert_eq!(at_2x, GlyphKey::new(1, 42, 28.0));
+    }
+}
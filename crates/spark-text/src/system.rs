@@ -146,6 +146,21 @@ impl TextSystem {
         &self.atlas
     }
 
+    /// Drop all cached glyphs (e.g. after a scale factor change, when glyphs
+    /// rasterized at the old pixel size will no longer be used).
+    pub fn clear_glyph_cache(&mut self) {
+        self.atlas.clear();
+    }
+
+    /// Move the glyph atlas to a new device after the old one was lost.
+    ///
+    /// Glyphs are rasterized and uploaded again as text is drawn; loaded
+    /// fonts are kept.
+    pub fn recreate_atlas(&mut self, device: &Device) {
+        let (width, height) = self.atlas.size();
+        self.atlas = GlyphAtlas::new(device, width, height);
+    }
+
     /// Shape and position text for rendering.
     pub fn shape(
         &mut self,
@@ -255,6 +270,8 @@ impl TextSystem {
     ) {
         let run = glyph_run.run();
         let font = run.font();
+        // Styles are scaled to physical pixels before shaping, so this is
+        // the effective pixel size the glyph is rasterized at.
         let font_size = run.font_size();
         
         // Convert brush color from [u8; 4] back to [f32; 4] for GlyphInstance
@@ -0,0 +1,351 @@
+//! Accessibility metadata that widgets report to assistive technologies.
+//!
+//! Widgets can also announce text, such as "3 results found", for screen
+//! readers to speak without moving focus. Announcements wait in the app's
+//! [`AnnouncementQueue`] until it publishes them, and are dropped while no
+//! assistive technology is listening.
+
+use accesskit::{Action, ActionData, Live, Node, Role};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+
+/// Accessibility information that widgets can provide.
+#[derive(Clone, Debug, Default)]
+pub struct AccessibleInfo {
+    /// The role of this element (button, text field, etc.)
+    pub role: AccessibleRole,
+    /// Human-readable name/label
+    pub name: Option<String>,
+    /// Human-readable description
+    pub description: Option<String>,
+    /// Current value (for sliders, text fields, etc.)
+    pub value: Option<String>,
+    /// Whether the element is focusable
+    pub focusable: bool,
+    /// Whether the element is currently focused
+    pub focused: bool,
+    /// Whether the element is disabled
+    pub disabled: bool,
+    /// Available actions
+    pub actions: Vec<AccessibleAction>,
+    /// Scroll position and range (for scroll views)
+    pub scroll: Option<AccessibleScroll>,
+    /// Leave this element and everything inside it out of the tree, e.g.
+    /// a decorative swatch or divider
+    pub hidden: bool,
+}
+
+/// Scroll position and maximum offsets, in logical pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AccessibleScroll {
+    pub x: f32,
+    pub y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+/// Role of an accessible element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AccessibleRole {
+    /// Generic container
+    #[default]
+    GenericContainer,
+    /// Push button
+    Button,
+    /// Text input field
+    TextField,
+    /// Static text label
+    Label,
+    /// Checkbox
+    CheckBox,
+    /// Radio button
+    RadioButton,
+    /// Slider
+    Slider,
+    /// List
+    List,
+    /// List item
+    ListItem,
+    /// Window
+    Window,
+    /// Scroll view
+    ScrollView,
+    /// Image
+    Image,
+    /// Pop-up list of choices
+    ComboBox,
+    /// Date and/or time input
+    DateTimeInput,
+    /// Group of mutually exclusive choices
+    RadioGroup,
+    /// Modal dialog
+    Dialog,
+    /// Progress bar or spinner
+    ProgressIndicator,
+    /// Rows of cells under column headers
+    Table,
+    /// Text field for search queries
+    SearchField,
+    /// Row of menus
+    MenuBar,
+}
+
+impl From<AccessibleRole> for Role {
+    fn from(role: AccessibleRole) -> Self {
+        match role {
+            AccessibleRole::GenericContainer => Role::GenericContainer,
+            AccessibleRole::Button => Role::Button,
+            AccessibleRole::TextField => Role::TextInput,
+            AccessibleRole::Label => Role::Label,
+            AccessibleRole::CheckBox => Role::CheckBox,
+            AccessibleRole::RadioButton => Role::RadioButton,
+            AccessibleRole::Slider => Role::Slider,
+            AccessibleRole::List => Role::List,
+            AccessibleRole::ListItem => Role::ListItem,
+            AccessibleRole::Window => Role::Window,
+            AccessibleRole::ScrollView => Role::ScrollView,
+            AccessibleRole::Image => Role::Image,
+            AccessibleRole::ComboBox => Role::ComboBox,
+            AccessibleRole::DateTimeInput => Role::DateTimeInput,
+            AccessibleRole::RadioGroup => Role::RadioGroup,
+            AccessibleRole::Dialog => Role::Dialog,
+            AccessibleRole::ProgressIndicator => Role::ProgressIndicator,
+            AccessibleRole::Table => Role::Table,
+            AccessibleRole::SearchField => Role::SearchInput,
+            AccessibleRole::MenuBar => Role::MenuBar,
+        }
+    }
+}
+
+/// Actions that assistive technologies can request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessibleAction {
+    /// Click/activate the element
+    Click,
+    /// Focus the element
+    Focus,
+    /// Set the element's value
+    SetValue,
+    /// Increment (for sliders, etc.)
+    Increment,
+    /// Decrement (for sliders, etc.)
+    Decrement,
+    /// Scroll up
+    ScrollUp,
+    /// Scroll down
+    ScrollDown,
+    /// Scroll left
+    ScrollLeft,
+    /// Scroll right
+    ScrollRight,
+}
+
+impl AccessibleAction {
+    /// Convert an AccessKit action, if it has a counterpart.
+    pub fn from_accesskit(action: Action) -> Option<Self> {
+        Some(match action {
+            Action::Click => Self::Click,
+            Action::Focus => Self::Focus,
+            Action::SetValue => Self::SetValue,
+            Action::Increment => Self::Increment,
+            Action::Decrement => Self::Decrement,
+            Action::ScrollUp => Self::ScrollUp,
+            Action::ScrollDown => Self::ScrollDown,
+            Action::ScrollLeft => Self::ScrollLeft,
+            Action::ScrollRight => Self::ScrollRight,
+            _ => return None,
+        })
+    }
+}
+
+/// Data carried by an action request (e.g. the new value for SetValue).
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccessibleValue {
+    /// A text value.
+    Text(String),
+    /// A numeric value.
+    Number(f64),
+}
+
+impl AccessibleValue {
+    /// Convert AccessKit action data, if it carries a value.
+    pub fn from_accesskit(data: &ActionData) -> Option<Self> {
+        match data {
+            ActionData::Value(value) => Some(Self::Text(value.to_string())),
+            ActionData::NumericValue(value) => Some(Self::Number(*value)),
+            _ => None,
+        }
+    }
+
+    /// The value as text (numbers are formatted).
+    pub fn as_text(&self) -> Cow<'_, str> {
+        match self {
+            Self::Text(text) => Cow::Borrowed(text),
+            Self::Number(value) => Cow::Owned(value.to_string()),
+        }
+    }
+}
+
+impl From<AccessibleAction> for Action {
+    fn from(action: AccessibleAction) -> Self {
+        match action {
+            AccessibleAction::Click => Action::Click,
+            AccessibleAction::Focus => Action::Focus,
+            AccessibleAction::SetValue => Action::SetValue,
+            AccessibleAction::Increment => Action::Increment,
+            AccessibleAction::Decrement => Action::Decrement,
+            AccessibleAction::ScrollUp => Action::ScrollUp,
+            AccessibleAction::ScrollDown => Action::ScrollDown,
+            AccessibleAction::ScrollLeft => Action::ScrollLeft,
+            AccessibleAction::ScrollRight => Action::ScrollRight,
+        }
+    }
+}
+
+/// Builds an AccessKit Node from AccessibleInfo.
+pub fn build_node(info: &AccessibleInfo) -> Node {
+    let mut node = Node::new(info.role.into());
+
+    if let Some(ref name) = info.name {
+        node.set_label(name.clone());
+    }
+
+    if let Some(ref desc) = info.description {
+        node.set_description(desc.clone());
+    }
+
+    if let Some(ref value) = info.value {
+        node.set_value(value.clone());
+    }
+
+    // Add Focus action to indicate the node is focusable
+    if info.focusable {
+        node.add_action(Action::Focus);
+    }
+
+    if info.disabled {
+        node.set_disabled();
+    }
+
+    if info.role == AccessibleRole::Dialog {
+        node.set_modal();
+    }
+
+    if let Some(scroll) = info.scroll {
+        node.set_scroll_x(scroll.x as f64);
+        node.set_scroll_x_min(0.0);
+        node.set_scroll_x_max(scroll.max_x as f64);
+        node.set_scroll_y(scroll.y as f64);
+        node.set_scroll_y_min(0.0);
+        node.set_scroll_y_max(scroll.max_y as f64);
+    }
+
+    // Add available actions
+    for action in &info.actions {
+        node.add_action((*action).into());
+    }
+
+    node
+}
+
+/// How urgently an announcement is spoken.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Politeness {
+    /// After the screen reader finishes what it is saying, e.g. search
+    /// results or a finished download.
+    #[default]
+    Polite,
+    /// Right away, interrupting speech, e.g. an error that needs attention.
+    Assertive,
+}
+
+impl From<Politeness> for Live {
+    fn from(politeness: Politeness) -> Self {
+        match politeness {
+            Politeness::Polite => Live::Polite,
+            Politeness::Assertive => Live::Assertive,
+        }
+    }
+}
+
+/// Text for screen readers to speak.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Announcement {
+    pub text: String,
+    pub politeness: Politeness,
+}
+
+/// The app's announcements, lent to widgets through their contexts.
+#[derive(Debug, Default)]
+pub struct AnnouncementQueue {
+    queued: RefCell<Vec<Announcement>>,
+    /// Whether assistive technology is listening, set by the app.
+    listening: Cell<bool>,
+}
+
+impl AnnouncementQueue {
+    /// Create an empty queue that drops announcements until listening.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Have screen readers speak `text` without moving focus.
+    ///
+    /// Does nothing while no assistive technology is listening. An
+    /// announcement identical to the one queued just before it is dropped.
+    pub fn announce(&self, text: impl Into<String>, politeness: Politeness) {
+        if !self.listening.get() {
+            return;
+        }
+        let announcement = Announcement {
+            text: text.into(),
+            politeness,
+        };
+        let mut queued = self.queued.borrow_mut();
+        if queued.last() != Some(&announcement) {
+            queued.push(announcement);
+        }
+    }
+
+    /// Set whether assistive technology is listening, dropping queued
+    /// announcements when it stops.
+    pub fn set_listening(&self, listening: bool) {
+        self.listening.set(listening);
+        if !listening {
+            self.queued.borrow_mut().clear();
+        }
+    }
+
+    /// Check if announcements are waiting to be published.
+    pub fn is_empty(&self) -> bool {
+        self.queued.borrow().is_empty()
+    }
+
+    /// Take the queued announcements, oldest first.
+    pub fn take(&self) -> Vec<Announcement> {
+        self.queued.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announcements_only_queue_while_listening() {
+        let queue = AnnouncementQueue::new();
+        queue.announce("ignored", Politeness::Polite);
+        assert!(queue.is_empty());
+
+        queue.set_listening(true);
+        queue.announce("3 results found", Politeness::Polite);
+        queue.announce("3 results found", Politeness::Polite);
+        queue.announce("Name is required", Politeness::Assertive);
+        let texts: Vec<_> = queue.take().into_iter().map(|a| a.text).collect();
+        assert_eq!(texts, ["3 results found", "Name is required"]);
+
+        queue.announce("pending", Politeness::Polite);
+        queue.set_listening(false);
+        assert!(queue.take().is_empty());
+    }
+}
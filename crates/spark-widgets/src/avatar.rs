@@ -0,0 +1,246 @@
+//! Avatar widget.
+
+use crate::image::fit_image;
+use crate::{AccessibleInfo, AccessibleRole, ImageFit, PaintContext, Theme, Widget};
+use spark_core::{Color, Rect};
+use spark_layout::WidgetId;
+use spark_render::ImageHandle;
+use spark_text::TextStyle;
+use taffy::prelude::*;
+
+/// Colors for avatars without an image, picked by their initials.
+const INITIALS_PALETTE: [u32; 6] = [0x2563EB, 0x7C3AED, 0xDB2777, 0xDC2626, 0x059669, 0x0891B2];
+/// Diameter of the status dot, as a share of the avatar's.
+const STATUS_SIZE: f32 = 0.3;
+
+/// Presence shown by an avatar's status dot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AvatarStatus {
+    Online,
+    Away,
+    Busy,
+    Offline,
+}
+
+impl AvatarStatus {
+    fn color(self, theme: &Theme) -> Color {
+        let colors = &theme.colors;
+        match self {
+            AvatarStatus::Online => colors.success,
+            AvatarStatus::Away => colors.warning,
+            AvatarStatus::Busy => colors.danger,
+            AvatarStatus::Offline => colors.disabled,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            AvatarStatus::Online => "online",
+            AvatarStatus::Away => "away",
+            AvatarStatus::Busy => "busy",
+            AvatarStatus::Offline => "offline",
+        }
+    }
+}
+
+/// The palette color for an avatar showing `initials`, so the same person
+/// keeps the same color.
+fn initials_color(initials: &str) -> Color {
+    let hash = initials
+        .chars()
+        .fold(0u32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as u32));
+    Color::from_hex(INITIALS_PALETTE[hash as usize % INITIALS_PALETTE.len()])
+}
+
+/// A round picture of a person, or their initials on a colored circle when
+/// there is no picture.
+///
+/// An optional status dot sits on the lower right edge and an optional ring
+/// surrounds the circle.
+pub struct Avatar {
+    id: WidgetId,
+    image: Option<ImageHandle>,
+    initials: String,
+    size: f32,
+    background: Option<Color>,
+    status: Option<AvatarStatus>,
+    ring: Option<(Color, f32)>,
+    label: Option<String>,
+}
+
+impl Avatar {
+    /// Create an empty 40 pixel avatar.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            image: None,
+            initials: String::new(),
+            size: 40.0,
+            background: None,
+            status: None,
+            ring: None,
+            label: None,
+        }
+    }
+
+    /// Show a PNG or JPEG picture, cropped to the circle.
+    ///
+    /// A picture that fails to decode is logged and the initials are shown.
+    #[cfg(feature = "image")]
+    pub fn image(mut self, bytes: &[u8]) -> Self {
+        self.image = ImageHandle::decode(bytes)
+            .map_err(|err| log::warn!("failed to decode avatar image: {err}"))
+            .ok();
+        self
+    }
+
+    /// Show already decoded pixels, cropped to the circle.
+    pub fn image_handle(mut self, image: ImageHandle) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    /// Set the initials shown without a picture.
+    pub fn initials(mut self, initials: impl Into<String>) -> Self {
+        self.initials = initials.into();
+        self
+    }
+
+    /// Set the diameter in logical pixels, ring included.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the circle color behind the initials; by default it is picked
+    /// from the initials.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Show a status dot.
+    pub fn status(mut self, status: AvatarStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Surround the avatar with a ring of `width` logical pixels.
+    pub fn ring(mut self, color: Color, width: f32) -> Self {
+        self.ring = Some((color, width));
+        self
+    }
+
+    /// Set the description announced by screen readers, e.g. the name.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+impl Default for Avatar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Avatar {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style {
+            size: Size {
+                width: length(self.size),
+                height: length(self.size),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let scale = ctx.scale_factor;
+        let theme = ctx.theme();
+        let diameter = bounds.width.min(bounds.height);
+        let circle = Rect::new(
+            bounds.x + (bounds.width - diameter) / 2.0,
+            bounds.y + (bounds.height - diameter) / 2.0,
+            diameter,
+            diameter,
+        );
+
+        // The picture or initials sit inside the ring
+        let inset = self.ring.map_or(0.0, |(_, width)| width * scale);
+        let content = Rect::new(
+            circle.x + inset,
+            circle.y + inset,
+            circle.width - inset * 2.0,
+            circle.height - inset * 2.0,
+        );
+        let radius = content.width / scale / 2.0;
+        match &self.image {
+            Some(image) => {
+                let (rect, uv) = fit_image(ImageFit::Cover, image.size(), content);
+                ctx.draw_image(rect, image, uv, Color::WHITE, radius);
+            }
+            None => {
+                let background = self
+                    .background
+                    .unwrap_or_else(|| initials_color(&self.initials));
+                ctx.fill_rounded_rect(content, background, radius);
+                let text_style = TextStyle::default()
+                    .with_size(content.width / scale * 0.4)
+                    .with_color(Color::WHITE);
+                ctx.draw_text_centered(&self.initials, &text_style, content);
+            }
+        }
+
+        if let Some((color, width)) = self.ring {
+            ctx.fill_bordered_rect(
+                circle,
+                Color::TRANSPARENT,
+                diameter / scale / 2.0,
+                width,
+                color,
+            );
+        }
+
+        if let Some(status) = self.status {
+            // Centered on the circle's edge, down and to the right
+            let dot = diameter * STATUS_SIZE;
+            let offset = diameter / 2.0 * std::f32::consts::FRAC_1_SQRT_2;
+            let (cx, cy) = (
+                circle.x + circle.width / 2.0 + offset,
+                circle.y + circle.height / 2.0 + offset,
+            );
+            let dot_rect = Rect::new(cx - dot / 2.0, cy - dot / 2.0, dot, dot);
+            ctx.fill_bordered_rect(
+                dot_rect,
+                status.color(theme),
+                dot / scale / 2.0,
+                2.0,
+                theme.colors.surface,
+            );
+        }
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let name = self
+            .label
+            .clone()
+            .or_else(|| (!self.initials.is_empty()).then(|| self.initials.clone()));
+        AccessibleInfo {
+            role: AccessibleRole::Image,
+            name,
+            description: self.status.map(|status| status.label().to_string()),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Notification badge widget.
+
+use crate::{AccessibleInfo, PaintContext, Theme, ThemedStyle, Widget};
+use spark_core::{Color, Rect};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use taffy::prelude::*;
+
+/// Style configuration for a badge.
+#[derive(Clone, Debug)]
+pub struct BadgeStyle {
+    pub background: Color,
+    pub text_color: Color,
+    /// Height of the pill; it is never narrower than this.
+    pub height: f32,
+    /// Horizontal space between the count and the pill edge.
+    pub padding: f32,
+    pub font_size: f32,
+}
+
+impl Default for BadgeStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for BadgeStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            background: colors.danger,
+            text_color: Color::WHITE,
+            height: 18.0,
+            padding: 5.0,
+            font_size: theme.typography.caption,
+        }
+    }
+}
+
+/// The text shown for `count`, or `None` when the badge is hidden.
+fn badge_label(count: u32, max: u32) -> Option<String> {
+    match count {
+        0 => None,
+        count if count > max => Some(format!("{max}+")),
+        count => Some(count.to_string()),
+    }
+}
+
+/// Wraps a widget and shows a count in a pill over its top-right corner.
+///
+/// The pill is centered on the corner, so it overhangs the wrapped widget
+/// without taking up layout space. Scroll containers still clip it.
+pub struct Badge {
+    id: WidgetId,
+    child: Box<dyn Widget>,
+    count: u32,
+    max: u32,
+    style: Option<BadgeStyle>,
+}
+
+impl Badge {
+    /// Wrap `child`; the badge stays hidden until given a count.
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            id: WidgetId::default(),
+            child: Box::new(child),
+            count: 0,
+            max: 99,
+            style: None,
+        }
+    }
+
+    /// Set the count; zero hides the badge.
+    pub fn count(mut self, count: u32) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Show counts above `max` as "`max`+".
+    pub fn max(mut self, max: u32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set the badge style.
+    pub fn with_style(mut self, style: BadgeStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Widget for Badge {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        // A column, so the child stretches across the badge like it would
+        // across the badge's parent
+        Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {
+        // The child is painted by the framework traversal
+    }
+
+    fn paint_after_children(&self, ctx: &mut PaintContext) {
+        let Some(label) = badge_label(self.count, self.max) else {
+            return;
+        };
+        let style = ctx.theme().resolve(&self.style);
+        let scale = ctx.scale_factor;
+
+        // Anchor to the child, which may be narrower than a stretched badge
+        let anchor = ctx
+            .layout_tree
+            .get_absolute_layout(self.child.id())
+            .map_or(ctx.bounds(), |layout| layout.bounds.scale(scale));
+
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.text_color);
+        let (text_width, _) = ctx.measure_text(&label, &text_style);
+        let height = style.height * scale;
+        let width = (text_width + style.padding * 2.0 * scale).max(height);
+        let pill = Rect::new(
+            anchor.x + anchor.width - width / 2.0,
+            anchor.y - height / 2.0,
+            width,
+            height,
+        );
+        ctx.fill_rounded_rect(pill, style.background, style.height / 2.0);
+        ctx.draw_text_centered(&label, &text_style, pill);
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        std::slice::from_ref(&self.child)
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        std::slice::from_mut(&mut self.child)
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            value: badge_label(self.count, self.max),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_label_hides_zero_and_caps_at_max() {
+        assert_eq!(badge_label(0, 99), None);
+        assert_eq!(badge_label(42, 99), Some("42".into()));
+        assert_eq!(badge_label(99, 99), Some("99".into()));
+        assert_eq!(badge_label(100, 99), Some("99+".into()));
+    }
+}
@@ -0,0 +1,511 @@
+//! Breadcrumb navigation widget.
+
+use crate::{
+    AccessibleInfo, CursorIcon, EventContext, EventResponse, IconName, LayoutContext, PaintContext,
+    Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{InputEvent, Key, NamedKey};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::cell::RefCell;
+use std::ops::Range;
+use taffy::prelude::*;
+
+/// Width of the chevron between two segments, in logical pixels.
+const SEPARATOR_WIDTH: f32 = 20.0;
+/// Size of the chevron icon within the separator, in logical pixels.
+const CHEVRON_SIZE: f32 = 16.0;
+/// Label of the item standing in for collapsed segments.
+const ELLIPSIS: &str = "…";
+/// Space between the trail and the list of collapsed segments.
+const LIST_GAP: f32 = 4.0;
+
+/// Style configuration for a breadcrumb.
+#[derive(Clone, Debug)]
+pub struct BreadcrumbStyle {
+    /// Color of the segments that can be clicked.
+    pub link_color: Color,
+    pub hover_color: Color,
+    /// Color of the last segment, the current location.
+    pub current_color: Color,
+    pub separator_color: Color,
+    /// Fill of the list of collapsed segments.
+    pub list_background: Color,
+    pub list_border_color: Color,
+    /// Fill behind the hovered row of the list.
+    pub highlight: Color,
+    pub corner_radius: f32,
+    /// Space between the list edge and its labels.
+    pub padding_h: f32,
+    /// Height of each row of the list.
+    pub row_height: f32,
+    pub font_size: f32,
+}
+
+impl Default for BreadcrumbStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for BreadcrumbStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            link_color: colors.text_muted,
+            hover_color: colors.primary,
+            current_color: colors.text,
+            separator_color: colors.text_muted,
+            list_background: colors.surface,
+            list_border_color: colors.border,
+            highlight: colors.selection,
+            corner_radius: theme.radius.md,
+            padding_h: theme.spacing.md,
+            row_height: 28.0,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// One item of the trail as shown.
+#[derive(Clone, Debug, PartialEq)]
+enum Crumb {
+    Segment(usize),
+    /// The "…" item standing in for these segments.
+    Collapsed(Range<usize>),
+}
+
+/// A crumb with its horizontal extent, relative to the trail's left edge.
+#[derive(Clone, Debug, PartialEq)]
+struct PlacedCrumb {
+    crumb: Crumb,
+    x: f32,
+    width: f32,
+}
+
+/// Lay out the trail for segments of the given widths within `available`.
+///
+/// When everything doesn't fit, segments after the first are collapsed
+/// into an ellipsis, keeping as many of the last segments as fit. The
+/// first and last segments always stay, even if they overflow.
+fn place_crumbs(widths: &[f32], ellipsis: f32, available: f32) -> Vec<PlacedCrumb> {
+    let place = |crumbs: Vec<Crumb>| {
+        let mut x = 0.0;
+        crumbs
+            .into_iter()
+            .map(|crumb| {
+                let width = match &crumb {
+                    Crumb::Segment(index) => widths[*index],
+                    Crumb::Collapsed(_) => ellipsis,
+                };
+                let placed = PlacedCrumb { crumb, x, width };
+                x += width + SEPARATOR_WIDTH;
+                placed
+            })
+            .collect::<Vec<_>>()
+    };
+    let fits =
+        |placed: &[PlacedCrumb]| placed.last().map_or(0.0, |last| last.x + last.width) <= available;
+
+    let count = widths.len();
+    let all = place((0..count).map(Crumb::Segment).collect());
+    if count <= 2 || fits(&all) {
+        return all;
+    }
+    let collapsed = |first_shown: usize| {
+        let mut crumbs = vec![Crumb::Segment(0), Crumb::Collapsed(1..first_shown)];
+        crumbs.extend((first_shown..count).map(Crumb::Segment));
+        place(crumbs)
+    };
+    (2..count - 1)
+        .map(collapsed)
+        .find(|placed| fits(placed))
+        .unwrap_or_else(|| collapsed(count - 1))
+}
+
+/// Text metrics from the last layout, in logical pixels.
+#[derive(Default)]
+struct Metrics {
+    widths: Vec<f32>,
+    ellipsis: f32,
+}
+
+/// A navigation trail of segments separated by chevrons.
+///
+/// Every segment but the last, which marks the current location, can be
+/// clicked. When the trail is too wide for its space, the middle segments
+/// collapse into "…", which opens a list of them.
+pub struct Breadcrumb {
+    id: WidgetId,
+    segments: Vec<String>,
+    style: Option<BreadcrumbStyle>,
+    /// Measured at layout time, hence the cell.
+    metrics: RefCell<Metrics>,
+    hovered: Option<usize>,
+    pressed: Option<usize>,
+    /// The segments listed while the collapsed list is open.
+    open: Option<Range<usize>>,
+    /// The hovered segment in the open list.
+    highlighted: Option<usize>,
+    on_click: Option<Box<dyn FnMut(usize) + Send + Sync>>,
+}
+
+impl Breadcrumb {
+    /// Create a breadcrumb from the segments, outermost first.
+    pub fn new<S: Into<String>>(segments: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            segments: segments.into_iter().map(Into::into).collect(),
+            style: None,
+            metrics: RefCell::new(Metrics::default()),
+            hovered: None,
+            pressed: None,
+            open: None,
+            highlighted: None,
+            on_click: None,
+        }
+    }
+
+    /// Set the handler called with the index of a clicked segment.
+    pub fn on_click(mut self, handler: impl FnMut(usize) + Send + Sync + 'static) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the breadcrumb style.
+    pub fn with_style(mut self, style: BreadcrumbStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The trail as laid out in `width` logical pixels.
+    fn crumbs(&self, width: f32) -> Vec<PlacedCrumb> {
+        let metrics = self.metrics.borrow();
+        if metrics.widths.len() != self.segments.len() {
+            return Vec::new();
+        }
+        // Leave room for rounding in the measured width
+        place_crumbs(&metrics.widths, metrics.ellipsis, width + 0.5)
+    }
+
+    /// The list of collapsed segments under the crumb at `crumb_x`, in
+    /// logical pixels.
+    fn list_rect(
+        &self,
+        bounds: Rect,
+        crumb_x: f32,
+        hidden: &Range<usize>,
+        style: &BreadcrumbStyle,
+    ) -> Rect {
+        let metrics = self.metrics.borrow();
+        let label_width = hidden
+            .clone()
+            .map(|index| metrics.widths.get(index).copied().unwrap_or(0.0))
+            .fold(0.0, f32::max);
+        Rect::new(
+            bounds.x + crumb_x,
+            bounds.y + bounds.height + LIST_GAP,
+            label_width + style.padding_h * 2.0,
+            hidden.len() as f32 * style.row_height,
+        )
+    }
+
+    /// The open list and its segments, in logical pixels.
+    fn open_list(&self, bounds: Rect, style: &BreadcrumbStyle) -> Option<(Rect, Range<usize>)> {
+        let hidden = self.open.clone()?;
+        let crumbs = self.crumbs(bounds.width);
+        let crumb = crumbs
+            .iter()
+            .find(|placed| placed.crumb == Crumb::Collapsed(hidden.clone()))?;
+        Some((self.list_rect(bounds, crumb.x, &hidden, style), hidden))
+    }
+
+    /// The index into the crumbs under `pos`.
+    fn crumb_at(crumbs: &[PlacedCrumb], bounds: Rect, pos: glam::Vec2) -> Option<usize> {
+        if !bounds.contains(pos) {
+            return None;
+        }
+        let x = pos.x - bounds.x;
+        crumbs
+            .iter()
+            .position(|placed| x >= placed.x && x < placed.x + placed.width)
+    }
+
+    /// The segment in the open list under `pos`, with rows `row_height`
+    /// logical pixels tall.
+    fn row_at(
+        list: Rect,
+        hidden: &Range<usize>,
+        pos: glam::Vec2,
+        row_height: f32,
+    ) -> Option<usize> {
+        if !list.contains(pos) {
+            return None;
+        }
+        let row = ((pos.y - list.y) / row_height) as usize;
+        Some(hidden.start + row).filter(|index| hidden.contains(index))
+    }
+
+    fn click(&mut self, index: usize) {
+        if let Some(handler) = &mut self.on_click {
+            handler(index);
+        }
+    }
+
+    fn paint_chevron(ctx: &mut PaintContext, x: f32, bounds: Rect, color: Color) {
+        let side = CHEVRON_SIZE * ctx.scale_factor;
+        let center_x = x + SEPARATOR_WIDTH / 2.0 * ctx.scale_factor;
+        let center_y = bounds.y + bounds.height / 2.0;
+        let icon = Rect::new(center_x - side / 2.0, center_y - side / 2.0, side, side);
+        IconName::ChevronRight.paint(ctx, icon, color);
+    }
+}
+
+impl Widget for Breadcrumb {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style::default()
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let scale = ctx.scale_factor;
+        let style = ctx.theme().resolve(&self.style);
+        let last = self.segments.len().saturating_sub(1);
+
+        for (position, placed) in self.crumbs(bounds.width / scale).iter().enumerate() {
+            let x = bounds.x + placed.x * scale;
+            if position > 0 {
+                let separator = x - SEPARATOR_WIDTH * scale;
+                Self::paint_chevron(ctx, separator, bounds, style.separator_color);
+            }
+            let (label, color) = match &placed.crumb {
+                Crumb::Segment(index) if *index == last => {
+                    (self.segments[*index].as_str(), style.current_color)
+                }
+                Crumb::Segment(index) => (self.segments[*index].as_str(), style.link_color),
+                Crumb::Collapsed(_) => (ELLIPSIS, style.link_color),
+            };
+            let color = if self.hovered == Some(position) {
+                style.hover_color
+            } else {
+                color
+            };
+            let text_style = TextStyle::default()
+                .with_size(style.font_size)
+                .with_color(color);
+            let cell = Rect::new(x, bounds.y, placed.width * scale, bounds.height);
+            ctx.draw_text_aligned(label, &text_style, cell, 0.0);
+        }
+    }
+
+    fn paint_overlay(&self, ctx: &mut PaintContext) {
+        let style = ctx.theme().resolve(&self.style);
+        let scale = ctx.scale_factor;
+        let Some((list, hidden)) = self.open_list(ctx.bounds().scale(1.0 / scale), &style) else {
+            return;
+        };
+        let list = list.scale(scale);
+
+        ctx.fill_bordered_rect(
+            list.expand(scale),
+            style.list_background,
+            style.corner_radius,
+            1.0,
+            style.list_border_color,
+        );
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.current_color);
+        let row_height = style.row_height * scale;
+        for (row, index) in hidden.enumerate() {
+            let row_rect = Rect::new(
+                list.x,
+                list.y + row as f32 * row_height,
+                list.width,
+                row_height,
+            );
+            if self.highlighted == Some(index) {
+                ctx.fill_rect(row_rect, style.highlight);
+            }
+            let label = &self.segments[index];
+            ctx.draw_text_aligned(label, &text_style, row_rect, style.padding_h * scale);
+        }
+    }
+
+    fn has_overlay(&self) -> bool {
+        self.open.is_some()
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let bounds = ctx.bounds();
+        let style = ctx.theme().resolve(&self.style).into_owned();
+        if let Some((list, hidden)) = self.open_list(bounds, &style) {
+            return match event {
+                InputEvent::PointerMove { pos, .. } => {
+                    let highlighted = Self::row_at(list, &hidden, *pos, style.row_height);
+                    if highlighted != self.highlighted {
+                        self.highlighted = highlighted;
+                        return EventResponse {
+                            repaint: true,
+                            ..Default::default()
+                        };
+                    }
+                    EventResponse::default()
+                }
+                // Clicking anywhere but the list dismisses it
+                InputEvent::PointerDown { pos, .. } if !list.contains(*pos) => {
+                    self.open = None;
+                    EventResponse::handled()
+                }
+                InputEvent::PointerUp { pos, .. } => {
+                    if let Some(index) = Self::row_at(list, &hidden, *pos, style.row_height) {
+                        self.open = None;
+                        self.click(index);
+                        return EventResponse::handled();
+                    }
+                    EventResponse::default()
+                }
+                InputEvent::KeyDown { event }
+                    if matches!(event.key, Key::Named(NamedKey::Escape)) =>
+                {
+                    self.open = None;
+                    EventResponse::handled()
+                }
+                _ => EventResponse::default(),
+            };
+        }
+
+        let crumbs = self.crumbs(bounds.width);
+        let last = self.segments.len().saturating_sub(1);
+        // The current location isn't a link
+        let clickable = event
+            .pos()
+            .and_then(|pos| Self::crumb_at(&crumbs, bounds, pos))
+            .filter(|&position| crumbs[position].crumb != Crumb::Segment(last));
+        match event {
+            InputEvent::PointerMove { .. } => {
+                let changed = clickable != self.hovered;
+                self.hovered = clickable;
+                EventResponse {
+                    repaint: changed,
+                    cursor: clickable.map(|_| CursorIcon::Pointer),
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerLeave if self.hovered.is_some() => {
+                self.hovered = None;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerDown { .. } if clickable.is_some() => {
+                self.pressed = clickable;
+                EventResponse::capture()
+            }
+            InputEvent::PointerCancel { .. } if self.pressed.is_some() => {
+                self.pressed = None;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { .. } if self.pressed.is_some() => {
+                let pressed = self.pressed.take();
+                if clickable == pressed {
+                    match pressed.map(|position| crumbs[position].crumb.clone()) {
+                        Some(Crumb::Segment(index)) => self.click(index),
+                        Some(Crumb::Collapsed(hidden)) => {
+                            self.open = Some(hidden);
+                            self.highlighted = None;
+                        }
+                        None => {}
+                    }
+                }
+                EventResponse::release()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            name: Some("Breadcrumb".to_string()),
+            value: Some(self.segments.join(" / ")),
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let font_size = ctx.theme.resolve(&self.style).font_size;
+        let text_style = TextStyle::default().with_size(font_size);
+        let mut height = font_size;
+        let mut measure = |text: &str| {
+            let (width, text_height) = ctx.text.measure(text, &text_style, None);
+            height = height.max(text_height);
+            width
+        };
+        let widths: Vec<f32> = self
+            .segments
+            .iter()
+            .map(|segment| measure(segment))
+            .collect();
+        let ellipsis = measure(ELLIPSIS);
+
+        let available = ctx.max_width.unwrap_or(f32::INFINITY);
+        let crumbs = place_crumbs(&widths, ellipsis, available);
+        let width = crumbs.last().map_or(0.0, |last| last.x + last.width);
+        *self.metrics.borrow_mut() = Metrics { widths, ellipsis };
+        Some((width, height))
+    }
+
+    fn wraps(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shown(placed: &[PlacedCrumb]) -> Vec<Crumb> {
+        placed.iter().map(|placed| placed.crumb.clone()).collect()
+    }
+
+    #[test]
+    fn test_place_crumbs_collapses_middle_segments() {
+        let widths = [50.0, 50.0, 50.0, 50.0, 50.0];
+        // Everything fits: 5 segments and 4 separators
+        let all = place_crumbs(&widths, 10.0, 330.0);
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[1].x, 70.0);
+
+        // Room for the first, the ellipsis and the last two
+        let placed = place_crumbs(&widths, 10.0, 250.0);
+        assert_eq!(
+            shown(&placed),
+            vec![
+                Crumb::Segment(0),
+                Crumb::Collapsed(1..3),
+                Crumb::Segment(3),
+                Crumb::Segment(4),
+            ]
+        );
+
+        // No room at all still keeps the first and last
+        let placed = place_crumbs(&widths, 10.0, 0.0);
+        assert_eq!(
+            shown(&placed),
+            vec![Crumb::Segment(0), Crumb::Collapsed(1..4), Crumb::Segment(4)]
+        );
+    }
+}
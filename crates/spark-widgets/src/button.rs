@@ -1,6 +1,9 @@
 //! Button widget.
 
-use crate::{EventContext, EventResponse, PaintContext, Widget};
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, ColorToken, CursorIcon, EventContext,
+    EventResponse, PaintContext, Theme, ThemedStyle, Widget,
+};
 use spark_core::Color;
 use spark_input::InputEvent;
 use spark_layout::WidgetId;
@@ -40,19 +43,26 @@ pub struct ButtonStyle {
 
 impl Default for ButtonStyle {
     fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for ButtonStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
         Self {
-            background: Color::from_hex(0x3B82F6),         // Blue
-            background_hovered: Color::from_hex(0x2563EB), // Darker blue
-            background_pressed: Color::from_hex(0x1D4ED8), // Even darker
-            background_disabled: Color::from_hex(0x9CA3AF), // Gray
-            text_color: Color::WHITE,
-            text_color_disabled: Color::from_hex(0x6B7280),
+            background: colors.primary,
+            background_hovered: colors.primary_hovered,
+            background_pressed: colors.primary_pressed,
+            background_disabled: colors.disabled,
+            text_color: colors.on_primary,
+            text_color_disabled: colors.text_disabled,
             border_color: Color::TRANSPARENT,
             border_width: 0.0,
-            corner_radius: 6.0,
-            padding_h: 16.0,
-            padding_v: 8.0,
-            font_size: 14.0,
+            corner_radius: theme.radius.md,
+            padding_h: theme.spacing.lg,
+            padding_v: theme.spacing.sm,
+            font_size: theme.typography.body,
             min_width: 0.0,  // Will be set based on label
             min_height: 0.0, // Will be set based on font_size
         }
@@ -63,36 +73,35 @@ impl Default for ButtonStyle {
 pub struct Button {
     id: WidgetId,
     label: String,
-    style: ButtonStyle,
+    style: Option<ButtonStyle>,
+    background: Option<Color>,
+    background_token: Option<ColorToken>,
+    text_color: Option<Color>,
+    corner_radius: Option<f32>,
     state: ButtonState,
     on_click: Option<Box<dyn FnMut() + Send + Sync>>,
+    /// Name for screen readers when the label alone doesn't say enough.
+    accessibility_label: Option<String>,
+    accessibility_description: Option<String>,
+    accessibility_hidden: bool,
 }
 
 impl Button {
     /// Create a new button with the given label.
     pub fn new(label: impl Into<String>) -> Self {
-        let label = label.into();
-        let style = ButtonStyle::default();
-        
-        // Estimate minimum size based on label and style
-        // Rough estimate: ~8px per character for 14px font, plus padding
-        let char_width = style.font_size * 0.6;
-        let estimated_text_width = label.len() as f32 * char_width;
-        let min_width = estimated_text_width + style.padding_h * 2.0;
-        
-        // Height: font size * line height (~1.4) + vertical padding
-        let min_height = style.font_size * 1.4 + style.padding_v * 2.0;
-        
         Self {
             id: WidgetId::default(),
-            label,
-            style: ButtonStyle {
-                min_width,
-                min_height,
-                ..style
-            },
+            label: label.into(),
+            style: None,
+            background: None,
+            background_token: None,
+            text_color: None,
+            corner_radius: None,
             state: ButtonState::Normal,
             on_click: None,
+            accessibility_label: None,
+            accessibility_description: None,
+            accessibility_hidden: false,
         }
     }
 
@@ -102,27 +111,55 @@ impl Button {
         self
     }
 
+    /// Set the name announced by screen readers instead of the label, e.g.
+    /// "Increase" for a "+" button.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
+    /// Set extra detail screen readers announce after the name.
+    pub fn accessibility_description(mut self, description: impl Into<String>) -> Self {
+        self.accessibility_description = Some(description.into());
+        self
+    }
+
+    /// Hide the button from screen readers, e.g. when another control
+    /// already does the same thing.
+    pub fn accessibility_hidden(mut self, hidden: bool) -> Self {
+        self.accessibility_hidden = hidden;
+        self
+    }
+
     /// Set the button style.
     pub fn with_style(mut self, style: ButtonStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
     /// Set the background color.
     pub fn background(mut self, color: Color) -> Self {
-        self.style.background = color;
+        self.background = Some(color);
+        self.background_token = None;
+        self
+    }
+
+    /// Set the background color from a theme token.
+    pub fn background_token(mut self, token: ColorToken) -> Self {
+        self.background = None;
+        self.background_token = Some(token);
         self
     }
 
     /// Set the text color.
     pub fn text_color(mut self, color: Color) -> Self {
-        self.style.text_color = color;
+        self.text_color = Some(color);
         self
     }
 
     /// Set corner radius.
     pub fn corner_radius(mut self, radius: f32) -> Self {
-        self.style.corner_radius = radius;
+        self.corner_radius = Some(radius);
         self
     }
 
@@ -134,19 +171,38 @@ impl Button {
         self
     }
 
-    fn current_background(&self) -> Color {
+    /// The style for `theme`, with the background, text color and radius
+    /// overrides applied.
+    fn resolved_style(&self, theme: &Theme) -> ButtonStyle {
+        let mut style = theme.resolve(&self.style).into_owned();
+        let background = self
+            .background
+            .or_else(|| self.background_token.map(|t| theme.color(t)));
+        if let Some(background) = background {
+            style.background = background;
+        }
+        if let Some(text_color) = self.text_color {
+            style.text_color = text_color;
+        }
+        if let Some(radius) = self.corner_radius {
+            style.corner_radius = radius;
+        }
+        style
+    }
+
+    fn current_background(&self, style: &ButtonStyle) -> Color {
         match self.state {
-            ButtonState::Normal => self.style.background,
-            ButtonState::Hovered => self.style.background_hovered,
-            ButtonState::Pressed => self.style.background_pressed,
-            ButtonState::Disabled => self.style.background_disabled,
+            ButtonState::Normal => style.background,
+            ButtonState::Hovered => style.background_hovered,
+            ButtonState::Pressed => style.background_pressed,
+            ButtonState::Disabled => style.background_disabled,
         }
     }
 
-    fn current_text_color(&self) -> Color {
+    fn current_text_color(&self, style: &ButtonStyle) -> Color {
         match self.state {
-            ButtonState::Disabled => self.style.text_color_disabled,
-            _ => self.style.text_color,
+            ButtonState::Disabled => style.text_color_disabled,
+            _ => style.text_color,
         }
     }
 }
@@ -160,17 +216,30 @@ impl Widget for Button {
         self.id = id;
     }
 
-    fn style(&self) -> Style {
+    fn style(&self, theme: &Theme) -> Style {
+        let style = theme.resolve(&self.style);
+        // A minimum of 0 is estimated from the label: ~0.6em per character,
+        // a ~1.4 line height, plus padding
+        let min_width = if style.min_width > 0.0 {
+            style.min_width
+        } else {
+            self.label.len() as f32 * style.font_size * 0.6 + style.padding_h * 2.0
+        };
+        let min_height = if style.min_height > 0.0 {
+            style.min_height
+        } else {
+            style.font_size * 1.4 + style.padding_v * 2.0
+        };
         Style {
             min_size: Size {
-                width: length(self.style.min_width),
-                height: length(self.style.min_height),
+                width: length(min_width),
+                height: length(min_height),
             },
             padding: Rect {
-                left: length(self.style.padding_h),
-                right: length(self.style.padding_h),
-                top: length(self.style.padding_v),
-                bottom: length(self.style.padding_v),
+                left: length(style.padding_h),
+                right: length(style.padding_h),
+                top: length(style.padding_v),
+                bottom: length(style.padding_v),
             },
             align_items: Some(AlignItems::Center),
             justify_content: Some(JustifyContent::Center),
@@ -180,44 +249,40 @@ impl Widget for Button {
 
     fn paint(&self, ctx: &mut PaintContext) {
         let bounds = ctx.bounds();
-        let bg = self.current_background();
-        let text_color = self.current_text_color();
+        let theme = ctx.theme();
+        let style = self.resolved_style(theme);
+        let bg = self.current_background(&style);
+        let text_color = self.current_text_color(&style);
         let scale = ctx.scale_factor;
 
         // Draw button background
-        if self.style.border_width > 0.0 {
+        if style.border_width > 0.0 {
             ctx.fill_bordered_rect(
                 bounds,
                 bg,
-                self.style.corner_radius,
-                self.style.border_width,
-                self.style.border_color,
+                style.corner_radius,
+                style.border_width,
+                style.border_color,
             );
         } else {
-            ctx.fill_rounded_rect(bounds, bg, self.style.corner_radius);
+            ctx.fill_rounded_rect(bounds, bg, style.corner_radius);
         }
 
-        // Focus ring (scale offset for HiDPI)
-        if ctx.has_focus() {
-            let offset = 2.0 * scale;
-            let focus_bounds = spark_core::Rect::new(
-                bounds.x - offset,
-                bounds.y - offset,
-                bounds.width + offset * 2.0,
-                bounds.height + offset * 2.0,
-            );
+        // Focus ring (scale offset for HiDPI), only for keyboard focus
+        if ctx.focus_visible() {
+            let focus_bounds = bounds.expand(2.0 * scale);
             ctx.fill_bordered_rect(
                 focus_bounds,
                 Color::TRANSPARENT,
-                self.style.corner_radius + 2.0,
+                style.corner_radius + 2.0,
                 2.0,
-                Color::from_hex(0x60A5FA),
+                theme.colors.focus_ring,
             );
         }
 
         // Draw the button label text, centered
         let text_style = TextStyle::default()
-            .with_size(self.style.font_size)
+            .with_size(style.font_size)
             .with_color(text_color);
         ctx.draw_text_centered(&self.label, &text_style, bounds);
     }
@@ -228,19 +293,26 @@ impl Widget for Button {
         }
 
         match event {
-            InputEvent::PointerMove { pos } => {
-                if ctx.contains(*pos) {
-                    if self.state != ButtonState::Pressed {
-                        self.state = ButtonState::Hovered;
-                    }
-                } else {
-                    self.state = ButtonState::Normal;
+            InputEvent::PointerEnter { .. } => {
+                if self.state != ButtonState::Pressed {
+                    self.state = ButtonState::Hovered;
                 }
                 EventResponse {
                     repaint: true,
                     ..Default::default()
                 }
             }
+            InputEvent::PointerLeave => {
+                self.state = ButtonState::Normal;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerMove { pos, .. } => EventResponse {
+                cursor: ctx.contains(*pos).then_some(CursorIcon::Pointer),
+                ..Default::default()
+            },
             InputEvent::PointerDown { pos, .. } => {
                 if ctx.contains(*pos) {
                     self.state = ButtonState::Pressed;
@@ -248,6 +320,13 @@ impl Widget for Button {
                 }
                 EventResponse::default()
             }
+            InputEvent::PointerCancel { .. } if self.state == ButtonState::Pressed => {
+                self.state = ButtonState::Normal;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
             InputEvent::PointerUp { pos, .. } => {
                 if self.state == ButtonState::Pressed {
                     if ctx.contains(*pos) {
@@ -284,13 +363,30 @@ impl Widget for Button {
         self.state != ButtonState::Disabled
     }
 
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let disabled = self.state == ButtonState::Disabled;
+        let name = self.accessibility_label.as_ref().unwrap_or(&self.label);
+        AccessibleInfo {
+            role: AccessibleRole::Button,
+            name: Some(name.clone()),
+            description: self.accessibility_description.clone(),
+            focusable: !disabled,
+            disabled,
+            actions: if disabled {
+                Vec::new()
+            } else {
+                vec![AccessibleAction::Click]
+            },
+            hidden: self.accessibility_hidden,
+            ..Default::default()
+        }
+    }
+
     fn measure(&self, ctx: &mut crate::LayoutContext) -> Option<(f32, f32)> {
-        let style = TextStyle::default().with_size(self.style.font_size);
-        let (w, h) = ctx.text.measure(&self.label, &style, None);
-        Some((
-            w + self.style.padding_h * 2.0,
-            h + self.style.padding_v * 2.0,
-        ))
+        let font_size = ctx.theme.resolve(&self.style).font_size;
+        let style = TextStyle::default().with_size(font_size);
+        // Padding comes from the style
+        Some(ctx.text.measure(&self.label, &style, None))
     }
 }
 
@@ -0,0 +1,106 @@
+//! Custom painting widget.
+
+use crate::{EventContext, EventResponse, PaintContext, Theme, Widget};
+use spark_core::Rect;
+use spark_input::InputEvent;
+use spark_layout::WidgetId;
+use taffy::prelude::*;
+
+type PaintHandler = Box<dyn Fn(&mut PaintContext, Rect) + Send + Sync>;
+type EventHandler = Box<dyn FnMut(&mut EventContext, &InputEvent) -> EventResponse + Send + Sync>;
+
+/// A widget that paints with a closure, for charts, gauges and other
+/// one-off drawings that don't warrant a `Widget` impl.
+///
+/// The closure gets the full [`PaintContext`] and the canvas bounds in
+/// physical pixels. Without an explicit size the canvas grows to fill the
+/// free space of its container.
+pub struct Canvas {
+    id: WidgetId,
+    paint: PaintHandler,
+    on_event: Option<EventHandler>,
+    size: Option<(f32, f32)>,
+    animate: bool,
+}
+
+impl Canvas {
+    /// Create a canvas painted by `paint`.
+    pub fn new(paint: impl Fn(&mut PaintContext, Rect) + Send + Sync + 'static) -> Self {
+        Self {
+            id: WidgetId::default(),
+            paint: Box::new(paint),
+            on_event: None,
+            size: None,
+            animate: false,
+        }
+    }
+
+    /// Set a fixed size in logical pixels.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Set the handler for input events.
+    ///
+    /// Pointer events outside the canvas arrive too; check them with
+    /// [`EventContext::contains`].
+    pub fn on_event(
+        mut self,
+        handler: impl FnMut(&mut EventContext, &InputEvent) -> EventResponse + Send + Sync + 'static,
+    ) -> Self {
+        self.on_event = Some(Box::new(handler));
+        self
+    }
+
+    /// Repaint every frame, for canvases animated by `elapsed_time`.
+    pub fn animate(mut self, animate: bool) -> Self {
+        self.animate = animate;
+        self
+    }
+}
+
+impl Widget for Canvas {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        match self.size {
+            Some((width, height)) => Style {
+                size: Size {
+                    width: length(width),
+                    height: length(height),
+                },
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            None => Style {
+                flex_grow: 1.0,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        ctx.push_clip(bounds);
+        (self.paint)(ctx, bounds);
+        ctx.pop_clip();
+    }
+
+    fn needs_animation(&self) -> bool {
+        self.animate
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        match &mut self.on_event {
+            Some(handler) => handler(ctx, event),
+            None => EventResponse::default(),
+        }
+    }
+}
@@ -0,0 +1,389 @@
+//! Checkbox widget.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext,
+    PaintContext, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::InputEvent;
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use taffy::prelude::*;
+
+/// Style configuration for a checkbox.
+#[derive(Clone, Debug)]
+pub struct CheckboxStyle {
+    /// Box fill while unchecked.
+    pub box_color: Color,
+    /// Box fill while checked.
+    pub box_color_checked: Color,
+    pub border_color: Color,
+    pub border_width: f32,
+    pub check_color: Color,
+    pub text_color: Color,
+    /// Side length of the box.
+    pub size: f32,
+    pub corner_radius: f32,
+    /// Space between the box and the label.
+    pub gap: f32,
+    pub font_size: f32,
+}
+
+impl Default for CheckboxStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for CheckboxStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            box_color: colors.surface,
+            box_color_checked: colors.primary,
+            border_color: colors.border,
+            border_width: 1.0,
+            check_color: colors.on_primary,
+            text_color: colors.text,
+            size: 18.0,
+            corner_radius: theme.radius.sm,
+            gap: theme.spacing.sm,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// A labeled box that toggles between checked and unchecked.
+///
+/// It can also start indeterminate, e.g. for a parent whose items are
+/// partly checked; toggling it from there checks it.
+pub struct Checkbox {
+    id: WidgetId,
+    label: String,
+    style: Option<CheckboxStyle>,
+    checked: bool,
+    indeterminate: bool,
+    pressed: bool,
+    on_change: Option<Box<dyn FnMut(bool) + Send + Sync>>,
+}
+
+impl Checkbox {
+    /// Create an unchecked checkbox with the given label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            label: label.into(),
+            style: None,
+            checked: false,
+            indeterminate: false,
+            pressed: false,
+            on_change: None,
+        }
+    }
+
+    /// Set whether the box starts checked.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Set whether the box starts indeterminate, drawn with a dash instead
+    /// of a check mark.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    /// Set the handler called with the new state when the box is toggled.
+    pub fn on_change(mut self, handler: impl FnMut(bool) + Send + Sync + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the checkbox style.
+    pub fn with_style(mut self, style: CheckboxStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Whether the box is checked.
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Whether the box is indeterminate.
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    fn toggle(&mut self) {
+        self.checked = self.indeterminate || !self.checked;
+        self.indeterminate = false;
+        if let Some(handler) = &mut self.on_change {
+            handler(self.checked);
+        }
+    }
+
+    /// The state as screen readers announce it.
+    fn state_name(&self) -> &'static str {
+        match (self.indeterminate, self.checked) {
+            (true, _) => "mixed",
+            (false, true) => "checked",
+            (false, false) => "unchecked",
+        }
+    }
+
+    /// Draw a check mark inside `mark`.
+    fn paint_check(ctx: &mut PaintContext, mark: Rect, color: Color) {
+        let stroke = (mark.width * 0.14).max(1.0);
+        let point = |fx: f32, fy: f32| (mark.x + mark.width * fx, mark.y + mark.height * fy);
+        ctx.draw_line(point(0.2, 0.52), point(0.42, 0.74), stroke, color);
+        ctx.draw_line(point(0.42, 0.74), point(0.8, 0.3), stroke, color);
+    }
+
+    /// Draw the indeterminate dash inside `mark`.
+    fn paint_dash(ctx: &mut PaintContext, mark: Rect, color: Color) {
+        let stroke = (mark.width * 0.14).max(1.0);
+        let y = mark.y + mark.height / 2.0;
+        let (left, right) = (mark.x + mark.width * 0.25, mark.x + mark.width * 0.75);
+        ctx.draw_line((left, y), (right, y), stroke, color);
+    }
+}
+
+impl Widget for Checkbox {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let style = theme.resolve(&self.style);
+        Style {
+            min_size: Size {
+                width: length(style.size),
+                height: length(style.size),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let theme = ctx.theme();
+        let style = theme.resolve(&self.style);
+        let scale = ctx.scale_factor;
+
+        // The box sits at the left edge, vertically centered
+        let size = style.size * scale;
+        let box_bounds = Rect::new(
+            bounds.x,
+            bounds.y + (bounds.height - size) / 2.0,
+            size,
+            size,
+        );
+        let (fill, border) = if self.checked || self.indeterminate {
+            (style.box_color_checked, style.box_color_checked)
+        } else {
+            (style.box_color, style.border_color)
+        };
+        ctx.fill_bordered_rect(
+            box_bounds,
+            fill,
+            style.corner_radius,
+            style.border_width,
+            border,
+        );
+        if self.indeterminate {
+            Self::paint_dash(ctx, box_bounds, style.check_color);
+        } else if self.checked {
+            Self::paint_check(ctx, box_bounds, style.check_color);
+        }
+
+        // Focus ring (scale offset for HiDPI), only for keyboard focus
+        if ctx.focus_visible() {
+            ctx.fill_bordered_rect(
+                box_bounds.expand(2.0 * scale),
+                Color::TRANSPARENT,
+                style.corner_radius + 2.0,
+                2.0,
+                theme.colors.focus_ring,
+            );
+        }
+
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.text_color);
+        ctx.draw_text_aligned(&self.label, &text_style, bounds, size + style.gap * scale);
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        match event {
+            InputEvent::PointerDown { pos, .. } => {
+                if ctx.contains(*pos) {
+                    self.pressed = true;
+                    return EventResponse::capture();
+                }
+                EventResponse::default()
+            }
+            InputEvent::PointerCancel { .. } if self.pressed => {
+                self.pressed = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                if !self.pressed {
+                    return EventResponse::default();
+                }
+                self.pressed = false;
+                if ctx.contains(*pos) {
+                    self.toggle();
+                }
+                EventResponse::release()
+            }
+            InputEvent::KeyDown { .. } => {
+                if ctx.has_focus() {
+                    use spark_input::{ActionMapper, StandardAction};
+                    let mapper = ActionMapper::new();
+                    if mapper.is_action(event, StandardAction::Activate) {
+                        self.toggle();
+                        return EventResponse::handled();
+                    }
+                }
+                EventResponse::default()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::CheckBox,
+            name: Some(self.label.clone()),
+            value: Some(self.state_name().into()),
+            focusable: true,
+            actions: vec![AccessibleAction::Click],
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let size = style.size;
+        if self.label.is_empty() {
+            return Some((size, size));
+        }
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let (width, height) = ctx.text.measure(&self.label, &text_style, None);
+        Some((size + style.gap + width, height.max(size)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnnouncementQueue, ClipboardService, EventPhase};
+    use spark_input::{FocusManager, Key, KeyboardEvent, NamedKey, PointerButton};
+    use spark_layout::LayoutTree;
+    use std::sync::{Arc, Mutex};
+
+    struct NoClipboard;
+
+    impl ClipboardService for NoClipboard {
+        fn set_text(&mut self, _text: String) {}
+        fn request_text(&mut self) {}
+    }
+
+    /// Deliver `events` to `checkbox`, laid out alone at the origin.
+    fn send(checkbox: &mut Checkbox, focused: bool, events: &[InputEvent]) {
+        let mut tree = LayoutTree::new();
+        let id = tree.new_leaf(checkbox.style(&Theme::light()));
+        tree.set_root(id);
+        tree.compute_layout(200.0, 100.0);
+        checkbox.set_id(id);
+        let mut focus = FocusManager::new();
+        if focused {
+            focus.set_focus(id);
+        }
+        let mut ctx = EventContext {
+            layout: tree.get_absolute_layout(id).unwrap(),
+            layout_tree: &tree,
+            focus: &mut focus,
+            widget_id: id,
+            has_capture: false,
+            target: id,
+            phase: EventPhase::Target,
+            theme: &Theme::light(),
+            clipboard: &mut NoClipboard,
+            announcements: &AnnouncementQueue::new(),
+        };
+        for event in events {
+            checkbox.event(&mut ctx, event);
+        }
+    }
+
+    fn click(pos: (f32, f32)) -> [InputEvent; 2] {
+        let pos = glam::Vec2::new(pos.0, pos.1);
+        [
+            InputEvent::pointer_down(pos, PointerButton::Primary),
+            InputEvent::pointer_up(pos, PointerButton::Primary),
+        ]
+    }
+
+    #[test]
+    fn test_clicks_inside_toggle_and_report_the_new_state() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let seen = changes.clone();
+        let mut checkbox = Checkbox::new("Remember me")
+            .on_change(move |checked| seen.lock().unwrap().push(checked));
+
+        send(&mut checkbox, false, &click((5.0, 5.0)));
+        assert!(checkbox.is_checked());
+        send(&mut checkbox, false, &click((5.0, 5.0)));
+        assert!(!checkbox.is_checked());
+        // Pressing outside the box does nothing
+        send(&mut checkbox, false, &click((150.0, 80.0)));
+        assert!(!checkbox.is_checked());
+        assert_eq!(*changes.lock().unwrap(), [true, false]);
+    }
+
+    #[test]
+    fn test_activate_toggles_only_when_focused() {
+        let enter = [InputEvent::KeyDown {
+            event: KeyboardEvent {
+                key: Key::Named(NamedKey::Enter),
+                ..Default::default()
+            },
+        }];
+        let mut checkbox = Checkbox::new("Remember me");
+        send(&mut checkbox, false, &enter);
+        assert!(!checkbox.is_checked());
+        send(&mut checkbox, true, &enter);
+        assert!(checkbox.is_checked());
+    }
+
+    #[test]
+    fn test_indeterminate_boxes_check_when_toggled() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let seen = changes.clone();
+        let mut checkbox = Checkbox::new("Select all")
+            .checked(true)
+            .indeterminate(true)
+            .on_change(move |checked| seen.lock().unwrap().push(checked));
+        let state = |checkbox: &Checkbox| checkbox.accessibility_info().value;
+        assert_eq!(state(&checkbox).as_deref(), Some("mixed"));
+
+        send(&mut checkbox, false, &click((5.0, 5.0)));
+        assert!(checkbox.is_checked() && !checkbox.is_indeterminate());
+        assert_eq!(state(&checkbox).as_deref(), Some("checked"));
+        assert_eq!(*changes.lock().unwrap(), [true]);
+    }
+}
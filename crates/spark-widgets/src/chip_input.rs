@@ -0,0 +1,580 @@
+//! Chip (tag) input widget.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleValue, CursorIcon, EventContext, EventResponse,
+    LayoutContext, PaintContext, TextInput, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{shortcuts, InputEvent, Key, NamedKey};
+use spark_layout::{ComputedLayout, WidgetId};
+use spark_text::TextStyle;
+use taffy::prelude::*;
+
+/// Side of the square around a chip's remove button, in logical pixels.
+const CLOSE_SIZE: f32 = 16.0;
+/// Space between a chip's remove button and its right edge.
+const CLOSE_INSET: f32 = 4.0;
+/// Narrowest the text entry gets before it wraps to the next line.
+const MIN_ENTRY_WIDTH: f32 = 80.0;
+
+/// Style configuration for a chip input.
+#[derive(Clone, Debug)]
+pub struct ChipInputStyle {
+    pub background: Color,
+    pub border_color: Color,
+    pub border_color_focused: Color,
+    pub border_width: f32,
+    pub corner_radius: f32,
+    /// Space between the border and the chips.
+    pub padding: f32,
+    /// Space between neighbouring chips, in both directions.
+    pub gap: f32,
+    pub chip_background: Color,
+    pub chip_text_color: Color,
+    /// Background of a chip's remove button while hovered.
+    pub chip_close_hovered: Color,
+    pub chip_height: f32,
+    /// Space between a chip's left edge and its label.
+    pub chip_padding: f32,
+    pub font_size: f32,
+    pub focus_ring_color: Color,
+}
+
+impl Default for ChipInputStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for ChipInputStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            background: colors.surface,
+            border_color: colors.border,
+            border_color_focused: colors.primary,
+            border_width: 1.0,
+            corner_radius: theme.radius.md,
+            padding: theme.spacing.xs + 2.0,
+            gap: theme.spacing.xs,
+            chip_background: colors.surface_alt,
+            chip_text_color: colors.text,
+            chip_close_hovered: colors.border,
+            chip_height: 24.0,
+            chip_padding: 10.0,
+            font_size: theme.typography.body,
+            focus_ring_color: colors.focus_ring.with_alpha(0.5),
+        }
+    }
+}
+
+/// Whether `c` ends a chip when typed or pasted.
+fn is_separator(c: char) -> bool {
+    c == ',' || c == '\n'
+}
+
+/// Split typed text into finished chips and the text still being typed.
+///
+/// Everything before the last separator becomes chips, trimmed and with
+/// empty entries dropped; the rest stays in the field.
+fn split_entries(text: &str) -> (Vec<String>, &str) {
+    let Some(end) = text.rfind(is_separator) else {
+        return (Vec::new(), text);
+    };
+    let chips = text[..end]
+        .split(is_separator)
+        .map(str::trim)
+        .filter(|chip| !chip.is_empty())
+        .map(str::to_string)
+        .collect();
+    (chips, &text[end + 1..])
+}
+
+/// Layout leaf sized to fit one chip; the chip input paints it.
+struct ChipSlot {
+    id: WidgetId,
+    label: String,
+    style: Option<ChipInputStyle>,
+}
+
+impl Widget for ChipSlot {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style {
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {}
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let (width, _) = ctx.text.measure(&self.label, &text_style, None);
+        let chrome = style.chip_padding + CLOSE_SIZE + CLOSE_INSET * 2.0;
+        Some((width + chrome, style.chip_height))
+    }
+}
+
+/// Layout leaf for the text entry after the chips; it takes the rest of
+/// the line, or wraps to a line of its own when too little is left.
+struct EntrySlot {
+    id: WidgetId,
+    style: Option<ChipInputStyle>,
+}
+
+impl Widget for EntrySlot {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        Style {
+            flex_grow: 1.0,
+            min_size: Size {
+                width: length(MIN_ENTRY_WIDTH),
+                height: length(theme.resolve(&self.style).chip_height),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {}
+}
+
+/// A field for entering a list of short values, shown as removable chips.
+///
+/// Enter or a comma turns the typed text into a chip, and pasted text is
+/// split at commas and line breaks. Backspace in the empty field removes
+/// the last chip; each chip also has its own remove button. The chips
+/// wrap onto more lines as needed.
+pub struct ChipInput {
+    id: WidgetId,
+    chips: Vec<String>,
+    /// One slot per chip, then the entry slot.
+    slots: Vec<Box<dyn Widget>>,
+    input: TextInput,
+    style: Option<ChipInputStyle>,
+    hovered_close: Option<usize>,
+    pressed_close: Option<usize>,
+    on_add: Option<Box<dyn FnMut(&str) + Send + Sync>>,
+    on_remove: Option<Box<dyn FnMut(usize) + Send + Sync>>,
+}
+
+impl ChipInput {
+    /// Create an empty chip input.
+    pub fn new() -> Self {
+        let mut input = TextInput::new();
+        input.set_padding_h(0.0);
+        input.set_embedded(true);
+        let mut chip_input = Self {
+            id: WidgetId::default(),
+            chips: Vec::new(),
+            slots: Vec::new(),
+            input,
+            style: None,
+            hovered_close: None,
+            pressed_close: None,
+            on_add: None,
+            on_remove: None,
+        };
+        chip_input.rebuild_slots();
+        chip_input
+    }
+
+    /// Set the initial chips.
+    pub fn chips<S: Into<String>>(mut self, chips: impl IntoIterator<Item = S>) -> Self {
+        self.chips = chips.into_iter().map(Into::into).collect();
+        self.rebuild_slots();
+        self
+    }
+
+    /// Set the text shown in the entry while it is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.input = self.input.placeholder(placeholder);
+        self
+    }
+
+    /// Set the handler called with the text of each new chip.
+    pub fn on_add(mut self, handler: impl FnMut(&str) + Send + Sync + 'static) -> Self {
+        self.on_add = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the handler called with the index of each removed chip.
+    pub fn on_remove(mut self, handler: impl FnMut(usize) + Send + Sync + 'static) -> Self {
+        self.on_remove = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the chip input style.
+    pub fn with_style(mut self, style: ChipInputStyle) -> Self {
+        self.style = Some(style);
+        self.rebuild_slots();
+        self
+    }
+
+    /// Get the chips, in order.
+    pub fn get_chips(&self) -> &[String] {
+        &self.chips
+    }
+
+    fn rebuild_slots(&mut self) {
+        self.slots = self
+            .chips
+            .iter()
+            .map(|label| {
+                Box::new(ChipSlot {
+                    id: WidgetId::default(),
+                    label: label.clone(),
+                    style: self.style.clone(),
+                }) as Box<dyn Widget>
+            })
+            .collect();
+        self.slots.push(Box::new(EntrySlot {
+            id: WidgetId::default(),
+            style: self.style.clone(),
+        }));
+    }
+
+    /// Turn `entries` into chips, returning whether any were added.
+    fn add_chips(&mut self, entries: Vec<String>) -> bool {
+        if entries.is_empty() {
+            return false;
+        }
+        for chip in entries {
+            if let Some(handler) = &mut self.on_add {
+                handler(&chip);
+            }
+            self.chips.push(chip);
+        }
+        self.rebuild_slots();
+        true
+    }
+
+    fn remove_chip(&mut self, index: usize) {
+        self.chips.remove(index);
+        self.hovered_close = None;
+        if let Some(handler) = &mut self.on_remove {
+            handler(index);
+        }
+        self.rebuild_slots();
+    }
+
+    /// Bounds of slot `index`, in logical pixels.
+    fn slot_bounds(&self, ctx: &EventContext, index: usize) -> Option<Rect> {
+        ctx.layout_tree
+            .get_absolute_layout(self.slots[index].id())
+            .map(|layout| layout.bounds)
+    }
+
+    /// The remove button of a chip, in the same units as `chip`.
+    fn close_rect(chip: Rect, scale: f32) -> Rect {
+        let size = CLOSE_SIZE * scale;
+        Rect::new(
+            chip.x + chip.width - size - CLOSE_INSET * scale,
+            chip.y + (chip.height - size) / 2.0,
+            size,
+            size,
+        )
+    }
+
+    /// The chip whose remove button is under `pos`.
+    fn close_at(&self, ctx: &EventContext, pos: glam::Vec2) -> Option<usize> {
+        (0..self.chips.len()).find(|&index| {
+            self.slot_bounds(ctx, index)
+                .is_some_and(|chip| Self::close_rect(chip, 1.0).contains(pos))
+        })
+    }
+
+    fn relayout() -> EventResponse {
+        EventResponse {
+            relayout: true,
+            ..EventResponse::handled()
+        }
+    }
+
+    fn paint_chip(&self, ctx: &mut PaintContext, index: usize, chip: Rect, style: &ChipInputStyle) {
+        let scale = ctx.scale_factor;
+        ctx.fill_rounded_rect(chip, style.chip_background, style.chip_height / 2.0);
+
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.chip_text_color);
+        let (_, text_height) = ctx.measure_text(&self.chips[index], &text_style);
+        ctx.draw_text(
+            &self.chips[index],
+            &text_style,
+            chip.x + style.chip_padding * scale,
+            chip.y + (chip.height - text_height) / 2.0,
+        );
+
+        let close = Self::close_rect(chip, scale);
+        if self.hovered_close == Some(index) {
+            ctx.fill_rounded_rect(close, style.chip_close_hovered, CLOSE_SIZE / 2.0);
+        }
+        let (cx, cy) = (close.x + close.width / 2.0, close.y + close.height / 2.0);
+        let arm = close.width * 0.2;
+        let color = style.chip_text_color;
+        ctx.draw_line(
+            (cx - arm, cy - arm),
+            (cx + arm, cy + arm),
+            1.5 * scale,
+            color,
+        );
+        ctx.draw_line(
+            (cx - arm, cy + arm),
+            (cx + arm, cy - arm),
+            1.5 * scale,
+            color,
+        );
+    }
+}
+
+impl Default for ChipInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for ChipInput {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let style = theme.resolve(&self.style);
+        let padding = length(style.padding);
+        let gap = length(style.gap);
+        Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            align_items: Some(AlignItems::Center),
+            gap: Size {
+                width: gap,
+                height: gap,
+            },
+            padding: taffy::Rect {
+                left: padding,
+                right: padding,
+                top: padding,
+                bottom: padding,
+            },
+            min_size: Size {
+                width: length(200.0),
+                height: auto(),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let scale = ctx.scale_factor;
+        let style = ctx.theme().resolve(&self.style);
+        let border = if ctx.has_focus() {
+            style.border_color_focused
+        } else {
+            style.border_color
+        };
+        ctx.fill_bordered_rect(
+            bounds,
+            style.background,
+            style.corner_radius,
+            style.border_width,
+            border,
+        );
+        if ctx.focus_visible() {
+            ctx.fill_bordered_rect(
+                bounds.expand(2.0 * scale),
+                Color::TRANSPARENT,
+                style.corner_radius + 2.0,
+                2.0,
+                style.focus_ring_color,
+            );
+        }
+
+        let slots: Vec<_> = self
+            .slots
+            .iter()
+            .map(|slot| {
+                ctx.layout_tree
+                    .get_absolute_layout(slot.id())
+                    .map(|layout| layout.bounds.scale(scale))
+            })
+            .collect();
+        for (index, chip) in slots.iter().take(self.chips.len()).enumerate() {
+            if let Some(chip) = chip {
+                self.paint_chip(ctx, index, *chip, &style);
+            }
+        }
+
+        // The entry paints as if it were laid out on its own
+        if let Some(Some(entry)) = slots.last() {
+            let own = std::mem::replace(&mut ctx.layout, ComputedLayout::new(*entry));
+            self.input.paint(ctx);
+            ctx.layout = own;
+        }
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        match event {
+            InputEvent::PointerMove { pos, .. } => {
+                let hovered = self.close_at(ctx, *pos);
+                let changed = hovered != self.hovered_close;
+                self.hovered_close = hovered;
+                let cursor = if hovered.is_some() {
+                    Some(CursorIcon::Pointer)
+                } else {
+                    ctx.contains(*pos).then_some(CursorIcon::Text)
+                };
+                return EventResponse {
+                    repaint: changed,
+                    cursor,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerLeave if self.hovered_close.is_some() => {
+                self.hovered_close = None;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerDown { pos, .. } => {
+                if let Some(index) = self.close_at(ctx, *pos) {
+                    self.pressed_close = Some(index);
+                    return EventResponse::capture();
+                }
+            }
+            InputEvent::PointerCancel { .. } if self.pressed_close.is_some() => {
+                self.pressed_close = None;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                if let Some(index) = self.pressed_close.take() {
+                    if self.close_at(ctx, *pos) == Some(index) {
+                        self.remove_chip(index);
+                        return EventResponse {
+                            release_pointer: true,
+                            ..Self::relayout()
+                        };
+                    }
+                    return EventResponse::release();
+                }
+            }
+            InputEvent::KeyDown { event } if ctx.has_focus() => {
+                if matches!(event.key, Key::Named(NamedKey::Enter)) {
+                    let (entries, _) = split_entries(&format!("{},", self.input.get_value()));
+                    self.input.replace_value("");
+                    if self.add_chips(entries) {
+                        return Self::relayout();
+                    }
+                    return EventResponse::handled();
+                }
+                if shortcuts::is_backspace(event)
+                    && self.input.get_value().is_empty()
+                    && !self.chips.is_empty()
+                {
+                    self.remove_chip(self.chips.len() - 1);
+                    return Self::relayout();
+                }
+            }
+            _ => {}
+        }
+
+        let response = self.input.event(ctx, event);
+        if matches!(
+            event,
+            InputEvent::TextInput { .. } | InputEvent::Paste { .. }
+        ) && self.input.get_value().contains(is_separator)
+        {
+            let (entries, rest) = split_entries(self.input.get_value());
+            let rest = rest.to_string();
+            self.input.replace_value(rest.trim_start());
+            if self.add_chips(entries) {
+                return Self::relayout();
+            }
+        }
+        response
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        &self.slots
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        &mut self.slots
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let info = self.input.accessibility_info();
+        AccessibleInfo {
+            description: if self.chips.is_empty() {
+                info.description.clone()
+            } else {
+                Some(self.chips.join(", "))
+            },
+            ..info
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        self.input.accessibility_action(ctx, action, value)
+    }
+
+    fn on_focus(&mut self) {
+        self.input.on_focus();
+    }
+
+    fn on_blur(&mut self) {
+        self.input.on_blur();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_entries_keeps_unfinished_text() {
+        assert_eq!(split_entries("rust"), (vec![], "rust"));
+        assert_eq!(split_entries("rust,"), (vec!["rust".to_string()], ""));
+        assert_eq!(
+            split_entries(" rust , gpu,,\nui,wid"),
+            (
+                vec!["rust".to_string(), "gpu".to_string(), "ui".to_string()],
+                "wid"
+            )
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! Clipboard access for widgets.
+//!
+//! The app owns the clipboard and lends it to widgets through
+//! [`EventContext::clipboard`](crate::EventContext::clipboard), so writes
+//! happen during the event that made them, while browsers still treat it as
+//! a user gesture. Reading is asynchronous on the web, so widgets never read
+//! the clipboard directly: [`ClipboardService::request_text`] asks for the
+//! contents, which arrive later as
+//! [`InputEvent::Paste`](spark_input::InputEvent::Paste) at the focused
+//! widget.
+
+/// The app's clipboard, as lent to widgets.
+pub trait ClipboardService {
+    /// Place text on the clipboard.
+    fn set_text(&mut self, text: String);
+
+    /// Ask for the clipboard's text, delivered to the focused widget as
+    /// `InputEvent::Paste`.
+    fn request_text(&mut self);
+}
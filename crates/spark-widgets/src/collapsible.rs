@@ -0,0 +1,361 @@
+//! Collapsible section widget.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext,
+    PaintContext, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{ActionMapper, InputEvent, StandardAction};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::cell::Cell;
+use std::f32::consts::FRAC_PI_2;
+use taffy::prelude::*;
+
+/// Seconds the chevron takes to turn.
+const CHEVRON_ANIMATION_SECONDS: f32 = 0.15;
+/// Half the height of the chevron, in logical pixels.
+const CHEVRON_SIZE: f32 = 4.0;
+
+/// Style configuration for a collapsible section.
+#[derive(Clone, Debug)]
+pub struct CollapsibleStyle {
+    pub header_background: Color,
+    /// Header fill while the pointer is over it.
+    pub header_hover_background: Color,
+    pub text_color: Color,
+    pub chevron_color: Color,
+    pub header_height: f32,
+    /// Space between the header edge and the chevron.
+    pub padding: f32,
+    /// Space between the chevron and the title.
+    pub gap: f32,
+    pub corner_radius: f32,
+    pub font_size: f32,
+}
+
+impl Default for CollapsibleStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for CollapsibleStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            header_background: Color::TRANSPARENT,
+            header_hover_background: colors.surface_alt,
+            text_color: colors.text,
+            chevron_color: colors.text_muted,
+            header_height: 36.0,
+            padding: theme.spacing.sm,
+            gap: theme.spacing.sm,
+            corner_radius: theme.radius.sm,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// Whether `target` is `widget` or one of its descendants.
+fn contains_widget(widget: &dyn Widget, target: WidgetId) -> bool {
+    widget.id() == target
+        || widget
+            .children()
+            .iter()
+            .any(|child| contains_widget(child.as_ref(), target))
+}
+
+/// A titled header that shows or hides its content when clicked.
+///
+/// Collapsed content is left out of layout entirely, so containers around
+/// the section (including [`Scroll`](crate::Scroll)) shrink to fit.
+pub struct Collapsible {
+    id: WidgetId,
+    title: String,
+    content: Box<dyn Widget>,
+    expanded: bool,
+    style: Option<CollapsibleStyle>,
+    hovered: bool,
+    pressed: bool,
+    on_toggle: Option<Box<dyn FnMut(bool) + Send + Sync>>,
+    /// The state last painted, to notice toggles while painting.
+    painted_expanded: Cell<bool>,
+    /// When the chevron started turning, in `elapsed_time` seconds.
+    turn_started: Cell<Option<f32>>,
+}
+
+impl Collapsible {
+    /// Create an expanded section showing `content` under `title`.
+    pub fn new(title: impl Into<String>, content: impl Widget + 'static) -> Self {
+        Self {
+            id: WidgetId::default(),
+            title: title.into(),
+            content: Box::new(content),
+            expanded: true,
+            style: None,
+            hovered: false,
+            pressed: false,
+            on_toggle: None,
+            painted_expanded: Cell::new(true),
+            turn_started: Cell::new(None),
+        }
+    }
+
+    /// Set whether the section starts expanded.
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self.painted_expanded.set(expanded);
+        self
+    }
+
+    /// Set the handler called with the new state when the section is toggled.
+    pub fn on_toggle(mut self, handler: impl FnMut(bool) + Send + Sync + 'static) -> Self {
+        self.on_toggle = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the collapsible section style.
+    pub fn with_style(mut self, style: CollapsibleStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Whether the content is shown.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    fn toggle(&mut self, ctx: &mut EventContext) -> EventResponse {
+        // Focus inside content that is about to disappear moves to the header
+        let focused = ctx.focus.focused();
+        if self.expanded && focused.is_some_and(|id| contains_widget(self.content.as_ref(), id)) {
+            ctx.focus.set_focus(self.id);
+        }
+        self.expanded = !self.expanded;
+        if let Some(handler) = &mut self.on_toggle {
+            handler(self.expanded);
+        }
+        EventResponse {
+            relayout: true,
+            ..EventResponse::handled()
+        }
+    }
+
+    /// The clickable header row, in the same units as `bounds`.
+    fn header_rect(bounds: Rect, scale: f32, style: &CollapsibleStyle) -> Rect {
+        let height = (style.header_height * scale).min(bounds.height);
+        Rect::new(bounds.x, bounds.y, bounds.width, height)
+    }
+
+    /// Chevron turn from 0 (pointing right) to 1 (pointing down), easing
+    /// toward the current state.
+    fn chevron_turn(&self, ctx: &PaintContext) -> f32 {
+        let now = ctx.elapsed_time;
+        if self.painted_expanded.replace(self.expanded) != self.expanded {
+            self.turn_started.set(Some(now));
+        }
+        let progress = match self.turn_started.get() {
+            Some(start) => ((now - start) / CHEVRON_ANIMATION_SECONDS).clamp(0.0, 1.0),
+            None => 1.0,
+        };
+        if progress < 1.0 {
+            ctx.request_animation_frame();
+        } else {
+            self.turn_started.set(None);
+        }
+        let eased = progress * progress * (3.0 - 2.0 * progress);
+        if self.expanded {
+            eased
+        } else {
+            1.0 - eased
+        }
+    }
+}
+
+impl Widget for Collapsible {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        if !self.expanded {
+            // A measured leaf of just the header
+            return Style::default();
+        }
+        Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            padding: taffy::Rect {
+                left: zero(),
+                right: zero(),
+                top: length(theme.resolve(&self.style).header_height),
+                bottom: zero(),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let theme = ctx.theme();
+        let style = theme.resolve(&self.style);
+        let scale = ctx.scale_factor;
+        let header = Self::header_rect(ctx.bounds(), scale, &style);
+
+        let background = if self.hovered {
+            style.header_hover_background
+        } else {
+            style.header_background
+        };
+        ctx.fill_rounded_rect(header, background, style.corner_radius);
+
+        // Chevron pointing right, turned a quarter to point down when expanded
+        let angle = self.chevron_turn(ctx) * FRAC_PI_2;
+        let (sin, cos) = angle.sin_cos();
+        let size = CHEVRON_SIZE * scale;
+        let center = (
+            header.x + style.padding * scale + size,
+            header.y + header.height / 2.0,
+        );
+        let point = |x: f32, y: f32| {
+            (
+                center.0 + (x * cos - y * sin) * size,
+                center.1 + (x * sin + y * cos) * size,
+            )
+        };
+        let stroke = 1.5 * scale;
+        let tip = point(0.5, 0.0);
+        ctx.draw_line(point(-0.5, -1.0), tip, stroke, style.chevron_color);
+        ctx.draw_line(tip, point(-0.5, 1.0), stroke, style.chevron_color);
+
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.text_color);
+        let text_x = center.0 + size + style.gap * scale;
+        let title = Rect::new(
+            text_x,
+            header.y,
+            header.x + header.width - text_x,
+            header.height,
+        );
+        ctx.draw_text_aligned(&self.title, &text_style, title, 0.0);
+
+        // Focus ring (scale offset for HiDPI), only for keyboard focus
+        if ctx.focus_visible() {
+            ctx.fill_bordered_rect(
+                header,
+                Color::TRANSPARENT,
+                style.corner_radius,
+                2.0,
+                theme.colors.focus_ring,
+            );
+        }
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let header = Self::header_rect(ctx.bounds(), 1.0, &ctx.theme().resolve(&self.style));
+        match event {
+            InputEvent::PointerMove { pos, .. } => {
+                let hovered = header.contains(*pos);
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    return EventResponse {
+                        repaint: true,
+                        ..Default::default()
+                    };
+                }
+                EventResponse::default()
+            }
+            InputEvent::PointerLeave if self.hovered => {
+                self.hovered = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerDown { pos, .. } if header.contains(*pos) => {
+                self.pressed = true;
+                EventResponse::capture()
+            }
+            InputEvent::PointerCancel { .. } if self.pressed => {
+                self.pressed = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                if !self.pressed {
+                    return EventResponse::default();
+                }
+                self.pressed = false;
+                if header.contains(*pos) {
+                    return EventResponse {
+                        release_pointer: true,
+                        ..self.toggle(ctx)
+                    };
+                }
+                EventResponse::release()
+            }
+            InputEvent::KeyDown { .. } => {
+                let activate = ActionMapper::new().is_action(event, StandardAction::Activate);
+                if ctx.has_focus() && activate {
+                    return self.toggle(ctx);
+                }
+                EventResponse::default()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        if self.expanded {
+            std::slice::from_ref(&self.content)
+        } else {
+            &[]
+        }
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        if self.expanded {
+            std::slice::from_mut(&mut self.content)
+        } else {
+            &mut []
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Button,
+            name: Some(self.title.clone()),
+            value: Some(
+                if self.expanded {
+                    "expanded"
+                } else {
+                    "collapsed"
+                }
+                .into(),
+            ),
+            focusable: true,
+            actions: vec![AccessibleAction::Click],
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let (text_width, _) = ctx.text.measure(&self.title, &text_style, None);
+        let chevron = CHEVRON_SIZE * 2.0;
+        let width = style.padding * 2.0 + chevron + style.gap + text_width;
+        Some((width, style.header_height))
+    }
+}
@@ -1,6 +1,9 @@
 //! Container widget for laying out children.
 
-use crate::{EventContext, EventResponse, PaintContext, Widget};
+use crate::{
+    AccessibleInfo, AccessibleRole, ColorToken, EventContext, EventResponse, PaintContext, Theme,
+    ThemeMode, Widget,
+};
 use spark_core::Color;
 use spark_input::InputEvent;
 use spark_layout::WidgetId;
@@ -12,9 +15,17 @@ pub struct Container {
     children: Vec<Box<dyn Widget>>,
     style: Style,
     background: Option<Color>,
+    background_token: Option<ColorToken>,
     corner_radius: f32,
     border_width: f32,
     border_color: Color,
+    elevation: f32,
+    /// Shadow color override; otherwise black, stronger in dark mode.
+    shadow_color: Option<Color>,
+    accessibility_label: Option<String>,
+    accessibility_description: Option<String>,
+    accessibility_hidden: bool,
+    role: AccessibleRole,
 }
 
 impl Default for Container {
@@ -35,9 +46,16 @@ impl Container {
                 ..Default::default()
             },
             background: None,
+            background_token: None,
             corner_radius: 0.0,
             border_width: 0.0,
             border_color: Color::TRANSPARENT,
+            elevation: 0.0,
+            shadow_color: None,
+            accessibility_label: None,
+            accessibility_description: None,
+            accessibility_hidden: false,
+            role: AccessibleRole::GenericContainer,
         }
     }
 
@@ -105,6 +123,14 @@ impl Container {
     /// Set the background color.
     pub fn background(mut self, color: Color) -> Self {
         self.background = Some(color);
+        self.background_token = None;
+        self
+    }
+
+    /// Set the background color from a theme token (follows theme switches).
+    pub fn background_token(mut self, token: ColorToken) -> Self {
+        self.background = None;
+        self.background_token = Some(token);
         self
     }
 
@@ -121,6 +147,20 @@ impl Container {
         self
     }
 
+    /// Raise the container off the page with a soft drop shadow.
+    ///
+    /// Higher elevations cast a wider, further offset shadow; 0 removes it.
+    pub fn elevation(mut self, elevation: f32) -> Self {
+        self.elevation = elevation.max(0.0);
+        self
+    }
+
+    /// Set the drop shadow color, including its opacity.
+    pub fn shadow_color(mut self, color: Color) -> Self {
+        self.shadow_color = Some(color);
+        self
+    }
+
     /// Set alignment.
     pub fn align_items(mut self, align: AlignItems) -> Self {
         self.style.align_items = Some(align);
@@ -239,6 +279,46 @@ impl Container {
         self.style.flex_wrap = taffy::FlexWrap::Wrap;
         self
     }
+
+    /// Take the container out of flow and place it at (`left`, `top`) in its parent.
+    ///
+    /// Use this for overlays such as popups and modals, which paint over
+    /// their earlier siblings.
+    pub fn absolute(mut self, left: f32, top: f32) -> Self {
+        self.style.position = Position::Absolute;
+        self.style.inset = taffy::Rect {
+            left: length(left),
+            top: length(top),
+            right: auto(),
+            bottom: auto(),
+        };
+        self
+    }
+
+    /// Set the label announced by screen readers.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
+    /// Set extra detail screen readers announce after the label.
+    pub fn accessibility_description(mut self, description: impl Into<String>) -> Self {
+        self.accessibility_description = Some(description.into());
+        self
+    }
+
+    /// Hide the container and its children from screen readers, e.g. a
+    /// color swatch or other decoration.
+    pub fn accessibility_hidden(mut self, hidden: bool) -> Self {
+        self.accessibility_hidden = hidden;
+        self
+    }
+
+    /// Override the accessibility role (e.g. `List` for a column of items).
+    pub fn role(mut self, role: AccessibleRole) -> Self {
+        self.role = role;
+        self
+    }
 }
 
 impl Widget for Container {
@@ -250,15 +330,32 @@ impl Widget for Container {
         self.id = id;
     }
 
-    fn style(&self) -> Style {
+    fn style(&self, _theme: &Theme) -> Style {
         self.style.clone()
     }
 
     fn paint(&self, ctx: &mut PaintContext) {
         let bounds = ctx.bounds();
 
+        if self.elevation > 0.0 {
+            let color = self.shadow_color.unwrap_or_else(|| {
+                let alpha = match ctx.theme().mode {
+                    ThemeMode::Light => 0.25,
+                    ThemeMode::Dark => 0.5,
+                };
+                Color::rgba(0.0, 0.0, 0.0, alpha)
+            });
+            // Light comes from above, so the shadow falls below the container
+            let mut shadow = bounds;
+            shadow.y += self.elevation * 0.5 * ctx.scale_factor;
+            ctx.draw_shadow(shadow, color, self.corner_radius, self.elevation * 2.0);
+        }
+
         // Draw background
-        if let Some(bg) = self.background {
+        let background = self
+            .background
+            .or_else(|| self.background_token.map(|t| ctx.theme().color(t)));
+        if let Some(bg) = background {
             if self.border_width > 0.0 {
                 ctx.fill_bordered_rect(
                     bounds,
@@ -282,6 +379,16 @@ impl Widget for Container {
         EventResponse::default()
     }
 
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: self.role,
+            name: self.accessibility_label.clone(),
+            description: self.accessibility_description.clone(),
+            hidden: self.accessibility_hidden,
+            ..Default::default()
+        }
+    }
+
     fn children(&self) -> &[Box<dyn Widget>] {
         &self.children
     }
@@ -1,10 +1,12 @@
 //! Context types passed to widgets during layout, paint, and events.
 
+use crate::{AnnouncementQueue, ClipboardService, Politeness, Signal, SignalRuntime, Theme};
 use spark_core::{Color, GlyphInstance, Rect};
 use spark_input::FocusManager;
 use spark_layout::{ComputedLayout, LayoutTree, WidgetId};
-use spark_render::DrawList;
+use spark_render::{DrawList, ImageHandle};
 use spark_text::{TextStyle, TextSystem};
+use std::cell::Cell;
 use wgpu::{Device, Queue};
 
 /// Context for layout measurement.
@@ -15,6 +17,10 @@ pub struct LayoutContext<'a> {
     pub max_width: Option<f32>,
     /// Available height constraint.
     pub max_height: Option<f32>,
+    /// Native view points per layout unit (1.0 unless the scale factor is overridden).
+    pub native_point_scale: f32,
+    /// The active theme.
+    pub theme: &'a Theme,
 }
 
 impl<'a> LayoutContext<'a> {
@@ -46,9 +52,22 @@ pub struct PaintContext<'a> {
     pub queue: &'a Queue,
     /// Elapsed time in seconds (for animations like cursor blinking).
     pub elapsed_time: f32,
+    /// The active theme.
+    pub theme: &'a Theme,
+    /// Set when a widget wants to be painted again on the next frame.
+    pub animation_requested: &'a Cell<bool>,
+    /// The app's signal runtime, for [`watch`](Self::watch).
+    pub signals: &'a SignalRuntime,
+    /// The app's queue of screen reader announcements.
+    pub announcements: &'a AnnouncementQueue,
 }
 
 impl<'a> PaintContext<'a> {
+    /// Get the active theme.
+    pub fn theme(&self) -> &'a Theme {
+        self.theme
+    }
+
     /// Get the widget's bounds.
     pub fn bounds(&self) -> Rect {
         self.layout.bounds
@@ -59,6 +78,30 @@ impl<'a> PaintContext<'a> {
         self.focus.has_focus(self.widget_id)
     }
 
+    /// Paint again on the next frame, while an animation is running.
+    pub fn request_animation_frame(&self) {
+        self.animation_requested.set(true);
+    }
+
+    /// Read `signal` and repaint this widget whenever it changes.
+    pub fn watch<T: Clone + Send + 'static>(&self, signal: &Signal<T>) -> T {
+        signal.subscribe(self.widget_id, self.signals);
+        signal.get()
+    }
+
+    /// Have screen readers speak `text` without moving focus. See
+    /// [`AnnouncementQueue::announce`].
+    pub fn announce(&self, text: impl Into<String>, politeness: Politeness) {
+        self.announcements.announce(text, politeness);
+    }
+
+    /// Check if this widget has focus that should show a focus ring.
+    ///
+    /// False when focus came from a pointer click.
+    pub fn focus_visible(&self) -> bool {
+        self.has_focus() && self.focus.focus_visible()
+    }
+
     /// Draw a filled rectangle.
     /// Bounds are in physical pixels.
     pub fn fill_rect(&mut self, bounds: Rect, color: Color) {
@@ -90,6 +133,67 @@ impl<'a> PaintContext<'a> {
             .bordered_rect(bounds, color, scaled_radius, scaled_border, border_color);
     }
 
+    /// Draw a soft shadow of a rounded rectangle, fading out `blur` past its
+    /// edges. Bounds are in physical pixels; radius and blur are scaled for HiDPI.
+    pub fn draw_shadow(&mut self, bounds: Rect, color: Color, radius: f32, blur: f32) {
+        let (radius, blur) = (radius * self.scale_factor, blur * self.scale_factor);
+        self.draw_list.shadow(bounds, color, radius, blur);
+    }
+
+    /// Draw an arc of the circle inscribed in `bounds`, with round ends.
+    /// Bounds are in physical pixels; thickness is scaled for HiDPI. Angles
+    /// are in radians, clockwise from 12 o'clock.
+    pub fn draw_arc(&mut self, bounds: Rect, color: Color, thickness: f32, start: f32, sweep: f32) {
+        let thickness = thickness * self.scale_factor;
+        self.draw_list.arc(bounds, color, thickness, start, sweep);
+    }
+
+    /// Draw a straight line from `from` to `to` with round ends.
+    ///
+    /// Axis-aligned lines are one rectangle snapped to pixel edges; others
+    /// are a run of overlapping round dabs, which suits short strokes such as
+    /// check marks and chevrons. Coordinates and `width` are in physical pixels.
+    pub fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        let radius = width / 2.0 / self.scale_factor;
+        if from.0 == to.0 || from.1 == to.1 {
+            let rect = if from.0 == to.0 {
+                let x = snap_center(from.0, width) - width / 2.0;
+                let (top, bottom) = (from.1.min(to.1), from.1.max(to.1));
+                let height = (bottom - top + width).round();
+                Rect::new(x, (top - width / 2.0).round(), width, height)
+            } else {
+                let y = snap_center(from.1, width) - width / 2.0;
+                let (left, right) = (from.0.min(to.0), from.0.max(to.0));
+                let length = (right - left + width).round();
+                Rect::new((left - width / 2.0).round(), y, length, width)
+            };
+            self.fill_rounded_rect(rect, color, radius);
+            return;
+        }
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = (dx.hypot(dy) / (width * 0.25)).ceil().max(1.0) as usize;
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            let x = from.0 + dx * t - width / 2.0;
+            let y = from.1 + dy * t - width / 2.0;
+            self.fill_rounded_rect(Rect::new(x, y, width, width), color, radius);
+        }
+    }
+
+    /// Draw the `uv` region (0-1 texture coordinates) of `image` into `bounds`.
+    /// Bounds are in physical pixels; the radius is scaled for HiDPI.
+    pub fn draw_image(
+        &mut self,
+        bounds: Rect,
+        image: &ImageHandle,
+        uv: Rect,
+        tint: Color,
+        radius: f32,
+    ) {
+        let scaled_radius = radius * self.scale_factor;
+        self.draw_list.image(bounds, image, uv, tint, scaled_radius);
+    }
+
     /// Push a clip rectangle.
     pub fn push_clip(&mut self, bounds: Rect) {
         self.draw_list.push_clip(bounds);
@@ -111,6 +215,12 @@ impl<'a> PaintContext<'a> {
         self.draw_list.pop_translation();
     }
 
+    /// The total translation applied to what is drawn now (physical pixels),
+    /// e.g. the offset of the enclosing scroll views.
+    pub fn translation(&self) -> (f32, f32) {
+        self.draw_list.translation()
+    }
+
     /// Draw text at the specified position.
     ///
     /// The text is shaped using the provided style and drawn with its
@@ -191,6 +301,29 @@ impl<'a> PaintContext<'a> {
     }
 }
 
+/// Where to center a stroke of `width` pixels near `center` so its edges
+/// land on pixel boundaries: odd widths on a pixel's middle, even widths
+/// between pixels.
+pub(crate) fn snap_center(center: f32, width: f32) -> f32 {
+    if width as i32 % 2 == 1 {
+        center.floor() + 0.5
+    } else {
+        center.round()
+    }
+}
+
+/// Where an event is on its way through the widget tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventPhase {
+    /// At the widget the event is aimed at: the topmost widget under the
+    /// pointer, the focused widget, or the widget holding the pointer capture.
+    #[default]
+    Target,
+    /// At an ancestor of the target, after the widgets below it left the
+    /// event unhandled.
+    Bubble,
+}
+
 /// Context for handling events.
 pub struct EventContext<'a> {
     /// The computed layout for this widget.
@@ -203,9 +336,24 @@ pub struct EventContext<'a> {
     pub widget_id: WidgetId,
     /// Whether this widget has pointer capture.
     pub has_capture: bool,
+    /// The widget the event is aimed at; `widget_id` unless bubbling.
+    pub target: WidgetId,
+    /// Whether the event is at its target or bubbling through an ancestor.
+    pub phase: EventPhase,
+    /// The active theme.
+    pub theme: &'a Theme,
+    /// The app's clipboard.
+    pub clipboard: &'a mut dyn ClipboardService,
+    /// The app's queue of screen reader announcements.
+    pub announcements: &'a AnnouncementQueue,
 }
 
 impl<'a> EventContext<'a> {
+    /// Get the active theme.
+    pub fn theme(&self) -> &'a Theme {
+        self.theme
+    }
+
     /// Get the widget's bounds.
     pub fn bounds(&self) -> Rect {
         self.layout.bounds
@@ -216,6 +364,17 @@ impl<'a> EventContext<'a> {
         self.focus.has_focus(self.widget_id)
     }
 
+    /// The widget the event is aimed at, e.g. the child that was clicked
+    /// while the event bubbles through its container.
+    pub fn target_id(&self) -> WidgetId {
+        self.target
+    }
+
+    /// Whether the event is at its target or bubbling up from a descendant.
+    pub fn phase(&self) -> EventPhase {
+        self.phase
+    }
+
     /// Request keyboard focus for this widget.
     pub fn request_focus(&mut self) {
         self.focus.set_focus(self.widget_id);
@@ -233,6 +392,17 @@ impl<'a> EventContext<'a> {
         self.layout.bounds.contains(pos)
     }
 
+    /// The app's clipboard, e.g. for a "copy link" button.
+    pub fn clipboard(&mut self) -> &mut dyn ClipboardService {
+        &mut *self.clipboard
+    }
+
+    /// Have screen readers speak `text` without moving focus, e.g. a
+    /// validation error. See [`AnnouncementQueue::announce`].
+    pub fn announce(&self, text: impl Into<String>, politeness: Politeness) {
+        self.announcements.announce(text, politeness);
+    }
+
     /// Convert a point to local coordinates.
     pub fn to_local(&self, pos: glam::Vec2) -> glam::Vec2 {
         glam::Vec2::new(
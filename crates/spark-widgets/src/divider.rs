@@ -0,0 +1,148 @@
+//! Divider line widget.
+
+use crate::{LayoutContext, PaintContext, Theme, Widget};
+use spark_core::{Color, Rect};
+use spark_layout::WidgetId;
+use taffy::prelude::*;
+
+/// A thin line separating content, horizontal unless made vertical.
+///
+/// Lines span the cross axis of their container, minus the inset at each end.
+pub struct Divider {
+    id: WidgetId,
+    vertical: bool,
+    /// Color override; otherwise the theme's border color.
+    color: Option<Color>,
+    thickness: f32,
+    inset: f32,
+}
+
+impl Divider {
+    /// Create a horizontal divider.
+    pub fn horizontal() -> Self {
+        Self {
+            id: WidgetId::default(),
+            vertical: false,
+            color: None,
+            thickness: 1.0,
+            inset: 0.0,
+        }
+    }
+
+    /// Create a vertical divider, for separating items in a row.
+    pub fn vertical() -> Self {
+        Self {
+            vertical: true,
+            ..Self::horizontal()
+        }
+    }
+
+    /// Set the line color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the line thickness in logical pixels.
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Leave space at both ends of the line, in logical pixels.
+    pub fn inset(mut self, inset: f32) -> Self {
+        self.inset = inset;
+        self
+    }
+}
+
+impl Default for Divider {
+    fn default() -> Self {
+        Self::horizontal()
+    }
+}
+
+/// The physical-pixel line drawn in `bounds`.
+///
+/// The thickness is rounded to whole pixels, never below one, so hairlines
+/// neither vanish nor blur across two pixel rows.
+fn line_rect(bounds: Rect, vertical: bool, thickness: f32, inset: f32, scale: f32) -> Rect {
+    let thickness = (thickness * scale).round().max(1.0);
+    let inset = inset * scale;
+    if vertical {
+        let x = (bounds.x + (bounds.width - thickness) / 2.0).round();
+        let height = (bounds.height - inset * 2.0).max(0.0);
+        Rect::new(x, bounds.y + inset, thickness, height)
+    } else {
+        let y = (bounds.y + (bounds.height - thickness) / 2.0).round();
+        let width = (bounds.width - inset * 2.0).max(0.0);
+        Rect::new(bounds.x + inset, y, width, thickness)
+    }
+}
+
+impl Widget for Divider {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        let (width, height) = if self.vertical {
+            (length(self.thickness), auto())
+        } else {
+            (auto(), length(self.thickness))
+        };
+        Style {
+            size: Size { width, height },
+            align_self: Some(AlignSelf::Stretch),
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let color = self.color.unwrap_or(ctx.theme().colors.border);
+        let line = line_rect(
+            ctx.bounds(),
+            self.vertical,
+            self.thickness,
+            self.inset,
+            ctx.scale_factor,
+        );
+        ctx.fill_rect(line, color);
+    }
+
+    fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        if self.vertical {
+            Some((self.thickness, 0.0))
+        } else {
+            Some((0.0, self.thickness))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_thickness_scales_to_whole_pixels() {
+        let bounds = Rect::new(0.0, 10.0, 200.0, 2.0);
+        let line = line_rect(bounds, false, 1.0, 0.0, 2.0);
+        assert_eq!(line, Rect::new(0.0, 10.0, 200.0, 2.0));
+        // A hairline at 1.5x still covers one whole pixel row
+        let line = line_rect(Rect::new(0.0, 10.0, 200.0, 1.5), false, 0.5, 0.0, 1.5);
+        assert_eq!(line.height, 1.0);
+        assert_eq!(line.y, line.y.round());
+    }
+
+    #[test]
+    fn test_inset_shortens_both_ends() {
+        let bounds = Rect::new(0.0, 0.0, 2.0, 100.0);
+        let line = line_rect(bounds, true, 1.0, 8.0, 2.0);
+        assert_eq!(line, Rect::new(0.0, 16.0, 2.0, 68.0));
+    }
+}
@@ -0,0 +1,231 @@
+//! File drop zone widget.
+
+use crate::{
+    AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext, PaintContext,
+    Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{DroppedFile, InputEvent};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use taffy::prelude::*;
+
+/// Style configuration for a drop zone.
+#[derive(Clone, Debug)]
+pub struct DropZoneStyle {
+    pub background: Color,
+    /// Background while files are dragged over the zone.
+    pub hover_background: Color,
+    pub border_color: Color,
+    /// Border color while files are dragged over the zone.
+    pub hover_border_color: Color,
+    pub text_color: Color,
+    pub border_width: f32,
+    /// Length of each border dash.
+    pub dash_length: f32,
+    /// Space between border dashes.
+    pub dash_gap: f32,
+    pub corner_radius: f32,
+    pub padding: f32,
+    pub font_size: f32,
+}
+
+impl Default for DropZoneStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for DropZoneStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            background: colors.surface,
+            hover_background: colors.selection,
+            border_color: colors.border,
+            hover_border_color: colors.primary,
+            text_color: colors.text_muted,
+            border_width: 2.0,
+            dash_length: 8.0,
+            dash_gap: 6.0,
+            corner_radius: theme.radius.md,
+            padding: theme.spacing.xl,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// Where the dashes go along an edge of `length`, as (offset, length)
+/// pairs. The gaps stretch a little so that both ends get a whole dash.
+fn dashes(length: f32, dash: f32, gap: f32) -> Vec<(f32, f32)> {
+    if length <= dash || dash <= 0.0 {
+        return vec![(0.0, length.max(0.0))];
+    }
+    let count = ((length + gap) / (dash + gap)).floor().max(2.0);
+    let step = (length - dash) / (count - 1.0);
+    (0..count as usize)
+        .map(|i| (i as f32 * step, dash))
+        .collect()
+}
+
+/// An area that files can be dragged onto, outlined with a dashed border
+/// that lights up while files hover over it.
+///
+/// Dropped files are handed to the [`DropZone::on_drop`] handler. On the
+/// web their data is read into memory, since browsers don't expose paths.
+pub struct DropZone {
+    id: WidgetId,
+    label: String,
+    hovered: bool,
+    style: Option<DropZoneStyle>,
+    on_drop: Option<Box<dyn FnMut(Vec<DroppedFile>) + Send + Sync>>,
+}
+
+impl DropZone {
+    /// Create a drop zone showing `label`, e.g. "Drop files here".
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            label: label.into(),
+            hovered: false,
+            style: None,
+            on_drop: None,
+        }
+    }
+
+    /// Set the handler called with the files dropped on the zone.
+    pub fn on_drop(
+        mut self,
+        handler: impl FnMut(Vec<DroppedFile>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_drop = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the drop zone style.
+    pub fn with_style(mut self, style: DropZoneStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    fn set_hovered(&mut self, hovered: bool) -> EventResponse {
+        let changed = hovered != self.hovered;
+        self.hovered = hovered;
+        EventResponse {
+            repaint: changed,
+            ..Default::default()
+        }
+    }
+}
+
+impl Widget for DropZone {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let style = theme.resolve(&self.style);
+        Style {
+            padding: taffy::Rect {
+                left: length(style.padding),
+                right: length(style.padding),
+                top: length(style.padding),
+                bottom: length(style.padding),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let scale = ctx.scale_factor;
+        let style = ctx.theme().resolve(&self.style);
+        let (background, border) = if self.hovered {
+            (style.hover_background, style.hover_border_color)
+        } else {
+            (style.background, style.border_color)
+        };
+        ctx.fill_rounded_rect(bounds, background, style.corner_radius);
+
+        // Dashes run along the straight part of each edge, between the corners
+        let width = style.border_width * scale;
+        let inset = style.corner_radius * scale;
+        let (dash, gap) = (style.dash_length * scale, style.dash_gap * scale);
+        let (right, bottom) = (
+            bounds.x + bounds.width - width,
+            bounds.y + bounds.height - width,
+        );
+        for (offset, length) in dashes(bounds.width - inset * 2.0, dash, gap) {
+            let x = bounds.x + inset + offset;
+            ctx.fill_rect(Rect::new(x, bounds.y, length, width), border);
+            ctx.fill_rect(Rect::new(x, bottom, length, width), border);
+        }
+        for (offset, length) in dashes(bounds.height - inset * 2.0, dash, gap) {
+            let y = bounds.y + inset + offset;
+            ctx.fill_rect(Rect::new(bounds.x, y, width, length), border);
+            ctx.fill_rect(Rect::new(right, y, width, length), border);
+        }
+
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.text_color);
+        ctx.draw_text_centered(&self.label, &text_style, bounds);
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        match event {
+            InputEvent::FileHover { pos } => self.set_hovered(ctx.contains(*pos)),
+            InputEvent::FileHoverCancelled => self.set_hovered(false),
+            InputEvent::FileDrop { pos, files } => {
+                let response = self.set_hovered(false);
+                if !ctx.contains(*pos) {
+                    return response;
+                }
+                if let Some(handler) = &mut self.on_drop {
+                    handler(files.clone());
+                }
+                EventResponse::handled()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::GenericContainer,
+            name: Some(self.label.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let text_style = TextStyle::default().with_size(style.font_size);
+        Some(ctx.text.measure(&self.label, &text_style, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashes_fill_the_edge_with_whole_dashes() {
+        let dashes = dashes(100.0, 10.0, 5.0);
+        assert_eq!(dashes.first(), Some(&(0.0, 10.0)));
+        let (last, length) = *dashes.last().unwrap();
+        assert_eq!(last + length, 100.0);
+        // 7 dashes and 6 slightly wider gaps
+        assert_eq!(dashes.len(), 7);
+    }
+
+    #[test]
+    fn test_dashes_on_a_short_edge() {
+        assert_eq!(dashes(6.0, 10.0, 5.0), vec![(0.0, 6.0)]);
+        assert_eq!(dashes(-2.0, 10.0, 5.0), vec![(0.0, 0.0)]);
+    }
+}
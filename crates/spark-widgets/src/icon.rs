@@ -0,0 +1,426 @@
+//! Vector icon widget.
+
+use crate::{AccessibleInfo, AccessibleRole, LayoutContext, PaintContext, Theme, Widget};
+use spark_core::{Color, Rect};
+use spark_layout::WidgetId;
+use std::borrow::Cow;
+use taffy::prelude::*;
+
+/// Width and height of the space icon segments are defined in.
+const ICON_GRID: f32 = 24.0;
+/// Stroke width of the built-in icons, in grid units.
+const STROKE: f32 = 2.0;
+
+/// One piece of an icon, in a 24×24 unit space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IconSegment {
+    /// A stroke with round ends.
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        width: f32,
+    },
+    /// A filled rounded rectangle; a radius of half its size makes a dot.
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radius: f32,
+    },
+    /// An outlined rounded rectangle; a radius of half its size makes a ring.
+    Outline {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radius: f32,
+        stroke: f32,
+    },
+}
+
+impl IconSegment {
+    /// A stroke of the built-in icons' width.
+    pub const fn line(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self::Line {
+            from: (x1, y1),
+            to: (x2, y2),
+            width: STROKE,
+        }
+    }
+
+    /// A ring of the built-in icons' stroke width, centered on `(x, y)`.
+    pub const fn circle(x: f32, y: f32, radius: f32) -> Self {
+        Self::Outline {
+            x: x - radius,
+            y: y - radius,
+            width: radius * 2.0,
+            height: radius * 2.0,
+            radius,
+            stroke: STROKE,
+        }
+    }
+}
+
+/// Icons built into the library.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IconName {
+    ChevronLeft,
+    ChevronRight,
+    ChevronUp,
+    ChevronDown,
+    Close,
+    Check,
+    Search,
+    Plus,
+    Minus,
+    Gear,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+}
+
+impl IconName {
+    /// The segments that draw this icon.
+    pub fn segments(self) -> &'static [IconSegment] {
+        match self {
+            Self::ChevronLeft => CHEVRON_LEFT,
+            Self::ChevronRight => CHEVRON_RIGHT,
+            Self::ChevronUp => CHEVRON_UP,
+            Self::ChevronDown => CHEVRON_DOWN,
+            Self::Close => CLOSE,
+            Self::Check => CHECK,
+            Self::Search => SEARCH,
+            Self::Plus => PLUS,
+            Self::Minus => MINUS,
+            Self::Gear => GEAR,
+            Self::ArrowLeft => ARROW_LEFT,
+            Self::ArrowRight => ARROW_RIGHT,
+            Self::ArrowUp => ARROW_UP,
+            Self::ArrowDown => ARROW_DOWN,
+        }
+    }
+
+    /// Paint this icon into `bounds` (physical pixels) as part of another
+    /// widget, such as a separator or a menu check mark.
+    pub fn paint(self, ctx: &mut PaintContext, bounds: Rect, color: Color) {
+        paint_segments(ctx, self.segments(), bounds, color);
+    }
+}
+
+const CHEVRON_LEFT: &[IconSegment] = &[
+    IconSegment::line(15.0, 6.0, 9.0, 12.0),
+    IconSegment::line(9.0, 12.0, 15.0, 18.0),
+];
+const CHEVRON_RIGHT: &[IconSegment] = &[
+    IconSegment::line(9.0, 6.0, 15.0, 12.0),
+    IconSegment::line(15.0, 12.0, 9.0, 18.0),
+];
+const CHEVRON_UP: &[IconSegment] = &[
+    IconSegment::line(6.0, 15.0, 12.0, 9.0),
+    IconSegment::line(12.0, 9.0, 18.0, 15.0),
+];
+const CHEVRON_DOWN: &[IconSegment] = &[
+    IconSegment::line(6.0, 9.0, 12.0, 15.0),
+    IconSegment::line(12.0, 15.0, 18.0, 9.0),
+];
+const CLOSE: &[IconSegment] = &[
+    IconSegment::line(6.0, 6.0, 18.0, 18.0),
+    IconSegment::line(18.0, 6.0, 6.0, 18.0),
+];
+const CHECK: &[IconSegment] = &[
+    IconSegment::line(5.0, 12.5, 10.0, 17.5),
+    IconSegment::line(10.0, 17.5, 19.0, 7.0),
+];
+const SEARCH: &[IconSegment] = &[
+    IconSegment::circle(10.5, 10.5, 6.5),
+    IconSegment::line(15.5, 15.5, 20.0, 20.0),
+];
+const PLUS: &[IconSegment] = &[
+    IconSegment::line(12.0, 5.0, 12.0, 19.0),
+    IconSegment::line(5.0, 12.0, 19.0, 12.0),
+];
+const MINUS: &[IconSegment] = &[IconSegment::line(5.0, 12.0, 19.0, 12.0)];
+const ARROW_LEFT: &[IconSegment] = &[
+    IconSegment::line(19.0, 12.0, 5.0, 12.0),
+    IconSegment::line(11.0, 6.0, 5.0, 12.0),
+    IconSegment::line(5.0, 12.0, 11.0, 18.0),
+];
+const ARROW_RIGHT: &[IconSegment] = &[
+    IconSegment::line(5.0, 12.0, 19.0, 12.0),
+    IconSegment::line(13.0, 6.0, 19.0, 12.0),
+    IconSegment::line(19.0, 12.0, 13.0, 18.0),
+];
+const ARROW_UP: &[IconSegment] = &[
+    IconSegment::line(12.0, 19.0, 12.0, 5.0),
+    IconSegment::line(6.0, 11.0, 12.0, 5.0),
+    IconSegment::line(12.0, 5.0, 18.0, 11.0),
+];
+const ARROW_DOWN: &[IconSegment] = &[
+    IconSegment::line(12.0, 5.0, 12.0, 19.0),
+    IconSegment::line(6.0, 13.0, 12.0, 19.0),
+    IconSegment::line(12.0, 19.0, 18.0, 13.0),
+];
+
+/// A ring with eight teeth around a hub.
+const GEAR: &[IconSegment] = &[
+    IconSegment::circle(12.0, 12.0, 6.5),
+    IconSegment::circle(12.0, 12.0, 2.5),
+    tooth(12.0, 2.5, 12.0, 5.0),
+    tooth(12.0, 19.0, 12.0, 21.5),
+    tooth(2.5, 12.0, 5.0, 12.0),
+    tooth(19.0, 12.0, 21.5, 12.0),
+    tooth(5.3, 5.3, 7.1, 7.1),
+    tooth(18.7, 5.3, 16.9, 7.1),
+    tooth(5.3, 18.7, 7.1, 16.9),
+    tooth(18.7, 18.7, 16.9, 16.9),
+];
+
+/// A gear tooth, thicker than the regular stroke.
+const fn tooth(x1: f32, y1: f32, x2: f32, y2: f32) -> IconSegment {
+    IconSegment::Line {
+        from: (x1, y1),
+        to: (x2, y2),
+        width: 3.0,
+    }
+}
+
+/// A stroke width in whole physical pixels, never below one.
+fn pixel_width(width: f32) -> f32 {
+    width.round().max(1.0)
+}
+
+/// Paint icon segments, centered in `bounds` on a whole pixel.
+fn paint_segments(ctx: &mut PaintContext, segments: &[IconSegment], bounds: Rect, color: Color) {
+    let scale = ctx.scale_factor;
+
+    // Center the grid in the bounds, starting on a whole pixel
+    let side = bounds.width.min(bounds.height);
+    let unit = side / ICON_GRID;
+    let x0 = (bounds.x + (bounds.width - side) / 2.0).round();
+    let y0 = (bounds.y + (bounds.height - side) / 2.0).round();
+    let point = |(x, y): (f32, f32)| (x0 + x * unit, y0 + y * unit);
+    let rect = |x: f32, y: f32, width: f32, height: f32| {
+        Rect::new(
+            (x0 + x * unit).round(),
+            (y0 + y * unit).round(),
+            (width * unit).round(),
+            (height * unit).round(),
+        )
+    };
+
+    for segment in segments {
+        match *segment {
+            IconSegment::Line { from, to, width } => {
+                let width = pixel_width(width * unit);
+                ctx.draw_line(point(from), point(to), width, color);
+            }
+            IconSegment::Rect {
+                x,
+                y,
+                width,
+                height,
+                radius,
+            } => {
+                let radius = radius * unit / scale;
+                ctx.fill_rounded_rect(rect(x, y, width, height), color, radius);
+            }
+            IconSegment::Outline {
+                x,
+                y,
+                width,
+                height,
+                radius,
+                stroke,
+            } => {
+                let stroke = pixel_width(stroke * unit) / scale;
+                let radius = radius * unit / scale;
+                let bounds = rect(x, y, width, height);
+                ctx.fill_bordered_rect(bounds, Color::TRANSPARENT, radius, stroke, color);
+            }
+        }
+    }
+}
+
+/// A small vector icon, from the built-in set or custom segments.
+///
+/// Icons scale to any size; straight strokes and outlines are snapped to
+/// whole physical pixels so they stay crisp.
+pub struct Icon {
+    id: WidgetId,
+    segments: Cow<'static, [IconSegment]>,
+    size: f32,
+    /// Color override; otherwise the theme's text color.
+    color: Option<Color>,
+    label: Option<String>,
+    description: Option<String>,
+    hidden: bool,
+}
+
+impl Icon {
+    /// Create a built-in icon.
+    pub fn new(name: IconName) -> Self {
+        Self::from_segments(Cow::Borrowed(name.segments()))
+    }
+
+    /// Create an icon from segments in a 24×24 unit space.
+    pub fn custom(segments: impl Into<Vec<IconSegment>>) -> Self {
+        Self::from_segments(Cow::Owned(segments.into()))
+    }
+
+    fn from_segments(segments: Cow<'static, [IconSegment]>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            segments,
+            size: 20.0,
+            color: None,
+            label: None,
+            description: None,
+            hidden: false,
+        }
+    }
+
+    /// Set the width and height in logical pixels.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the icon color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the name announced by screen readers; unlabeled icons are
+    /// treated as decoration and hidden from them.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set extra detail screen readers announce after the name.
+    pub fn accessibility_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Hide a labeled icon from screen readers anyway, e.g. next to text
+    /// that says the same.
+    pub fn accessibility_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+}
+
+impl Widget for Icon {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style {
+            size: Size {
+                width: length(self.size),
+                height: length(self.size),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let color = self.color.unwrap_or(ctx.theme().colors.text);
+        paint_segments(ctx, &self.segments, bounds, color);
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        match &self.label {
+            Some(label) if !self.hidden => AccessibleInfo {
+                role: AccessibleRole::Image,
+                name: Some(label.clone()),
+                description: self.description.clone(),
+                ..Default::default()
+            },
+            _ => AccessibleInfo {
+                hidden: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        Some((self.size, self.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::snap_center;
+
+    #[test]
+    fn test_strokes_snap_to_pixel_edges() {
+        // Odd widths center on a pixel, even widths between two
+        assert_eq!(snap_center(10.3, 1.0), 10.5);
+        assert_eq!(snap_center(10.3, 3.0) - 1.5, 9.0);
+        assert_eq!(snap_center(10.3, 2.0), 10.0);
+        assert_eq!(snap_center(10.6, 4.0) - 2.0, 9.0);
+        // Thin strokes at small sizes still cover a pixel
+        assert_eq!(pixel_width(2.0 * 16.0 / ICON_GRID), 1.0);
+        assert_eq!(pixel_width(0.2), 1.0);
+        assert_eq!(pixel_width(2.0 * 20.0 * 2.0 / ICON_GRID), 3.0);
+    }
+
+    #[test]
+    fn test_builtin_icons_fit_the_grid() {
+        use IconName::*;
+        let names = [
+            ChevronLeft,
+            ChevronRight,
+            ChevronUp,
+            ChevronDown,
+            Close,
+            Check,
+            Search,
+            Plus,
+            Minus,
+            Gear,
+            ArrowLeft,
+            ArrowRight,
+            ArrowUp,
+            ArrowDown,
+        ];
+        let on_grid = |v: f32| (0.0..=ICON_GRID).contains(&v);
+        for name in names {
+            for segment in name.segments() {
+                let inside = match *segment {
+                    IconSegment::Line { from, to, .. } => {
+                        on_grid(from.0) && on_grid(from.1) && on_grid(to.0) && on_grid(to.1)
+                    }
+                    IconSegment::Rect {
+                        x,
+                        y,
+                        width,
+                        height,
+                        ..
+                    }
+                    | IconSegment::Outline {
+                        x,
+                        y,
+                        width,
+                        height,
+                        ..
+                    } => on_grid(x) && on_grid(y) && on_grid(x + width) && on_grid(y + height),
+                };
+                assert!(inside, "{name:?} leaves the grid");
+            }
+        }
+    }
+}
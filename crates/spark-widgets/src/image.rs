@@ -0,0 +1,192 @@
+//! Image widget.
+
+use crate::{AccessibleInfo, AccessibleRole, LayoutContext, PaintContext, Widget};
+use spark_core::{Color, Rect};
+use spark_layout::WidgetId;
+use spark_render::ImageHandle;
+
+/// How an image is scaled into its bounds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageFit {
+    /// Show the whole image, centered, keeping its aspect ratio.
+    #[default]
+    Contain,
+    /// Fill the bounds, keeping the aspect ratio and cropping the overflow.
+    Cover,
+    /// Stretch the image to the bounds.
+    Fill,
+}
+
+/// Displays an image, sized to its pixel dimensions unless laid out otherwise.
+pub struct Image {
+    id: WidgetId,
+    image: Option<ImageHandle>,
+    fit: ImageFit,
+    corner_radius: f32,
+    tint: Color,
+    label: Option<String>,
+    description: Option<String>,
+    hidden: bool,
+}
+
+impl Image {
+    /// Show already decoded pixels.
+    pub fn new(image: ImageHandle) -> Self {
+        Self::from_handle(Some(image))
+    }
+
+    /// Decode a PNG or JPEG file.
+    ///
+    /// A file that fails to decode is logged and shows nothing.
+    #[cfg(feature = "image")]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let image = ImageHandle::decode(bytes)
+            .map_err(|err| log::warn!("failed to decode image: {err}"))
+            .ok();
+        Self::from_handle(image)
+    }
+
+    fn from_handle(image: Option<ImageHandle>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            image,
+            fit: ImageFit::default(),
+            corner_radius: 0.0,
+            tint: Color::WHITE,
+            label: None,
+            description: None,
+            hidden: false,
+        }
+    }
+
+    /// Set how the image is scaled into its bounds.
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Round the corners of the drawn image.
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    /// Multiply every pixel by `tint`, e.g. to recolor an icon.
+    pub fn tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Set the description announced by screen readers.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set longer detail screen readers announce after the label, e.g. what
+    /// a chart shows.
+    pub fn accessibility_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Hide a purely decorative image from screen readers.
+    pub fn accessibility_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// The displayed image, if it decoded.
+    pub fn image(&self) -> Option<&ImageHandle> {
+        self.image.as_ref()
+    }
+}
+
+/// Where an image of `size` pixels is drawn in `bounds`, and which part of it.
+pub(crate) fn fit_image(fit: ImageFit, size: (u32, u32), bounds: Rect) -> (Rect, Rect) {
+    let full = Rect::new(0.0, 0.0, 1.0, 1.0);
+    let (width, height) = (size.0 as f32, size.1 as f32);
+    if width <= 0.0 || height <= 0.0 {
+        return (bounds, full);
+    }
+    let scale_x = bounds.width / width;
+    let scale_y = bounds.height / height;
+    match fit {
+        ImageFit::Contain => {
+            let scale = scale_x.min(scale_y);
+            let (w, h) = (width * scale, height * scale);
+            let x = bounds.x + (bounds.width - w) / 2.0;
+            let y = bounds.y + (bounds.height - h) / 2.0;
+            (Rect::new(x, y, w, h), full)
+        }
+        ImageFit::Cover => {
+            // Show the centered part of the image that fills the bounds
+            let scale = scale_x.max(scale_y);
+            let uv_width = bounds.width / scale / width;
+            let uv_height = bounds.height / scale / height;
+            let uv = Rect::new(
+                (1.0 - uv_width) / 2.0,
+                (1.0 - uv_height) / 2.0,
+                uv_width,
+                uv_height,
+            );
+            (bounds, uv)
+        }
+        ImageFit::Fill => (bounds, full),
+    }
+}
+
+impl Widget for Image {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let Some(image) = &self.image else {
+            return;
+        };
+        let (rect, uv) = fit_image(self.fit, image.size(), ctx.bounds());
+        ctx.draw_image(rect, image, uv, self.tint, self.corner_radius);
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Image,
+            name: self.label.clone(),
+            description: self.description.clone(),
+            hidden: self.hidden,
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let (width, height) = self.image.as_ref().map_or((0, 0), ImageHandle::size);
+        Some((width as f32, height as f32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contain_letterboxes_and_cover_crops() {
+        let bounds = Rect::new(0.0, 0.0, 200.0, 100.0);
+        let full = Rect::new(0.0, 0.0, 1.0, 1.0);
+
+        let (rect, uv) = fit_image(ImageFit::Contain, (100, 100), bounds);
+        assert_eq!(rect, Rect::new(50.0, 0.0, 100.0, 100.0));
+        assert_eq!(uv, full);
+
+        let (rect, uv) = fit_image(ImageFit::Cover, (100, 100), bounds);
+        assert_eq!(rect, bounds);
+        assert_eq!(uv, Rect::new(0.0, 0.25, 1.0, 0.5));
+
+        let (rect, uv) = fit_image(ImageFit::Fill, (100, 100), bounds);
+        assert_eq!((rect, uv), (bounds, full));
+    }
+}
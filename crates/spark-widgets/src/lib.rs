@@ -1,20 +1,86 @@
 //! Spark Widgets - UI widget library.
 
+pub mod accessibility;
+mod avatar;
+mod badge;
+mod breadcrumb;
 mod button;
+mod canvas;
+mod checkbox;
+mod chip_input;
+mod clipboard;
+mod collapsible;
 mod container;
 mod context;
+mod divider;
+mod drop_zone;
+mod icon;
+mod image;
+#[cfg(feature = "markdown")]
+mod markdown;
+mod menu_bar;
+mod modal;
+mod progress_bar;
+mod progress_ring;
+mod radio;
 mod scroll;
+mod search_input;
+mod segmented_control;
+mod select;
+mod signal;
+mod spacer;
+mod split_pane;
+mod stack;
+mod switch;
+mod table;
 mod text;
 mod text_input;
+mod theme;
 mod widget;
 
+pub use accessibility::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleScroll, AccessibleValue,
+    Announcement, AnnouncementQueue, Politeness,
+};
+pub use avatar::{Avatar, AvatarStatus};
+pub use badge::{Badge, BadgeStyle};
+pub use breadcrumb::{Breadcrumb, BreadcrumbStyle};
 pub use button::{Button, ButtonState, ButtonStyle};
+pub use canvas::Canvas;
+pub use checkbox::{Checkbox, CheckboxStyle};
+pub use chip_input::{ChipInput, ChipInputStyle};
+pub use clipboard::ClipboardService;
+pub use collapsible::{Collapsible, CollapsibleStyle};
 pub use container::Container;
-pub use context::{EventContext, LayoutContext, PaintContext};
+pub use context::{EventContext, EventPhase, LayoutContext, PaintContext};
+pub use divider::Divider;
+pub use drop_zone::{DropZone, DropZoneStyle};
+pub use icon::{Icon, IconName, IconSegment};
+pub use image::{Image, ImageFit};
+#[cfg(feature = "markdown")]
+pub use markdown::Markdown;
+pub use menu_bar::{MenuBar, MenuBarItem, MenuBarStyle, MenuList};
+pub use spark_render::ImageHandle;
+pub use modal::{Modal, ModalHandle, ModalStyle};
+pub use progress_bar::{ProgressBar, ProgressBarStyle};
+pub use progress_ring::{ProgressRing, ProgressRingStyle};
+pub use radio::{RadioButton, RadioButtonStyle, RadioGroup};
 pub use scroll::{Scroll, ScrollDirection, ScrollbarStyle};
+pub use search_input::SearchInput;
+pub use segmented_control::{SegmentedControl, SegmentedControlStyle};
+pub use select::{Select, SelectStyle};
+pub use signal::{Signal, SignalRuntime};
+pub use spacer::Spacer;
+pub use split_pane::{SplitPane, SplitPaneStyle};
+pub use stack::{Alignment, Stack};
+pub use switch::{Switch, SwitchStyle};
+pub use table::{ColumnWidth, SortDirection, Table, TableStyle};
 pub use text::{Text, TextAlign};
 pub use text_input::{TextInput, TextInputStyle};
-pub use widget::{EventResponse, Widget};
+pub use theme::{
+    ColorToken, Radius, Spacing, Theme, ThemeColors, ThemeMode, ThemedStyle, Typography,
+};
+pub use widget::{CursorIcon, EventResponse, Widget};
 
 // Re-export layout types for convenience
 pub use spark_layout::{styles, taffy, WidgetId};
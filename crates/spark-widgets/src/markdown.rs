@@ -0,0 +1,672 @@
+//! Markdown text widget.
+
+use crate::{
+    AccessibleInfo, AccessibleRole, CursorIcon, EventContext, EventResponse, LayoutContext,
+    PaintContext, Theme, Widget,
+};
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use spark_core::Rect;
+use spark_input::InputEvent;
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::cell::RefCell;
+use taffy::prelude::*;
+
+/// Space between blocks, relative to the body font size.
+const BLOCK_GAP: f32 = 0.75;
+/// Indent per list level, relative to the body font size.
+const LIST_INDENT: f32 = 1.5;
+/// Padding inside code blocks, relative to the body font size.
+const CODE_PADDING: f32 = 0.5;
+
+/// Inline formatting of a span of text.
+#[derive(Clone, Debug, PartialEq)]
+struct Span {
+    text: String,
+    bold: bool,
+    italic: bool,
+    code: bool,
+    /// Index into [`Document::links`].
+    link: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum BlockKind {
+    Paragraph,
+    Heading(u8),
+    /// A list item with its bullet or number.
+    ListItem(String),
+    CodeBlock,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Block {
+    kind: BlockKind,
+    /// List nesting depth.
+    indent: usize,
+    spans: Vec<Span>,
+}
+
+/// Parsed markdown: blocks of styled spans, and the link targets they use.
+#[derive(Clone, Debug, Default)]
+struct Document {
+    blocks: Vec<Block>,
+    links: Vec<String>,
+}
+
+impl Document {
+    /// Parse the supported subset of `source`; anything else is dropped or
+    /// shown as plain text.
+    fn parse(source: &str) -> Self {
+        let mut doc = Document::default();
+        let mut block: Option<Block> = None;
+        // Next number of each open list, or `None` for bullets
+        let mut lists: Vec<Option<u64>> = Vec::new();
+        let (mut bold, mut italic) = (0, 0);
+        let mut link = None;
+
+        for event in Parser::new(source) {
+            let (text, code) = match event {
+                Event::Start(Tag::Paragraph) => {
+                    // Loose list items wrap their text in a paragraph
+                    let in_item = block
+                        .as_ref()
+                        .is_some_and(|b| matches!(b.kind, BlockKind::ListItem(_)));
+                    if !in_item {
+                        doc.finish(&mut block);
+                    }
+                    continue;
+                }
+                Event::Start(Tag::Heading { level, .. }) => {
+                    doc.finish(&mut block);
+                    block = Some(Block::new(BlockKind::Heading(level as u8), lists.len()));
+                    continue;
+                }
+                Event::Start(Tag::CodeBlock(_)) => {
+                    doc.finish(&mut block);
+                    block = Some(Block::new(BlockKind::CodeBlock, lists.len()));
+                    continue;
+                }
+                Event::Start(Tag::List(start)) => {
+                    doc.finish(&mut block);
+                    lists.push(start);
+                    continue;
+                }
+                Event::End(TagEnd::List(_)) => {
+                    doc.finish(&mut block);
+                    lists.pop();
+                    continue;
+                }
+                Event::Start(Tag::Item) => {
+                    doc.finish(&mut block);
+                    let marker = match lists.last_mut() {
+                        Some(Some(number)) => {
+                            *number += 1;
+                            format!("{}.", *number - 1)
+                        }
+                        _ => "•".to_string(),
+                    };
+                    block = Some(Block::new(BlockKind::ListItem(marker), lists.len()));
+                    continue;
+                }
+                Event::End(
+                    TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock | TagEnd::Item,
+                ) => {
+                    doc.finish(&mut block);
+                    continue;
+                }
+                Event::Start(Tag::Strong) => {
+                    bold += 1;
+                    continue;
+                }
+                Event::End(TagEnd::Strong) => {
+                    bold -= 1;
+                    continue;
+                }
+                Event::Start(Tag::Emphasis) => {
+                    italic += 1;
+                    continue;
+                }
+                Event::End(TagEnd::Emphasis) => {
+                    italic -= 1;
+                    continue;
+                }
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    doc.links.push(dest_url.into_string());
+                    link = Some(doc.links.len() - 1);
+                    continue;
+                }
+                Event::End(TagEnd::Link) => {
+                    link = None;
+                    continue;
+                }
+                Event::Text(text) => (text, false),
+                Event::Code(text) => (text, true),
+                Event::SoftBreak => (" ".into(), false),
+                Event::HardBreak => ("\n".into(), false),
+                _ => continue,
+            };
+
+            let block = block.get_or_insert_with(|| Block::new(BlockKind::Paragraph, lists.len()));
+            let code = code || block.kind == BlockKind::CodeBlock;
+            block.spans.push(Span {
+                text: text.into_string(),
+                bold: bold > 0,
+                italic: italic > 0,
+                code,
+                link,
+            });
+        }
+        doc.finish(&mut block);
+        doc
+    }
+
+    /// Add the open block, if it has anything to show.
+    fn finish(&mut self, block: &mut Option<Block>) {
+        let Some(mut block) = block.take() else {
+            return;
+        };
+        if block.kind == BlockKind::CodeBlock {
+            // Fenced code ends with a newline that would add a blank line
+            if let Some(last) = block.spans.last_mut() {
+                last.text.truncate(last.text.trim_end_matches('\n').len());
+            }
+        }
+        if !block.spans.is_empty() || matches!(block.kind, BlockKind::ListItem(_)) {
+            self.blocks.push(block);
+        }
+    }
+
+    /// The text without formatting, for accessibility.
+    fn plain_text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(|block| block.spans.iter().map(|span| span.text.as_str()).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Block {
+    fn new(kind: BlockKind, indent: usize) -> Self {
+        Self {
+            kind,
+            indent,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Font size relative to body text.
+    fn font_scale(&self) -> f32 {
+        match self.kind {
+            BlockKind::Heading(1) => 2.0,
+            BlockKind::Heading(2) => 1.5,
+            BlockKind::Heading(3) => 1.25,
+            _ => 1.0,
+        }
+    }
+}
+
+/// A run of text placed by [`layout_document`], in logical pixels relative
+/// to the widget.
+#[derive(Clone, Debug)]
+struct PlacedRun {
+    text: String,
+    /// Style without color; colors come from the theme when painting.
+    style: TextStyle,
+    /// Inline code, drawn on a tinted background.
+    code: bool,
+    link: Option<usize>,
+    rect: Rect,
+}
+
+#[derive(Clone, Debug, Default)]
+struct DocumentLayout {
+    runs: Vec<PlacedRun>,
+    /// Backgrounds of code blocks.
+    code_blocks: Vec<Rect>,
+    width: f32,
+    height: f32,
+}
+
+impl DocumentLayout {
+    /// The link under `pos`, relative to the widget.
+    fn link_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.runs
+            .iter()
+            .filter(|run| run.rect.contains(glam::Vec2::new(x, y)))
+            .find_map(|run| run.link)
+    }
+}
+
+/// Lay out `doc` at `max_width`, wrapping between words.
+///
+/// `measure` returns the size of text in a style, in the same units as
+/// `max_width`.
+fn layout_document(
+    doc: &Document,
+    font_size: f32,
+    max_width: f32,
+    mut measure: impl FnMut(&str, &TextStyle) -> (f32, f32),
+) -> DocumentLayout {
+    let mut layout = DocumentLayout::default();
+    let mut y = 0.0;
+    for (index, block) in doc.blocks.iter().enumerate() {
+        if index > 0 {
+            y += font_size * BLOCK_GAP;
+        }
+        let size = font_size * block.font_scale();
+        let heading = matches!(block.kind, BlockKind::Heading(_));
+        let padding = if block.kind == BlockKind::CodeBlock {
+            font_size * CODE_PADDING
+        } else {
+            0.0
+        };
+        let indent = block.indent as f32 * font_size * LIST_INDENT;
+        let left = indent + padding;
+        let right = (max_width - padding).max(left);
+        let block_top = y;
+        y += padding;
+
+        if let BlockKind::ListItem(marker) = &block.kind {
+            let style = TextStyle::default().with_size(size);
+            let (width, height) = measure(marker.as_str(), &style);
+            let x = (indent - width - font_size * 0.4).max(0.0);
+            layout.runs.push(PlacedRun {
+                text: marker.clone(),
+                style,
+                code: false,
+                link: None,
+                rect: Rect::new(x, y, width, height),
+            });
+        }
+
+        let mut line = Line::new(left, measure("", &TextStyle::default().with_size(size)).1);
+        for (span_index, span) in block.spans.iter().enumerate() {
+            let mut style = TextStyle::default().with_size(size);
+            if span.bold || heading {
+                style = style.bold();
+            }
+            if span.italic {
+                style = style.italic();
+            }
+            if span.code {
+                style = style.with_family("monospace");
+            }
+            // Trailing spaces don't count toward measured widths
+            let space = measure("x x", &style).0 - measure("xx", &style).0;
+
+            for (line_index, text) in span.text.split('\n').enumerate() {
+                if line_index > 0 {
+                    y = line.finish(&mut layout, y);
+                }
+                for (word_index, word) in text.split(' ').enumerate() {
+                    if word_index > 0 {
+                        line.x += space;
+                    }
+                    if word.is_empty() {
+                        continue;
+                    }
+                    let (width, height) = measure(word, &style);
+                    if line.x + width > right && line.x > left {
+                        y = line.finish(&mut layout, y);
+                    }
+                    line.push(span_index, word, &style, span, width, height);
+                }
+            }
+        }
+        y = line.finish(&mut layout, y);
+        layout.width = layout.width.max(line.widest + padding);
+
+        y += padding;
+        if block.kind == BlockKind::CodeBlock {
+            let width = (max_width - indent).max(0.0);
+            layout
+                .code_blocks
+                .push(Rect::new(indent, block_top, width, y - block_top));
+        }
+    }
+    layout.height = y;
+    layout
+}
+
+/// The line being filled by [`layout_document`].
+struct Line {
+    left: f32,
+    x: f32,
+    /// Height of an empty line.
+    min_height: f32,
+    runs: Vec<(usize, PlacedRun)>,
+    /// Right edge of the widest line so far, ignoring trailing spaces.
+    widest: f32,
+}
+
+impl Line {
+    fn new(left: f32, min_height: f32) -> Self {
+        Self {
+            left,
+            x: left,
+            min_height,
+            runs: Vec::new(),
+            widest: 0.0,
+        }
+    }
+
+    /// Place a word at the cursor, joining it to the previous run when
+    /// both come from the same span.
+    fn push(
+        &mut self,
+        span_index: usize,
+        word: &str,
+        style: &TextStyle,
+        span: &Span,
+        width: f32,
+        height: f32,
+    ) {
+        match self.runs.last_mut() {
+            Some((index, run)) if *index == span_index => {
+                let gap = self.x - (run.rect.x + run.rect.width);
+                // Spaces in the run's own font stand in for the gap
+                let spaces = if gap > 0.0 { " " } else { "" };
+                run.text.push_str(spaces);
+                run.text.push_str(word);
+                run.rect.width = self.x + width - run.rect.x;
+            }
+            _ => self.runs.push((
+                span_index,
+                PlacedRun {
+                    text: word.to_string(),
+                    style: style.clone(),
+                    code: span.code,
+                    link: span.link,
+                    rect: Rect::new(self.x, 0.0, width, height),
+                },
+            )),
+        }
+        self.x += width;
+    }
+
+    /// Move the runs into `layout` at `y`, bottom-aligned, and return the
+    /// top of the next line.
+    fn finish(&mut self, layout: &mut DocumentLayout, y: f32) -> f32 {
+        let height = self
+            .runs
+            .iter()
+            .map(|(_, run)| run.rect.height)
+            .fold(self.min_height, f32::max);
+        if let Some((_, run)) = self.runs.last() {
+            self.widest = self.widest.max(run.rect.x + run.rect.width);
+        }
+        for (_, mut run) in self.runs.drain(..) {
+            run.rect.y = y + height - run.rect.height;
+            layout.runs.push(run);
+        }
+        self.x = self.left;
+        y + height
+    }
+}
+
+/// Formatted text from a subset of Markdown: headings, bold and italic,
+/// inline code and code blocks, bullet and numbered lists, and links.
+///
+/// Text wraps to the width the widget is given, so long documents belong
+/// in a [`Scroll`](crate::Scroll).
+pub struct Markdown {
+    id: WidgetId,
+    source: String,
+    document: Document,
+    font_size: f32,
+    on_link: Option<Box<dyn FnMut(&str) + Send + Sync>>,
+    hovered_link: Option<usize>,
+    pressed_link: Option<usize>,
+    /// Layout from the last paint, with the width it was made for.
+    layout: RefCell<Option<(f32, DocumentLayout)>>,
+}
+
+impl Markdown {
+    /// Create a widget showing `source`.
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        Self {
+            id: WidgetId::default(),
+            document: Document::parse(&source),
+            source,
+            font_size: 16.0,
+            on_link: None,
+            hovered_link: None,
+            pressed_link: None,
+            layout: RefCell::new(None),
+        }
+    }
+
+    /// Set the body font size; headings scale with it.
+    pub fn size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Set the handler called with a link's URL when it is clicked.
+    pub fn on_link(mut self, handler: impl FnMut(&str) + Send + Sync + 'static) -> Self {
+        self.on_link = Some(Box::new(handler));
+        self
+    }
+
+    /// Get the Markdown source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The link under `pos`, using the layout from the last paint.
+    fn link_at(&self, ctx: &EventContext, pos: glam::Vec2) -> Option<usize> {
+        let local = ctx.to_local(pos);
+        let layout = self.layout.borrow();
+        layout.as_ref()?.1.link_at(local.x, local.y)
+    }
+}
+
+impl Widget for Markdown {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style::default()
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let scale = ctx.scale_factor;
+        let width = bounds.width / scale;
+
+        let mut cached = self.layout.borrow_mut();
+        if !matches!(&*cached, Some((cached_width, _)) if *cached_width == width) {
+            let layout = layout_document(&self.document, self.font_size, width, |text, style| {
+                let (width, height) = ctx.measure_text(text, style);
+                (width / scale, height / scale)
+            });
+            *cached = Some((width, layout));
+        }
+        let Some((_, layout)) = &*cached else {
+            return;
+        };
+
+        let colors = &ctx.theme().colors;
+        let to_physical = |rect: Rect| {
+            Rect::new(
+                bounds.x + rect.x * scale,
+                bounds.y + rect.y * scale,
+                rect.width * scale,
+                rect.height * scale,
+            )
+        };
+        for block in &layout.code_blocks {
+            ctx.fill_rounded_rect(to_physical(*block), colors.surface_alt, 4.0);
+        }
+        for run in &layout.runs {
+            let rect = to_physical(run.rect);
+            if run.code {
+                let background = Rect::new(
+                    rect.x - 2.0 * scale,
+                    rect.y,
+                    rect.width + 4.0 * scale,
+                    rect.height,
+                );
+                ctx.fill_rounded_rect(background, colors.surface_alt, 3.0);
+            }
+            let color = match run.link {
+                Some(link) if self.hovered_link == Some(link) => colors.primary_hovered,
+                Some(_) => colors.primary,
+                None => colors.text,
+            };
+            let style = run.style.clone().with_color(color);
+            ctx.draw_text(&run.text, &style, rect.x, rect.y);
+            if run.link.is_some() {
+                let underline = rect.y + rect.height - scale;
+                ctx.draw_line(
+                    (rect.x, underline),
+                    (rect.x + rect.width, underline),
+                    scale,
+                    color,
+                );
+            }
+        }
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        match event {
+            InputEvent::PointerMove { pos, .. } => {
+                let hovered = self.link_at(ctx, *pos);
+                let changed = hovered != self.hovered_link;
+                self.hovered_link = hovered;
+                EventResponse {
+                    repaint: changed,
+                    cursor: hovered.map(|_| CursorIcon::Pointer),
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerLeave if self.hovered_link.is_some() => {
+                self.hovered_link = None;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerDown { pos, .. } => match self.link_at(ctx, *pos) {
+                Some(link) => {
+                    self.pressed_link = Some(link);
+                    EventResponse::capture()
+                }
+                None => EventResponse::default(),
+            },
+            InputEvent::PointerCancel { .. } if self.pressed_link.is_some() => {
+                self.pressed_link = None;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                let Some(pressed) = self.pressed_link.take() else {
+                    return EventResponse::default();
+                };
+                if self.link_at(ctx, *pos) == Some(pressed) {
+                    if let Some(handler) = &mut self.on_link {
+                        handler(&self.document.links[pressed]);
+                    }
+                }
+                EventResponse::release()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Label,
+            name: Some(self.document.plain_text()),
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let max_width = ctx.max_width.unwrap_or(f32::INFINITY);
+        let layout = layout_document(&self.document, self.font_size, max_width, |text, style| {
+            ctx.text.measure(text, style, None)
+        });
+        Some((layout.width, layout.height))
+    }
+
+    fn wraps(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ten pixels per character, twenty per line.
+    fn fixed_measure(text: &str, _style: &TextStyle) -> (f32, f32) {
+        (text.chars().count() as f32 * 10.0, 20.0)
+    }
+
+    #[test]
+    fn test_parse_blocks_and_inline_styles() {
+        let doc = Document::parse(
+            "# Title\n\nSome **bold** and *italic* `code`.\n\n- one\n- [two](https://example.com)\n\n1. first\n2. second",
+        );
+        let kinds: Vec<_> = doc.blocks.iter().map(|block| block.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            [
+                BlockKind::Heading(1),
+                BlockKind::Paragraph,
+                BlockKind::ListItem("•".into()),
+                BlockKind::ListItem("•".into()),
+                BlockKind::ListItem("1.".into()),
+                BlockKind::ListItem("2.".into()),
+            ]
+        );
+
+        let spans = &doc.blocks[1].spans;
+        assert!(spans.iter().any(|span| span.text == "bold" && span.bold));
+        assert!(spans
+            .iter()
+            .any(|span| span.text == "italic" && span.italic));
+        assert!(spans.iter().any(|span| span.text == "code" && span.code));
+
+        let link = &doc.blocks[3].spans[0];
+        assert_eq!(link.text, "two");
+        assert_eq!(doc.links[link.link.unwrap()], "https://example.com");
+    }
+
+    #[test]
+    fn test_layout_wraps_between_words() {
+        let doc = Document::parse("aaa bbb ccc");
+        // "aaa bbb" fits in 80; "ccc" moves to the next line
+        let layout = layout_document(&doc, 16.0, 80.0, fixed_measure);
+        let lines: Vec<_> = layout
+            .runs
+            .iter()
+            .map(|run| (run.text.as_str(), run.rect.y))
+            .collect();
+        assert_eq!(lines, [("aaa bbb", 0.0), ("ccc", 20.0)]);
+        assert_eq!(layout.height, 40.0);
+        assert_eq!(layout.width, 70.0);
+
+        let unwrapped = layout_document(&doc, 16.0, f32::INFINITY, fixed_measure);
+        assert_eq!((unwrapped.width, unwrapped.height), (110.0, 20.0));
+    }
+
+    #[test]
+    fn test_link_hit_testing() {
+        let doc = Document::parse("see [docs](https://example.com/docs)");
+        let layout = layout_document(&doc, 16.0, 400.0, fixed_measure);
+        assert_eq!(layout.link_at(5.0, 10.0), None);
+        assert_eq!(layout.link_at(45.0, 10.0), Some(0));
+    }
+}
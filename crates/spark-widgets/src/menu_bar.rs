@@ -0,0 +1,904 @@
+//! In-app menu bar widget.
+
+use crate::{
+    AccessibleInfo, AccessibleRole, EventContext, EventResponse, IconName, LayoutContext,
+    PaintContext, Theme, ThemeMode, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{InputEvent, Key, KeyboardEvent, NamedKey, Shortcut};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::cell::{Cell, RefCell};
+use taffy::prelude::*;
+
+/// Width of the column left of item labels, where check marks go.
+const CHECK_WIDTH: f32 = 28.0;
+/// Width of the column right of item labels, where submenu arrows go.
+const ARROW_WIDTH: f32 = 24.0;
+/// Size of the check mark and arrow icons, in logical pixels.
+const ICON_SIZE: f32 = 16.0;
+/// Space between an item's label and its shortcut hint.
+const SHORTCUT_GAP: f32 = 24.0;
+/// Height of a separator row.
+const SEPARATOR_HEIGHT: f32 = 9.0;
+/// Space above the first and below the last row of a dropdown.
+const LIST_PADDING: f32 = 4.0;
+/// Narrowest a dropdown gets.
+const MIN_LIST_WIDTH: f32 = 160.0;
+
+/// Style configuration for a menu bar.
+#[derive(Clone, Debug)]
+pub struct MenuBarStyle {
+    pub background: Color,
+    pub border_color: Color,
+    pub text_color: Color,
+    /// Color of disabled items.
+    pub disabled_color: Color,
+    /// Color of shortcut hints and submenu arrows.
+    pub hint_color: Color,
+    /// Fill behind the open or hovered top-level title.
+    pub active_background: Color,
+    /// Fill of the dropdowns.
+    pub list_background: Color,
+    /// Fill behind the highlighted item.
+    pub highlight: Color,
+    /// Height of the bar.
+    pub height: f32,
+    /// Space on each side of a top-level title.
+    pub padding_h: f32,
+    /// Height of each item in a dropdown.
+    pub row_height: f32,
+    pub corner_radius: f32,
+    pub font_size: f32,
+}
+
+impl Default for MenuBarStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for MenuBarStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            background: colors.surface,
+            border_color: colors.border,
+            text_color: colors.text,
+            disabled_color: colors.text_disabled,
+            hint_color: colors.text_muted,
+            active_background: colors.surface_alt,
+            list_background: colors.surface,
+            highlight: colors.selection,
+            height: 32.0,
+            padding_h: theme.spacing.md,
+            row_height: 28.0,
+            corner_radius: theme.radius.md,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// What choosing an item does.
+enum ItemAction {
+    Call(Box<dyn FnMut() + Send + Sync>),
+    Toggle {
+        checked: bool,
+        on_toggle: Box<dyn FnMut(bool) + Send + Sync>,
+    },
+}
+
+/// An item in a [`MenuList`].
+pub struct MenuBarItem {
+    label: String,
+    /// The shortcut hint, formatted for the current platform.
+    shortcut: Option<String>,
+    enabled: bool,
+    action: ItemAction,
+}
+
+impl MenuBarItem {
+    /// Create an item that calls `handler` when chosen.
+    pub fn new(label: impl Into<String>, handler: impl FnMut() + Send + Sync + 'static) -> Self {
+        Self {
+            label: label.into(),
+            shortcut: None,
+            enabled: true,
+            action: ItemAction::Call(Box::new(handler)),
+        }
+    }
+
+    /// Create an item with a check mark that toggles when chosen, calling
+    /// `handler` with the new state.
+    pub fn checkable(
+        label: impl Into<String>,
+        checked: bool,
+        handler: impl FnMut(bool) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            action: ItemAction::Toggle {
+                checked,
+                on_toggle: Box::new(handler),
+            },
+            ..Self::new(label, || {})
+        }
+    }
+
+    /// Show a shortcut such as `"Mod+S"` next to the label.
+    ///
+    /// This is only a hint; the shortcut itself is handled elsewhere. Panics
+    /// if `spec` is not a valid shortcut.
+    pub fn shortcut(mut self, spec: &str) -> Self {
+        self.shortcut = Some(Shortcut::new(spec).to_string());
+        self
+    }
+
+    /// Set whether the item can be chosen.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Run the item's action.
+    fn activate(&mut self) {
+        match &mut self.action {
+            ItemAction::Call(handler) => handler(),
+            ItemAction::Toggle { checked, on_toggle } => {
+                *checked = !*checked;
+                on_toggle(*checked);
+            }
+        }
+    }
+}
+
+/// An entry in a [`MenuList`].
+enum MenuEntry {
+    Item(MenuBarItem),
+    Separator,
+    Submenu(String, MenuList),
+}
+
+impl MenuEntry {
+    /// Whether keyboard navigation stops at this entry.
+    fn selectable(&self) -> bool {
+        match self {
+            MenuEntry::Item(item) => item.enabled,
+            MenuEntry::Separator => false,
+            MenuEntry::Submenu(..) => true,
+        }
+    }
+
+    fn height(&self, row_height: f32) -> f32 {
+        match self {
+            MenuEntry::Separator => SEPARATOR_HEIGHT,
+            _ => row_height,
+        }
+    }
+}
+
+/// The items of a dropdown or submenu.
+#[derive(Default)]
+pub struct MenuList {
+    entries: Vec<MenuEntry>,
+    /// Measured at layout time, in logical pixels.
+    width: Cell<f32>,
+}
+
+impl MenuList {
+    /// Create an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item that calls `handler` when chosen.
+    pub fn item(
+        self,
+        label: impl Into<String>,
+        handler: impl FnMut() + Send + Sync + 'static,
+    ) -> Self {
+        self.add(MenuBarItem::new(label, handler))
+    }
+
+    /// Add a prepared item, e.g. a checkable one or one with a shortcut hint.
+    pub fn add(mut self, item: MenuBarItem) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+
+    /// Add a separator line.
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// Add a submenu built by `build`, opening to the side.
+    pub fn submenu(
+        mut self,
+        title: impl Into<String>,
+        build: impl FnOnce(MenuList) -> MenuList,
+    ) -> Self {
+        self.entries
+            .push(MenuEntry::Submenu(title.into(), build(MenuList::new())));
+        self
+    }
+
+    fn height(&self, row_height: f32) -> f32 {
+        let rows: f32 = self
+            .entries
+            .iter()
+            .map(|entry| entry.height(row_height))
+            .sum();
+        rows + LIST_PADDING * 2.0
+    }
+
+    /// Measure this list and its submenus.
+    fn measure(&self, ctx: &mut LayoutContext, text_style: &TextStyle) {
+        let mut content: f32 = 0.0;
+        for entry in &self.entries {
+            let width = match entry {
+                MenuEntry::Item(item) => {
+                    let (label, _) = ctx.text.measure(&item.label, text_style, None);
+                    let shortcut = item.shortcut.as_ref().map_or(0.0, |shortcut| {
+                        ctx.text.measure(shortcut, text_style, None).0 + SHORTCUT_GAP
+                    });
+                    label + shortcut
+                }
+                MenuEntry::Separator => 0.0,
+                MenuEntry::Submenu(title, list) => {
+                    list.measure(ctx, text_style);
+                    ctx.text.measure(title, text_style, None).0
+                }
+            };
+            content = content.max(width);
+        }
+        self.width
+            .set((content + CHECK_WIDTH + ARROW_WIDTH).max(MIN_LIST_WIDTH));
+    }
+
+    /// Each entry's row inside the list at `rect`, with lengths multiplied
+    /// by `scale`.
+    fn rows(&self, rect: Rect, row_height: f32, scale: f32) -> Vec<Rect> {
+        let mut y = rect.y + LIST_PADDING * scale;
+        self.entries
+            .iter()
+            .map(|entry| {
+                let height = entry.height(row_height) * scale;
+                let row = Rect::new(rect.x, y, rect.width, height);
+                y += height;
+                row
+            })
+            .collect()
+    }
+}
+
+/// The next entry keyboard navigation stops at, after `from` or before it
+/// when going back, wrapping around. Starts at either end without `from`.
+fn next_selectable(selectable: &[bool], from: Option<usize>, forward: bool) -> Option<usize> {
+    let count = selectable.len();
+    (1..=count)
+        .map(|step| match (from, forward) {
+            (Some(from), true) => (from + step) % count,
+            (Some(from), false) => (from + count - step % count) % count,
+            (None, true) => step - 1,
+            (None, false) => count - step,
+        })
+        .find(|&index| selectable[index])
+}
+
+/// A row of menus along the top of the window, for apps that draw their
+/// own menus instead of, or besides, the native menu bar.
+///
+/// Clicking a title or pressing Alt with its first letter opens its
+/// dropdown. While one is open, hovering another title switches to it, and
+/// the arrow keys, Enter and Escape navigate. Choosing an item runs it and
+/// closes the menu; clicking outside closes it too.
+pub struct MenuBar {
+    id: WidgetId,
+    menus: Vec<(String, MenuList)>,
+    style: Option<MenuBarStyle>,
+    /// Measured at layout time, hence the cell.
+    title_widths: RefCell<Vec<f32>>,
+    hovered_title: Option<usize>,
+    /// The open top-level menu.
+    open: Option<usize>,
+    /// The highlighted entry of each open list, outermost first. A list
+    /// whose highlighted entry is a submenu has that submenu open.
+    path: Vec<usize>,
+}
+
+impl MenuBar {
+    /// Create an empty menu bar.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            menus: Vec::new(),
+            style: None,
+            title_widths: RefCell::new(Vec::new()),
+            hovered_title: None,
+            open: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Add a top-level menu built by `build`.
+    pub fn menu(
+        mut self,
+        title: impl Into<String>,
+        build: impl FnOnce(MenuList) -> MenuList,
+    ) -> Self {
+        self.menus.push((title.into(), build(MenuList::new())));
+        self
+    }
+
+    /// Set the menu bar style.
+    pub fn with_style(mut self, style: MenuBarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The top-level titles, with lengths multiplied by `scale`.
+    fn title_rects(&self, bounds: Rect, scale: f32, style: &MenuBarStyle) -> Vec<Rect> {
+        let mut x = bounds.x;
+        self.title_widths
+            .borrow()
+            .iter()
+            .map(|width| {
+                let width = (width + style.padding_h * 2.0) * scale;
+                let rect = Rect::new(x, bounds.y, width, bounds.height);
+                x += width;
+                rect
+            })
+            .collect()
+    }
+
+    fn title_at(&self, bounds: Rect, pos: glam::Vec2, style: &MenuBarStyle) -> Option<usize> {
+        self.title_rects(bounds, 1.0, style)
+            .iter()
+            .position(|rect| rect.contains(pos))
+    }
+
+    /// The open lists, outermost first.
+    fn open_lists(&self) -> Vec<&MenuList> {
+        let Some((_, mut list)) = self.open.and_then(|open| self.menus.get(open)) else {
+            return Vec::new();
+        };
+        let mut lists = vec![list];
+        for &index in &self.path {
+            match list.entries.get(index) {
+                Some(MenuEntry::Submenu(_, submenu)) => {
+                    list = submenu;
+                    lists.push(list);
+                }
+                _ => break,
+            }
+        }
+        lists
+    }
+
+    /// The open list at `level`, for changing it.
+    fn open_list_mut(&mut self, level: usize) -> Option<&mut MenuList> {
+        let mut list = &mut self.menus.get_mut(self.open?)?.1;
+        for &index in self.path.get(..level)? {
+            match list.entries.get_mut(index) {
+                Some(MenuEntry::Submenu(_, submenu)) => list = submenu,
+                _ => return None,
+            }
+        }
+        Some(list)
+    }
+
+    /// Where each open list is shown, with lengths multiplied by `scale`.
+    ///
+    /// The dropdown hangs below its title; each submenu opens beside the
+    /// row that opened it.
+    fn list_rects(&self, bounds: Rect, scale: f32, style: &MenuBarStyle) -> Vec<Rect> {
+        let Some(title) = self
+            .open
+            .and_then(|open| self.title_rects(bounds, scale, style).get(open).copied())
+        else {
+            return Vec::new();
+        };
+        let row_height = style.row_height;
+        let lists = self.open_lists();
+        let mut rects: Vec<Rect> = Vec::new();
+        for (level, list) in lists.iter().enumerate() {
+            let (x, y) = match rects.last() {
+                None => (title.x, title.y + title.height),
+                Some(parent) => {
+                    let rows = lists[level - 1].rows(*parent, row_height, scale);
+                    let row = rows[self.path[level - 1]];
+                    (parent.x + parent.width, row.y - LIST_PADDING * scale)
+                }
+            };
+            rects.push(Rect::new(
+                x,
+                y,
+                list.width.get() * scale,
+                list.height(row_height) * scale,
+            ));
+        }
+        rects
+    }
+
+    /// The level and entry index of the row under `pos`, innermost list first.
+    fn entry_at(
+        &self,
+        bounds: Rect,
+        pos: glam::Vec2,
+        style: &MenuBarStyle,
+    ) -> Option<(usize, usize)> {
+        let lists = self.open_lists();
+        let rects = self.list_rects(bounds, 1.0, style);
+        (0..rects.len()).rev().find_map(|level| {
+            if !rects[level].contains(pos) {
+                return None;
+            }
+            let rows = lists[level].rows(rects[level], style.row_height, 1.0);
+            rows.iter()
+                .position(|row| row.contains(pos))
+                .map(|index| (level, index))
+        })
+    }
+
+    fn open_menu(&mut self, index: usize) {
+        self.open = Some(index);
+        self.path.clear();
+    }
+
+    fn close(&mut self) {
+        self.open = None;
+        self.path.clear();
+    }
+
+    /// Highlight the next or previous entry of the innermost open list.
+    fn move_highlight(&mut self, forward: bool) {
+        let level = self.path.len().saturating_sub(1);
+        let from = self.path.get(level).copied();
+        let Some(list) = self.open_lists().get(level).copied() else {
+            return;
+        };
+        let selectable: Vec<bool> = list.entries.iter().map(MenuEntry::selectable).collect();
+        if let Some(index) = next_selectable(&selectable, from, forward) {
+            self.path.truncate(level);
+            self.path.push(index);
+        }
+    }
+
+    /// Enter the highlighted submenu, highlighting its first entry.
+    fn enter_submenu(&mut self) -> bool {
+        let lists = self.open_lists();
+        let level = self.path.len();
+        if lists.len() <= level {
+            return false;
+        }
+        let selectable: Vec<bool> = lists[level]
+            .entries
+            .iter()
+            .map(MenuEntry::selectable)
+            .collect();
+        if let Some(index) = next_selectable(&selectable, None, true) {
+            self.path.push(index);
+        }
+        true
+    }
+
+    /// Choose entry `index` of the open list at `level`.
+    fn choose(&mut self, level: usize, index: usize) {
+        let Some(entry) = self
+            .open_list_mut(level)
+            .and_then(|list| list.entries.get_mut(index))
+        else {
+            return;
+        };
+        match entry {
+            MenuEntry::Item(item) if item.enabled => {
+                item.activate();
+                self.close();
+            }
+            MenuEntry::Submenu(..) => {
+                self.path.truncate(level);
+                self.path.push(index);
+                self.enter_submenu();
+            }
+            _ => {}
+        }
+    }
+
+    /// The menu whose title starts with the letter pressed with Alt.
+    fn mnemonic(&self, event: &KeyboardEvent) -> Option<usize> {
+        let Key::Character(key) = &event.key else {
+            return None;
+        };
+        if !event.modifiers.alt() || event.modifiers.ctrl() || event.modifiers.meta() {
+            return None;
+        }
+        let key = key.chars().next()?.to_lowercase().next()?;
+        self.menus.iter().position(|(title, _)| {
+            title
+                .chars()
+                .next()
+                .is_some_and(|first| first.to_lowercase().next() == Some(key))
+        })
+    }
+
+    fn key_down(&mut self, event: &KeyboardEvent) -> bool {
+        let Some(open) = self.open else {
+            // The focused bar opens its first menu
+            return match event.key {
+                Key::Named(NamedKey::ArrowDown | NamedKey::Enter) if !self.menus.is_empty() => {
+                    self.open_menu(0);
+                    self.move_highlight(true);
+                    true
+                }
+                _ => false,
+            };
+        };
+        let count = self.menus.len();
+        match event.key {
+            Key::Named(NamedKey::ArrowDown) => self.move_highlight(true),
+            Key::Named(NamedKey::ArrowUp) => self.move_highlight(false),
+            Key::Named(NamedKey::ArrowRight) => {
+                if self.path.is_empty() || !self.enter_submenu() {
+                    self.open_menu((open + 1) % count);
+                    self.move_highlight(true);
+                }
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                if self.path.len() > 1 {
+                    self.path.pop();
+                } else {
+                    self.open_menu((open + count - 1) % count);
+                    self.move_highlight(true);
+                }
+            }
+            Key::Named(NamedKey::Enter) => {
+                if let Some(&index) = self.path.last() {
+                    self.choose(self.path.len() - 1, index);
+                }
+            }
+            Key::Named(NamedKey::Escape) => {
+                if self.path.len() > 1 {
+                    self.path.pop();
+                } else {
+                    self.close();
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn paint_check(ctx: &mut PaintContext, row: Rect, color: Color) {
+        let x = row.x + CHECK_WIDTH / 2.0 * ctx.scale_factor;
+        let icon = Self::icon_rect(ctx, x, row);
+        IconName::Check.paint(ctx, icon, color);
+    }
+
+    fn paint_arrow(ctx: &mut PaintContext, row: Rect, color: Color) {
+        let x = row.x + row.width - ARROW_WIDTH / 2.0 * ctx.scale_factor;
+        let icon = Self::icon_rect(ctx, x, row);
+        IconName::ChevronRight.paint(ctx, icon, color);
+    }
+
+    /// The square an indicator icon fills, centered on `x` in `row`.
+    fn icon_rect(ctx: &PaintContext, x: f32, row: Rect) -> Rect {
+        let side = ICON_SIZE * ctx.scale_factor;
+        let y = row.y + row.height / 2.0;
+        Rect::new(x - side / 2.0, y - side / 2.0, side, side)
+    }
+
+    fn paint_list(
+        &self,
+        ctx: &mut PaintContext,
+        list: &MenuList,
+        rect: Rect,
+        highlighted: Option<usize>,
+        style: &MenuBarStyle,
+    ) {
+        let scale = ctx.scale_factor;
+        let shadow_alpha = match ctx.theme().mode {
+            ThemeMode::Light => 0.2,
+            ThemeMode::Dark => 0.5,
+        };
+        let mut shadow = rect;
+        shadow.y += 2.0 * scale;
+        ctx.draw_shadow(
+            shadow,
+            Color::rgba(0.0, 0.0, 0.0, shadow_alpha),
+            style.corner_radius,
+            8.0,
+        );
+        ctx.fill_bordered_rect(
+            rect,
+            style.list_background,
+            style.corner_radius,
+            1.0,
+            style.border_color,
+        );
+
+        let hint_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.hint_color);
+        let rows = list.rows(rect, style.row_height, scale);
+        for (index, (entry, row)) in list.entries.iter().zip(rows).enumerate() {
+            let (label, enabled) = match entry {
+                MenuEntry::Separator => {
+                    let y = (row.y + row.height / 2.0).round();
+                    let line = Rect::new(
+                        row.x + scale,
+                        y,
+                        row.width - 2.0 * scale,
+                        scale.round().max(1.0),
+                    );
+                    ctx.fill_rect(line, style.border_color);
+                    continue;
+                }
+                MenuEntry::Item(item) => (&item.label, item.enabled),
+                MenuEntry::Submenu(title, _) => (title, true),
+            };
+            if highlighted == Some(index) && enabled {
+                let inset = Rect::new(
+                    row.x + 4.0 * scale,
+                    row.y,
+                    row.width - 8.0 * scale,
+                    row.height,
+                );
+                ctx.fill_rounded_rect(inset, style.highlight, style.corner_radius / 2.0);
+            }
+            let color = if enabled {
+                style.text_color
+            } else {
+                style.disabled_color
+            };
+            let text_style = TextStyle::default()
+                .with_size(style.font_size)
+                .with_color(color);
+            ctx.draw_text_aligned(label, &text_style, row, CHECK_WIDTH * scale);
+
+            match entry {
+                MenuEntry::Item(item) => {
+                    if let ItemAction::Toggle { checked: true, .. } = item.action {
+                        Self::paint_check(ctx, row, color);
+                    }
+                    if let Some(shortcut) = &item.shortcut {
+                        let (width, height) = ctx.measure_text(shortcut, &hint_style);
+                        let x = row.x + row.width - ARROW_WIDTH * scale - width;
+                        let y = row.y + (row.height - height) / 2.0;
+                        ctx.draw_text(shortcut, &hint_style, x, y);
+                    }
+                }
+                MenuEntry::Submenu(..) => Self::paint_arrow(ctx, row, style.hint_color),
+                MenuEntry::Separator => {}
+            }
+        }
+    }
+}
+
+impl Default for MenuBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for MenuBar {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        Style {
+            size: Size {
+                width: percent(1.0),
+                height: length(theme.resolve(&self.style).height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let scale = ctx.scale_factor;
+        let style = ctx.theme().resolve(&self.style);
+
+        ctx.fill_rect(bounds, style.background);
+        let border = scale.round().max(1.0);
+        let line = Rect::new(
+            bounds.x,
+            bounds.y + bounds.height - border,
+            bounds.width,
+            border,
+        );
+        ctx.fill_rect(line, style.border_color);
+
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.text_color);
+        for (index, title) in self
+            .title_rects(bounds, scale, &style)
+            .into_iter()
+            .enumerate()
+        {
+            if self.open == Some(index) || self.hovered_title == Some(index) {
+                let inset = Rect::new(
+                    title.x,
+                    title.y + 3.0 * scale,
+                    title.width,
+                    title.height - 6.0 * scale,
+                );
+                ctx.fill_rounded_rect(inset, style.active_background, style.corner_radius / 2.0);
+            }
+            let label = &self.menus[index].0;
+            ctx.draw_text_aligned(label, &text_style, title, style.padding_h * scale);
+        }
+
+        // Keyboard focus without an open menu outlines the first title
+        if ctx.focus_visible() && self.open.is_none() {
+            if let Some(first) = self.title_rects(bounds, scale, &style).first() {
+                let theme = ctx.theme();
+                ctx.fill_bordered_rect(
+                    *first,
+                    Color::TRANSPARENT,
+                    style.corner_radius / 2.0,
+                    2.0,
+                    theme.colors.focus_ring,
+                );
+            }
+        }
+    }
+
+    fn paint_overlay(&self, ctx: &mut PaintContext) {
+        let style = ctx.theme().resolve(&self.style);
+        let rects = self.list_rects(ctx.bounds(), ctx.scale_factor, &style);
+        for (level, (list, rect)) in self.open_lists().into_iter().zip(rects).enumerate() {
+            self.paint_list(ctx, list, rect, self.path.get(level).copied(), &style);
+        }
+    }
+
+    fn has_overlay(&self) -> bool {
+        self.open.is_some()
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let bounds = ctx.bounds();
+        let style = ctx.theme().resolve(&self.style).into_owned();
+        match event {
+            InputEvent::PointerMove { pos, .. } => {
+                let title = self.title_at(bounds, *pos, &style);
+                if self.open.is_some() {
+                    if let Some(title) = title.filter(|&title| Some(title) != self.open) {
+                        self.open_menu(title);
+                        return EventResponse::handled();
+                    }
+                    if let Some((level, index)) = self.entry_at(bounds, *pos, &style) {
+                        if self.path.get(level) != Some(&index) || self.path.len() != level + 1 {
+                            self.path.truncate(level);
+                            self.path.push(index);
+                        }
+                        return EventResponse::handled();
+                    }
+                }
+                if title != self.hovered_title {
+                    self.hovered_title = title;
+                    return EventResponse {
+                        repaint: true,
+                        ..Default::default()
+                    };
+                }
+                EventResponse::default()
+            }
+            InputEvent::PointerLeave if self.hovered_title.is_some() => {
+                self.hovered_title = None;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerDown { pos, .. } => {
+                if let Some(title) = self.title_at(bounds, *pos, &style) {
+                    if self.open == Some(title) {
+                        self.close();
+                        return EventResponse::handled();
+                    }
+                    self.open_menu(title);
+                    // Focus so the arrow keys work on the open menu
+                    ctx.request_focus();
+                    return EventResponse::focus();
+                }
+                if self.open.is_some() {
+                    // Clicking anywhere but the open lists dismisses them
+                    if self.entry_at(bounds, *pos, &style).is_none() {
+                        self.close();
+                    }
+                    return EventResponse::handled();
+                }
+                EventResponse::default()
+            }
+            InputEvent::PointerUp { pos, .. } if self.open.is_some() => {
+                if let Some((level, index)) = self.entry_at(bounds, *pos, &style) {
+                    self.choose(level, index);
+                    return EventResponse::handled();
+                }
+                EventResponse::default()
+            }
+            InputEvent::KeyDown { event } => {
+                if let Some(index) = self.mnemonic(event) {
+                    self.open_menu(index);
+                    self.move_highlight(true);
+                    ctx.request_focus();
+                    return EventResponse::focus();
+                }
+                if ctx.has_focus() && self.key_down(event) {
+                    return EventResponse::handled();
+                }
+                EventResponse::default()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        // Alt with each title's first letter, handled by `mnemonic`
+        self.menus
+            .iter()
+            .filter_map(|(title, _)| title.chars().next())
+            .filter_map(|first| Shortcut::parse(&format!("Alt+{first}")).ok())
+            .collect()
+    }
+
+    fn on_blur(&mut self) {
+        self.close();
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::MenuBar,
+            value: self.open.map(|open| self.menus[open].0.clone()),
+            focusable: true,
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let widths: Vec<f32> = self
+            .menus
+            .iter()
+            .map(|(title, list)| {
+                list.measure(ctx, &text_style);
+                ctx.text.measure(title, &text_style, None).0
+            })
+            .collect();
+        let width = widths
+            .iter()
+            .map(|width| width + style.padding_h * 2.0)
+            .sum();
+        *self.title_widths.borrow_mut() = widths;
+        Some((width, style.height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_selectable_skips_and_wraps() {
+        let selectable = [true, false, true, true, false];
+        assert_eq!(next_selectable(&selectable, None, true), Some(0));
+        assert_eq!(next_selectable(&selectable, None, false), Some(3));
+        assert_eq!(next_selectable(&selectable, Some(0), true), Some(2));
+        assert_eq!(next_selectable(&selectable, Some(3), true), Some(0));
+        assert_eq!(next_selectable(&selectable, Some(0), false), Some(3));
+        assert_eq!(next_selectable(&[false, false], None, true), None);
+    }
+}
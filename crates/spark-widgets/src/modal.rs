@@ -0,0 +1,229 @@
+//! Modal dialog widget.
+
+use crate::{
+    AccessibleInfo, AccessibleRole, EventContext, EventResponse, PaintContext, Theme, ThemeMode,
+    ThemedStyle, Widget,
+};
+use spark_core::Color;
+use spark_input::{ActionMapper, InputEvent, StandardAction};
+use spark_layout::WidgetId;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use taffy::prelude::*;
+
+/// Opens and closes a [`Modal`] from anywhere, such as a button's click handler.
+#[derive(Clone, Debug, Default)]
+pub struct ModalHandle {
+    open: Arc<AtomicBool>,
+}
+
+impl ModalHandle {
+    /// Create a handle for a closed modal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the modal.
+    pub fn open(&self) {
+        self.open.store(true, Ordering::Relaxed);
+    }
+
+    /// Hide the modal without calling its dismiss handler.
+    pub fn close(&self) {
+        self.open.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether the modal is showing.
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Relaxed)
+    }
+}
+
+/// Style configuration for a modal.
+#[derive(Clone, Debug)]
+pub struct ModalStyle {
+    /// Translucent fill over the UI behind the dialog.
+    pub scrim: Color,
+}
+
+impl Default for ModalStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for ModalStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let alpha = match theme.mode {
+            ThemeMode::Light => 0.4,
+            ThemeMode::Dark => 0.6,
+        };
+        Self {
+            scrim: Color::rgba(0.0, 0.0, 0.0, alpha),
+        }
+    }
+}
+
+/// A dialog centered over a scrim that blocks input to the rest of the UI.
+///
+/// While open, only the dialog content receives events and Tab focus stays
+/// inside it; Escape closes it and calls the dismiss handler. Add it as a
+/// child of the root so the scrim covers the window.
+pub struct Modal {
+    id: WidgetId,
+    content: Vec<Box<dyn Widget>>,
+    handle: ModalHandle,
+    style: Option<ModalStyle>,
+    label: Option<String>,
+    on_dismiss: Option<Box<dyn FnMut() + Send + Sync>>,
+}
+
+impl Modal {
+    /// Create a closed modal showing `content`.
+    pub fn new(content: impl Widget + 'static) -> Self {
+        Self {
+            id: WidgetId::default(),
+            content: vec![Box::new(content)],
+            handle: ModalHandle::new(),
+            style: None,
+            label: None,
+            on_dismiss: None,
+        }
+    }
+
+    /// Share the open state with `handle`, created before the modal.
+    pub fn with_handle(mut self, handle: ModalHandle) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Set whether the modal starts open.
+    pub fn open(self, open: bool) -> Self {
+        self.handle.open.store(open, Ordering::Relaxed);
+        self
+    }
+
+    /// Set the handler called when Escape dismisses the modal.
+    pub fn on_dismiss(mut self, handler: impl FnMut() + Send + Sync + 'static) -> Self {
+        self.on_dismiss = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the dialog title announced by screen readers.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the modal style.
+    pub fn with_style(mut self, style: ModalStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// A handle that opens and closes this modal.
+    pub fn handle(&self) -> ModalHandle {
+        self.handle.clone()
+    }
+
+    /// Whether the modal is showing.
+    pub fn is_open(&self) -> bool {
+        self.handle.is_open()
+    }
+
+    fn dismiss(&mut self) {
+        self.handle.close();
+        if let Some(handler) = &mut self.on_dismiss {
+            handler();
+        }
+    }
+}
+
+impl Widget for Modal {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        if !self.is_open() {
+            return Style {
+                display: Display::None,
+                ..Default::default()
+            };
+        }
+        // Cover the parent and center the content
+        Style {
+            position: Position::Absolute,
+            inset: taffy::Rect {
+                left: length(0.0),
+                right: length(0.0),
+                top: length(0.0),
+                bottom: length(0.0),
+            },
+            justify_content: Some(JustifyContent::Center),
+            align_items: Some(AlignItems::Center),
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        if !self.is_open() {
+            return;
+        }
+        let scrim = ctx.theme().resolve(&self.style).scrim;
+        ctx.fill_rect(ctx.bounds(), scrim);
+    }
+
+    fn event(&mut self, _ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        if !self.is_open() {
+            return EventResponse::default();
+        }
+        if ActionMapper::new().is_action(event, StandardAction::Cancel) {
+            self.dismiss();
+            return EventResponse {
+                relayout: true,
+                ..EventResponse::handled()
+            };
+        }
+        // Clicks that miss the content stop at the scrim
+        if event.is_pointer_event() {
+            return EventResponse {
+                handled: true,
+                ..Default::default()
+            };
+        }
+        EventResponse::default()
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        if self.is_open() {
+            &self.content
+        } else {
+            &[]
+        }
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        if self.is_open() {
+            &mut self.content
+        } else {
+            &mut []
+        }
+    }
+
+    fn is_modal(&self) -> bool {
+        self.is_open()
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Dialog,
+            name: self.label.clone(),
+            ..Default::default()
+        }
+    }
+}
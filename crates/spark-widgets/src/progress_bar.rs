@@ -0,0 +1,176 @@
+//! Progress bar widget.
+
+use crate::{
+    AccessibleInfo, AccessibleRole, LayoutContext, PaintContext, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_layout::WidgetId;
+use taffy::prelude::*;
+
+/// Seconds for the indeterminate segment to sweep across the track.
+const SWEEP_SECONDS: f32 = 1.5;
+/// Indeterminate segment width as a fraction of the track.
+const SEGMENT_FRACTION: f32 = 0.3;
+/// Width used when the layout does not stretch the bar.
+const DEFAULT_WIDTH: f32 = 200.0;
+
+/// Style configuration for a progress bar.
+#[derive(Clone, Debug)]
+pub struct ProgressBarStyle {
+    pub track_color: Color,
+    pub fill_color: Color,
+    /// Thickness of the bar.
+    pub height: f32,
+    pub corner_radius: f32,
+}
+
+impl Default for ProgressBarStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for ProgressBarStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            track_color: colors.surface_alt,
+            fill_color: colors.primary,
+            height: 6.0,
+            corner_radius: 3.0,
+        }
+    }
+}
+
+/// A horizontal bar showing how far a task has progressed.
+///
+/// In indeterminate mode a segment sweeps across the track for as long as
+/// the bar is shown.
+pub struct ProgressBar {
+    id: WidgetId,
+    value: f32,
+    max: f32,
+    indeterminate: bool,
+    style: Option<ProgressBarStyle>,
+}
+
+impl ProgressBar {
+    /// Create an empty progress bar out of 1.0.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            value: 0.0,
+            max: 1.0,
+            indeterminate: false,
+            style: None,
+        }
+    }
+
+    /// Set the progress, from 0 to `max`.
+    pub fn value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Set the value of a finished task.
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Show ongoing work of unknown length instead of a value.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    /// Set the progress bar style.
+    pub fn with_style(mut self, style: ProgressBarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Completed fraction, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.value / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for ProgressBar {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let height = theme.resolve(&self.style).height;
+        Style {
+            min_size: Size {
+                width: length(height * 2.0),
+                height: length(height),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let style = ctx.theme().resolve(&self.style);
+        let height = style.height * ctx.scale_factor;
+        let track = Rect::new(
+            bounds.x,
+            bounds.y + (bounds.height - height) / 2.0,
+            bounds.width,
+            height,
+        );
+        ctx.fill_rounded_rect(track, style.track_color, style.corner_radius);
+
+        if !self.indeterminate {
+            let fill = Rect::new(track.x, track.y, track.width * self.fraction(), height);
+            if fill.width > 0.0 {
+                ctx.fill_rounded_rect(fill, style.fill_color, style.corner_radius);
+            }
+            return;
+        }
+
+        // A segment enters on the left and leaves on the right, eased
+        let t = (ctx.elapsed_time % SWEEP_SECONDS) / SWEEP_SECONDS;
+        let eased = t * t * (3.0 - 2.0 * t);
+        let segment_width = track.width * SEGMENT_FRACTION;
+        let x = track.x - segment_width + (track.width + segment_width) * eased;
+        let segment = Rect::new(x, track.y, segment_width, height);
+        ctx.push_clip(track);
+        ctx.fill_rounded_rect(segment, style.fill_color, style.corner_radius);
+        ctx.pop_clip();
+    }
+
+    fn needs_animation(&self) -> bool {
+        self.indeterminate
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let percent = (self.fraction() * 100.0).round();
+        AccessibleInfo {
+            role: AccessibleRole::ProgressIndicator,
+            value: (!self.indeterminate).then(|| format!("{percent}%")),
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        Some((DEFAULT_WIDTH, ctx.theme.resolve(&self.style).height))
+    }
+}
@@ -0,0 +1,220 @@
+//! Progress ring widget.
+
+use crate::{AccessibleInfo, AccessibleRole, PaintContext, Theme, ThemedStyle, Widget};
+use spark_core::{Color, Rect};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::f32::consts::TAU;
+use taffy::prelude::*;
+
+/// Seconds for the indeterminate arc to go once around.
+const SPIN_SECONDS: f32 = 1.2;
+/// Indeterminate arc length as a fraction of the ring.
+const SPIN_FRACTION: f32 = 0.25;
+
+/// Style configuration for a progress ring.
+#[derive(Clone, Debug)]
+pub struct ProgressRingStyle {
+    pub track_color: Color,
+    pub fill_color: Color,
+    pub text_color: Color,
+    /// Width of the ring.
+    pub thickness: f32,
+    /// Size of the percentage, as a share of the diameter.
+    pub font_scale: f32,
+}
+
+impl Default for ProgressRingStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for ProgressRingStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            track_color: colors.surface_alt,
+            fill_color: colors.primary,
+            text_color: colors.text,
+            thickness: 6.0,
+            font_scale: 0.25,
+        }
+    }
+}
+
+/// A circular gauge filling clockwise from 12 o'clock, with the percentage
+/// in the middle.
+///
+/// In indeterminate mode an arc spins around the track for as long as the
+/// ring is shown.
+pub struct ProgressRing {
+    id: WidgetId,
+    value: f32,
+    max: f32,
+    indeterminate: bool,
+    size: f32,
+    show_label: bool,
+    thickness: Option<f32>,
+    style: Option<ProgressRingStyle>,
+}
+
+impl ProgressRing {
+    /// Create an empty 48 pixel progress ring out of 1.0.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            value: 0.0,
+            max: 1.0,
+            indeterminate: false,
+            size: 48.0,
+            show_label: true,
+            thickness: None,
+            style: None,
+        }
+    }
+
+    /// Set the progress, from 0 to `max`.
+    pub fn value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Set the value of a finished task.
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Show ongoing work of unknown length instead of a value.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    /// Set the diameter in logical pixels.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the width of the ring in logical pixels.
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    /// Show or hide the percentage in the middle; shown by default.
+    pub fn show_label(mut self, show: bool) -> Self {
+        self.show_label = show;
+        self
+    }
+
+    /// Set the progress ring style.
+    pub fn with_style(mut self, style: ProgressRingStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Completed fraction, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.value / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The style for `theme`, with the thickness override applied.
+    fn resolved_style(&self, theme: &Theme) -> ProgressRingStyle {
+        let mut style = theme.resolve(&self.style).into_owned();
+        if let Some(thickness) = self.thickness {
+            style.thickness = thickness;
+        }
+        style
+    }
+}
+
+impl Default for ProgressRing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for ProgressRing {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style {
+            size: Size {
+                width: length(self.size),
+                height: length(self.size),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let scale = ctx.scale_factor;
+        let style = self.resolved_style(ctx.theme());
+        let diameter = bounds.width.min(bounds.height);
+        let circle = Rect::new(
+            bounds.x + (bounds.width - diameter) / 2.0,
+            bounds.y + (bounds.height - diameter) / 2.0,
+            diameter,
+            diameter,
+        );
+        ctx.draw_arc(circle, style.track_color, style.thickness, 0.0, TAU);
+
+        if self.indeterminate {
+            let start = (ctx.elapsed_time % SPIN_SECONDS) / SPIN_SECONDS * TAU;
+            ctx.draw_arc(
+                circle,
+                style.fill_color,
+                style.thickness,
+                start,
+                SPIN_FRACTION * TAU,
+            );
+            return;
+        }
+
+        let fraction = self.fraction();
+        if fraction > 0.0 {
+            ctx.draw_arc(
+                circle,
+                style.fill_color,
+                style.thickness,
+                0.0,
+                fraction * TAU,
+            );
+        }
+        if self.show_label {
+            let text_style = TextStyle::default()
+                .with_size(diameter / scale * style.font_scale)
+                .with_color(style.text_color);
+            let label = format!("{}%", (fraction * 100.0).round());
+            ctx.draw_text_centered(&label, &text_style, circle);
+        }
+    }
+
+    fn needs_animation(&self) -> bool {
+        self.indeterminate
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let percent = (self.fraction() * 100.0).round();
+        AccessibleInfo {
+            role: AccessibleRole::ProgressIndicator,
+            value: (!self.indeterminate).then(|| format!("{percent}%")),
+            ..Default::default()
+        }
+    }
+}
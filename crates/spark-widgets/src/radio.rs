@@ -0,0 +1,488 @@
+//! Radio buttons and the group that keeps their selection exclusive.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext,
+    PaintContext, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{ActionMapper, InputEvent, StandardAction};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use taffy::prelude::*;
+
+/// Index stored while nothing is selected.
+const NO_SELECTION: usize = usize::MAX;
+
+/// Selection shared between a group and its buttons.
+struct GroupShared {
+    selected: AtomicUsize,
+    on_select: Mutex<Option<Box<dyn FnMut(usize) + Send>>>,
+}
+
+impl GroupShared {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            selected: AtomicUsize::new(NO_SELECTION),
+            on_select: Mutex::new(None),
+        })
+    }
+
+    fn selected(&self) -> Option<usize> {
+        let index = self.selected.load(Ordering::Relaxed);
+        (index != NO_SELECTION).then_some(index)
+    }
+
+    /// Select `index`, telling the handler if the selection changed.
+    fn select(&self, index: usize) {
+        if self.selected.swap(index, Ordering::Relaxed) == index {
+            return;
+        }
+        if let Some(handler) = self.on_select.lock().unwrap().as_mut() {
+            handler(index);
+        }
+    }
+}
+
+/// Style configuration for a radio button.
+#[derive(Clone, Debug)]
+pub struct RadioButtonStyle {
+    /// Ring fill.
+    pub ring_color: Color,
+    pub border_color: Color,
+    /// Border while selected.
+    pub border_color_selected: Color,
+    pub border_width: f32,
+    /// Inner dot while selected.
+    pub dot_color: Color,
+    pub text_color: Color,
+    pub text_color_disabled: Color,
+    /// Fill while disabled.
+    pub ring_color_disabled: Color,
+    /// Diameter of the ring.
+    pub size: f32,
+    /// Space between the ring and the label.
+    pub gap: f32,
+    pub font_size: f32,
+}
+
+impl Default for RadioButtonStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for RadioButtonStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            ring_color: colors.surface,
+            border_color: colors.border,
+            border_color_selected: colors.primary,
+            border_width: 1.5,
+            dot_color: colors.primary,
+            text_color: colors.text,
+            text_color_disabled: colors.text_disabled,
+            ring_color_disabled: colors.disabled,
+            size: 18.0,
+            gap: theme.spacing.sm,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// One option in a [`RadioGroup`].
+pub struct RadioButton {
+    id: WidgetId,
+    label: String,
+    style: Option<RadioButtonStyle>,
+    disabled: bool,
+    pressed: bool,
+    /// Position within the group.
+    index: usize,
+    group: Arc<GroupShared>,
+}
+
+impl RadioButton {
+    /// Create a radio button with the given label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            label: label.into(),
+            style: None,
+            disabled: false,
+            pressed: false,
+            index: 0,
+            group: GroupShared::new(),
+        }
+    }
+
+    /// Set the radio button style.
+    pub fn with_style(mut self, style: RadioButtonStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Disable the option; it can't be clicked, focused or reached with arrow keys.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Whether this option is the group's selection.
+    pub fn is_selected(&self) -> bool {
+        self.group.selected() == Some(self.index)
+    }
+}
+
+impl Widget for RadioButton {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let style = theme.resolve(&self.style);
+        Style {
+            min_size: Size {
+                width: length(style.size),
+                height: length(style.size),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let theme = ctx.theme();
+        let style = theme.resolve(&self.style);
+        let scale = ctx.scale_factor;
+        let selected = self.is_selected();
+
+        // A fully rounded square draws the ring at the left edge
+        let size = style.size * scale;
+        let ring = Rect::new(
+            bounds.x,
+            bounds.y + (bounds.height - size) / 2.0,
+            size,
+            size,
+        );
+        let (fill, border) = match (self.disabled, selected) {
+            (true, _) => (style.ring_color_disabled, style.border_color),
+            (false, true) => (style.ring_color, style.border_color_selected),
+            (false, false) => (style.ring_color, style.border_color),
+        };
+        ctx.fill_bordered_rect(ring, fill, style.size / 2.0, style.border_width, border);
+        if selected {
+            let dot = ring.inset(size * 0.25, size * 0.25);
+            let color = if self.disabled {
+                style.text_color_disabled
+            } else {
+                style.dot_color
+            };
+            ctx.fill_rounded_rect(dot, color, style.size / 4.0);
+        }
+
+        // Focus ring (scale offset for HiDPI), only for keyboard focus
+        if ctx.focus_visible() {
+            ctx.fill_bordered_rect(
+                ring.expand(2.0 * scale),
+                Color::TRANSPARENT,
+                style.size / 2.0 + 2.0,
+                2.0,
+                theme.colors.focus_ring,
+            );
+        }
+
+        let text_color = if self.disabled {
+            style.text_color_disabled
+        } else {
+            style.text_color
+        };
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(text_color);
+        ctx.draw_text_aligned(&self.label, &text_style, bounds, size + style.gap * scale);
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        if self.disabled {
+            return EventResponse::default();
+        }
+
+        match event {
+            InputEvent::PointerDown { pos, .. } => {
+                if ctx.contains(*pos) {
+                    self.pressed = true;
+                    return EventResponse::capture();
+                }
+                EventResponse::default()
+            }
+            InputEvent::PointerCancel { .. } if self.pressed => {
+                self.pressed = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                if !self.pressed {
+                    return EventResponse::default();
+                }
+                self.pressed = false;
+                if ctx.contains(*pos) {
+                    self.group.select(self.index);
+                }
+                EventResponse::release()
+            }
+            InputEvent::KeyDown { .. } => {
+                let activate = ActionMapper::new().is_action(event, StandardAction::Activate);
+                if ctx.has_focus() && activate {
+                    self.group.select(self.index);
+                    return EventResponse::handled();
+                }
+                // Arrow keys bubble up to the group
+                EventResponse::default()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        !self.disabled
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let value = if self.is_selected() {
+            "selected"
+        } else {
+            "not selected"
+        };
+        AccessibleInfo {
+            role: AccessibleRole::RadioButton,
+            name: Some(self.label.clone()),
+            value: Some(value.into()),
+            focusable: !self.disabled,
+            disabled: self.disabled,
+            actions: if self.disabled {
+                Vec::new()
+            } else {
+                vec![AccessibleAction::Click]
+            },
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let size = style.size;
+        if self.label.is_empty() {
+            return Some((size, size));
+        }
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let (width, height) = ctx.text.measure(&self.label, &text_style, None);
+        Some((size + style.gap + width, height.max(size)))
+    }
+}
+
+/// Lays out [`RadioButton`]s and keeps at most one of them selected.
+///
+/// Arrow keys move the selection, and focus, between the enabled options.
+pub struct RadioGroup {
+    id: WidgetId,
+    children: Vec<Box<dyn Widget>>,
+    /// Which children can be reached with arrow keys.
+    enabled: Vec<bool>,
+    style: Style,
+    accessibility_label: Option<String>,
+    shared: Arc<GroupShared>,
+}
+
+impl Default for RadioGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RadioGroup {
+    /// Create an empty group with its options in a column.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            children: Vec::new(),
+            enabled: Vec::new(),
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                gap: Size {
+                    width: length(8.0),
+                    height: length(8.0),
+                },
+                ..Default::default()
+            },
+            accessibility_label: None,
+            shared: GroupShared::new(),
+        }
+    }
+
+    /// Add an option; its index is the number of options added before it.
+    pub fn child(mut self, mut button: RadioButton) -> Self {
+        button.index = self.children.len();
+        button.group = self.shared.clone();
+        self.enabled.push(!button.disabled);
+        self.children.push(Box::new(button));
+        self
+    }
+
+    /// Select the option at `index` without calling the handler.
+    pub fn selected(self, index: usize) -> Self {
+        self.shared.selected.store(index, Ordering::Relaxed);
+        self
+    }
+
+    /// Set the handler called with the index of a newly selected option.
+    pub fn on_select(self, handler: impl FnMut(usize) + Send + 'static) -> Self {
+        *self.shared.on_select.lock().unwrap() = Some(Box::new(handler));
+        self
+    }
+
+    /// Lay the options out in a row.
+    pub fn row(mut self) -> Self {
+        self.style.flex_direction = FlexDirection::Row;
+        self
+    }
+
+    /// Set the space between options.
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.style.gap = Size {
+            width: length(gap),
+            height: length(gap),
+        };
+        self
+    }
+
+    /// Set the label announced by screen readers.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
+    /// The selected option's index.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.shared.selected()
+    }
+}
+
+/// The next enabled option after `from` (or before it), wrapping around.
+fn step(enabled: &[bool], from: usize, forward: bool) -> Option<usize> {
+    let len = enabled.len();
+    (1..len)
+        .map(|offset| {
+            if forward {
+                (from + offset) % len
+            } else {
+                (from + len - offset) % len
+            }
+        })
+        .find(|&index| enabled[index])
+}
+
+impl Widget for RadioGroup {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        self.style.clone()
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {
+        // Children are painted by the framework traversal
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        if !matches!(event, InputEvent::KeyDown { .. }) {
+            return EventResponse::default();
+        }
+        let Some(focused) = ctx.focus.focused() else {
+            return EventResponse::default();
+        };
+        let Some(from) = self.children.iter().position(|child| child.id() == focused) else {
+            return EventResponse::default();
+        };
+
+        let mapper = ActionMapper::new();
+        let forward = if mapper.is_action(event, StandardAction::MoveDown)
+            || mapper.is_action(event, StandardAction::MoveRight)
+        {
+            true
+        } else if mapper.is_action(event, StandardAction::MoveUp)
+            || mapper.is_action(event, StandardAction::MoveLeft)
+        {
+            false
+        } else {
+            return EventResponse::default();
+        };
+
+        if let Some(index) = step(&self.enabled, from, forward) {
+            self.shared.select(index);
+            ctx.focus.set_focus(self.children[index].id());
+        }
+        EventResponse::handled()
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::RadioGroup,
+            name: self.accessibility_label.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        &mut self.children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrow_steps_wrap_and_skip_disabled() {
+        let enabled = [true, false, true, true];
+        assert_eq!(step(&enabled, 0, true), Some(2));
+        assert_eq!(step(&enabled, 3, true), Some(0));
+        assert_eq!(step(&enabled, 0, false), Some(3));
+        assert_eq!(step(&enabled, 2, false), Some(0));
+        assert_eq!(step(&[true], 0, true), None);
+    }
+
+    #[test]
+    fn test_selection_is_shared_and_reported_once() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let log = reported.clone();
+        let group = RadioGroup::new()
+            .child(RadioButton::new("A"))
+            .child(RadioButton::new("B"))
+            .on_select(move |index| log.lock().unwrap().push(index));
+        assert_eq!(group.selected_index(), None);
+
+        group.shared.select(1);
+        group.shared.select(1);
+        assert_eq!(group.selected_index(), Some(1));
+        assert_eq!(*reported.lock().unwrap(), vec![1]);
+    }
+}
@@ -1,13 +1,19 @@
 //! Scrollable container widget.
 
-use crate::{EventContext, EventResponse, PaintContext, Widget};
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleScroll, AccessibleValue,
+    EventContext, EventResponse, PaintContext, Theme, ThemedStyle, Widget,
+};
 use std::cell::Cell;
 use spark_core::{Color, Rect};
-use spark_input::InputEvent;
+use spark_input::{InputEvent, SCROLL_LINE_HEIGHT};
 use spark_layout::WidgetId;
 use taffy::prelude::*;
 use taffy::{Overflow, Point};
 
+/// Fraction of the viewport scrolled by an accessibility scroll action.
+const SCROLL_PAGE_FRACTION: f32 = 0.8;
+
 /// Scroll direction.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ScrollDirection {
@@ -42,6 +48,19 @@ impl Default for ScrollbarStyle {
     }
 }
 
+impl ThemedStyle for ScrollbarStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let c = &theme.colors;
+        Self {
+            track_color: c.border.with_alpha(0.5),
+            thumb_color: c.disabled,
+            thumb_hover_color: c.text_muted,
+            width: 8.0,
+            corner_radius: theme.radius.sm,
+        }
+    }
+}
+
 /// A scrollable container widget.
 pub struct Scroll {
     id: WidgetId,
@@ -50,8 +69,12 @@ pub struct Scroll {
     offset_x: f32,
     offset_y: f32,
     content_size: Cell<(f32, f32)>,
-    style: ScrollbarStyle,
+    /// Viewport size in logical pixels, from the last paint.
+    viewport_size: Cell<(f32, f32)>,
+    style: Option<ScrollbarStyle>,
     layout_style: Style,
+    /// Logical pixels scrolled per wheel line.
+    line_height: f32,
     dragging_scrollbar: bool,
     hover_scrollbar: bool,
     debug_overlay: bool,
@@ -73,7 +96,8 @@ impl Scroll {
             offset_x: 0.0,
             offset_y: 0.0,
             content_size: Cell::new((0.0, 0.0)),
-            style: ScrollbarStyle::default(),
+            viewport_size: Cell::new((0.0, 0.0)),
+            style: None,
             layout_style: Style {
                 display: Display::Flex,
                 flex_direction: FlexDirection::Column,
@@ -83,6 +107,7 @@ impl Scroll {
                 },
                 ..Default::default()
             },
+            line_height: SCROLL_LINE_HEIGHT,
             dragging_scrollbar: false,
             hover_scrollbar: false,
             debug_overlay: false,
@@ -168,9 +193,16 @@ impl Scroll {
         self
     }
 
+    /// Set how far one notch of a mouse wheel scrolls, in logical pixels.
+    /// Trackpads and touch scroll by the distance moved regardless.
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
     /// Set the scrollbar style.
     pub fn scrollbar_style(mut self, style: ScrollbarStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
@@ -281,11 +313,16 @@ impl Scroll {
         self.content_size.set(size);
     }
 
-    fn scrollbar_rect(&self, viewport: Rect) -> Option<Rect> {
-        self.scrollbar_rect_for(viewport, self.content_size.get())
+    fn scrollbar_rect(&self, viewport: Rect, width: f32) -> Option<Rect> {
+        self.scrollbar_rect_for(viewport, self.content_size.get(), width)
     }
 
-    fn scrollbar_rect_for(&self, viewport: Rect, content_size: (f32, f32)) -> Option<Rect> {
+    fn scrollbar_rect_for(
+        &self,
+        viewport: Rect,
+        content_size: (f32, f32),
+        width: f32,
+    ) -> Option<Rect> {
         match self.direction {
             ScrollDirection::Vertical | ScrollDirection::Both => {
                 if content_size.1 <= viewport.height {
@@ -299,9 +336,9 @@ impl Scroll {
                     * (track_height - thumb_height);
 
                 Some(Rect::new(
-                    viewport.x + viewport.width - self.style.width,
+                    viewport.x + viewport.width - width,
                     viewport.y + thumb_y,
-                    self.style.width,
+                    width,
                     thumb_height,
                 ))
             }
@@ -317,9 +354,9 @@ impl Scroll {
 
                 Some(Rect::new(
                     viewport.x + thumb_x,
-                    viewport.y + viewport.height - self.style.width,
+                    viewport.y + viewport.height - width,
                     thumb_width,
-                    self.style.width,
+                    width,
                 ))
             }
         }
@@ -335,7 +372,7 @@ impl Widget for Scroll {
         self.id = id;
     }
 
-    fn style(&self) -> Style {
+    fn style(&self, _theme: &Theme) -> Style {
         self.layout_style.clone()
     }
 
@@ -343,11 +380,79 @@ impl Widget for Scroll {
         true
     }
 
+    fn accessibility_info(&self) -> AccessibleInfo {
+        let (content_w, content_h) = self.content_size.get();
+        let (view_w, view_h) = self.viewport_size.get();
+        let actions = match self.direction {
+            ScrollDirection::Vertical => {
+                vec![AccessibleAction::ScrollUp, AccessibleAction::ScrollDown]
+            }
+            ScrollDirection::Horizontal => {
+                vec![AccessibleAction::ScrollLeft, AccessibleAction::ScrollRight]
+            }
+            ScrollDirection::Both => vec![
+                AccessibleAction::ScrollUp,
+                AccessibleAction::ScrollDown,
+                AccessibleAction::ScrollLeft,
+                AccessibleAction::ScrollRight,
+            ],
+        };
+        AccessibleInfo {
+            role: AccessibleRole::ScrollView,
+            actions,
+            scroll: Some(AccessibleScroll {
+                x: self.offset_x,
+                y: self.offset_y,
+                max_x: (content_w - view_w).max(0.0),
+                max_y: (content_h - view_h).max(0.0),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn scroll_offset(&self) -> Option<(f32, f32)> {
+        Some(self.offset())
+    }
+
+    fn set_scroll_offset(&mut self, x: f32, y: f32) {
+        self.set_offset(x, y);
+    }
+
+    fn scroll_to_visible(&mut self, rect: Rect, viewport: Rect) {
+        Scroll::scroll_to_visible(self, rect, viewport);
+    }
+
+    fn accessibility_action(
+        &mut self,
+        ctx: &mut EventContext,
+        action: AccessibleAction,
+        _value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        let bounds = ctx.bounds();
+        // Scroll by most of a page, keeping some context on screen.
+        let page_x = bounds.width * SCROLL_PAGE_FRACTION;
+        let page_y = bounds.height * SCROLL_PAGE_FRACTION;
+        match action {
+            AccessibleAction::ScrollUp => self.offset_y -= page_y,
+            AccessibleAction::ScrollDown => self.offset_y += page_y,
+            AccessibleAction::ScrollLeft => self.offset_x -= page_x,
+            AccessibleAction::ScrollRight => self.offset_x += page_x,
+            _ => return EventResponse::default(),
+        }
+        self.update_content_size(ctx.layout_tree);
+        self.clamp_offset(bounds);
+        EventResponse::handled()
+    }
+
     fn paint(&self, ctx: &mut PaintContext) {
         let bounds = ctx.bounds();
 
         self.content_size
             .set(self.content_size_from_tree(ctx.layout_tree));
+        self.viewport_size.set((
+            bounds.width / ctx.scale_factor,
+            bounds.height / ctx.scale_factor,
+        ));
 
         // Clip content
         ctx.push_clip(bounds);
@@ -361,6 +466,7 @@ impl Widget for Scroll {
     fn paint_after_children(&self, ctx: &mut PaintContext) {
         let bounds = ctx.bounds();
         let scale_factor = ctx.scale_factor;
+        let style = ctx.theme().resolve(&self.style);
 
         // Pop translation and clip in reverse order
         ctx.pop_translation();
@@ -375,43 +481,34 @@ impl Widget for Scroll {
         );
 
         let content_size = self.content_size_from_tree(ctx.layout_tree);
-        if let Some(scrollbar) = self.scrollbar_rect_for(logical_bounds, content_size) {
+        let scrollbar = self.scrollbar_rect_for(logical_bounds, content_size, style.width);
+        if let Some(scrollbar) = scrollbar {
             let track_logical = match self.direction {
                 ScrollDirection::Vertical | ScrollDirection::Both => Rect::new(
-                    logical_bounds.x + logical_bounds.width - self.style.width,
+                    logical_bounds.x + logical_bounds.width - style.width,
                     logical_bounds.y,
-                    self.style.width,
+                    style.width,
                     logical_bounds.height,
                 ),
                 ScrollDirection::Horizontal => Rect::new(
                     logical_bounds.x,
-                    logical_bounds.y + logical_bounds.height - self.style.width,
+                    logical_bounds.y + logical_bounds.height - style.width,
                     logical_bounds.width,
-                    self.style.width,
+                    style.width,
                 ),
             };
 
-            let track = Rect::new(
-                track_logical.x * scale_factor,
-                track_logical.y * scale_factor,
-                track_logical.width * scale_factor,
-                track_logical.height * scale_factor,
-            );
-            ctx.fill_rounded_rect(track, self.style.track_color, self.style.corner_radius);
+            let track = track_logical.scale(scale_factor);
+            ctx.fill_rounded_rect(track, style.track_color, style.corner_radius);
 
-            let scrollbar = Rect::new(
-                scrollbar.x * scale_factor,
-                scrollbar.y * scale_factor,
-                scrollbar.width * scale_factor,
-                scrollbar.height * scale_factor,
-            );
+            let scrollbar = scrollbar.scale(scale_factor);
 
             let thumb_color = if self.hover_scrollbar || self.dragging_scrollbar {
-                self.style.thumb_hover_color
+                style.thumb_hover_color
             } else {
-                self.style.thumb_color
+                style.thumb_color
             };
-            ctx.fill_rounded_rect(scrollbar, thumb_color, self.style.corner_radius);
+            ctx.fill_rounded_rect(scrollbar, thumb_color, style.corner_radius);
         }
 
         if self.debug_overlay {
@@ -453,30 +550,48 @@ impl Widget for Scroll {
 
     fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
         let bounds = ctx.bounds();
+        let scrollbar_width = ctx.theme().resolve(&self.style).width;
 
         self.update_content_size(ctx.layout_tree);
 
         match event {
-            InputEvent::Scroll { delta, pos } => {
+            InputEvent::Scroll {
+                delta,
+                pos,
+                modifiers,
+            } => {
                 if ctx.contains(*pos) {
+                    let before = self.offset();
+                    let delta = delta.to_pixels(self.line_height);
+                    // Shift turns a vertical wheel horizontal, unless the
+                    // platform already did
+                    let delta = if modifiers.shift() && delta.x == 0.0 {
+                        glam::Vec2::new(delta.y, 0.0)
+                    } else {
+                        delta
+                    };
                     match self.direction {
                         ScrollDirection::Vertical => {
-                            self.offset_y -= delta.y * 20.0;
+                            self.offset_y -= delta.y;
                         }
                         ScrollDirection::Horizontal => {
-                            self.offset_x -= delta.x * 20.0;
+                            self.offset_x -= delta.x;
                         }
                         ScrollDirection::Both => {
-                            self.offset_x -= delta.x * 20.0;
-                            self.offset_y -= delta.y * 20.0;
+                            self.offset_x -= delta.x;
+                            self.offset_y -= delta.y;
                         }
                     }
                     self.clamp_offset(bounds);
-                    return EventResponse::handled();
+                    // At the end, or with nothing to scroll, let an outer
+                    // container scroll instead
+                    if self.offset() != before {
+                        return EventResponse::handled();
+                    }
                 }
             }
-            InputEvent::PointerMove { pos } => {
-                if let Some(scrollbar) = self.scrollbar_rect(bounds) {
+            InputEvent::PointerMove { pos, .. } => {
+                if let Some(scrollbar) = self.scrollbar_rect(bounds, scrollbar_width) {
                     let was_hover = self.hover_scrollbar;
                     self.hover_scrollbar = scrollbar.contains(*pos);
                     if was_hover != self.hover_scrollbar {
@@ -487,6 +602,20 @@ impl Widget for Scroll {
                     }
                 }
             }
+            InputEvent::PointerCancel { .. } if self.dragging_scrollbar => {
+                self.dragging_scrollbar = false;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerLeave if self.hover_scrollbar => {
+                self.hover_scrollbar = false;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
             _ => {}
         }
 
@@ -0,0 +1,318 @@
+//! Search field widget.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, CursorIcon, EventContext,
+    EventResponse, PaintContext, TextInput, TextInputStyle, Theme, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{InputEvent, Key, NamedKey};
+use spark_layout::WidgetId;
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+use taffy::prelude::*;
+
+/// Width reserved at each end for the magnifier and the clear button.
+const ICON_AREA: f32 = 32.0;
+/// Diameter of the magnifier lens and the clear button, in logical pixels.
+const ICON_SIZE: f32 = 12.0;
+/// Quiet time after the last edit before `on_change` fires.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+type SearchHandler = Box<dyn FnMut(&str) + Send + Sync>;
+
+/// A text field for search queries, with a magnifier icon and a clear button.
+///
+/// `on_search` fires on Enter. `on_change` is debounced: it fires once the
+/// value has stopped changing for a moment, so handlers can run queries.
+/// Escape clears a non-empty field, then releases focus.
+pub struct SearchInput {
+    id: WidgetId,
+    input: TextInput,
+    on_search: Option<SearchHandler>,
+    /// Called while painting once edits settle, hence the cell.
+    on_change: RefCell<Option<SearchHandler>>,
+    debounce: f32,
+    /// Set by edits; cleared once `on_change` has seen the value.
+    change_pending: Cell<bool>,
+    /// When the pending change was first painted, in `elapsed_time` seconds.
+    change_painted_at: Cell<Option<f32>>,
+    clear_hovered: bool,
+    clear_pressed: bool,
+}
+
+impl SearchInput {
+    /// Create an empty search field.
+    pub fn new() -> Self {
+        let mut input = TextInput::new();
+        input.set_padding_h(ICON_AREA);
+        Self {
+            id: WidgetId::default(),
+            input,
+            on_search: None,
+            on_change: RefCell::new(None),
+            debounce: DEFAULT_DEBOUNCE.as_secs_f32(),
+            change_pending: Cell::new(false),
+            change_painted_at: Cell::new(None),
+            clear_hovered: false,
+            clear_pressed: false,
+        }
+    }
+
+    /// Set the initial query.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.input = self.input.value(value);
+        self
+    }
+
+    /// Set the text shown while the field is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.input = self.input.placeholder(placeholder);
+        self
+    }
+
+    /// Set the handler called with the query when Enter is pressed.
+    pub fn on_search(mut self, handler: impl FnMut(&str) + Send + Sync + 'static) -> Self {
+        self.on_search = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the handler called with the query once typing pauses.
+    pub fn on_change(self, handler: impl FnMut(&str) + Send + Sync + 'static) -> Self {
+        *self.on_change.borrow_mut() = Some(Box::new(handler));
+        self
+    }
+
+    /// Set how long the query must stay unchanged before `on_change` fires.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.debounce = delay.as_secs_f32();
+        self
+    }
+
+    /// Set the field style; the padding stays wide enough for the icons.
+    pub fn with_style(mut self, style: TextInputStyle) -> Self {
+        self.input = self.input.with_style(style);
+        self
+    }
+
+    /// Get the current query.
+    pub fn get_value(&self) -> &str {
+        self.input.get_value()
+    }
+
+    fn clear(&mut self) {
+        self.input.replace_value("");
+        self.change_pending.set(true);
+        self.change_painted_at.set(None);
+    }
+
+    /// The clear button, in the same units as `bounds`.
+    fn clear_rect(bounds: Rect, scale: f32) -> Rect {
+        let area = ICON_AREA * scale;
+        Rect::new(
+            bounds.x + bounds.width - area,
+            bounds.y,
+            area,
+            bounds.height,
+        )
+    }
+
+    /// Fire `on_change` once the pending change has been painted for
+    /// longer than the debounce delay; until then keep frames coming.
+    fn flush_change(&self, ctx: &PaintContext) {
+        if !self.change_pending.get() {
+            return;
+        }
+        let now = ctx.elapsed_time;
+        let since = self.change_painted_at.get().unwrap_or(now);
+        self.change_painted_at.set(Some(since));
+        if now - since < self.debounce {
+            ctx.request_animation_frame();
+            return;
+        }
+        self.change_pending.set(false);
+        self.change_painted_at.set(None);
+        if let Some(handler) = self.on_change.borrow_mut().as_mut() {
+            handler(self.input.get_value());
+        }
+    }
+
+    fn paint_magnifier(ctx: &mut PaintContext, bounds: Rect, color: Color) {
+        let scale = ctx.scale_factor;
+        let size = ICON_SIZE * scale;
+        let lens = Rect::new(
+            bounds.x + (ICON_AREA * scale - size) / 2.0 - scale,
+            bounds.y + (bounds.height - size) / 2.0 - scale,
+            size * 0.8,
+            size * 0.8,
+        );
+        ctx.fill_bordered_rect(lens, Color::TRANSPARENT, ICON_SIZE * 0.4, 1.5, color);
+        let handle_start = (lens.x + lens.width * 0.85, lens.y + lens.height * 0.85);
+        let handle_end = (lens.x + size, lens.y + size);
+        ctx.draw_line(handle_start, handle_end, 1.5 * scale, color);
+    }
+
+    fn paint_clear_button(&self, ctx: &mut PaintContext, button: Rect, theme: &Theme) {
+        let scale = ctx.scale_factor;
+        let size = ICON_SIZE * scale;
+        let (cx, cy) = (
+            button.x + button.width / 2.0,
+            button.y + button.height / 2.0,
+        );
+        if self.clear_hovered {
+            let circle = Rect::new(cx - size * 0.75, cy - size * 0.75, size * 1.5, size * 1.5);
+            ctx.fill_rounded_rect(circle, theme.colors.surface_alt, ICON_SIZE * 0.75);
+        }
+        let arm = size * 0.3;
+        let color = theme.colors.text_muted;
+        ctx.draw_line(
+            (cx - arm, cy - arm),
+            (cx + arm, cy + arm),
+            1.5 * scale,
+            color,
+        );
+        ctx.draw_line(
+            (cx - arm, cy + arm),
+            (cx + arm, cy - arm),
+            1.5 * scale,
+            color,
+        );
+    }
+}
+
+impl Default for SearchInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for SearchInput {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        self.input.style(theme)
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        self.flush_change(ctx);
+        self.input.paint(ctx);
+
+        let bounds = ctx.bounds();
+        let theme = ctx.theme();
+        Self::paint_magnifier(ctx, bounds, theme.colors.text_muted);
+        if !self.input.get_value().is_empty() {
+            let button = Self::clear_rect(bounds, ctx.scale_factor);
+            self.paint_clear_button(ctx, button, theme);
+        }
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let clear = Self::clear_rect(ctx.bounds(), 1.0);
+        let has_value = !self.input.get_value().is_empty();
+        match event {
+            InputEvent::PointerMove { pos, .. } => {
+                let hovered = has_value && clear.contains(*pos);
+                let changed = hovered != self.clear_hovered;
+                self.clear_hovered = hovered;
+                // The field shows the I-beam, except over the clear button
+                let mut response = self.input.event(ctx, event);
+                response.repaint |= changed;
+                if hovered {
+                    response.cursor = Some(CursorIcon::Pointer);
+                }
+                return response;
+            }
+            InputEvent::PointerLeave if self.clear_hovered => {
+                self.clear_hovered = false;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerDown { pos, .. } if has_value && clear.contains(*pos) => {
+                self.clear_pressed = true;
+                return EventResponse::capture();
+            }
+            InputEvent::PointerCancel { .. } if self.clear_pressed => {
+                self.clear_pressed = false;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerUp { pos, .. } if self.clear_pressed => {
+                self.clear_pressed = false;
+                if clear.contains(*pos) {
+                    self.clear();
+                    self.clear_hovered = false;
+                    ctx.request_focus();
+                    return EventResponse {
+                        release_pointer: true,
+                        ..EventResponse::focus()
+                    };
+                }
+                return EventResponse::release();
+            }
+            InputEvent::KeyDown { event } if ctx.has_focus() => match &event.key {
+                Key::Named(NamedKey::Escape) if has_value => {
+                    self.clear();
+                    return EventResponse::handled();
+                }
+                Key::Named(NamedKey::Enter) => {
+                    if let Some(handler) = &mut self.on_search {
+                        handler(self.input.get_value());
+                    }
+                    return EventResponse::handled();
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+
+        let before = self.input.get_value().to_string();
+        let response = self.input.event(ctx, event);
+        if self.input.get_value() != before {
+            self.change_pending.set(true);
+            self.change_painted_at.set(None);
+        }
+        response
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::SearchField,
+            ..self.input.accessibility_info()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        let response = self.input.accessibility_action(ctx, action, value);
+        if response.handled {
+            self.change_pending.set(true);
+            self.change_painted_at.set(None);
+        }
+        response
+    }
+
+    fn on_focus(&mut self) {
+        self.input.on_focus();
+    }
+
+    fn on_blur(&mut self) {
+        self.input.on_blur();
+    }
+}
@@ -0,0 +1,355 @@
+//! Segmented control widget.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, EventContext, EventResponse,
+    LayoutContext, PaintContext, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{ActionMapper, InputEvent, StandardAction};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::cell::Cell;
+use taffy::prelude::*;
+
+/// Seconds the selection pill takes to slide to a new segment.
+const PILL_ANIMATION_SECONDS: f32 = 0.15;
+
+/// Style configuration for a segmented control.
+#[derive(Clone, Debug)]
+pub struct SegmentedControlStyle {
+    pub background: Color,
+    /// Fill of the pill behind the selected segment.
+    pub selected_background: Color,
+    pub text_color: Color,
+    pub selected_text_color: Color,
+    pub corner_radius: f32,
+    /// Space between the pill and the control edge.
+    pub inset: f32,
+    /// Horizontal space around the widest label in each segment.
+    pub segment_padding: f32,
+    pub height: f32,
+    pub font_size: f32,
+}
+
+impl Default for SegmentedControlStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for SegmentedControlStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            background: colors.surface_alt,
+            selected_background: colors.primary,
+            text_color: colors.text,
+            selected_text_color: colors.on_primary,
+            corner_radius: theme.radius.md,
+            inset: 2.0,
+            segment_padding: theme.spacing.md,
+            height: 32.0,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// The segment under `x` when `count` equal segments span `bounds`.
+fn segment_at(bounds: Rect, count: usize, x: f32) -> Option<usize> {
+    if count == 0 || bounds.width <= 0.0 || x < bounds.x || x >= bounds.x + bounds.width {
+        return None;
+    }
+    let index = ((x - bounds.x) / bounds.width * count as f32) as usize;
+    Some(index.min(count - 1))
+}
+
+/// A row of equal-width segments, one of which is selected, for switching
+/// between views.
+pub struct SegmentedControl {
+    id: WidgetId,
+    segments: Vec<String>,
+    selected: usize,
+    style: Option<SegmentedControlStyle>,
+    /// The segment a press started on.
+    pressed: Option<usize>,
+    on_change: Option<Box<dyn FnMut(usize) + Send + Sync>>,
+    accessibility_label: Option<String>,
+    /// The selection last painted, to notice changes while painting.
+    painted_selected: Cell<usize>,
+    /// Pill position last painted, in segments from the left.
+    painted_position: Cell<f32>,
+    /// Where the pill started sliding from and when, in `elapsed_time` seconds.
+    slide: Cell<Option<(f32, f32)>>,
+}
+
+impl SegmentedControl {
+    /// Create a control with one segment per label; the first is selected.
+    pub fn new<S: Into<String>>(segments: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            segments: segments.into_iter().map(Into::into).collect(),
+            selected: 0,
+            style: None,
+            pressed: None,
+            on_change: None,
+            accessibility_label: None,
+            painted_selected: Cell::new(0),
+            painted_position: Cell::new(0.0),
+            slide: Cell::new(None),
+        }
+    }
+
+    /// Set the initially selected segment.
+    pub fn selected(mut self, index: usize) -> Self {
+        if index < self.segments.len() {
+            self.selected = index;
+            self.painted_selected.set(index);
+            self.painted_position.set(index as f32);
+        }
+        self
+    }
+
+    /// Set the handler called with the index of a newly selected segment.
+    pub fn on_change(mut self, handler: impl FnMut(usize) + Send + Sync + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the name announced by screen readers.
+    pub fn accessibility_label(mut self, label: impl Into<String>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
+    /// Set the segmented control style.
+    pub fn with_style(mut self, style: SegmentedControlStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Index of the selected segment.
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    fn select(&mut self, index: usize) {
+        if index == self.selected || index >= self.segments.len() {
+            return;
+        }
+        self.selected = index;
+        if let Some(handler) = &mut self.on_change {
+            handler(index);
+        }
+    }
+
+    /// Pill position in segments from the left, easing toward the selection.
+    fn pill_position(&self, ctx: &PaintContext) -> f32 {
+        let now = ctx.elapsed_time;
+        if self.painted_selected.replace(self.selected) != self.selected {
+            // Start from wherever the pill is, even mid-slide
+            self.slide.set(Some((self.painted_position.get(), now)));
+        }
+        let target = self.selected as f32;
+        let position = match self.slide.get() {
+            Some((from, start)) => {
+                let progress = ((now - start) / PILL_ANIMATION_SECONDS).clamp(0.0, 1.0);
+                if progress < 1.0 {
+                    ctx.request_animation_frame();
+                } else {
+                    self.slide.set(None);
+                }
+                let eased = progress * progress * (3.0 - 2.0 * progress);
+                from + (target - from) * eased
+            }
+            None => target,
+        };
+        self.painted_position.set(position);
+        position
+    }
+}
+
+impl Widget for SegmentedControl {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let style = theme.resolve(&self.style);
+        Style {
+            min_size: Size {
+                width: auto(),
+                height: length(style.height),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let theme = ctx.theme();
+        let style = theme.resolve(&self.style);
+        let scale = ctx.scale_factor;
+        ctx.fill_rounded_rect(bounds, style.background, style.corner_radius);
+        if self.segments.is_empty() {
+            return;
+        }
+
+        let segment_width = bounds.width / self.segments.len() as f32;
+        let inset = style.inset * scale;
+        let position = self.pill_position(ctx);
+        let pill = Rect::new(
+            bounds.x + segment_width * position + inset,
+            bounds.y + inset,
+            segment_width - inset * 2.0,
+            bounds.height - inset * 2.0,
+        );
+        let pill_radius = (style.corner_radius - style.inset).max(0.0);
+        ctx.fill_rounded_rect(pill, style.selected_background, pill_radius);
+
+        for (index, label) in self.segments.iter().enumerate() {
+            // Labels change color as the pill passes under them
+            let coverage = (1.0 - (position - index as f32).abs()).clamp(0.0, 1.0);
+            let color = style.text_color.lerp(style.selected_text_color, coverage);
+            let text_style = TextStyle::default()
+                .with_size(style.font_size)
+                .with_color(color);
+            let segment = Rect::new(
+                bounds.x + segment_width * index as f32,
+                bounds.y,
+                segment_width,
+                bounds.height,
+            );
+            ctx.draw_text_centered(label, &text_style, segment);
+        }
+
+        // Focus ring (scale offset for HiDPI), only for keyboard focus
+        if ctx.focus_visible() {
+            ctx.fill_bordered_rect(
+                bounds.expand(2.0 * scale),
+                Color::TRANSPARENT,
+                style.corner_radius + 2.0,
+                2.0,
+                theme.colors.focus_ring,
+            );
+        }
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let bounds = ctx.bounds();
+        match event {
+            InputEvent::PointerDown { pos, .. } if ctx.contains(*pos) => {
+                self.pressed = segment_at(bounds, self.segments.len(), pos.x);
+                EventResponse::capture()
+            }
+            InputEvent::PointerCancel { .. } if self.pressed.is_some() => {
+                self.pressed = None;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                let Some(pressed) = self.pressed.take() else {
+                    return EventResponse::default();
+                };
+                if ctx.contains(*pos)
+                    && segment_at(bounds, self.segments.len(), pos.x) == Some(pressed)
+                {
+                    self.select(pressed);
+                }
+                EventResponse::release()
+            }
+            InputEvent::KeyDown { .. } if ctx.has_focus() => {
+                let mapper = ActionMapper::new();
+                let index = if mapper.is_action(event, StandardAction::MoveRight) {
+                    self.selected + 1
+                } else if mapper.is_action(event, StandardAction::MoveLeft) {
+                    self.selected.saturating_sub(1)
+                } else {
+                    return EventResponse::default();
+                };
+                self.select(index);
+                EventResponse::handled()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        !self.segments.is_empty()
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::RadioGroup,
+            name: self.accessibility_label.clone(),
+            value: self.segments.get(self.selected).cloned(),
+            focusable: true,
+            actions: vec![
+                AccessibleAction::Increment,
+                AccessibleAction::Decrement,
+                AccessibleAction::SetValue,
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        let index = match (action, value) {
+            (AccessibleAction::Increment, _) => self.selected + 1,
+            (AccessibleAction::Decrement, _) => self.selected.saturating_sub(1),
+            // A segment's label, or its index
+            (AccessibleAction::SetValue, Some(AccessibleValue::Text(label))) => {
+                match self.segments.iter().position(|segment| segment == label) {
+                    Some(index) => index,
+                    None => return EventResponse::default(),
+                }
+            }
+            (AccessibleAction::SetValue, Some(AccessibleValue::Number(index))) => *index as usize,
+            _ => return EventResponse::default(),
+        };
+        self.select(index);
+        EventResponse::handled()
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let (widest, tallest) = self
+            .segments
+            .iter()
+            .map(|label| ctx.text.measure(label, &text_style, None))
+            .fold((0.0_f32, 0.0_f32), |(w, h), (width, height)| {
+                (w.max(width), h.max(height))
+            });
+        let segment_width = widest + style.segment_padding * 2.0;
+        Some((
+            segment_width * self.segments.len() as f32,
+            style.height.max(tallest + style.inset * 2.0),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_at_splits_bounds_evenly() {
+        let bounds = Rect::new(10.0, 0.0, 300.0, 32.0);
+        assert_eq!(segment_at(bounds, 3, 10.0), Some(0));
+        assert_eq!(segment_at(bounds, 3, 110.0), Some(1));
+        assert_eq!(segment_at(bounds, 3, 309.0), Some(2));
+        assert_eq!(segment_at(bounds, 3, 310.0), None);
+        assert_eq!(segment_at(bounds, 0, 50.0), None);
+    }
+}
@@ -0,0 +1,433 @@
+//! Dropdown select widget.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext,
+    PaintContext, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{ActionMapper, InputEvent, StandardAction};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use taffy::prelude::*;
+
+/// Options shown before the open list scrolls.
+const MAX_VISIBLE_ROWS: usize = 8;
+/// Space between the field and the open list, in logical pixels.
+const LIST_GAP: f32 = 4.0;
+/// Width reserved for the chevron, in logical pixels.
+const CHEVRON_SPACE: f32 = 20.0;
+
+/// Style configuration for a select.
+#[derive(Clone, Debug)]
+pub struct SelectStyle {
+    pub background: Color,
+    pub border_color: Color,
+    pub border_width: f32,
+    pub text_color: Color,
+    pub chevron_color: Color,
+    /// Fill of the open option list.
+    pub list_background: Color,
+    /// Fill behind the highlighted option.
+    pub highlight: Color,
+    pub corner_radius: f32,
+    pub padding_h: f32,
+    pub padding_v: f32,
+    /// Height of each option in the open list.
+    pub row_height: f32,
+    pub font_size: f32,
+}
+
+impl Default for SelectStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for SelectStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            background: colors.surface,
+            border_color: colors.border,
+            border_width: 1.0,
+            text_color: colors.text,
+            chevron_color: colors.text_muted,
+            list_background: colors.surface,
+            highlight: colors.selection,
+            corner_radius: theme.radius.md,
+            padding_h: theme.spacing.md,
+            padding_v: theme.spacing.sm,
+            row_height: 28.0,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// A field showing the chosen option, which opens a list of options when clicked.
+pub struct Select {
+    id: WidgetId,
+    options: Vec<String>,
+    selected: Option<usize>,
+    style: Option<SelectStyle>,
+    open: bool,
+    pressed: bool,
+    /// Option under the pointer or keyboard highlight while open.
+    highlighted: usize,
+    /// First option shown in the open list.
+    first_visible: usize,
+    /// Scrolling of the open list not yet amounting to a whole row, in
+    /// logical pixels.
+    scroll_rest: f32,
+    on_change: Option<Box<dyn FnMut(usize, &str) + Send + Sync>>,
+}
+
+impl Select {
+    /// Create a select with the given options and nothing chosen.
+    pub fn new<S: Into<String>>(options: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            options: options.into_iter().map(Into::into).collect(),
+            selected: None,
+            style: None,
+            open: false,
+            pressed: false,
+            highlighted: 0,
+            first_visible: 0,
+            scroll_rest: 0.0,
+            on_change: None,
+        }
+    }
+
+    /// Choose the option at `index`.
+    pub fn selected(mut self, index: usize) -> Self {
+        self.selected = (index < self.options.len()).then_some(index);
+        self
+    }
+
+    /// Set the handler called with the index and label of a newly chosen option.
+    pub fn on_change(mut self, handler: impl FnMut(usize, &str) + Send + Sync + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the select style.
+    pub fn with_style(mut self, style: SelectStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The chosen option's index.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    fn visible_rows(&self) -> usize {
+        self.options.len().min(MAX_VISIBLE_ROWS)
+    }
+
+    /// The open list below `field`, with lengths multiplied by `scale`.
+    fn list_rect(&self, field: Rect, scale: f32, row_height: f32) -> Rect {
+        Rect::new(
+            field.x,
+            field.y + field.height + LIST_GAP * scale,
+            field.width,
+            self.visible_rows() as f32 * row_height * scale,
+        )
+    }
+
+    /// The option under `pos` in a list laid out in logical pixels.
+    fn row_at(&self, list: Rect, pos: glam::Vec2, row_height: f32) -> Option<usize> {
+        if !list.contains(pos) {
+            return None;
+        }
+        let row = ((pos.y - list.y) / row_height) as usize;
+        let index = self.first_visible + row;
+        (index < self.options.len()).then_some(index)
+    }
+
+    fn open_list(&mut self) {
+        if self.options.is_empty() {
+            return;
+        }
+        self.open = true;
+        self.scroll_rest = 0.0;
+        self.highlighted = self.selected.unwrap_or(0);
+        self.reveal_highlight();
+    }
+
+    /// Scroll the open list so the highlighted option is shown.
+    fn reveal_highlight(&mut self) {
+        let rows = self.visible_rows();
+        if self.highlighted < self.first_visible {
+            self.first_visible = self.highlighted;
+        } else if self.highlighted >= self.first_visible + rows {
+            self.first_visible = self.highlighted + 1 - rows;
+        }
+    }
+
+    fn choose(&mut self, index: usize) {
+        self.open = false;
+        if self.selected == Some(index) {
+            return;
+        }
+        self.selected = Some(index);
+        if let Some(handler) = &mut self.on_change {
+            handler(index, &self.options[index]);
+        }
+    }
+
+    fn key_down(&mut self, event: &InputEvent) -> EventResponse {
+        let mapper = ActionMapper::new();
+        let last = self.options.len().saturating_sub(1);
+        if mapper.is_action(event, StandardAction::MoveDown) {
+            if self.open {
+                self.highlighted = (self.highlighted + 1).min(last);
+                self.reveal_highlight();
+            } else {
+                self.open_list();
+            }
+        } else if mapper.is_action(event, StandardAction::MoveUp) {
+            if self.open {
+                self.highlighted = self.highlighted.saturating_sub(1);
+                self.reveal_highlight();
+            } else {
+                self.open_list();
+            }
+        } else if mapper.is_action(event, StandardAction::Activate) {
+            if self.open {
+                self.choose(self.highlighted);
+            } else {
+                self.open_list();
+            }
+        } else if self.open && mapper.is_action(event, StandardAction::Cancel) {
+            self.open = false;
+        } else {
+            return EventResponse::default();
+        }
+        EventResponse::handled()
+    }
+}
+
+impl Widget for Select {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let style = theme.resolve(&self.style);
+        Style {
+            padding: taffy::Rect {
+                left: length(style.padding_h),
+                right: length(style.padding_h),
+                top: length(style.padding_v),
+                bottom: length(style.padding_v),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let theme = ctx.theme();
+        let style = theme.resolve(&self.style);
+        let scale = ctx.scale_factor;
+
+        ctx.fill_bordered_rect(
+            bounds,
+            style.background,
+            style.corner_radius,
+            style.border_width,
+            style.border_color,
+        );
+
+        // Focus ring (scale offset for HiDPI), only for keyboard focus
+        if ctx.focus_visible() {
+            ctx.fill_bordered_rect(
+                bounds.expand(2.0 * scale),
+                Color::TRANSPARENT,
+                style.corner_radius + 2.0,
+                2.0,
+                theme.colors.focus_ring,
+            );
+        }
+
+        if let Some(label) = self.selected.map(|i| &self.options[i]) {
+            let text_style = TextStyle::default()
+                .with_size(style.font_size)
+                .with_color(style.text_color);
+            ctx.draw_text_aligned(label, &text_style, bounds, style.padding_h * scale);
+        }
+
+        // Chevron pointing down, or up while the list is open
+        let center_x = bounds.x + bounds.width - (style.padding_h + CHEVRON_SPACE / 2.0) * scale;
+        let center_y = bounds.y + bounds.height / 2.0;
+        let (half_w, half_h) = (4.0 * scale, 2.0 * scale);
+        let tip = if self.open { -half_h } else { half_h };
+        let (stroke, color) = (1.5 * scale, style.chevron_color);
+        let left = (center_x - half_w, center_y - tip);
+        let right = (center_x + half_w, center_y - tip);
+        let tip = (center_x, center_y + tip);
+        ctx.draw_line(left, tip, stroke, color);
+        ctx.draw_line(tip, right, stroke, color);
+    }
+
+    fn paint_overlay(&self, ctx: &mut PaintContext) {
+        let theme = ctx.theme();
+        let style = theme.resolve(&self.style);
+        let scale = ctx.scale_factor;
+        let list = self.list_rect(ctx.bounds(), scale, style.row_height);
+
+        ctx.fill_bordered_rect(
+            list.expand(style.border_width * scale),
+            style.list_background,
+            style.corner_radius,
+            style.border_width,
+            style.border_color,
+        );
+
+        ctx.push_clip(list);
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.text_color);
+        let row_height = style.row_height * scale;
+        let rows = self.first_visible..self.first_visible + self.visible_rows();
+        for (row, index) in rows.enumerate() {
+            let Some(label) = self.options.get(index) else {
+                break;
+            };
+            let row_rect = Rect::new(
+                list.x,
+                list.y + row as f32 * row_height,
+                list.width,
+                row_height,
+            );
+            if index == self.highlighted {
+                ctx.fill_rect(row_rect, style.highlight);
+            }
+            ctx.draw_text_aligned(label, &text_style, row_rect, style.padding_h * scale);
+        }
+        ctx.pop_clip();
+
+        // Scroll position indicator when not every option fits
+        if self.options.len() > MAX_VISIBLE_ROWS {
+            let total = self.options.len() as f32;
+            let thumb = Rect::new(
+                list.x + list.width - 4.0 * scale,
+                list.y + list.height * self.first_visible as f32 / total,
+                3.0 * scale,
+                list.height * MAX_VISIBLE_ROWS as f32 / total,
+            );
+            ctx.fill_rounded_rect(thumb, style.chevron_color, 1.5);
+        }
+    }
+
+    fn has_overlay(&self) -> bool {
+        self.open
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let row_height = ctx.theme().resolve(&self.style).row_height;
+        let list = self.list_rect(ctx.bounds(), 1.0, row_height);
+        match event {
+            InputEvent::PointerDown { pos, .. } => {
+                if self.open {
+                    // Clicking anywhere but the list dismisses it
+                    if !list.contains(*pos) {
+                        self.open = false;
+                    }
+                    return EventResponse::handled();
+                }
+                if ctx.contains(*pos) {
+                    self.pressed = true;
+                    return EventResponse::capture();
+                }
+                EventResponse::default()
+            }
+            InputEvent::PointerCancel { .. } if self.pressed => {
+                self.pressed = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                if self.open {
+                    if let Some(index) = self.row_at(list, *pos, row_height) {
+                        self.choose(index);
+                        return EventResponse::handled();
+                    }
+                    return EventResponse::default();
+                }
+                if !self.pressed {
+                    return EventResponse::default();
+                }
+                self.pressed = false;
+                if !ctx.contains(*pos) {
+                    return EventResponse::release();
+                }
+                // Focus so the arrow keys work on the open list
+                self.open_list();
+                EventResponse {
+                    request_focus: true,
+                    ..EventResponse::release()
+                }
+            }
+            InputEvent::PointerMove { pos, .. } if self.open => {
+                match self.row_at(list, *pos, row_height) {
+                    Some(index) => {
+                        self.highlighted = index;
+                        EventResponse::handled()
+                    }
+                    None => EventResponse::default(),
+                }
+            }
+            InputEvent::Scroll { delta, pos, .. } if self.open && list.contains(*pos) => {
+                // A wheel line is a row; pixels add up until they make one
+                self.scroll_rest -= delta.to_pixels(row_height).y;
+                let rows = (self.scroll_rest / row_height).trunc();
+                self.scroll_rest -= rows * row_height;
+                let max_first = self.options.len() - self.visible_rows();
+                let first = self.first_visible as f32 + rows;
+                self.first_visible = (first.max(0.0) as usize).min(max_first);
+                EventResponse::handled()
+            }
+            InputEvent::KeyDown { .. } if ctx.has_focus() => self.key_down(event),
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn on_blur(&mut self) {
+        self.open = false;
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::ComboBox,
+            value: self.selected.map(|i| self.options[i].clone()),
+            focusable: true,
+            actions: vec![AccessibleAction::Click],
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let (mut width, mut height) = (0.0f32, 0.0f32);
+        for option in &self.options {
+            let (w, h) = ctx.text.measure(option, &text_style, None);
+            width = width.max(w);
+            height = height.max(h);
+        }
+        // Padding comes from the style
+        Some((width + CHEVRON_SPACE, height.max(style.font_size)))
+    }
+}
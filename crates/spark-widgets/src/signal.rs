@@ -0,0 +1,255 @@
+//! Shared state that repaints the widgets reading it.
+//!
+//! A [`Signal`] holds a value that event handlers, native callbacks or other
+//! threads can change. Widgets read it through
+//! [`PaintContext::watch`](crate::PaintContext::watch), which subscribes them
+//! with the app's [`SignalRuntime`]; setting the signal afterwards queues the
+//! subscribers as dirty there and wakes the app, which takes them with
+//! [`SignalRuntime::take_dirty`] and lays out and repaints.
+
+use spark_layout::WidgetId;
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Wakes the app's event loop when a signal changes.
+type Waker = Box<dyn Fn() + Send + Sync>;
+
+/// One app's side of its signals: the widgets to repaint and how to wake
+/// the app. Clones share the same state.
+#[derive(Clone, Default)]
+pub struct SignalRuntime {
+    inner: Arc<RuntimeInner>,
+}
+
+#[derive(Default)]
+struct RuntimeInner {
+    /// Widgets whose signals changed since the last `take_dirty`.
+    dirty: Mutex<Vec<WidgetId>>,
+    waker: Mutex<Option<Waker>>,
+    /// Signals with subscribers from this app, for pruning.
+    signals: Mutex<Vec<Weak<dyn Subscribers>>>,
+}
+
+impl SignalRuntime {
+    /// Create a runtime with no subscribers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how a change wakes the app, so signals set from other threads are
+    /// picked up without waiting for input. Called by the app at startup.
+    pub fn set_waker(&self, waker: impl Fn() + Send + Sync + 'static) {
+        *self.inner.waker.lock().unwrap() = Some(Box::new(waker));
+    }
+
+    /// Take the widgets that watch a signal changed since the last call.
+    pub fn take_dirty(&self) -> Vec<WidgetId> {
+        std::mem::take(&mut *self.inner.dirty.lock().unwrap())
+    }
+
+    /// Drop this app's subscribers that `keep` rejects, e.g. widgets that are
+    /// gone from the tree after a rebuild.
+    pub fn retain(&self, keep: impl Fn(WidgetId) -> bool) {
+        self.inner.signals.lock().unwrap().retain(|signal| {
+            let Some(signal) = signal.upgrade() else {
+                return false;
+            };
+            signal.retain(&self.inner, &keep)
+        });
+    }
+
+    /// Queue `widget` as dirty and wake the app.
+    fn mark_dirty(&self, widget: WidgetId) {
+        self.inner.dirty.lock().unwrap().push(widget);
+        if let Some(wake) = self.inner.waker.lock().unwrap().as_ref() {
+            wake();
+        }
+    }
+}
+
+/// A widget watching a signal, and the app it belongs to.
+struct Subscriber {
+    widget: WidgetId,
+    runtime: SignalRuntime,
+}
+
+/// A signal's subscribers, without its value type.
+trait Subscribers: Send + Sync {
+    /// Keep only `runtime`'s subscribers that pass `keep`; false once the
+    /// signal has none left from that runtime.
+    fn retain(&self, runtime: &Arc<RuntimeInner>, keep: &dyn Fn(WidgetId) -> bool) -> bool;
+}
+
+struct Inner<T> {
+    value: Mutex<T>,
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl<T: Send> Subscribers for Inner<T> {
+    fn retain(&self, runtime: &Arc<RuntimeInner>, keep: &dyn Fn(WidgetId) -> bool) -> bool {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|s| !Arc::ptr_eq(&s.runtime.inner, runtime) || keep(s.widget));
+        subscribers
+            .iter()
+            .any(|s| Arc::ptr_eq(&s.runtime.inner, runtime))
+    }
+}
+
+/// A shared value that repaints the widgets watching it when it changes.
+///
+/// Clones share the value, so a signal can be handed to the button that
+/// changes it and the widget that shows it:
+///
+/// ```ignore
+/// let count = Signal::new(0);
+/// let button = Button::new("+1").on_click({
+///     let count = count.clone();
+///     move || count.update(|n| *n += 1)
+/// });
+/// // In the display widget's paint: `let n = ctx.watch(&self.count);`
+/// ```
+pub struct Signal<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Signal<T> {
+    /// Create a signal holding `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                value: Mutex::new(value),
+                subscribers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Get a copy of the value, without subscribing.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.inner.value.lock().unwrap().clone()
+    }
+
+    /// Read the value by reference, without subscribing.
+    pub fn with<R>(&self, read: impl FnOnce(&T) -> R) -> R {
+        read(&self.inner.value.lock().unwrap())
+    }
+
+    /// Replace the value and repaint the widgets watching it.
+    pub fn set(&self, value: T) {
+        *self.inner.value.lock().unwrap() = value;
+        self.notify();
+    }
+
+    /// Change the value in place and repaint the widgets watching it.
+    pub fn update(&self, change: impl FnOnce(&mut T)) {
+        change(&mut self.inner.value.lock().unwrap());
+        self.notify();
+    }
+
+    /// Repaint `widget` whenever the value changes, through `runtime`.
+    pub fn subscribe(&self, widget: WidgetId, runtime: &SignalRuntime)
+    where
+        T: Send + 'static,
+    {
+        let mut subscribers = self.inner.subscribers.lock().unwrap();
+        let known = |s: &Subscriber| Arc::ptr_eq(&s.runtime.inner, &runtime.inner);
+        if subscribers.iter().any(|s| known(s) && s.widget == widget) {
+            return;
+        }
+        if !subscribers.iter().any(known) {
+            let signal: Weak<dyn Subscribers> = Arc::downgrade(&self.inner);
+            runtime.inner.signals.lock().unwrap().push(signal);
+        }
+        subscribers.push(Subscriber {
+            widget,
+            runtime: runtime.clone(),
+        });
+    }
+
+    /// Queue the subscribers as dirty and wake their apps.
+    fn notify(&self) {
+        let subscribers = self.inner.subscribers.lock().unwrap();
+        for subscriber in subscribers.iter() {
+            subscriber.runtime.mark_dirty(subscriber.widget);
+        }
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Default> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|value| f.debug_tuple("Signal").field(value).finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_layout::taffy::Style;
+    use spark_layout::LayoutTree;
+
+    #[test]
+    fn test_set_marks_subscribers_dirty() {
+        let runtime = SignalRuntime::new();
+        let count = Signal::new(1);
+        // Nobody watches yet, so nothing is queued
+        count.set(2);
+        assert!(runtime.take_dirty().is_empty());
+
+        let widget = WidgetId::default();
+        count.subscribe(widget, &runtime);
+        count.subscribe(widget, &runtime);
+        count.clone().update(|n| *n += 1);
+        assert_eq!(count.get(), 3);
+        assert_eq!(runtime.take_dirty(), vec![widget]);
+        assert!(runtime.take_dirty().is_empty());
+
+        // Another app's runtime sees none of it
+        let other = SignalRuntime::new();
+        count.set(4);
+        assert!(other.take_dirty().is_empty());
+    }
+
+    #[test]
+    fn test_rebuilds_keep_the_subscriber_count() {
+        let runtime = SignalRuntime::new();
+        let label = Signal::new("hello");
+        let rebuild = |children: usize| {
+            let mut tree = LayoutTree::new();
+            let ids: Vec<_> = (0..children)
+                .map(|_| tree.new_leaf(Style::default()))
+                .collect();
+            let root = tree.new_with_children(Style::default(), &ids);
+            tree.set_root(root);
+            runtime.retain(|id| tree.get_layout(id).is_some());
+            ids
+        };
+        let subscribers = || label.inner.subscribers.lock().unwrap().len();
+
+        for _ in 0..3 {
+            let ids = rebuild(3);
+            label.subscribe(ids[2], &runtime);
+            assert_eq!(subscribers(), 1);
+        }
+
+        // The watcher is gone from the next tree, so its subscription goes too
+        rebuild(1);
+        assert_eq!(subscribers(), 0);
+        assert!(runtime.inner.signals.lock().unwrap().is_empty());
+    }
+}
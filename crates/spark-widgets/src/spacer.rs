@@ -0,0 +1,60 @@
+//! Flexible spacer widget.
+
+use crate::{LayoutContext, PaintContext, Theme, Widget};
+use spark_layout::WidgetId;
+use taffy::prelude::*;
+
+/// Empty space that grows to push its siblings apart.
+///
+/// In a row, `Spacer` between two buttons puts one at each end.
+pub struct Spacer {
+    id: WidgetId,
+    grow: f32,
+}
+
+impl Spacer {
+    /// Create a spacer taking an equal share of the free space.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            grow: 1.0,
+        }
+    }
+
+    /// Set the share of free space relative to other growing siblings.
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+}
+
+impl Default for Spacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Spacer {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style {
+            flex_grow: self.grow,
+            flex_shrink: 1.0,
+            flex_basis: zero(),
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {}
+
+    fn measure(&self, _ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        Some((0.0, 0.0))
+    }
+}
@@ -0,0 +1,315 @@
+//! Split pane widget.
+
+use crate::{CursorIcon, EventContext, EventResponse, PaintContext, Theme, ThemedStyle, Widget};
+use spark_core::{Color, Rect};
+use spark_input::InputEvent;
+use spark_layout::WidgetId;
+use taffy::prelude::*;
+
+/// Style configuration for a split pane.
+#[derive(Clone, Debug)]
+pub struct SplitPaneStyle {
+    pub divider_color: Color,
+    /// Divider line color while hovered or dragged.
+    pub active_color: Color,
+    /// Width of the draggable strip between the panes.
+    pub thickness: f32,
+    /// Width of the line drawn in the middle of the strip.
+    pub line_width: f32,
+}
+
+impl Default for SplitPaneStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for SplitPaneStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            divider_color: colors.border,
+            active_color: colors.primary,
+            thickness: 8.0,
+            line_width: 1.0,
+        }
+    }
+}
+
+/// The ratio that puts the divider as close to `ratio` as the minimum
+/// pane sizes allow, when `available` is shared between the panes.
+///
+/// When both minimums don't fit, the first pane keeps its minimum.
+fn clamp_ratio(ratio: f32, available: f32, min_first: f32, min_second: f32) -> f32 {
+    if available <= 0.0 {
+        return ratio.clamp(0.0, 1.0);
+    }
+    let first = (ratio * available)
+        .min(available - min_second)
+        .max(min_first);
+    (first / available).clamp(0.0, 1.0)
+}
+
+/// Two panes side by side (or stacked) with a draggable divider between.
+///
+/// Dragging the divider changes how the space is shared; double-clicking
+/// it restores the initial ratio. The split pane fills its parent.
+pub struct SplitPane {
+    id: WidgetId,
+    panes: Vec<Box<dyn Widget>>,
+    vertical: bool,
+    ratio: f32,
+    initial_ratio: f32,
+    min_sizes: (f32, f32),
+    style: Option<SplitPaneStyle>,
+    hovered: bool,
+    dragging: bool,
+    on_change: Option<Box<dyn FnMut(f32) + Send + Sync>>,
+}
+
+impl SplitPane {
+    /// Create a split with `first` on the left and `second` on the right.
+    pub fn horizontal(first: impl Widget + 'static, second: impl Widget + 'static) -> Self {
+        Self {
+            id: WidgetId::default(),
+            panes: vec![Box::new(first), Box::new(second)],
+            vertical: false,
+            ratio: 0.5,
+            initial_ratio: 0.5,
+            min_sizes: (0.0, 0.0),
+            style: None,
+            hovered: false,
+            dragging: false,
+            on_change: None,
+        }
+    }
+
+    /// Create a split with `first` on top and `second` below.
+    pub fn vertical(first: impl Widget + 'static, second: impl Widget + 'static) -> Self {
+        Self {
+            vertical: true,
+            ..Self::horizontal(first, second)
+        }
+    }
+
+    /// Set the share of the space given to the first pane, from 0 to 1.
+    ///
+    /// This is also the ratio that double-clicking the divider restores.
+    pub fn ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self.initial_ratio = self.ratio;
+        self
+    }
+
+    /// Set the smallest sizes of the first and second pane, in logical pixels.
+    pub fn min_sizes(mut self, first: f32, second: f32) -> Self {
+        self.min_sizes = (first, second);
+        self
+    }
+
+    /// Set the handler called with the new ratio while the divider moves.
+    pub fn on_change(mut self, handler: impl FnMut(f32) + Send + Sync + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the split pane style.
+    pub fn with_style(mut self, style: SplitPaneStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The share of the space given to the first pane.
+    pub fn get_ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Move the divider; takes effect at the next layout.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+    }
+
+    /// The draggable strip, `thickness` across in the same units as
+    /// `bounds`, placed after the first pane as laid out.
+    fn divider_rect(&self, ctx_bounds: Rect, first: Option<Rect>, thickness: f32) -> Rect {
+        if self.vertical {
+            let y = first.map_or(ctx_bounds.y, |first| first.y + first.height);
+            Rect::new(ctx_bounds.x, y, ctx_bounds.width, thickness)
+        } else {
+            let x = first.map_or(ctx_bounds.x, |first| first.x + first.width);
+            Rect::new(x, ctx_bounds.y, thickness, ctx_bounds.height)
+        }
+    }
+
+    /// The resize cursor for dragging the divider.
+    fn resize_cursor(&self) -> CursorIcon {
+        if self.vertical {
+            CursorIcon::RowResize
+        } else {
+            CursorIcon::ColResize
+        }
+    }
+
+    fn set_ratio_and_notify(&mut self, ratio: f32) -> EventResponse {
+        if ratio != self.ratio {
+            self.ratio = ratio;
+            if let Some(handler) = &mut self.on_change {
+                handler(ratio);
+            }
+        }
+        EventResponse {
+            relayout: true,
+            ..EventResponse::handled()
+        }
+    }
+}
+
+impl Widget for SplitPane {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        let thickness = theme.resolve(&self.style).thickness;
+        let (min_first, min_second) = self.min_sizes;
+        let tracks = vec![
+            minmax(length(min_first), fr(self.ratio)),
+            minmax(length(min_second), fr(1.0 - self.ratio)),
+        ];
+        let mut style = Style {
+            display: Display::Grid,
+            size: Size {
+                width: percent(1.0),
+                height: percent(1.0),
+            },
+            ..Default::default()
+        };
+        if self.vertical {
+            style.grid_template_rows = tracks;
+            style.gap.height = length(thickness);
+        } else {
+            style.grid_template_columns = tracks;
+            style.gap.width = length(thickness);
+        }
+        style
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let style = ctx.theme().resolve(&self.style);
+        let scale = ctx.scale_factor;
+        let first = ctx
+            .layout_tree
+            .get_absolute_layout(self.panes[0].id())
+            .map(|layout| layout.bounds.scale(scale));
+        let strip = self.divider_rect(ctx.bounds(), first, style.thickness * scale);
+
+        let active = self.hovered || self.dragging;
+        let (color, width) = if active {
+            (style.active_color, style.line_width * 2.0)
+        } else {
+            (style.divider_color, style.line_width)
+        };
+        let width = (width * scale).round().max(1.0);
+        let line = if self.vertical {
+            let y = (strip.y + (strip.height - width) / 2.0).round();
+            Rect::new(strip.x, y, strip.width, width)
+        } else {
+            let x = (strip.x + (strip.width - width) / 2.0).round();
+            Rect::new(x, strip.y, width, strip.height)
+        };
+        ctx.fill_rect(line, color);
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let bounds = ctx.bounds();
+        let first = ctx
+            .layout_tree
+            .get_absolute_layout(self.panes[0].id())
+            .map(|layout| layout.bounds);
+        let thickness = ctx.theme().resolve(&self.style).thickness;
+        let strip = self.divider_rect(bounds, first, thickness);
+        match event {
+            InputEvent::PointerMove { pos, .. } if self.dragging => {
+                let (start, length, pos) = if self.vertical {
+                    (bounds.y, bounds.height, pos.y)
+                } else {
+                    (bounds.x, bounds.width, pos.x)
+                };
+                let available = length - thickness;
+                let first = pos - start - thickness / 2.0;
+                let (min_first, min_second) = self.min_sizes;
+                let ratio = clamp_ratio(first / available, available, min_first, min_second);
+                self.set_ratio_and_notify(ratio).with_cursor(self.resize_cursor())
+            }
+            InputEvent::PointerMove { pos, .. } => {
+                let hovered = strip.contains(*pos);
+                let changed = hovered != self.hovered;
+                self.hovered = hovered;
+                EventResponse {
+                    repaint: changed,
+                    cursor: hovered.then_some(self.resize_cursor()),
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerLeave if self.hovered => {
+                self.hovered = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerDown {
+                pos, click_count, ..
+            } if strip.contains(*pos) => {
+                // A double click resets the divider
+                if *click_count == 2 {
+                    return self.set_ratio_and_notify(self.initial_ratio);
+                }
+                self.dragging = true;
+                EventResponse::capture()
+            }
+            InputEvent::PointerCancel { .. } if self.dragging => {
+                self.dragging = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } if self.dragging => {
+                self.dragging = false;
+                self.hovered = strip.contains(*pos);
+                EventResponse::release()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        &self.panes
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        &mut self.panes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_ratio_respects_min_sizes() {
+        // Plenty of room: the ratio is kept
+        assert_eq!(clamp_ratio(0.5, 1000.0, 150.0, 200.0), 0.5);
+        // Dragged too far either way
+        assert_eq!(clamp_ratio(0.05, 1000.0, 150.0, 200.0), 0.15);
+        assert_eq!(clamp_ratio(0.95, 1000.0, 150.0, 200.0), 0.8);
+        // Not enough room for both: the first pane keeps its minimum
+        assert_eq!(clamp_ratio(0.5, 300.0, 150.0, 200.0), 0.5);
+        assert_eq!(clamp_ratio(0.9, 300.0, 150.0, 200.0), 0.5);
+    }
+}
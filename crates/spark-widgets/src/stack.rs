@@ -0,0 +1,284 @@
+//! Stack container widget.
+
+use crate::{PaintContext, Theme, Widget};
+use spark_layout::WidgetId;
+use std::cell::Cell;
+use std::rc::Rc;
+use taffy::prelude::*;
+
+/// Where a layer sits within its stack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+/// Position along one axis.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    Start,
+    Center,
+    End,
+}
+
+impl Alignment {
+    /// The horizontal and vertical position.
+    fn edges(self) -> (Edge, Edge) {
+        match self {
+            Alignment::TopLeft => (Edge::Start, Edge::Start),
+            Alignment::Top => (Edge::Center, Edge::Start),
+            Alignment::TopRight => (Edge::End, Edge::Start),
+            Alignment::Left => (Edge::Start, Edge::Center),
+            Alignment::Center => (Edge::Center, Edge::Center),
+            Alignment::Right => (Edge::End, Edge::Center),
+            Alignment::BottomLeft => (Edge::Start, Edge::End),
+            Alignment::Bottom => (Edge::Center, Edge::End),
+            Alignment::BottomRight => (Edge::End, Edge::End),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Placement {
+    alignment: Alignment,
+    offset: (f32, f32),
+}
+
+/// The layout style of a layer placed by `placement`.
+///
+/// Layers aligned to an edge are inset from it; centered layers are
+/// aligned by the stack and shifted with opposite margins. Either way a
+/// positive offset moves the layer right and down.
+fn layer_style(placement: Placement) -> Style {
+    let (horizontal, vertical) = placement.alignment.edges();
+    let (x, y) = placement.offset;
+    let mut style = Style {
+        position: Position::Absolute,
+        ..Default::default()
+    };
+    match horizontal {
+        Edge::Start => style.inset.left = length(x),
+        Edge::End => style.inset.right = length(-x),
+        Edge::Center => {
+            style.justify_self = Some(JustifySelf::Center);
+            style.margin.left = length(x);
+            style.margin.right = length(-x);
+        }
+    }
+    match vertical {
+        Edge::Start => style.inset.top = length(y),
+        Edge::End => style.inset.bottom = length(-y),
+        Edge::Center => {
+            style.align_self = Some(AlignSelf::Center);
+            style.margin.top = length(y);
+            style.margin.bottom = length(-y);
+        }
+    }
+    style
+}
+
+/// Holds one widget above the base of a stack, taken out of flow.
+struct StackLayer {
+    id: WidgetId,
+    child: Box<dyn Widget>,
+    /// Shared with the stack, so `align` and `offset` can change it after
+    /// the layer is added.
+    placement: Rc<Cell<Placement>>,
+}
+
+impl Widget for StackLayer {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        layer_style(self.placement.get())
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {}
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        std::slice::from_ref(&self.child)
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        std::slice::from_mut(&mut self.child)
+    }
+}
+
+/// Layers children on top of each other.
+///
+/// The first child is the base: it stays in flow and sizes the stack.
+/// Every later child is a layer placed over it with [`Stack::align`] and
+/// [`Stack::offset`]. Later children paint on top of earlier ones and get
+/// pointer events first.
+///
+/// ```ignore
+/// Stack::new()
+///     .child(Image::new(cover))
+///     .child(Icon::new(IconName::Check).size(16.0))
+///     .align(Alignment::TopRight)
+///     .offset(-8.0, 8.0)
+/// ```
+pub struct Stack {
+    id: WidgetId,
+    children: Vec<Box<dyn Widget>>,
+    /// Placement of the last child, unless it is the base.
+    last: Option<Rc<Cell<Placement>>>,
+    size: Option<(f32, f32)>,
+}
+
+impl Stack {
+    /// Create an empty stack.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            children: Vec::new(),
+            last: None,
+            size: None,
+        }
+    }
+
+    /// Add a child over the earlier ones, at the top left until aligned.
+    pub fn child(mut self, widget: impl Widget + 'static) -> Self {
+        if self.children.is_empty() {
+            self.children.push(Box::new(widget));
+            return self;
+        }
+        let placement = Rc::new(Cell::new(Placement::default()));
+        self.last = Some(placement.clone());
+        self.children.push(Box::new(StackLayer {
+            id: WidgetId::default(),
+            child: Box::new(widget),
+            placement,
+        }));
+        self
+    }
+
+    /// Align the last added child within the stack. The base is not moved.
+    pub fn align(self, alignment: Alignment) -> Self {
+        if let Some(placement) = &self.last {
+            placement.set(Placement {
+                alignment,
+                ..placement.get()
+            });
+        }
+        self
+    }
+
+    /// Shift the last added child from its aligned position by (`x`, `y`)
+    /// logical pixels. The base is not moved.
+    pub fn offset(self, x: f32, y: f32) -> Self {
+        if let Some(placement) = &self.last {
+            placement.set(Placement {
+                offset: (x, y),
+                ..placement.get()
+            });
+        }
+        self
+    }
+
+    /// Give the stack a fixed size instead of the size of its base.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Stack {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        let mut style = Style {
+            display: Display::Grid,
+            ..Default::default()
+        };
+        if let Some((width, height)) = self.size {
+            style.size = Size {
+                width: length(width),
+                height: length(height),
+            };
+        }
+        style
+    }
+
+    fn paint(&self, _ctx: &mut PaintContext) {}
+
+    fn stacks_children(&self) -> bool {
+        true
+    }
+
+    fn children(&self) -> &[Box<dyn Widget>] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+        &mut self.children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style_for(alignment: Alignment, x: f32, y: f32) -> Style {
+        layer_style(Placement {
+            alignment,
+            offset: (x, y),
+        })
+    }
+
+    #[test]
+    fn test_layer_style_insets_from_aligned_edges() {
+        let style = style_for(Alignment::TopRight, 4.0, -4.0);
+        assert_eq!(style.position, Position::Absolute);
+        assert_eq!(style.inset.right, LengthPercentageAuto::length(-4.0));
+        assert_eq!(style.inset.top, LengthPercentageAuto::length(-4.0));
+        assert_eq!(style.inset.left, LengthPercentageAuto::auto());
+        assert_eq!(style.inset.bottom, LengthPercentageAuto::auto());
+
+        let style = style_for(Alignment::BottomLeft, 8.0, -2.0);
+        assert_eq!(style.inset.left, LengthPercentageAuto::length(8.0));
+        assert_eq!(style.inset.bottom, LengthPercentageAuto::length(2.0));
+    }
+
+    #[test]
+    fn test_layer_style_centers_with_margins() {
+        let style = style_for(Alignment::Center, 10.0, 0.0);
+        assert_eq!(style.justify_self, Some(JustifySelf::Center));
+        assert_eq!(style.align_self, Some(AlignSelf::Center));
+        assert_eq!(style.margin.left, LengthPercentageAuto::length(10.0));
+        assert_eq!(style.margin.right, LengthPercentageAuto::length(-10.0));
+        assert_eq!(style.inset.left, LengthPercentageAuto::auto());
+
+        // Centered on one axis only
+        let style = style_for(Alignment::Bottom, 0.0, -6.0);
+        assert_eq!(style.justify_self, Some(JustifySelf::Center));
+        assert_eq!(style.align_self, None);
+        assert_eq!(style.inset.bottom, LengthPercentageAuto::length(6.0));
+    }
+}
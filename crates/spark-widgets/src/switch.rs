@@ -0,0 +1,278 @@
+//! On/off switch widget.
+
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext,
+    PaintContext, Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{ActionMapper, InputEvent, StandardAction};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use std::cell::Cell;
+use taffy::prelude::*;
+
+/// Seconds the knob takes to slide across the track.
+const KNOB_ANIMATION_SECONDS: f32 = 0.15;
+
+/// Style configuration for a switch.
+#[derive(Clone, Debug)]
+pub struct SwitchStyle {
+    /// Track fill while off.
+    pub track_color: Color,
+    /// Track fill while on.
+    pub track_color_on: Color,
+    pub knob_color: Color,
+    pub text_color: Color,
+    pub track_width: f32,
+    pub track_height: f32,
+    /// Space between the knob and the track edge.
+    pub knob_inset: f32,
+    /// Space between the track and the label.
+    pub gap: f32,
+    pub font_size: f32,
+}
+
+impl Default for SwitchStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for SwitchStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            track_color: colors.border,
+            track_color_on: colors.primary,
+            knob_color: Color::WHITE,
+            text_color: colors.text,
+            track_width: 40.0,
+            track_height: 24.0,
+            knob_inset: 2.0,
+            gap: theme.spacing.sm,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+/// A pill-shaped on/off switch with an optional label.
+pub struct Switch {
+    id: WidgetId,
+    label: Option<String>,
+    style: Option<SwitchStyle>,
+    checked: bool,
+    pressed: bool,
+    on_change: Option<Box<dyn FnMut(bool) + Send + Sync>>,
+    /// The state last painted, to notice toggles while painting.
+    painted_checked: Cell<bool>,
+    /// When the knob started sliding, in `elapsed_time` seconds.
+    slide_started: Cell<Option<f32>>,
+}
+
+impl Switch {
+    /// Create a switch that starts off.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            label: None,
+            style: None,
+            checked: false,
+            pressed: false,
+            on_change: None,
+            painted_checked: Cell::new(false),
+            slide_started: Cell::new(None),
+        }
+    }
+
+    /// Set whether the switch starts on.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self.painted_checked.set(checked);
+        self
+    }
+
+    /// Set the handler called with the new state when the switch is toggled.
+    pub fn on_change(mut self, handler: impl FnMut(bool) + Send + Sync + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Show a label to the right of the track.
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Set the switch style.
+    pub fn with_style(mut self, style: SwitchStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Whether the switch is on.
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    fn toggle(&mut self) {
+        self.checked = !self.checked;
+        if let Some(handler) = &mut self.on_change {
+            handler(self.checked);
+        }
+    }
+
+    /// Knob position from 0 (off) to 1 (on), easing toward the current state.
+    fn knob_position(&self, ctx: &PaintContext) -> f32 {
+        let now = ctx.elapsed_time;
+        if self.painted_checked.replace(self.checked) != self.checked {
+            self.slide_started.set(Some(now));
+        }
+        let progress = match self.slide_started.get() {
+            Some(start) => ((now - start) / KNOB_ANIMATION_SECONDS).clamp(0.0, 1.0),
+            None => 1.0,
+        };
+        if progress < 1.0 {
+            ctx.request_animation_frame();
+        } else {
+            self.slide_started.set(None);
+        }
+        let eased = progress * progress * (3.0 - 2.0 * progress);
+        if self.checked {
+            eased
+        } else {
+            1.0 - eased
+        }
+    }
+}
+
+impl Default for Switch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Switch {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let theme = ctx.theme();
+        let style = theme.resolve(&self.style);
+        let scale = ctx.scale_factor;
+        let position = self.knob_position(ctx);
+
+        // Track at the left edge, vertically centered
+        let track_width = style.track_width * scale;
+        let track_height = style.track_height * scale;
+        let track = Rect::new(
+            bounds.x,
+            bounds.y + (bounds.height - track_height) / 2.0,
+            track_width,
+            track_height,
+        );
+        let track_color = style.track_color.lerp(style.track_color_on, position);
+        ctx.fill_rounded_rect(track, track_color, style.track_height / 2.0);
+
+        let inset = style.knob_inset * scale;
+        let knob_size = track_height - inset * 2.0;
+        let knob = Rect::new(
+            track.x + inset + (track_width - track_height) * position,
+            track.y + inset,
+            knob_size,
+            knob_size,
+        );
+        let knob_radius = style.track_height / 2.0 - style.knob_inset;
+        ctx.fill_rounded_rect(knob, style.knob_color, knob_radius);
+
+        // Focus ring (scale offset for HiDPI), only for keyboard focus
+        if ctx.focus_visible() {
+            ctx.fill_bordered_rect(
+                track.expand(2.0 * scale),
+                Color::TRANSPARENT,
+                style.track_height / 2.0 + 2.0,
+                2.0,
+                theme.colors.focus_ring,
+            );
+        }
+
+        if let Some(label) = &self.label {
+            let text_style = TextStyle::default()
+                .with_size(style.font_size)
+                .with_color(style.text_color);
+            let (_, text_height) = ctx.measure_text(label, &text_style);
+            let x = track.x + track_width + style.gap * scale;
+            let y = bounds.y + (bounds.height - text_height) / 2.0;
+            ctx.draw_text(label, &text_style, x, y);
+        }
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        match event {
+            InputEvent::PointerDown { pos, .. } => {
+                if ctx.contains(*pos) {
+                    self.pressed = true;
+                    return EventResponse::capture();
+                }
+                EventResponse::default()
+            }
+            InputEvent::PointerCancel { .. } if self.pressed => {
+                self.pressed = false;
+                EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                }
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                if !self.pressed {
+                    return EventResponse::default();
+                }
+                self.pressed = false;
+                if ctx.contains(*pos) {
+                    self.toggle();
+                }
+                EventResponse::release()
+            }
+            InputEvent::KeyDown { .. } => {
+                let activate = ActionMapper::new().is_action(event, StandardAction::Activate);
+                if ctx.has_focus() && activate {
+                    self.toggle();
+                    return EventResponse::handled();
+                }
+                EventResponse::default()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::CheckBox,
+            name: self.label.clone(),
+            value: Some(if self.checked { "on" } else { "off" }.into()),
+            focusable: true,
+            actions: vec![AccessibleAction::Click],
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let (width, height) = (style.track_width, style.track_height);
+        let Some(label) = &self.label else {
+            return Some((width, height));
+        };
+        let text_style = TextStyle::default().with_size(style.font_size);
+        let (text_width, text_height) = ctx.text.measure(label, &text_style, None);
+        Some((width + style.gap + text_width, height.max(text_height)))
+    }
+}
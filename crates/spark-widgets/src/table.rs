@@ -0,0 +1,549 @@
+//! Table widget with a pinned header and virtualized rows.
+
+use crate::{
+    AccessibleInfo, AccessibleRole, EventContext, EventResponse, LayoutContext, PaintContext,
+    Theme, ThemedStyle, Widget,
+};
+use spark_core::{Color, Rect};
+use spark_input::{InputEvent, SCROLL_LINE_HEIGHT};
+use spark_layout::WidgetId;
+use spark_text::TextStyle;
+use taffy::prelude::*;
+
+/// Width of the scrollbar thumb, in logical pixels.
+const SCROLLBAR_WIDTH: f32 = 4.0;
+/// Rows measured when the table has no explicit height.
+const MEASURED_ROWS: usize = 8;
+/// Width measured for each flexible column.
+const MEASURED_FLEX_WIDTH: f32 = 120.0;
+
+/// How wide a table column is.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnWidth {
+    /// A width in logical pixels.
+    Fixed(f32),
+    /// A share of the width left after fixed columns.
+    Flex(f32),
+}
+
+/// Direction shown by a column's sort indicator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// The opposite direction.
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+/// Style configuration for a table.
+#[derive(Clone, Debug)]
+pub struct TableStyle {
+    pub background: Color,
+    /// Fill of every other row.
+    pub stripe_background: Color,
+    /// Fill of the row under the pointer.
+    pub hover_background: Color,
+    pub header_background: Color,
+    pub header_text_color: Color,
+    pub text_color: Color,
+    /// Line under the header.
+    pub border_color: Color,
+    pub scrollbar_color: Color,
+    pub header_height: f32,
+    pub row_height: f32,
+    /// Space between a cell's edge and its text.
+    pub cell_padding: f32,
+    pub font_size: f32,
+}
+
+impl Default for TableStyle {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for TableStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            background: colors.surface,
+            stripe_background: colors.surface_alt,
+            hover_background: colors.primary.with_alpha(0.12),
+            header_background: colors.surface_alt,
+            header_text_color: colors.text_muted,
+            text_color: colors.text,
+            border_color: colors.border,
+            scrollbar_color: colors.disabled,
+            header_height: 32.0,
+            row_height: 28.0,
+            cell_padding: theme.spacing.sm,
+            font_size: theme.typography.body,
+        }
+    }
+}
+
+struct Column {
+    title: String,
+    width: ColumnWidth,
+}
+
+/// What a press started on, so the release only acts on the same target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Pressed {
+    Header(usize),
+    Row(usize),
+}
+
+/// Resolve column widths: fixed columns first, flexible ones share the rest.
+fn column_widths(columns: &[ColumnWidth], total: f32) -> Vec<f32> {
+    let fixed: f32 = columns
+        .iter()
+        .map(|column| match column {
+            ColumnWidth::Fixed(width) => *width,
+            ColumnWidth::Flex(_) => 0.0,
+        })
+        .sum();
+    let weights: f32 = columns
+        .iter()
+        .map(|column| match column {
+            ColumnWidth::Flex(weight) => weight.max(0.0),
+            ColumnWidth::Fixed(_) => 0.0,
+        })
+        .sum();
+    let remaining = (total - fixed).max(0.0);
+    columns
+        .iter()
+        .map(|column| match column {
+            ColumnWidth::Fixed(width) => *width,
+            ColumnWidth::Flex(_) if weights <= 0.0 => 0.0,
+            ColumnWidth::Flex(weight) => remaining * weight.max(0.0) / weights,
+        })
+        .collect()
+}
+
+/// Columns of text cells under a header that stays put while rows scroll.
+///
+/// Cells come from a closure and only visible rows are asked for, so large
+/// tables cost no more to paint than small ones.
+pub struct Table {
+    id: WidgetId,
+    columns: Vec<Column>,
+    row_count: usize,
+    cell: Box<dyn Fn(usize, usize) -> String + Send + Sync>,
+    sort: Option<(usize, SortDirection)>,
+    on_sort: Option<Box<dyn FnMut(usize, SortDirection) + Send + Sync>>,
+    on_row_click: Option<Box<dyn FnMut(usize) + Send + Sync>>,
+    on_row_double_click: Option<Box<dyn FnMut(usize) + Send + Sync>>,
+    style: Option<TableStyle>,
+    height: Option<f32>,
+    /// Body scroll offset in logical pixels.
+    offset: f32,
+    hovered: Option<usize>,
+    pressed: Option<Pressed>,
+}
+
+impl Table {
+    /// Create a table with no columns or rows.
+    pub fn new() -> Self {
+        Self {
+            id: WidgetId::default(),
+            columns: Vec::new(),
+            row_count: 0,
+            cell: Box::new(|_, _| String::new()),
+            sort: None,
+            on_sort: None,
+            on_row_click: None,
+            on_row_double_click: None,
+            style: None,
+            height: None,
+            offset: 0.0,
+            hovered: None,
+            pressed: None,
+        }
+    }
+
+    /// Add a column with a header title.
+    pub fn column(mut self, title: impl Into<String>, width: ColumnWidth) -> Self {
+        self.columns.push(Column {
+            title: title.into(),
+            width,
+        });
+        self
+    }
+
+    /// Show `count` rows whose cell text is `cell(row, column)`.
+    pub fn rows(
+        mut self,
+        count: usize,
+        cell: impl Fn(usize, usize) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.row_count = count;
+        self.cell = Box::new(cell);
+        self
+    }
+
+    /// Show a sort indicator on `column`.
+    pub fn sorted(mut self, column: usize, direction: SortDirection) -> Self {
+        self.sort = Some((column, direction));
+        self
+    }
+
+    /// Make headers clickable; a click sorts by that column, reversing the
+    /// direction when it is already the sort column.
+    pub fn on_sort(
+        mut self,
+        handler: impl FnMut(usize, SortDirection) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_sort = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the handler called with the index of a clicked row.
+    pub fn on_row_click(mut self, handler: impl FnMut(usize) + Send + Sync + 'static) -> Self {
+        self.on_row_click = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the handler called with the index of a double-clicked row, e.g.
+    /// to open it. The row's clicks are still reported to `on_row_click`.
+    pub fn on_row_double_click(
+        mut self,
+        handler: impl FnMut(usize) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_row_double_click = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the height; otherwise the table is measured to show a few rows.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Set the table style.
+    pub fn with_style(mut self, style: TableStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// The current sort column and direction.
+    pub fn sort(&self) -> Option<(usize, SortDirection)> {
+        self.sort
+    }
+
+    /// Column left edges and widths across `width`.
+    fn column_spans(&self, x: f32, width: f32, scale: f32) -> Vec<(f32, f32)> {
+        let sizes: Vec<ColumnWidth> = self.columns.iter().map(|column| column.width).collect();
+        let mut left = x;
+        column_widths(&sizes, width / scale)
+            .into_iter()
+            .map(|column_width| {
+                let span = (left, column_width * scale);
+                left += column_width * scale;
+                span
+            })
+            .collect()
+    }
+
+    /// The scrolling area below the header, with lengths multiplied by `scale`.
+    fn body_rect(bounds: Rect, scale: f32, style: &TableStyle) -> Rect {
+        let header = (style.header_height * scale).min(bounds.height);
+        Rect::new(
+            bounds.x,
+            bounds.y + header,
+            bounds.width,
+            bounds.height - header,
+        )
+    }
+
+    fn max_offset(&self, bounds: Rect, style: &TableStyle) -> f32 {
+        let content = self.row_count as f32 * style.row_height;
+        (content - Self::body_rect(bounds, 1.0, style).height).max(0.0)
+    }
+
+    /// The row under `pos`, with everything in logical pixels.
+    fn row_at(&self, bounds: Rect, pos: glam::Vec2, style: &TableStyle) -> Option<usize> {
+        let body = Self::body_rect(bounds, 1.0, style);
+        if !body.contains(pos) {
+            return None;
+        }
+        let row = ((pos.y - body.y + self.offset) / style.row_height) as usize;
+        (row < self.row_count).then_some(row)
+    }
+
+    /// The header cell under `pos`, with everything in logical pixels.
+    fn header_at(&self, bounds: Rect, pos: glam::Vec2, style: &TableStyle) -> Option<usize> {
+        let header = Rect::new(bounds.x, bounds.y, bounds.width, style.header_height);
+        if !header.contains(pos) {
+            return None;
+        }
+        self.column_spans(bounds.x, bounds.width, 1.0)
+            .iter()
+            .position(|(left, width)| pos.x >= *left && pos.x < left + width)
+    }
+
+    fn click_header(&mut self, column: usize) {
+        let Some(handler) = &mut self.on_sort else {
+            return;
+        };
+        let direction = match self.sort {
+            Some((current, direction)) if current == column => direction.reversed(),
+            _ => SortDirection::Ascending,
+        };
+        self.sort = Some((column, direction));
+        handler(column, direction);
+    }
+
+    /// Draw a small chevron pointing up or down, centered on `center`.
+    fn paint_sort_indicator(
+        ctx: &mut PaintContext,
+        center: (f32, f32),
+        direction: SortDirection,
+        color: Color,
+    ) {
+        let scale = ctx.scale_factor;
+        let (half, rise) = (4.0 * scale, 2.0 * scale);
+        let (x, y) = center;
+        let (tip, base) = match direction {
+            SortDirection::Ascending => (y - rise, y + rise),
+            SortDirection::Descending => (y + rise, y - rise),
+        };
+        let stroke = 1.5 * scale;
+        ctx.draw_line((x - half, base), (x, tip), stroke, color);
+        ctx.draw_line((x, tip), (x + half, base), stroke, color);
+    }
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Table {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> Style {
+        Style {
+            size: Size {
+                width: auto(),
+                height: self.height.map_or(auto(), length),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let bounds = ctx.bounds();
+        let style = ctx.theme().resolve(&self.style);
+        let scale = ctx.scale_factor;
+        let columns = self.column_spans(bounds.x, bounds.width, scale);
+        let padding = style.cell_padding * scale;
+        let row_height = style.row_height * scale;
+        let text_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.text_color);
+
+        ctx.fill_rect(bounds, style.background);
+
+        // Body first, clipped below the header, so the header covers it
+        let body = Self::body_rect(bounds, scale, &style);
+        let offset = self.offset * scale;
+        let first = (self.offset / style.row_height) as usize;
+        ctx.push_clip(body);
+        for row in first..self.row_count {
+            let y = body.y + row as f32 * row_height - offset;
+            if y >= body.y + body.height {
+                break;
+            }
+            let row_rect = Rect::new(body.x, y, body.width, row_height);
+            if self.hovered == Some(row) {
+                ctx.fill_rect(row_rect, style.hover_background);
+            } else if row % 2 == 1 {
+                ctx.fill_rect(row_rect, style.stripe_background);
+            }
+            for (column, &(left, width)) in columns.iter().enumerate() {
+                let cell = Rect::new(left, y, width, row_height);
+                let text = (self.cell)(row, column);
+                ctx.push_clip(cell);
+                ctx.draw_text_aligned(&text, &text_style, cell, padding);
+                ctx.pop_clip();
+            }
+        }
+
+        // Scrollbar thumb while rows overflow
+        let content = self.row_count as f32 * row_height;
+        if content > body.height && body.height > 0.0 {
+            let thumb_height = (body.height * body.height / content).max(row_height);
+            let travel = body.height - thumb_height;
+            let progress = self.offset / self.max_offset(bounds.scale(1.0 / scale), &style);
+            let thumb = Rect::new(
+                body.x + body.width - (SCROLLBAR_WIDTH + 2.0) * scale,
+                body.y + travel * progress,
+                SCROLLBAR_WIDTH * scale,
+                thumb_height,
+            );
+            ctx.fill_rounded_rect(thumb, style.scrollbar_color, SCROLLBAR_WIDTH / 2.0);
+        }
+        ctx.pop_clip();
+
+        // Pinned header
+        let header_height = (style.header_height * scale).min(bounds.height);
+        let header = Rect::new(bounds.x, bounds.y, bounds.width, header_height);
+        ctx.fill_rect(header, style.header_background);
+        let header_style = TextStyle::default()
+            .with_size(style.font_size)
+            .with_color(style.header_text_color);
+        for (column, &(left, width)) in columns.iter().enumerate() {
+            let cell = Rect::new(left, header.y, width, header_height);
+            ctx.push_clip(cell);
+            ctx.draw_text_aligned(&self.columns[column].title, &header_style, cell, padding);
+            ctx.pop_clip();
+            if let Some((_, direction)) = self.sort.filter(|(sorted, _)| *sorted == column) {
+                let center = (
+                    left + width - padding - 4.0 * scale,
+                    header.y + header_height / 2.0,
+                );
+                Self::paint_sort_indicator(ctx, center, direction, style.header_text_color);
+            }
+        }
+        let line = Rect::new(
+            header.x,
+            header.y + header_height - scale,
+            header.width,
+            scale,
+        );
+        ctx.fill_rect(line, style.border_color);
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let bounds = ctx.bounds();
+        let style = ctx.theme().resolve(&self.style).into_owned();
+        match event {
+            InputEvent::PointerMove { pos, .. } => {
+                let hovered = self.row_at(bounds, *pos, &style);
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    return EventResponse {
+                        repaint: true,
+                        ..Default::default()
+                    };
+                }
+            }
+            InputEvent::PointerLeave if self.hovered.is_some() => {
+                self.hovered = None;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerDown {
+                pos, click_count, ..
+            } => {
+                self.pressed = self
+                    .header_at(bounds, *pos, &style)
+                    .map(Pressed::Header)
+                    .or_else(|| self.row_at(bounds, *pos, &style).map(Pressed::Row));
+                if let (Some(Pressed::Row(row)), 2) = (self.pressed, *click_count) {
+                    if let Some(handler) = &mut self.on_row_double_click {
+                        handler(row);
+                    }
+                }
+                if self.pressed.is_some() {
+                    // Capture so the release comes back even off the table
+                    return EventResponse::capture();
+                }
+            }
+            InputEvent::PointerCancel { .. } if self.pressed.is_some() => {
+                self.pressed = None;
+                return EventResponse {
+                    repaint: true,
+                    ..Default::default()
+                };
+            }
+            InputEvent::PointerUp { pos, .. } => {
+                let Some(pressed) = self.pressed.take() else {
+                    return EventResponse::default();
+                };
+                match pressed {
+                    Pressed::Header(column)
+                        if self.header_at(bounds, *pos, &style) == Some(column) =>
+                    {
+                        self.click_header(column);
+                    }
+                    Pressed::Row(row) if self.row_at(bounds, *pos, &style) == Some(row) => {
+                        if let Some(handler) = &mut self.on_row_click {
+                            handler(row);
+                        }
+                    }
+                    _ => {}
+                }
+                return EventResponse::release();
+            }
+            InputEvent::Scroll { delta, pos, .. } if ctx.contains(*pos) => {
+                let delta = delta.to_pixels(SCROLL_LINE_HEIGHT);
+                let offset = (self.offset - delta.y).clamp(0.0, self.max_offset(bounds, &style));
+                if offset != self.offset {
+                    self.offset = offset;
+                    self.hovered = self.row_at(bounds, *pos, &style);
+                    return EventResponse::handled();
+                }
+            }
+            _ => {}
+        }
+        EventResponse::default()
+    }
+
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Table,
+            value: Some(format!("{} rows", self.row_count)),
+            ..Default::default()
+        }
+    }
+
+    fn measure(&self, ctx: &mut LayoutContext) -> Option<(f32, f32)> {
+        let style = ctx.theme.resolve(&self.style);
+        let width = self
+            .columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Flex(_) => MEASURED_FLEX_WIDTH,
+            })
+            .sum();
+        let rows = self.row_count.min(MEASURED_ROWS) as f32;
+        Some((width, style.header_height + rows * style.row_height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flex_columns_share_the_width_left_by_fixed_ones() {
+        let columns = [
+            ColumnWidth::Flex(1.0),
+            ColumnWidth::Fixed(80.0),
+            ColumnWidth::Flex(3.0),
+        ];
+        assert_eq!(column_widths(&columns, 480.0), vec![100.0, 80.0, 300.0]);
+        // Fixed columns keep their width when there is no room left
+        assert_eq!(column_widths(&columns, 50.0), vec![0.0, 80.0, 0.0]);
+    }
+}
@@ -1,6 +1,9 @@
 //! Text widget for displaying static text.
 
-use crate::{EventContext, EventResponse, PaintContext, Widget};
+use crate::{
+    AccessibleInfo, AccessibleRole, ColorToken, EventContext, EventResponse, PaintContext, Theme,
+    Widget,
+};
 use spark_core::Color;
 use spark_input::InputEvent;
 use spark_layout::WidgetId;
@@ -20,8 +23,11 @@ pub enum TextAlign {
 pub struct Text {
     id: WidgetId,
     content: String,
-    color: Color,
-    font_size: f32,
+    /// Explicit color; when unset the theme's text color is used.
+    color: Option<Color>,
+    color_token: ColorToken,
+    /// Explicit size; when unset the theme's large body size is used.
+    font_size: Option<f32>,
     bold: bool,
     italic: bool,
     align: TextAlign,
@@ -33,8 +39,9 @@ impl Text {
         Self {
             id: WidgetId::default(),
             content: content.into(),
-            color: Color::from_hex(0x1F2937), // Default dark gray text
-            font_size: 16.0,
+            color: None,
+            color_token: ColorToken::Text,
+            font_size: None,
             bold: false,
             italic: false,
             align: TextAlign::Left,
@@ -43,13 +50,20 @@ impl Text {
 
     /// Set the text color.
     pub fn color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the text color from a theme token.
+    pub fn color_token(mut self, token: ColorToken) -> Self {
+        self.color = None;
+        self.color_token = token;
         self
     }
 
     /// Set the font size.
     pub fn size(mut self, size: f32) -> Self {
-        self.font_size = size;
+        self.font_size = Some(size);
         self
     }
 
@@ -95,13 +109,13 @@ impl Text {
 
     /// Create a small/caption-style text.
     pub fn caption(content: impl Into<String>) -> Self {
-        Self::new(content).size(12.0).color(Color::from_hex(0x6B7280))
+        Self::new(content).size(12.0).color_token(ColorToken::TextMuted)
     }
 
-    fn text_style(&self) -> TextStyle {
-        let mut style = TextStyle::default()
-            .with_size(self.font_size)
-            .with_color(self.color);
+    fn text_style(&self, theme: &Theme) -> TextStyle {
+        let color = self.color.unwrap_or_else(|| theme.color(self.color_token));
+        let size = self.font_size.unwrap_or(theme.typography.body_large);
+        let mut style = TextStyle::default().with_size(size).with_color(color);
 
         if self.bold {
             style = style.bold();
@@ -123,13 +137,13 @@ impl Widget for Text {
         self.id = id;
     }
 
-    fn style(&self) -> Style {
+    fn style(&self, _theme: &Theme) -> Style {
         Style::default()
     }
 
     fn paint(&self, ctx: &mut PaintContext) {
         let bounds = ctx.bounds();
-        let style = self.text_style();
+        let style = self.text_style(ctx.theme());
 
         if self.content.is_empty() {
             return;
@@ -160,8 +174,16 @@ impl Widget for Text {
         false
     }
 
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::Label,
+            name: Some(self.content.clone()),
+            ..Default::default()
+        }
+    }
+
     fn measure(&self, ctx: &mut crate::LayoutContext) -> Option<(f32, f32)> {
-        let style = self.text_style();
+        let style = self.text_style(ctx.theme);
         let (w, h) = ctx.text.measure(&self.content, &style, None);
         Some((w, h))
     }
@@ -1,8 +1,14 @@
 //! Text input widget.
 
-use crate::{EventContext, EventResponse, PaintContext, Widget};
+use crate::{
+    AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleValue, CursorIcon, EventContext,
+    EventResponse, PaintContext, Theme, ThemedStyle, Widget,
+};
+use std::borrow::Cow;
 use spark_core::Color;
-use spark_input::{shortcuts, InputEvent, Key};
+use spark_input::{
+    shortcuts, ActionMapper, CompositionState, InputEvent, Key, StandardAction,
+};
 use spark_layout::WidgetId;
 use spark_text::TextStyle;
 use taffy::prelude::*;
@@ -21,34 +27,91 @@ pub struct TextInputStyle {
     pub padding_h: f32,
     pub padding_v: f32,
     pub font_size: f32,
+    pub focus_ring_color: Color,
+    pub selection_color: Color,
 }
 
 impl Default for TextInputStyle {
     fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+impl ThemedStyle for TextInputStyle {
+    fn from_theme(theme: &Theme) -> Self {
+        let c = &theme.colors;
         Self {
-            background: Color::WHITE,
-            background_focused: Color::WHITE,
-            text_color: Color::from_hex(0x1F2937),
-            placeholder_color: Color::from_hex(0x9CA3AF),
-            border_color: Color::from_hex(0xD1D5DB),
-            border_color_focused: Color::from_hex(0x3B82F6),
+            background: c.surface,
+            background_focused: c.surface,
+            text_color: c.text,
+            placeholder_color: c.disabled,
+            border_color: c.border,
+            border_color_focused: c.primary,
             border_width: 1.0,
-            corner_radius: 6.0,
-            padding_h: 12.0,
-            padding_v: 8.0,
-            font_size: 14.0,
+            corner_radius: theme.radius.md,
+            padding_h: theme.spacing.md,
+            padding_v: theme.spacing.sm,
+            font_size: theme.typography.body,
+            focus_ring_color: c.focus_ring.with_alpha(0.5),
+            selection_color: c.selection,
         }
     }
 }
 
+/// Character drawn in place of each character of a password.
+const MASK_CHAR: char = '•';
+
+/// Byte range of the word, or run of spaces or punctuation, around `offset`.
+fn word_range(text: &str, offset: usize) -> (usize, usize) {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let Some(class) = text[offset..]
+        .chars()
+        .next()
+        .or_else(|| text[..offset].chars().next_back())
+        .map(is_word)
+    else {
+        return (offset, offset);
+    };
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_word(c) == class)
+        .last()
+        .map_or(offset, |(i, _)| i);
+    let end = text[offset..]
+        .char_indices()
+        .find(|&(_, c)| is_word(c) != class)
+        .map_or(text.len(), |(i, _)| offset + i);
+    (start, end)
+}
+
+/// The caret in logical window coordinates, from the drawn cursor rect and
+/// the translation it is drawn under (both in physical pixels).
+fn caret_rect(cursor: spark_core::Rect, translation: (f32, f32), scale: f32) -> spark_core::Rect {
+    spark_core::Rect::new(
+        (cursor.x + translation.0) / scale,
+        (cursor.y + translation.1) / scale,
+        cursor.width / scale,
+        cursor.height / scale,
+    )
+}
+
 /// A single-line text input widget.
 pub struct TextInput {
     id: WidgetId,
     value: String,
     placeholder: String,
-    style: TextInputStyle,
+    style: Option<TextInputStyle>,
+    /// Horizontal padding set by a wrapping widget, over the style's.
+    padding_h: Option<f32>,
     cursor_pos: usize,
     selection_start: Option<usize>,
+    /// Mask the value when drawing and when reporting it to assistive technology.
+    password: bool,
+    /// Uncommitted IME text, drawn at the cursor.
+    composition: Option<String>,
+    /// Drawn inside another widget's frame: no background, border or focus ring.
+    embedded: bool,
     on_change: Option<Box<dyn FnMut(&str) + Send + Sync>>,
     on_submit: Option<Box<dyn FnMut(&str) + Send + Sync>>,
 }
@@ -60,9 +123,13 @@ impl TextInput {
             id: WidgetId::default(),
             value: String::new(),
             placeholder: String::new(),
-            style: TextInputStyle::default(),
+            style: None,
+            padding_h: None,
             cursor_pos: 0,
             selection_start: None,
+            password: false,
+            composition: None,
+            embedded: false,
             on_change: None,
             on_submit: None,
         }
@@ -81,6 +148,12 @@ impl TextInput {
         self
     }
 
+    /// Mask the value, for passwords.
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
     /// Set the change handler.
     pub fn on_change(mut self, handler: impl FnMut(&str) + Send + Sync + 'static) -> Self {
         self.on_change = Some(Box::new(handler));
@@ -95,15 +168,42 @@ impl TextInput {
 
     /// Set the style.
     pub fn with_style(mut self, style: TextInputStyle) -> Self {
-        self.style = style;
+        self.style = Some(style);
         self
     }
 
+    /// The style for `theme`, with the padding override applied.
+    fn resolved_style(&self, theme: &Theme) -> TextInputStyle {
+        let mut style = theme.resolve(&self.style).into_owned();
+        if let Some(padding) = self.padding_h {
+            style.padding_h = padding;
+        }
+        style
+    }
+
+    /// Override the horizontal padding, keeping the rest of the style.
+    pub(crate) fn set_padding_h(&mut self, padding: f32) {
+        self.padding_h = Some(padding);
+    }
+
+    /// Draw only the text and caret, for fields inside another widget's frame.
+    pub(crate) fn set_embedded(&mut self, embedded: bool) {
+        self.embedded = embedded;
+    }
+
     /// Get the current value.
     pub fn get_value(&self) -> &str {
         &self.value
     }
 
+    /// Replace the whole value, placing the cursor at the end.
+    pub(crate) fn replace_value(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cursor_pos = self.value.len();
+        self.selection_start = None;
+        self.fire_change();
+    }
+
     fn insert_char(&mut self, c: char) {
         self.delete_selection();
         self.value.insert(self.cursor_pos, c);
@@ -195,11 +295,71 @@ impl TextInput {
         }
     }
 
+    /// The selected part of the value.
+    fn selected_text(&self) -> Option<&str> {
+        let start = self.selection_start?;
+        let (from, to) = if start < self.cursor_pos {
+            (start, self.cursor_pos)
+        } else {
+            (self.cursor_pos, start)
+        };
+        (from < to).then(|| &self.value[from..to])
+    }
+
+    /// Insert pasted or composed text, dropping line breaks and other control characters.
+    fn insert_text(&mut self, text: &str) {
+        let text: String = text
+            .chars()
+            .map(|c| if c == '\n' || c == '\t' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        if !text.is_empty() {
+            self.insert_str(&text);
+        }
+    }
+
+    /// The IME composition in progress, if it has any text.
+    fn preedit(&self) -> Option<&str> {
+        self.composition
+            .as_deref()
+            .filter(|text| !text.is_empty() && !self.password)
+    }
+
     fn select_all(&mut self) {
         self.selection_start = Some(0);
         self.cursor_pos = self.value.len();
     }
 
+    /// Select the word around the cursor; a password selects as a whole,
+    /// so its word breaks stay hidden.
+    fn select_word(&mut self) {
+        if self.password {
+            self.select_all();
+            return;
+        }
+        let (start, end) = word_range(&self.value, self.cursor_pos);
+        self.selection_start = Some(start);
+        self.cursor_pos = end;
+    }
+
+    /// The text as drawn: the value, or one mask character per character.
+    fn display_text(&self) -> Cow<'_, str> {
+        if self.password {
+            Cow::Owned(MASK_CHAR.to_string().repeat(self.value.chars().count()))
+        } else {
+            Cow::Borrowed(&self.value)
+        }
+    }
+
+    /// Map a byte offset in the value to the same position in the display text.
+    fn display_offset(&self, offset: usize) -> usize {
+        if self.password {
+            self.value[..offset].chars().count() * MASK_CHAR.len_utf8()
+        } else {
+            offset
+        }
+    }
+
     fn fire_change(&mut self) {
         if let Some(handler) = &mut self.on_change {
             handler(&self.value);
@@ -222,13 +382,14 @@ impl Widget for TextInput {
         self.id = id;
     }
 
-    fn style(&self) -> Style {
+    fn style(&self, theme: &Theme) -> Style {
+        let style = self.resolved_style(theme);
         Style {
             padding: Rect {
-                left: length(self.style.padding_h),
-                right: length(self.style.padding_h),
-                top: length(self.style.padding_v),
-                bottom: length(self.style.padding_v),
+                left: length(style.padding_h),
+                right: length(style.padding_h),
+                top: length(style.padding_v),
+                bottom: length(style.padding_v),
             },
             min_size: Size {
                 width: length(100.0),
@@ -242,71 +403,78 @@ impl Widget for TextInput {
         let bounds = ctx.bounds();
         let focused = ctx.has_focus();
         let scale = ctx.scale_factor;
+        let style = self.resolved_style(ctx.theme());
 
         let bg = if focused {
-            self.style.background_focused
+            style.background_focused
         } else {
-            self.style.background
+            style.background
         };
 
         let border = if focused {
-            self.style.border_color_focused
+            style.border_color_focused
         } else {
-            self.style.border_color
+            style.border_color
         };
 
-        // Draw background
-        ctx.fill_bordered_rect(
-            bounds,
-            bg,
-            self.style.corner_radius,
-            self.style.border_width,
-            border,
-        );
-
-        // Focus ring (scale the offset values)
-        if focused {
-            let offset = 2.0 * scale;
-            let focus_bounds = spark_core::Rect::new(
-                bounds.x - offset,
-                bounds.y - offset,
-                bounds.width + offset * 2.0,
-                bounds.height + offset * 2.0,
-            );
-            ctx.fill_bordered_rect(
-                focus_bounds,
-                Color::TRANSPARENT,
-                self.style.corner_radius + 2.0,
-                2.0,
-                Color::from_hex(0x60A5FA).with_alpha(0.5),
-            );
+        if !self.embedded {
+            // Draw background
+            ctx.fill_bordered_rect(bounds, bg, style.corner_radius, style.border_width, border);
+
+            // Focus ring (scale the offset values), only for keyboard focus
+            if ctx.focus_visible() {
+                let focus_bounds = bounds.expand(2.0 * scale);
+                ctx.fill_bordered_rect(
+                    focus_bounds,
+                    Color::TRANSPARENT,
+                    style.corner_radius + 2.0,
+                    2.0,
+                    style.focus_ring_color,
+                );
+            }
         }
 
         // Calculate text area (inside padding) - scale padding for physical pixels
-        let padding_h = self.style.padding_h * scale;
+        let padding_h = style.padding_h * scale;
         let text_x = bounds.x + padding_h;
         let text_width = bounds.width - padding_h * 2.0;
 
         // Create text style (font size is in logical pixels, will be scaled by draw_text)
         let text_style = TextStyle::default()
-            .with_size(self.style.font_size)
-            .with_color(self.style.text_color);
+            .with_size(style.font_size)
+            .with_color(style.text_color);
 
         let placeholder_style = TextStyle::default()
-            .with_size(self.style.font_size)
-            .with_color(self.style.placeholder_color);
+            .with_size(style.font_size)
+            .with_color(style.placeholder_color);
 
         // Measure text height for vertical centering
         let (_, text_height) = ctx.measure_text("Ay", &text_style);
         let text_y = bounds.y + (bounds.height - text_height) / 2.0;
 
         // Draw placeholder or value
-        if self.value.is_empty() {
+        if let Some(preedit) = self.preedit() {
+            // Composed text sits at the cursor, underlined until committed
+            let before = &self.value[..self.cursor_pos];
+            let after = &self.value[self.cursor_pos..];
+            ctx.draw_text(&format!("{before}{preedit}{after}"), &text_style, text_x, text_y);
+            let (preedit_x, _) = ctx.measure_text(before, &text_style);
+            let (preedit_width, _) = ctx.measure_text(preedit, &text_style);
+            let underline = spark_core::Rect::new(
+                text_x + preedit_x,
+                text_y + text_height - scale,
+                preedit_width,
+                scale,
+            );
+            ctx.fill_rect(underline, style.text_color);
+        } else if self.value.is_empty() {
             // Draw placeholder text
             if !self.placeholder.is_empty() {
                 ctx.draw_text(&self.placeholder, &placeholder_style, text_x, text_y);
             }
         } else {
+            let display = self.display_text();
+
             // Draw selection highlight if any
             if let Some(sel_start) = self.selection_start {
                 let (start, end) = if sel_start < self.cursor_pos {
@@ -316,11 +484,12 @@ impl Widget for TextInput {
                 };
 
                 // Measure text before selection start
-                let text_before_sel = &self.value[..start];
+                let (start, end) = (self.display_offset(start), self.display_offset(end));
+                let text_before_sel = &display[..start];
                 let (sel_x_start, _) = ctx.measure_text(text_before_sel, &text_style);
 
                 // Measure selected text
-                let selected_text = &self.value[start..end];
+                let selected_text = &display[start..end];
                 let (sel_width, _) = ctx.measure_text(selected_text, &text_style);
 
                 // Draw selection rectangle
@@ -331,47 +500,68 @@ impl Widget for TextInput {
                         sel_width.min(text_width - sel_x_start),
                         text_height,
                     );
-                    ctx.fill_rect(sel_rect, Color::from_hex(0x3B82F6).with_alpha(0.3));
+                    ctx.fill_rect(sel_rect, style.selection_color);
                 }
             }
 
             // Draw the text value
-            ctx.draw_text(&self.value, &text_style, text_x, text_y);
+            ctx.draw_text(&display, &text_style, text_x, text_y);
         }
 
         // Draw cursor when focused
         if focused {
-            // Blink cursor at ~2Hz
-            let cursor_visible = (ctx.elapsed_time * 2.0).fract() < 0.5;
+            // Measure text up to cursor position
+            let display = self.display_text();
+            let mut text_before_cursor = display[..self.display_offset(self.cursor_pos)].to_string();
+            if let Some(preedit) = self.preedit() {
+                text_before_cursor.push_str(preedit);
+            }
+            let (cursor_x_offset, _) = ctx.measure_text(&text_before_cursor, &text_style);
 
-            if cursor_visible {
-                // Measure text up to cursor position
-                let text_before_cursor = &self.value[..self.cursor_pos];
-                let (cursor_x_offset, _) = ctx.measure_text(text_before_cursor, &text_style);
+            let cursor_x = text_x + cursor_x_offset;
+            let cursor_height = text_height;
 
-                let cursor_x = text_x + cursor_x_offset;
-                let cursor_height = text_height;
+            // Draw cursor line (scale cursor width)
+            let cursor_width = 2.0 * scale;
+            let cursor_rect = spark_core::Rect::new(cursor_x, text_y, cursor_width, cursor_height);
 
-                // Draw cursor line (scale cursor width)
-                let cursor_width = 2.0 * scale;
-                let cursor_rect = spark_core::Rect::new(cursor_x, text_y, cursor_width, cursor_height);
-                ctx.fill_rect(cursor_rect, self.style.text_color);
+            // Report the caret where it lands on screen (inside scroll views too)
+            // so the IME window follows it
+            let caret = caret_rect(cursor_rect, ctx.translation(), scale);
+            ctx.focus.report_caret_rect(caret);
+
+            // Blink cursor at ~2Hz
+            let cursor_visible = (ctx.elapsed_time * 2.0).fract() < 0.5;
+            if cursor_visible {
+                ctx.fill_rect(cursor_rect, style.text_color);
             }
         }
     }
 
     fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
         match event {
-            InputEvent::PointerDown { pos, .. } => {
+            InputEvent::PointerDown {
+                pos, click_count, ..
+            } => {
                 if ctx.contains(*pos) {
                     ctx.request_focus();
-                    // TODO: Position cursor based on click position
-                    self.cursor_pos = self.value.len();
-                    self.selection_start = None;
+                    match *click_count {
+                        1 => {
+                            // TODO: Position cursor based on click position
+                            self.cursor_pos = self.value.len();
+                            self.selection_start = None;
+                        }
+                        2 => self.select_word(),
+                        // The whole line, which is the whole value
+                        _ => self.select_all(),
+                    }
                     return EventResponse::focus();
                 }
                 EventResponse::default()
             }
+            InputEvent::PointerMove { pos, .. } if ctx.contains(*pos) => {
+                EventResponse::default().with_cursor(CursorIcon::Text)
+            }
             InputEvent::KeyDown { event } => {
                 if !ctx.has_focus() {
                     return EventResponse::default();
@@ -385,6 +575,26 @@ impl Widget for TextInput {
                     return EventResponse::handled();
                 }
 
+                // Clipboard; a password is never copied out
+                let mapper = ActionMapper::new();
+                let input = InputEvent::KeyDown { event: event.clone() };
+                if mapper.is_action(&input, StandardAction::Paste) {
+                    ctx.clipboard().request_text();
+                    return EventResponse::handled();
+                }
+                let copy = mapper.is_action(&input, StandardAction::Copy);
+                let cut = mapper.is_action(&input, StandardAction::Cut);
+                if copy || cut {
+                    if let Some(text) = self.selected_text().filter(|_| !self.password) {
+                        ctx.clipboard().set_text(text.to_string());
+                        if cut {
+                            self.delete_selection();
+                            self.fire_change();
+                        }
+                    }
+                    return EventResponse::handled();
+                }
+
                 if shortcuts::is_backspace(event) {
                     self.backspace();
                     return EventResponse::handled();
@@ -454,6 +664,28 @@ impl Widget for TextInput {
                 }
                 EventResponse::default()
             }
+            InputEvent::Composition { event } => {
+                if !ctx.has_focus() {
+                    return EventResponse::default();
+                }
+                match event.state {
+                    CompositionState::Start | CompositionState::Update => {
+                        self.composition = Some(event.data.clone());
+                    }
+                    CompositionState::End => {
+                        self.composition = None;
+                        self.insert_text(&event.data);
+                    }
+                }
+                EventResponse::handled()
+            }
+            InputEvent::Paste { text } => {
+                if ctx.has_focus() {
+                    self.insert_text(text);
+                    return EventResponse::handled();
+                }
+                EventResponse::default()
+            }
             _ => EventResponse::default(),
         }
     }
@@ -462,6 +694,32 @@ impl Widget for TextInput {
         true
     }
 
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo {
+            role: AccessibleRole::TextField,
+            value: Some(self.display_text().into_owned()),
+            description: (!self.placeholder.is_empty()).then(|| self.placeholder.clone()),
+            focusable: true,
+            actions: vec![AccessibleAction::SetValue],
+            ..Default::default()
+        }
+    }
+
+    fn accessibility_action(
+        &mut self,
+        _ctx: &mut EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        match (action, value) {
+            (AccessibleAction::SetValue, Some(value)) => {
+                self.replace_value(&value.as_text());
+                EventResponse::handled()
+            }
+            _ => EventResponse::default(),
+        }
+    }
+
     fn on_focus(&mut self) {
         // Select all on focus
         self.select_all();
@@ -469,6 +727,35 @@ impl Widget for TextInput {
 
     fn on_blur(&mut self) {
         self.selection_start = None;
+        self.composition = None;
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_range() {
+        let text = "hello, wide world";
+        assert_eq!(word_range(text, 2), (0, 5));
+        assert_eq!(word_range(text, 5), (5, 7));
+        assert_eq!(word_range(text, 7), (7, 11));
+        assert_eq!(word_range(text, text.len()), (12, 17));
+        assert_eq!(word_range("", 0), (0, 0));
+    }
+
+    #[test]
+    fn test_caret_rect_follows_the_scroll_offset() {
+        let scale = 2.0;
+        let mut draw_list = spark_render::DrawList::new();
+        // An input 30 logical pixels down a scroll view scrolled by 100
+        draw_list.push_translation((0.0, -100.0 * scale));
+        let cursor = spark_core::Rect::new(20.0, 60.0, 4.0, 32.0);
+        let caret = caret_rect(cursor, draw_list.translation(), scale);
+        assert_eq!(caret, spark_core::Rect::new(10.0, -70.0, 2.0, 16.0));
+        draw_list.pop_translation();
+        let caret = caret_rect(cursor, draw_list.translation(), scale);
+        assert_eq!(caret, spark_core::Rect::new(10.0, 30.0, 2.0, 16.0));
+    }
+}
@@ -0,0 +1,294 @@
+//! Theme tokens (colors, spacing, radius, typography) with light and dark variants.
+
+use spark_core::Color;
+use std::borrow::Cow;
+
+/// Light or dark appearance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ThemeMode {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Semantic color tokens.
+#[derive(Clone, Debug)]
+pub struct ThemeColors {
+    /// Window background.
+    pub background: Color,
+    /// Panels and cards.
+    pub surface: Color,
+    /// Raised or alternating surfaces.
+    pub surface_alt: Color,
+    /// Primary accent.
+    pub primary: Color,
+    /// Primary accent while hovered.
+    pub primary_hovered: Color,
+    /// Primary accent while pressed.
+    pub primary_pressed: Color,
+    /// Text drawn on the primary accent.
+    pub on_primary: Color,
+    /// Default text.
+    pub text: Color,
+    /// Secondary text (captions, section labels).
+    pub text_muted: Color,
+    /// Borders and dividers.
+    pub border: Color,
+    /// Focus ring.
+    pub focus_ring: Color,
+    /// Disabled control fill.
+    pub disabled: Color,
+    /// Disabled text.
+    pub text_disabled: Color,
+    /// Text selection highlight.
+    pub selection: Color,
+    /// Success state.
+    pub success: Color,
+    /// Warning state.
+    pub warning: Color,
+    /// Destructive/error state.
+    pub danger: Color,
+    /// Neutral secondary actions.
+    pub secondary: Color,
+}
+
+/// Spacing scale in logical pixels.
+#[derive(Clone, Debug)]
+pub struct Spacing {
+    pub xs: f32,
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    pub xl: f32,
+    pub xxl: f32,
+}
+
+/// Corner radius scale in logical pixels.
+#[derive(Clone, Debug)]
+pub struct Radius {
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    /// Large enough to produce pills and circles.
+    pub full: f32,
+}
+
+/// Font size scale in logical pixels.
+#[derive(Clone, Debug)]
+pub struct Typography {
+    pub caption: f32,
+    pub body: f32,
+    pub body_large: f32,
+    pub subheader: f32,
+    pub header: f32,
+    pub display: f32,
+}
+
+/// A named color token, resolved against the active theme at paint time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorToken {
+    Background,
+    Surface,
+    SurfaceAlt,
+    Primary,
+    OnPrimary,
+    Text,
+    TextMuted,
+    Border,
+    FocusRing,
+    Success,
+    Warning,
+    Danger,
+    Secondary,
+}
+
+/// A complete set of design tokens.
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub mode: ThemeMode,
+    pub colors: ThemeColors,
+    pub spacing: Spacing,
+    pub radius: Radius,
+    pub typography: Typography,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            xs: 4.0,
+            sm: 8.0,
+            md: 12.0,
+            lg: 16.0,
+            xl: 24.0,
+            xxl: 32.0,
+        }
+    }
+}
+
+impl Default for Radius {
+    fn default() -> Self {
+        Self {
+            sm: 4.0,
+            md: 6.0,
+            lg: 12.0,
+            full: 9999.0,
+        }
+    }
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            caption: 12.0,
+            body: 14.0,
+            body_large: 16.0,
+            subheader: 18.0,
+            header: 24.0,
+            display: 28.0,
+        }
+    }
+}
+
+impl Theme {
+    /// The default light theme.
+    pub fn light() -> Self {
+        Self {
+            mode: ThemeMode::Light,
+            colors: ThemeColors {
+                background: Color::from_hex(0xF3F4F6),      // Gray 100
+                surface: Color::WHITE,
+                surface_alt: Color::from_hex(0xF9FAFB),     // Gray 50
+                primary: Color::from_hex(0x3B82F6),         // Blue
+                primary_hovered: Color::from_hex(0x2563EB), // Darker blue
+                primary_pressed: Color::from_hex(0x1D4ED8), // Even darker
+                on_primary: Color::WHITE,
+                text: Color::from_hex(0x1F2937),            // Gray 800
+                text_muted: Color::from_hex(0x6B7280),      // Gray 500
+                border: Color::from_hex(0xD1D5DB),          // Gray 300
+                focus_ring: Color::from_hex(0x60A5FA),      // Blue 400
+                disabled: Color::from_hex(0x9CA3AF),        // Gray 400
+                text_disabled: Color::from_hex(0x6B7280),   // Gray 500
+                selection: Color::from_hex(0x3B82F6).with_alpha(0.3),
+                success: Color::from_hex(0x22C55E),         // Green
+                warning: Color::from_hex(0xF59E0B),         // Amber
+                danger: Color::from_hex(0xEF4444),          // Red
+                secondary: Color::from_hex(0x64748B),       // Slate
+            },
+            spacing: Spacing::default(),
+            radius: Radius::default(),
+            typography: Typography::default(),
+        }
+    }
+
+    /// The default dark theme.
+    pub fn dark() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            colors: ThemeColors {
+                background: Color::from_hex(0x0F172A),      // Slate 900
+                surface: Color::from_hex(0x1E293B),         // Slate 800
+                surface_alt: Color::from_hex(0x334155),     // Slate 700
+                primary: Color::from_hex(0x3B82F6),         // Blue
+                primary_hovered: Color::from_hex(0x60A5FA), // Lighter blue
+                primary_pressed: Color::from_hex(0x2563EB), // Darker blue
+                on_primary: Color::WHITE,
+                text: Color::from_hex(0xF1F5F9),            // Slate 100
+                text_muted: Color::from_hex(0x94A3B8),      // Slate 400
+                border: Color::from_hex(0x475569),          // Slate 600
+                focus_ring: Color::from_hex(0x93C5FD),      // Blue 300
+                disabled: Color::from_hex(0x475569),        // Slate 600
+                text_disabled: Color::from_hex(0x94A3B8),   // Slate 400
+                selection: Color::from_hex(0x60A5FA).with_alpha(0.35),
+                success: Color::from_hex(0x22C55E),         // Green
+                warning: Color::from_hex(0xF59E0B),         // Amber
+                danger: Color::from_hex(0xEF4444),          // Red
+                secondary: Color::from_hex(0x64748B),       // Slate
+            },
+            spacing: Spacing::default(),
+            radius: Radius::default(),
+            typography: Typography::default(),
+        }
+    }
+
+    /// The default theme for a mode.
+    pub fn for_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Light => Self::light(),
+            ThemeMode::Dark => Self::dark(),
+        }
+    }
+
+    /// Resolve a color token.
+    pub fn color(&self, token: ColorToken) -> Color {
+        let c = &self.colors;
+        match token {
+            ColorToken::Background => c.background,
+            ColorToken::Surface => c.surface,
+            ColorToken::SurfaceAlt => c.surface_alt,
+            ColorToken::Primary => c.primary,
+            ColorToken::OnPrimary => c.on_primary,
+            ColorToken::Text => c.text,
+            ColorToken::TextMuted => c.text_muted,
+            ColorToken::Border => c.border,
+            ColorToken::FocusRing => c.focus_ring,
+            ColorToken::Success => c.success,
+            ColorToken::Warning => c.warning,
+            ColorToken::Danger => c.danger,
+            ColorToken::Secondary => c.secondary,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+/// A widget style whose defaults come from theme tokens.
+///
+/// Widgets keep the style they were given as an `Option` and resolve it with
+/// [`Theme::resolve`] against the theme they are laid out and painted with,
+/// so an unstyled widget follows theme switches, sizes included.
+pub trait ThemedStyle: Clone {
+    /// Build the style from theme tokens.
+    fn from_theme(theme: &Theme) -> Self;
+}
+
+impl Theme {
+    /// `style` if one was set, otherwise the default style for this theme.
+    pub fn resolve<'a, S: ThemedStyle>(&self, style: &'a Option<S>) -> Cow<'a, S> {
+        match style {
+            Some(style) => Cow::Borrowed(style),
+            None => Cow::Owned(S::from_theme(self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_resolve_per_mode() {
+        let light = Theme::for_mode(ThemeMode::Light);
+        let dark = Theme::for_mode(ThemeMode::Dark);
+        assert_eq!(light.color(ColorToken::Text), light.colors.text);
+        assert_ne!(light.color(ColorToken::Background), dark.color(ColorToken::Background));
+    }
+
+    #[test]
+    fn test_unset_styles_resolve_from_the_theme() {
+        let mut theme = Theme::light();
+        theme.spacing.lg = 40.0;
+        theme.radius.md = 2.0;
+        theme.typography.body = 20.0;
+        let style = theme.resolve::<crate::ButtonStyle>(&None);
+        assert_eq!(style.padding_h, 40.0);
+        assert_eq!(style.corner_radius, 2.0);
+        assert_eq!(style.font_size, 20.0);
+
+        let custom = Some(crate::ButtonStyle::from_theme(&Theme::light()));
+        assert_eq!(theme.resolve(&custom).padding_h, Theme::light().spacing.lg);
+    }
+}
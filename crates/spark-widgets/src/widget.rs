@@ -1,14 +1,40 @@
 //! Widget trait and response types.
 
-use spark_input::InputEvent;
+use crate::{AccessibleAction, AccessibleInfo, AccessibleValue, Theme, ThemeMode};
+use spark_core::Rect;
+use spark_input::{InputEvent, Shortcut};
 use spark_layout::WidgetId;
 
+/// Pointer shape shown over a widget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorIcon {
+    /// The platform's arrow.
+    #[default]
+    Default,
+    /// A pointing hand, for links and buttons.
+    Pointer,
+    /// An I-beam, for editable text.
+    Text,
+    /// Resizing left and right, e.g. a vertical divider.
+    ColResize,
+    /// Resizing up and down, e.g. a horizontal divider.
+    RowResize,
+    /// An open hand, over something that can be dragged.
+    Grab,
+    /// A closed hand, while dragging.
+    Grabbing,
+    /// Over something disabled.
+    NotAllowed,
+}
+
 /// Response from handling an event.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct EventResponse {
-    /// Whether the event was handled and should not propagate.
+    /// Whether the event was handled, which stops it bubbling to ancestors.
     pub handled: bool,
-    /// Request to capture all pointer events (e.g., during drag).
+    /// Request to capture all events from the event's pointer (e.g., during
+    /// drag). Each finger on a touch screen is captured on its own. The
+    /// capture ends with the pointer's release or `PointerCancel`.
     pub capture_pointer: bool,
     /// Request to release pointer capture.
     pub release_pointer: bool,
@@ -20,6 +46,15 @@ pub struct EventResponse {
     pub repaint: bool,
     /// Request a layout recalculation.
     pub relayout: bool,
+    /// Request a new UI zoom multiplier (applied on top of the window scale factor).
+    pub ui_zoom: Option<f32>,
+    /// Request a switch to the app's light or dark theme.
+    pub theme_mode: Option<ThemeMode>,
+    /// Close the window and return from `App::run` after the current event.
+    pub exit: bool,
+    /// Pointer shape to show, set in response to a pointer move over the
+    /// widget. The topmost widget asking wins; with none, the arrow is shown.
+    pub cursor: Option<CursorIcon>,
 }
 
 impl EventResponse {
@@ -67,6 +102,39 @@ impl EventResponse {
         }
     }
 
+    /// Change the UI zoom multiplier and handle the event.
+    pub fn zoom(zoom: f32) -> Self {
+        Self {
+            handled: true,
+            ui_zoom: Some(zoom),
+            ..Self::default()
+        }
+    }
+
+    /// Switch the app to its light or dark theme and handle the event.
+    pub fn switch_theme(mode: ThemeMode) -> Self {
+        Self {
+            handled: true,
+            theme_mode: Some(mode),
+            ..Self::default()
+        }
+    }
+
+    /// Close the app, e.g. from a Quit menu item, and handle the event.
+    pub fn exit() -> Self {
+        Self {
+            handled: true,
+            exit: true,
+            ..Self::default()
+        }
+    }
+
+    /// Show `cursor` while the pointer is over the widget.
+    pub fn with_cursor(mut self, cursor: CursorIcon) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
     /// Merge another response into this one.
     pub fn merge(&mut self, other: EventResponse) {
         self.handled |= other.handled;
@@ -76,11 +144,21 @@ impl EventResponse {
         self.release_focus |= other.release_focus;
         self.repaint |= other.repaint;
         self.relayout |= other.relayout;
+        self.ui_zoom = other.ui_zoom.or(self.ui_zoom);
+        self.theme_mode = other.theme_mode.or(self.theme_mode);
+        self.exit |= other.exit;
+        self.cursor = other.cursor.or(self.cursor);
     }
 
     /// Check if any action was requested.
     pub fn needs_action(&self) -> bool {
-        self.repaint || self.relayout || self.request_focus || self.capture_pointer
+        self.repaint
+            || self.relayout
+            || self.request_focus
+            || self.capture_pointer
+            || self.ui_zoom.is_some()
+            || self.theme_mode.is_some()
+            || self.exit
     }
 }
 
@@ -93,7 +171,11 @@ pub trait Widget {
     fn set_id(&mut self, id: WidgetId);
 
     /// Get the layout style for this widget.
-    fn style(&self) -> taffy::Style {
+    ///
+    /// `theme` is the app's active theme, for sizes such as padding that
+    /// come from theme tokens.
+    fn style(&self, theme: &Theme) -> taffy::Style {
+        let _ = theme;
         taffy::Style::default()
     }
 
@@ -106,7 +188,48 @@ pub trait Widget {
         // Default: no-op
     }
 
+    /// Paint content that floats above the whole UI, such as an open dropdown list.
+    ///
+    /// Called after every widget has painted, outside any clip and over
+    /// everything painted before. Return true from
+    /// [`has_overlay`](Self::has_overlay) while there is something to paint.
+    fn paint_overlay(&self, _ctx: &mut super::PaintContext) {}
+
+    /// Whether this widget has an open overlay.
+    ///
+    /// Such widgets see pointer events before the widgets beneath the overlay.
+    fn has_overlay(&self) -> bool {
+        false
+    }
+
+    /// Whether this widget is an open modal.
+    ///
+    /// While one is open, only its subtree receives input and Tab focus, and
+    /// it paints over the rest of the UI.
+    fn is_modal(&self) -> bool {
+        false
+    }
+
+    /// Whether this widget animates on its own, such as a spinner.
+    ///
+    /// The app keeps repainting every frame while any painted widget returns
+    /// true, without the widget requesting each frame.
+    fn needs_animation(&self) -> bool {
+        false
+    }
+
     /// Handle an input event.
+    ///
+    /// Pointer events go to the topmost widget under the pointer, keys and
+    /// text to the focused widget, and then bubble to their ancestors until
+    /// one returns a handled response; [`phase`](crate::EventContext::phase)
+    /// and [`target_id`](crate::EventContext::target_id) tell a container
+    /// whether the event is its own or a descendant's. Open overlays, and a
+    /// widget holding the pointer capture, get pointer events first as their
+    /// target, without bubbling. [`InputEvent::pointer`] tells the mouse and
+    /// each finger apart; a tap presses the primary button.
+    /// Positions are in the widget's layout space, with enclosing scroll
+    /// offsets already applied.
     fn event(&mut self, ctx: &mut super::EventContext, event: &InputEvent) -> EventResponse {
         let _ = (ctx, event);
         EventResponse::default()
@@ -128,22 +251,75 @@ pub trait Widget {
     /// Called when the widget loses focus.
     fn on_blur(&mut self) {}
 
+    /// Whether children overlap, later ones on top. Events then reach
+    /// the children last to first, so the topmost gets them first.
+    fn stacks_children(&self) -> bool {
+        false
+    }
+
     /// Whether this widget can receive keyboard focus.
     fn focusable(&self) -> bool {
         false
     }
 
+    /// Key presses this widget takes without focus, such as a menu bar's
+    /// mnemonics. The app binds them in a shortcut map and sends a matching
+    /// press straight here, before the focused widget sees it.
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        Vec::new()
+    }
+
     /// Whether this widget is a scroll container.
     fn is_scroll_container(&self) -> bool {
         false
     }
 
-    /// Measure the widget's preferred size (for intrinsic sizing).
+    /// Current scroll offset in logical pixels, for scroll containers.
+    fn scroll_offset(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    /// Restore a scroll offset (e.g. after the UI is rebuilt).
+    fn set_scroll_offset(&mut self, _x: f32, _y: f32) {}
+
+    /// Scroll so `rect` (relative to the content origin) is inside `viewport`.
+    fn scroll_to_visible(&mut self, _rect: Rect, _viewport: Rect) {}
+
+    /// Describe this widget to assistive technologies.
+    fn accessibility_info(&self) -> AccessibleInfo {
+        AccessibleInfo::default()
+    }
+
+    /// Handle an action requested by assistive technology.
+    ///
+    /// Focus is handled by the framework. Click falls back to the widget's
+    /// activate key handling when this returns an unhandled response.
+    fn accessibility_action(
+        &mut self,
+        ctx: &mut super::EventContext,
+        action: AccessibleAction,
+        value: Option<&AccessibleValue>,
+    ) -> EventResponse {
+        let _ = (ctx, action, value);
+        EventResponse::default()
+    }
+
+    /// Measure the widget's intrinsic content size, in logical pixels.
+    ///
+    /// Called for leaf widgets when the layout tree is built. The style's
+    /// padding is added around the result and explicit sizes take precedence.
     fn measure(&self, ctx: &mut super::LayoutContext) -> Option<(f32, f32)> {
         let _ = ctx;
         None
     }
 
+    /// Whether the measured size depends on the width, as with wrapping
+    /// text. Such leaves are measured again with `max_width` set to the
+    /// width layout gives them.
+    fn wraps(&self) -> bool {
+        false
+    }
+
     /// Check if this widget is a native widget (rendered by the platform).
     /// Default implementation returns false.
     fn is_native(&self) -> bool {
@@ -162,4 +338,11 @@ pub trait Widget {
     fn register_native(&self, _widget_id: WidgetId, _register: &mut dyn FnMut(WidgetId, *mut std::ffi::c_void)) {
         // Default: do nothing
     }
+
+    /// The native view that native descendants are placed in, for native containers
+    /// such as scroll views. Their first child's layout is the view's coordinate space.
+    /// Default implementation returns None.
+    fn native_document_view(&self) -> Option<*mut std::ffi::c_void> {
+        None
+    }
 }
@@ -2,11 +2,41 @@
 //!
 //! This module provides integration with AccessKit for cross-platform
 //! accessibility support (screen readers, alternative input methods, etc.)
+//!
+//! The platform adapter owns the window's accessibility children, so native
+//! views embedded in the tree are not reachable on their own. Each one gets a
+//! stand-in node built from the widget's `accessibility_info`, placed where
+//! the widget sits in the tree, and actions on it go to the widget's
+//! `accessibility_action` instead of synthesized input events.
+//!
+//! Announcements are spoken through live regions: hidden nodes under the
+//! window, one per politeness, whose text changes with each announcement.
+
+pub(crate) mod adapter;
 
 use accesskit::{Action, Node, NodeId, Role, Tree, TreeId, TreeUpdate};
-use spark_layout::WidgetId;
+use glam::Vec2;
+use spark_layout::{LayoutTree, WidgetId};
+use spark_widgets::{Announcement, Politeness, Widget};
 use std::collections::HashMap;
 
+/// Nodes for announcements, out of the way of ids given to widgets.
+const LIVE_REGIONS: [(NodeId, Politeness); 2] = [
+    (NodeId(u64::MAX - 1), Politeness::Polite),
+    (NodeId(u64::MAX), Politeness::Assertive),
+];
+
+/// Destination for tree updates: the platform adapter, or a fake in tests.
+pub(crate) trait TreeUpdateSink {
+    /// Build and publish an update if assistive technology is listening.
+    fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate);
+}
+
+pub use spark_widgets::accessibility::{
+    build_node, AccessibleAction, AccessibleInfo, AccessibleRole, AccessibleScroll,
+    AccessibleValue,
+};
+
 /// Maps between Spark WidgetIds and AccessKit NodeIds.
 pub struct AccessibilityIdMap {
     widget_to_node: HashMap<WidgetId, NodeId>,
@@ -61,148 +91,6 @@ impl AccessibilityIdMap {
     }
 }
 
-/// Accessibility information that widgets can provide.
-#[derive(Clone, Debug, Default)]
-pub struct AccessibleInfo {
-    /// The role of this element (button, text field, etc.)
-    pub role: AccessibleRole,
-    /// Human-readable name/label
-    pub name: Option<String>,
-    /// Human-readable description
-    pub description: Option<String>,
-    /// Current value (for sliders, text fields, etc.)
-    pub value: Option<String>,
-    /// Whether the element is focusable
-    pub focusable: bool,
-    /// Whether the element is currently focused
-    pub focused: bool,
-    /// Whether the element is disabled
-    pub disabled: bool,
-    /// Available actions
-    pub actions: Vec<AccessibleAction>,
-}
-
-/// Role of an accessible element.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub enum AccessibleRole {
-    /// Generic container
-    #[default]
-    GenericContainer,
-    /// Push button
-    Button,
-    /// Text input field
-    TextField,
-    /// Static text label
-    Label,
-    /// Checkbox
-    CheckBox,
-    /// Radio button
-    RadioButton,
-    /// Slider
-    Slider,
-    /// List
-    List,
-    /// List item
-    ListItem,
-    /// Window
-    Window,
-    /// Scroll view
-    ScrollView,
-    /// Image
-    Image,
-}
-
-impl From<AccessibleRole> for Role {
-    fn from(role: AccessibleRole) -> Self {
-        match role {
-            AccessibleRole::GenericContainer => Role::GenericContainer,
-            AccessibleRole::Button => Role::Button,
-            AccessibleRole::TextField => Role::TextInput,
-            AccessibleRole::Label => Role::Label,
-            AccessibleRole::CheckBox => Role::CheckBox,
-            AccessibleRole::RadioButton => Role::RadioButton,
-            AccessibleRole::Slider => Role::Slider,
-            AccessibleRole::List => Role::List,
-            AccessibleRole::ListItem => Role::ListItem,
-            AccessibleRole::Window => Role::Window,
-            AccessibleRole::ScrollView => Role::ScrollView,
-            AccessibleRole::Image => Role::Image,
-        }
-    }
-}
-
-/// Actions that assistive technologies can request.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum AccessibleAction {
-    /// Click/activate the element
-    Click,
-    /// Focus the element
-    Focus,
-    /// Set the element's value
-    SetValue,
-    /// Increment (for sliders, etc.)
-    Increment,
-    /// Decrement (for sliders, etc.)
-    Decrement,
-    /// Scroll up
-    ScrollUp,
-    /// Scroll down
-    ScrollDown,
-    /// Scroll left
-    ScrollLeft,
-    /// Scroll right
-    ScrollRight,
-}
-
-impl From<AccessibleAction> for Action {
-    fn from(action: AccessibleAction) -> Self {
-        match action {
-            AccessibleAction::Click => Action::Click,
-            AccessibleAction::Focus => Action::Focus,
-            AccessibleAction::SetValue => Action::SetValue,
-            AccessibleAction::Increment => Action::Increment,
-            AccessibleAction::Decrement => Action::Decrement,
-            AccessibleAction::ScrollUp => Action::ScrollUp,
-            AccessibleAction::ScrollDown => Action::ScrollDown,
-            AccessibleAction::ScrollLeft => Action::ScrollLeft,
-            AccessibleAction::ScrollRight => Action::ScrollRight,
-        }
-    }
-}
-
-/// Builds an AccessKit Node from AccessibleInfo.
-pub fn build_node(info: &AccessibleInfo) -> Node {
-    let mut node = Node::new(info.role.into());
-
-    if let Some(ref name) = info.name {
-        node.set_label(name.clone());
-    }
-
-    if let Some(ref desc) = info.description {
-        node.set_description(desc.clone());
-    }
-
-    if let Some(ref value) = info.value {
-        node.set_value(value.clone());
-    }
-
-    // Add Focus action to indicate the node is focusable
-    if info.focusable {
-        node.add_action(Action::Focus);
-    }
-
-    if info.disabled {
-        node.set_disabled();
-    }
-
-    // Add available actions
-    for action in &info.actions {
-        node.add_action((*action).into());
-    }
-
-    node
-}
-
 /// Trait for widgets to provide accessibility information.
 pub trait Accessible {
     /// Get the accessibility info for this widget.
@@ -220,6 +108,11 @@ pub trait Accessible {
 pub struct AccessibilityManager {
     id_map: AccessibilityIdMap,
     root_id: NodeId,
+    /// Nodes as assistive technology last saw them.
+    published: HashMap<NodeId, Node>,
+    /// Latest text of each live region, in `LIVE_REGIONS` order; a region
+    /// joins the tree with its first announcement.
+    announced: [Option<String>; 2],
 }
 
 impl Default for AccessibilityManager {
@@ -233,6 +126,8 @@ impl AccessibilityManager {
         Self {
             id_map: AccessibilityIdMap::new(),
             root_id: NodeId(0), // Root is always 0
+            published: HashMap::new(),
+            announced: [None, None],
         }
     }
 
@@ -250,6 +145,172 @@ impl AccessibilityManager {
         }
     }
 
+    /// Build a full tree update from the widget tree.
+    ///
+    /// Node bounds are in physical pixels relative to the window, where the
+    /// widget shows after scrolling, so magnifiers follow focus into scrolled
+    /// content. Node ids are reassigned in traversal order, so an unchanged
+    /// widget tree keeps its ids across layouts. Hidden widgets are left out
+    /// along with their descendants.
+    pub fn build_tree(
+        &mut self,
+        app_name: &str,
+        root: &dyn Widget,
+        layout_tree: &LayoutTree,
+        focused: Option<WidgetId>,
+        scale_factor: f32,
+    ) -> TreeUpdate {
+        fn add_widget(
+            manager: &mut AccessibilityManager,
+            widget: &dyn Widget,
+            layout_tree: &LayoutTree,
+            scale_factor: f32,
+            scrolled: Vec2,
+            nodes: &mut Vec<(NodeId, Node)>,
+        ) -> Option<NodeId> {
+            let info = widget.accessibility_info();
+            if info.hidden {
+                return None;
+            }
+            let node_id = manager.get_node_id(widget.id());
+            let mut node = build_node(&info);
+            if widget.focusable() {
+                node.add_action(Action::Focus);
+            }
+            if let Some(layout) = layout_tree.get_absolute_layout(widget.id()) {
+                let b = layout.bounds;
+                let (x, y) = (b.x - scrolled.x, b.y - scrolled.y);
+                let s = scale_factor as f64;
+                node.set_bounds(accesskit::Rect {
+                    x0: x as f64 * s,
+                    y0: y as f64 * s,
+                    x1: (x + b.width) as f64 * s,
+                    y1: (y + b.height) as f64 * s,
+                });
+            }
+            let (offset_x, offset_y) = widget.scroll_offset().unwrap_or_default();
+            let scrolled = scrolled + Vec2::new(offset_x, offset_y);
+            let children = widget
+                .children()
+                .iter()
+                .filter_map(|child| {
+                    add_widget(
+                        manager,
+                        child.as_ref(),
+                        layout_tree,
+                        scale_factor,
+                        scrolled,
+                        nodes,
+                    )
+                })
+                .collect::<Vec<_>>();
+            node.set_children(children);
+            nodes.push((node_id, node));
+            Some(node_id)
+        }
+
+        self.clear();
+        let mut nodes = Vec::new();
+        let root_child = add_widget(
+            self,
+            root,
+            layout_tree,
+            scale_factor,
+            Vec2::ZERO,
+            &mut nodes,
+        );
+
+        let mut children: Vec<_> = root_child.into_iter().collect();
+        for ((id, politeness), text) in LIVE_REGIONS.iter().zip(&self.announced) {
+            let Some(text) = text else { continue };
+            let mut region = Node::new(Role::Label);
+            region.set_live((*politeness).into());
+            region.set_label(text.clone());
+            children.push(*id);
+            nodes.push((*id, region));
+        }
+
+        let mut window = Node::new(Role::Window);
+        window.set_label(app_name.to_string());
+        window.set_children(children);
+        nodes.push((self.root_id, window));
+
+        TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(self.root_id)),
+            tree_id: TreeId::ROOT,
+            focus: self.focus_node(focused),
+        }
+    }
+
+    /// Send the full tree, or only the nodes that changed since the last
+    /// update and the focus, to `sink`.
+    ///
+    /// Either way every node is built again; the changed ones are found by
+    /// diffing the whole tree against what was last sent, so the cost grows
+    /// with the tree, not with the change. Returns whether an update was
+    /// sent (nothing is sent while assistive technology is inactive).
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn publish_diff(
+        &mut self,
+        sink: &mut impl TreeUpdateSink,
+        full_tree: bool,
+        app_name: &str,
+        root: &dyn Widget,
+        layout_tree: &LayoutTree,
+        focused: Option<WidgetId>,
+        scale_factor: f32,
+    ) -> bool {
+        let mut sent = false;
+        sink.update_if_active(|| {
+            sent = true;
+            let mut update = self.build_tree(app_name, root, layout_tree, focused, scale_factor);
+            let previous = std::mem::take(&mut self.published);
+            self.published = update.nodes.iter().cloned().collect();
+            if !full_tree {
+                // Removed nodes drop out with their parent's new child list
+                update.tree = None;
+                update
+                    .nodes
+                    .retain(|(id, node)| previous.get(id) != Some(node));
+            }
+            update
+        });
+        sent
+    }
+
+    /// Speak `announcements` with the next update. Several of the same
+    /// politeness are read out together.
+    pub fn announce(&mut self, announcements: Vec<Announcement>) {
+        for ((_, politeness), announced) in LIVE_REGIONS.iter().zip(&mut self.announced) {
+            let texts: Vec<_> = announcements
+                .iter()
+                .filter(|announcement| announcement.politeness == *politeness)
+                .map(|announcement| announcement.text.as_str())
+                .collect();
+            if !texts.is_empty() {
+                *announced = Some(texts.join(" "));
+            }
+        }
+    }
+
+    /// Build an update that only moves focus.
+    pub fn focus_update(&self, focused: Option<WidgetId>) -> TreeUpdate {
+        TreeUpdate {
+            nodes: Vec::new(),
+            tree: None,
+            tree_id: TreeId::ROOT,
+            focus: self.focus_node(focused),
+        }
+    }
+
+    /// The node for the focused widget, or the window when nothing is focused.
+    pub fn focus_node(&self, focused: Option<WidgetId>) -> NodeId {
+        focused
+            .and_then(|id| self.id_map.get_node(id))
+            .unwrap_or(self.root_id)
+    }
+
     /// Get or create a NodeId for a widget.
     pub fn get_node_id(&mut self, widget_id: WidgetId) -> NodeId {
         self.id_map.get_or_create(widget_id)
@@ -274,6 +335,90 @@ impl AccessibilityManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clipboard::MemoryClipboard;
+    use spark_input::FocusManager;
+    use spark_widgets::{AnnouncementQueue, EventContext, EventPhase, Theme};
+
+    /// Lay out `root` and everything under it in a fresh tree.
+    fn tree_with_children(root: &mut dyn Widget, width: f32, height: f32) -> LayoutTree {
+        fn add(widget: &mut dyn Widget, tree: &mut LayoutTree) -> WidgetId {
+            let style = widget.style(&Theme::light());
+            let children: Vec<_> = widget
+                .children_mut()
+                .iter_mut()
+                .map(|child| add(child.as_mut(), tree))
+                .collect();
+            let id = if children.is_empty() {
+                tree.new_leaf(style)
+            } else {
+                tree.new_with_children(style, &children)
+            };
+            widget.set_id(id);
+            id
+        }
+        let mut tree = LayoutTree::new();
+        let root_id = add(root, &mut tree);
+        tree.set_root(root_id);
+        tree.compute_layout(width, height);
+        tree
+    }
+
+    /// Run `handle` with a context for the widget `id`, as the app would
+    /// when delivering it an action.
+    fn event_ctx<R>(
+        layout_tree: &LayoutTree,
+        id: WidgetId,
+        handle: impl FnOnce(&mut EventContext) -> R,
+    ) -> R {
+        let mut focus = FocusManager::new();
+        let mut clipboard = MemoryClipboard::default();
+        let mut ctx = EventContext {
+            layout: layout_tree.get_absolute_layout(id).unwrap(),
+            layout_tree,
+            focus: &mut focus,
+            widget_id: id,
+            has_capture: false,
+            target: id,
+            phase: EventPhase::Target,
+            theme: &Theme::light(),
+            clipboard: &mut clipboard,
+            announcements: &AnnouncementQueue::new(),
+        };
+        handle(&mut ctx)
+    }
+
+    /// Publish `root` the way the app does and return the update sent.
+    fn publish(
+        manager: &mut AccessibilityManager,
+        root: &dyn Widget,
+        layout_tree: &LayoutTree,
+        focused: Option<WidgetId>,
+        full_tree: bool,
+    ) -> TreeUpdate {
+        let mut adapter = FakeAdapter::default();
+        manager.publish_diff(
+            &mut adapter,
+            full_tree,
+            "Test",
+            root,
+            layout_tree,
+            focused,
+            1.0,
+        );
+        adapter.updates.pop().unwrap()
+    }
+
+    /// Records every update instead of talking to the platform.
+    #[derive(Default)]
+    struct FakeAdapter {
+        updates: Vec<TreeUpdate>,
+    }
+
+    impl TreeUpdateSink for FakeAdapter {
+        fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate) {
+            self.updates.push(updater());
+        }
+    }
 
     #[test]
     fn test_id_mapping() {
@@ -304,4 +449,282 @@ mod tests {
         assert_eq!(node.role(), Role::Button);
         assert_eq!(node.label(), Some("Click Me"));
     }
+
+    #[test]
+    fn test_builtin_widget_info() {
+        use spark_widgets::{Button, Text, TextInput};
+
+        let button = Button::new("Save").accessibility_info();
+        assert_eq!(button.role, AccessibleRole::Button);
+        assert_eq!(button.name.as_deref(), Some("Save"));
+        assert!(button.actions.contains(&AccessibleAction::Click));
+
+        let disabled = Button::new("Save").disabled(true).accessibility_info();
+        assert!(disabled.disabled);
+        assert!(disabled.actions.is_empty());
+
+        let label = Text::new("Hello").accessibility_info();
+        assert_eq!(label.role, AccessibleRole::Label);
+        assert_eq!(label.name.as_deref(), Some("Hello"));
+
+        let password = TextInput::new()
+            .value("hunter2")
+            .placeholder("Password")
+            .password(true)
+            .accessibility_info();
+        assert_eq!(password.role, AccessibleRole::TextField);
+        assert_eq!(password.value.as_deref(), Some("•••••••"));
+        assert_eq!(password.description.as_deref(), Some("Password"));
+    }
+
+    #[test]
+    fn test_names_descriptions_and_hidden_decoration() {
+        use spark_widgets::{Button, Container, Icon, IconName, Text};
+
+        let mut root: Box<dyn Widget> = Box::new(
+            Container::new()
+                .child(
+                    Container::new()
+                        .accessibility_hidden(true)
+                        .child(Text::new("swatch")),
+                )
+                .child(Icon::new(IconName::Check))
+                .child(
+                    Button::new("+")
+                        .accessibility_label("Increase")
+                        .accessibility_description("Adds one to the count"),
+                ),
+        );
+        let layout_tree = tree_with_children(root.as_mut(), 200.0, 100.0);
+
+        let mut manager = AccessibilityManager::new();
+        let update = publish(&mut manager, root.as_ref(), &layout_tree, None, true);
+        // Window + container + button; the swatch, its text and the
+        // unlabeled icon are left out
+        assert_eq!(update.nodes.len(), 3);
+        let (_, button) = update
+            .nodes
+            .iter()
+            .find(|(_, node)| node.role() == Role::Button)
+            .unwrap();
+        assert_eq!(button.label(), Some("Increase"));
+        assert_eq!(button.description(), Some("Adds one to the count"));
+        let (_, container) = update
+            .nodes
+            .iter()
+            .find(|(id, _)| manager.get_widget_id(*id) == Some(root.id()))
+            .unwrap();
+        assert_eq!(container.children().len(), 1);
+    }
+
+    #[test]
+    fn test_build_tree_covers_widgets_and_focus() {
+        use spark_widgets::{Container, TextInput};
+
+        let mut root: Box<dyn Widget> = Box::new(Container::new().child(TextInput::new()));
+        let layout_tree = tree_with_children(root.as_mut(), 200.0, 100.0);
+        let input_id = root.children()[0].id();
+
+        let mut manager = AccessibilityManager::new();
+        let update = manager.build_tree("Test", root.as_ref(), &layout_tree, None, 2.0);
+        // Window + container + text input
+        assert_eq!(update.nodes.len(), 3);
+        assert_eq!(update.focus, manager.root_id());
+
+        let focused = manager.focus_update(Some(input_id));
+        assert_eq!(manager.get_widget_id(focused.focus), Some(input_id));
+        assert!(focused.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_bounds_follow_scrolling() {
+        use spark_widgets::{Scroll, TextInput};
+
+        let mut root: Box<dyn Widget> =
+            Box::new(Scroll::new().size(100.0, 50.0).content(TextInput::new()));
+        let layout_tree = tree_with_children(root.as_mut(), 200.0, 100.0);
+        let (root_id, input_id) = (root.id(), root.children()[0].id());
+        root.set_scroll_offset(0.0, 30.0);
+
+        let mut manager = AccessibilityManager::new();
+        let update = manager.build_tree("Test", root.as_ref(), &layout_tree, Some(input_id), 2.0);
+        let mut bounds = |widget| {
+            let node = manager.get_node_id(widget);
+            let (_, node) = update.nodes.iter().find(|(id, _)| *id == node).unwrap();
+            node.bounds().unwrap()
+        };
+        // The scroll stays put while its content moves up, in physical pixels
+        assert_eq!(bounds(root_id).y0, 0.0);
+        assert_eq!(bounds(input_id).y0, -60.0);
+        assert_eq!(manager.get_widget_id(update.focus), Some(input_id));
+    }
+
+    #[test]
+    fn test_set_value_action_updates_text_input() {
+        use spark_widgets::TextInput;
+
+        let mut input = TextInput::new().value("old");
+        let layout_tree = tree_with_children(&mut input, 200.0, 100.0);
+
+        event_ctx(&layout_tree, input.id(), |ctx| {
+            let value =
+                AccessibleValue::from_accesskit(&accesskit::ActionData::Value("new".into()));
+            let response =
+                input.accessibility_action(ctx, AccessibleAction::SetValue, value.as_ref());
+            assert!(response.handled);
+            assert_eq!(input.get_value(), "new");
+
+            // Actions the widget does not support are left unhandled.
+            let response = input.accessibility_action(ctx, AccessibleAction::ScrollDown, None);
+            assert!(!response.handled);
+        });
+    }
+
+    #[test]
+    fn test_value_actions_reach_segments_and_chip_entry() {
+        use spark_widgets::{ChipInput, SegmentedControl};
+
+        /// Lay `widget` out alone and hand it `action`.
+        fn act(widget: &mut dyn Widget, action: AccessibleAction, value: Option<AccessibleValue>) {
+            let layout_tree = tree_with_children(widget, 300.0, 100.0);
+            let response = event_ctx(&layout_tree, widget.id(), |ctx| {
+                widget.accessibility_action(ctx, action, value.as_ref())
+            });
+            assert!(response.handled, "{action:?} was not handled");
+        }
+
+        let mut segments = SegmentedControl::new(["Day", "Week", "Month"]);
+        act(&mut segments, AccessibleAction::Increment, None);
+        assert_eq!(segments.selected_index(), 1);
+        let month = AccessibleValue::Text("Month".into());
+        act(&mut segments, AccessibleAction::SetValue, Some(month));
+        assert_eq!(segments.selected_index(), 2);
+        act(&mut segments, AccessibleAction::Decrement, None);
+        assert_eq!(segments.accessibility_info().value.as_deref(), Some("Week"));
+
+        let mut chips = ChipInput::new().chips(["rust"]);
+        let gpu = AccessibleValue::Text("gpu".into());
+        act(&mut chips, AccessibleAction::SetValue, Some(gpu));
+        assert_eq!(chips.accessibility_info().value.as_deref(), Some("gpu"));
+        assert_eq!(chips.get_chips(), ["rust"]);
+    }
+
+    #[test]
+    fn test_announcements_update_live_regions() {
+        use spark_widgets::Text;
+
+        let mut root: Box<dyn Widget> = Box::new(Text::new("Results"));
+        let layout_tree = tree_with_children(root.as_mut(), 200.0, 100.0);
+
+        let mut manager = AccessibilityManager::new();
+        let publish = |manager: &mut AccessibilityManager, full| {
+            publish(manager, root.as_ref(), &layout_tree, None, full)
+        };
+        let polite = |text: &str| Announcement {
+            text: text.into(),
+            politeness: Politeness::Polite,
+        };
+
+        // No regions until something is announced
+        assert_eq!(publish(&mut manager, true).nodes.len(), 2);
+
+        manager.announce(vec![polite("3 results"), polite("sorted by name")]);
+        let update = publish(&mut manager, false);
+        let (region_id, region) = update
+            .nodes
+            .iter()
+            .find(|(_, node)| node.live() == Some(accesskit::Live::Polite))
+            .unwrap();
+        assert_eq!(region.label(), Some("3 results sorted by name"));
+        assert_eq!(manager.get_widget_id(*region_id), None);
+        // Never takes focus from the widgets
+        assert_eq!(update.focus, manager.root_id());
+
+        // The same text again changes nothing
+        manager.announce(vec![polite("3 results sorted by name")]);
+        assert!(publish(&mut manager, false).nodes.is_empty());
+    }
+
+    #[test]
+    fn test_focus_tracks_tab_sequence() {
+        use spark_widgets::{Button, Container, Text, TextInput};
+
+        let mut root: Box<dyn Widget> = Box::new(
+            Container::new()
+                .child(TextInput::new())
+                .child(Text::new("not focusable"))
+                .child(Button::new("OK")),
+        );
+        let layout_tree = tree_with_children(root.as_mut(), 200.0, 100.0);
+        let child_ids: Vec<_> = root.children().iter().map(|child| child.id()).collect();
+
+        let mut focus = FocusManager::new();
+        crate::app::collect_focusable(root.as_ref(), &mut focus);
+        assert_eq!(focus.focusable_count(), 2);
+
+        let mut manager = AccessibilityManager::new();
+        let publish = |manager: &mut AccessibilityManager, focus: &FocusManager, full| {
+            publish(manager, root.as_ref(), &layout_tree, focus.focused(), full).focus
+        };
+
+        // Nothing focused: the window has accessibility focus.
+        assert_eq!(publish(&mut manager, &focus, true), manager.root_id());
+
+        // Tab, Tab, Tab (wraps), Shift+Tab
+        let expected = [child_ids[0], child_ids[2], child_ids[0], child_ids[2]];
+        for (step, expected) in expected.into_iter().enumerate() {
+            if step == 3 {
+                focus.focus_previous();
+            } else {
+                focus.focus_next();
+            }
+            let node = publish(&mut manager, &focus, false);
+            assert_eq!(manager.get_widget_id(node), Some(expected));
+            assert_eq!(focus.focused(), Some(expected));
+        }
+
+        // Assistive technology moving focus is mirrored the same way.
+        let node = manager.get_node_id(child_ids[0]);
+        focus.set_focus(manager.get_widget_id(node).unwrap());
+        assert_eq!(publish(&mut manager, &focus, false), node);
+    }
+
+    #[test]
+    fn test_updates_carry_only_changed_nodes() {
+        use spark_widgets::{Button, Container, TextInput};
+
+        let mut root: Box<dyn Widget> = Box::new(
+            Container::new()
+                .child(TextInput::new().value("old"))
+                .child(Button::new("OK")),
+        );
+        let layout_tree = tree_with_children(root.as_mut(), 200.0, 100.0);
+        let input_id = root.children()[0].id();
+
+        let mut manager = AccessibilityManager::new();
+        let full = publish(&mut manager, root.as_ref(), &layout_tree, None, true);
+        assert!(full.tree.is_some());
+        assert_eq!(full.nodes.len(), 4);
+
+        // Nothing changed, so only the focus goes out
+        let unchanged = publish(&mut manager, root.as_ref(), &layout_tree, None, false);
+        assert!(unchanged.tree.is_none());
+        assert!(unchanged.nodes.is_empty());
+
+        event_ctx(&layout_tree, input_id, |ctx| {
+            let value =
+                AccessibleValue::from_accesskit(&accesskit::ActionData::Value("new".into()));
+            root.children_mut()[0].accessibility_action(
+                ctx,
+                AccessibleAction::SetValue,
+                value.as_ref(),
+            );
+        });
+
+        let edited = publish(&mut manager, root.as_ref(), &layout_tree, None, false);
+        assert_eq!(edited.nodes.len(), 1);
+        let (node_id, node) = &edited.nodes[0];
+        assert_eq!(manager.get_widget_id(*node_id), Some(input_id));
+        assert_eq!(node.value(), Some("new"));
+    }
 }
@@ -0,0 +1,279 @@
+//! Platform accessibility adapters.
+//!
+//! `accesskit_winit` is built against winit 0.30, so the platform adapters are
+//! driven directly from the window's raw handle instead. Requests from
+//! assistive technology may arrive on any thread; they are queued and the
+//! event loop is woken to handle them.
+
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, TreeUpdate,
+};
+use super::TreeUpdateSink;
+use std::sync::{Arc, Mutex};
+use winit::event_loop::EventLoopProxy;
+use winit::window::Window;
+
+/// Requests from assistive technology waiting for the event loop.
+#[derive(Debug, Default)]
+pub(crate) struct PendingRequests {
+    /// The platform asked for the full tree (accessibility was activated).
+    pub initial_tree: bool,
+    /// Actions such as Click or Focus, in arrival order.
+    pub actions: Vec<ActionRequest>,
+}
+
+type SharedRequests = Arc<Mutex<PendingRequests>>;
+
+struct Activation {
+    pending: SharedRequests,
+    proxy: EventLoopProxy,
+}
+
+impl ActivationHandler for Activation {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        // The widget tree lives on the event loop thread; answer from there.
+        self.pending.lock().unwrap().initial_tree = true;
+        self.proxy.wake_up();
+        None
+    }
+}
+
+struct Actions {
+    pending: SharedRequests,
+    proxy: EventLoopProxy,
+}
+
+impl ActionHandler for Actions {
+    fn do_action(&mut self, request: ActionRequest) {
+        self.pending.lock().unwrap().actions.push(request);
+        self.proxy.wake_up();
+    }
+}
+
+struct Deactivation;
+
+impl DeactivationHandler for Deactivation {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+/// Connects a window to the platform accessibility API.
+pub(crate) struct Adapter {
+    inner: platform::Adapter,
+    pending: SharedRequests,
+}
+
+impl Adapter {
+    /// Create an adapter for a window that has not been shown yet.
+    pub(crate) fn new(window: &dyn Window, proxy: EventLoopProxy) -> Self {
+        let pending = SharedRequests::default();
+        let inner = platform::Adapter::new(
+            window,
+            Activation {
+                pending: pending.clone(),
+                proxy: proxy.clone(),
+            },
+            Actions {
+                pending: pending.clone(),
+                proxy,
+            },
+            Deactivation,
+        );
+        Self { inner, pending }
+    }
+
+    /// Tell the platform whether the window has keyboard focus.
+    pub(crate) fn set_window_focused(&mut self, focused: bool) {
+        self.inner.set_window_focused(focused);
+    }
+
+    /// Update the window's screen bounds (used by AT-SPI for hit testing).
+    pub(crate) fn set_window_bounds(&mut self, window: &dyn Window) {
+        self.inner.set_window_bounds(window);
+    }
+
+    /// Take all queued requests.
+    pub(crate) fn take_requests(&self) -> PendingRequests {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+}
+
+impl TreeUpdateSink for Adapter {
+    fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate) {
+        self.inner.update_if_active(updater);
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+    pub(super) struct Adapter {
+        adapter: Option<accesskit_macos::SubclassingAdapter>,
+    }
+
+    impl Adapter {
+        pub(super) fn new(
+            window: &dyn Window,
+            activation: impl 'static + ActivationHandler,
+            actions: impl 'static + ActionHandler,
+            _deactivation: impl 'static + DeactivationHandler,
+        ) -> Self {
+            let view = window.window_handle().ok().and_then(|handle| match handle.as_raw() {
+                RawWindowHandle::AppKit(handle) => Some(handle.ns_view.as_ptr()),
+                _ => None,
+            });
+            // SAFETY: the view belongs to a live window that outlives the adapter.
+            let adapter = view.map(|view| unsafe {
+                accesskit_macos::SubclassingAdapter::new(view, activation, actions)
+            });
+            Self { adapter }
+        }
+
+        pub(super) fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate) {
+            if let Some(events) = self.adapter.as_mut().and_then(|a| a.update_if_active(updater)) {
+                events.raise();
+            }
+        }
+
+        pub(super) fn set_window_focused(&mut self, focused: bool) {
+            if let Some(events) = self
+                .adapter
+                .as_mut()
+                .and_then(|a| a.update_view_focus_state(focused))
+            {
+                events.raise();
+            }
+        }
+
+        pub(super) fn set_window_bounds(&mut self, _window: &dyn Window) {}
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+    pub(super) struct Adapter {
+        adapter: Option<accesskit_windows::SubclassingAdapter>,
+    }
+
+    impl Adapter {
+        pub(super) fn new(
+            window: &dyn Window,
+            activation: impl 'static + ActivationHandler,
+            actions: impl 'static + ActionHandler + Send,
+            _deactivation: impl 'static + DeactivationHandler,
+        ) -> Self {
+            let hwnd = window.window_handle().ok().and_then(|handle| match handle.as_raw() {
+                RawWindowHandle::Win32(handle) => Some(handle.hwnd.get()),
+                _ => None,
+            });
+            let adapter = hwnd.map(|hwnd| {
+                accesskit_windows::SubclassingAdapter::new(
+                    accesskit_windows::HWND(hwnd as _),
+                    activation,
+                    actions,
+                )
+            });
+            Self { adapter }
+        }
+
+        pub(super) fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate) {
+            if let Some(events) = self.adapter.as_mut().and_then(|a| a.update_if_active(updater)) {
+                events.raise();
+            }
+        }
+
+        // The subclassing adapter tracks window focus itself.
+        pub(super) fn set_window_focused(&mut self, _focused: bool) {}
+
+        pub(super) fn set_window_bounds(&mut self, _window: &dyn Window) {}
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod platform {
+    use super::*;
+
+    pub(super) struct Adapter {
+        adapter: accesskit_unix::Adapter,
+    }
+
+    impl Adapter {
+        pub(super) fn new(
+            _window: &dyn Window,
+            activation: impl 'static + ActivationHandler + Send,
+            actions: impl 'static + ActionHandler + Send,
+            deactivation: impl 'static + DeactivationHandler + Send,
+        ) -> Self {
+            Self {
+                adapter: accesskit_unix::Adapter::new(activation, actions, deactivation),
+            }
+        }
+
+        pub(super) fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate) {
+            self.adapter.update_if_active(updater);
+        }
+
+        pub(super) fn set_window_focused(&mut self, focused: bool) {
+            self.adapter.update_window_focus_state(focused);
+        }
+
+        pub(super) fn set_window_bounds(&mut self, window: &dyn Window) {
+            let outer = window.outer_position().unwrap_or_default();
+            let outer_size = window.outer_size();
+            let inset = window.surface_position();
+            let inner_size = window.surface_size();
+            let outer_rect = accesskit::Rect::from_origin_size(
+                (outer.x as f64, outer.y as f64),
+                (outer_size.width as f64, outer_size.height as f64),
+            );
+            let inner_rect = accesskit::Rect::from_origin_size(
+                ((outer.x + inset.x) as f64, (outer.y + inset.y) as f64),
+                (inner_size.width as f64, inner_size.height as f64),
+            );
+            self.adapter.set_root_window_bounds(outer_rect, inner_rect);
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+mod platform {
+    use super::*;
+
+    /// No accessibility backend on this platform.
+    pub(super) struct Adapter;
+
+    impl Adapter {
+        pub(super) fn new(
+            _window: &dyn Window,
+            _activation: impl 'static + ActivationHandler,
+            _actions: impl 'static + ActionHandler,
+            _deactivation: impl 'static + DeactivationHandler,
+        ) -> Self {
+            Self
+        }
+
+        pub(super) fn update_if_active(&mut self, _updater: impl FnOnce() -> TreeUpdate) {}
+
+        pub(super) fn set_window_focused(&mut self, _focused: bool) {}
+
+        pub(super) fn set_window_bounds(&mut self, _window: &dyn Window) {}
+    }
+}
@@ -1,16 +1,33 @@
 //! Application runner and main event loop.
 
-use spark_core::{init_wgpu, Color, SurfaceState};
-use spark_input::{FocusManager, InputEvent, PointerButton};
-use spark_layout::LayoutTree;
-use spark_render::{DrawList, Renderer};
+use crate::accessibility::{adapter::Adapter as AccessibilityAdapter, AccessibilityManager};
+use crate::audit::{self, AuditIssue};
+use crate::clipboard::{ClipboardBackend, MemoryClipboard};
+use crate::dpi;
+use crate::menu::{Menu, MenuCommand, PendingCommands};
+use crate::reload::UiSnapshot;
+use crate::task::AppHandle;
+use crate::AppError;
+use spark_core::{init_wgpu, Color, Rect, SurfaceError, SurfaceState, WgpuOptions};
+use spark_input::{
+    Action, ActionMapper, ClickCounter, CustomAction, DroppedFile, FocusManager, InputEvent, Key,
+    KeyboardEvent, Modifiers, ModifiersExt, NamedKey, Pointer, PointerButton, PointerId,
+    ScrollDelta, Shortcut, ShortcutMap, StandardAction, TouchPan,
+};
+use spark_layout::{LayoutTree, WidgetId};
+use spark_render::{DrawList, RenderError, Renderer};
 use spark_text::TextSystem;
-use spark_widgets::{EventContext, PaintContext, Widget};
+use spark_widgets::{
+    AccessibleAction, AccessibleValue, AnnouncementQueue, ClipboardService, CursorIcon,
+    EventContext, EventPhase, EventResponse, LayoutContext, PaintContext, SignalRuntime, Theme,
+    ThemeMode, Widget,
+};
+use std::collections::HashMap;
 use wgpu::{Device, Queue};
 use winit::event::WindowEvent;
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
-use spark_native_apple::ViewManager;
+use spark_native_apple::{NativeAppearance, ViewManager, ViewPlacement};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
@@ -26,10 +43,69 @@ pub struct AppConfig {
     pub width: u32,
     /// Initial window height.
     pub height: u32,
-    /// Background color.
-    pub background: Color,
+    /// Background color; defaults to the active theme's background.
+    pub background: Option<Color>,
     /// Enable VSync.
     pub vsync: bool,
+    /// Force a specific scale factor instead of the window's (e.g. to test HiDPI layouts).
+    pub scale_factor_override: Option<f32>,
+    /// Initial UI zoom multiplier applied on top of the scale factor.
+    pub ui_zoom: f32,
+    /// Handle Ctrl/Cmd with `=`, `-` and `0` to zoom the UI in, out, or reset it.
+    pub zoom_shortcuts: bool,
+    /// Multiplier on how far mouse wheels and trackpads scroll.
+    pub wheel_sensitivity: f32,
+    /// Initial theme mode.
+    pub theme_mode: ThemeMode,
+    /// Follow the system light/dark appearance instead of `theme_mode`.
+    pub follow_system_theme: bool,
+    /// Theme used in light mode.
+    pub light_theme: Theme,
+    /// Theme used in dark mode.
+    pub dark_theme: Theme,
+    /// Warn about small focusable widgets and low-contrast text, and outline them.
+    pub audit_accessibility: bool,
+    /// Menu bar installed at startup (shown on macOS only for now).
+    pub menu: Option<Menu>,
+    /// App-wide shortcuts, checked before widgets see a key press.
+    pub shortcuts: ShortcutMap,
+    /// GPU adapter and device selection.
+    pub wgpu: WgpuOptions,
+    /// Files or directories whose changes trigger a UI rebuild in reloadable apps.
+    #[cfg(feature = "hot-reload")]
+    pub watch_paths: Vec<std::path::PathBuf>,
+    /// Id of the page element holding the canvas; `None` fills the whole page.
+    #[cfg(target_arch = "wasm32")]
+    pub canvas_parent: Option<String>,
+}
+
+impl AppConfig {
+    /// Force a specific scale factor instead of the window's.
+    pub fn scale_factor_override(mut self, scale_factor: f32) -> Self {
+        self.scale_factor_override = Some(scale_factor);
+        self
+    }
+
+    /// Audit touch target sizes and text contrast after layout (debug aid).
+    pub fn audit_accessibility(mut self, enabled: bool) -> Self {
+        self.audit_accessibility = enabled;
+        self
+    }
+
+    /// Draw into the element with id `id` instead of the whole page.
+    #[cfg(target_arch = "wasm32")]
+    pub fn canvas_parent(mut self, id: impl Into<String>) -> Self {
+        self.canvas_parent = Some(id.into());
+        self
+    }
+
+    /// The configured theme for a mode.
+    pub fn theme(&self, mode: ThemeMode) -> &Theme {
+        match mode {
+            ThemeMode::Light => &self.light_theme,
+            ThemeMode::Dark => &self.dark_theme,
+        }
+    }
 }
 
 impl Default for AppConfig {
@@ -38,15 +114,51 @@ impl Default for AppConfig {
             title: String::from("Spark App"),
             width: 800,
             height: 600,
-            background: Color::from_hex(0xF3F4F6),
+            background: None,
             vsync: true,
+            scale_factor_override: None,
+            ui_zoom: 1.0,
+            zoom_shortcuts: false,
+            wheel_sensitivity: 1.0,
+            theme_mode: ThemeMode::Light,
+            follow_system_theme: false,
+            light_theme: Theme::light(),
+            dark_theme: Theme::dark(),
+            audit_accessibility: false,
+            menu: None,
+            shortcuts: ShortcutMap::new(),
+            wgpu: WgpuOptions::default(),
+            #[cfg(feature = "hot-reload")]
+            watch_paths: Vec::new(),
+            #[cfg(target_arch = "wasm32")]
+            canvas_parent: None,
         }
     }
 }
 
+/// Smallest allowed UI zoom multiplier.
+const MIN_UI_ZOOM: f32 = 0.25;
+/// Largest allowed UI zoom multiplier.
+const MAX_UI_ZOOM: f32 = 5.0;
+/// Zoom change per Ctrl/Cmd `=` or `-` press.
+const UI_ZOOM_STEP: f32 = 0.1;
+/// Lost GPU devices in a row the app rebuilds before giving up.
+const MAX_DEVICE_RECOVERIES: u32 = 3;
+/// Frames presented after a recovery before earlier losses are forgotten.
+const STABLE_FRAMES_AFTER_RECOVERY: u32 = 300;
+
+/// Callback invoked when the application hits an error.
+type ErrorCallback = Box<dyn FnMut(&AppError)>;
+
+/// Callback invoked for custom actions from the menu bar and shortcuts.
+type ActionCallback = Box<dyn FnMut(&CustomAction)>;
+
 /// The main application struct.
 pub struct App {
     config: AppConfig,
+    on_error: Option<ErrorCallback>,
+    on_action: Option<ActionCallback>,
+    handle: AppHandle,
 }
 
 impl App {
@@ -54,6 +166,9 @@ impl App {
     pub fn new() -> Self {
         Self {
             config: AppConfig::default(),
+            on_error: None,
+            on_action: None,
+            handle: AppHandle::new(),
         }
     }
 
@@ -70,22 +185,198 @@ impl App {
         self
     }
 
-    /// Set the background color.
+    /// Set the background color, overriding the theme's.
     pub fn with_background(mut self, color: Color) -> Self {
-        self.config.background = color;
+        self.config.background = Some(color);
+        self
+    }
+
+    /// Set the initial theme mode.
+    pub fn with_theme_mode(mut self, mode: ThemeMode) -> Self {
+        self.config.theme_mode = mode;
+        self
+    }
+
+    /// Follow the system light/dark appearance.
+    pub fn with_system_theme(mut self, enabled: bool) -> Self {
+        self.config.follow_system_theme = enabled;
+        self
+    }
+
+    /// Replace the theme used in light mode.
+    ///
+    /// Built-in widgets without a style of their own take their colors,
+    /// spacing, radii and font sizes from the active theme as they are laid
+    /// out and painted.
+    pub fn with_light_theme(mut self, theme: Theme) -> Self {
+        self.config.light_theme = theme;
+        self
+    }
+
+    /// Replace the theme used in dark mode.
+    pub fn with_dark_theme(mut self, theme: Theme) -> Self {
+        self.config.dark_theme = theme;
+        self
+    }
+
+    /// Force a specific scale factor instead of the window's.
+    pub fn with_scale_factor_override(mut self, scale_factor: f32) -> Self {
+        self.config.scale_factor_override = Some(scale_factor);
+        self
+    }
+
+    /// Set the initial UI zoom multiplier.
+    pub fn with_ui_zoom(mut self, zoom: f32) -> Self {
+        self.config.ui_zoom = zoom.clamp(MIN_UI_ZOOM, MAX_UI_ZOOM);
+        self
+    }
+
+    /// Enable browser-style Ctrl/Cmd `=`, `-` and `0` zoom shortcuts.
+    pub fn with_zoom_shortcuts(mut self, enabled: bool) -> Self {
+        self.config.zoom_shortcuts = enabled;
+        self
+    }
+
+    /// Scroll `sensitivity` times as far per wheel notch or trackpad swipe.
+    pub fn with_wheel_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.config.wheel_sensitivity = sensitivity.max(0.0);
+        self
+    }
+
+    /// Choose the GPU backends, power preference and required device features.
+    pub fn with_wgpu_options(mut self, options: WgpuOptions) -> Self {
+        self.config.wgpu = options;
+        self
+    }
+
+    /// Warn about and outline small focusable widgets and low-contrast text.
+    pub fn with_accessibility_audit(mut self, enabled: bool) -> Self {
+        self.config.audit_accessibility = enabled;
+        self
+    }
+
+    /// Set the menu bar, e.g. `Menu::new().submenu("File", |m| m.item("Open…", "o", open))`.
+    ///
+    /// On macOS it becomes the application's main menu, after an app menu
+    /// with Services, Hide and Quit.
+    pub fn with_menu(mut self, menu: Menu) -> Self {
+        self.config.menu = Some(menu);
+        self
+    }
+
+    /// Rebuild the UI when files under `path` change (requires [`App::run_reloadable`]).
+    #[cfg(feature = "hot-reload")]
+    pub fn watch_path(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.config.watch_paths.push(path.into());
+        self
+    }
+
+    /// Embed the app in the page element with id `id`.
+    ///
+    /// The app draws into a canvas filling the element and follows its
+    /// size, leaving the rest of the page alone. If the element is itself a
+    /// `<canvas>`, the app draws into it directly. Only one app can run per
+    /// page; starting another fails with [`MountError::MultipleApps`](crate::web::MountError::MultipleApps).
+    #[cfg(target_arch = "wasm32")]
+    pub fn with_canvas_parent(mut self, id: impl Into<String>) -> Self {
+        self.config.canvas_parent = Some(id.into());
+        self
+    }
+
+    /// Set a callback invoked for every error, including recoverable frame errors.
+    pub fn on_error(mut self, callback: impl FnMut(&AppError) + 'static) -> Self {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+
+    /// Bind a shortcut to an action for the whole app, e.g.
+    /// `bind_shortcut(Shortcut::new("Mod+K"), "command_palette")`.
+    ///
+    /// The shortcut wins over widgets and the default bindings. Custom actions
+    /// go to [`App::on_action`]; standard ones go to the focused widget.
+    pub fn bind_shortcut(mut self, shortcut: Shortcut, action: impl Into<Action>) -> Self {
+        self.config.shortcuts.bind(shortcut, action);
+        self
+    }
+
+    /// Make a shortcut do nothing, e.g. to turn off a default such as `Mod+A`.
+    pub fn disable_shortcut(mut self, shortcut: Shortcut) -> Self {
+        self.config.shortcuts.disable(shortcut);
         self
     }
 
+    /// Set a callback invoked when a menu item made with
+    /// [`MenuItem::action`](crate::MenuItem::action) is chosen or a shortcut
+    /// bound to a custom action is pressed.
+    pub fn on_action(mut self, callback: impl FnMut(&CustomAction) + 'static) -> Self {
+        self.on_action = Some(Box::new(callback));
+        self
+    }
+
+    /// Get a handle for spawning background work and posting its results
+    /// back to the UI thread once the app runs.
+    pub fn handle(&self) -> AppHandle {
+        self.handle.clone()
+    }
+
     /// Run the application with the given root widget.
-    pub fn run<F>(self, build_ui: F) -> !
+    ///
+    /// Returns once the window is closed or the app asks to exit (see
+    /// [`EventResponse::exit`] and [`AppHandle::exit`]), after the UI and GPU
+    /// resources are dropped, or with the first fatal error.
+    pub fn run<F>(self, build_ui: F) -> Result<(), AppError>
     where
         F: FnOnce() -> Box<dyn Widget> + 'static,
     {
-        let event_loop = winit::event_loop::EventLoop::new().unwrap();
-        let runner = AppRunner::new(self.config, build_ui);
-        let runner_leaked: &'static mut AppRunner<F> = Box::leak(Box::new(runner));
-        event_loop.run_app(runner_leaked).unwrap();
-        std::process::exit(0);
+        let mut build_ui = Some(build_ui);
+        self.run_runner(move || build_ui.take().map(|build| build()), false)
+    }
+
+    /// Run the application in development mode, rebuilding the UI on demand.
+    ///
+    /// Press Ctrl+R (Cmd+R on macOS) to tear down the widget tree and call
+    /// `build_ui` again. The window and renderer are kept, and scroll offsets
+    /// and focus carry over to widgets at the same position in the new tree.
+    /// With the `hot-reload` feature, changes under [`App::watch_path`] paths
+    /// also trigger a rebuild. Returns like [`App::run`].
+    pub fn run_reloadable<F>(self, mut build_ui: F) -> Result<(), AppError>
+    where
+        F: FnMut() -> Box<dyn Widget> + 'static,
+    {
+        self.run_runner(move || Some(build_ui()), true)
+    }
+
+    fn run_runner<F>(self, build_ui: F, reloadable: bool) -> Result<(), AppError>
+    where
+        F: FnMut() -> Option<Box<dyn Widget>> + 'static,
+    {
+        #[cfg(target_arch = "wasm32")]
+        crate::web::claim_page()?;
+        // An exit requested before this run (or left over from an earlier
+        // one) must not end it
+        self.handle.take_exit();
+        let event_loop = winit::event_loop::EventLoop::new()?;
+        self.handle.set_proxy(event_loop.create_proxy());
+        let mut runner = AppRunner::new(
+            self.config,
+            self.on_error,
+            self.on_action,
+            self.handle,
+            build_ui,
+            reloadable,
+        );
+        // The browser keeps calling into the runner after this returns
+        #[cfg(target_arch = "wasm32")]
+        let runner: &'static mut AppRunner<F> = Box::leak(Box::new(runner));
+        #[cfg(not(target_arch = "wasm32"))]
+        let runner = &mut runner;
+        event_loop.run_app(&mut *runner)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        runner.shutdown();
+        match runner.fatal_error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
     }
 }
 
@@ -95,11 +386,220 @@ impl Default for App {
     }
 }
 
+/// Counts lost GPU devices, forgetting them once frames present again for a
+/// while, so only losses in quick succession make the app give up.
+#[derive(Debug, Default)]
+struct DeviceRecoveries {
+    lost: u32,
+    stable_frames: u32,
+}
+
+impl DeviceRecoveries {
+    /// Note a lost device; false once too many were lost in a row.
+    fn record_loss(&mut self) -> bool {
+        self.lost += 1;
+        self.stable_frames = 0;
+        self.lost <= MAX_DEVICE_RECOVERIES
+    }
+
+    /// Note a presented frame.
+    fn frame_presented(&mut self) {
+        if self.lost == 0 {
+            return;
+        }
+        self.stable_frames += 1;
+        if self.stable_frames >= STABLE_FRAMES_AFTER_RECOVERY {
+            *self = Self::default();
+        }
+    }
+}
+
 /// Internal application runner that handles the event loop.
-struct AppRunner<F: FnOnce() -> Box<dyn Widget>> {
+struct AppRunner<F: FnMut() -> Option<Box<dyn Widget>>> {
     config: AppConfig,
-    build_ui: Option<F>,
+    on_error: Option<ErrorCallback>,
+    on_action: Option<ActionCallback>,
+    /// Closures posted from background work.
+    handle: AppHandle,
+    fatal_error: Option<AppError>,
+    /// Set by an event response asking to close the app.
+    exit_requested: bool,
+    /// Builds the root widget; returns `None` once a one-shot builder is used up.
+    build_ui: F,
+    /// Whether Ctrl/Cmd+R (and file changes) rebuild the UI.
+    reloadable: bool,
+    /// Set by the file watcher when a rebuild is pending.
+    #[cfg(feature = "hot-reload")]
+    reload_pending: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    #[cfg(feature = "hot-reload")]
+    watcher: Option<crate::reload::FileWatcher>,
+    /// Commands queued by the menu bar.
+    menu_commands: PendingCommands,
+    /// The installed menu bar.
+    #[cfg(target_os = "macos")]
+    menu_bar: Option<crate::menu::MenuBar>,
+    /// Reports canvas resizes and pixel ratio changes on the web.
+    #[cfg(target_arch = "wasm32")]
+    canvas_watcher: Option<crate::web::CanvasWatcher>,
+    /// Pointer and touch input from the canvas on the web.
+    #[cfg(target_arch = "wasm32")]
+    pointer_input: Option<crate::web::PointerInput>,
+    /// Hidden field for keyboard and IME input to text widgets on the web.
+    #[cfg(target_arch = "wasm32")]
+    text_entry: Option<crate::web::TextEntry>,
+    /// Watches the URL hash for `spark::web` routing.
+    #[cfg(target_arch = "wasm32")]
+    route_watcher: Option<crate::web::RouteWatcher>,
+    /// Set by the device-lost callback with the driver's message.
+    device_lost: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+    /// Lost devices replaced recently.
+    device_recoveries: DeviceRecoveries,
     state: Option<AppState>,
+    /// The window, lent to `state` as a `'static` borrow and freed by
+    /// [`shutdown`](Self::shutdown).
+    window: Option<*mut dyn winit::window::Window>,
+}
+
+impl AppState {
+    /// Ask for a redraw.
+    ///
+    /// On the web this waits for an animation frame, and for the page to be
+    /// visible again if it is hidden.
+    fn request_redraw(&self) {
+        #[cfg(target_arch = "wasm32")]
+        if let Some(scheduler) = &self.frame_scheduler {
+            scheduler.request();
+            return;
+        }
+        self.window.request_redraw();
+    }
+
+    /// Seconds since start for animations, not counting time the page was hidden.
+    fn elapsed_time(&self) -> f32 {
+        let elapsed = self.start_time.elapsed();
+        #[cfg(target_arch = "wasm32")]
+        let elapsed = match &self.frame_scheduler {
+            Some(scheduler) => elapsed.saturating_sub(scheduler.hidden_time()),
+            None => elapsed,
+        };
+        elapsed.as_secs_f32()
+    }
+
+    /// Check if something under `pos` can scroll, so a finger there pans.
+    fn scrolls_at(&self, pos: glam::Vec2) -> bool {
+        let root = self.root_widget.as_ref();
+        let scope = self
+            .modal
+            .and_then(|id| find_widget(root, id))
+            .unwrap_or(root);
+        hit_path(scope, &self.layout_tree, pos)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| find_widget(root, id))
+            .any(can_scroll)
+    }
+
+    /// Fill in a pointer button event's time, and a press's click count.
+    fn stamp_pointer_event(&mut self, mut event: InputEvent) -> InputEvent {
+        let now = self.start_time.elapsed();
+        match &mut event {
+            InputEvent::PointerDown {
+                pos,
+                button,
+                click_count,
+                time,
+                ..
+            } => {
+                *click_count = self.clicks.press(*pos, *button, now);
+                *time = now;
+            }
+            InputEvent::PointerUp { time, .. } => *time = now,
+            _ => {}
+        }
+        event
+    }
+
+    /// Recompute the effective scale factor and schedule a relayout if it changed.
+    fn update_scale_factor(&mut self, config: &AppConfig) {
+        let base = config
+            .scale_factor_override
+            .unwrap_or(self.window_scale_factor);
+        let effective = base * self.ui_zoom;
+        if effective != self.scale_factor {
+            self.scale_factor = effective;
+            self.needs_layout = true;
+            self.request_redraw();
+        }
+    }
+
+    /// Set the UI zoom multiplier.
+    fn set_ui_zoom(&mut self, config: &AppConfig, zoom: f32) {
+        self.ui_zoom = zoom.clamp(MIN_UI_ZOOM, MAX_UI_ZOOM);
+        self.update_scale_factor(config);
+    }
+
+    /// Factor converting layout units to native view points.
+    ///
+    /// Native views are positioned in window points, which only match layout
+    /// units when neither zoom nor a scale override is active.
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    fn native_point_scale(&self) -> f32 {
+        dpi::native_point_scale(self.scale_factor, self.window_scale_factor)
+    }
+
+    /// Move native views to their layout, offset by the GPU scroll containers around them.
+    ///
+    /// Scrolling moves GPU content without a relayout, so this runs on every
+    /// repaint as well as after layout.
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    fn update_native_frames(&mut self) {
+        let point_scale = self.native_point_scale();
+        let (_, logical_height) = dpi::logical_size(self.surface_state.size, self.scale_factor);
+        let Some(manager) = self.native_view_manager.as_mut() else {
+            return;
+        };
+
+        // Native frames are in window points
+        let mut layouts = HashMap::new();
+        self.layout_tree.traverse(|widget_id, computed, _depth| {
+            if manager.uses_layout(widget_id) {
+                layouts.insert(widget_id, scale_layout(computed, point_scale));
+            }
+        });
+        let placements: HashMap<_, _> =
+            crate::compositing::native_placements(self.root_widget.as_ref(), &self.layout_tree)
+                .into_iter()
+                .map(|(widget_id, placement)| {
+                    let (x, y) = placement.offset;
+                    let placement = ViewPlacement {
+                        offset: (x * point_scale, y * point_scale),
+                        visible: placement.visible,
+                    };
+                    (widget_id, placement)
+                })
+                .collect();
+        manager.update_layouts(
+            &layouts,
+            &placements,
+            logical_height * point_scale,
+            self.window_scale_factor,
+        );
+    }
+
+    /// Switch to the configured theme for `mode`, then lay out and repaint,
+    /// since sizes come from the theme too.
+    fn set_theme_mode(&mut self, config: &AppConfig, mode: ThemeMode) {
+        if self.theme.mode != mode {
+            self.theme = config.theme(mode).clone();
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            if let Some(manager) = self.native_view_manager.as_mut() {
+                manager.set_appearance(NativeAppearance::from_theme(&self.theme));
+            }
+            self.needs_layout = true;
+            self.needs_repaint = true;
+            self.request_redraw();
+        }
+    }
 }
 
 struct AppState {
@@ -114,23 +614,401 @@ struct AppState {
     focus_manager: FocusManager,
     root_widget: Box<dyn Widget>,
     start_time: Instant,
+    /// Drives redraws from animation frames on the web.
+    #[cfg(target_arch = "wasm32")]
+    frame_scheduler: Option<crate::web::FrameScheduler>,
     mouse_pos: glam::Vec2,
+    /// Effective scale factor: window (or override) scale times UI zoom.
     scale_factor: f32,
+    /// Scale factor reported by the window.
+    window_scale_factor: f32,
+    /// UI zoom multiplier.
+    ui_zoom: f32,
+    /// Current keyboard modifiers.
+    modifiers: winit::keyboard::ModifiersState,
+    /// Whether IME input is currently enabled on the window.
+    ime_allowed: bool,
+    /// Last caret rect sent to the IME, in logical pixels.
+    ime_cursor_area: Option<spark_core::Rect>,
+    /// Pointer shape last set on the window.
+    cursor: CursorIcon,
+    /// Widgets that captured a pointer, e.g. during a drag, by pointer. Each
+    /// gets every event from its pointer until it releases the pointer or
+    /// the pointer's button is released.
+    pointer_capture: HashMap<PointerId, WidgetId>,
+    /// Ids from the root (or open modal) down to the topmost widget under
+    /// the pointer.
+    hovered: Vec<WidgetId>,
+    /// Recent presses, for double and triple clicks.
+    clicks: ClickCounter,
+    /// The first finger on the screen, when it touched down on something
+    /// that scrolls.
+    touch_pan: Option<(PointerId, TouchPan)>,
+    /// Pointers with a button down, whose presses are cancelled if their
+    /// release can't arrive.
+    pressed: Vec<Pointer>,
+    /// Active theme.
+    theme: Theme,
+    /// Widget-to-node mapping for the accessibility tree.
+    accessibility: AccessibilityManager,
+    /// Platform accessibility bridge.
+    accessibility_adapter: AccessibilityAdapter,
+    /// Clipboard for copy and paste requests from widgets.
+    clipboard: Box<dyn ClipboardBackend>,
+    /// Widgets watching signals, and those to repaint.
+    signals: SignalRuntime,
+    /// Shortcuts widgets take without focus, e.g. menu mnemonics.
+    widget_shortcuts: ShortcutMap<WidgetId>,
+    /// Announcements from widgets, published with the next accessibility
+    /// update.
+    announcements: AnnouncementQueue,
+    /// The next accessibility update must carry the full tree.
+    accessibility_needs_tree: bool,
+    /// Issues found by the accessibility audit, outlined each frame.
+    audit_issues: Vec<AuditIssue>,
+    /// Text contrast should be audited on the next paint.
+    audit_contrast_pending: bool,
+    /// The open modal; only its subtree receives input.
+    modal: Option<WidgetId>,
+    /// Focus to restore when the modal closes.
+    focus_before_modal: Option<WidgetId>,
     needs_layout: bool,
     needs_repaint: bool,
     #[cfg(any(target_os = "macos", target_os = "ios"))]
     native_view_manager: Option<ViewManager>,
 }
 
-impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
-    fn new(config: AppConfig, build_ui: F) -> Self {
+impl<F: FnMut() -> Option<Box<dyn Widget>>> AppRunner<F> {
+    fn new(
+        config: AppConfig,
+        on_error: Option<ErrorCallback>,
+        on_action: Option<ActionCallback>,
+        handle: AppHandle,
+        build_ui: F,
+        reloadable: bool,
+    ) -> Self {
         Self {
             config,
-            build_ui: Some(build_ui),
+            on_error,
+            on_action,
+            handle,
+            fatal_error: None,
+            exit_requested: false,
+            build_ui,
+            reloadable,
+            #[cfg(feature = "hot-reload")]
+            reload_pending: Default::default(),
+            #[cfg(feature = "hot-reload")]
+            watcher: None,
+            menu_commands: PendingCommands::default(),
+            #[cfg(target_os = "macos")]
+            menu_bar: None,
+            #[cfg(target_arch = "wasm32")]
+            canvas_watcher: None,
+            #[cfg(target_arch = "wasm32")]
+            pointer_input: None,
+            #[cfg(target_arch = "wasm32")]
+            text_entry: None,
+            #[cfg(target_arch = "wasm32")]
+            route_watcher: None,
+            device_lost: Default::default(),
+            device_recoveries: DeviceRecoveries::default(),
             state: None,
+            window: None,
+        }
+    }
+
+    /// Have `device` report its loss through a wake-up.
+    fn watch_device_lost(
+        &self,
+        device: &Device,
+        event_loop: &dyn winit::event_loop::ActiveEventLoop,
+    ) {
+        let lost = self.device_lost.clone();
+        let proxy = event_loop.create_proxy();
+        device.set_device_lost_callback(move |reason, message| {
+            // Dropping a replaced device reports it as destroyed
+            if matches!(reason, wgpu::DeviceLostReason::Destroyed) {
+                return;
+            }
+            *lost.lock().unwrap() = Some(message);
+            proxy.wake_up();
+        });
+    }
+
+    /// Replace a lost GPU device, keeping the widget tree, layout and focus.
+    fn recover_device(&mut self, event_loop: &dyn winit::event_loop::ActiveEventLoop) {
+        let Some(message) = self.device_lost.lock().unwrap().take() else {
+            return;
+        };
+        if !self.device_recoveries.record_loss() {
+            return self.fail(event_loop, AppError::DeviceLost(message));
+        }
+        let Some(mut state) = self.state.take() else {
+            return;
+        };
+        log::warn!("GPU device lost ({message}); reinitializing");
+
+        // The old surface has to go before the window gets a new one
+        drop(state.surface_state);
+        let (device, queue, surface_state) =
+            match pollster::block_on(init_wgpu(state.window, &self.config.wgpu)) {
+                Ok(gpu) => gpu,
+                Err(err) => return self.fail(event_loop, err.into()),
+            };
+        let renderer = match Renderer::new(&device, surface_state.config.format) {
+            Ok(renderer) => renderer,
+            Err(err) => return self.fail(event_loop, err.into()),
+        };
+        self.watch_device_lost(&device, event_loop);
+        // The new renderer starts with no image textures; images upload
+        // again from their handles' pixels as they are drawn
+        state.text_system.recreate_atlas(&device);
+        state.device = device;
+        state.queue = queue;
+        state.surface_state = surface_state;
+        state.renderer = renderer;
+        state.needs_repaint = true;
+        state.request_redraw();
+        self.state = Some(state);
+    }
+
+    /// Match the surface and scale factor to the canvas after the browser resized or zoomed it.
+    #[cfg(target_arch = "wasm32")]
+    fn apply_canvas_change(&mut self) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let Some(metrics) = self.canvas_watcher.as_ref().and_then(|w| w.take_change()) else {
+            return;
+        };
+        if metrics.width > 0 && metrics.height > 0 {
+            state
+                .surface_state
+                .resize(&state.device, metrics.width, metrics.height);
+        }
+        let scale_factor = metrics.scale_factor as f32;
+        if state.window_scale_factor != scale_factor {
+            state.window_scale_factor = scale_factor;
+            state.update_scale_factor(&self.config);
+            // Glyphs cached at the old pixel size won't be reused.
+            state.text_system.clear_glyph_cache();
+        }
+        state.needs_layout = true;
+        state.request_redraw();
+    }
+
+    /// Deliver pointer, touch and text input read from the page.
+    #[cfg(target_arch = "wasm32")]
+    fn handle_web_input(&mut self) {
+        if self.state.is_none() {
+            return;
+        }
+        let pointer_events = self.pointer_input.as_ref().map(|input| input.take_events());
+        let text_events = self.text_entry.as_ref().map(|entry| entry.take_events());
+        let events = pointer_events.into_iter().chain(text_events).flatten();
+        for event in events {
+            if let (InputEvent::PointerMove { pos, .. }, Some(state)) =
+                (&event, self.state.as_mut())
+            {
+                state.mouse_pos = *pos;
+            }
+            if let InputEvent::KeyDown { event: key } = &event {
+                if self.handle_shortcut(key) {
+                    continue;
+                }
+            }
+            let key_down = matches!(event, InputEvent::KeyDown { .. }).then(|| event.clone());
+            let response = self.handle_event(event);
+            if let Some(key_down) = key_down.filter(|_| !response.handled) {
+                self.traverse_focus(&key_down);
+            }
+        }
+    }
+
+    /// Run route callbacks and rebuild the UI after the page's route changed.
+    #[cfg(target_arch = "wasm32")]
+    fn apply_route_change(&mut self) {
+        let Some(route) = self.route_watcher.as_ref().and_then(|w| w.take_change()) else {
+            return;
+        };
+        if let Some(watcher) = &self.route_watcher {
+            watcher.notify(&route);
+        }
+        // Only reloadable apps can build their UI again
+        if self.reloadable {
+            self.reload();
+        }
+    }
+
+    /// Tell the canvas pointer input the current scale and scrollable areas.
+    #[cfg(target_arch = "wasm32")]
+    fn update_pointer_input(&self) {
+        let (Some(input), Some(state)) = (self.pointer_input.as_ref(), self.state.as_ref()) else {
+            return;
+        };
+        // A CSS pixel is `window_scale_factor` physical pixels
+        input.set_scale(state.window_scale_factor / state.scale_factor);
+        let (mut scroll, mut text) = (Vec::new(), Vec::new());
+        touch_regions(state.root_widget.as_ref(), &state.layout_tree, &mut scroll, &mut text);
+        input.set_scroll_regions(scroll);
+        input.set_text_regions(text);
+    }
+
+    /// Rebuild the widget tree, keeping scroll offsets and focus by position.
+    ///
+    /// Native views of the old tree are removed from the window by the
+    /// layout pass that embeds the new ones.
+    fn reload(&mut self) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let Some(mut root_widget) = (self.build_ui)() else {
+            return;
+        };
+
+        let snapshot = UiSnapshot::capture(
+            state.root_widget.as_ref(),
+            state.focus_manager.focused(),
+        );
+        snapshot.restore_scroll(root_widget.as_mut());
+        state.root_widget = root_widget;
+        state.pointer_capture.clear();
+        state.touch_pan = None;
+        state.pressed.clear();
+        state.hovered.clear();
+        state.focus_manager.clear_focus();
+        state.focus_manager.clear_focusable();
+
+        self.build_layout();
+
+        let state = self.state.as_mut().unwrap();
+        if let Some(id) = snapshot.focused_id(state.root_widget.as_ref()) {
+            state.focus_manager.set_focus(id);
+        }
+        state.needs_repaint = true;
+        state.request_redraw();
+        log::info!("UI reloaded");
+    }
+
+    /// Run commands queued by the menu bar.
+    fn handle_menu_commands(&mut self, event_loop: &dyn winit::event_loop::ActiveEventLoop) {
+        let commands = std::mem::take(&mut *self.menu_commands.lock().unwrap());
+        for command in commands {
+            match command {
+                MenuCommand::Callback(callback) => callback(),
+                MenuCommand::Standard(action) => self.perform_standard_action(action),
+                MenuCommand::Action(action) => self.perform_custom_action(&action),
+                MenuCommand::Quit => self.close(event_loop),
+            }
+        }
+    }
+
+    /// Run closures posted through a `UiSender`, then repaint.
+    fn run_posted_callbacks(&mut self) {
+        let callbacks = self.handle.take_posted();
+        if callbacks.is_empty() {
+            return;
+        }
+        for callback in callbacks {
+            callback();
+        }
+        if let Some(state) = self.state.as_mut() {
+            state.needs_layout = true;
+            state.needs_repaint = true;
+            state.request_redraw();
+        }
+    }
+
+    /// Hand a custom action to the app's action handler.
+    fn perform_custom_action(&mut self, action: &CustomAction) {
+        match self.on_action.as_mut() {
+            Some(callback) => callback(action),
+            None => log::debug!("no action handler for {:?}", action.0),
+        }
+    }
+
+    /// Send a standard action to the focused widget as its default shortcut.
+    fn perform_standard_action(&mut self, action: StandardAction) {
+        if self.state.is_none() {
+            return;
+        }
+        match action {
+            StandardAction::FocusNext => return self.move_focus(true),
+            StandardAction::FocusPrevious => return self.move_focus(false),
+            _ => {}
+        }
+        let Some(shortcut) = ActionMapper::new().shortcut_for(action).cloned() else {
+            log::debug!("no default shortcut performs {action:?}");
+            return;
+        };
+        self.handle_event(InputEvent::KeyDown {
+            event: KeyboardEvent {
+                key: shortcut.key,
+                modifiers: shortcut.modifiers,
+                ..Default::default()
+            },
+        });
+    }
+
+    /// Remove native views and exit the event loop.
+    fn close(&mut self, event_loop: &dyn winit::event_loop::ActiveEventLoop) {
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        if let Some(manager) = self.state.as_mut().and_then(|s| s.native_view_manager.as_mut()) {
+            manager.clear();
+        }
+        event_loop.exit();
+    }
+
+    /// Tear everything down once the event loop has stopped: native views,
+    /// then the surface before the device it belongs to, then the window.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    fn shutdown(&mut self) {
+        #[cfg(target_os = "macos")]
+        {
+            self.menu_bar = None;
+        }
+        if let Some(mut state) = self.state.take() {
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            if let Some(manager) = state.native_view_manager.as_mut() {
+                manager.clear();
+            }
+            drop(state.surface_state);
+            drop(state.renderer);
+            drop(state.queue);
+            drop(state.device);
+        }
+        if let Some(window) = self.window.take() {
+            // SAFETY: the pointer comes from `Box::into_raw`, and the state
+            // that borrowed the window was dropped above.
+            drop(unsafe { Box::from_raw(window) });
+        }
+    }
+
+    /// Report an error to the callback and the log.
+    fn report_error(&mut self, err: &AppError) {
+        log::error!("{err}");
+        if let Some(callback) = self.on_error.as_mut() {
+            callback(err);
         }
     }
 
+    /// Report a fatal error and stop the event loop.
+    ///
+    /// Native apps get the error back from [`App::run`], which is where it
+    /// gets printed; on the web `run` never returns, so it is logged here.
+    fn fail(&mut self, event_loop: &dyn winit::event_loop::ActiveEventLoop, err: AppError) {
+        #[cfg(target_arch = "wasm32")]
+        log::error!("{err}");
+        if let Some(callback) = self.on_error.as_mut() {
+            callback(&err);
+        }
+        if self.fatal_error.is_none() {
+            self.fatal_error = Some(err);
+        }
+        event_loop.exit();
+    }
+
     fn build_layout(&mut self) {
         let state = self.state.as_mut().unwrap();
 
@@ -140,16 +1018,19 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
         // Initialize native view manager if needed
         #[cfg(any(target_os = "macos", target_os = "ios"))]
         if state.native_view_manager.is_none() {
-            state.native_view_manager = Some(ViewManager::new());
+            let mut manager = ViewManager::new();
+            manager.set_appearance(NativeAppearance::from_theme(&state.theme));
+            state.native_view_manager = Some(manager);
         }
 
         // Build layout tree from widget tree
         fn add_to_layout(
             widget: &mut dyn Widget,
             tree: &mut LayoutTree,
+            ctx: &mut LayoutContext,
             in_scroll: bool,
         ) -> spark_layout::WidgetId {
-            let mut style = widget.style();
+            let mut style = widget.style(ctx.theme);
             if in_scroll {
                 style.flex_shrink = 0.0;
             }
@@ -160,12 +1041,17 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
                 .map(|child| add_to_layout(
                     child.as_mut(),
                     tree,
+                    ctx,
                     in_scroll || is_scroll,
                 ))
                 .collect();
 
             let id = if children_ids.is_empty() {
-                tree.new_leaf(style)
+                match widget.measure(ctx) {
+                    Some(size) if widget.wraps() => tree.new_wrapping_leaf(style, size),
+                    Some(size) => tree.new_measured_leaf(style, size),
+                    None => tree.new_leaf(style),
+                }
             } else {
                 tree.new_with_children(style, &children_ids)
             };
@@ -174,16 +1060,35 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
             id
         }
         
-        // Register native widgets after layout tree is built
+        // Collect native widgets after layout tree is built
         // NOTE: This is a limitation - we can't easily detect native widgets from Box<dyn Widget>
-        // The registration happens by traversing the widget tree and checking type IDs
+        // The collection happens by traversing the widget tree and checking is_native()
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        #[derive(Default)]
+        struct NativeWidgets {
+            views: Vec<(spark_layout::WidgetId, spark_native_apple::NativeViewHandle)>,
+            /// Widgets inside a native container, with that container.
+            parents: Vec<(spark_layout::WidgetId, spark_layout::WidgetId)>,
+            /// Containers with their document view and content widget.
+            documents: Vec<(
+                spark_layout::WidgetId,
+                spark_native_apple::NativeViewHandle,
+                spark_layout::WidgetId,
+            )>,
+        }
+
         #[cfg(any(target_os = "macos", target_os = "ios"))]
-        fn register_native_widgets(
+        fn native_handle(ptr: *mut std::ffi::c_void) -> spark_native_apple::NativeViewHandle {
+            // SAFETY: native widgets register their own live views.
+            unsafe { spark_native_apple::NativeViewHandle::from_ptr(ptr.cast()) }
+        }
+
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        fn collect_native_widgets(
             widget: &mut dyn Widget,
-            manager: &mut ViewManager,
+            container: Option<spark_layout::WidgetId>,
+            native: &mut NativeWidgets,
         ) {
-
-            
             let widget_id = widget.id();
             // Get children before we try to downcast (to avoid move issues)
             let children: Vec<*mut dyn Widget> = widget.children_mut().iter_mut().map(|c| c.as_mut() as *mut dyn Widget).collect();
@@ -191,94 +1096,449 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
             // Try to detect native widgets using is_native() method
             // This is the proper way since we can't downcast Box<dyn Widget>
             if widget.is_native() {
-                eprintln!("Found native widget with ID: {:?}", widget_id);
-                // Use the register_native method to register the widget
+                // Use the register_native method to get the view
                 // This avoids the need for downcasting
                 widget.register_native(widget_id, &mut |id, ptr| {
-                    use spark_native_apple::NativeViewHandle;
-                    #[cfg(target_os = "macos")]
-                    {
-                        let view_handle = NativeViewHandle::AppKit(ptr as *mut objc2::runtime::AnyObject);
-                        manager.register_widget(id, view_handle);
-                    }
-                    #[cfg(target_os = "ios")]
-                    {
-                        let view_handle = NativeViewHandle::UIKit(ptr as *mut objc2::runtime::AnyObject);
-                        manager.register_widget(id, view_handle);
+                    native.views.push((id, native_handle(ptr)));
+                    if let Some(container) = container {
+                        native.parents.push((id, container));
                     }
                 });
             }
+
+            // Native descendants of a container go in its document view
+            let mut container = container;
+            if let Some(document) = widget.native_document_view() {
+                if let Some(content) = widget.children().first() {
+                    native.documents.push((widget_id, native_handle(document), content.id()));
+                    container = Some(widget_id);
+                }
+            }
             
-            // Recursively register children
+            // Recursively collect children
             for child_ptr in children {
                 let child: &mut dyn Widget = unsafe { &mut *child_ptr };
-                register_native_widgets(child, manager);
+                collect_native_widgets(child, container, native);
             }
         }
 
+        // Use surface size - this should be in physical pixels
+        // But we need to convert to logical pixels for layout
+        let (logical_width, logical_height) =
+            dpi::logical_size(state.surface_state.size, state.scale_factor);
+
+        let native_point_scale =
+            dpi::native_point_scale(state.scale_factor, state.window_scale_factor);
+        let mut layout_ctx = LayoutContext {
+            text: &mut state.text_system,
+            max_width: Some(logical_width),
+            max_height: None,
+            native_point_scale,
+            theme: &state.theme,
+        };
         let root_id = add_to_layout(
             state.root_widget.as_mut(),
             &mut state.layout_tree,
+            &mut layout_ctx,
             false,
         );
         state.layout_tree.set_root(root_id);
+        // Widgets left out of the new tree stop watching their signals
+        let tree = &state.layout_tree;
+        state.signals.retain(|id| tree.get_layout(id).is_some());
+
+        // Tab order follows tree order, kept inside an open modal
+        let root = state.root_widget.as_ref();
+        let modal = find_modal(root);
+        let focus_scope = modal.and_then(|id| find_widget(root, id)).unwrap_or(root);
+        state.focus_manager.clear_focusable();
+        collect_focusable(focus_scope, &mut state.focus_manager);
+        state.widget_shortcuts.clear();
+        collect_shortcuts(focus_scope, &mut state.widget_shortcuts);
+
+        // Move focus into a modal when it opens and back when it closes
+        let previous_focus = state.focus_manager.focused();
+        if modal.is_some() != state.modal.is_some() {
+            if modal.is_some() {
+                state.focus_before_modal = previous_focus;
+                state.focus_manager.clear_focus();
+                state.focus_manager.focus_next();
+            } else if let Some(id) = state.focus_before_modal.take() {
+                state.focus_manager.set_focus(id);
+            } else {
+                state.focus_manager.clear_focus();
+            }
+        }
+        state.modal = modal;
+
+        // Compute layout, measuring wrapping text at the width it gets
+        let root = state.root_widget.as_ref();
+        let text = &mut state.text_system;
+        let theme = &state.theme;
+        state.layout_tree.compute_layout_with_wrapping(
+            logical_width,
+            logical_height,
+            |id, max_width| {
+                let mut ctx = LayoutContext {
+                    text: &mut *text,
+                    max_width: Some(max_width),
+                    max_height: None,
+                    native_point_scale,
+                    theme,
+                };
+                find_widget(root, id)?.measure(&mut ctx)
+            },
+        );
+        clamp_scroll_offsets(state.root_widget.as_mut(), &state.layout_tree);
 
-        // Compute layout
-        // Use surface size - this should be in physical pixels
-        // But we need to convert to logical pixels for layout
-        let size = state.surface_state.size;
-        let logical_width = (size.width as f32) / state.scale_factor;
-        let logical_height = (size.height as f32) / state.scale_factor;
-        state
-            .layout_tree
-            .compute_layout(logical_width, logical_height);
-        
-        // Store logical size for later use
-        let window_height_logical = logical_height;
 
         // Register native widgets and update their layouts
         #[cfg(any(target_os = "macos", target_os = "ios"))]
         if let Some(ref mut manager) = state.native_view_manager {
-            // Try to register native widgets
-            // Note: This won't work with trait objects - we need a different approach
-            // For now, native widgets must be registered manually or through type-specific code
-            register_native_widgets(state.root_widget.as_mut(), manager);
-            
-            use std::collections::HashMap;
-            let mut layouts = HashMap::new();
-            
-            // Collect all layouts for native widgets
-            state.layout_tree.traverse(|widget_id, computed, _depth| {
-                if manager.get_view(widget_id).is_some() {
-                    layouts.insert(widget_id, *computed);
-                }
-            });
-            
-            // Update layouts for registered native widgets
-            // The layout was computed with logical pixels, so parent_height should be logical too
-            manager.update_layouts(
-                &layouts,
-                window_height_logical,
-                state.scale_factor,
+            // Register new native widgets and remove views whose widgets are gone
+            let mut native = NativeWidgets::default();
+            collect_native_widgets(state.root_widget.as_mut(), None, &mut native);
+            manager.sync_widgets(native.views);
+            for (container_id, document, content_id) in native.documents {
+                manager.set_document_view(container_id, document, content_id);
+            }
+            for (widget_id, container_id) in native.parents {
+                manager.set_parent(widget_id, container_id);
+            }
+            manager.attach_children();
+        }
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        state.update_native_frames();
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        if let Some(ref mut manager) = state.native_view_manager {
+            // Native views draw over all GPU content; hide those under GPU overlays
+            let stacking = crate::compositing::native_stacking(
+                state.root_widget.as_ref(),
+                &state.layout_tree,
+                &state.theme,
             );
+            manager.set_stacking(stacking.order, stacking.covered);
+        }
+
+        if self.config.audit_accessibility {
+            state.audit_issues = audit::audit_targets(state.root_widget.as_ref(), &state.layout_tree);
+            state.audit_issues.iter().for_each(AuditIssue::log);
+            state.audit_contrast_pending = true;
         }
 
         state.needs_layout = false;
         state.needs_repaint = true;
+        let focus_changed = state.focus_manager.focused() != previous_focus;
+
+        self.update_accessibility(false);
+        if focus_changed {
+            self.focus_changed(previous_focus);
+        }
     }
 
-    fn paint(&mut self) {
-        let state = self.state.as_mut().unwrap();
-        state.draw_list.clear();
+    /// Push the widget tree, or what changed in it, to assistive technology.
+    fn update_accessibility(&mut self, full_tree: bool) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let full_tree = full_tree || state.accessibility_needs_tree;
+        state.accessibility.announce(state.announcements.take());
+        let sent = state.accessibility.publish_diff(
+            &mut state.accessibility_adapter,
+            full_tree,
+            &self.config.title,
+            state.root_widget.as_ref(),
+            &state.layout_tree,
+            state.focus_manager.focused(),
+            state.scale_factor,
+        );
+        if sent && full_tree {
+            state.accessibility_needs_tree = false;
+        }
+        // Announcing costs nothing while no one listens
+        state.announcements.set_listening(sent);
+    }
 
-        // Get elapsed time for animations
-        let elapsed_time = state.start_time.elapsed().as_secs_f32();
+    /// Handle tree requests and actions queued by assistive technology.
+    fn handle_accessibility_requests(&mut self) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let requests = state.accessibility_adapter.take_requests();
+        if requests.initial_tree {
+            state.accessibility_needs_tree = true;
+            self.update_accessibility(true);
+        }
+        for request in requests.actions {
+            self.handle_accessibility_action(request);
+        }
+    }
+
+    /// Route an assistive technology action to the widget it targets.
+    fn handle_accessibility_action(&mut self, request: accesskit::ActionRequest) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let Some(widget_id) = state.accessibility.get_widget_id(request.target_node) else {
+            return;
+        };
+        let Some(action) = AccessibleAction::from_accesskit(request.action) else {
+            log::debug!("unhandled accessibility action {:?}", request.action);
+            return;
+        };
+        let value = request.data.as_ref().and_then(AccessibleValue::from_accesskit);
+
+        match action {
+            AccessibleAction::Focus => self.set_focus(Some(widget_id)),
+            AccessibleAction::Click => self.activate(widget_id),
+            _ => {
+                // Offer the action to the target, then its ancestors, so a
+                // scroll request on any descendant moves the enclosing Scroll.
+                let path = widget_path(state.root_widget.as_ref(), widget_id).unwrap_or_default();
+                for id in path.into_iter().rev() {
+                    let response = self.dispatch_to(id, PointerId::PRIMARY, |widget, ctx| {
+                        widget.accessibility_action(ctx, action, value.as_ref())
+                    });
+                    if response.is_some_and(|r| r.handled) {
+                        return;
+                    }
+                }
+                log::debug!("no widget handled accessibility action {action:?}");
+            }
+        }
+    }
+
+    /// Activate a widget the way Enter would, as requested by assistive technology.
+    fn activate(&mut self, target: WidgetId) {
+        let response = self.dispatch_to(target, PointerId::PRIMARY, |widget, ctx| {
+            widget.accessibility_action(ctx, AccessibleAction::Click, None)
+        });
+        if response.is_none_or(|r| r.handled) {
+            return;
+        }
+
+        let state = self.state.as_mut().unwrap();
+        let Some(widget) = find_widget_mut(state.root_widget.as_mut(), target) else {
+            return;
+        };
+        // Native views receive clicks from the platform, never synthesized ones.
+        if widget.is_native() {
+            log::debug!("native widget {target:?} did not handle Click");
+            return;
+        }
+        if widget.focusable() {
+            // Activate only fires for the focused widget.
+            self.set_focus(Some(target));
+            let event = InputEvent::KeyDown {
+                event: KeyboardEvent {
+                    key: Key::Named(NamedKey::Enter),
+                    ..Default::default()
+                },
+            };
+            self.dispatch_to(target, PointerId::PRIMARY, |widget, ctx| {
+                widget.event(ctx, &event)
+            });
+            return;
+        }
+        // Widgets without keyboard activation still respond to a click,
+        // aimed where the widget shows so that hit testing finds it.
+        self.scroll_into_view(target);
+        let state = self.state.as_ref().unwrap();
+        if let Some(layout) = state.layout_tree.get_absolute_layout(target) {
+            let b = layout.bounds;
+            let center = glam::Vec2::new(b.x + b.width / 2.0, b.y + b.height / 2.0);
+            let pos = center - scroll_offset_above(state.root_widget.as_ref(), target);
+            let button = PointerButton::Primary;
+            self.handle_event(InputEvent::pointer_down(pos, button));
+            self.handle_event(InputEvent::pointer_up(pos, button));
+        }
+    }
+
+    /// Move keyboard focus, as Tab traversal or assistive technology would.
+    fn set_focus(&mut self, target: Option<WidgetId>) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let previous_focus = state.focus_manager.focused();
+        match target {
+            Some(id) => {
+                state.focus_manager.set_focus(id);
+                state.focus_manager.set_focus_visible(true);
+            }
+            None => state.focus_manager.clear_focus(),
+        }
+        self.apply_response(EventResponse::handled(), previous_focus);
+    }
+
+    /// Notify widgets of a focus change and publish it to assistive technology.
+    fn focus_changed(&mut self, previous: Option<WidgetId>) {
+        let state = self.state.as_mut().unwrap();
+        let current = state.focus_manager.focused();
+        if let Some(widget) = previous.and_then(|id| find_widget_mut(state.root_widget.as_mut(), id)) {
+            widget.on_blur();
+        }
+        if let Some(widget) = current.and_then(|id| find_widget_mut(state.root_widget.as_mut(), id)) {
+            widget.on_focus();
+        }
+        if let Some(id) = current {
+            self.scroll_into_view(id);
+        }
+
+        // Repaint focus rings
+        let state = self.state.as_mut().unwrap();
+        state.needs_repaint = true;
+        state.request_redraw();
+        self.update_accessibility(false);
+    }
+
+    /// Run the app-wide shortcut for a key press, if it has one.
+    ///
+    /// Returns whether the shortcut map took the key, in which case no
+    /// widget should see it.
+    fn handle_shortcut(&mut self, event: &KeyboardEvent) -> bool {
+        let shortcuts = &self.config.shortcuts;
+        if shortcuts.is_disabled(event) {
+            return true;
+        }
+        match shortcuts.action_for(event).cloned() {
+            Some(Action::Custom(action)) => self.perform_custom_action(&action),
+            Some(Action::Standard(action)) => self.perform_standard_action(action),
+            None => return self.handle_widget_shortcut(event),
+        }
+        true
+    }
+
+    /// Send a key press to the widget that takes it without focus, such as a
+    /// menu bar's mnemonic. Returns whether that widget handled it.
+    fn handle_widget_shortcut(&mut self, event: &KeyboardEvent) -> bool {
+        let target = self
+            .state
+            .as_ref()
+            .and_then(|state| state.widget_shortcuts.action_for(event).copied());
+        let Some(target) = target else {
+            return false;
+        };
+        let key_down = InputEvent::KeyDown {
+            event: event.clone(),
+        };
+        self.dispatch_to(target, PointerId::PRIMARY, |widget, ctx| {
+            widget.event(ctx, &key_down)
+        })
+        .is_some_and(|response| response.handled)
+    }
+
+    /// Move focus along the tab order when an unhandled key press maps to
+    /// `FocusNext` or `FocusPrevious`.
+    fn traverse_focus(&mut self, key_down: &InputEvent) {
+        match ActionMapper::new().map_event(key_down) {
+            Some(Action::Standard(StandardAction::FocusNext)) => self.move_focus(true),
+            Some(Action::Standard(StandardAction::FocusPrevious)) => self.move_focus(false),
+            _ => {}
+        }
+    }
+
+    /// Move focus to the next or previous widget in tab order, wrapping
+    /// around at either end.
+    fn move_focus(&mut self, forward: bool) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let previous_focus = state.focus_manager.focused();
+        if forward {
+            state.focus_manager.focus_next();
+        } else {
+            state.focus_manager.focus_previous();
+        }
+        self.apply_response(EventResponse::handled(), previous_focus);
+    }
+
+    /// Adjust enclosing scroll containers so `target` is visible.
+    fn scroll_into_view(&mut self, target: WidgetId) {
+        let state = self.state.as_mut().unwrap();
+        let Some(path) = widget_path(state.root_widget.as_ref(), target) else {
+            return;
+        };
+        let Some(mut rect) = state.layout_tree.get_absolute_layout(target).map(|l| l.bounds) else {
+            return;
+        };
+
+        // Innermost container first; each one shifts the rect seen by the next.
+        for &id in path.iter().rev().skip(1) {
+            let Some(viewport) = state.layout_tree.get_absolute_layout(id).map(|l| l.bounds) else {
+                continue;
+            };
+            let Some(widget) = find_widget_mut(state.root_widget.as_mut(), id) else {
+                continue;
+            };
+            if !widget.is_scroll_container() {
+                continue;
+            }
+            let local = rect.translate(-viewport.pos());
+            widget.scroll_to_visible(local, viewport);
+            let (offset_x, offset_y) = widget.scroll_offset().unwrap_or_default();
+            rect.x -= offset_x;
+            rect.y -= offset_y;
+        }
+    }
+
+    /// Deliver a call to exactly one widget, bypassing hit testing.
+    ///
+    /// This is the targeted counterpart to [`Self::handle_event`], used for
+    /// accessibility actions. `pointer` is the one the widget may capture.
+    /// Returns `None` if the widget is not laid out.
+    fn dispatch_to(
+        &mut self,
+        target: WidgetId,
+        pointer: PointerId,
+        deliver: impl FnOnce(&mut dyn Widget, &mut EventContext) -> EventResponse,
+    ) -> Option<EventResponse> {
+        let state = self.state.as_mut()?;
+        let layout = state.layout_tree.get_absolute_layout(target)?;
+        let widget = find_widget_mut(state.root_widget.as_mut(), target)?;
+        let previous_focus = state.focus_manager.focused();
+
+        let mut ctx = EventContext {
+            layout,
+            layout_tree: &state.layout_tree,
+            focus: &mut state.focus_manager,
+            widget_id: target,
+            has_capture: state.pointer_capture.get(&pointer) == Some(&target),
+            target,
+            phase: EventPhase::Target,
+            theme: &state.theme,
+            clipboard: &mut *state.clipboard,
+            announcements: &state.announcements,
+        };
+        let response = deliver(widget, &mut ctx);
+
+        if response.capture_pointer {
+            state.pointer_capture.insert(pointer, target);
+        } else if response.release_pointer && state.pointer_capture.get(&pointer) == Some(&target) {
+            state.pointer_capture.remove(&pointer);
+        }
+        if response.request_focus {
+            state.focus_manager.set_focus(target);
+            state.focus_manager.set_focus_visible(true);
+        } else if response.release_focus && state.focus_manager.has_focus(target) {
+            state.focus_manager.clear_focus();
+        }
+        self.apply_response(response, previous_focus);
+        Some(response)
+    }
+
+    fn paint(&mut self) {
+        let state = self.state.as_mut().unwrap();
+        state.draw_list.clear();
+
+        // Get elapsed time for animations
+        let elapsed_time = state.elapsed_time();
 
         // We need to use raw pointers to pass mutable references through the recursive function
         // This is safe because we control the lifetime and don't alias
         let text_system_ptr = &mut state.text_system as *mut TextSystem;
         let device_ptr = &state.device as *const Device;
         let queue_ptr = &state.queue as *const Queue;
+        let animation_requested = std::cell::Cell::new(false);
 
         // Paint widgets (skip native widgets as they render themselves)
         #[allow(clippy::too_many_arguments)]
@@ -287,11 +1547,15 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
             layout_tree: &LayoutTree,
             focus: &FocusManager,
             draw_list: &mut DrawList,
+            theme: &Theme,
             scale_factor: f32,
             text_system_ptr: *mut TextSystem,
             device_ptr: *const Device,
             queue_ptr: *const Queue,
             elapsed_time: f32,
+            animation_requested: &std::cell::Cell<bool>,
+            signals: &SignalRuntime,
+            announcements: &AnnouncementQueue,
             #[cfg(any(target_os = "macos", target_os = "ios"))]
             native_view_manager: Option<&ViewManager>,
         ) {
@@ -303,17 +1567,21 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
                 if manager.get_view(id).is_some() {
                     // This is a native widget, skip GPU painting
                     // Still paint children in case they're not native
-                    for child in widget.children() {
+                    for child in widget.children().iter().filter(|c| !c.is_modal()) {
                         paint_widget(
                             child.as_ref(),
                             layout_tree,
                             focus,
                             draw_list,
+                            theme,
                             scale_factor,
                             text_system_ptr,
                             device_ptr,
                             queue_ptr,
                             elapsed_time,
+                            animation_requested,
+                            signals,
+                            announcements,
                             #[cfg(any(target_os = "macos", target_os = "ios"))]
                             Some(manager),
                         );
@@ -330,14 +1598,8 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
 
                 // Scale layout bounds from logical to physical pixels
                 // Layout is computed in logical pixels, but renderer uses physical pixels
-                let scaled_layout = spark_layout::ComputedLayout::new(
-                    spark_core::Rect::new(
-                        layout.bounds.x * scale_factor,
-                        layout.bounds.y * scale_factor,
-                        layout.bounds.width * scale_factor,
-                        layout.bounds.height * scale_factor,
-                    )
-                );
+                let scaled_layout =
+                    spark_layout::ComputedLayout::new(layout.bounds.scale(scale_factor));
 
                 let mut ctx = PaintContext {
                     draw_list,
@@ -345,26 +1607,37 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
                     layout_tree,
                     focus,
                     widget_id: id,
+                    theme,
                     scale_factor,
                     text_system,
                     device,
                     queue,
                     elapsed_time,
+                    animation_requested,
+                    signals,
+                    announcements,
                 };
                 widget.paint(&mut ctx);
+                if widget.needs_animation() {
+                    animation_requested.set(true);
+                }
 
-                // Paint children
-                for child in widget.children() {
+                // Paint children; an open modal is painted last, in its own layer
+                for child in widget.children().iter().filter(|c| !c.is_modal()) {
                     paint_widget(
                         child.as_ref(),
                         layout_tree,
                         focus,
                         ctx.draw_list,
+                        theme,
                         scale_factor,
                         text_system_ptr,
                         device_ptr,
                         queue_ptr,
                         elapsed_time,
+                        animation_requested,
+                        signals,
+                        announcements,
                         #[cfg(any(target_os = "macos", target_os = "ios"))]
                         native_view_manager,
                     );
@@ -380,26 +1653,404 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
             &state.layout_tree,
             &state.focus_manager,
             &mut state.draw_list,
+            &state.theme,
             state.scale_factor,
             text_system_ptr,
             device_ptr,
             queue_ptr,
             elapsed_time,
+            &animation_requested,
+            &state.signals,
+            &state.announcements,
             #[cfg(any(target_os = "macos", target_os = "ios"))]
             state.native_view_manager.as_ref(),
         );
 
+        // Open overlays (e.g. dropdown lists) follow scroll offsets but not
+        // clips, so only the translations are replayed
+        #[allow(clippy::too_many_arguments)]
+        fn paint_overlays(
+            widget: &dyn Widget,
+            layout_tree: &LayoutTree,
+            focus: &FocusManager,
+            draw_list: &mut DrawList,
+            theme: &Theme,
+            scale_factor: f32,
+            text_system_ptr: *mut TextSystem,
+            device_ptr: *const Device,
+            queue_ptr: *const Queue,
+            elapsed_time: f32,
+            animation_requested: &std::cell::Cell<bool>,
+            signals: &SignalRuntime,
+            announcements: &AnnouncementQueue,
+        ) {
+            let id = widget.id();
+            if widget.has_overlay() {
+                if let Some(layout) = layout_tree.get_absolute_layout(id) {
+                    // SAFETY: as in paint_widget
+                    let text_system = unsafe { &mut *text_system_ptr };
+                    let device = unsafe { &*device_ptr };
+                    let queue = unsafe { &*queue_ptr };
+                    let mut ctx = PaintContext {
+                        draw_list: &mut *draw_list,
+                        layout: spark_layout::ComputedLayout::new(
+                            layout.bounds.scale(scale_factor),
+                        ),
+                        layout_tree,
+                        focus,
+                        widget_id: id,
+                        theme,
+                        scale_factor,
+                        text_system,
+                        device,
+                        queue,
+                        elapsed_time,
+                        animation_requested,
+                        signals,
+                        announcements,
+                    };
+                    widget.paint_overlay(&mut ctx);
+                }
+            }
+
+            let scroll = widget
+                .scroll_offset()
+                .filter(|_| widget.is_scroll_container());
+            if let Some((x, y)) = scroll {
+                draw_list.push_translation((-x * scale_factor, -y * scale_factor));
+            }
+            for child in widget.children().iter().filter(|c| !c.is_modal()) {
+                paint_overlays(
+                    child.as_ref(),
+                    layout_tree,
+                    focus,
+                    draw_list,
+                    theme,
+                    scale_factor,
+                    text_system_ptr,
+                    device_ptr,
+                    queue_ptr,
+                    elapsed_time,
+                    animation_requested,
+                    signals,
+                    announcements,
+                );
+            }
+            if scroll.is_some() {
+                draw_list.pop_translation();
+            }
+        }
+
+        let mut overlays = Vec::new();
+        collect_overlays(state.root_widget.as_ref(), &mut overlays);
+        if !overlays.is_empty() {
+            state.draw_list.begin_layer();
+            paint_overlays(
+                state.root_widget.as_ref(),
+                &state.layout_tree,
+                &state.focus_manager,
+                &mut state.draw_list,
+                &state.theme,
+                state.scale_factor,
+                text_system_ptr,
+                device_ptr,
+                queue_ptr,
+                elapsed_time,
+                &animation_requested,
+                &state.signals,
+                &state.announcements,
+            );
+        }
+
+        // An open modal covers everything, overlays included
+        let modal = state
+            .modal
+            .and_then(|id| find_widget(state.root_widget.as_ref(), id));
+        if let Some(modal) = modal {
+            state.draw_list.begin_layer();
+            paint_widget(
+                modal,
+                &state.layout_tree,
+                &state.focus_manager,
+                &mut state.draw_list,
+                &state.theme,
+                state.scale_factor,
+                text_system_ptr,
+                device_ptr,
+                queue_ptr,
+                elapsed_time,
+                &animation_requested,
+                &state.signals,
+                &state.announcements,
+                #[cfg(any(target_os = "macos", target_os = "ios"))]
+                state.native_view_manager.as_ref(),
+            );
+            let mut overlays = Vec::new();
+            collect_overlays(modal, &mut overlays);
+            if !overlays.is_empty() {
+                state.draw_list.begin_layer();
+                paint_overlays(
+                    modal,
+                    &state.layout_tree,
+                    &state.focus_manager,
+                    &mut state.draw_list,
+                    &state.theme,
+                    state.scale_factor,
+                    text_system_ptr,
+                    device_ptr,
+                    queue_ptr,
+                    elapsed_time,
+                    &animation_requested,
+                    &state.signals,
+                    &state.announcements,
+                );
+            }
+        }
+
+        if self.config.audit_accessibility {
+            if state.audit_contrast_pending {
+                let background = self.config.background.unwrap_or(state.theme.colors.background);
+                let issues = audit::audit_contrast(state.draw_list.commands(), background);
+                issues.iter().for_each(AuditIssue::log);
+                state.audit_issues.extend(issues);
+                state.audit_contrast_pending = false;
+            }
+            audit::draw_overlay(&mut state.draw_list, &state.audit_issues, state.scale_factor);
+        }
+
         state.needs_repaint = false;
+        if animation_requested.get() {
+            state.needs_repaint = true;
+            state.request_redraw();
+        }
+
+        // Enable the IME only while a text widget reports a caret, and keep
+        // the candidate window next to it.
+        use winit::window::{ImeCapabilities, ImeEnableRequest, ImeRequest, ImeRequestData};
+        let caret = state.focus_manager.take_caret_rect();
+        match caret {
+            Some(rect) => {
+                let data = ImeRequestData::default().with_cursor_area(
+                    winit::dpi::PhysicalPosition::new(
+                        rect.x * state.scale_factor,
+                        rect.y * state.scale_factor,
+                    )
+                    .into(),
+                    winit::dpi::PhysicalSize::new(
+                        rect.width * state.scale_factor,
+                        rect.height * state.scale_factor,
+                    )
+                    .into(),
+                );
+                if !state.ime_allowed {
+                    let caps = ImeCapabilities::new().with_cursor_area();
+                    if let Some(request) = ImeEnableRequest::new(caps, data) {
+                        let _ = state.window.request_ime_update(ImeRequest::Enable(request));
+                    }
+                    state.ime_allowed = true;
+                } else if state.ime_cursor_area != Some(rect) {
+                    let _ = state.window.request_ime_update(ImeRequest::Update(data));
+                }
+            }
+            None if state.ime_allowed => {
+                let _ = state.window.request_ime_update(ImeRequest::Disable);
+                state.ime_allowed = false;
+            }
+            None => {}
+        }
+        state.ime_cursor_area = caret;
+
+        // winit's web backend has no IME; the text entry field stands in
+        #[cfg(target_arch = "wasm32")]
+        if let Some(entry) = &self.text_entry {
+            let css = state.scale_factor / state.window_scale_factor;
+            entry.set_caret(caret.map(|rect| {
+                Rect::new(rect.x * css, rect.y * css, rect.width * css, rect.height * css)
+            }));
+        }
+    }
+
+    /// The modifier keys held, as last reported by the window.
+    fn held_modifiers(&self) -> Modifiers {
+        self.state.as_ref().map_or(Modifiers::empty(), |state| {
+            modifiers_from_winit(state.modifiers)
+        })
+    }
+
+    /// Deliver a pointer moving to `pos`. The first finger pans the content
+    /// it touched down on, instead of moving, once past the touch slop.
+    fn pointer_moved(&mut self, pos: glam::Vec2, pointer: Pointer) {
+        let scroll = self
+            .state
+            .as_mut()
+            .and_then(|state| match &mut state.touch_pan {
+                Some((id, pan)) if *id == pointer.id => pan.move_to(pos),
+                _ => None,
+            });
+        let moved = InputEvent::pointer_move(pos)
+            .with_pointer(pointer)
+            .with_modifiers(self.held_modifiers());
+        self.handle_event(scroll.unwrap_or(moved));
+    }
+
+    /// Deliver a press of `pointer`'s `button` at `pos`.
+    fn pointer_pressed(&mut self, pos: glam::Vec2, button: PointerButton, pointer: Pointer) {
+        let modifiers = self.held_modifiers();
+        if pointer.is_touch() {
+            if let Some(state) = self.state.as_mut() {
+                if pointer.primary && state.scrolls_at(pos) {
+                    state.touch_pan = Some((pointer.id, TouchPan::new(pos)));
+                }
+            }
+            // A finger is only over what it touches
+            let moved = InputEvent::pointer_move(pos).with_pointer(pointer);
+            self.handle_event(moved.with_modifiers(modifiers));
+        }
+        let pressed = InputEvent::pointer_down(pos, button).with_pointer(pointer);
+        self.handle_event(pressed.with_modifiers(modifiers));
+    }
+
+    /// Deliver a release of `pointer`'s `button` at `pos`.
+    fn pointer_released(&mut self, pos: glam::Vec2, button: PointerButton, pointer: Pointer) {
+        let panned = self
+            .state
+            .as_mut()
+            .and_then(|state| state.touch_pan.take_if(|(id, _)| *id == pointer.id))
+            .is_some_and(|(_, pan)| pan.is_panning());
+        // A pan isn't a tap
+        if panned {
+            self.handle_event(InputEvent::pointer_cancel().with_pointer(pointer));
+            return;
+        }
+        let released = InputEvent::pointer_up(pos, button).with_pointer(pointer);
+        self.handle_event(released.with_modifiers(self.held_modifiers()));
+    }
+
+    /// Forget a pointer that left the window. A finger leaves when it's
+    /// lifted, or without being lifted when the system stops tracking it;
+    /// a press still in progress is cancelled.
+    fn pointer_left(&mut self, pointer: Pointer) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        if state.pressed.iter().any(|pressed| pressed.id == pointer.id) {
+            self.handle_event(InputEvent::pointer_cancel().with_pointer(pointer));
+        }
+        if pointer.primary {
+            self.update_hover(None);
+        }
+    }
+
+    /// End `pointer`'s press without a release. Whatever captured it, or
+    /// else whatever it hovers, gets `PointerCancel` to drop its pressed or
+    /// dragging state.
+    fn cancel_pointer(&mut self, pointer: Pointer) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        state.pressed.retain(|pressed| pressed.id != pointer.id);
+        state.touch_pan = state.touch_pan.filter(|(id, _)| *id != pointer.id);
+        let targets = cancel_targets(
+            state.pointer_capture.remove(&pointer.id),
+            &state.hovered,
+            pointer,
+        );
+        let cancel = InputEvent::PointerCancel { pointer };
+        for target in targets {
+            self.dispatch_to(target, pointer.id, |widget, ctx| widget.event(ctx, &cancel));
+        }
     }
 
-    fn handle_event(&mut self, event: InputEvent) {
+    fn handle_event(&mut self, event: InputEvent) -> EventResponse {
+        let event = match self.state.as_mut() {
+            Some(state) => state.stamp_pointer_event(event),
+            None => event,
+        };
+        // Events without a pointer of their own, like wheel scrolls, count
+        // as the mouse's
+        let pointer = event.pointer().unwrap_or_default();
+        if let Some(state) = self.state.as_mut() {
+            let pressed = &mut state.pressed;
+            match event {
+                InputEvent::PointerDown { .. } if !pressed.iter().any(|p| p.id == pointer.id) => {
+                    pressed.push(pointer);
+                }
+                InputEvent::PointerUp { .. } => pressed.retain(|p| p.id != pointer.id),
+                InputEvent::PointerCancel { .. } => {
+                    self.cancel_pointer(pointer);
+                    return EventResponse::default();
+                }
+                _ => {}
+            }
+        }
+        if let InputEvent::PointerMove { pos, .. } = event {
+            // Hover follows the mouse, or the first finger
+            if pointer.primary {
+                self.update_hover(Some(pos));
+            }
+        }
+
+        // A widget holding the pointer capture gets its pointer's events
+        // exclusively
+        let capture = self
+            .state
+            .as_ref()
+            .and_then(|s| s.pointer_capture.get(&pointer.id).copied());
+        if let Some(target) = capture.filter(|_| event.is_pointer_event()) {
+            let root = self.state.as_ref().unwrap().root_widget.as_ref();
+            let translated = event.translated(scroll_offset_above(root, target));
+            let response = self.dispatch_to(target, pointer.id, |widget, ctx| {
+                widget.event(ctx, &translated)
+            });
+            let state = self.state.as_mut().unwrap();
+            if response.is_none() || matches!(event, InputEvent::PointerUp { .. }) {
+                // Gone from the tree, or the drag is over
+                state.pointer_capture.remove(&pointer.id);
+                if pointer.primary {
+                    self.update_hover(event.pos());
+                }
+            }
+            if let Some(response) = response {
+                self.update_cursor(&event, response);
+                return response;
+            }
+        }
+
+        // Open overlays float above everything, so they see pointer events first
+        if event.is_pointer_event() {
+            let mut overlays = Vec::new();
+            if let Some(state) = self.state.as_ref() {
+                let root = state.root_widget.as_ref();
+                let scope = state
+                    .modal
+                    .and_then(|id| find_widget(root, id))
+                    .unwrap_or(root);
+                collect_overlays(scope, &mut overlays);
+            }
+            for id in overlays {
+                let root = self.state.as_ref().unwrap().root_widget.as_ref();
+                let translated = event.translated(scroll_offset_above(root, id));
+                let response =
+                    self.dispatch_to(id, pointer.id, |widget, ctx| widget.event(ctx, &translated));
+                if let Some(response) = response.filter(|r| r.handled) {
+                    self.update_cursor(&event, response);
+                    return response;
+                }
+            }
+        }
+
         let state = self.state.as_mut().unwrap();
 
-        // Simple event dispatch - dispatch to all widgets, let them check bounds
-        fn dispatch_event(
+        // Deliver to every widget, children first, letting each check bounds
+        // or focus itself
+        fn broadcast_event(
             widget: &mut dyn Widget,
             layout_tree: &LayoutTree,
+            theme: &Theme,
+            clipboard: &mut dyn ClipboardService,
+            announcements: &AnnouncementQueue,
             focus_id: Option<spark_layout::WidgetId>,
+            capture: &mut Option<WidgetId>,
             event: &InputEvent,
         ) -> (spark_widgets::EventResponse, Option<spark_layout::WidgetId>) {
             let id = widget.id();
@@ -412,8 +2063,22 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
 
             // First dispatch to children (bubble up)
             let mut new_focus = focus_id;
-            for child in widget.children_mut() {
-                let (response, focus) = dispatch_event(child.as_mut(), layout_tree, new_focus, event);
+            let stacked = widget.stacks_children();
+            let mut children: Vec<_> = widget.children_mut().iter_mut().collect();
+            if stacked {
+                children.reverse();
+            }
+            for child in children {
+                let (response, focus) = broadcast_event(
+                    child.as_mut(),
+                    layout_tree,
+                    theme,
+                    &mut *clipboard,
+                    announcements,
+                    new_focus,
+                    capture,
+                    event,
+                );
                 new_focus = focus;
                 if response.handled {
                     return (response, new_focus);
@@ -426,86 +2091,748 @@ impl<F: FnOnce() -> Box<dyn Widget>> AppRunner<F> {
                 temp_focus.set_focus(fid);
             }
 
-            let mut ctx = EventContext {
-                layout,
-                layout_tree,
-                focus: &mut temp_focus,
-                widget_id: id,
-                has_capture: false,
-            };
+            let mut ctx = EventContext {
+                layout,
+                layout_tree,
+                focus: &mut temp_focus,
+                widget_id: id,
+                has_capture: *capture == Some(id),
+                target: id,
+                phase: EventPhase::Target,
+                theme,
+                clipboard,
+                announcements,
+            };
+
+            let response = widget.event(&mut ctx, event);
+            if response.capture_pointer {
+                *capture = Some(id);
+            }
+
+            // Update focus
+            if response.request_focus {
+                new_focus = Some(id);
+            } else if response.release_focus && new_focus == Some(id) {
+                new_focus = None;
+            } else if temp_focus.focused() != new_focus {
+                // Moved through the context, e.g. a radio group's arrow keys
+                new_focus = temp_focus.focused();
+            }
+
+            (response, new_focus)
+        }
+
+        // Widgets on the way may capture the event's pointer
+        let mut capture = state.pointer_capture.remove(&pointer.id);
+
+        // While a modal is open, only its subtree receives events
+        let current_focus = state.focus_manager.focused();
+        let path = {
+            let root = state.root_widget.as_ref();
+            let scope = state
+                .modal
+                .and_then(|id| find_widget(root, id))
+                .unwrap_or(root);
+            event_path(scope, &state.layout_tree, current_focus, &event)
+        };
+        let mut response = match &path {
+            Some(path) => bubble_event(
+                state.root_widget.as_mut(),
+                &state.layout_tree,
+                &state.theme,
+                &mut *state.clipboard,
+                &state.announcements,
+                &mut state.focus_manager,
+                &mut capture,
+                path,
+                &event,
+            ),
+            None => EventResponse::default(),
+        };
+
+        // Events without a target go everywhere
+        if path.is_none() {
+            let root = state.root_widget.as_mut();
+            let scope = match state.modal {
+                Some(id) if find_widget(root, id).is_some() => find_widget_mut(root, id).unwrap(),
+                _ => root,
+            };
+            let (broadcast, new_focus) = broadcast_event(
+                scope,
+                &state.layout_tree,
+                &state.theme,
+                &mut *state.clipboard,
+                &state.announcements,
+                state.focus_manager.focused(),
+                &mut capture,
+                &event,
+            );
+            response = broadcast;
+
+            // Update focus manager
+            if let Some(fid) = new_focus {
+                state.focus_manager.set_focus(fid);
+            } else if state.focus_manager.focused().is_some() {
+                state.focus_manager.clear_focus();
+            }
+        }
+
+        if let Some(target) = capture {
+            state.pointer_capture.insert(pointer.id, target);
+        }
+
+        if state.focus_manager.focused() != current_focus {
+            // Clicking focuses without a ring; keys and other events show it.
+            state.focus_manager.set_focus_visible(!event.is_pointer_event());
+        }
+        self.apply_response(response, current_focus);
+        self.update_cursor(&event, response);
+        response
+    }
+
+    /// Send `PointerLeave` and `PointerEnter` to the widgets the pointer
+    /// left and entered by moving to `pos`, or out of the window with `None`.
+    ///
+    /// Hover stays put while a widget holds a pointer capture.
+    fn update_hover(&mut self, pos: Option<glam::Vec2>) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        if !state.pointer_capture.is_empty() {
+            return;
+        }
+        let hovered = pos
+            .and_then(|pos| {
+                let root = state.root_widget.as_ref();
+                let scope = state
+                    .modal
+                    .and_then(|id| find_widget(root, id))
+                    .unwrap_or(root);
+                hit_path(scope, &state.layout_tree, pos)
+            })
+            .unwrap_or_default();
+        let offsets = path_scroll_offsets(state.root_widget.as_ref(), &hovered);
+        let previous = std::mem::replace(&mut state.hovered, hovered.clone());
+
+        for &id in previous.iter().rev().filter(|id| !hovered.contains(id)) {
+            self.dispatch_to(id, PointerId::PRIMARY, |widget, ctx| {
+                widget.event(ctx, &InputEvent::PointerLeave)
+            });
+        }
+        if let Some(pos) = pos {
+            for (&id, offset) in hovered.iter().zip(offsets) {
+                if !previous.contains(&id) {
+                    let enter = InputEvent::PointerEnter { pos: pos + offset };
+                    self.dispatch_to(id, PointerId::PRIMARY, |widget, ctx| {
+                        widget.event(ctx, &enter)
+                    });
+                }
+            }
+        }
+    }
+
+    /// Show the pointer shape asked for by the widgets under the pointer,
+    /// or the arrow when none asks.
+    fn update_cursor(&mut self, event: &InputEvent, response: EventResponse) {
+        if !matches!(event, InputEvent::PointerMove { .. }) {
+            return;
+        }
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let cursor = response.cursor.unwrap_or_default();
+        if cursor != state.cursor {
+            state.cursor = cursor;
+            state.window.set_cursor(cursor_to_winit(cursor).into());
+        }
+    }
+
+    /// Apply repaint, relayout, zoom and theme requests after an event.
+    fn apply_response(&mut self, response: EventResponse, previous_focus: Option<WidgetId>) {
+        let state = self.state.as_mut().unwrap();
+        let focus_changed = state.focus_manager.focused() != previous_focus;
+
+        if response.repaint {
+            state.needs_repaint = true;
+        }
+        if response.relayout || find_modal(state.root_widget.as_ref()) != state.modal {
+            state.needs_layout = true;
+        }
+        if let Some(zoom) = response.ui_zoom {
+            state.set_ui_zoom(&self.config, zoom);
+        }
+        if let Some(mode) = response.theme_mode {
+            state.set_theme_mode(&self.config, mode);
+        }
+        // Closed once the event batch is done
+        self.exit_requested |= response.exit;
+        
+        // Request redraw if we need to repaint or relayout
+        if state.needs_repaint || state.needs_layout {
+            state.request_redraw();
+        }
+
+        let state = self.state.as_ref().unwrap();
+        if focus_changed {
+            self.focus_changed(previous_focus);
+        } else if (response.repaint && !state.needs_layout) || !state.announcements.is_empty() {
+            // E.g. a toggled checkbox; layouts publish their own changes
+            self.update_accessibility(false);
+        }
+    }
+
+    /// Deliver text read from the clipboard to the focused widget.
+    fn deliver_pasted_text(&mut self) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        for text in state.clipboard.take_pasted() {
+            self.handle_event(InputEvent::Paste { text });
+        }
+    }
+}
+
+/// Check if `widget` is a scroll container with something to scroll.
+fn can_scroll(widget: &dyn Widget) -> bool {
+    widget.is_scroll_container()
+        && widget
+            .accessibility_info()
+            .scroll
+            .is_some_and(|scroll| scroll.max_x > 0.0 || scroll.max_y > 0.0)
+}
+
+/// Collect the bounds of scroll containers with something to scroll, and of
+/// text widgets, for handling touches before the app sees them.
+#[cfg(target_arch = "wasm32")]
+fn touch_regions(
+    widget: &dyn Widget,
+    layout_tree: &LayoutTree,
+    scroll: &mut Vec<Rect>,
+    text: &mut Vec<Rect>,
+) {
+    let info = widget.accessibility_info();
+    let scrollable = can_scroll(widget);
+    let editable = matches!(
+        info.role,
+        spark_widgets::AccessibleRole::TextField | spark_widgets::AccessibleRole::SearchField
+    );
+    if scrollable || editable {
+        if let Some(layout) = layout_tree.get_absolute_layout(widget.id()) {
+            if scrollable {
+                scroll.push(layout.bounds);
+            } else {
+                text.push(layout.bounds);
+            }
+        }
+    }
+    for child in widget.children() {
+        touch_regions(child.as_ref(), layout_tree, scroll, text);
+    }
+}
+
+/// Find the widget with `target` in the tree.
+fn find_widget_mut(widget: &mut dyn Widget, target: WidgetId) -> Option<&mut dyn Widget> {
+    if widget.id() == target {
+        return Some(widget);
+    }
+    widget
+        .children_mut()
+        .iter_mut()
+        .find_map(|child| find_widget_mut(child.as_mut(), target))
+}
+
+fn find_widget(widget: &dyn Widget, target: WidgetId) -> Option<&dyn Widget> {
+    if widget.id() == target {
+        return Some(widget);
+    }
+    widget
+        .children()
+        .iter()
+        .find_map(|child| find_widget(child.as_ref(), target))
+}
+
+/// Pull scroll offsets back inside content that got shorter, e.g. after a
+/// section collapsed, so scroll containers never show space past the end.
+fn clamp_scroll_offsets(widget: &mut dyn Widget, layout_tree: &LayoutTree) {
+    fn content_end(widget: &dyn Widget, layout_tree: &LayoutTree) -> (f32, f32) {
+        let mut end = layout_tree
+            .get_absolute_layout(widget.id())
+            .map_or((0.0, 0.0), |layout| {
+                let bounds = layout.bounds;
+                (bounds.x + bounds.width, bounds.y + bounds.height)
+            });
+        for child in widget.children() {
+            let (right, bottom) = content_end(child.as_ref(), layout_tree);
+            end = (end.0.max(right), end.1.max(bottom));
+        }
+        end
+    }
+
+    let offset = widget
+        .scroll_offset()
+        .filter(|_| widget.is_scroll_container());
+    let viewport = layout_tree.get_absolute_layout(widget.id());
+    if let (Some((x, y)), Some(viewport)) = (offset, viewport) {
+        let (right, bottom) = content_end(widget, layout_tree);
+        let bounds = viewport.bounds;
+        let max_x = (right - bounds.x - bounds.width).max(0.0);
+        let max_y = (bottom - bounds.y - bounds.height).max(0.0);
+        if x > max_x || y > max_y {
+            widget.set_scroll_offset(x.min(max_x), y.min(max_y));
+        }
+    }
+    for child in widget.children_mut() {
+        clamp_scroll_offsets(child.as_mut(), layout_tree);
+    }
+}
+
+/// The open modal painted last, if any.
+fn find_modal(widget: &dyn Widget) -> Option<WidgetId> {
+    if widget.is_modal() {
+        return Some(widget.id());
+    }
+    widget
+        .children()
+        .iter()
+        .rev()
+        .find_map(|child| find_modal(child.as_ref()))
+}
+
+/// Widgets with an open overlay, topmost (painted last) first.
+///
+/// Open modals are left out; collect from the modal itself instead.
+fn collect_overlays(widget: &dyn Widget, overlays: &mut Vec<WidgetId>) {
+    for child in widget.children().iter().rev().filter(|c| !c.is_modal()) {
+        collect_overlays(child.as_ref(), overlays);
+    }
+    if widget.has_overlay() {
+        overlays.push(widget.id());
+    }
+}
+
+/// Register focusable widgets in tree (tab) order.
+pub(crate) fn collect_focusable(widget: &dyn Widget, focus: &mut FocusManager) {
+    if widget.focusable() {
+        focus.register_focusable(widget.id());
+    }
+    for child in widget.children() {
+        collect_focusable(child.as_ref(), focus);
+    }
+}
+
+/// Bind the shortcuts widgets take without focus to those widgets. Of two
+/// widgets wanting the same shortcut, the later one in tree order gets it.
+fn collect_shortcuts(widget: &dyn Widget, shortcuts: &mut ShortcutMap<WidgetId>) {
+    for shortcut in widget.shortcuts() {
+        shortcuts.bind(shortcut, widget.id());
+    }
+    for child in widget.children() {
+        collect_shortcuts(child.as_ref(), shortcuts);
+    }
+}
 
-            let response = widget.event(&mut ctx, event);
-            
-            // Update focus
-            if response.request_focus {
-                new_focus = Some(id);
-            } else if response.release_focus && new_focus == Some(id) {
-                new_focus = None;
-            }
+/// Ids from `widget` down to the topmost widget under `pos`.
+///
+/// Later children are on top of earlier ones. Scroll containers clip their
+/// content, and `pos` is shifted by their offset on the way down.
+fn hit_path(
+    widget: &dyn Widget,
+    layout_tree: &LayoutTree,
+    pos: glam::Vec2,
+) -> Option<Vec<WidgetId>> {
+    let bounds = layout_tree.get_absolute_layout(widget.id())?.bounds;
+    let mut content_pos = pos;
+    if let Some((x, y)) = widget
+        .scroll_offset()
+        .filter(|_| widget.is_scroll_container())
+    {
+        if !bounds.contains(pos) {
+            return None;
+        }
+        content_pos += glam::Vec2::new(x, y);
+    }
+    let child_path = widget
+        .children()
+        .iter()
+        .rev()
+        .filter(|child| !child.is_modal())
+        .find_map(|child| hit_path(child.as_ref(), layout_tree, content_pos));
+    match child_path {
+        Some(mut path) => {
+            path.insert(0, widget.id());
+            Some(path)
+        }
+        None => bounds.contains(pos).then(|| vec![widget.id()]),
+    }
+}
 
-            (response, new_focus)
+/// The widgets `event` bubbles through, outermost first: down to the topmost
+/// widget under the pointer for pointer events, and to the focused widget
+/// (or `scope` itself) for keys and text. `None` for events every widget sees.
+fn event_path(
+    scope: &dyn Widget,
+    layout_tree: &LayoutTree,
+    focused: Option<WidgetId>,
+    event: &InputEvent,
+) -> Option<Vec<WidgetId>> {
+    match event {
+        InputEvent::PointerMove { pos, .. }
+        | InputEvent::PointerDown { pos, .. }
+        | InputEvent::PointerUp { pos, .. }
+        | InputEvent::Scroll { pos, .. } => {
+            Some(hit_path(scope, layout_tree, *pos).unwrap_or_default())
         }
+        InputEvent::KeyDown { .. }
+        | InputEvent::KeyUp { .. }
+        | InputEvent::TextInput { .. }
+        | InputEvent::Paste { .. }
+        | InputEvent::Composition { .. } => Some(
+            focused
+                .and_then(|id| widget_path(scope, id))
+                .unwrap_or_else(|| vec![scope.id()]),
+        ),
+        _ => None,
+    }
+}
 
-        let current_focus = state.focus_manager.focused();
-        let (response, new_focus) = dispatch_event(
-            state.root_widget.as_mut(),
-            &state.layout_tree,
-            current_focus,
-            &event,
-        );
+/// The widgets told that `pointer`'s press was cancelled, innermost first:
+/// the one holding its capture, or else those under the mouse or first
+/// finger.
+fn cancel_targets(
+    capture: Option<WidgetId>,
+    hovered: &[WidgetId],
+    pointer: Pointer,
+) -> Vec<WidgetId> {
+    match capture {
+        Some(target) => vec![target],
+        None if pointer.primary => hovered.iter().rev().copied().collect(),
+        None => Vec::new(),
+    }
+}
 
-        // Update focus manager
-        if let Some(fid) = new_focus {
-            state.focus_manager.set_focus(fid);
-        } else if current_focus.is_some() && new_focus.is_none() {
-            state.focus_manager.clear_focus();
+/// Deliver `event` along `path` from the innermost widget outwards, until
+/// one handles it.
+///
+/// The innermost widget is the target; the rest see the event bubble. Each
+/// widget gets positions in its own layout space, past the offsets of the
+/// scroll containers above it. The innermost widget asking for a cursor wins.
+fn bubble_event(
+    root: &mut dyn Widget,
+    layout_tree: &LayoutTree,
+    theme: &Theme,
+    clipboard: &mut dyn ClipboardService,
+    announcements: &AnnouncementQueue,
+    focus: &mut FocusManager,
+    capture: &mut Option<WidgetId>,
+    path: &[WidgetId],
+    event: &InputEvent,
+) -> EventResponse {
+    let Some(&target) = path.last() else {
+        return EventResponse::default();
+    };
+    let offsets = path_scroll_offsets(root, path);
+    let mut response = EventResponse::default();
+    for (&id, offset) in path.iter().zip(offsets).rev() {
+        let Some(layout) = layout_tree.get_absolute_layout(id) else {
+            continue;
+        };
+        let Some(widget) = find_widget_mut(root, id) else {
+            continue;
+        };
+        let mut ctx = EventContext {
+            layout,
+            layout_tree,
+            focus: &mut *focus,
+            widget_id: id,
+            has_capture: *capture == Some(id),
+            target,
+            phase: if id == target {
+                EventPhase::Target
+            } else {
+                EventPhase::Bubble
+            },
+            theme,
+            clipboard: &mut *clipboard,
+            announcements,
+        };
+        let widget_response = widget.event(&mut ctx, &event.translated(offset));
+
+        if widget_response.capture_pointer {
+            *capture = Some(id);
         }
-
-        if response.repaint {
-            state.needs_repaint = true;
+        if widget_response.request_focus {
+            focus.set_focus(id);
+        } else if widget_response.release_focus && focus.has_focus(id) {
+            focus.clear_focus();
         }
-        if response.relayout {
-            state.needs_layout = true;
+        let cursor = response.cursor.or(widget_response.cursor);
+        response.merge(widget_response);
+        response.cursor = cursor;
+        if response.handled {
+            break;
         }
-        
-        // Request redraw if we need to repaint or relayout
-        if state.needs_repaint || state.needs_layout {
-            state.window.request_redraw();
+    }
+    response
+}
+
+/// For each widget on `path`, how far the scroll containers above it have
+/// scrolled: what to add to a window position to reach its layout space.
+fn path_scroll_offsets(root: &dyn Widget, path: &[WidgetId]) -> Vec<glam::Vec2> {
+    let mut offset = glam::Vec2::ZERO;
+    path.iter()
+        .map(|&id| {
+            let above = offset;
+            let scroll = find_widget(root, id)
+                .filter(|widget| widget.is_scroll_container())
+                .and_then(|widget| widget.scroll_offset());
+            if let Some((x, y)) = scroll {
+                offset += glam::Vec2::new(x, y);
+            }
+            above
+        })
+        .collect()
+}
+
+/// How far the scroll containers above `target` have scrolled.
+fn scroll_offset_above(root: &dyn Widget, target: WidgetId) -> glam::Vec2 {
+    widget_path(root, target)
+        .and_then(|path| path_scroll_offsets(root, &path).pop())
+        .unwrap_or_default()
+}
+
+/// Ids from the root down to `target`, inclusive.
+fn widget_path(widget: &dyn Widget, target: WidgetId) -> Option<Vec<WidgetId>> {
+    if widget.id() == target {
+        return Some(vec![target]);
+    }
+    widget.children().iter().find_map(|child| {
+        let mut path = widget_path(child.as_ref(), target)?;
+        path.insert(0, widget.id());
+        Some(path)
+    })
+}
+
+/// Map the kind of pointer winit reports to the one carried on pointer events.
+fn pointer_from_winit(kind: winit::event::PointerKind, primary: bool) -> Pointer {
+    match kind {
+        winit::event::PointerKind::Touch(finger) => {
+            Pointer::touch(finger.into_raw() as u64, primary)
         }
+        winit::event::PointerKind::TabletTool(_) => Pointer::pen(),
+        winit::event::PointerKind::Mouse | winit::event::PointerKind::Unknown => Pointer::MOUSE,
+    }
+}
+
+/// Map winit's modifier state to the one carried on key events.
+fn modifiers_from_winit(state: winit::keyboard::ModifiersState) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    modifiers.set(Modifiers::SHIFT, state.shift_key());
+    modifiers.set(Modifiers::CONTROL, state.control_key());
+    modifiers.set(Modifiers::ALT, state.alt_key());
+    modifiers.set(Modifiers::META, state.meta_key());
+    modifiers
+}
+
+/// Map winit's system appearance to a theme mode.
+fn theme_mode_from_winit(theme: winit::window::Theme) -> ThemeMode {
+    match theme {
+        winit::window::Theme::Light => ThemeMode::Light,
+        winit::window::Theme::Dark => ThemeMode::Dark,
+    }
+}
+
+/// Map a widget's pointer shape to winit's.
+fn cursor_to_winit(cursor: CursorIcon) -> winit::cursor::CursorIcon {
+    use winit::cursor::CursorIcon as Winit;
+    match cursor {
+        CursorIcon::Default => Winit::Default,
+        CursorIcon::Pointer => Winit::Pointer,
+        CursorIcon::Text => Winit::Text,
+        CursorIcon::ColResize => Winit::ColResize,
+        CursorIcon::RowResize => Winit::RowResize,
+        CursorIcon::Grab => Winit::Grab,
+        CursorIcon::Grabbing => Winit::Grabbing,
+        CursorIcon::NotAllowed => Winit::NotAllowed,
     }
 }
 
-impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for AppRunner<F> {
+/// Scale a computed layout by a uniform factor.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn scale_layout(layout: &spark_layout::ComputedLayout, factor: f32) -> spark_layout::ComputedLayout {
+    spark_layout::ComputedLayout::new(layout.bounds.scale(factor))
+}
+
+impl<F: FnMut() -> Option<Box<dyn Widget>>> winit::application::ApplicationHandler
+    for AppRunner<F>
+{
     fn can_create_surfaces(&mut self, event_loop: &dyn winit::event_loop::ActiveEventLoop) {
-        let window = event_loop
-            .create_window(
-                winit::window::WindowAttributes::default()
-                    .with_title(&self.config.title)
-                    .with_surface_size(winit::dpi::LogicalSize::new(
-                        self.config.width,
-                        self.config.height,
-                    )),
-            )
-            .expect("create window");
-
-        let window_leaked: &'static mut Box<dyn winit::window::Window> =
-            Box::leak(Box::new(window));
-        let window: &'static dyn winit::window::Window = &**window_leaked;
+        // Called again when a mobile app resumes; keep the existing window
+        if let Some(state) = self.state.as_mut() {
+            state.needs_repaint = true;
+            state.request_redraw();
+            return;
+        }
+        let attributes = winit::window::WindowAttributes::default()
+            .with_title(&self.config.title)
+            // Hidden until the accessibility adapter is attached
+            .with_visible(false)
+            .with_surface_size(winit::dpi::LogicalSize::new(
+                self.config.width,
+                self.config.height,
+            ));
+        #[cfg(target_arch = "wasm32")]
+        let attributes = {
+            use winit::platform::web::WindowAttributesWeb;
+            let web = match &self.config.canvas_parent {
+                Some(parent) => match crate::web::mount_canvas(parent) {
+                    Ok(canvas) => WindowAttributesWeb::default().with_canvas(Some(canvas)),
+                    Err(err) => return self.fail(event_loop, err.into()),
+                },
+                // Without a parent the canvas takes over the page
+                None => WindowAttributesWeb::default().with_append(true),
+            };
+            attributes.with_platform_attributes(Box::new(web))
+        };
+        let window = match event_loop.create_window(attributes) {
+            Ok(window) => window,
+            Err(err) => return self.fail(event_loop, err.into()),
+        };
+
+        let window = Box::into_raw(window);
+        self.window = Some(window);
+        // SAFETY: the window is only freed in `shutdown`, after the state.
+        let window: &'static dyn winit::window::Window = unsafe { &*window };
+        let accessibility_adapter = AccessibilityAdapter::new(window, event_loop.create_proxy());
+
+        #[cfg(target_arch = "wasm32")]
+        let frame_scheduler = match crate::web::FrameScheduler::new(move || {
+            window.request_redraw()
+        }) {
+            Ok(scheduler) => Some(scheduler),
+            Err(err) => {
+                log::warn!("falling back to winit redraw scheduling: {err:?}");
+                None
+            }
+        };
+        let clipboard: Box<dyn ClipboardBackend> = Box::new(MemoryClipboard::default());
+        #[cfg(not(any(target_arch = "wasm32", target_os = "ios", target_os = "android")))]
+        let clipboard: Box<dyn ClipboardBackend> = match crate::clipboard::SystemClipboard::new() {
+            Ok(system) => Box::new(system),
+            Err(err) => {
+                log::warn!("using an in-app clipboard: {err}");
+                clipboard
+            }
+        };
+        #[cfg(target_arch = "wasm32")]
+        let clipboard: Box<dyn ClipboardBackend> = {
+            use winit::platform::web::WindowExtWeb;
+            let mut paste_targets: Vec<web_sys::EventTarget> = Vec::new();
+            if let Some(canvas) = window.canvas().map(|canvas| canvas.clone()) {
+                let proxy = event_loop.create_proxy();
+                match crate::web::CanvasWatcher::new(canvas.clone(), move || proxy.wake_up()) {
+                    Ok(watcher) => self.canvas_watcher = Some(watcher),
+                    Err(err) => log::warn!("failed to watch the canvas size: {err:?}"),
+                }
+                let proxy = event_loop.create_proxy();
+                match crate::web::PointerInput::new(canvas.clone(), move || proxy.wake_up()) {
+                    Ok(input) => self.pointer_input = Some(input),
+                    Err(err) => log::warn!("failed to listen for pointer events: {err:?}"),
+                }
+                let proxy = event_loop.create_proxy();
+                match crate::web::TextEntry::new(canvas.clone(), move || proxy.wake_up()) {
+                    Ok(entry) => {
+                        if let Some(input) = &self.pointer_input {
+                            input.set_text_target(Some(entry.element()));
+                        }
+                        paste_targets.push(entry.element().into());
+                        self.text_entry = Some(entry);
+                    }
+                    Err(err) => log::warn!("failed to add the text entry field: {err:?}"),
+                }
+                paste_targets.push(canvas.into());
+            }
+            let proxy = event_loop.create_proxy();
+            match crate::web::RouteWatcher::new(move || proxy.wake_up()) {
+                Ok(watcher) => self.route_watcher = Some(watcher),
+                Err(err) => log::warn!("failed to watch the page's route: {err:?}"),
+            }
+            let proxy = event_loop.create_proxy();
+            match crate::web::WebClipboard::new(paste_targets, move || proxy.wake_up()) {
+                Ok(web) => Box::new(web),
+                Err(err) => {
+                    log::warn!("using an in-app clipboard: {err:?}");
+                    clipboard
+                }
+            }
+        };
 
         // Initialize wgpu - use pollster on native, web handles this specially
-        let (device, queue, surface_state) = pollster::block_on(init_wgpu(window));
+        let (device, queue, surface_state) =
+            match pollster::block_on(init_wgpu(window, &self.config.wgpu)) {
+                Ok(gpu) => gpu,
+                Err(err) => return self.fail(event_loop, err.into()),
+            };
 
-        let renderer = Renderer::new(&device, surface_state.config.format);
+        let renderer = match Renderer::new(&device, surface_state.config.format) {
+            Ok(renderer) => renderer,
+            Err(err) => return self.fail(event_loop, err.into()),
+        };
         let text_system = TextSystem::new(&device);
+        self.watch_device_lost(&device, event_loop);
         let draw_list = DrawList::new();
         let layout_tree = LayoutTree::new();
         let focus_manager = FocusManager::new();
 
+        let theme_mode = if self.config.follow_system_theme {
+            window.theme().map_or(self.config.theme_mode, theme_mode_from_winit)
+        } else {
+            self.config.theme_mode
+        };
+        let theme = self.config.theme(theme_mode).clone();
+
         // Build the UI
-        let build_ui = self.build_ui.take().expect("build_ui already called");
-        let root_widget = build_ui();
+        let Some(root_widget) = (self.build_ui)() else {
+            return;
+        };
+
+        #[cfg(feature = "hot-reload")]
+        if self.reloadable && !self.config.watch_paths.is_empty() {
+            match crate::reload::FileWatcher::new(
+                &self.config.watch_paths,
+                self.reload_pending.clone(),
+                event_loop.create_proxy(),
+            ) {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(err) => log::warn!("failed to watch files for hot reload: {err}"),
+            }
+        }
+
+        let signals = SignalRuntime::new();
+        let proxy = event_loop.create_proxy();
+        signals.set_waker(move || proxy.wake_up());
+
+        #[cfg(target_os = "macos")]
+        if let Some(menu) = self.config.menu.take() {
+            let proxy = event_loop.create_proxy();
+            self.menu_bar = Some(crate::menu::install(
+                menu,
+                &self.config.title,
+                self.menu_commands.clone(),
+                move || proxy.wake_up(),
+            ));
+        }
+        #[cfg(not(target_os = "macos"))]
+        if self.config.menu.take().is_some() {
+            log::debug!("the menu bar is only shown on macOS");
+        }
 
-        let scale_factor = window.scale_factor() as f32;
+        let window_scale_factor = window.scale_factor() as f32;
+        let ui_zoom = self.config.ui_zoom;
+        let scale_factor = self
+            .config
+            .scale_factor_override
+            .unwrap_or(window_scale_factor)
+            * ui_zoom;
 
         self.state = Some(AppState {
             window,
@@ -519,8 +2846,33 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
             focus_manager,
             root_widget,
             start_time: Instant::now(),
+            #[cfg(target_arch = "wasm32")]
+            frame_scheduler,
             mouse_pos: glam::Vec2::ZERO,
             scale_factor,
+            window_scale_factor,
+            ui_zoom,
+            modifiers: Default::default(),
+            ime_allowed: false,
+            ime_cursor_area: None,
+            cursor: CursorIcon::Default,
+            pointer_capture: HashMap::new(),
+            hovered: Vec::new(),
+            clicks: ClickCounter::new(),
+            touch_pan: None,
+            pressed: Vec::new(),
+            theme,
+            accessibility: AccessibilityManager::new(),
+            accessibility_adapter,
+            clipboard,
+            signals,
+            widget_shortcuts: ShortcutMap::default(),
+            announcements: AnnouncementQueue::new(),
+            accessibility_needs_tree: true,
+            audit_issues: Vec::new(),
+            audit_contrast_pending: false,
+            modal: None,
+            focus_before_modal: None,
             needs_layout: true,
             needs_repaint: true,
             #[cfg(any(target_os = "macos", target_os = "ios"))]
@@ -529,30 +2881,21 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
 
         // Build initial layout (this registers native widgets)
         self.build_layout();
+        window.set_visible(true);
         
         // Embed native views into the window after layout
         // This must happen after layout so widgets are registered
         #[cfg(any(target_os = "macos", target_os = "ios"))]
         {
-            // Get all data we need before any mutable borrows
-            let (size, scale_factor, all_layouts) = {
-                let state = self.state.as_ref().unwrap();
-                let mut layouts = std::collections::HashMap::new();
-                state.layout_tree.traverse(|widget_id, computed, _depth| {
-                    layouts.insert(widget_id, *computed);
-                });
-                (state.surface_state.size, state.scale_factor, layouts)
-            };
-            
-            // Now get mutable access to manager
             if let Some(ref mut manager) = self.state.as_mut().unwrap().native_view_manager {
             // Embed native views into window - inline implementation
             use raw_window_handle::{HasWindowHandle, RawWindowHandle};
             use objc2::runtime::AnyObject;
-            use spark_native_apple::ffi::appkit::NSView;
             
             #[cfg(target_os = "macos")]
             {
+                use spark_native_apple::ffi::appkit::NSView;
+
                 // Get the raw window handle from winit
                 if let Ok(handle_ref) = window.window_handle() {
                      let raw_handle = handle_ref.as_raw();
@@ -564,7 +2907,7 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                             
                             if !content_view_ptr.is_null() {
                                 // Set the content view as the root view in the manager
-                                manager.set_root_view(spark_native_apple::NativeViewHandle::AppKit(content_view_ptr));
+                                manager.set_root_view(spark_native_apple::NativeViewHandle::from_ptr(content_view_ptr));
                                 
                                 // Create content view wrapper
                                 let content_view = NSView::from_ptr(content_view_ptr);
@@ -572,16 +2915,16 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                                 // Add all registered native views to the content view
                                 let view_count = manager.get_all_views().len();
                                 for view_handle in manager.get_all_views().values() {
-                                    let spark_native_apple::NativeViewHandle::AppKit(ptr) = view_handle;
-                                    // Create NSView wrapper from pointer
-                                    let native_view = NSView::from_ptr(*ptr);
+                                    // Create NSView wrapper from the handle
+                                    let native_view = NSView::from_ptr(view_handle.as_ptr());
                                     content_view.add_subview(&native_view);
-                                    native_view.set_visible(true);
                                     native_view.set_wants_layer(true);
-                                    // Bring to front to ensure visibility
-                                    native_view.bring_to_front();
                                 }
                                 
+                                // Move views inside native containers back into them
+                                manager.attach_children();
+                                manager.apply_stacking();
+
                                 eprintln!("Embedded {} native views into window content view", view_count);
                             } else {
                                 eprintln!("Warning: Content view is null, cannot embed native views");
@@ -594,24 +2937,34 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                     eprintln!("Warning: Failed to get raw window handle");
                 }
             }
-            
-                // Update layouts again now that views are embedded
-                // Filter layouts to only include registered native widgets
-                let native_widget_ids: Vec<_> = manager.get_all_views().keys().copied().collect();
-                let layouts: std::collections::HashMap<_, _> = all_layouts
-                    .into_iter()
-                    .filter(|(id, _)| native_widget_ids.contains(id))
-                    .collect();
-                
-                // Convert physical pixels to logical pixels for parent_height
-                // The layout was computed with logical pixels, so we need logical height
-                let window_height_logical = (size.height as f32) / scale_factor;
-                manager.update_layouts(
-                    &layouts,
-                    window_height_logical,
-                    scale_factor,
-                );
+
+            #[cfg(target_os = "ios")]
+            {
+                use spark_native_apple::ffi::uikit::UIView;
+
+                match window.window_handle().map(|handle| handle.as_raw()) {
+                    Ok(RawWindowHandle::UiKit(handle)) => {
+                        let root_ptr = handle.ui_view.as_ptr() as *mut AnyObject;
+                        // SAFETY: the window's root view lives as long as the window.
+                        let root_view = unsafe { UIView::from_ptr(root_ptr) };
+                        manager.set_root_view(root_view.handle());
+                        for view_handle in manager.get_all_views().values() {
+                            // SAFETY: registered handles keep their views alive.
+                            let native_view = unsafe { UIView::from_ptr(view_handle.as_ptr()) };
+                            root_view.add_subview(&native_view);
+                        }
+                        // Move views inside native containers back into them
+                        manager.attach_children();
+                        manager.apply_stacking();
+                    }
+                    Ok(_) => log::warn!("window handle is not a UIKit handle"),
+                    Err(err) => log::warn!("failed to get the window handle: {err}"),
+                }
+            }
             }
+
+            // Update layouts again now that views are embedded
+            self.state.as_mut().unwrap().update_native_frames();
         }
     }
 
@@ -622,11 +2975,10 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
         event: WindowEvent,
     ) {
         match event {
-            WindowEvent::CloseRequested => {
-                event_loop.exit();
-            }
+            WindowEvent::CloseRequested => self.close(event_loop),
             WindowEvent::SurfaceResized(size) => {
                 if let Some(state) = self.state.as_mut() {
+                    state.accessibility_adapter.set_window_bounds(state.window);
                     if size.width > 0 && size.height > 0 {
                         state
                             .surface_state
@@ -635,61 +2987,172 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                     }
                 }
             }
-            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                surface_size_writer,
+            } => {
                 if let Some(state) = self.state.as_mut() {
-                    state.scale_factor = scale_factor as f32;
+                    // Winit picks the new physical size; match the surface to it
+                    // now rather than waiting for a separate resize event.
+                    if let Ok(size) = surface_size_writer.surface_size() {
+                        if size.width > 0 && size.height > 0 {
+                            state
+                                .surface_state
+                                .resize(&state.device, size.width, size.height);
+                        }
+                    }
+                    state.window_scale_factor = scale_factor as f32;
+                    state.update_scale_factor(&self.config);
+                    // Glyphs cached at the old pixel size won't be reused.
+                    state.text_system.clear_glyph_cache();
+                    // Native frames depend on the window scale even when the
+                    // effective scale is pinned by an override.
                     state.needs_layout = true;
+                    state.request_redraw();
+                }
+            }
+            WindowEvent::ThemeChanged(theme) => {
+                if let Some(state) = self.state.as_mut() {
+                    if self.config.follow_system_theme {
+                        state.set_theme_mode(&self.config, theme_mode_from_winit(theme));
+                    }
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                if let Some(state) = self.state.as_mut() {
+                    state.modifiers = modifiers.state();
                 }
             }
-            WindowEvent::PointerMoved { position, .. } => {
+            WindowEvent::PointerMoved {
+                position,
+                primary,
+                source,
+                ..
+            } => {
+                // Read from the canvas instead, along with touches
+                #[cfg(target_arch = "wasm32")]
+                if self.pointer_input.is_some() {
+                    return;
+                }
                 // Convert physical pixels to logical pixels for event handling
                 let scale_factor = self.state.as_ref().map(|s| s.scale_factor).unwrap_or(1.0);
-                let pos = glam::Vec2::new(
-                    position.x as f32 / scale_factor,
-                    position.y as f32 / scale_factor,
-                );
-                if let Some(s) = self.state.as_mut() {
+                let pos = dpi::logical_position(position, scale_factor);
+                let pointer = pointer_from_winit(source.into(), primary);
+                if let Some(s) = self.state.as_mut().filter(|_| !pointer.is_touch()) {
                     s.mouse_pos = pos;
                 }
-                self.handle_event(InputEvent::PointerMove { pos });
+                self.pointer_moved(pos, pointer);
             }
             WindowEvent::PointerButton {
                 state: btn_state,
+                position,
+                primary,
                 button,
                 ..
             } => {
-                let pos = self.state.as_ref().map(|s| s.mouse_pos).unwrap_or_default();
-                let button = match button {
-                    winit::event::ButtonSource::Mouse(mb) => match mb {
-                        winit::event::MouseButton::Left => PointerButton::Primary,
-                        winit::event::MouseButton::Right => PointerButton::Secondary,
-                        winit::event::MouseButton::Middle => PointerButton::Auxiliary,
-                        _ => PointerButton::Primary,
-                    },
-                    _ => PointerButton::Primary,
+                #[cfg(target_arch = "wasm32")]
+                if self.pointer_input.is_some() {
+                    return;
+                }
+                let scale_factor = self.state.as_ref().map(|s| s.scale_factor).unwrap_or(1.0);
+                let pos = dpi::logical_position(position, scale_factor);
+                // Taps press the primary button, like a left click
+                let (button, pointer) = match button {
+                    winit::event::ButtonSource::Mouse(mb) => {
+                        let button = match mb {
+                            winit::event::MouseButton::Left => PointerButton::Primary,
+                            winit::event::MouseButton::Right => PointerButton::Secondary,
+                            winit::event::MouseButton::Middle => PointerButton::Auxiliary,
+                            _ => PointerButton::Primary,
+                        };
+                        (button, Pointer::MOUSE)
+                    }
+                    winit::event::ButtonSource::Touch { finger_id, .. } => {
+                        let kind = winit::event::PointerKind::Touch(finger_id);
+                        (PointerButton::Primary, pointer_from_winit(kind, primary))
+                    }
+                    winit::event::ButtonSource::TabletTool { .. } => {
+                        (PointerButton::Primary, Pointer::pen())
+                    }
+                    winit::event::ButtonSource::Unknown(_) => {
+                        (PointerButton::Primary, Pointer::MOUSE)
+                    }
                 };
 
                 match btn_state {
                     winit::event::ElementState::Pressed => {
-                        self.handle_event(InputEvent::PointerDown { pos, button });
+                        self.pointer_pressed(pos, button, pointer);
                     }
                     winit::event::ElementState::Released => {
-                        self.handle_event(InputEvent::PointerUp { pos, button });
+                        self.pointer_released(pos, button, pointer);
                     }
                 }
             }
+            WindowEvent::PointerLeft { primary, kind, .. } => {
+                self.pointer_left(pointer_from_winit(kind, primary));
+            }
+            WindowEvent::DragEntered { position, .. } | WindowEvent::DragMoved { position } => {
+                let scale_factor = self.state.as_ref().map(|s| s.scale_factor).unwrap_or(1.0);
+                let pos = dpi::logical_position(position, scale_factor);
+                self.handle_event(InputEvent::FileHover { pos });
+            }
+            WindowEvent::DragLeft { .. } => {
+                self.handle_event(InputEvent::FileHoverCancelled);
+            }
+            WindowEvent::DragDropped { paths, position } => {
+                let scale_factor = self.state.as_ref().map(|s| s.scale_factor).unwrap_or(1.0);
+                let pos = dpi::logical_position(position, scale_factor);
+                let files = paths.into_iter().map(DroppedFile::from_path).collect();
+                self.handle_event(InputEvent::FileDrop { pos, files });
+            }
             WindowEvent::MouseWheel { delta, .. } => {
                 let pos = self.state.as_ref().map(|s| s.mouse_pos).unwrap_or_default();
                 let delta = match delta {
-                    winit::event::MouseScrollDelta::LineDelta(x, y) => glam::Vec2::new(x, y),
+                    winit::event::MouseScrollDelta::LineDelta(x, y) => {
+                        ScrollDelta::Lines(glam::Vec2::new(x, y))
+                    }
                     winit::event::MouseScrollDelta::PixelDelta(p) => {
-                        glam::Vec2::new(p.x as f32 / 20.0, p.y as f32 / 20.0)
+                        let scale_factor =
+                            self.state.as_ref().map(|s| s.scale_factor).unwrap_or(1.0);
+                        ScrollDelta::Pixels(dpi::logical_position(p, scale_factor))
                     }
                 };
-                self.handle_event(InputEvent::Scroll { pos, delta });
+                let delta = delta.scaled(self.config.wheel_sensitivity);
+                let scroll = InputEvent::scroll(pos, delta);
+                self.handle_event(scroll.with_modifiers(self.held_modifiers()));
             }
             WindowEvent::KeyboardInput { event, .. } => {
-                use spark_input::{ui_events::keyboard::Code, Key, KeyboardEvent, NamedKey};
+                use spark_input::ui_events::keyboard::Code;
+
+                if self.config.zoom_shortcuts && event.state.is_pressed() {
+                    let primary = self.held_modifiers().primary();
+                    if let Some(state) = self.state.as_mut() {
+                        if let (true, winit::keyboard::Key::Character(c)) =
+                            (primary, &event.logical_key)
+                        {
+                            let zoom = match c.as_str() {
+                                "=" | "+" => Some(state.ui_zoom + UI_ZOOM_STEP),
+                                "-" => Some(state.ui_zoom - UI_ZOOM_STEP),
+                                "0" => Some(1.0),
+                                _ => None,
+                            };
+                            if let Some(zoom) = zoom {
+                                state.set_ui_zoom(&self.config, zoom);
+                                return;
+                            }
+                        }
+                    }
+                }
+
+                if self.reloadable && event.state.is_pressed() && !event.repeat {
+                    let primary = self.held_modifiers().primary();
+                    if let (true, winit::keyboard::Key::Character(c)) = (primary, &event.logical_key) {
+                        if c.eq_ignore_ascii_case("r") {
+                            self.reload();
+                            return;
+                        }
+                    }
+                }
 
                 let key = match &event.logical_key {
                     winit::keyboard::Key::Character(c) => Key::Character(c.to_string()),
@@ -718,14 +3181,24 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                 // Use a generic code since we're translating from logical key
                 let code = Code::Unidentified;
 
-                let kb_event = if event.state.is_pressed() {
-                    KeyboardEvent::key_down(key.clone(), code)
+                let mut kb_event = if event.state.is_pressed() {
+                    KeyboardEvent::key_down(key, code)
                 } else {
                     KeyboardEvent::key_up(key, code)
                 };
+                if let Some(state) = self.state.as_ref() {
+                    kb_event.modifiers = modifiers_from_winit(state.modifiers);
+                }
 
                 if event.state.is_pressed() {
-                    self.handle_event(InputEvent::KeyDown { event: kb_event });
+                    if self.handle_shortcut(&kb_event) {
+                        return;
+                    }
+                    let key_down = InputEvent::KeyDown { event: kb_event };
+                    let response = self.handle_event(key_down.clone());
+                    if !response.handled {
+                        self.traverse_focus(&key_down);
+                    }
                 } else {
                     self.handle_event(InputEvent::KeyUp { event: kb_event });
                 }
@@ -740,15 +3213,30 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                     }
                 }
             }
+            WindowEvent::Moved(_) => {
+                if let Some(state) = self.state.as_mut() {
+                    state.accessibility_adapter.set_window_bounds(state.window);
+                }
+            }
             WindowEvent::Focused(focused) => {
+                if let Some(state) = self.state.as_mut() {
+                    state.accessibility_adapter.set_window_focused(focused);
+                }
                 if focused {
                     self.handle_event(InputEvent::FocusGained);
                 } else {
+                    // Presses in progress won't see their release
+                    let pressed = self.state.as_ref().map(|s| s.pressed.clone());
+                    for pointer in pressed.unwrap_or_default() {
+                        self.handle_event(InputEvent::pointer_cancel().with_pointer(pointer));
+                    }
                     self.handle_event(InputEvent::FocusLost);
                 }
             }
             WindowEvent::RedrawRequested => {
-                let state = self.state.as_mut().unwrap();
+                let Some(state) = self.state.as_mut() else {
+                    return;
+                };
 
                 if state.needs_layout {
                     self.build_layout();
@@ -756,7 +3244,16 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
 
                 let state = self.state.as_mut().unwrap();
                 if state.needs_repaint {
+                    // Follow GPU scroll offsets, which change without a relayout
+                    #[cfg(any(target_os = "macos", target_os = "ios"))]
+                    state.update_native_frames();
                     self.paint();
+                    #[cfg(target_arch = "wasm32")]
+                    self.update_pointer_input();
+                    // Announced while painting
+                    if !self.state.as_ref().unwrap().announcements.is_empty() {
+                        self.update_accessibility(false);
+                    }
                 }
 
                 let state = self.state.as_mut().unwrap();
@@ -768,7 +3265,8 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                     .set_viewport(size.width as f32, size.height as f32, state.scale_factor);
                 state
                     .renderer
-                    .set_time(state.start_time.elapsed().as_secs_f32());
+                    .set_time(state.elapsed_time());
+                state.renderer.set_cursor(state.mouse_pos);
 
                 // Prepare render
                 state.renderer.prepare(
@@ -778,33 +3276,11 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                     state.text_system.atlas(),
                 );
 
-                // Get frame
-                let frame = match state.surface_state.surface.get_current_texture() {
-                    Ok(frame) => frame,
-                    Err(_) => {
-                        state.surface_state.reconfigure(&state.device);
-                        state
-                            .surface_state
-                            .surface
-                            .get_current_texture()
-                            .unwrap()
-                    }
-                };
-
-                let view = frame
-                    .texture
-                    .create_view(&wgpu::TextureViewDescriptor::default());
-
-                let mut encoder = state
-                    .device
-                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                        label: Some("spark_encoder"),
-                    });
-
-                let bg = self.config.background;
-                state.renderer.render(
-                    &mut encoder,
-                    &view,
+                let bg = self.config.background.unwrap_or(state.theme.colors.background);
+                let result = state.renderer.render_to_surface(
+                    &state.device,
+                    &state.queue,
+                    &mut state.surface_state,
                     wgpu::Color {
                         r: bg.r as f64,
                         g: bg.g as f64,
@@ -813,15 +3289,430 @@ impl<F: FnOnce() -> Box<dyn Widget>> winit::application::ApplicationHandler for
                     },
                 );
 
-                state.queue.submit(Some(encoder.finish()));
-                frame.present();
+                match result {
+                    Ok(()) => self.device_recoveries.frame_presented(),
+                    // Transient: skip this frame and try again on the next redraw.
+                    Err(
+                        err @ RenderError::Surface(
+                            SurfaceError::Timeout | SurfaceError::Outdated | SurfaceError::Lost,
+                        ),
+                    ) => {
+                        let Some(state) = self.state.as_ref() else {
+                            return;
+                        };
+                        state.request_redraw();
+                        // Outdated frames are routine while resizing; only
+                        // bother the app once recovery stops working
+                        let recreate_failed = state.surface_state.recreate_failed();
+                        if recreate_failed {
+                            self.report_error(&AppError::from(err));
+                        } else {
+                            log::debug!("skipping frame: {err}");
+                        }
+                    }
+                    Err(err) => self.fail(event_loop, err.into()),
+                }
             }
             _ => {}
         }
     }
 
-    fn about_to_wait(&mut self, _event_loop: &dyn winit::event_loop::ActiveEventLoop) {
-        // Request redraw for animation
-        // In a real app, you'd only request this when needed
+    fn proxy_wake_up(&mut self, event_loop: &dyn winit::event_loop::ActiveEventLoop) {
+        self.recover_device(event_loop);
+        self.handle_accessibility_requests();
+        self.handle_menu_commands(event_loop);
+        #[cfg(target_arch = "wasm32")]
+        self.apply_canvas_change();
+        #[cfg(target_arch = "wasm32")]
+        self.handle_web_input();
+        #[cfg(target_arch = "wasm32")]
+        self.apply_route_change();
+
+        #[cfg(feature = "hot-reload")]
+        if self
+            .reload_pending
+            .swap(false, std::sync::atomic::Ordering::Relaxed)
+        {
+            self.reload();
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &dyn winit::event_loop::ActiveEventLoop) {
+        // Requests from callbacks that run outside handle_event, like native
+        // controls', are picked up here
+        if let Some(mode) = self.handle.take_theme_mode() {
+            if let Some(state) = self.state.as_mut() {
+                state.set_theme_mode(&self.config, mode);
+            }
+        }
+        if let Some(text) = self.handle.take_clipboard_text() {
+            if let Some(state) = self.state.as_mut() {
+                state.clipboard.set_text(text);
+            }
+        }
+        self.run_posted_callbacks();
+        let announcements = self.handle.take_announcements();
+        if let Some(state) = self.state.as_mut().filter(|_| !announcements.is_empty()) {
+            for announcement in announcements {
+                state
+                    .announcements
+                    .announce(announcement.text, announcement.politeness);
+            }
+            self.update_accessibility(false);
+        }
+        // Read by widgets' paste requests, or later by the web clipboard
+        self.deliver_pasted_text();
+        // Signals set by handlers, native callbacks or other threads
+        if let Some(state) = self.state.as_mut() {
+            if !state.signals.take_dirty().is_empty() {
+                state.needs_layout = true;
+                state.needs_repaint = true;
+                state.request_redraw();
+            }
+        }
+        if std::mem::take(&mut self.exit_requested) || self.handle.take_exit() {
+            self.close(event_loop);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_layout::taffy;
+    use spark_widgets::{Button, Container, MenuBar, Scroll, TextInput};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    type Log = Rc<RefCell<Vec<(WidgetId, WidgetId, EventPhase)>>>;
+
+    /// A fixed-size widget that records the presses it sees.
+    struct Probe {
+        id: WidgetId,
+        size: f32,
+        handles: bool,
+        children: Vec<Box<dyn Widget>>,
+        log: Log,
+    }
+
+    impl Probe {
+        fn new(size: f32, handles: bool, log: &Log) -> Self {
+            Self {
+                id: WidgetId::default(),
+                size,
+                handles,
+                children: Vec::new(),
+                log: log.clone(),
+            }
+        }
+
+        fn child(mut self, child: Probe) -> Self {
+            self.children.push(Box::new(child));
+            self
+        }
+    }
+
+    impl Widget for Probe {
+        fn id(&self) -> WidgetId {
+            self.id
+        }
+
+        fn set_id(&mut self, id: WidgetId) {
+            self.id = id;
+        }
+
+        fn style(&self, _theme: &Theme) -> taffy::Style {
+            taffy::Style {
+                size: taffy::Size {
+                    width: taffy::Dimension::length(self.size),
+                    height: taffy::Dimension::length(self.size),
+                },
+                ..Default::default()
+            }
+        }
+
+        fn paint(&self, _ctx: &mut PaintContext) {}
+
+        fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+            if let InputEvent::PointerDown { .. } = event {
+                self.log
+                    .borrow_mut()
+                    .push((self.id, ctx.target_id(), ctx.phase()));
+                if self.handles {
+                    return EventResponse::capture();
+                }
+            }
+            EventResponse::default()
+        }
+
+        fn children(&self) -> &[Box<dyn Widget>] {
+            &self.children
+        }
+
+        fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+            &mut self.children
+        }
+    }
+
+    fn lay_out(widget: &mut dyn Widget, width: f32, height: f32) -> LayoutTree {
+        fn add(widget: &mut dyn Widget, tree: &mut LayoutTree, in_scroll: bool) -> WidgetId {
+            let mut style = widget.style(&Theme::light());
+            if in_scroll {
+                style.flex_shrink = 0.0;
+            }
+            let is_scroll = widget.is_scroll_container();
+            let children: Vec<_> = widget
+                .children_mut()
+                .iter_mut()
+                .map(|child| add(child.as_mut(), tree, in_scroll || is_scroll))
+                .collect();
+            let id = tree.new_with_children(style, &children);
+            widget.set_id(id);
+            id
+        }
+
+        let mut tree = LayoutTree::new();
+        let root = add(widget, &mut tree, false);
+        tree.set_root(root);
+        tree.compute_layout(width, height);
+        tree
+    }
+
+    fn dispatch(
+        root: &mut dyn Widget,
+        layout_tree: &LayoutTree,
+        capture: &mut Option<WidgetId>,
+        event: InputEvent,
+    ) -> EventResponse {
+        let path = event_path(&*root, layout_tree, None, &event).unwrap();
+        let mut focus = FocusManager::new();
+        let theme = Theme::light();
+        let mut clipboard = MemoryClipboard::default();
+        bubble_event(
+            root,
+            layout_tree,
+            &theme,
+            &mut clipboard,
+            &AnnouncementQueue::new(),
+            &mut focus,
+            capture,
+            &path,
+            &event,
+        )
+    }
+
+    /// Deliver `event` to `target` alone, the way `AppRunner::dispatch_to` does.
+    fn deliver(
+        root: &mut dyn Widget,
+        layout_tree: &LayoutTree,
+        target: WidgetId,
+        event: &InputEvent,
+    ) -> EventResponse {
+        let mut focus = FocusManager::new();
+        let mut clipboard = MemoryClipboard::default();
+        let mut ctx = EventContext {
+            layout: layout_tree.get_absolute_layout(target).unwrap(),
+            layout_tree,
+            focus: &mut focus,
+            widget_id: target,
+            has_capture: true,
+            target,
+            phase: EventPhase::Target,
+            theme: &Theme::light(),
+            clipboard: &mut clipboard,
+            announcements: &AnnouncementQueue::new(),
+        };
+        let widget = find_widget_mut(root, target).unwrap();
+        widget.event(&mut ctx, event)
+    }
+
+    #[test]
+    fn test_focus_loss_mid_press_cancels_without_a_click() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let mut button = {
+            let clicks = clicks.clone();
+            Button::new("Save").on_click(move || {
+                clicks.fetch_add(1, Ordering::Relaxed);
+            })
+        };
+        let tree = lay_out(&mut button, 200.0, 200.0);
+        let pos = glam::Vec2::new(10.0, 10.0);
+        let mut capture = None;
+        let press = InputEvent::pointer_down(pos, PointerButton::Primary);
+        dispatch(&mut button, &tree, &mut capture, press);
+        assert_eq!(capture, Some(button.id()));
+
+        // The window loses focus; the button holding the capture hears of it
+        let targets = cancel_targets(capture.take(), &[], Pointer::MOUSE);
+        assert_eq!(targets, [button.id()]);
+        let cancel = InputEvent::pointer_cancel();
+        deliver(&mut button, &tree, targets[0], &cancel);
+
+        // A release arriving anyway is not a click
+        let release = InputEvent::pointer_up(pos, PointerButton::Primary);
+        dispatch(&mut button, &tree, &mut capture, release);
+        assert_eq!(clicks.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_pointer_events_bubble_from_the_topmost_hit() {
+        let log = Log::default();
+        let mut card = Probe::new(200.0, false, &log).child(Probe::new(50.0, false, &log));
+        let tree = lay_out(&mut card, 200.0, 200.0);
+        let (card_id, child_id) = (card.id(), card.children[0].id());
+        let mut capture = None;
+        let press = |x, y| InputEvent::pointer_down(glam::Vec2::new(x, y), PointerButton::Primary);
+
+        // Unhandled by the child, so the card sees it bubble
+        let response = dispatch(&mut card, &tree, &mut capture, press(10.0, 10.0));
+        assert!(!response.handled);
+        assert_eq!(
+            log.take(),
+            [
+                (child_id, child_id, EventPhase::Target),
+                (card_id, child_id, EventPhase::Bubble),
+            ]
+        );
+
+        // The card's own background
+        dispatch(&mut card, &tree, &mut capture, press(150.0, 150.0));
+        assert_eq!(log.take(), [(card_id, card_id, EventPhase::Target)]);
+
+        // Handled by the child, which stops it bubbling
+        card.children = vec![Box::new(Probe::new(50.0, true, &log))];
+        let tree = lay_out(&mut card, 200.0, 200.0);
+        let child_id = card.children[0].id();
+        let response = dispatch(&mut card, &tree, &mut capture, press(10.0, 10.0));
+        assert!(response.handled);
+        assert_eq!(log.take(), [(child_id, child_id, EventPhase::Target)]);
+        assert_eq!(capture, Some(child_id));
+    }
+
+    #[test]
+    fn test_scroll_bubbles_past_a_scroll_that_cannot_move() {
+        let mut outer = Scroll::new().height(100.0).content(
+            Container::new()
+                .column()
+                .child(
+                    Scroll::new()
+                        .height(50.0)
+                        .content(Container::new().size(100.0, 20.0)),
+                )
+                .child(Container::new().size(100.0, 300.0)),
+        );
+        let tree = lay_out(&mut outer, 200.0, 100.0);
+        let lines = ScrollDelta::Lines(glam::Vec2::new(0.0, -1.0));
+        let wheel = InputEvent::scroll(glam::Vec2::new(10.0, 10.0), lines);
+
+        let response = dispatch(&mut outer, &tree, &mut None, wheel);
+        assert!(response.handled);
+        let inner = &outer.children()[0].children()[0];
+        assert_eq!(inner.scroll_offset(), Some((0.0, 0.0)));
+        assert_eq!(outer.offset(), (0.0, 20.0));
+    }
+
+    #[test]
+    fn test_wheel_lines_use_the_line_height_and_pixels_apply_as_is() {
+        let mut scroll = Scroll::new()
+            .height(100.0)
+            .line_height(40.0)
+            .content(Container::new().size(100.0, 500.0));
+        let tree = lay_out(&mut scroll, 200.0, 100.0);
+        let wheel = |delta| InputEvent::scroll(glam::Vec2::new(10.0, 10.0), delta);
+
+        let notch = ScrollDelta::Lines(glam::Vec2::new(0.0, -1.0));
+        dispatch(&mut scroll, &tree, &mut None, wheel(notch));
+        assert_eq!(scroll.offset(), (0.0, 40.0));
+
+        let swipe = ScrollDelta::Pixels(glam::Vec2::new(0.0, -7.5));
+        dispatch(&mut scroll, &tree, &mut None, wheel(swipe));
+        assert_eq!(scroll.offset(), (0.0, 47.5));
+
+        // Sensitivity scales both kinds alike
+        dispatch(&mut scroll, &tree, &mut None, wheel(notch.scaled(2.0)));
+        assert_eq!(scroll.offset(), (0.0, 127.5));
+    }
+
+    #[test]
+    fn test_text_input_copies_and_pastes_through_the_lent_clipboard() {
+        let mut input = TextInput::new().value("copied");
+        let mut layout_tree = LayoutTree::new();
+        let id = layout_tree.new_leaf(input.style(&Theme::light()));
+        input.set_id(id);
+        layout_tree.set_root(id);
+        layout_tree.compute_layout(200.0, 100.0);
+
+        let mut focus = FocusManager::new();
+        focus.set_focus(id);
+        let mut clipboard = MemoryClipboard::default();
+        let mapper = ActionMapper::new();
+        for action in [StandardAction::SelectAll, StandardAction::Copy, StandardAction::Paste] {
+            let shortcut = mapper.shortcut_for(action).unwrap();
+            let mut ctx = EventContext {
+                layout: layout_tree.get_absolute_layout(id).unwrap(),
+                layout_tree: &layout_tree,
+                focus: &mut focus,
+                widget_id: id,
+                has_capture: false,
+                target: id,
+                phase: EventPhase::Target,
+                theme: &Theme::light(),
+                clipboard: &mut clipboard,
+                announcements: &AnnouncementQueue::new(),
+            };
+            let event = InputEvent::KeyDown {
+                event: KeyboardEvent {
+                    key: shortcut.key.clone(),
+                    modifiers: shortcut.modifiers,
+                    ..Default::default()
+                },
+            };
+            assert!(input.event(&mut ctx, &event).handled);
+        }
+
+        // The paste request reads what the copy wrote
+        assert_eq!(clipboard.take_pasted(), vec!["copied".to_string()]);
+    }
+
+    #[test]
+    fn test_menu_mnemonics_reach_the_menu_bar_without_focus() {
+        let mut root = Container::new()
+            .child(Button::new("Save"))
+            .child(MenuBar::new().menu("File", |menu| menu.item("Open", || {})));
+        let tree = lay_out(&mut root, 400.0, 300.0);
+        let bar = Widget::children(&root)[1].id();
+        let mut shortcuts = ShortcutMap::default();
+        collect_shortcuts(&root, &mut shortcuts);
+
+        let press = KeyboardEvent {
+            key: Key::Character("f".into()),
+            modifiers: Modifiers::ALT,
+            ..Default::default()
+        };
+        assert_eq!(shortcuts.action_for(&press), Some(&bar));
+        let response = deliver(&mut root, &tree, bar, &InputEvent::KeyDown { event: press });
+        assert!(response.handled && response.request_focus);
+        let opened = Widget::children(&root)[1].accessibility_info().value;
+        assert_eq!(opened.as_deref(), Some("File"));
+    }
+
+    #[test]
+    fn test_device_recoveries_reset_after_stable_frames() {
+        let mut recoveries = DeviceRecoveries::default();
+        for _ in 0..MAX_DEVICE_RECOVERIES {
+            assert!(recoveries.record_loss());
+        }
+        // A stable stretch forgives the earlier losses
+        for _ in 0..STABLE_FRAMES_AFTER_RECOVERY {
+            recoveries.frame_presented();
+        }
+        for _ in 0..MAX_DEVICE_RECOVERIES {
+            assert!(recoveries.record_loss());
+        }
+        // Losses a few frames apart still give up
+        recoveries.frame_presented();
+        assert!(!recoveries.record_loss());
     }
 }
@@ -0,0 +1,193 @@
+//! Debug-time accessibility audit.
+//!
+//! Enabled with [`AppConfig::audit_accessibility`](crate::AppConfig::audit_accessibility).
+//! After each layout the app checks focusable widgets against a minimum
+//! target size, and after the next paint it checks painted text against the
+//! color painted behind it. Issues are logged once and outlined on screen.
+
+use spark_core::{Color, Rect, Vec2};
+use spark_layout::{LayoutTree, WidgetId};
+use spark_render::{DrawCommand, DrawList};
+use spark_widgets::Widget;
+
+/// Minimum width and height of focusable widgets, in logical pixels.
+pub(crate) const MIN_TARGET_SIZE: f32 = 24.0;
+/// Minimum contrast ratio for text (WCAG AA for body text).
+pub(crate) const MIN_TEXT_CONTRAST: f32 = 4.5;
+/// Rects with less opacity than this are not treated as text backgrounds.
+const MIN_BACKGROUND_ALPHA: f32 = 0.5;
+/// Outline color for flagged regions.
+const OVERLAY_COLOR: Color = Color::rgb(1.0, 0.0, 0.6);
+
+/// A problem found by the audit.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum AuditIssue {
+    /// A focusable widget smaller than [`MIN_TARGET_SIZE`] (bounds in logical pixels).
+    SmallTarget { widget: WidgetId, bounds: Rect },
+    /// Text below [`MIN_TEXT_CONTRAST`] against its background (bounds in physical pixels).
+    LowContrast { bounds: Rect, ratio: f32 },
+}
+
+impl AuditIssue {
+    /// Bounds of the flagged region in physical pixels.
+    fn physical_bounds(&self, scale_factor: f32) -> Rect {
+        match self {
+            Self::SmallTarget { bounds, .. } => bounds.scale(scale_factor),
+            Self::LowContrast { bounds, .. } => *bounds,
+        }
+    }
+
+    /// Log the issue as a warning.
+    pub(crate) fn log(&self) {
+        match self {
+            Self::SmallTarget { widget, bounds } => log::warn!(
+                "a11y audit: focusable widget {widget:?} is {:.0}x{:.0}, smaller than {MIN_TARGET_SIZE}x{MIN_TARGET_SIZE}",
+                bounds.width,
+                bounds.height,
+            ),
+            Self::LowContrast { bounds, ratio } => log::warn!(
+                "a11y audit: text at ({:.0}, {:.0}) has contrast {ratio:.2}:1, below {MIN_TEXT_CONTRAST}:1",
+                bounds.x,
+                bounds.y,
+            ),
+        }
+    }
+}
+
+/// Find focusable widgets smaller than the minimum target size.
+pub(crate) fn audit_targets(root: &dyn Widget, layout_tree: &LayoutTree) -> Vec<AuditIssue> {
+    fn visit(widget: &dyn Widget, layout_tree: &LayoutTree, issues: &mut Vec<AuditIssue>) {
+        if widget.focusable() {
+            if let Some(layout) = layout_tree.get_absolute_layout(widget.id()) {
+                let bounds = layout.bounds;
+                if bounds.width < MIN_TARGET_SIZE || bounds.height < MIN_TARGET_SIZE {
+                    issues.push(AuditIssue::SmallTarget {
+                        widget: widget.id(),
+                        bounds,
+                    });
+                }
+            }
+        }
+        for child in widget.children() {
+            visit(child.as_ref(), layout_tree, issues);
+        }
+    }
+
+    let mut issues = Vec::new();
+    visit(root, layout_tree, &mut issues);
+    issues
+}
+
+/// Find text runs with too little contrast against the rect painted behind them.
+///
+/// `background` is the window clear color, used where nothing opaque was painted.
+pub(crate) fn audit_contrast(commands: &[DrawCommand], background: Color) -> Vec<AuditIssue> {
+    let mut issues = Vec::new();
+    let mut translations: Vec<(f32, f32)> = Vec::new();
+    let mut offset = (0.0, 0.0);
+    let mut painted: Vec<(Rect, Color)> = Vec::new();
+
+    for command in commands {
+        match command {
+            DrawCommand::Rect { bounds, color, .. } if color.a >= MIN_BACKGROUND_ALPHA => {
+                let bounds = bounds.translate(Vec2::new(offset.0, offset.1));
+                painted.push((bounds, *color));
+            }
+            DrawCommand::Text { glyphs } => {
+                let Some(first) = glyphs.first() else {
+                    continue;
+                };
+                let (mut x0, mut y0) = (f32::INFINITY, f32::INFINITY);
+                let (mut x1, mut y1) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+                for glyph in glyphs {
+                    x0 = x0.min(glyph.pos[0]);
+                    y0 = y0.min(glyph.pos[1]);
+                    x1 = x1.max(glyph.pos[0] + glyph.size[0]);
+                    y1 = y1.max(glyph.pos[1] + glyph.size[1]);
+                }
+                let bounds = Rect::new(x0 + offset.0, y0 + offset.1, x1 - x0, y1 - y0);
+                let behind = painted
+                    .iter()
+                    .rev()
+                    .find(|(rect, _)| rect.contains(bounds.center()))
+                    .map_or(background, |(_, color)| *color);
+
+                let text = Color::from(first.color);
+                let ratio = blend(text, behind).contrast_ratio(behind);
+                if ratio < MIN_TEXT_CONTRAST {
+                    issues.push(AuditIssue::LowContrast { bounds, ratio });
+                }
+            }
+            DrawCommand::PushTranslation { offset: (dx, dy) } => {
+                translations.push(offset);
+                offset = (offset.0 + dx, offset.1 + dy);
+            }
+            DrawCommand::PopTranslation => {
+                offset = translations.pop().unwrap_or((0.0, 0.0));
+            }
+            _ => {}
+        }
+    }
+    issues
+}
+
+/// Composite a translucent color over an opaque one.
+fn blend(top: Color, bottom: Color) -> Color {
+    let a = top.a;
+    Color::rgb(
+        top.r * a + bottom.r * (1.0 - a),
+        top.g * a + bottom.g * (1.0 - a),
+        top.b * a + bottom.b * (1.0 - a),
+    )
+}
+
+/// Outline every flagged region.
+pub(crate) fn draw_overlay(draw_list: &mut DrawList, issues: &[AuditIssue], scale_factor: f32) {
+    for issue in issues {
+        let bounds = issue.physical_bounds(scale_factor);
+        draw_list.bordered_rect(bounds, Color::TRANSPARENT, 0.0, 2.0 * scale_factor, OVERLAY_COLOR);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_core::GlyphInstance;
+
+    fn text_at(x: f32, y: f32, color: Color) -> DrawCommand {
+        DrawCommand::Text {
+            glyphs: vec![GlyphInstance {
+                pos: [x, y],
+                size: [8.0, 12.0],
+                uv_pos: [0.0, 0.0],
+                uv_size: [0.0, 0.0],
+                color: color.to_array(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_low_contrast_text_is_flagged() {
+        let grey = Color::from_hex(0x9CA3AF);
+        let commands = [
+            DrawCommand::rect(Rect::new(0.0, 0.0, 100.0, 100.0), Color::WHITE),
+            text_at(10.0, 10.0, grey),
+            text_at(10.0, 40.0, Color::BLACK),
+        ];
+        let issues = audit_contrast(&commands, Color::WHITE);
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(issues[0], AuditIssue::LowContrast { bounds, .. } if bounds.y == 10.0));
+    }
+
+    #[test]
+    fn test_background_follows_translation() {
+        let commands = [
+            DrawCommand::PushTranslation { offset: (0.0, 200.0) },
+            DrawCommand::rect(Rect::new(0.0, 0.0, 100.0, 100.0), Color::BLACK),
+            DrawCommand::PopTranslation,
+            // White text over the translated black rect: fine.
+            text_at(10.0, 210.0, Color::WHITE),
+        ];
+        assert!(audit_contrast(&commands, Color::WHITE).is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Clipboard backends.
+//!
+//! The app keeps one [`ClipboardBackend`] and lends it to widgets as a
+//! [`ClipboardService`], then delivers whatever it reads back as
+//! `InputEvent::Paste`. Reads may finish later (the web's Clipboard API is
+//! promise-based), so backends buffer the text until the app takes it.
+
+use spark_widgets::ClipboardService;
+
+/// Platform clipboard access.
+///
+/// [`request_text`](ClipboardService::request_text) starts reading the
+/// clipboard; the result shows up in [`take_pasted`](Self::take_pasted),
+/// now or after a wake-up.
+pub(crate) trait ClipboardBackend: ClipboardService {
+    /// Take the text read since the last call.
+    fn take_pasted(&mut self) -> Vec<String>;
+}
+
+/// Clipboard kept inside the process.
+///
+/// Used where the system clipboard can't be opened, so copy and paste
+/// still work between the app's own widgets.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryClipboard {
+    text: Option<String>,
+    pasted: Vec<String>,
+}
+
+impl ClipboardService for MemoryClipboard {
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+
+    fn request_text(&mut self) {
+        if let Some(text) = &self.text {
+            self.pasted.push(text.clone());
+        }
+    }
+}
+
+impl ClipboardBackend for MemoryClipboard {
+    fn take_pasted(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pasted)
+    }
+}
+
+/// The desktop system clipboard, through arboard.
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios", target_os = "android")))]
+pub(crate) struct SystemClipboard {
+    clipboard: arboard::Clipboard,
+    pasted: Vec<String>,
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios", target_os = "android")))]
+impl SystemClipboard {
+    /// Open the system clipboard.
+    pub(crate) fn new() -> Result<Self, arboard::Error> {
+        Ok(Self {
+            clipboard: arboard::Clipboard::new()?,
+            pasted: Vec::new(),
+        })
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios", target_os = "android")))]
+impl ClipboardService for SystemClipboard {
+    fn set_text(&mut self, text: String) {
+        if let Err(err) = self.clipboard.set_text(text) {
+            log::warn!("failed to write the clipboard: {err}");
+        }
+    }
+
+    fn request_text(&mut self) {
+        match self.clipboard.get_text() {
+            Ok(text) => self.pasted.push(text),
+            // Empty, or holding something other than text
+            Err(arboard::Error::ContentNotAvailable) => {}
+            Err(err) => log::warn!("failed to read the clipboard: {err}"),
+        }
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios", target_os = "android")))]
+impl ClipboardBackend for SystemClipboard {
+    fn take_pasted(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pasted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_clipboard_round_trip() {
+        let mut clipboard = MemoryClipboard::default();
+        clipboard.request_text();
+        assert!(clipboard.take_pasted().is_empty());
+
+        clipboard.set_text("hello".into());
+        clipboard.request_text();
+        assert_eq!(clipboard.take_pasted(), vec!["hello".to_string()]);
+        assert!(clipboard.take_pasted().is_empty());
+    }
+}
@@ -0,0 +1,244 @@
+//! Stacking of native views relative to GPU content.
+//!
+//! Native views sit in layers above the GPU-rendered content view, so a GPU
+//! widget can never be drawn over one. Instead, native views are ordered
+//! among themselves by paint order, and a native view is hidden while an
+//! absolutely positioned GPU widget painted after it (a popup, modal or
+//! toast) overlaps its bounds.
+//!
+//! GPU scroll containers move their content at paint time, which the layout
+//! doesn't include, so native views inside them are moved by the enclosing
+//! scroll offsets and hidden once they scroll out of the viewport.
+//!
+//! Limitations:
+//! - covered views are hidden entirely, not clipped to the overlay;
+//! - translucent overlays hide what is below them instead of tinting it;
+//! - GPU widgets in normal flow that overlap a native view still draw
+//!   underneath it;
+//! - scroll offsets are ignored when testing for overlap;
+//! - a view partly scrolled out of a viewport is drawn whole, over the
+//!   content around the scroll container.
+
+use spark_core::{Rect, Vec2};
+use spark_layout::{taffy, LayoutTree, WidgetId};
+use spark_widgets::{Theme, Widget};
+use std::collections::{HashMap, HashSet};
+
+/// How native views should be stacked.
+#[derive(Debug, Default)]
+pub(crate) struct NativeStacking {
+    /// Native widgets in paint order, back to front.
+    pub order: Vec<WidgetId>,
+    /// Native widgets covered by a GPU overlay.
+    pub covered: HashSet<WidgetId>,
+}
+
+/// Work out paint order and coverage of the native widgets under `root`.
+pub(crate) fn native_stacking(
+    root: &dyn Widget,
+    layout_tree: &LayoutTree,
+    theme: &Theme,
+) -> NativeStacking {
+    fn visit(
+        widget: &dyn Widget,
+        layout_tree: &LayoutTree,
+        theme: &Theme,
+        natives: &mut Vec<(WidgetId, Rect)>,
+        stacking: &mut NativeStacking,
+    ) {
+        let bounds = layout_tree
+            .get_absolute_layout(widget.id())
+            .map(|layout| layout.bounds);
+        if widget.is_native() {
+            stacking.order.push(widget.id());
+            if let Some(bounds) = bounds {
+                natives.push((widget.id(), bounds));
+            }
+        } else if widget.style(theme).position == taffy::Position::Absolute {
+            // Painted after every native widget seen so far
+            if let Some(bounds) = bounds {
+                for (id, native_bounds) in natives.iter() {
+                    if native_bounds.intersects(&bounds) {
+                        stacking.covered.insert(*id);
+                    }
+                }
+            }
+        }
+        for child in widget.children() {
+            visit(child.as_ref(), layout_tree, theme, natives, stacking);
+        }
+    }
+
+    let mut stacking = NativeStacking::default();
+    visit(root, layout_tree, theme, &mut Vec::new(), &mut stacking);
+    stacking
+}
+
+/// Where GPU scrolling puts a native widget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct NativePlacement {
+    /// Offset from the layout position, in logical pixels.
+    pub offset: (f32, f32),
+    /// Whether any of the widget is inside every enclosing scroll viewport.
+    pub visible: bool,
+}
+
+/// Work out where GPU scroll containers move the native widgets under `root`.
+///
+/// Widgets inside a native container are left out, since AppKit or UIKit
+/// scrolls and clips those.
+pub(crate) fn native_placements(
+    root: &dyn Widget,
+    layout_tree: &LayoutTree,
+) -> HashMap<WidgetId, NativePlacement> {
+    fn visit(
+        widget: &dyn Widget,
+        layout_tree: &LayoutTree,
+        scroll: (f32, f32),
+        clip: Option<Rect>,
+        placements: &mut HashMap<WidgetId, NativePlacement>,
+    ) {
+        let bounds = layout_tree
+            .get_absolute_layout(widget.id())
+            .map(|layout| layout.bounds.translate(Vec2::new(-scroll.0, -scroll.1)));
+        if widget.is_native() {
+            let visible = match (clip, bounds) {
+                (Some(clip), Some(bounds)) => clip.intersects(&bounds),
+                _ => true,
+            };
+            placements.insert(widget.id(), NativePlacement { offset: (-scroll.0, -scroll.1), visible });
+        }
+        if widget.native_document_view().is_some() {
+            return;
+        }
+
+        let (mut scroll, mut clip) = (scroll, clip);
+        if widget.is_scroll_container() {
+            if let Some(bounds) = bounds {
+                // An empty viewport hides everything inside it
+                clip = Some(match clip {
+                    Some(clip) => clip.intersection(&bounds).unwrap_or(Rect::ZERO),
+                    None => bounds,
+                });
+            }
+            let (scroll_x, scroll_y) = widget.scroll_offset().unwrap_or_default();
+            scroll = (scroll.0 + scroll_x, scroll.1 + scroll_y);
+        }
+        for child in widget.children() {
+            visit(child.as_ref(), layout_tree, scroll, clip, placements);
+        }
+    }
+
+    let mut placements = HashMap::new();
+    visit(root, layout_tree, (0.0, 0.0), None, &mut placements);
+    placements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_widgets::{Container, PaintContext, Scroll};
+
+    /// Stand-in for a native control.
+    struct FakeNative {
+        id: WidgetId,
+    }
+
+    impl Widget for FakeNative {
+        fn id(&self) -> WidgetId {
+            self.id
+        }
+
+        fn set_id(&mut self, id: WidgetId) {
+            self.id = id;
+        }
+
+        fn style(&self, _theme: &Theme) -> taffy::Style {
+            use taffy::prelude::*;
+            taffy::Style {
+                size: Size {
+                    width: length(100.0),
+                    height: length(30.0),
+                },
+                flex_shrink: 0.0,
+                ..Default::default()
+            }
+        }
+
+        fn paint(&self, _ctx: &mut PaintContext) {}
+
+        fn is_native(&self) -> bool {
+            true
+        }
+    }
+
+    fn build(widget: &mut dyn Widget, tree: &mut LayoutTree) -> WidgetId {
+        let children: Vec<_> = widget
+            .children_mut()
+            .iter_mut()
+            .map(|child| build(child.as_mut(), tree))
+            .collect();
+        let id = if children.is_empty() {
+            tree.new_leaf(widget.style(&Theme::light()))
+        } else {
+            tree.new_with_children(widget.style(&Theme::light()), &children)
+        };
+        widget.set_id(id);
+        id
+    }
+
+    #[test]
+    fn test_overlay_covers_earlier_native_widgets() {
+        let native = || FakeNative { id: WidgetId::default() };
+        let mut root = Container::new()
+            .size(400.0, 400.0)
+            .child(native())
+            .child(Container::new().height(200.0))
+            .child(native())
+            // Overlaps the first native widget only
+            .child(Container::new().absolute(0.0, 0.0).size(200.0, 100.0))
+            // Painted above the overlay, so never covered
+            .child(native());
+        let mut tree = LayoutTree::new();
+        let root_id = build(&mut root, &mut tree);
+        tree.set_root(root_id);
+        tree.compute_layout(400.0, 400.0);
+
+        let stacking = native_stacking(&root, &tree, &Theme::light());
+        let ids: Vec<WidgetId> = [0, 2, 4]
+            .into_iter()
+            .map(|index| Widget::children(&root)[index].id())
+            .collect();
+        assert_eq!(stacking.order, ids);
+        assert_eq!(stacking.covered, HashSet::from([ids[0]]));
+    }
+
+    #[test]
+    fn test_scrolled_native_widgets_move_and_hide() {
+        let native = || FakeNative { id: WidgetId::default() };
+        let content = Container::new()
+            .child(native())
+            .child(Container::new().height(150.0).flex_shrink(0.0))
+            .child(native());
+        let mut root = Container::new()
+            .size(400.0, 400.0)
+            .child(Scroll::new().size(200.0, 100.0).content(content))
+            .child(native());
+        Widget::children_mut(&mut root)[0].set_scroll_offset(0.0, 120.0);
+        let mut tree = LayoutTree::new();
+        let root_id = build(&mut root, &mut tree);
+        tree.set_root(root_id);
+        tree.compute_layout(400.0, 400.0);
+
+        let placements = native_placements(&root, &tree);
+        let scroll = &Widget::children(&root)[0];
+        let content = &Widget::children(scroll.as_ref())[0];
+        let inside = |index: usize| placements[&Widget::children(content.as_ref())[index].id()];
+        // Scrolled above the viewport
+        assert_eq!(inside(0), NativePlacement { offset: (0.0, -120.0), visible: false });
+        // Scrolled into view: 180 - 120 is inside the 100 high viewport
+        assert_eq!(inside(2), NativePlacement { offset: (0.0, -120.0), visible: true });
+        let outside = placements[&Widget::children(&root)[1].id()];
+        assert_eq!(outside, NativePlacement { offset: (0.0, 0.0), visible: true });
+    }
+}
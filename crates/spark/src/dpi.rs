@@ -0,0 +1,79 @@
+//! Conversions between logical, physical and native coordinates.
+//!
+//! Layout runs in logical pixels, the renderer draws in physical pixels, and
+//! native AppKit/UIKit views are positioned in window points (logical pixels
+//! at the window's own scale factor, ignoring UI zoom and overrides).
+//!
+//! Manual test plan for moving a window between a 1x and a 2x display:
+//! 1. Run `kitchen-sink` on the 1x display and drag it to the 2x display.
+//! 2. Text should stay sharp (no blurry upscaled glyphs) and the same
+//!    logical size; the window should keep its logical size.
+//! 3. Hover and click buttons near the bottom-right corner to check that
+//!    hit testing still lines up with what is drawn.
+//! 4. On macOS, run `native-demo` and check native controls stay aligned
+//!    with the GPU-drawn content after the move, and again after moving back.
+
+use glam::Vec2;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+
+/// Logical size of a physical surface.
+pub(crate) fn logical_size(size: PhysicalSize<u32>, scale_factor: f32) -> (f32, f32) {
+    (
+        size.width as f32 / scale_factor,
+        size.height as f32 / scale_factor,
+    )
+}
+
+/// Convert a physical pointer position to logical pixels.
+pub(crate) fn logical_position(position: PhysicalPosition<f64>, scale_factor: f32) -> Vec2 {
+    Vec2::new(
+        position.x as f32 / scale_factor,
+        position.y as f32 / scale_factor,
+    )
+}
+
+/// Factor from layout units to native window points.
+///
+/// This is 1.0 unless UI zoom or a scale override changes the effective scale
+/// relative to the window's own scale factor.
+pub(crate) fn native_point_scale(effective_scale: f32, window_scale: f32) -> f32 {
+    effective_scale / window_scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_core::Rect;
+
+    #[test]
+    fn test_logical_size_is_stable_across_scale_change() {
+        // Winit doubles the physical size when moving from a 1x to a 2x display.
+        let before = logical_size(PhysicalSize::new(800, 600), 1.0);
+        let after = logical_size(PhysicalSize::new(1600, 1200), 2.0);
+        assert_eq!(before, (800.0, 600.0));
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_pointer_position_maps_to_same_logical_point() {
+        let at_1x = logical_position(PhysicalPosition::new(100.0, 50.0), 1.0);
+        let at_2x = logical_position(PhysicalPosition::new(200.0, 100.0), 2.0);
+        assert_eq!(at_1x, at_2x);
+    }
+
+    #[test]
+    fn test_physical_rect_doubles_at_2x() {
+        let logical = Rect::new(10.0, 20.0, 30.0, 40.0);
+        assert_eq!(logical.scale(1.0), logical);
+        assert_eq!(logical.scale(2.0), Rect::new(20.0, 40.0, 60.0, 80.0));
+    }
+
+    #[test]
+    fn test_native_points_ignore_window_scale() {
+        // Without zoom, layout units are window points at any display scale.
+        assert_eq!(native_point_scale(1.0, 1.0), 1.0);
+        assert_eq!(native_point_scale(2.0, 2.0), 1.0);
+        // With 150% UI zoom on a 2x display, points scale by the zoom only.
+        assert_eq!(native_point_scale(3.0, 2.0), 1.5);
+    }
+}
@@ -0,0 +1,82 @@
+//! Application-level error type.
+
+use spark_core::WgpuInitError;
+use spark_render::RenderError;
+use std::fmt;
+
+/// Errors that can stop a Spark application.
+#[derive(Debug)]
+pub enum AppError {
+    /// The event loop could not be created or failed while running.
+    EventLoop(winit::error::EventLoopError),
+    /// The window could not be created.
+    Window(winit::error::RequestError),
+    /// GPU initialization failed.
+    WgpuInit(WgpuInitError),
+    /// The renderer failed to build or draw a frame.
+    Render(RenderError),
+    /// The GPU device was lost more often than the app will recover from.
+    DeviceLost(String),
+    /// The canvas couldn't be placed in the page.
+    #[cfg(target_arch = "wasm32")]
+    Mount(crate::web::MountError),
+}
+
+impl From<winit::error::EventLoopError> for AppError {
+    fn from(err: winit::error::EventLoopError) -> Self {
+        Self::EventLoop(err)
+    }
+}
+
+impl From<winit::error::RequestError> for AppError {
+    fn from(err: winit::error::RequestError) -> Self {
+        Self::Window(err)
+    }
+}
+
+impl From<WgpuInitError> for AppError {
+    fn from(err: WgpuInitError) -> Self {
+        Self::WgpuInit(err)
+    }
+}
+
+impl From<RenderError> for AppError {
+    fn from(err: RenderError) -> Self {
+        Self::Render(err)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl From<crate::web::MountError> for AppError {
+    fn from(err: crate::web::MountError) -> Self {
+        Self::Mount(err)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EventLoop(e) => write!(f, "event loop error: {e}"),
+            Self::Window(e) => write!(f, "failed to create window: {e}"),
+            Self::WgpuInit(e) => write!(f, "GPU initialization failed: {e}"),
+            Self::Render(e) => write!(f, "rendering failed: {e}"),
+            Self::DeviceLost(message) => write!(f, "GPU device lost: {message}"),
+            #[cfg(target_arch = "wasm32")]
+            Self::Mount(e) => write!(f, "failed to mount the canvas: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EventLoop(e) => Some(e),
+            Self::Window(e) => Some(e),
+            Self::WgpuInit(e) => Some(e),
+            Self::Render(e) => Some(e),
+            Self::DeviceLost(_) => None,
+            #[cfg(target_arch = "wasm32")]
+            Self::Mount(e) => Some(e),
+        }
+    }
+}
@@ -5,7 +5,7 @@
 //! ```rust,no_run
 //! use spark::prelude::*;
 //!
-//! fn main() {
+//! fn main() -> Result<(), AppError> {
 //!     // On web, call init_web() first
 //!     #[cfg(target_arch = "wasm32")]
 //!     spark::init_web();
@@ -15,30 +15,53 @@
 //!         .run(|| {
 //!             Box::new(Container::new()
 //!                 .child(Button::new("Click me!")))
-//!         });
+//!         })
 //! }
 //! ```
 
 mod app;
+mod audit;
+mod clipboard;
+#[cfg(any(target_os = "macos", target_os = "ios", test))]
+mod compositing;
+mod dpi;
+mod error;
+mod menu;
+mod reload;
+mod task;
 pub mod accessibility;
 
 #[cfg(target_arch = "wasm32")]
-mod web;
+pub mod web;
 
 pub use app::{App, AppConfig};
+pub use error::AppError;
+pub use menu::{Menu, MenuItem, MenuItemHandle};
+pub use spark_core::WgpuOptions;
+pub use task::{AppHandle, UiSender};
 
 #[cfg(target_arch = "wasm32")]
-pub use web::init_web;
+pub use web::{init_web, open_file_dialog};
 
 /// Re-exports of commonly used types.
 pub mod prelude {
-    pub use crate::{App, AppConfig};
+    pub use crate::{App, AppConfig, AppError, AppHandle, Menu, MenuItem, UiSender};
     pub use spark_core::{Color, Rect};
-    pub use spark_input::{InputEvent, Key, Modifiers, PointerButton};
+    pub use spark_input::{
+        Action, CustomAction, DroppedFile, InputEvent, Key, Modifiers, ModifiersExt, PointerButton,
+        ScrollDelta, Shortcut,
+    };
     pub use spark_layout::taffy;
+    #[cfg(feature = "markdown")]
+    pub use spark_widgets::Markdown;
     pub use spark_widgets::{
-        Button, ButtonStyle, Container, EventResponse, Scroll, ScrollDirection, Text, TextAlign,
-        TextInput, Widget,
+        Alignment, Avatar, AvatarStatus, Badge, Breadcrumb, Button, ButtonStyle, Canvas, Checkbox,
+        CheckboxStyle, ChipInput, ClipboardService, Collapsible, ColorToken, ColumnWidth,
+        Container, CursorIcon, Divider, DropZone, EventResponse, Icon, IconName, IconSegment,
+        Image, ImageFit, ImageHandle, MenuBar, MenuBarItem, Modal, ModalHandle, Politeness,
+        ProgressBar, ProgressRing, RadioButton, RadioGroup, Scroll, ScrollDirection, SearchInput,
+        SegmentedControl, Select, Signal, SortDirection, Spacer, SplitPane, Stack, Switch, Table,
+        Text, TextAlign, TextInput, Theme, ThemeMode, Widget,
     };
 }
 
@@ -0,0 +1,465 @@
+//! Application menu bar.
+//!
+//! A [`Menu`] describes the menu bar declaratively and is passed to
+//! [`App::with_menu`](crate::App::with_menu). On macOS it is installed as the
+//! NSApplication main menu behind a generated app menu (Services, Hide, Quit);
+//! other platforms don't show it yet.
+//!
+//! Item callbacks run on the main thread as soon as the item is chosen.
+//! Standard actions such as Copy and Quit need the app, so they are queued
+//! and handled when the event loop wakes, as are custom actions, which go to
+//! [`App::on_action`](crate::App::on_action).
+
+use spark_input::{CustomAction, Key, Platform, Shortcut, StandardAction};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// What choosing a menu item does.
+#[derive(Clone)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) enum MenuCommand {
+    /// Call a user callback.
+    Callback(Arc<dyn Fn() + Send + Sync>),
+    /// Perform a standard action on the focused widget.
+    Standard(StandardAction),
+    /// Hand a custom action to the app's action handler.
+    Action(CustomAction),
+    /// Close the window and exit the event loop.
+    Quit,
+}
+
+/// Commands waiting for the event loop.
+pub(crate) type PendingCommands = Arc<Mutex<Vec<MenuCommand>>>;
+
+/// State shared between a [`MenuItem`], its handles and the installed item.
+#[derive(Default)]
+struct ItemState {
+    disabled: AtomicBool,
+    checked: AtomicBool,
+    /// The installed NSMenuItem, once the menu bar exists.
+    #[cfg(target_os = "macos")]
+    native: std::sync::OnceLock<Arc<spark_native_apple::ffi::appkit::NSMenuItem>>,
+}
+
+/// Changes a menu item after the menu bar is installed.
+#[derive(Clone)]
+pub struct MenuItemHandle {
+    state: Arc<ItemState>,
+}
+
+impl MenuItemHandle {
+    /// Enable or disable the item.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.state.disabled.store(!enabled, Ordering::Relaxed);
+        #[cfg(target_os = "macos")]
+        if let Some(item) = self.state.native.get().cloned() {
+            spark_native_apple::run_on_main(move || item.set_enabled(enabled));
+        }
+    }
+
+    /// Check if the item is enabled.
+    pub fn is_enabled(&self) -> bool {
+        !self.state.disabled.load(Ordering::Relaxed)
+    }
+
+    /// Show or hide the item's check mark.
+    pub fn set_checked(&self, checked: bool) {
+        self.state.checked.store(checked, Ordering::Relaxed);
+        #[cfg(target_os = "macos")]
+        if let Some(item) = self.state.native.get().cloned() {
+            spark_native_apple::run_on_main(move || item.set_checked(checked));
+        }
+    }
+
+    /// Check if the item shows a check mark.
+    pub fn is_checked(&self) -> bool {
+        self.state.checked.load(Ordering::Relaxed)
+    }
+}
+
+/// A menu item that runs a command when chosen.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub struct MenuItem {
+    title: String,
+    shortcut: Option<Shortcut>,
+    command: MenuCommand,
+    state: Arc<ItemState>,
+}
+
+impl MenuItem {
+    /// Create an item that calls `callback` when chosen.
+    ///
+    /// `key` is a key equivalent: a single key pressed with Cmd (Ctrl on other
+    /// platforms), a full shortcut such as `"Mod+Shift+S"`, or `""` for none.
+    /// Panics if `key` is not a valid shortcut.
+    pub fn new<F>(title: impl Into<String>, key: &str, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        Self::with_command(title, key, MenuCommand::Callback(Arc::new(callback)))
+    }
+
+    /// Create an item that performs a standard action on the focused widget.
+    pub fn standard(title: impl Into<String>, key: &str, action: StandardAction) -> Self {
+        Self::with_command(title, key, MenuCommand::Standard(action))
+    }
+
+    /// Create an item that sends the custom action `name` to
+    /// [`App::on_action`](crate::App::on_action).
+    pub fn action(title: impl Into<String>, key: &str, name: impl Into<String>) -> Self {
+        Self::with_command(title, key, MenuCommand::Action(CustomAction::new(name)))
+    }
+
+    fn with_command(title: impl Into<String>, key: &str, command: MenuCommand) -> Self {
+        Self {
+            title: title.into(),
+            shortcut: parse_key(key),
+            command,
+            state: Arc::default(),
+        }
+    }
+
+    /// Set whether the item starts enabled.
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.state.disabled.store(!enabled, Ordering::Relaxed);
+        self
+    }
+
+    /// Set whether the item starts with a check mark.
+    pub fn checked(self, checked: bool) -> Self {
+        self.state.checked.store(checked, Ordering::Relaxed);
+        self
+    }
+
+    /// Get a handle for changing the item at runtime.
+    pub fn handle(&self) -> MenuItemHandle {
+        MenuItemHandle {
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Parse a key equivalent; a lone key uses the platform's primary modifier.
+fn parse_key(key: &str) -> Option<Shortcut> {
+    if key.is_empty() {
+        return None;
+    }
+    if key.chars().count() == 1 {
+        let modifiers = Platform::current().primary_modifier();
+        return Some(Shortcut::from_parts(modifiers, Key::Character(key.to_string())));
+    }
+    Some(Shortcut::new(key))
+}
+
+/// An entry in a [`Menu`].
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+enum MenuEntry {
+    Item(MenuItem),
+    Separator,
+    Submenu(String, Menu),
+}
+
+/// A menu bar or submenu.
+#[derive(Default)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub struct Menu {
+    entries: Vec<MenuEntry>,
+}
+
+impl Menu {
+    /// Create an empty menu.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item that calls `callback` when chosen. See [`MenuItem::new`] for `key`.
+    pub fn item<F>(self, title: impl Into<String>, key: &str, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.add(MenuItem::new(title, key, callback))
+    }
+
+    /// Add an item that sends the custom action `name` to
+    /// [`App::on_action`](crate::App::on_action).
+    pub fn action(self, title: impl Into<String>, key: &str, name: impl Into<String>) -> Self {
+        self.add(MenuItem::action(title, key, name))
+    }
+
+    /// Add a prepared item, e.g. one whose handle was taken.
+    pub fn add(mut self, item: MenuItem) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+
+    /// Add a separator line.
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// Add a submenu built by `build`.
+    pub fn submenu(mut self, title: impl Into<String>, build: impl FnOnce(Menu) -> Menu) -> Self {
+        self.entries
+            .push(MenuEntry::Submenu(title.into(), build(Menu::new())));
+        self
+    }
+
+    /// Add the standard Edit menu, routed to the focused text widget.
+    pub fn edit_menu(self) -> Self {
+        use StandardAction::*;
+        self.submenu("Edit", |m| {
+            m.add(MenuItem::standard("Undo", "z", Undo))
+                .add(MenuItem::standard("Redo", "Mod+Shift+Z", Redo))
+                .separator()
+                .add(MenuItem::standard("Cut", "x", Cut))
+                .add(MenuItem::standard("Copy", "c", Copy))
+                .add(MenuItem::standard("Paste", "v", Paste))
+                .add(MenuItem::standard("Select All", "a", SelectAll))
+        })
+    }
+}
+
+/// The installed menu bar; keeps the action target alive.
+#[cfg(target_os = "macos")]
+pub(crate) struct MenuBar {
+    _target: spark_native_apple::ActionTarget,
+    _menu: spark_native_apple::ffi::appkit::NSMenu,
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) use macos::install;
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::*;
+    use objc2::runtime::Sel;
+    use objc2::sel;
+    use spark_input::NamedKey;
+    use spark_native_apple::ffi::appkit::{self, NSEventModifierFlags, NSMenu, NSMenuItem};
+    use spark_native_apple::ActionTarget;
+
+    /// Build `menu` behind an app menu and install it as the main menu.
+    ///
+    /// Commands that need the app are pushed to `pending`, then `wake` is called.
+    pub(crate) fn install(
+        menu: Menu,
+        app_name: &str,
+        pending: PendingCommands,
+        wake: impl Fn() + Send + Sync + 'static,
+    ) -> MenuBar {
+        let mut commands = Vec::new();
+        let mut items = Vec::new();
+
+        let menu_bar = NSMenu::new("");
+        menu_bar.set_autoenables_items(false);
+        let app_item = NSMenuItem::new(app_name, "");
+        app_item.set_submenu(&app_menu(app_name, &mut commands, &mut items));
+        menu_bar.add_item(&app_item);
+        build(&menu_bar, menu, &mut commands, &mut items);
+
+        let target = ActionTarget::new(move |sender| {
+            // SAFETY: this target is only attached to menu items.
+            let tag = unsafe { NSMenuItem::from_ptr(sender) }.tag();
+            let Some(command) = usize::try_from(tag).ok().and_then(|i| commands.get(i)) else {
+                return;
+            };
+            match command {
+                MenuCommand::Callback(callback) => callback(),
+                MenuCommand::Standard(action) => {
+                    // Native text fields handle editing through the responder chain.
+                    if let Some(selector) = responder_selector(*action) {
+                        if appkit::first_responder_is_text_view()
+                            && appkit::send_action_to_first_responder(selector)
+                        {
+                            return;
+                        }
+                    }
+                    pending.lock().unwrap().push(command.clone());
+                    wake();
+                }
+                MenuCommand::Action(_) | MenuCommand::Quit => {
+                    pending.lock().unwrap().push(command.clone());
+                    wake();
+                }
+            }
+        });
+        for item in &items {
+            item.set_action(target.as_ptr(), ActionTarget::selector());
+        }
+
+        appkit::set_main_menu(&menu_bar);
+        MenuBar {
+            _target: target,
+            _menu: menu_bar,
+        }
+    }
+
+    /// The app menu: Services, Hide and Quit.
+    fn app_menu(
+        app_name: &str,
+        commands: &mut Vec<MenuCommand>,
+        items: &mut Vec<Arc<NSMenuItem>>,
+    ) -> NSMenu {
+        let menu = NSMenu::new(app_name);
+        menu.set_autoenables_items(false);
+
+        let services = NSMenu::new("Services");
+        let services_item = NSMenuItem::new("Services", "");
+        services_item.set_submenu(&services);
+        appkit::set_services_menu(&services);
+        menu.add_item(&services_item);
+        menu.add_item(&NSMenuItem::separator());
+
+        // These go to NSApplication through the responder chain.
+        let hide = NSMenuItem::new(&format!("Hide {app_name}"), "h");
+        hide.set_action(std::ptr::null_mut(), sel!(hide:));
+        menu.add_item(&hide);
+        let hide_others = NSMenuItem::new("Hide Others", "h");
+        hide_others.set_key_equivalent_modifier_mask(
+            NSEventModifierFlags::COMMAND | NSEventModifierFlags::OPTION,
+        );
+        hide_others.set_action(std::ptr::null_mut(), sel!(hideOtherApplications:));
+        menu.add_item(&hide_others);
+        let show_all = NSMenuItem::new("Show All", "");
+        show_all.set_action(std::ptr::null_mut(), sel!(unhideAllApplications:));
+        menu.add_item(&show_all);
+        menu.add_item(&NSMenuItem::separator());
+
+        let quit = MenuItem::with_command(format!("Quit {app_name}"), "q", MenuCommand::Quit);
+        add_item(&menu, quit, commands, items);
+        menu
+    }
+
+    /// Append `menu`'s entries to `parent`.
+    fn build(
+        parent: &NSMenu,
+        menu: Menu,
+        commands: &mut Vec<MenuCommand>,
+        items: &mut Vec<Arc<NSMenuItem>>,
+    ) {
+        for entry in menu.entries {
+            match entry {
+                MenuEntry::Item(item) => add_item(parent, item, commands, items),
+                MenuEntry::Separator => parent.add_item(&NSMenuItem::separator()),
+                MenuEntry::Submenu(title, menu) => {
+                    let submenu = NSMenu::new(&title);
+                    submenu.set_autoenables_items(false);
+                    build(&submenu, menu, commands, items);
+                    let item = NSMenuItem::new(&title, "");
+                    item.set_submenu(&submenu);
+                    parent.add_item(&item);
+                }
+            }
+        }
+    }
+
+    /// Append an item whose tag indexes its command.
+    fn add_item(
+        parent: &NSMenu,
+        item: MenuItem,
+        commands: &mut Vec<MenuCommand>,
+        items: &mut Vec<Arc<NSMenuItem>>,
+    ) {
+        let (key, modifiers) = item
+            .shortcut
+            .as_ref()
+            .map_or((String::new(), NSEventModifierFlags::default()), key_equivalent);
+        let native = Arc::new(NSMenuItem::new(&item.title, &key));
+        native.set_key_equivalent_modifier_mask(modifiers);
+        native.set_tag(commands.len() as isize);
+        native.set_enabled(!item.state.disabled.load(Ordering::Relaxed));
+        native.set_checked(item.state.checked.load(Ordering::Relaxed));
+        parent.add_item(&native);
+
+        let _ = item.state.native.set(native.clone());
+        commands.push(item.command);
+        items.push(native);
+    }
+
+    /// The key equivalent string and modifier mask for a shortcut.
+    fn key_equivalent(shortcut: &Shortcut) -> (String, NSEventModifierFlags) {
+        let key = match &shortcut.key {
+            Key::Character(c) => c.clone(),
+            Key::Named(named) => {
+                // NSEvent function-key code points for the keys that have them.
+                let c = match named {
+                    NamedKey::Enter => '\r',
+                    NamedKey::Tab => '\t',
+                    NamedKey::Escape => '\u{1b}',
+                    NamedKey::Backspace => '\u{8}',
+                    NamedKey::Delete => '\u{7f}',
+                    NamedKey::ArrowUp => '\u{f700}',
+                    NamedKey::ArrowDown => '\u{f701}',
+                    NamedKey::ArrowLeft => '\u{f702}',
+                    NamedKey::ArrowRight => '\u{f703}',
+                    NamedKey::Home => '\u{f729}',
+                    NamedKey::End => '\u{f72b}',
+                    NamedKey::PageUp => '\u{f72c}',
+                    NamedKey::PageDown => '\u{f72d}',
+                    _ => return (String::new(), NSEventModifierFlags::default()),
+                };
+                c.to_string()
+            }
+        };
+
+        let m = shortcut.modifiers;
+        let mut mask = NSEventModifierFlags::default();
+        if m.meta() {
+            mask |= NSEventModifierFlags::COMMAND;
+        }
+        if m.ctrl() {
+            mask |= NSEventModifierFlags::CONTROL;
+        }
+        if m.alt() {
+            mask |= NSEventModifierFlags::OPTION;
+        }
+        if m.shift() {
+            mask |= NSEventModifierFlags::SHIFT;
+        }
+        (key, mask)
+    }
+
+    /// The responder-chain selector native text views implement for an action.
+    fn responder_selector(action: StandardAction) -> Option<Sel> {
+        match action {
+            StandardAction::Cut => Some(sel!(cut:)),
+            StandardAction::Copy => Some(sel!(copy:)),
+            StandardAction::Paste => Some(sel!(paste:)),
+            StandardAction::SelectAll => Some(sel!(selectAll:)),
+            StandardAction::Undo => Some(sel!(undo:)),
+            StandardAction::Redo => Some(sel!(redo:)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_input::Modifiers;
+
+    #[test]
+    fn test_lone_key_uses_primary_modifier() {
+        let shortcut = parse_key("o").unwrap();
+        assert_eq!(shortcut.modifiers, Platform::current().primary_modifier());
+        assert_eq!(shortcut.key, Key::Character("o".into()));
+
+        let shortcut = parse_key("Mod+Shift+S").unwrap();
+        assert!(shortcut.modifiers.contains(Modifiers::SHIFT));
+        assert!(parse_key("").is_none());
+    }
+
+    #[test]
+    fn test_handle_tracks_item_state() {
+        let item = MenuItem::new("Show Grid", "g", || {}).checked(true);
+        let handle = item.handle();
+        assert!(handle.is_checked());
+        assert!(handle.is_enabled());
+
+        handle.set_enabled(false);
+        handle.set_checked(false);
+        assert!(!item.handle().is_enabled());
+        assert!(!item.handle().is_checked());
+    }
+}
@@ -0,0 +1,152 @@
+//! Development-time UI reloading.
+//!
+//! When the root widget is rebuilt, widget ids are reassigned, so state is
+//! carried across by tree position: the path of child indices from the root.
+
+use spark_layout::WidgetId;
+use spark_widgets::Widget;
+
+/// Child-index path from the root to a widget.
+type WidgetPath = Vec<usize>;
+
+/// State captured from the old widget tree before a reload.
+#[derive(Debug, Default)]
+pub(crate) struct UiSnapshot {
+    scroll_offsets: Vec<(WidgetPath, (f32, f32))>,
+    focused: Option<WidgetPath>,
+}
+
+impl UiSnapshot {
+    /// Record scroll offsets and the focused widget's position.
+    pub(crate) fn capture(root: &dyn Widget, focused: Option<WidgetId>) -> Self {
+        fn walk(
+            widget: &dyn Widget,
+            path: &mut WidgetPath,
+            focused: Option<WidgetId>,
+            snapshot: &mut UiSnapshot,
+        ) {
+            if let Some(offset) = widget.scroll_offset() {
+                snapshot.scroll_offsets.push((path.clone(), offset));
+            }
+            if focused == Some(widget.id()) {
+                snapshot.focused = Some(path.clone());
+            }
+            for (i, child) in widget.children().iter().enumerate() {
+                path.push(i);
+                walk(child.as_ref(), path, focused, snapshot);
+                path.pop();
+            }
+        }
+
+        let mut snapshot = Self::default();
+        walk(root, &mut Vec::new(), focused, &mut snapshot);
+        snapshot
+    }
+
+    /// Reapply scroll offsets to widgets at the same positions in a new tree.
+    pub(crate) fn restore_scroll(&self, root: &mut dyn Widget) {
+        for (path, (x, y)) in &self.scroll_offsets {
+            if let Some(widget) = widget_at_mut(root, path) {
+                if widget.scroll_offset().is_some() {
+                    widget.set_scroll_offset(*x, *y);
+                }
+            }
+        }
+    }
+
+    /// Id of the widget at the previously focused position, if it is still focusable.
+    ///
+    /// Call after layout so the new tree has ids assigned.
+    pub(crate) fn focused_id(&self, root: &dyn Widget) -> Option<WidgetId> {
+        let widget = widget_at(root, self.focused.as_ref()?)?;
+        widget.focusable().then(|| widget.id())
+    }
+}
+
+fn widget_at<'a>(root: &'a dyn Widget, path: &[usize]) -> Option<&'a dyn Widget> {
+    let mut widget = root;
+    for &i in path {
+        widget = widget.children().get(i)?.as_ref();
+    }
+    Some(widget)
+}
+
+fn widget_at_mut<'a>(root: &'a mut dyn Widget, path: &[usize]) -> Option<&'a mut dyn Widget> {
+    let mut widget = root;
+    for &i in path {
+        widget = widget.children_mut().get_mut(i)?.as_mut();
+    }
+    Some(widget)
+}
+
+/// Watches files and wakes the event loop when they change.
+#[cfg(feature = "hot-reload")]
+pub(crate) struct FileWatcher {
+    _watcher: notify::RecommendedWatcher,
+}
+
+#[cfg(feature = "hot-reload")]
+impl FileWatcher {
+    /// Watch `paths` recursively, setting `pending` and waking the loop on changes.
+    pub(crate) fn new(
+        paths: &[std::path::PathBuf],
+        pending: std::sync::Arc<std::sync::atomic::AtomicBool>,
+        proxy: winit::event_loop::EventLoopProxy,
+    ) -> notify::Result<Self> {
+        use notify::Watcher;
+        use std::sync::atomic::Ordering;
+
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            match res {
+                Ok(event) if event.kind.is_modify() || event.kind.is_create() => {
+                    pending.store(true, Ordering::Relaxed);
+                    proxy.wake_up();
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!("file watch error: {err}"),
+            }
+        })?;
+        for path in paths {
+            watcher.watch(path, notify::RecursiveMode::Recursive)?;
+        }
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_widgets::{Container, Scroll, Text};
+
+    fn build() -> Box<dyn Widget> {
+        Box::new(
+            Container::new()
+                .child(Text::new("header"))
+                .child(Scroll::new().content(Text::new("body"))),
+        )
+    }
+
+    #[test]
+    fn test_scroll_offset_restored_by_position() {
+        let mut old = build();
+        old.children_mut()[1].set_scroll_offset(0.0, 120.0);
+
+        let snapshot = UiSnapshot::capture(old.as_ref(), None);
+        let mut new = build();
+        snapshot.restore_scroll(new.as_mut());
+
+        assert_eq!(new.children()[1].scroll_offset(), Some((0.0, 120.0)));
+        assert_eq!(new.children()[0].scroll_offset(), None);
+    }
+
+    #[test]
+    fn test_missing_paths_are_ignored() {
+        let mut old = build();
+        old.children_mut()[1].set_scroll_offset(0.0, 50.0);
+        let snapshot = UiSnapshot::capture(old.as_ref(), None);
+
+        let mut new: Box<dyn Widget> = Box::new(Container::new().child(Text::new("only")));
+        snapshot.restore_scroll(new.as_mut());
+        assert_eq!(new.children()[0].scroll_offset(), None);
+    }
+}
@@ -0,0 +1,203 @@
+//! Background work that reports back to the UI thread.
+//!
+//! An [`AppHandle`] from [`App::handle`](crate::App::handle) spawns futures
+//! off the UI thread and hands out [`UiSender`]s. Closures posted through a
+//! sender run on the UI thread before the next frame, after which the app
+//! lays out and repaints, so they can safely update widget state.
+
+use spark_widgets::{Announcement, Politeness, ThemeMode};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A closure posted to run on the UI thread.
+type UiCallback = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct Shared {
+    posted: Mutex<Vec<UiCallback>>,
+    /// Wakes the event loop; set once the app is running.
+    proxy: Mutex<Option<winit::event_loop::EventLoopProxy>>,
+    /// Theme mode requested since the last frame.
+    theme_mode: Mutex<Option<ThemeMode>>,
+    /// Text to place on the clipboard; the latest write wins.
+    clipboard_text: Mutex<Option<String>>,
+    /// Announcements made since the last frame.
+    announcements: Mutex<Vec<Announcement>>,
+    /// Set by [`AppHandle::exit`] until the app takes it.
+    exit: AtomicBool,
+}
+
+impl Shared {
+    fn wake(&self) {
+        if let Some(proxy) = self.proxy.lock().unwrap().as_ref() {
+            proxy.wake_up();
+        }
+    }
+}
+
+/// Posts closures to run on the UI thread, from any thread.
+#[derive(Clone)]
+pub struct UiSender {
+    shared: Arc<Shared>,
+}
+
+impl UiSender {
+    /// Run `callback` on the UI thread before the next frame, then repaint.
+    pub fn post(&self, callback: impl FnOnce() + Send + 'static) {
+        self.shared.posted.lock().unwrap().push(Box::new(callback));
+        self.shared.wake();
+    }
+}
+
+/// Handle to a running app for background work.
+///
+/// ```ignore
+/// let app = App::new();
+/// let handle = app.handle();
+/// app.run(move || {
+///     let ui = handle.sender();
+///     handle.spawn(async move {
+///         let rows = load_rows().await;
+///         ui.post(move || println!("{} rows", rows.len()));
+///     });
+///     build_ui()
+/// })
+/// ```
+#[derive(Clone)]
+pub struct AppHandle {
+    shared: Arc<Shared>,
+}
+
+impl AppHandle {
+    pub(crate) fn new() -> Self {
+        Self {
+            shared: Arc::default(),
+        }
+    }
+
+    /// Get a sender for posting closures to the UI thread.
+    pub fn sender(&self) -> UiSender {
+        UiSender {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Run `future` in the background.
+    ///
+    /// On desktop each future gets its own thread, so it may block; on the
+    /// web it runs on the browser's event loop and must not.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn(&self, future: impl Future<Output = ()> + Send + 'static) {
+        let spawned = std::thread::Builder::new()
+            .name("spark-task".into())
+            .spawn(move || pollster::block_on(future));
+        if let Err(err) = spawned {
+            log::error!("failed to spawn a task thread: {err}");
+        }
+    }
+
+    /// Run `future` in the background.
+    ///
+    /// On desktop each future gets its own thread, so it may block; on the
+    /// web it runs on the browser's event loop and must not.
+    #[cfg(target_arch = "wasm32")]
+    pub fn spawn(&self, future: impl Future<Output = ()> + 'static) {
+        wasm_bindgen_futures::spawn_local(future);
+    }
+
+    /// Switch the app to its light or dark theme before the next frame,
+    /// e.g. from a button's click handler.
+    pub fn set_theme_mode(&self, mode: ThemeMode) {
+        *self.shared.theme_mode.lock().unwrap() = Some(mode);
+        self.shared.wake();
+    }
+
+    /// Place text on the clipboard before the next frame, e.g. from a "copy
+    /// link" button's click handler.
+    pub fn set_clipboard_text(&self, text: impl Into<String>) {
+        *self.shared.clipboard_text.lock().unwrap() = Some(text.into());
+        self.shared.wake();
+    }
+
+    /// Have screen readers speak `text` without moving focus, e.g. when
+    /// background work finishes.
+    pub fn announce(&self, text: impl Into<String>, politeness: Politeness) {
+        let announcement = Announcement {
+            text: text.into(),
+            politeness,
+        };
+        self.shared.announcements.lock().unwrap().push(announcement);
+        self.shared.wake();
+    }
+
+    /// Close the window and return from `App::run` after the current event,
+    /// e.g. from a Quit button's click handler.
+    pub fn exit(&self) {
+        self.shared.exit.store(true, Ordering::Relaxed);
+        self.shared.wake();
+    }
+
+    /// Wake `proxy`'s event loop whenever a closure is posted.
+    pub(crate) fn set_proxy(&self, proxy: winit::event_loop::EventLoopProxy) {
+        *self.shared.proxy.lock().unwrap() = Some(proxy);
+    }
+
+    /// Take the closures posted since the last call.
+    pub(crate) fn take_posted(&self) -> Vec<UiCallback> {
+        std::mem::take(&mut *self.shared.posted.lock().unwrap())
+    }
+
+    /// Take the theme mode requested since the last call.
+    pub(crate) fn take_theme_mode(&self) -> Option<ThemeMode> {
+        self.shared.theme_mode.lock().unwrap().take()
+    }
+
+    /// Take the clipboard text set since the last call.
+    pub(crate) fn take_clipboard_text(&self) -> Option<String> {
+        self.shared.clipboard_text.lock().unwrap().take()
+    }
+
+    /// Take the announcements made since the last call.
+    pub(crate) fn take_announcements(&self) -> Vec<Announcement> {
+        std::mem::take(&mut *self.shared.announcements.lock().unwrap())
+    }
+
+    /// Take the pending exit request.
+    pub(crate) fn take_exit(&self) -> bool {
+        self.shared.exit.swap(false, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_posted_closures_are_taken_in_order() {
+        let handle = AppHandle::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        for expected in 0..2 {
+            let calls = calls.clone();
+            handle.sender().post(move || {
+                assert_eq!(calls.fetch_add(1, Ordering::Relaxed), expected);
+            });
+        }
+        for callback in handle.take_posted() {
+            callback();
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        assert!(handle.take_posted().is_empty());
+    }
+
+    #[test]
+    fn test_exit_requests_stay_with_their_app() {
+        let handle = AppHandle::new();
+        let other = AppHandle::new();
+        handle.clone().exit();
+        assert!(!other.take_exit());
+        assert!(handle.take_exit());
+        assert!(!handle.take_exit());
+    }
+}
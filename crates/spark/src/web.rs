@@ -1,7 +1,26 @@
 //! Web platform support for WASM/WebGPU.
 
+#[cfg(target_arch = "wasm32")]
+use std::cell::{Cell, RefCell};
+#[cfg(target_arch = "wasm32")]
+use std::rc::Rc;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsCast;
+#[cfg(target_arch = "wasm32")]
+use web_sys::{HtmlCanvasElement, MediaQueryList, ResizeObserver};
+#[cfg(target_arch = "wasm32")]
+use glam::Vec2;
+#[cfg(target_arch = "wasm32")]
+use spark_core::Rect;
+#[cfg(target_arch = "wasm32")]
+use spark_input::ui_events::keyboard::Code;
+#[cfg(target_arch = "wasm32")]
+use spark_input::{
+    CompositionEvent, CompositionState, DroppedFile, FileContents, InputEvent, Key,
+    KeyboardEvent, Modifiers, Pointer, PointerButton, TouchPan,
+};
 
 /// Initialize the web platform (call this before App::run on web).
 #[cfg(target_arch = "wasm32")]
@@ -11,6 +30,1351 @@ pub fn init_web() {
     log::info!("Spark web platform initialized");
 }
 
+/// Why the app's canvas couldn't be placed in the page.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MountError {
+    /// No element has the id given to [`App::with_canvas_parent`](crate::App::with_canvas_parent).
+    MissingElement(String),
+    /// The element already holds another app's canvas.
+    AlreadyMounted(String),
+    /// Another app already runs on this page; winit allows one event loop per page.
+    MultipleApps,
+    /// A DOM call failed.
+    Dom(String),
+}
+
+#[cfg(target_arch = "wasm32")]
+impl std::fmt::Display for MountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingElement(id) => write!(f, "no element with id `{id}` to hold the canvas"),
+            Self::AlreadyMounted(id) => write!(f, "element `{id}` already holds a Spark app"),
+            Self::MultipleApps => write!(f, "only one Spark app can run per page"),
+            Self::Dom(err) => write!(f, "DOM error: {err}"),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl std::error::Error for MountError {}
+
+#[cfg(target_arch = "wasm32")]
+impl From<JsValue> for MountError {
+    fn from(err: JsValue) -> Self {
+        Self::Dom(format!("{err:?}"))
+    }
+}
+
+/// Attribute marking elements that hold an app's canvas.
+#[cfg(target_arch = "wasm32")]
+const MOUNTED_ATTRIBUTE: &str = "data-spark-app";
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    static APP_STARTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Record that an app runs on this page, failing if one already does.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn claim_page() -> Result<(), MountError> {
+    if APP_STARTED.with(|started| started.replace(true)) {
+        return Err(MountError::MultipleApps);
+    }
+    Ok(())
+}
+
+/// The canvas to draw into inside the element with id `parent`.
+///
+/// A `<canvas>` element is used as is; any other element gets a new canvas
+/// filling its box, so the app follows the element's size.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn mount_canvas(parent: &str) -> Result<HtmlCanvasElement, MountError> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| MountError::Dom("no document".into()))?;
+    let element = document
+        .get_element_by_id(parent)
+        .ok_or_else(|| MountError::MissingElement(parent.into()))?;
+    if element.has_attribute(MOUNTED_ATTRIBUTE) {
+        return Err(MountError::AlreadyMounted(parent.into()));
+    }
+    element.set_attribute(MOUNTED_ATTRIBUTE, "")?;
+    if let Some(canvas) = element.dyn_ref::<HtmlCanvasElement>() {
+        return Ok(canvas.clone());
+    }
+    let canvas: HtmlCanvasElement = document.create_element("canvas")?.unchecked_into();
+    canvas.set_attribute("style", "display:block;width:100%;height:100%")?;
+    element.append_child(&canvas)?;
+    Ok(canvas)
+}
+
+/// Size of the canvas in physical pixels and the device pixel ratio.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CanvasMetrics {
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+}
+
+/// Watches the canvas's CSS size and the device pixel ratio.
+///
+/// Browsers change these on window resize, zoom and moves between monitors,
+/// and winit's web backend doesn't report every change, so the app polls
+/// [`take_change`](Self::take_change) when woken.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct CanvasWatcher {
+    canvas: HtmlCanvasElement,
+    changed: Rc<Cell<bool>>,
+    observer: ResizeObserver,
+    _on_resize: Closure<dyn FnMut()>,
+    /// Query matching the current pixel ratio; replaced each time it stops matching.
+    ratio_query: Rc<RefCell<Option<MediaQueryList>>>,
+    on_ratio_change: Rc<RefCell<Option<Closure<dyn FnMut()>>>>,
+    last: Cell<Option<CanvasMetrics>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl CanvasWatcher {
+    /// Start watching `canvas`, calling `wake` after each change.
+    pub fn new(canvas: HtmlCanvasElement, wake: impl Fn() + 'static) -> Result<Self, JsValue> {
+        let changed = Rc::new(Cell::new(true));
+        let wake = Rc::new(wake);
+
+        let on_resize = {
+            let changed = changed.clone();
+            let wake = wake.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                changed.set(true);
+                wake();
+            })
+        };
+        let observer = ResizeObserver::new(on_resize.as_ref().unchecked_ref())?;
+        observer.observe(&canvas);
+
+        let ratio_query = Rc::new(RefCell::new(None));
+        let on_ratio_change = Rc::new(RefCell::new(None));
+        *on_ratio_change.borrow_mut() = Some({
+            let changed = changed.clone();
+            let ratio_query = ratio_query.clone();
+            let on_ratio_change = Rc::downgrade(&on_ratio_change);
+            Closure::<dyn FnMut()>::new(move || {
+                changed.set(true);
+                // The query only matches the old ratio; watch for the new one
+                if let Some(on_ratio_change) = on_ratio_change.upgrade() {
+                    watch_pixel_ratio(&ratio_query, &on_ratio_change);
+                }
+                wake();
+            })
+        });
+        watch_pixel_ratio(&ratio_query, &on_ratio_change);
+
+        Ok(Self {
+            canvas,
+            changed,
+            observer,
+            _on_resize: on_resize,
+            ratio_query,
+            on_ratio_change,
+            last: Cell::new(None),
+        })
+    }
+
+    /// The canvas metrics, if they changed since the last call.
+    ///
+    /// Also sizes the canvas's drawing buffer to match.
+    pub fn take_change(&self) -> Option<CanvasMetrics> {
+        if !self.changed.replace(false) {
+            return None;
+        }
+        let scale_factor = web_sys::window()?.device_pixel_ratio();
+        let physical = |css: i32| (css.max(0) as f64 * scale_factor).round() as u32;
+        let metrics = CanvasMetrics {
+            width: physical(self.canvas.client_width()),
+            height: physical(self.canvas.client_height()),
+            scale_factor,
+        };
+        if self.last.replace(Some(metrics)) == Some(metrics) {
+            return None;
+        }
+        self.canvas.set_width(metrics.width);
+        self.canvas.set_height(metrics.height);
+        Some(metrics)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for CanvasWatcher {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+        if let Some(query) = self.ratio_query.borrow_mut().take() {
+            query.set_onchange(None);
+        }
+        // Break the closure's reference to its own cell
+        self.on_ratio_change.borrow_mut().take();
+    }
+}
+
+/// Point `on_change` at a media query matching only the current pixel ratio.
+#[cfg(target_arch = "wasm32")]
+fn watch_pixel_ratio(
+    query: &RefCell<Option<MediaQueryList>>,
+    on_change: &RefCell<Option<Closure<dyn FnMut()>>>,
+) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    if let Some(old) = query.borrow_mut().take() {
+        old.set_onchange(None);
+    }
+    let ratio = window.device_pixel_ratio();
+    let Ok(Some(new)) = window.match_media(&format!("(resolution: {ratio}dppx)")) else {
+        return;
+    };
+    if let Some(on_change) = on_change.borrow().as_ref() {
+        new.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    }
+    *query.borrow_mut() = Some(new);
+}
+
+/// Schedules redraws with `requestAnimationFrame`, and none while the page is hidden.
+///
+/// At most one animation frame is requested at a time, and only when a
+/// redraw is pending. Time spent hidden is tracked so animations can resume
+/// where they left off instead of jumping ahead.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct FrameScheduler {
+    shared: Rc<Scheduler>,
+}
+
+#[cfg(target_arch = "wasm32")]
+struct Scheduler {
+    window: web_sys::Window,
+    document: web_sys::Document,
+    /// Called from the animation frame; asks winit for the redraw.
+    redraw: Box<dyn Fn()>,
+    /// Id of the requested animation frame, if any.
+    frame: Cell<Option<i32>>,
+    /// A redraw was requested while the page was hidden.
+    pending: Cell<bool>,
+    hidden_since: Cell<Option<web_time::Instant>>,
+    /// Total time spent hidden before `hidden_since`.
+    hidden_total: Cell<std::time::Duration>,
+    on_frame: RefCell<Option<Closure<dyn FnMut()>>>,
+    on_visibility_change: RefCell<Option<Closure<dyn FnMut()>>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl FrameScheduler {
+    /// Create a scheduler that calls `redraw` from animation frames.
+    pub fn new(redraw: impl Fn() + 'static) -> Result<Self, JsValue> {
+        let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+        let document = window
+            .document()
+            .ok_or_else(|| JsValue::from_str("no document"))?;
+        let hidden_since = document.hidden().then(web_time::Instant::now);
+        let shared = Rc::new(Scheduler {
+            window,
+            document,
+            redraw: Box::new(redraw),
+            frame: Cell::new(None),
+            pending: Cell::new(false),
+            hidden_since: Cell::new(hidden_since),
+            hidden_total: Cell::new(std::time::Duration::ZERO),
+            on_frame: RefCell::new(None),
+            on_visibility_change: RefCell::new(None),
+        });
+
+        let weak = Rc::downgrade(&shared);
+        *shared.on_frame.borrow_mut() = Some(Closure::<dyn FnMut()>::new(move || {
+            if let Some(shared) = weak.upgrade() {
+                shared.frame.set(None);
+                (shared.redraw)();
+            }
+        }));
+        let weak = Rc::downgrade(&shared);
+        *shared.on_visibility_change.borrow_mut() = Some(Closure::<dyn FnMut()>::new(move || {
+            if let Some(shared) = weak.upgrade() {
+                shared.visibility_changed();
+            }
+        }));
+        if let Some(callback) = shared.on_visibility_change.borrow().as_ref() {
+            shared
+                .document
+                .set_onvisibilitychange(Some(callback.as_ref().unchecked_ref()));
+        }
+        Ok(Self { shared })
+    }
+
+    /// Redraw on the next animation frame, or once the page is visible again.
+    pub fn request(&self) {
+        let shared = &self.shared;
+        if shared.frame.get().is_some() {
+            return;
+        }
+        if shared.document.hidden() {
+            shared.pending.set(true);
+        } else {
+            shared.schedule();
+        }
+    }
+
+    /// Total time the page has been hidden, including now.
+    pub fn hidden_time(&self) -> std::time::Duration {
+        let shared = &self.shared;
+        let current = shared
+            .hidden_since
+            .get()
+            .map(|since| since.elapsed())
+            .unwrap_or_default();
+        shared.hidden_total.get() + current
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Scheduler {
+    fn schedule(&self) {
+        let Some(callback) = self.on_frame.borrow().as_ref().map(|c| c.as_ref().clone()) else {
+            return;
+        };
+        match self.window.request_animation_frame(callback.unchecked_ref()) {
+            Ok(id) => self.frame.set(Some(id)),
+            Err(err) => log::warn!("requestAnimationFrame failed: {err:?}"),
+        }
+    }
+
+    fn visibility_changed(&self) {
+        if self.document.hidden() {
+            // Browsers throttle frames of hidden pages anyway; stop asking
+            if let Some(id) = self.frame.take() {
+                let _ = self.window.cancel_animation_frame(id);
+                self.pending.set(true);
+            }
+            if self.hidden_since.get().is_none() {
+                self.hidden_since.set(Some(web_time::Instant::now()));
+            }
+        } else {
+            if let Some(since) = self.hidden_since.take() {
+                self.hidden_total.set(self.hidden_total.get() + since.elapsed());
+            }
+            if self.pending.replace(false) && self.frame.get().is_none() {
+                self.schedule();
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for FrameScheduler {
+    fn drop(&mut self) {
+        let shared = &self.shared;
+        if let Some(id) = shared.frame.take() {
+            let _ = shared.window.cancel_animation_frame(id);
+        }
+        shared.document.set_onvisibilitychange(None);
+    }
+}
+
+/// Clipboard backed by the browser.
+///
+/// Writes use `navigator.clipboard.writeText`, or `document.execCommand("copy")`
+/// where the Clipboard API is missing (older browsers and insecure contexts);
+/// both must run inside the user gesture that asked for them. Reads use
+/// `navigator.clipboard.readText` where the browser permits it. Otherwise the
+/// text comes from `paste` events on the canvas and the text entry field,
+/// which also cover pastes started from the browser's own menus.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct WebClipboard {
+    window: web_sys::Window,
+    document: web_sys::Document,
+    /// Elements whose `paste` events deliver text.
+    paste_targets: Vec<web_sys::EventTarget>,
+    inbox: Rc<PasteInbox>,
+    on_paste: Closure<dyn FnMut(web_sys::ClipboardEvent)>,
+}
+
+/// Pasted text waiting for the app, shared with the async callbacks.
+#[cfg(target_arch = "wasm32")]
+struct PasteInbox {
+    texts: RefCell<Vec<String>>,
+    /// A `readText` call is outstanding; a paste event answers it instead.
+    read_pending: Cell<bool>,
+    wake: Box<dyn Fn()>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl PasteInbox {
+    fn push(&self, text: String) {
+        self.texts.borrow_mut().push(text);
+        (self.wake)();
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl WebClipboard {
+    /// Create the clipboard, listening for pastes on `paste_targets`.
+    ///
+    /// `wake` is called when pasted text arrives outside an event.
+    pub fn new(
+        paste_targets: Vec<web_sys::EventTarget>,
+        wake: impl Fn() + 'static,
+    ) -> Result<Self, JsValue> {
+        let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+        let document = window
+            .document()
+            .ok_or_else(|| JsValue::from_str("no document"))?;
+        let inbox = Rc::new(PasteInbox {
+            texts: RefCell::new(Vec::new()),
+            read_pending: Cell::new(false),
+            wake: Box::new(wake),
+        });
+
+        let on_paste = {
+            let inbox = inbox.clone();
+            Closure::<dyn FnMut(web_sys::ClipboardEvent)>::new(
+                move |event: web_sys::ClipboardEvent| {
+                    let Some(text) = event
+                        .clipboard_data()
+                        .and_then(|data| data.get_data("text/plain").ok())
+                    else {
+                        return;
+                    };
+                    event.prevent_default();
+                    inbox.read_pending.set(false);
+                    inbox.push(text);
+                },
+            )
+        };
+        let mut clipboard = Self {
+            window,
+            document,
+            paste_targets: Vec::new(),
+            inbox,
+            on_paste,
+        };
+        for target in paste_targets {
+            target.add_event_listener_with_callback(
+                "paste",
+                clipboard.on_paste.as_ref().unchecked_ref(),
+            )?;
+            clipboard.paste_targets.push(target);
+        }
+        Ok(clipboard)
+    }
+
+    /// The async Clipboard API, if this context has it.
+    fn clipboard(&self) -> Option<web_sys::Clipboard> {
+        let clipboard = self.window.navigator().clipboard();
+        (!clipboard.is_undefined()).then_some(clipboard)
+    }
+
+    /// Copy through a temporary text area; only works inside a user gesture.
+    fn exec_command_copy(&self, text: &str) -> Result<bool, JsValue> {
+        let Some(document) = self.document.dyn_ref::<web_sys::HtmlDocument>() else {
+            return Ok(false);
+        };
+        let Some(body) = document.body() else {
+            return Ok(false);
+        };
+        let area: web_sys::HtmlTextAreaElement = document.create_element("textarea")?.dyn_into()?;
+        area.set_value(text);
+        area.set_attribute("readonly", "")?;
+        area.set_attribute("style", "position:fixed;top:0;left:0;opacity:0;pointer-events:none")?;
+        let focused = document.active_element();
+        body.append_child(&area)?;
+        area.select();
+        let copied = document.exec_command("copy");
+        body.remove_child(&area)?;
+        // Selecting the text area took the focus
+        if let Some(focused) = focused.and_then(|e| e.dyn_into::<web_sys::HtmlElement>().ok()) {
+            focused.focus()?;
+        }
+        copied
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl spark_widgets::ClipboardService for WebClipboard {
+    fn set_text(&mut self, text: String) {
+        let Some(clipboard) = self.clipboard() else {
+            match self.exec_command_copy(&text) {
+                Ok(true) => {}
+                Ok(false) => log::warn!("the browser refused to copy to the clipboard"),
+                Err(err) => log::warn!("failed to copy to the clipboard: {err:?}"),
+            }
+            return;
+        };
+        let promise = clipboard.write_text(&text);
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(err) = wasm_bindgen_futures::JsFuture::from(promise).await {
+                log::warn!("failed to copy to the clipboard: {err:?}");
+            }
+        });
+    }
+
+    fn request_text(&mut self) {
+        let Some(clipboard) = self.clipboard() else {
+            // Only a paste event can deliver the text
+            return;
+        };
+        self.inbox.read_pending.set(true);
+        let inbox = self.inbox.clone();
+        let promise = clipboard.read_text();
+        wasm_bindgen_futures::spawn_local(async move {
+            match wasm_bindgen_futures::JsFuture::from(promise).await {
+                Ok(text) => {
+                    // Skip the text if a paste event already delivered it
+                    if inbox.read_pending.replace(false) {
+                        if let Some(text) = text.as_string() {
+                            inbox.push(text);
+                        }
+                    }
+                }
+                Err(err) => {
+                    inbox.read_pending.set(false);
+                    log::debug!("clipboard read refused, waiting for a paste event: {err:?}");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl crate::clipboard::ClipboardBackend for WebClipboard {
+    fn take_pasted(&mut self) -> Vec<String> {
+        std::mem::take(&mut *self.inbox.texts.borrow_mut())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for WebClipboard {
+    fn drop(&mut self) {
+        for target in &self.paste_targets {
+            let _ = target
+                .remove_event_listener_with_callback("paste", self.on_paste.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// Mouse, touch and pen input read from the canvas's pointer events.
+///
+/// winit's web backend doesn't deliver touches, so on the web all pointer
+/// input comes from here. Each finger is its own pointer. A first finger
+/// that starts on a scrollable widget pans it once it moves past a small
+/// slop, and the page doesn't scroll under it; touches anywhere else leave
+/// scrolling and zooming to the browser.
+/// A tap on a text widget focuses the text entry field right away, since
+/// mobile browsers only show the keyboard for focus from a user gesture.
+///
+/// Files dropped on the canvas are read into memory and delivered as
+/// `InputEvent::FileDrop`, instead of the browser navigating to them.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct PointerInput {
+    shared: Rc<PointerShared>,
+    listeners: Vec<(&'static str, Closure<dyn FnMut(web_sys::Event)>)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+struct PointerShared {
+    canvas: HtmlCanvasElement,
+    /// Translated events waiting for the app.
+    events: RefCell<Vec<InputEvent>>,
+    /// Logical pixels per CSS pixel.
+    scale: Cell<f32>,
+    /// Bounds of the widgets that can scroll, in logical pixels.
+    scroll_regions: RefCell<Vec<Rect>>,
+    /// Bounds of the text widgets, in logical pixels.
+    text_regions: RefCell<Vec<Rect>>,
+    /// Focused when a text widget is tapped.
+    text_target: RefCell<Option<web_sys::HtmlElement>>,
+    touch: Cell<Option<TouchGesture>>,
+    wake: Box<dyn Fn()>,
+}
+
+/// Where lifted fingers are moved, so that nothing stays hovered.
+#[cfg(target_arch = "wasm32")]
+const OFF_CANVAS: Vec2 = Vec2::new(-1.0e6, -1.0e6);
+
+/// The primary touch or pen contact in progress.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, Debug)]
+struct TouchGesture {
+    pointer_id: i32,
+    /// Started on a scrollable widget, so moving pans it.
+    scrolls: bool,
+    pan: TouchPan,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl PointerInput {
+    /// Start listening to pointer events on `canvas`, calling `wake` after each.
+    pub fn new(canvas: HtmlCanvasElement, wake: impl Fn() + 'static) -> Result<Self, JsValue> {
+        let mut input = Self {
+            shared: Rc::new(PointerShared {
+                canvas,
+                events: RefCell::new(Vec::new()),
+                scale: Cell::new(1.0),
+                scroll_regions: RefCell::new(Vec::new()),
+                text_regions: RefCell::new(Vec::new()),
+                text_target: RefCell::new(None),
+                touch: Cell::new(None),
+                wake: Box::new(wake),
+            }),
+            listeners: Vec::new(),
+        };
+
+        let handlers: [(&'static str, fn(&PointerShared, &web_sys::PointerEvent)); 4] = [
+            ("pointerdown", PointerShared::pointer_down),
+            ("pointermove", PointerShared::pointer_move),
+            ("pointerup", PointerShared::pointer_up),
+            ("pointercancel", PointerShared::pointer_cancel),
+        ];
+        for (name, handler) in handlers {
+            let shared = Rc::downgrade(&input.shared);
+            let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+                let Some(shared) = shared.upgrade() else {
+                    return;
+                };
+                if let Some(event) = event.dyn_ref::<web_sys::PointerEvent>() {
+                    handler(&shared, event);
+                }
+            });
+            input.listen(name, listener, true)?;
+        }
+
+        // Pans of our widgets must not scroll the page; only a non-passive
+        // touchmove listener can stop that
+        let shared = Rc::downgrade(&input.shared);
+        let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            let scrolls = shared
+                .upgrade()
+                .and_then(|shared| shared.touch.get())
+                .is_some_and(|touch| touch.scrolls);
+            if scrolls {
+                event.prevent_default();
+            }
+        });
+        input.listen("touchmove", listener, false)?;
+
+        // Accepting drags is what keeps the browser from opening the files
+        let shared = Rc::downgrade(&input.shared);
+        let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            event.prevent_default();
+            if let (Some(shared), Some(event)) =
+                (shared.upgrade(), event.dyn_ref::<web_sys::DragEvent>())
+            {
+                shared.hover_files(event);
+            }
+        });
+        input.listen("dragover", listener, false)?;
+        let shared = Rc::downgrade(&input.shared);
+        let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            if let Some(shared) = shared.upgrade() {
+                shared.push([InputEvent::FileHoverCancelled]);
+            }
+        });
+        input.listen("dragleave", listener, true)?;
+        let shared = Rc::downgrade(&input.shared);
+        let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            event.prevent_default();
+            if let (Some(shared), Some(event)) =
+                (shared.upgrade(), event.dyn_ref::<web_sys::DragEvent>())
+            {
+                shared.drop_files(event);
+            }
+        });
+        input.listen("drop", listener, false)?;
+        Ok(input)
+    }
+
+    fn listen(
+        &mut self,
+        name: &'static str,
+        listener: Closure<dyn FnMut(web_sys::Event)>,
+        passive: bool,
+    ) -> Result<(), JsValue> {
+        let options = web_sys::AddEventListenerOptions::new();
+        options.set_passive(passive);
+        self.shared
+            .canvas
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                name,
+                listener.as_ref().unchecked_ref(),
+                &options,
+            )?;
+        self.listeners.push((name, listener));
+        Ok(())
+    }
+
+    /// Set the number of logical pixels per CSS pixel.
+    pub fn set_scale(&self, scale: f32) {
+        self.shared.scale.set(scale);
+    }
+
+    /// Set the bounds of the widgets that can scroll, in logical pixels.
+    pub fn set_scroll_regions(&self, regions: Vec<Rect>) {
+        *self.shared.scroll_regions.borrow_mut() = regions;
+    }
+
+    /// Set the bounds of the text widgets, in logical pixels.
+    pub fn set_text_regions(&self, regions: Vec<Rect>) {
+        *self.shared.text_regions.borrow_mut() = regions;
+    }
+
+    /// Set the element to focus when a text widget is tapped.
+    pub fn set_text_target(&self, target: Option<web_sys::HtmlElement>) {
+        *self.shared.text_target.borrow_mut() = target;
+    }
+
+    /// Take the events translated since the last call.
+    pub fn take_events(&self) -> Vec<InputEvent> {
+        std::mem::take(&mut *self.shared.events.borrow_mut())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl PointerShared {
+    fn position(&self, event: &web_sys::PointerEvent) -> Vec2 {
+        Vec2::new(event.offset_x() as f32, event.offset_y() as f32) * self.scale.get()
+    }
+
+    fn push<const N: usize>(&self, events: [InputEvent; N]) {
+        self.events.borrow_mut().extend(events);
+        (self.wake)();
+    }
+
+    /// Push `events` as coming from `from`'s pointer, with its modifiers.
+    fn push_from<const N: usize>(&self, from: &web_sys::PointerEvent, events: [InputEvent; N]) {
+        let (pointer, modifiers) = (pointer_of(from), modifiers_of(from));
+        self.push(events.map(|event| event.with_pointer(pointer).with_modifiers(modifiers)));
+    }
+
+    fn pointer_down(&self, event: &web_sys::PointerEvent) {
+        let pos = self.position(event);
+        // Keep receiving the pointer while it's dragged off the canvas
+        let _ = self.canvas.set_pointer_capture(event.pointer_id());
+        let button = if event.pointer_type() == "mouse" {
+            mouse_button(event)
+        } else if !event.is_primary() {
+            // Extra fingers press, but only the first one pans
+            PointerButton::Primary
+        } else {
+            let scrolls = self
+                .scroll_regions
+                .borrow()
+                .iter()
+                .any(|region| region.contains(pos));
+            if scrolls {
+                // No emulated mouse events or text selection for our pans
+                event.prevent_default();
+            }
+            self.touch.set(Some(TouchGesture {
+                pointer_id: event.pointer_id(),
+                scrolls,
+                pan: TouchPan::new(pos),
+            }));
+            PointerButton::Primary
+        };
+        self.push_from(
+            event,
+            [
+                InputEvent::pointer_move(pos),
+                InputEvent::pointer_down(pos, button),
+            ],
+        );
+    }
+
+    fn pointer_move(&self, event: &web_sys::PointerEvent) {
+        let pos = self.position(event);
+        let Some(mut touch) = self
+            .touch
+            .get()
+            .filter(|touch| touch.pointer_id == event.pointer_id() && touch.scrolls)
+        else {
+            self.push_from(event, [InputEvent::pointer_move(pos)]);
+            return;
+        };
+        let scroll = touch.pan.move_to(pos);
+        self.touch.set(Some(touch));
+        self.push_from(event, [scroll.unwrap_or(InputEvent::pointer_move(pos))]);
+    }
+
+    fn pointer_up(&self, event: &web_sys::PointerEvent) {
+        let pos = self.position(event);
+        let _ = self.canvas.release_pointer_capture(event.pointer_id());
+        match self.take_touch(event) {
+            Some(touch) => {
+                let panned = touch.pan.is_panning();
+                let tapped_text = !panned
+                    && self.text_regions.borrow().iter().any(|region| region.contains(pos));
+                if tapped_text {
+                    if let Some(target) = self.text_target.borrow().as_ref() {
+                        let _ = target.focus();
+                    }
+                }
+                // A pan isn't a tap
+                let released = if panned {
+                    InputEvent::pointer_cancel()
+                } else {
+                    InputEvent::pointer_up(pos, PointerButton::Primary)
+                };
+                self.push_from(
+                    event,
+                    [
+                        released,
+                        // Fingers don't hover once lifted
+                        InputEvent::pointer_move(OFF_CANVAS),
+                    ],
+                );
+            }
+            None => self.push_from(event, [InputEvent::pointer_up(pos, mouse_button(event))]),
+        }
+    }
+
+    /// Files are dragged over the canvas; only their drop reveals what they are.
+    fn hover_files(&self, event: &web_sys::DragEvent) {
+        let Some(data) = event.data_transfer() else {
+            return;
+        };
+        // Dragged text or links aren't files
+        if !data.types().includes(&JsValue::from_str("Files"), 0) {
+            return;
+        }
+        data.set_drop_effect("copy");
+        let pos = Vec2::new(event.offset_x() as f32, event.offset_y() as f32) * self.scale.get();
+        self.push([InputEvent::FileHover { pos }]);
+    }
+
+    /// Read the dropped files, then deliver them where they were dropped.
+    fn drop_files(self: Rc<Self>, event: &web_sys::DragEvent) {
+        let Some(files) = event.data_transfer().and_then(|data| data.files()) else {
+            return;
+        };
+        let pos = Vec2::new(event.offset_x() as f32, event.offset_y() as f32) * self.scale.get();
+        wasm_bindgen_futures::spawn_local(async move {
+            let files = read_files(files).await;
+            if !files.is_empty() {
+                self.push([InputEvent::FileDrop { pos, files }]);
+            }
+        });
+    }
+
+    /// The browser took the pointer, e.g. to scroll the page.
+    fn pointer_cancel(&self, event: &web_sys::PointerEvent) {
+        self.take_touch(event);
+        self.push_from(
+            event,
+            [
+                InputEvent::pointer_cancel(),
+                InputEvent::pointer_move(OFF_CANVAS),
+            ],
+        );
+    }
+
+    /// End the touch gesture if `event` belongs to it.
+    fn take_touch(&self, event: &web_sys::PointerEvent) -> Option<TouchGesture> {
+        let touch = self.touch.get()?;
+        if touch.pointer_id != event.pointer_id() {
+            return None;
+        }
+        self.touch.set(None);
+        Some(touch)
+    }
+}
+
+/// Read `files` into memory, skipping any that fail.
+#[cfg(target_arch = "wasm32")]
+async fn read_files(files: web_sys::FileList) -> Vec<DroppedFile> {
+    let mut read = Vec::new();
+    for index in 0..files.length() {
+        let Some(file) = files.get(index) else {
+            continue;
+        };
+        match wasm_bindgen_futures::JsFuture::from(file.array_buffer()).await {
+            Ok(buffer) => {
+                let mime = file.type_();
+                read.push(DroppedFile {
+                    name: file.name(),
+                    mime: (!mime.is_empty()).then_some(mime),
+                    contents: FileContents::Bytes(js_sys::Uint8Array::new(&buffer).to_vec()),
+                });
+            }
+            Err(err) => log::warn!("failed to read {}: {err:?}", file.name()),
+        }
+    }
+    read
+}
+
+/// Let the user pick files, calling `callback` with their contents.
+///
+/// `accept` uses the syntax of the file input's `accept` attribute, e.g.
+/// `"image/*,.pdf"`, or `""` for any file. Browsers only open the dialog
+/// during a user gesture such as a click, and `callback` isn't called if
+/// the user cancels.
+#[cfg(target_arch = "wasm32")]
+pub fn open_file_dialog(
+    accept: &str,
+    multiple: bool,
+    callback: impl FnOnce(Vec<DroppedFile>) + 'static,
+) {
+    if let Err(err) = show_file_dialog(accept, multiple, callback) {
+        log::warn!("failed to open the file dialog: {err:?}");
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_file_dialog(
+    accept: &str,
+    multiple: bool,
+    callback: impl FnOnce(Vec<DroppedFile>) + 'static,
+) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let body = document.body().ok_or_else(|| JsValue::from_str("no body"))?;
+    let input: web_sys::HtmlInputElement = document.create_element("input")?.dyn_into()?;
+    input.set_type("file");
+    input.set_accept(accept);
+    input.set_multiple(multiple);
+    input.set_attribute("style", "display:none")?;
+    // Some browsers only open dialogs for inputs in the document
+    body.append_child(&input)?;
+
+    let on_change = {
+        let input = input.clone();
+        Closure::once_into_js(move || {
+            input.remove();
+            if let Some(files) = input.files() {
+                wasm_bindgen_futures::spawn_local(async move {
+                    callback(read_files(files).await);
+                });
+            }
+        })
+    };
+    let on_cancel = {
+        let input = input.clone();
+        Closure::once_into_js(move || input.remove())
+    };
+    input.add_event_listener_with_callback("change", on_change.unchecked_ref())?;
+    input.add_event_listener_with_callback("cancel", on_cancel.unchecked_ref())?;
+    input.click();
+    Ok(())
+}
+
+/// The mouse, finger or pen `event` comes from.
+#[cfg(target_arch = "wasm32")]
+fn pointer_of(event: &web_sys::PointerEvent) -> Pointer {
+    match event.pointer_type().as_str() {
+        "touch" => Pointer::touch(event.pointer_id() as u64, event.is_primary()),
+        "pen" => Pointer::pen(),
+        _ => Pointer::MOUSE,
+    }
+}
+
+/// The modifier keys held during `event`.
+#[cfg(target_arch = "wasm32")]
+fn modifiers_of(event: &web_sys::MouseEvent) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    modifiers.set(Modifiers::SHIFT, event.shift_key());
+    modifiers.set(Modifiers::CONTROL, event.ctrl_key());
+    modifiers.set(Modifiers::ALT, event.alt_key());
+    modifiers.set(Modifiers::META, event.meta_key());
+    modifiers
+}
+
+#[cfg(target_arch = "wasm32")]
+fn mouse_button(event: &web_sys::PointerEvent) -> PointerButton {
+    match event.button() {
+        1 => PointerButton::Auxiliary,
+        2 => PointerButton::Secondary,
+        _ => PointerButton::Primary,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for PointerInput {
+    fn drop(&mut self) {
+        for (name, listener) in &self.listeners {
+            let _ = self
+                .shared
+                .canvas
+                .remove_event_listener_with_callback(name, listener.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// Style of the text entry field: invisible, but focusable and on screen so
+/// browsers place IME windows and soft keyboards near it. The 16px font
+/// keeps iOS from zooming the page when it's focused.
+#[cfg(target_arch = "wasm32")]
+const TEXT_ENTRY_STYLE: &str = "position:fixed;width:1px;height:1px;margin:0;padding:0;\
+    border:0;outline:none;resize:none;overflow:hidden;opacity:0;pointer-events:none;\
+    font-size:16px;white-space:pre";
+
+/// Hidden text field that receives keyboard, IME and soft-keyboard input
+/// for spark's text widgets.
+///
+/// The canvas isn't editable, so browsers neither run IME composition on
+/// it nor show a soft keyboard for it. While a text widget has focus this
+/// off-screen `<textarea>` is focused at its caret instead, and its key,
+/// `input` and composition events become `InputEvent`s.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct TextEntry {
+    shared: Rc<TextEntryShared>,
+    listeners: Vec<(&'static str, Closure<dyn FnMut(web_sys::Event)>)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+struct TextEntryShared {
+    area: web_sys::HtmlTextAreaElement,
+    canvas: HtmlCanvasElement,
+    document: web_sys::Document,
+    /// Translated events waiting for the app.
+    events: RefCell<Vec<InputEvent>>,
+    /// A text widget has focus, so the field should too.
+    active: Cell<bool>,
+    wake: Box<dyn Fn()>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl TextEntry {
+    /// Add the field to the page, calling `wake` after each event.
+    pub fn new(canvas: HtmlCanvasElement, wake: impl Fn() + 'static) -> Result<Self, JsValue> {
+        let document = canvas
+            .owner_document()
+            .ok_or_else(|| JsValue::from_str("no document"))?;
+        let body = document.body().ok_or_else(|| JsValue::from_str("no body"))?;
+        let area: web_sys::HtmlTextAreaElement = document.create_element("textarea")?.dyn_into()?;
+        for (name, value) in [
+            ("autocapitalize", "off"),
+            ("autocomplete", "off"),
+            ("autocorrect", "off"),
+            ("spellcheck", "false"),
+            ("tabindex", "-1"),
+            ("aria-hidden", "true"),
+            ("style", TEXT_ENTRY_STYLE),
+        ] {
+            area.set_attribute(name, value)?;
+        }
+        body.append_child(&area)?;
+
+        let mut entry = Self {
+            shared: Rc::new(TextEntryShared {
+                area,
+                canvas,
+                document,
+                events: RefCell::new(Vec::new()),
+                active: Cell::new(false),
+                wake: Box::new(wake),
+            }),
+            listeners: Vec::new(),
+        };
+        let handlers: [(&'static str, fn(&TextEntryShared, &web_sys::Event)); 7] = [
+            ("keydown", |shared, event| shared.key(event, true)),
+            ("keyup", |shared, event| shared.key(event, false)),
+            ("input", TextEntryShared::input),
+            ("compositionstart", |shared, event| {
+                shared.composition(event, CompositionState::Start)
+            }),
+            ("compositionupdate", |shared, event| {
+                shared.composition(event, CompositionState::Update)
+            }),
+            ("compositionend", |shared, event| {
+                shared.composition(event, CompositionState::End)
+            }),
+            ("blur", |shared, _| {
+                // Focus went elsewhere; take it back on the next caret report
+                shared.active.set(false);
+            }),
+        ];
+        for (name, handler) in handlers {
+            let shared = Rc::downgrade(&entry.shared);
+            let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+                if let Some(shared) = shared.upgrade() {
+                    handler(&shared, &event);
+                }
+            });
+            entry
+                .shared
+                .area
+                .add_event_listener_with_callback(name, listener.as_ref().unchecked_ref())?;
+            entry.listeners.push((name, listener));
+        }
+        Ok(entry)
+    }
+
+    /// The field, for focusing it from a user gesture and listening to its pastes.
+    pub fn element(&self) -> web_sys::HtmlElement {
+        self.shared.area.clone().into()
+    }
+
+    /// Focus the field at `caret`, in CSS pixels from the canvas's top-left,
+    /// or give the focus back to the canvas when no text widget has it.
+    pub fn set_caret(&self, caret: Option<Rect>) {
+        let shared = &self.shared;
+        let Some(caret) = caret else {
+            if shared.active.replace(false) && shared.has_focus() {
+                let _ = shared.canvas.focus();
+            }
+            return;
+        };
+
+        // IME windows and soft keyboards follow the field
+        let bounds = shared.canvas.get_bounding_client_rect();
+        let style = shared.area.style();
+        let _ = style.set_property("left", &format!("{}px", bounds.left() + caret.x as f64));
+        let _ = style.set_property("top", &format!("{}px", bounds.top() + caret.y as f64));
+        let _ = style.set_property("height", &format!("{}px", caret.height.max(1.0)));
+
+        if shared.active.get() {
+            return;
+        }
+        // Don't take the focus from the rest of the page
+        let canvas: &web_sys::Element = shared.canvas.as_ref();
+        let ours = shared.document.active_element().map_or(true, |focused| {
+            focused == *canvas || shared.document.body().is_some_and(|body| *body == focused)
+        });
+        if ours && shared.area.focus().is_ok() {
+            shared.active.set(true);
+        }
+    }
+
+    /// Take the events translated since the last call.
+    pub fn take_events(&self) -> Vec<InputEvent> {
+        std::mem::take(&mut *self.shared.events.borrow_mut())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl TextEntryShared {
+    fn has_focus(&self) -> bool {
+        let area: &web_sys::Element = self.area.as_ref();
+        self.document.active_element().is_some_and(|focused| focused == *area)
+    }
+
+    fn push(&self, event: InputEvent) {
+        self.events.borrow_mut().push(event);
+        (self.wake)();
+    }
+
+    fn key(&self, event: &web_sys::Event, down: bool) {
+        let Some(event) = event.dyn_ref::<web_sys::KeyboardEvent>() else {
+            return;
+        };
+        // Keys that are part of a composition belong to the IME
+        if event.is_composing() || event.key_code() == 229 {
+            return;
+        }
+        let Ok(key) = event.key().parse::<Key>() else {
+            return;
+        };
+        let code = event.code().parse().unwrap_or(Code::Unidentified);
+        let mut modifiers = Modifiers::empty();
+        modifiers.set(Modifiers::SHIFT, event.shift_key());
+        modifiers.set(Modifiers::CONTROL, event.ctrl_key());
+        modifiers.set(Modifiers::ALT, event.alt_key());
+        modifiers.set(Modifiers::META, event.meta_key());
+        // Editing keys act on our widget, not the field. Text arrives as
+        // `input` events, and shortcuts keep their default so the browser
+        // still fires paste events.
+        if down && matches!(key, Key::Named(_)) {
+            event.prevent_default();
+        }
+        let mut keyboard = if down {
+            KeyboardEvent::key_down(key, code)
+        } else {
+            KeyboardEvent::key_up(key, code)
+        };
+        keyboard.modifiers = modifiers;
+        keyboard.repeat = event.repeat();
+        self.push(if down {
+            InputEvent::KeyDown { event: keyboard }
+        } else {
+            InputEvent::KeyUp { event: keyboard }
+        });
+    }
+
+    fn input(&self, event: &web_sys::Event) {
+        let Some(event) = event.dyn_ref::<web_sys::InputEvent>() else {
+            return;
+        };
+        // Composition events carry composed text
+        if event.is_composing() {
+            return;
+        }
+        let text = self.area.value();
+        self.area.set_value("");
+        match event.input_type().as_str() {
+            // The clipboard delivers pastes; compositions end on their own
+            "insertFromPaste" | "insertFromDrop" | "insertCompositionText"
+            | "insertFromComposition" | "deleteCompositionText" => {}
+            _ if !text.is_empty() => self.push(InputEvent::TextInput { text }),
+            _ => {}
+        }
+    }
+
+    fn composition(&self, event: &web_sys::Event, state: CompositionState) {
+        let data = event
+            .dyn_ref::<web_sys::CompositionEvent>()
+            .and_then(|event| event.data())
+            .unwrap_or_default();
+        if state == CompositionState::End {
+            self.area.set_value("");
+        }
+        self.push(InputEvent::Composition {
+            event: CompositionEvent { state, data },
+        });
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for TextEntry {
+    fn drop(&mut self) {
+        let area = &self.shared.area;
+        for (name, listener) in &self.listeners {
+            let _ = area.remove_event_listener_with_callback(name, listener.as_ref().unchecked_ref());
+        }
+        area.remove();
+    }
+}
+
+/// Route hooks registered with [`on_route_change`] and the app's wake-up.
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+struct Routes {
+    callbacks: Vec<Rc<dyn Fn(&str)>>,
+    wake: Option<Rc<dyn Fn()>>,
+    changed: bool,
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    static ROUTES: RefCell<Routes> = RefCell::default();
+}
+
+/// Mark the route changed and wake the app to deliver it.
+#[cfg(target_arch = "wasm32")]
+fn route_changed() {
+    let wake = ROUTES.with(|routes| {
+        let mut routes = routes.borrow_mut();
+        routes.changed = true;
+        routes.wake.clone()
+    });
+    if let Some(wake) = wake {
+        wake();
+    }
+}
+
+/// The page's current route, taken from the URL hash.
+///
+/// `https://example.com/#/settings` gives `"/settings"`; a URL without a
+/// hash gives `"/"`. Routes live in the hash so the app works from any
+/// static file server without rewrite rules.
+#[cfg(target_arch = "wasm32")]
+pub fn current_route() -> String {
+    let hash = web_sys::window()
+        .and_then(|window| window.location().hash().ok())
+        .unwrap_or_default();
+    match hash.strip_prefix('#').unwrap_or(&hash) {
+        "" => "/".to_string(),
+        route => route.to_string(),
+    }
+}
+
+/// Navigate to `route`, adding a browser history entry.
+///
+/// The back and forward buttons return to earlier routes. Callbacks from
+/// [`on_route_change`] run on the UI thread, and apps started with
+/// [`App::run_reloadable`](crate::App::run_reloadable) rebuild their UI so
+/// it can follow [`current_route`].
+#[cfg(target_arch = "wasm32")]
+pub fn push_route(route: &str) {
+    if route == current_route() {
+        return;
+    }
+    let Some(history) = web_sys::window().and_then(|window| window.history().ok()) else {
+        return;
+    };
+    if let Err(err) = history.push_state_with_url(&JsValue::NULL, "", Some(&format!("#{route}"))) {
+        log::warn!("failed to push route {route}: {err:?}");
+        return;
+    }
+    // Pushing a history entry doesn't fire popstate
+    route_changed();
+}
+
+/// Call `callback` with the new route whenever it changes.
+///
+/// Covers [`push_route`], the back and forward buttons, and links or
+/// address bar edits that change the hash. Callbacks run on the UI thread
+/// between events, before the UI is rebuilt.
+#[cfg(target_arch = "wasm32")]
+pub fn on_route_change(callback: impl Fn(&str) + 'static) {
+    ROUTES.with(|routes| routes.borrow_mut().callbacks.push(Rc::new(callback)));
+}
+
+/// Watches the page's history for route changes and wakes the app.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct RouteWatcher {
+    last: RefCell<String>,
+    listener: Closure<dyn FnMut()>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl RouteWatcher {
+    /// Listen for history navigation, calling `wake` when the route may have changed.
+    pub fn new(wake: impl Fn() + 'static) -> Result<Self, JsValue> {
+        let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+        let listener = Closure::<dyn FnMut()>::new(route_changed);
+        // Back and forward fire popstate; editing the hash also fires hashchange
+        for name in ["popstate", "hashchange"] {
+            window.add_event_listener_with_callback(name, listener.as_ref().unchecked_ref())?;
+        }
+        ROUTES.with(|routes| routes.borrow_mut().wake = Some(Rc::new(wake)));
+        Ok(Self {
+            last: RefCell::new(current_route()),
+            listener,
+        })
+    }
+
+    /// The new route, if it changed since the last call.
+    pub fn take_change(&self) -> Option<String> {
+        let changed = ROUTES.with(|routes| std::mem::take(&mut routes.borrow_mut().changed));
+        if !changed {
+            return None;
+        }
+        let route = current_route();
+        if *self.last.borrow() == route {
+            return None;
+        }
+        *self.last.borrow_mut() = route.clone();
+        Some(route)
+    }
+
+    /// Run the [`on_route_change`] callbacks.
+    pub fn notify(&self, route: &str) {
+        // Cloned out so callbacks can register more callbacks or push routes
+        let callbacks = ROUTES.with(|routes| routes.borrow().callbacks.clone());
+        for callback in callbacks {
+            callback(route);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for RouteWatcher {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            for name in ["popstate", "hashchange"] {
+                let _ = window
+                    .remove_event_listener_with_callback(name, self.listener.as_ref().unchecked_ref());
+            }
+        }
+        ROUTES.with(|routes| routes.borrow_mut().wake = None);
+    }
+}
+
 /// Macro to set up web entry point.
 #[cfg(target_arch = "wasm32")]
 #[macro_export]
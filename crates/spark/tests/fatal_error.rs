@@ -0,0 +1,32 @@
+//! A fatal startup error comes back from `App::run` instead of panicking,
+//! and reaches the error callback no more than once.
+//!
+//! Runs without the test harness, since winit only creates event loops on
+//! the main thread.
+
+use spark::prelude::*;
+use spark::WgpuOptions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn main() {
+    let reported = Rc::new(RefCell::new(Vec::new()));
+    let seen = reported.clone();
+    // No adapter supports every feature
+    let options = WgpuOptions {
+        required_features: wgpu::Features::all(),
+        ..Default::default()
+    };
+    let result = App::new()
+        .with_wgpu_options(options)
+        .on_error(move |err| seen.borrow_mut().push(err.to_string()))
+        .run(|| Box::new(Container::new()));
+
+    let err = result.expect_err("startup should fail");
+    // Without a display the event loop fails before any callback can run
+    let reported = reported.borrow();
+    assert!(reported.len() <= 1, "reported {} times", reported.len());
+    if let Some(message) = reported.first() {
+        assert_eq!(message, &err.to_string());
+    }
+}
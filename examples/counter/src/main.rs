@@ -0,0 +1,103 @@
+//! Counter - Shared state with signals
+//!
+//! The buttons change a `Signal`, and the display watches it while
+//! painting, so every change repaints it without any manual bookkeeping.
+
+use spark::layout::WidgetId;
+use spark::prelude::*;
+use spark::text::TextStyle;
+use spark::widgets::PaintContext;
+
+fn main() -> Result<(), AppError> {
+    #[cfg(target_arch = "wasm32")]
+    spark::init_web();
+    #[cfg(not(target_arch = "wasm32"))]
+    env_logger::init();
+
+    App::new()
+        .with_title("Counter - Spark")
+        .with_size(480, 320)
+        .with_theme_mode(ThemeMode::Dark)
+        .run(build_ui)
+}
+
+fn build_ui() -> Box<dyn Widget> {
+    let count = Signal::new(0);
+    let step = |delta: i32| {
+        let count = count.clone();
+        move || count.update(|n| *n += delta)
+    };
+
+    Box::new(
+        Container::new()
+            .fill()
+            .center()
+            .gap(24.0)
+            .background_token(ColorToken::Background)
+            .child(CounterDisplay::new(count.clone()))
+            .child(
+                Container::new()
+                    .row()
+                    .gap(12.0)
+                    // Screen readers would otherwise read out "minus" and "plus"
+                    .child(
+                        Button::new("-")
+                            .accessibility_label("Decrease")
+                            .on_click(step(-1)),
+                    )
+                    .child(Button::new("Reset").on_click({
+                        let count = count.clone();
+                        move || count.set(0)
+                    }))
+                    .child(
+                        Button::new("+")
+                            .accessibility_label("Increase")
+                            .on_click(step(1)),
+                    ),
+            ),
+    )
+}
+
+/// Shows the current count in large type.
+struct CounterDisplay {
+    id: WidgetId,
+    count: Signal<i32>,
+}
+
+impl CounterDisplay {
+    fn new(count: Signal<i32>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            count,
+        }
+    }
+}
+
+impl Widget for CounterDisplay {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            size: taffy::Size {
+                width: taffy::Dimension::length(160.0),
+                height: taffy::Dimension::length(72.0),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let count = ctx.watch(&self.count);
+        let theme = ctx.theme();
+        let style = TextStyle::default()
+            .with_size(theme.typography.display)
+            .with_color(theme.colors.text);
+        ctx.draw_text_centered(&count.to_string(), &style, ctx.bounds());
+    }
+}
@@ -2,17 +2,20 @@
 
 use spark::prelude::*;
 
-fn main() {
+fn main() -> Result<(), AppError> {
     env_logger::init();
 
     App::new()
         .with_title("Spark Demo")
         .with_size(800, 600)
         .with_background(Color::from_hex(0x1F2937)) // Dark background
-        .run(build_ui);
+        .run(build_ui)
 }
 
 fn build_ui() -> Box<dyn Widget> {
+    let discard_dialog = ModalHandle::new();
+    let open_dialog = discard_dialog.clone();
+
     Box::new(
         Container::new()
             .fill()
@@ -20,12 +23,43 @@ fn build_ui() -> Box<dyn Widget> {
             .center()
             .gap(16.0)
             .background(Color::from_hex(0x1F2937))
+            .child(
+                MenuBar::new()
+                    .menu("File", |m| {
+                        m.add(MenuBarItem::new("New", || println!("New")).shortcut("Mod+N"))
+                            .add(MenuBarItem::new("Open...", || println!("Open")).shortcut("Mod+O"))
+                            .submenu("Open Recent", |m| {
+                                m.item("notes.md", || println!("Open notes.md"))
+                                    .item("todo.md", || println!("Open todo.md"))
+                            })
+                            .separator()
+                            .add(MenuBarItem::new("Save", || println!("Save")).enabled(false))
+                    })
+                    .menu("View", |m| {
+                        m.add(MenuBarItem::checkable("Show Sidebar", true, |shown| {
+                            println!("Sidebar shown: {shown}")
+                        }))
+                    }),
+            )
             .child(
                 // Header
                 Container::new()
+                    .row()
+                    .gap(12.0)
                     .padding(16.0)
                     .background(Color::from_hex(0x374151))
-                    .corner_radius(12.0),
+                    .corner_radius(12.0)
+                    .elevation(4.0)
+                    .child(Icon::new(IconName::Search).color(Color::WHITE))
+                    .child(Icon::new(IconName::Plus).color(Color::WHITE))
+                    .child(Icon::new(IconName::Gear).color(Color::WHITE))
+                    .child(
+                        Avatar::new()
+                            .initials("GS")
+                            .size(32.0)
+                            .status(AvatarStatus::Online)
+                            .accessibility_label("Signed in"),
+                    ),
             )
             .child(
                 // Main content area
@@ -47,8 +81,11 @@ fn build_ui() -> Box<dyn Widget> {
                                     .background(Color::from_hex(0x3B82F6)),
                             )
                             .child(
-                                Button::new("Settings")
-                                    .background(Color::from_hex(0x6B7280)),
+                                Badge::new(
+                                    Button::new("Settings")
+                                        .background(Color::from_hex(0x6B7280)),
+                                )
+                                .count(3),
                             )
                             .child(
                                 Button::new("Profile")
@@ -64,11 +101,21 @@ fn build_ui() -> Box<dyn Widget> {
                             .background(Color::from_hex(0x374151))
                             .corner_radius(8.0)
                             .flex_grow(1.0)
+                            .child(
+                                Breadcrumb::new(["Home", "Projects", "Spark", "Widgets", "Forms"])
+                                    .on_click(|index| println!("Breadcrumb: {index}")),
+                            )
                             .child(
                                 // Form
                                 Container::new()
                                     .column()
                                     .gap(12.0)
+                                    .child(
+                                        SearchInput::new()
+                                            .placeholder("Search...")
+                                            .on_change(|query| println!("Searching: {query}"))
+                                            .on_search(|query| println!("Search: {query}")),
+                                    )
                                     .child(
                                         TextInput::new()
                                             .placeholder("Enter your name...")
@@ -77,6 +124,70 @@ fn build_ui() -> Box<dyn Widget> {
                                         TextInput::new()
                                             .placeholder("Enter your email...")
                                     )
+                                    .child(
+                                        ChipInput::new()
+                                            .chips(["rust", "gpu"])
+                                            .placeholder("Add a tag...")
+                                            .on_add(|tag| println!("Tag added: {tag}"))
+                                            .on_remove(|index| println!("Tag removed: {index}")),
+                                    )
+                                    .child(DropZone::new("Drop files here").on_drop(|files| {
+                                        for file in files {
+                                            println!("Dropped: {}", file.name);
+                                        }
+                                    }))
+                                    .child(
+                                        Checkbox::new("Remember me")
+                                            .on_change(|checked| println!("Remember me: {checked}")),
+                                    )
+                                    .child(
+                                        RadioGroup::new()
+                                            .row()
+                                            .gap(16.0)
+                                            .child(RadioButton::new("Email"))
+                                            .child(RadioButton::new("Phone"))
+                                            .child(RadioButton::new("Fax").disabled(true))
+                                            .selected(0)
+                                            .on_select(|index| println!("Contact by option {index}")),
+                                    )
+                                    .child(
+                                        Switch::new()
+                                            .label("Notifications")
+                                            .checked(true)
+                                            .on_change(|on| println!("Notifications: {on}")),
+                                    )
+                                    .child(
+                                        SegmentedControl::new(["Day", "Week", "Month"])
+                                            .selected(1)
+                                            .on_change(|index| println!("View {index}")),
+                                    )
+                                    .child(
+                                        Select::new(["Small", "Medium", "Large"])
+                                            .selected(1)
+                                            .on_change(|_, size| println!("Size: {size}")),
+                                    )
+                                    .child(ProgressBar::new().value(0.6))
+                                    .child(ProgressBar::new().indeterminate(true))
+                                    .child(
+                                        Container::new()
+                                            .row()
+                                            .gap(16.0)
+                                            .child(ProgressRing::new().value(0.7).thickness(6.0))
+                                            .child(ProgressRing::new().indeterminate(true)),
+                                    )
+                                    .child(files_table())
+                                    .child(
+                                        Collapsible::new(
+                                            "Advanced",
+                                            Container::new()
+                                                .column()
+                                                .gap(8.0)
+                                                .child(Checkbox::new("Verbose logging"))
+                                                .child(Checkbox::new("Beta features")),
+                                        )
+                                        .expanded(false),
+                                    )
+                                    .child(Divider::horizontal())
                                     .child(
                                         Container::new()
                                             .row()
@@ -85,9 +196,11 @@ fn build_ui() -> Box<dyn Widget> {
                                                 Button::new("Submit")
                                                     .background(Color::from_hex(0x10B981)),
                                             )
+                                            .child(Spacer::new())
                                             .child(
                                                 Button::new("Cancel")
-                                                    .background(Color::from_hex(0xEF4444)),
+                                                    .background(Color::from_hex(0xEF4444))
+                                                    .on_click(move || open_dialog.open()),
                                             ),
                                     ),
                             )
@@ -101,11 +214,81 @@ fn build_ui() -> Box<dyn Widget> {
                                     .child(color_box(0x10B981)) // Green
                                     .child(color_box(0x3B82F6)) // Blue
                                     .child(color_box(0x8B5CF6)) // Purple
-                                    .child(color_box(0xEC4899)), // Pink
+                                    .child(color_box(0xEC4899)) // Pink
+                                    .child(
+                                        Image::new(gradient(48))
+                                            .corner_radius(8.0)
+                                            .accessibility_label("Gradient swatch"),
+                                    )
+                                    .child(sparkline()),
                             ),
                     ),
+            )
+            .child(discard_modal(discard_dialog)),
+    )
+}
+
+/// Confirmation shown by the form's Cancel button.
+fn discard_modal(handle: ModalHandle) -> Modal {
+    let keep = handle.clone();
+    let discard = handle.clone();
+    Modal::new(
+        Container::new()
+            .column()
+            .gap(16.0)
+            .padding(24.0)
+            .background(Color::from_hex(0x374151))
+            .corner_radius(12.0)
+            .child(Text::new("Discard your changes?").color(Color::WHITE))
+            .child(
+                Container::new()
+                    .row()
+                    .gap(8.0)
+                    .child(Button::new("Keep editing").on_click(move || keep.close()))
+                    .child(
+                        Button::new("Discard")
+                            .background(Color::from_hex(0xEF4444))
+                            .on_click(move || discard.close()),
+                    ),
             ),
     )
+    .with_handle(handle)
+    .accessibility_label("Discard changes")
+    .on_dismiss(|| println!("Kept editing"))
+}
+
+/// A square image fading from red to blue, generated instead of loaded.
+fn gradient(size: u32) -> ImageHandle {
+    let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+    for _y in 0..size {
+        for x in 0..size {
+            let t = x as f32 / (size - 1) as f32;
+            pixels.extend([((1.0 - t) * 255.0) as u8, 64, (t * 255.0) as u8, 255]);
+        }
+    }
+    ImageHandle::from_rgba(size, size, pixels).expect("pixel count matches the size")
+}
+
+/// A scrolling sine wave drawn point by point.
+fn sparkline() -> Canvas {
+    Canvas::new(|ctx, bounds| {
+        let color = ctx.theme().colors.primary;
+        let mid = bounds.y + bounds.height / 2.0;
+        let amplitude = bounds.height * 0.4;
+        let phase = ctx.elapsed_time * 3.0;
+        let wave = |x: f32| {
+            let t = (x - bounds.x) / bounds.width;
+            mid - (t * 12.0 + phase).sin() * amplitude
+        };
+        let step = bounds.width / 48.0;
+        let width = 2.0 * ctx.scale_factor;
+        for i in 0..48 {
+            let x = bounds.x + i as f32 * step;
+            ctx.draw_line((x, wave(x)), (x + step, wave(x + step)), width, color);
+        }
+    })
+    .size(120.0, 48.0)
+    .animate(true)
 }
 
 fn color_box(hex: u32) -> Container {
@@ -115,3 +298,17 @@ fn color_box(hex: u32) -> Container {
         .corner_radius(8.0)
 }
 
+fn files_table() -> Table {
+    Table::new()
+        .column("Name", ColumnWidth::Flex(1.0))
+        .column("Size", ColumnWidth::Fixed(80.0))
+        .rows(1_000, |row, column| match column {
+            0 => format!("file-{row}.txt"),
+            _ => format!("{} KB", row * 7 % 512),
+        })
+        .sorted(0, SortDirection::Ascending)
+        .on_sort(|column, direction| println!("Sort by column {column}: {direction:?}"))
+        .on_row_click(|row| println!("Selected file-{row}.txt"))
+        .on_row_double_click(|row| println!("Opened file-{row}.txt"))
+        .height(140.0)
+}
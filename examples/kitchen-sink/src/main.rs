@@ -2,42 +2,54 @@
 
 use spark::prelude::*;
 
-fn main() {
+fn main() -> Result<(), AppError> {
     env_logger::init();
 
-    App::new()
+    let app = App::new()
         .with_title("Kitchen Sink - Spark")
         .with_size(1200, 900)
-        .with_background(Color::from_hex(0x0F172A))
-        .run(build_ui);
+        .with_theme_mode(ThemeMode::Dark);
+    let handle = app.handle();
+    app.run(move || build_ui(handle))
 }
 
-fn build_ui() -> Box<dyn Widget> {
+/// Font sizes shared by both themes.
+fn typography() -> spark::widgets::Typography {
+    Theme::default().typography
+}
+
+fn build_ui(handle: AppHandle) -> Box<dyn Widget> {
     Box::new(
         Container::new()
             .fill()
             .row()
-            .background(Color::from_hex(0x0F172A))
-            .child(build_sidebar())
-            .child(build_main_area()),
+            .background_token(ColorToken::Background)
+            .child(build_sidebar(handle.clone()))
+            .child(build_main_area(handle)),
     )
 }
 
 /// Left sidebar with button gallery and text samples
-fn build_sidebar() -> Container {
+fn build_sidebar(handle: AppHandle) -> Container {
+    let typography = typography();
+    let mut dark = true;
+
     Container::new()
         .column()
         .gap(32.0) // Increased gap between major sections
         .padding(24.0)
         .width(250.0) // Increased width
         .fill_height()
-        .background(Color::from_hex(0x1E293B))
-        .child(
-            Text::new("Kitchen Sink")
-                .size(24.0)
-                .bold()
-                .color(Color::WHITE),
-        )
+        .background_token(ColorToken::Surface)
+        .child(Text::new("Kitchen Sink").size(typography.header).bold())
+        .child(Button::new("Toggle theme").on_click({
+            let handle = handle.clone();
+            move || {
+                dark = !dark;
+                handle.set_theme_mode(if dark { ThemeMode::Dark } else { ThemeMode::Light });
+            }
+        }))
+        .child(Button::new("Quit").on_click(move || handle.exit()))
         // Section: Button Gallery
         .child(
             Container::new()
@@ -45,41 +57,40 @@ fn build_sidebar() -> Container {
                 .gap(12.0)
                 .child(
                     Text::new("Buttons")
-                        .size(14.0)
+                        .size(typography.body)
                         .bold()
-                        .color(Color::from_hex(0x94A3B8)),
+                        .color_token(ColorToken::TextMuted),
                 )
                 .child(
                     Button::new("Default")
-                        .background(Color::from_hex(0x3B82F6))
                         .on_click(|| {
                             log::info!("Default button clicked!");
                         }),
                 )
                 .child(
                     Button::new("Success")
-                        .background(Color::from_hex(0x22C55E))
+                        .background_token(ColorToken::Success)
                         .on_click(|| {
                             log::info!("Success button clicked!");
                         }),
                 )
                 .child(
                     Button::new("Danger")
-                        .background(Color::from_hex(0xEF4444))
+                        .background_token(ColorToken::Danger)
                         .on_click(|| {
                             log::info!("Danger button clicked!");
                         }),
                 )
                 .child(
                     Button::new("Warning")
-                        .background(Color::from_hex(0xF59E0B))
+                        .background_token(ColorToken::Warning)
                         .on_click(|| {
                             log::info!("Warning button clicked!");
                         }),
                 )
                 .child(
                     Button::new("Secondary")
-                        .background(Color::from_hex(0x64748B))
+                        .background_token(ColorToken::Secondary)
                         .on_click(|| {
                             log::info!("Secondary button clicked!");
                         }),
@@ -92,31 +103,25 @@ fn build_sidebar() -> Container {
                 .gap(16.0)
                 .child(
                     Text::new("Typography")
-                        .size(14.0)
+                        .size(typography.body)
                         .bold()
-                        .color(Color::from_hex(0x94A3B8)),
+                        .color_token(ColorToken::TextMuted),
                 )
                 .child(
                     Text::new("Heading Text")
-                        .size(28.0)
-                        .bold()
-                        .color(Color::WHITE),
+                        .size(typography.display)
+                        .bold(),
                 )
                 .child(
                     Text::new("Body text example that might wrap if it is too long for the sidebar.")
-                        .size(16.0)
-                        .color(Color::from_hex(0xE2E8F0)),
+                        .size(typography.body_large),
                 )
-                .child(
-                    Text::new("Small caption text")
-                        .size(12.0)
-                        .color(Color::from_hex(0x94A3B8)),
-                ),
+                .child(Text::caption("Small caption text")),
         )
 }
 
 /// Main content area with scrollable sections
-fn build_main_area() -> Scroll {
+fn build_main_area(handle: AppHandle) -> Scroll {
     Scroll::new()
         .vertical()
         .content(
@@ -126,6 +131,7 @@ fn build_main_area() -> Scroll {
                 .padding(32.0)
                 .flex_grow(1.0)
                 .child(build_input_section())
+                .child(build_clipboard_section(handle))
                 .child(build_container_section())
                 .child(build_scroll_section()),
         )
@@ -140,45 +146,60 @@ fn build_input_section() -> Container {
             .gap(12.0)
             .child(TextInput::new().placeholder("Enter text..."))
             .child(TextInput::new().placeholder("Email address..."))
-            .child(TextInput::new().placeholder("Password...")),
+            .child(TextInput::new().placeholder("Password...").password(true)),
+    )
+}
+
+/// Copy and paste round trip through the system clipboard
+fn build_clipboard_section(handle: AppHandle) -> Container {
+    const SAMPLE: &str = "Copied from the Spark kitchen sink";
+
+    section(
+        "Clipboard",
+        Container::new()
+            .column()
+            .gap(12.0)
+            .child(Text::new(SAMPLE).color_token(ColorToken::TextMuted))
+            .child(Button::new("Copy text").on_click(move || {
+                handle.set_clipboard_text(SAMPLE);
+                log::info!("Copied {SAMPLE:?}");
+            }))
+            .child(TextInput::new().placeholder("Paste here with Ctrl+V / Cmd+V...")),
     )
 }
 
 /// Nested and overlapping containers section
 fn build_container_section() -> Container {
+    let typography = typography();
+
     Container::new()
         .column()
         .gap(16.0)
         .padding(24.0)
-        .background(Color::from_hex(0x1E293B))
+        .background_token(ColorToken::Surface)
         .corner_radius(12.0)
         .child(
             Text::new("Nested Containers")
-                .size(18.0)
-                .bold()
-                .color(Color::WHITE),
+                .size(typography.subheader)
+                .bold(),
         )
         // 3-level nesting
         .child(
             Container::new()
                 .padding(16.0)
-                .background(Color::from_hex(0x3B82F6).with_alpha(0.3))
+                .background_token(ColorToken::SurfaceAlt)
                 .corner_radius(8.0)
                 .child(
                     Container::new()
                         .padding(16.0)
-                        .background(Color::from_hex(0x22C55E).with_alpha(0.3))
+                        .background_token(ColorToken::Background)
                         .corner_radius(8.0)
                         .child(
                             Container::new()
                                 .padding(16.0)
-                                .background(Color::from_hex(0x8B5CF6).with_alpha(0.3))
+                                .background_token(ColorToken::SurfaceAlt)
                                 .corner_radius(8.0)
-                                .child(
-                                    Text::new("Level 3")
-                                        .size(14.0)
-                                        .color(Color::WHITE),
-                                ),
+                                .child(Text::new("Level 3").size(typography.body)),
                         ),
                 ),
         )
@@ -186,6 +207,7 @@ fn build_container_section() -> Container {
 
 /// Scrollable content section
 fn build_scroll_section() -> Container {
+    let typography = typography();
     let mut scroll_content = Container::new()
         .column()
         .gap(8.0);
@@ -196,17 +218,13 @@ fn build_scroll_section() -> Container {
             Container::new()
                 .padding(12.0)
                 .min_size(0.0, 40.0)
-                .background(if i % 2 == 0 {
-                    Color::from_hex(0x334155)
+                .background_token(if i % 2 == 0 {
+                    ColorToken::SurfaceAlt
                 } else {
-                    Color::from_hex(0x1E293B)
+                    ColorToken::Surface
                 })
                 .corner_radius(4.0)
-                .child(
-                    Text::new(&format!("Item {}", i + 1))
-                        .size(14.0)
-                        .color(Color::WHITE),
-                ),
+                .child(Text::new(format!("Item {}", i + 1)).size(typography.body)),
         );
     }
 
@@ -229,13 +247,12 @@ fn section(title: &str, content: Container) -> Container {
         .column()
         .gap(16.0)
         .padding(24.0)
-        .background(Color::from_hex(0x1E293B))
+        .background_token(ColorToken::Surface)
         .corner_radius(12.0)
         .child(
             Text::new(title)
-                .size(18.0)
-                .bold()
-                .color(Color::WHITE),
+                .size(typography().subheader)
+                .bold(),
         )
         .child(content)
 }
@@ -2,14 +2,15 @@
 
 use spark::prelude::*;
 
-fn main() {
+fn main() -> Result<(), AppError> {
     env_logger::init();
 
     App::new()
         .with_title("Layout Gallery - Spark")
         .with_size(1000, 800)
         .with_background(Color::from_hex(0x1F2937))
-        .run(build_ui);
+        // Ctrl/Cmd+R rebuilds the gallery without restarting
+        .run_reloadable(build_ui)
 }
 
 /// Creates a labeled test section
@@ -15,173 +15,257 @@ use spark_widgets::Container;
 use spark_native_apple::widgets::{
     NativeButton, NativeLabel, NativeProgressIndicator, NativeSlider, NativeSwitch, NativeTextField,
 };
+#[cfg(target_os = "macos")]
+use spark_native_apple::widgets::{
+    CheckboxState, NativeCheckbox, NativeComboBox, NativeDatePicker, NativeRadioGroup,
+    NativeScrollView, NativeSegmentedControl,
+};
 
-fn main() {
-    App::new()
+fn main() -> Result<(), AppError> {
+    let app = App::new()
         .with_title("Native Widget Demo")
         .with_size(900, 700)
-        .run(|| {
-            #[cfg(any(target_os = "macos", target_os = "ios"))]
-            {
-                Box::new(
+        .with_theme_mode(ThemeMode::Dark)
+        // Edit items reach the focused native text field
+        .with_menu(
+            Menu::new()
+                .submenu("File", |m| {
+                    m.item("Open…", "o", || println!("Open chosen"))
+                        .action("Export…", "e", "export")
+                })
+                .edit_menu(),
+        )
+        .bind_shortcut(Shortcut::new("Mod+K"), "command_palette")
+        .on_action(|action| println!("Action: {}", action.0));
+    // Native controls report changes outside any event context
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    let handle = app.handle();
+    app.run(move || {
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        {
+            // The slider drives this bar through its handle.
+            let progress = NativeProgressIndicator::new()
+                .min_value(0.0)
+                .max_value(100.0)
+                .value(50.0)
+                .width(250.0);
+            let progress_handle = progress.handle();
+
+            let content = Container::new()
+                .padding(30.0)
+                .gap(20.0)
+                .align_start() // Align children to start for proper layout
+                .child(
                     Container::new()
-                        .fill()
-                        .padding(30.0)
-                        .gap(20.0)
-                        .align_start() // Align children to start for proper layout
+                        .gap(16.0)
+                        .fill_width() // Fill width but let height be content-based
+                        .child(NativeLabel::new("Native macOS Widgets Demo"))
                         .child(
                             Container::new()
-                                .gap(16.0)
-                                .fill_width() // Fill width but let height be content-based
-                                .child(NativeLabel::new("Native macOS Widgets Demo").appearance("NSAppearanceNameAqua"))
+                                .gap(12.0)
+                                .fill_width()
+                                // Buttons section
                                 .child(
                                     Container::new()
-                                        .gap(12.0)
+                                        .gap(8.0)
                                         .fill_width()
-                                        // Buttons section
+                                        .child(NativeLabel::new("Buttons:"))
                                         .child(
                                             Container::new()
+                                                .row()
                                                 .gap(8.0)
-                                                .fill_width()
-                                                .child(NativeLabel::new("Buttons:"))
-                                                .child(
-                                                    Container::new()
-                                                        .row()
-                                                        .gap(8.0)
-                                                        .wrap() // Allow wrapping for smaller screens
-                                                        .child(NativeButton::new("Primary Button").appearance("NSAppearanceNameAqua").on_click(
-                                                            || {
-                                                                println!("Primary button clicked!");
-                                                            },
-                                                        ))
-                                                        .child(NativeButton::new("Secondary Button").appearance("NSAppearanceNameAqua").on_click(
-                                                            || {
-                                                                println!("Secondary button clicked!");
-                                                            },
-                                                        ))
-                                                ),
-                                        )
-                                        // Text input section
+                                                .wrap() // Allow wrapping for smaller screens
+                                                .child(NativeButton::new("Primary Button").on_click(
+                                                    || {
+                                                        println!("Primary button clicked!");
+                                                    },
+                                                ))
+                                                .child(NativeButton::new("Secondary Button").on_click(
+                                                    || {
+                                                        println!("Secondary button clicked!");
+                                                    },
+                                                ))
+                                        ),
+                                )
+                                // Text input section
+                                .child(
+                                    Container::new()
+                                        .gap(8.0)
+                                        .fill_width()
+                                        .child(NativeLabel::new("Text Input:"))
                                         .child(
-                                            Container::new()
-                                                .gap(8.0)
-                                                .fill_width()
-                                                .child(NativeLabel::new("Text Input:"))
-                                                .child(
-                                                    NativeTextField::new()
-                                                        .placeholder("Enter text here...")
-                                                        .width(300.0) // Customizable width
-                                                        .on_change(|text| {
-                                                            println!("Text changed: {}", text);
-                                                        }),
-                                                ),
-                                        )
-                                        // Slider section
+                                            NativeTextField::new()
+                                                .placeholder("Enter text here...")
+                                                .width(300.0) // Customizable width
+                                                .on_change(|text| {
+                                                    println!("Text changed: {}", text);
+                                                }),
+                                        ),
+                                )
+                                // Slider section
+                                .child(
+                                    Container::new()
+                                        .gap(8.0)
+                                        .fill_width()
+                                        .child(NativeLabel::new("Slider:"))
                                         .child(
-                                            Container::new()
-                                                .gap(8.0)
-                                                .fill_width()
-                                                .child(NativeLabel::new("Slider:"))
-                                                .child(
-                                                    NativeSlider::new()
-                                                        .min_value(0.0)
-                                                        .max_value(100.0)
-                                                        .value(50.0)
-                                                        .width(250.0) // Customizable width
-                                                        .on_change(|value| {
-                                                            println!("Slider value: {:.1}", value);
-                                                        }),
-                                                ),
-                                        )
-                                        // Switches section
+                                            NativeSlider::new()
+                                                .min_value(0.0)
+                                                .max_value(100.0)
+                                                .value(50.0)
+                                                .width(250.0) // Customizable width
+                                                .on_change(move |value| {
+                                                    println!("Slider value: {:.1}", value);
+                                                    progress_handle.set_value(value);
+                                                }),
+                                        ),
+                                )
+                                // Switches section
+                                .child(
+                                    Container::new()
+                                        .gap(8.0)
+                                        .fill_width()
+                                        .child(NativeLabel::new("Switches:"))
                                         .child(
                                             Container::new()
                                                 .gap(8.0)
-                                                .fill_width()
-                                                .child(NativeLabel::new("Switches:"))
                                                 .child(
                                                     Container::new()
-                                                        .gap(8.0)
+                                                        .row()
+                                                        .gap(12.0)
+                                                        .center()
                                                         .child(
-                                                            Container::new()
-                                                                .row()
-                                                                .gap(12.0)
-                                                                .center()
-                                                                .child(
-                                                                    NativeSwitch::new("Enable notifications")
-                                                                        .appearance("NSAppearanceNameAqua")
-                                                                        .checked(false)
-                                                                        .on_change(|checked| {
-                                                                            println!(
-                                                                                "Notifications: {}",
-                                                                                if checked { "enabled" } else { "disabled" }
-                                                                            );
-                                                                        }),
-                                                                )
-                                                                .child(NativeLabel::new("Enable notifications").appearance("NSAppearanceNameAqua")),
+                                                            NativeSwitch::new("Enable notifications")
+                                                                .checked(false)
+                                                                .on_change(|checked| {
+                                                                    println!(
+                                                                        "Notifications: {}",
+                                                                        if checked { "enabled" } else { "disabled" }
+                                                                    );
+                                                                }),
                                                         )
+                                                        .child(NativeLabel::new("Enable notifications")),
+                                                )
+                                                .child(
+                                                    Container::new()
+                                                        .row()
+                                                        .gap(12.0)
+                                                        .center()
                                                         .child(
-                                                            Container::new()
-                                                                .row()
-                                                                .gap(12.0)
-                                                                .center()
-                                                                .child(
-                                                                    NativeSwitch::new("Dark mode")
-                                                                        .appearance("NSAppearanceNameAqua")
-                                                                        .checked(true)
-                                                                        .on_change(|checked| {
-                                                                            println!(
-                                                                                "Dark mode: {}",
-                                                                                if checked { "on" } else { "off" }
-                                                                            );
-                                                                        }),
-                                                                )
-                                                                .child(NativeLabel::new("Dark mode").appearance("NSAppearanceNameAqua")),
-                                                        ),
+                                                            NativeSwitch::new("Dark mode")
+                                                                .checked(true)
+                                                                .on_change(move |checked| {
+                                                                    println!("Dark mode: {checked}");
+                                                                    // Native controls follow the theme
+                                                                    handle.set_theme_mode(if checked {
+                                                                        ThemeMode::Dark
+                                                                    } else {
+                                                                        ThemeMode::Light
+                                                                    });
+                                                                }),
+                                                        )
+                                                        .child(NativeLabel::new("Dark mode")),
                                                 ),
-                                        )
-                                        // Progress indicators section
+                                        ),
+                                )
+                                // Progress indicators section
+                                .child(
+                                    Container::new()
+                                        .gap(8.0)
+                                        .fill_width()
+                                        .child(NativeLabel::new("Progress Indicators:"))
                                         .child(
                                             Container::new()
-                                                .gap(8.0)
+                                                .gap(12.0)
                                                 .fill_width()
-                                                .child(NativeLabel::new("Progress Indicators:"))
+                                                .child(progress)
                                                 .child(
                                                     Container::new()
-                                                        .gap(12.0)
-                                                        .fill_width()
-                                                        .child(
-                                                            NativeProgressIndicator::new()
-                                                                .appearance("NSAppearanceNameAqua")
-                                                                .min_value(0.0)
-                                                                .max_value(100.0)
-                                                                .value(45.0)
-                                                                .width(250.0),
-                                                        )
-                                                        .child(
-                                                            Container::new()
-                                                                .row()
-                                                                .gap(8.0)
-                                                                .center()
-                                                                .child(NativeProgressIndicator::new().appearance("NSAppearanceNameAqua").indeterminate(true))
-                                                                .child(NativeLabel::new("Loading...").appearance("NSAppearanceNameAqua")),
-                                                        ),
+                                                        .row()
+                                                        .gap(8.0)
+                                                        .center()
+                                                        .child(NativeProgressIndicator::new().indeterminate(true))
+                                                        .child(NativeLabel::new("Loading...")),
                                                 ),
                                         ),
                                 ),
                         ),
-                )
-            }
+                );
 
-            #[cfg(not(any(target_os = "macos", target_os = "ios")))]
-            {
-                Box::new(
-                    Container::new()
-                        .fill()
-                        .center()
-                        .padding(20.0)
-                        .child(Button::new("Native widgets not available on this platform")),
-                )
-            }
-        });
+            // Controls that only exist on macOS
+            #[cfg(target_os = "macos")]
+            let content = content.child(
+                Container::new()
+                    .gap(8.0)
+                    .fill_width()
+                    .child(NativeLabel::new("Check Boxes (compare with the switches above):"))
+                    .child(
+                        NativeCheckbox::new("Remember me")
+                            .on_change(|state| {
+                                println!("Remember me: {state:?}");
+                            }),
+                    )
+                    .child(
+                        NativeCheckbox::new("Select all")
+                            .state(CheckboxState::Mixed)
+                            .on_change(|state| {
+                                println!("Select all: {state:?}");
+                            }),
+                    )
+                    .child(NativeLabel::new("Radio Buttons:"))
+                    .child(
+                        NativeRadioGroup::new()
+                            .options(vec!["Small".into(), "Medium".into(), "Large".into()])
+                            .selected(1)
+                            .on_change(|index| {
+                                println!("Size picked: {index}");
+                            }),
+                    )
+                    .child(NativeLabel::new("Segmented Control:"))
+                    .child(
+                        NativeSegmentedControl::new()
+                            .segments(vec!["Day".into(), "Week".into(), "Month".into()])
+                            .on_change(|index| {
+                                println!("Segment selected: {index}");
+                            }),
+                    )
+                    .child(NativeLabel::new("Combo Box:"))
+                    .child(
+                        NativeComboBox::new()
+                            .items(vec!["Helvetica".into(), "Menlo".into(), "SF Pro".into()])
+                            .placeholder("Font")
+                            .on_change(|text| {
+                                println!("Font: {text}");
+                            })
+                            .on_select(|index| {
+                                println!("Font picked: {index}");
+                            }),
+                    )
+                    .child(NativeLabel::new("Date:"))
+                    .child(NativeDatePicker::new().on_change(
+                        |seconds| {
+                            println!("Date: {seconds:.0} seconds since 1970");
+                        },
+                    )),
+            );
+
+            // AppKit scrolls the native widgets inside the scroll view.
+            #[cfg(target_os = "macos")]
+            let root = NativeScrollView::new().fill().content(content.fill_width());
+            #[cfg(target_os = "ios")]
+            let root = content.fill();
+            Box::new(root)
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+        {
+            Box::new(
+                Container::new()
+                    .fill()
+                    .center()
+                    .padding(20.0)
+                    .child(Button::new("Native widgets not available on this platform")),
+            )
+        }
+    })
 }
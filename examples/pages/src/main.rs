@@ -0,0 +1,71 @@
+//! Pages - Two pages behind URL hash routes
+//!
+//! On the web each page has its own URL (`#/` and `#/about`), and the
+//! browser's back and forward buttons move between them. Other platforms
+//! show the home page only.
+
+use spark::prelude::*;
+
+fn main() -> Result<(), AppError> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        spark::init_web();
+        spark::web::on_route_change(|route| log::info!("route changed to {route}"));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    env_logger::init();
+
+    App::new()
+        .with_title("Pages - Spark")
+        .with_size(800, 600)
+        .with_theme_mode(ThemeMode::Dark)
+        // Rebuilt on every route change
+        .run_reloadable(build_ui)
+}
+
+/// The route shown by the UI.
+fn current_route() -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        spark::web::current_route()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        "/".to_string()
+    }
+}
+
+fn navigate(route: &'static str) {
+    #[cfg(target_arch = "wasm32")]
+    spark::web::push_route(route);
+    #[cfg(not(target_arch = "wasm32"))]
+    log::info!("routes need a browser; staying on the home page instead of {route}");
+}
+
+fn page(title: &str, body: &str, link: &str, route: &'static str) -> Container {
+    Container::new()
+        .column()
+        .gap(16.0)
+        .padding(32.0)
+        .child(Text::new(title).size(24.0).bold())
+        .child(Text::new(body))
+        .child(Button::new(link).on_click(move || navigate(route)))
+}
+
+fn build_ui() -> Box<dyn Widget> {
+    let content = match current_route().as_str() {
+        "/about" => page(
+            "About",
+            "Press the browser's back button to return home.",
+            "Home",
+            "/",
+        ),
+        _ => page(
+            "Home",
+            "This page lives at #/. Open the about page, then use back and forward.",
+            "About",
+            "/about",
+        ),
+    };
+    Box::new(content.fill())
+}
@@ -0,0 +1,162 @@
+//! Selection - Picking rows with modifier clicks
+//!
+//! Click selects one file, Ctrl (Cmd on macOS) + click toggles one and
+//! Shift + click extends from the last plain click. The rows are wider than
+//! the window, so Shift + wheel scrolls them sideways.
+
+use spark::layout::WidgetId;
+use spark::prelude::*;
+use spark::text::TextStyle;
+use spark::widgets::{EventContext, PaintContext};
+
+/// Logical height of each row, including the status line.
+const ROW_HEIGHT: f32 = 32.0;
+
+fn main() -> Result<(), AppError> {
+    #[cfg(target_arch = "wasm32")]
+    spark::init_web();
+    #[cfg(not(target_arch = "wasm32"))]
+    env_logger::init();
+
+    App::new()
+        .with_title("Selection - Spark")
+        .with_size(480, 480)
+        .with_theme_mode(ThemeMode::Dark)
+        .run(build_ui)
+}
+
+fn build_ui() -> Box<dyn Widget> {
+    let files = (1..=40)
+        .map(|i| format!("photos/2026/summer/IMG_{:04}.jpg", 1000 + i * 7))
+        .collect();
+
+    Box::new(
+        Container::new()
+            .fill()
+            .column()
+            .padding(24.0)
+            .background_token(ColorToken::Background)
+            .child(
+                Scroll::new()
+                    .fill()
+                    .direction(ScrollDirection::Both)
+                    .content(FileList::new(files)),
+            ),
+    )
+}
+
+/// A status line over rows that select like a file manager's.
+struct FileList {
+    id: WidgetId,
+    files: Vec<String>,
+    selected: Vec<bool>,
+    /// Where Shift + click ranges start.
+    anchor: usize,
+}
+
+impl FileList {
+    fn new(files: Vec<String>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            selected: vec![false; files.len()],
+            files,
+            anchor: 0,
+        }
+    }
+
+    fn click(&mut self, row: usize, modifiers: Modifiers) {
+        let toggle = modifiers.primary();
+        if modifiers.shift() {
+            // Toggle-extending keeps what was selected outside the range
+            if !toggle {
+                self.selected.fill(false);
+            }
+            let (from, to) = (self.anchor.min(row), self.anchor.max(row));
+            self.selected[from..=to].fill(true);
+        } else if toggle {
+            self.selected[row] = !self.selected[row];
+            self.anchor = row;
+        } else {
+            self.selected.fill(false);
+            self.selected[row] = true;
+            self.anchor = row;
+        }
+    }
+}
+
+impl Widget for FileList {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            size: taffy::Size {
+                width: taffy::Dimension::length(720.0),
+                height: taffy::Dimension::length((self.files.len() + 1) as f32 * ROW_HEIGHT),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let theme = ctx.theme();
+        let bounds = ctx.bounds();
+        let row_height = ROW_HEIGHT * ctx.scale_factor;
+        let row = |i: usize| {
+            Rect::new(
+                bounds.x,
+                bounds.y + i as f32 * row_height,
+                bounds.width,
+                row_height,
+            )
+        };
+
+        let muted = TextStyle::default()
+            .with_size(theme.typography.caption)
+            .with_color(theme.colors.text_muted);
+        let count = self.selected.iter().filter(|&&selected| selected).count();
+        let status = format!("{count} of {} selected", self.files.len());
+        ctx.draw_text_aligned(&status, &muted, row(0), 0.0);
+
+        let text = TextStyle::default()
+            .with_size(theme.typography.body)
+            .with_color(theme.colors.text);
+        for (i, label) in self.files.iter().enumerate() {
+            let bounds = row(i + 1);
+            if self.selected[i] {
+                ctx.fill_rounded_rect(bounds, theme.colors.selection, theme.radius.sm);
+            } else if i % 2 == 0 {
+                ctx.fill_rounded_rect(bounds, theme.colors.surface, theme.radius.sm);
+            }
+            ctx.draw_text_aligned(label, &text, bounds, 12.0 * ctx.scale_factor);
+        }
+    }
+
+    fn event(&mut self, ctx: &mut EventContext, event: &InputEvent) -> EventResponse {
+        let InputEvent::PointerDown {
+            pos,
+            button: PointerButton::Primary,
+            modifiers,
+            ..
+        } = event
+        else {
+            return EventResponse::default();
+        };
+        let bounds = ctx.bounds();
+        let row = ((pos.y - bounds.y) / ROW_HEIGHT) as usize;
+        if !ctx.contains(*pos) || row == 0 || row > self.files.len() {
+            return EventResponse::default();
+        }
+        self.click(row - 1, *modifiers);
+        EventResponse {
+            handled: true,
+            repaint: true,
+            ..Default::default()
+        }
+    }
+}
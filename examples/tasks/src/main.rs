@@ -0,0 +1,136 @@
+//! Tasks - Background loading that reports back to the UI
+//!
+//! "Load" spawns a task that pretends to fetch rows over the network, then
+//! posts them to the UI thread, where they fill the list and screen readers
+//! announce how many arrived.
+
+use spark::layout::WidgetId;
+use spark::prelude::*;
+use spark::text::TextStyle;
+use spark::widgets::PaintContext;
+
+fn main() -> Result<(), AppError> {
+    #[cfg(target_arch = "wasm32")]
+    spark::init_web();
+    #[cfg(not(target_arch = "wasm32"))]
+    env_logger::init();
+
+    let app = App::new()
+        .with_title("Tasks - Spark")
+        .with_size(480, 480)
+        .with_theme_mode(ThemeMode::Dark);
+    let handle = app.handle();
+    app.run(move || build_ui(handle))
+}
+
+/// Stand-in for a network request.
+async fn fetch_rows(page: u32) -> Vec<String> {
+    // Task threads may block on desktop; the web has no threads to spare
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::sleep(std::time::Duration::from_millis(800));
+    (1..=8).map(|i| format!("Page {page}, row {i}")).collect()
+}
+
+fn build_ui(handle: AppHandle) -> Box<dyn Widget> {
+    let rows = Signal::new(Vec::new());
+    let status = Signal::new("Nothing loaded yet".to_string());
+    let ui = handle.sender();
+    let mut page = 0;
+
+    let load = {
+        let (rows, status) = (rows.clone(), status.clone());
+        move || {
+            page += 1;
+            status.set("Loading…".into());
+            let (ui, app) = (ui.clone(), handle.clone());
+            let (rows, status) = (rows.clone(), status.clone());
+            handle.spawn(async move {
+                let fetched = fetch_rows(page).await;
+                // Runs on the UI thread before the next frame
+                ui.post(move || {
+                    let loaded = format!("Loaded {} rows", fetched.len());
+                    // Screen readers hear it without leaving the button
+                    app.announce(loaded.clone(), Politeness::Polite);
+                    status.set(loaded);
+                    rows.set(fetched);
+                });
+            });
+        }
+    };
+
+    Box::new(
+        Container::new()
+            .fill()
+            .column()
+            .gap(16.0)
+            .padding(24.0)
+            .background_token(ColorToken::Background)
+            .child(Button::new("Load").on_click(load))
+            .child(RowList::new(status, rows)),
+    )
+}
+
+/// A status line over the loaded rows.
+struct RowList {
+    id: WidgetId,
+    status: Signal<String>,
+    rows: Signal<Vec<String>>,
+}
+
+impl RowList {
+    fn new(status: Signal<String>, rows: Signal<Vec<String>>) -> Self {
+        Self {
+            id: WidgetId::default(),
+            status,
+            rows,
+        }
+    }
+}
+
+impl Widget for RowList {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn style(&self, _theme: &Theme) -> taffy::Style {
+        taffy::Style {
+            flex_grow: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn paint(&self, ctx: &mut PaintContext) {
+        let theme = ctx.theme();
+        let bounds = ctx.bounds();
+        let row_height = 32.0 * ctx.scale_factor;
+        let row = |i: usize| {
+            Rect::new(
+                bounds.x,
+                bounds.y + i as f32 * row_height,
+                bounds.width,
+                row_height,
+            )
+        };
+
+        let muted = TextStyle::default()
+            .with_size(theme.typography.caption)
+            .with_color(theme.colors.text_muted);
+        let status = ctx.watch(&self.status);
+        ctx.draw_text_aligned(&status, &muted, row(0), 0.0);
+
+        let text = TextStyle::default()
+            .with_size(theme.typography.body)
+            .with_color(theme.colors.text);
+        for (i, label) in ctx.watch(&self.rows).iter().enumerate() {
+            let bounds = row(i + 1);
+            if i % 2 == 0 {
+                ctx.fill_rounded_rect(bounds, theme.colors.surface, theme.radius.sm);
+            }
+            ctx.draw_text_aligned(label, &text, bounds, 12.0 * ctx.scale_factor);
+        }
+    }
+}
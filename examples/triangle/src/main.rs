@@ -1,6 +1,9 @@
 use std::time::Instant;
 
-use spark_core::{pipeline::Pipeline, wgpu_init::init_wgpu};
+use spark_core::{
+    pipeline::Pipeline,
+    wgpu_init::{init_wgpu, WgpuOptions},
+};
 
 #[repr(C)]
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, Debug)]
@@ -37,7 +40,8 @@ impl winit::application::ApplicationHandler for App {
             Box::leak(Box::new(window));
         let window: &'static dyn winit::window::Window = &**window_leaked;
 
-        let (device, queue, surface_state) = pollster::block_on(init_wgpu(window));
+        let (device, queue, surface_state) =
+            pollster::block_on(init_wgpu(window, &WgpuOptions::default())).expect("initialize wgpu");
         let shader_src = include_str!("../../../assets/shaders/basic.wgsl");
         let pipeline: Pipeline<SceneUniform> = Pipeline::new(
             &device,
@@ -45,7 +49,8 @@ impl winit::application::ApplicationHandler for App {
             "vs_main",
             "fs_main",
             wgpu::TextureFormat::from(surface_state.config.format),
-        );
+        )
+        .expect("build pipeline");
 
         self.device = Some(device);
         self.queue = Some(queue);
@@ -85,11 +90,11 @@ impl winit::application::ApplicationHandler for App {
             let color = [t.sin() * 0.5 + 0.5, t.cos() * 0.5 + 0.5, 0.3, 1.0];
             pipeline.update_uniforms(queue, &SceneUniform { color });
 
-            let frame = match surface_state.surface.get_current_texture() {
+            let frame = match surface_state.acquire_frame(device) {
                 Ok(frame) => frame,
-                Err(_) => {
-                    surface_state.reconfigure(device);
-                    surface_state.surface.get_current_texture().unwrap()
+                Err(err) => {
+                    log::warn!("skipping frame: {err}");
+                    return;
                 }
             };
             let view = frame